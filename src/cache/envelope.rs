@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::graph::CodeGraph;
 
@@ -17,27 +18,158 @@ use crate::graph::CodeGraph;
 /// Bumped to 6 in Phase 18 when `DecoratorInfo.framework` field was added,
 /// SideEffectImport/DotImport/Embeds/HasDecorator edge kinds were added,
 /// Go language support was added, and GoAbsolute/GoBlank/GoDot import kinds were added.
-pub const CACHE_VERSION: u32 = 6;
+/// Bumped to 7 when Protobuf support added the Message/Service/Rpc symbol kinds.
+/// Bumped to 8 when SQL support added the Table/View symbol kinds and the
+/// `ReferencesTable` edge kind.
+/// Bumped to 9 when Markdown docs were linked with the `DocumentedBy` edge kind.
+/// Bumped to 10 when OpenAPI specs added the `Endpoint` symbol kind and the `HandledBy`
+/// edge kind.
+/// Bumped to 11 when named imports were bound to symbols with the `ImportsSymbol` edge kind.
+/// Bumped to 12 when Rust use trees were resolved per leaf path, so graphs cached with
+/// crate-root edges for grouped `use` declarations are rebuilt.
+/// Bumped to 13 when Rust `impl Trait for Type` blocks gained `Implements` edges.
+/// Bumped to 14 when files and symbols gained a `category` field.
+/// Bumped to 15 when Rust method calls started resolving through receiver types.
+/// Bumped to 16 when `ResolvedImport` edges gained `is_type_only`.
+/// Bumped to 17 when functions and methods gained a `signature`.
+/// Bumped to 18 when `FileMeta` gained `content_hash`.
+/// Bumped to 19 when symbols gained `col_end` and a byte range.
+/// Bumped to 20 when `CodeGraph` gained `kind_index`.
+/// Bumped to 21 when Rust `Module` nodes and `module_index` were added.
+/// Bumped to 22 when files and modules gained `is_test` and the `Tests` edge kind was added.
+/// Bumped to 23 when `Calls` and `ResolvedImport` edges gained collapse counts and call-site lines.
+/// Bumped to 24 when crate roots gained `DeclaresDependency` edges to their `Cargo.toml` dependencies.
+/// Bumped to 25 when Rust symbols gained their `#[cfg(...)]` predicate.
+/// Bumped to 26 when JSX render sites gained `Renders` edges.
+/// Bumped to 27 when TypeScript namespaces became symbols and merged declarations folded
+/// into one symbol.
+/// Bumped to 28 when CommonJS `module.exports`/`exports.x` assignments marked symbols exported.
+/// Bumped to 29 when export aliases gained `ExportsAs` edges and `alias_index`.
+/// Bumped to 30 when TS/JS symbols gained visibility (`SymbolVisibility::Protected`) and
+/// class fields became child symbols.
+/// Bumped to 31 when TS abstract classes were indexed, symbols gained `is_abstract` and
+/// `is_override`, and class methods gained `Overrides` edges.
+/// Bumped to 32 when Rust impl methods gained `Overrides` edges to their trait methods.
+/// Bumped to 33 when functions and methods gained `complexity`.
+/// Bumped to 34 when files gained `line_count`.
+/// Bumped to 35 when files gained `content_hash`.
+/// Bumped to 36 when imports resolved into `node_modules` became `ExternalPackage` edges.
+/// Bumped to 37 when file paths were stored project-relative with a root fingerprint.
+/// Bumped to 38 when `ResolvedImport` edges gained the import statement's `line` and `col`.
+/// Bumped to 39 when Rust impl methods gained `derived`.
+/// Bumped to 40 when string literals naming symbols gained `StringRef` edges.
+/// Bumped to 41 when content hashes switched to FNV-1a.
+/// Bumped to 42 when Rust uniform paths into child modules resolved to their files.
+/// Bumped to 43 when `ImportsSymbol` edges gained the binding statement's `line` and `col`.
+/// Bumped to 44 when grouped Rust `use` edges went back to the whole statement as specifier.
+pub const CACHE_VERSION: u32 = 44;
 
 /// Cache directory name (created in project root).
 pub const CACHE_DIR: &str = ".code-graph";
 /// Cache file name within CACHE_DIR.
 pub const CACHE_FILE: &str = "graph.bin";
 
-/// Metadata for a cached file: mtime (seconds since epoch) + file size.
+/// Set by `--paranoid`: verify the content hash of every file, not just its mtime and size.
+static PARANOID: AtomicBool = AtomicBool::new(false);
+
+/// Check file content hashes, not just mtime and size, for the rest of the process.
+pub fn set_paranoid(paranoid: bool) {
+    PARANOID.store(paranoid, Ordering::Relaxed);
+}
+
+/// `true` under `--paranoid`.
+pub fn paranoid() -> bool {
+    PARANOID.load(Ordering::Relaxed)
+}
+
+/// Hash of the file's bytes, or `None` when it cannot be read.
+fn content_hash(path: &Path) -> Option<u64> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(crate::parser::content_hash(&bytes))
+}
+
+fn mtime_secs(metadata: &std::fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Metadata for a cached file: mtime (seconds since epoch) + file size, plus the content
+/// hash recorded when the file was parsed.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FileMeta {
     pub mtime_secs: u64,
     pub size: u64,
+    #[serde(default)]
+    pub content_hash: Option<u64>,
+}
+
+impl FileMeta {
+    /// Current metadata of `path` with the given content hash, or `None` when it cannot
+    /// be stat'ed.
+    pub fn read(path: &Path, content_hash: Option<u64>) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            mtime_secs: mtime_secs(&metadata),
+            size: metadata.len(),
+            content_hash,
+        })
+    }
+
+    /// `true` when a hashed file has the cached size and content, whatever its mtime. Used
+    /// for relocated caches, whose mtimes come from another checkout.
+    pub fn matches_content(&self, path: &Path, metadata: &std::fs::Metadata) -> bool {
+        self.size == metadata.len()
+            && self.content_hash.is_some()
+            && self.content_hash == content_hash(path)
+    }
+
+    /// `true` when `path` still matches this cached metadata: same mtime and size and,
+    /// when `verify_content` is set (`--paranoid`), the same content. A file cached
+    /// without a hash then counts as changed.
+    pub fn is_current(
+        &self,
+        path: &Path,
+        metadata: &std::fs::Metadata,
+        verify_content: bool,
+    ) -> bool {
+        if self.mtime_secs != mtime_secs(metadata) || self.size != metadata.len() {
+            return false;
+        }
+        !verify_content || (self.content_hash.is_some() && self.content_hash == content_hash(path))
+    }
 }
 
 /// Envelope wrapping the serialized graph with version and staleness metadata.
+///
+/// On disk, file paths in `file_mtimes` and the graph are project-relative, so a cache
+/// copied to another checkout (a CI workspace, a container mount) still applies;
+/// [`load_cache`] rebases them onto the loading root.
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct CacheEnvelope {
     pub version: u32,
-    pub project_root: PathBuf,
+    /// [`root_fingerprint`] of the root the cache was saved under.
+    pub root_fingerprint: u64,
     pub file_mtimes: HashMap<PathBuf, FileMeta>,
     pub graph: CodeGraph,
+    /// Set by [`load_cache`] when the cache was saved under another root. Mtimes do not
+    /// survive a copy, so the staleness diff then compares content hashes instead.
+    #[serde(skip)]
+    pub relocated: bool,
+}
+
+/// `project_root` made absolute, so `.` and the full path to the same directory name the
+/// same root.
+pub(super) fn canonical_root(project_root: &Path) -> PathBuf {
+    std::fs::canonicalize(project_root).unwrap_or_else(|_| project_root.to_path_buf())
+}
+
+/// Hash of the absolute project root, telling a relocated cache from one saved in place.
+pub fn root_fingerprint(project_root: &Path) -> u64 {
+    crate::parser::content_hash(canonical_root(project_root).as_os_str().as_encoded_bytes())
 }
 
 /// Build the cache file path for a project: `<project_root>/.code-graph/graph.bin`
@@ -45,43 +177,49 @@ pub fn cache_path(project_root: &Path) -> PathBuf {
     project_root.join(CACHE_DIR).join(CACHE_FILE)
 }
 
-/// Collect current filesystem metadata (mtime + size) for all files in the graph.
+/// Collect current filesystem metadata (mtime + size) for all files in the graph, with
+/// the content hash recorded on each parsed file. Under `--paranoid`, files without one
+/// (docs, configs) are hashed here so they can be verified too.
 pub fn collect_file_mtimes(graph: &CodeGraph) -> HashMap<PathBuf, FileMeta> {
-    let mut mtimes = HashMap::new();
-    for path in graph.file_index.keys() {
-        if let Ok(metadata) = std::fs::metadata(path) {
-            let mtime_secs = metadata
-                .modified()
-                .ok()
-                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                .map(|d| d.as_secs())
-                .unwrap_or(0);
-            mtimes.insert(
-                path.clone(),
-                FileMeta {
-                    mtime_secs,
-                    size: metadata.len(),
-                },
-            );
-        }
-    }
-    mtimes
+    graph
+        .file_index
+        .iter()
+        .filter_map(|(path, &idx)| {
+            let parsed_hash = match &graph.graph[idx] {
+                crate::graph::node::GraphNode::File(fi) => fi.content_hash,
+                _ => None,
+            };
+            let hash = parsed_hash.or_else(|| paranoid().then(|| content_hash(path)).flatten());
+            Some((path.clone(), FileMeta::read(path, hash)?))
+        })
+        .collect()
 }
 
 /// Save the graph to disk atomically using bincode serialization.
 ///
-/// Writes to a temp file first, then renames to the final path.
+/// Writes to a temp file first, then renames to the final path, under the cache lock.
 /// Creates the `.code-graph/` directory if it doesn't exist.
 pub fn save_cache(project_root: &Path, graph: &CodeGraph) -> anyhow::Result<()> {
+    let project_root = &canonical_root(project_root);
     let cache_dir = project_root.join(CACHE_DIR);
     std::fs::create_dir_all(&cache_dir)?;
+    let _lock = super::lock::acquire(project_root)?;
 
-    let file_mtimes = collect_file_mtimes(graph);
+    let file_mtimes = collect_file_mtimes(graph)
+        .into_iter()
+        .map(|(path, meta)| match path.strip_prefix(project_root) {
+            Ok(rel) => (rel.to_path_buf(), meta),
+            Err(_) => (path, meta),
+        })
+        .collect();
+    let mut graph = graph.clone();
+    graph.rebase_paths(project_root, Path::new(""));
     let envelope = CacheEnvelope {
         version: CACHE_VERSION,
-        project_root: project_root.to_path_buf(),
+        root_fingerprint: root_fingerprint(project_root),
         file_mtimes,
-        graph: graph.clone(),
+        graph,
+        relocated: false,
     };
 
     // Atomic write: temp file in same directory, then rename
@@ -94,19 +232,30 @@ pub fn save_cache(project_root: &Path, graph: &CodeGraph) -> anyhow::Result<()>
     Ok(())
 }
 
-/// Load the cached graph from disk. Returns None if:
+/// Load the cached graph from disk, with its file paths rebased onto the absolute
+/// `project_root`.
+/// Returns None if:
 /// - Cache file doesn't exist
 /// - Cache version doesn't match CACHE_VERSION
 /// - Deserialization fails (corrupt cache)
 pub fn load_cache(project_root: &Path) -> Option<CacheEnvelope> {
+    let project_root = &canonical_root(project_root);
     let target = cache_path(project_root);
     let bytes = std::fs::read(&target).ok()?;
     let result =
         bincode::serde::decode_from_slice::<CacheEnvelope, _>(&bytes, bincode::config::standard());
-    match result {
-        Ok((envelope, _)) if envelope.version == CACHE_VERSION => Some(envelope),
-        _ => None, // version mismatch or corrupt — caller will do full rebuild
-    }
+    let mut envelope = match result {
+        Ok((envelope, _)) if envelope.version == CACHE_VERSION => envelope,
+        _ => return None, // version mismatch or corrupt — caller will do full rebuild
+    };
+    envelope.graph.rebase_paths(Path::new(""), project_root);
+    envelope.file_mtimes = envelope
+        .file_mtimes
+        .into_iter()
+        .map(|(path, meta)| (project_root.join(path), meta))
+        .collect();
+    envelope.relocated = envelope.root_fingerprint != root_fingerprint(project_root);
+    Some(envelope)
 }
 
 #[cfg(test)]
@@ -143,6 +292,70 @@ mod tests {
         assert!(loaded.file_mtimes.contains_key(&fake_file));
     }
 
+    #[test]
+    fn test_relocated_cache_rebases_paths() {
+        let saved = tempfile::tempdir().unwrap();
+        let moved = tempfile::tempdir().unwrap();
+        for root in [saved.path(), moved.path()] {
+            std::fs::create_dir(root.join("src")).unwrap();
+            std::fs::write(root.join("src/app.ts"), "export const a = 1;").unwrap();
+        }
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(saved.path().join("src/app.ts"), "typescript");
+        let hash = crate::parser::content_hash(b"export const a = 1;");
+        graph.set_file_source(app, 1, Some(hash));
+        save_cache(saved.path(), &graph).unwrap();
+
+        let in_place = load_cache(saved.path()).unwrap();
+        assert!(!in_place.relocated);
+        assert!(
+            in_place
+                .graph
+                .file_index
+                .contains_key(&saved.path().join("src/app.ts"))
+        );
+
+        std::fs::create_dir(moved.path().join(CACHE_DIR)).unwrap();
+        std::fs::copy(cache_path(saved.path()), cache_path(moved.path())).unwrap();
+        let loaded = load_cache(moved.path()).unwrap();
+        assert!(loaded.relocated);
+        let file = moved.path().join("src/app.ts");
+        let &idx = loaded.graph.file_index.get(&file).expect("rebased file");
+        assert!(matches!(
+            &loaded.graph.graph[idx],
+            crate::graph::node::GraphNode::File(fi) if fi.path == file
+        ));
+        let meta = &loaded.file_mtimes[&file];
+        assert!(meta.matches_content(&file, &std::fs::metadata(&file).unwrap()));
+    }
+
+    #[test]
+    fn test_content_hash_detects_same_size_edit() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let file = tmp_dir.path().join("a.ts");
+        std::fs::write(&file, "export const a = 1;").unwrap();
+        let hash = crate::parser::content_hash(b"export const a = 1;");
+        let meta = FileMeta::read(&file, Some(hash)).unwrap();
+        let stat = std::fs::metadata(&file).unwrap();
+        assert!(meta.is_current(&file, &stat, false));
+        assert!(meta.is_current(&file, &stat, true));
+
+        // Same size, mtime restored: only the hash tells the files apart, and it is only
+        // checked when asked for.
+        let mtime = stat.modified().unwrap();
+        std::fs::write(&file, "export const a = 2;").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+        let stat = std::fs::metadata(&file).unwrap();
+        assert_eq!(stat.len(), meta.size);
+        assert!(meta.is_current(&file, &stat, false));
+        assert!(!meta.is_current(&file, &stat, true));
+    }
+
     #[test]
     fn test_load_missing_cache_returns_none() {
         let tmp_dir = tempfile::tempdir().unwrap();
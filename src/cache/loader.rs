@@ -6,8 +6,9 @@ use rayon::prelude::*;
 use super::envelope::CacheEnvelope;
 use crate::graph::CodeGraph;
 
-/// Apply staleness diff: compare cached file mtimes against current filesystem,
-/// re-parse changed/new files, remove deleted files.
+/// Apply staleness diff: compare cached file mtimes (and, under `--paranoid`, content
+/// hashes, see [`FileMeta::is_current`](super::envelope::FileMeta::is_current)) against
+/// current filesystem, re-parse changed/new files, remove deleted files.
 ///
 /// Threshold: if >= 10% of files changed, discard and do full rebuild instead.
 pub fn apply_staleness_diff(
@@ -16,9 +17,18 @@ pub fn apply_staleness_diff(
 ) -> anyhow::Result<CodeGraph> {
     let mut graph = envelope.graph;
     let cached_mtimes = envelope.file_mtimes;
+    let relocated = envelope.relocated;
+    let paranoid = super::envelope::paranoid();
+    let is_current = |path: &PathBuf, metadata: &std::fs::Metadata| {
+        cached_mtimes.get(path).is_some_and(|cached| {
+            cached.is_current(path, metadata, paranoid)
+                || (relocated && cached.matches_content(path, metadata))
+        })
+    };
 
     // Walk current files
     let config = crate::config::CodeGraphConfig::load(project_root);
+    crate::parser::dynamic::install(project_root, &config.grammar);
     let current_files = crate::walker::walk_project(project_root, &config, false, None)?;
 
     // Phase 12: Also walk non-parsed files to prevent false "deleted" detection.
@@ -30,27 +40,31 @@ pub fn apply_staleness_diff(
     // Find changed and new files
     let mut files_to_reparse: Vec<PathBuf> = Vec::new();
     for file in &current_files {
-        if let Ok(metadata) = std::fs::metadata(file) {
-            let mtime_secs = metadata
-                .modified()
-                .ok()
-                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                .map(|d| d.as_secs())
-                .unwrap_or(0);
-            let size = metadata.len();
-
-            match cached_mtimes.get(file) {
-                Some(cached) if cached.mtime_secs == mtime_secs && cached.size == size => {
-                    // Unchanged -- skip
-                }
-                _ => {
-                    // Changed or new -- needs re-parse
-                    files_to_reparse.push(file.clone());
-                }
-            }
+        if let Ok(metadata) = std::fs::metadata(file)
+            && !is_current(file, &metadata)
+        {
+            // Changed or new -- needs re-parse
+            files_to_reparse.push(file.clone());
         }
     }
 
+    // Markdown docs and OpenAPI specs are not re-parsed, but changed or new ones must be
+    // re-linked.
+    let changed_docs: Vec<PathBuf> = non_parsed_files
+        .iter()
+        .filter(|p| {
+            crate::resolver::docs_resolver::is_markdown(p)
+                || crate::resolver::openapi_resolver::is_openapi_spec(p)
+        })
+        .filter(|p| {
+            let Ok(metadata) = std::fs::metadata(p) else {
+                return false;
+            };
+            !is_current(p, &metadata)
+        })
+        .cloned()
+        .collect();
+
     // Find deleted files (in cache but not on disk)
     let deleted_files: Vec<PathBuf> = cached_mtimes
         .keys()
@@ -93,15 +107,25 @@ pub fn apply_staleness_diff(
                     "rs" => "rust",
                     "py" => "python",
                     "go" => "go",
-                    _ => return None,
+                    "proto" => "proto",
+                    "sql" => "sql",
+                    "astro" => "astro",
+                    "zig" => "zig",
+                    "sh" | "bash" => "shell",
+                    "css" | "scss" => "css",
+                    "ipynb" => crate::parser::notebook::kernel_language(&source),
+                    _ => crate::parser::dynamic::language_for_path(path)?,
                 };
-            let result = crate::parser::parse_file_parallel(path, &source).ok()?;
+            let mut result = crate::parser::parse_file_parallel(path, &source).ok()?;
+            result.line_count = crate::parser::count_lines(&source);
+            result.content_hash = Some(crate::parser::content_hash(&source));
             Some((path.clone(), language_str, result))
         })
         .collect();
 
     for (path, language_str, result) in &reparsed {
         let file_idx = graph.add_file(path.clone(), language_str);
+        graph.set_file_source(file_idx, result.line_count, result.content_hash);
         for (symbol, children) in &result.symbols {
             let sym_idx = graph.add_symbol(file_idx, symbol.clone());
             for child in children {
@@ -172,6 +196,19 @@ pub fn apply_staleness_diff(
         }
     }
 
+    // Re-link docs and specs: a changed doc or spec drops its old links with its node, and
+    // re-parsed files come back as fresh symbol nodes without the DocumentedBy and
+    // HandledBy edges they had.
+    for doc in &changed_docs {
+        graph.remove_file_from_graph(doc);
+        let kind = crate::graph::node::classify_file_kind(doc);
+        graph.add_non_parsed_file(doc.clone(), kind);
+    }
+    if !files_to_reparse.is_empty() || !deleted_files.is_empty() || !changed_docs.is_empty() {
+        crate::resolver::docs_resolver::link_docs(&mut graph, project_root);
+        crate::resolver::openapi_resolver::link_openapi(&mut graph);
+    }
+
     // Phase 25: Enrich decorator frameworks and add HasDecorator self-edges after partial re-parse.
     // Only run when files were actually changed or deleted to avoid unnecessary full-graph scans.
     if !files_to_reparse.is_empty() || !deleted_files.is_empty() {
@@ -179,6 +216,29 @@ pub fn apply_staleness_diff(
         crate::query::decorators::add_has_decorator_edges(&mut graph);
     }
 
+    // Overrides are rebuilt from the whole class hierarchy: a changed base class affects
+    // subclasses in files that did not change.
+    if !files_to_reparse.is_empty() || !deleted_files.is_empty() {
+        crate::resolver::overrides::link_overrides(&mut graph);
+    }
+
+    // Test classification is re-derived unconditionally: a module may have moved under
+    // `#[cfg(test)]` without its own file changing.
+    crate::resolver::tests_resolver::link_tests(&mut graph, project_root);
+
+    // Cargo declarations are re-read unconditionally: `Cargo.toml` is not a source file,
+    // so editing it marks nothing stale.
+    crate::resolver::cargo_deps::link_cargo_dependencies(&mut graph, project_root);
+
+    // String references are re-linked unconditionally: a literal in an unchanged file may
+    // name a symbol added elsewhere, and the pass only reads files when enabled.
+    crate::resolver::string_refs::link_string_refs(&mut graph, &config.string_refs);
+
+    // Categories and symbol exclusions are re-applied unconditionally: the rules may have
+    // changed since the cache was written.
+    crate::query::categories::apply_categories(&mut graph, project_root, &config.category);
+    crate::query::exclusions::apply_symbol_exclusions(&mut graph, &config.symbols.exclude);
+
     Ok(graph)
 }
 
@@ -190,7 +250,11 @@ pub fn apply_staleness_diff(
 /// 3. Save the resulting graph to cache.
 ///
 /// The `verbose` flag is forwarded to `build_graph()` when a full rebuild is needed.
+///
+/// The cache lock is held throughout, so a concurrent command waits and then loads the
+/// cache this one saved instead of rebuilding it alongside.
 pub fn load_or_build(project_root: &Path, verbose: bool) -> anyhow::Result<CodeGraph> {
+    let _lock = super::lock::acquire(project_root)?;
     let graph = match super::load_cache(project_root) {
         Some(envelope) => {
             if verbose {
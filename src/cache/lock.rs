@@ -0,0 +1,332 @@
+//! Cross-process lock on `.code-graph/` so concurrent commands do not rebuild and write
+//! the cache over each other.
+//!
+//! The lock is a `.code-graph/lock` file created exclusively and holding the owner's PID
+//! followed by a token unique to this acquisition. A second command waits for it to go
+//! away, takes it over immediately when the owner process is gone, and takes it over with
+//! a warning once [`LOCK_WAIT`] passes, so a hung process never blocks CI for good.
+//! Takeover renames the stale file aside before creating a fresh one, so of several
+//! waiters only one removes it, and a guard only deletes the file while it still holds
+//! its own token. The lock is re-entrant within the process: nested acquisitions (a
+//! build that saves the cache, rayon workers of the same build) share the outer one.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Condvar, LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::envelope::{CACHE_DIR, canonical_root};
+
+/// Lock file name within CACHE_DIR.
+pub const LOCK_FILE: &str = "lock";
+
+/// How long to wait for a live owner before taking the lock over.
+pub const LOCK_WAIT: Duration = Duration::from_secs(60);
+
+/// Interval between attempts while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Set by `--no-lock`: never create or wait for the lock.
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Lock files held by this process.
+static HELD: LazyLock<(Mutex<HashMap<PathBuf, Held>>, Condvar)> =
+    LazyLock::new(|| (Mutex::new(HashMap::new()), Condvar::new()));
+
+/// Distinguishes tokens written by this process within the same clock tick.
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// A lock file held by this process.
+struct Held {
+    /// Number of live guards sharing the lock.
+    depth: usize,
+    /// Contents written to the lock file; `None` while a thread is still taking it.
+    token: Option<String>,
+}
+
+/// Skip cache locking for the rest of the process.
+pub fn set_disabled(disabled: bool) {
+    DISABLED.store(disabled, Ordering::Relaxed);
+}
+
+/// Build the lock file path for a project: `<project_root>/.code-graph/lock`
+pub fn lock_path(project_root: &Path) -> PathBuf {
+    project_root.join(CACHE_DIR).join(LOCK_FILE)
+}
+
+/// Held cache lock; released when the last guard of this process is dropped.
+#[must_use = "the lock is released when the guard is dropped"]
+pub struct CacheLock {
+    /// `None` when locking is disabled.
+    path: Option<PathBuf>,
+}
+
+impl Drop for CacheLock {
+    fn drop(&mut self) {
+        let Some(path) = self.path.take() else {
+            return;
+        };
+        let (held, _) = &*HELD;
+        let mut held = held.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = held.get_mut(&path).expect("held lock is tracked");
+        entry.depth -= 1;
+        if entry.depth > 0 {
+            return;
+        }
+        let token = held
+            .remove(&path)
+            .and_then(|entry| entry.token)
+            .expect("held lock has a token");
+        // The file may have been taken over after LOCK_WAIT; leave the new owner's alone.
+        if std::fs::read_to_string(&path).is_ok_and(|contents| contents == token) {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+/// Take the cache lock of `project_root`, waiting for another process that holds it.
+///
+/// Returns at once when this process already holds the lock or `--no-lock` is set.
+pub fn acquire(project_root: &Path) -> anyhow::Result<CacheLock> {
+    acquire_within(project_root, LOCK_WAIT)
+}
+
+fn acquire_within(project_root: &Path, wait: Duration) -> anyhow::Result<CacheLock> {
+    if DISABLED.load(Ordering::Relaxed) {
+        return Ok(CacheLock { path: None });
+    }
+    // Canonical, so callers spelling the root differently share one re-entrant lock.
+    let path = lock_path(&canonical_root(project_root));
+    let (held, taken) = &*HELD;
+    {
+        let mut held = held.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            match held.get_mut(&path) {
+                Some(entry) if entry.token.is_some() => {
+                    entry.depth += 1;
+                    return Ok(CacheLock { path: Some(path) });
+                }
+                // Another thread of this process is taking the file: share its lock.
+                Some(_) => held = taken.wait(held).unwrap_or_else(|e| e.into_inner()),
+                None => break,
+            }
+        }
+        held.insert(
+            path.clone(),
+            Held {
+                depth: 0,
+                token: None,
+            },
+        );
+    }
+
+    let result = std::fs::create_dir_all(project_root.join(CACHE_DIR))
+        .map_err(anyhow::Error::from)
+        .and_then(|()| take_lock_file(&path, wait));
+    let mut held = held.lock().unwrap_or_else(|e| e.into_inner());
+    let outcome = match result {
+        Ok(token) => {
+            let entry = held.get_mut(&path).expect("acquiring lock is tracked");
+            entry.depth = 1;
+            entry.token = Some(token);
+            Ok(CacheLock { path: Some(path) })
+        }
+        Err(e) => {
+            held.remove(&path);
+            Err(e)
+        }
+    };
+    taken.notify_all();
+    outcome
+}
+
+/// Contents of a new lock file: the owner's PID and a token unique to this acquisition.
+fn new_token() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    format!(
+        "{} {:x}-{:x}",
+        std::process::id(),
+        nanos,
+        NEXT_TOKEN.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// PID recorded in lock file contents.
+fn owner_pid(contents: &str) -> Option<u32> {
+    contents.split_whitespace().next()?.parse().ok()
+}
+
+/// Create the lock file, waiting up to `wait` for its owner and taking it over from a
+/// dead owner or after the wait. Returns the token written to the file.
+fn take_lock_file(path: &Path, wait: Duration) -> anyhow::Result<String> {
+    let token = new_token();
+    let mut start = Instant::now();
+    let mut announced = false;
+    loop {
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+        {
+            Ok(mut file) => {
+                file.write_all(token.as_bytes())?;
+                return Ok(token);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "failed to create cache lock {}: {}",
+                    path.display(),
+                    e
+                ));
+            }
+        }
+
+        // An empty lock is still being written by its owner: wait for it like a live one.
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            // Released between the attempt and the read.
+            continue;
+        };
+        let owner = owner_pid(&contents);
+        let owner_alive = owner.is_some_and(crate::daemon::pid::process_is_alive);
+        if !owner_alive && owner.is_some() {
+            set_aside(path, &contents, &token)?;
+            continue;
+        }
+        if start.elapsed() >= wait {
+            eprintln!(
+                "warning: cache lock {} still held by pid {} after {}s; taking it over",
+                path.display(),
+                owner.map_or_else(|| "?".to_string(), |p| p.to_string()),
+                wait.as_secs()
+            );
+            set_aside(path, &contents, &token)?;
+            // Another waiter may win the race; give its owner a full wait too.
+            start = Instant::now();
+            continue;
+        }
+        if !announced {
+            if let Some(pid) = owner {
+                eprintln!(
+                    "waiting for cache lock held by pid {} (use --no-lock to skip)",
+                    pid
+                );
+            }
+            announced = true;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Move the stale lock file whose contents were `seen` out of the way.
+///
+/// The rename is atomic, so when several waiters take over at once only one moves the
+/// file and the others find it gone. If the file moved is no longer the stale one (a
+/// faster waiter already replaced it), it is linked back unless yet another lock exists.
+fn set_aside(path: &Path, seen: &str, token: &str) -> anyhow::Result<()> {
+    let aside = path.with_file_name(format!("{}.stale.{}", LOCK_FILE, token.replace(' ', "-")));
+    match std::fs::rename(path, &aside) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => {
+            return Err(anyhow::anyhow!(
+                "failed to take over cache lock {}: {}",
+                path.display(),
+                e
+            ));
+        }
+    }
+    if std::fs::read_to_string(&aside).map_or(true, |contents| contents != seen) {
+        let _ = std::fs::hard_link(&aside, path);
+    }
+    let _ = std::fs::remove_file(&aside);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_reentrant_and_released() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = lock_path(tmp.path());
+        {
+            let _outer = acquire(tmp.path()).unwrap();
+            assert_eq!(
+                owner_pid(&std::fs::read_to_string(&path).unwrap()),
+                Some(std::process::id())
+            );
+            {
+                let _inner = acquire(tmp.path()).unwrap();
+            }
+            // Workers of the same process share the lock instead of waiting on it.
+            let root = tmp.path().to_path_buf();
+            std::thread::spawn(move || acquire_within(&root, Duration::ZERO).map(drop))
+                .join()
+                .unwrap()
+                .unwrap();
+            assert!(path.exists(), "inner guard must not release the lock");
+        }
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_lock_is_shared_across_spellings_of_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("sub")).unwrap();
+        let path = lock_path(tmp.path());
+        let _outer = acquire(tmp.path()).unwrap();
+        let token = std::fs::read_to_string(&path).unwrap();
+        {
+            let _inner = acquire_within(&tmp.path().join("sub/.."), Duration::ZERO).unwrap();
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), token);
+        }
+        assert!(path.exists(), "inner guard must not release the lock");
+    }
+
+    #[test]
+    fn test_lock_from_dead_process_is_stolen() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = lock_path(tmp.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // Far above any real pid_max.
+        std::fs::write(&path, "999999999").unwrap();
+        let _lock = acquire_within(tmp.path(), Duration::ZERO).unwrap();
+        assert_eq!(
+            owner_pid(&std::fs::read_to_string(&path).unwrap()),
+            Some(std::process::id())
+        );
+    }
+
+    #[test]
+    fn test_lock_held_by_live_process_is_taken_after_wait() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = lock_path(tmp.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // This test process is alive but does not hold the lock through this thread.
+        std::fs::write(&path, std::process::id().to_string()).unwrap();
+        let start = Instant::now();
+        let _lock = acquire_within(tmp.path(), Duration::from_millis(300)).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(
+            owner_pid(&std::fs::read_to_string(&path).unwrap()),
+            Some(std::process::id())
+        );
+    }
+
+    #[test]
+    fn test_lock_taken_over_is_not_removed_by_old_owner() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = lock_path(tmp.path());
+        let lock = acquire(tmp.path()).unwrap();
+        // Another process took the lock over after LOCK_WAIT.
+        std::fs::write(&path, "1 other").unwrap();
+        drop(lock);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 other");
+    }
+}
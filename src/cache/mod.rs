@@ -1,4 +1,5 @@
 pub mod envelope;
 pub mod loader;
+pub mod lock;
 pub use envelope::{load_cache, save_cache};
 pub use loader::load_or_build;
@@ -48,6 +48,23 @@ pub enum ProjectAction {
     },
 }
 
+/// Action for the `config` subcommand.
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Validate code-graph.toml and print the effective configuration with the source of
+    /// each value (default, file, or CODE_GRAPH_* environment variable).
+    ///
+    /// Exits with status 1 when the file fails to parse or has unknown keys.
+    Check {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Output the report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 /// Action for the `snapshot` subcommand.
 #[derive(Subcommand, Debug)]
 pub enum SnapshotAction {
@@ -87,6 +104,30 @@ pub enum SnapshotAction {
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Index the given path even if it does not look like a project root (no package.json,
+    /// Cargo.toml, go.mod, pyproject.toml, .git, or src/).
+    #[arg(long, global = true)]
+    pub force: bool,
+
+    /// Also compare content hashes when checking the cache for staleness, so edits that
+    /// keep both mtime and size are caught. Off by default: it reads every file.
+    #[arg(long, global = true)]
+    pub paranoid: bool,
+
+    /// Do not take the `.code-graph/` lock that keeps concurrent commands from rebuilding
+    /// and writing the cache at the same time.
+    #[arg(long, global = true)]
+    pub no_lock: bool,
+
+    /// Never page `--format table` output, even when stdout is a terminal.
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+
+    /// Shorten file paths in `--format table` output to at most this many characters,
+    /// eliding the middle (`packages/…/src/index.ts`).
+    #[arg(long, global = true, value_name = "N")]
+    pub max_path_width: Option<usize>,
 }
 
 /// Output format for query results.
@@ -101,6 +142,91 @@ pub enum OutputFormat {
     Json,
 }
 
+/// Message style for `impact --notify-format`.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum NotifyFormat {
+    /// Slack mrkdwn, one message per owner.
+    Slack,
+    /// GitHub markdown (e.g. a PR comment), one `@owner` section per owner.
+    Github,
+}
+
+/// Which way `callgraph` follows calls.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum CallDirection {
+    /// Callers: what calls the symbol.
+    In,
+    /// Callees: what the symbol calls (default).
+    #[default]
+    Out,
+    /// Both callers and callees.
+    Both,
+}
+
+/// What `hotspots` ranks.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum HotspotLevel {
+    /// Files, by the files importing them (default).
+    #[default]
+    File,
+    /// Symbols, by the files calling, binding, rendering, or extending them.
+    Symbol,
+}
+
+/// A dependency kind `impact --edges` follows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ImpactEdge {
+    /// Resolved imports (default).
+    Imports,
+    /// Calls into the changed symbols, with or without an import.
+    Calls,
+    /// `export * from` barrels and aliased re-exports.
+    Reexports,
+}
+
+/// Who a `report` is written for.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ReportAudience {
+    /// New team members and AI agents: an architecture primer (default).
+    #[default]
+    Onboarding,
+}
+
+/// How `find`, `refs`, and `impact` group their results (`--group-by`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    /// The file of each result.
+    File,
+    /// Symbol kind for `find`, reference kind for `refs`, confidence tier for `impact`.
+    Kind,
+    /// The package of each result's file (see `pkg-query`).
+    Package,
+    /// The language of each result's file.
+    Language,
+}
+
+/// How `find`, `refs`, and `impact` order their results (`--sort`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SortBy {
+    /// Symbol name for `find`, caller name for `refs`, path for `impact`.
+    Name,
+    /// File, then line.
+    Line,
+    /// Most referenced first: references to each definition for `find`, references per
+    /// file for `refs`, importers of each file for `impact`.
+    Refs,
+}
+
+/// What `circular` finds cycles between.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum CycleGranularity {
+    /// Files, through imports (default).
+    #[default]
+    File,
+    /// Symbols, through calls, `extends`, and `implements`.
+    Symbol,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Index a project directory, discovering and parsing all source files.
@@ -122,6 +248,15 @@ pub enum Commands {
         #[arg(long, value_delimiter = ',')]
         language: Vec<String>,
 
+        /// List the files that would be indexed without parsing or writing the cache.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// With --dry-run, also list skipped paths and the ignore rule, exclude pattern,
+        /// or extension filter that skipped each one.
+        #[arg(long, requires = "dry_run")]
+        explain_ignores: bool,
+
         /// Skip building the vector embedding index (disables RAG agent).
         ///
         /// By default, `code-graph index` builds per-symbol vector embeddings using
@@ -133,13 +268,34 @@ pub enum Commands {
         no_embeddings: bool,
     },
 
+    /// Index several projects concurrently and print a combined summary.
+    ///
+    /// Each project is fully re-indexed and its cache saved, as with `index`. All projects
+    /// share one pool of worker threads, so a large repository does not starve the others.
+    IndexAll {
+        /// Projects to index: registered aliases or paths, comma-separated or repeated.
+        /// Defaults to every project in the registry (`code-graph project list`).
+        #[arg(long, value_delimiter = ',')]
+        projects: Vec<String>,
+
+        /// Worker threads shared by all projects (default: one per CPU).
+        #[arg(long)]
+        jobs: Option<usize>,
+
+        /// Output results as JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Find a symbol's definition (file:line location).
     ///
     /// Re-indexes the project before executing the query. Supports regex patterns
     /// (e.g. "User.*Service"), case-insensitive matching, kind filters, and file scoping.
+    /// `find --kind trait` with no pattern lists every symbol of the given kinds.
     Find {
-        /// Symbol name or regex pattern (e.g. "UserService" or "User.*Service").
-        symbol: String,
+        /// Symbol name or regex pattern (e.g. "UserService" or "User.*Service"). `*` matches
+        /// every name; omitting the pattern is the same as `*` and requires `--kind`.
+        symbol: Option<String>,
 
         /// Path to the project root (auto-detected from cwd when omitted).
         path: Option<PathBuf>,
@@ -167,6 +323,24 @@ pub enum Commands {
         /// Filter results by language (rust/rs, typescript/ts, javascript/js).
         #[arg(long = "language", alias = "lang")]
         language: Option<String>,
+
+        /// Only show symbols in this category (`[[category]]` rules in code-graph.toml).
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Filter by visibility (comma-separated: pub, pub(crate), protected, private).
+        /// TS/JS top-level symbols are `pub` when exported; class members follow their
+        /// `private`/`protected`/`#name` modifier.
+        #[arg(long, value_delimiter = ',')]
+        visibility: Vec<String>,
+
+        /// Group results by file, kind, package, or language.
+        #[arg(long, value_enum)]
+        group_by: Option<GroupBy>,
+
+        /// Order results by name, line, or reference count instead of the default order.
+        #[arg(long, value_enum)]
+        sort: Option<SortBy>,
     },
 
     /// Find all references to a symbol across the codebase.
@@ -202,6 +376,24 @@ pub enum Commands {
         /// Filter results by language (rust/rs, typescript/ts, javascript/js).
         #[arg(long = "language", alias = "lang")]
         language: Option<String>,
+
+        /// Keep only these reference kinds (comma-separated: import, call, render, read,
+        /// write). `--ref-kind write` lists every site that assigns a variable.
+        #[arg(long, value_delimiter = ',')]
+        ref_kind: Vec<String>,
+
+        /// Leave out calls from methods a derive macro generated (`#[automatically_derived]`
+        /// impls in expanded or generated code).
+        #[arg(long)]
+        exclude_derived: bool,
+
+        /// Group results by file, kind, package, or language.
+        #[arg(long, value_enum)]
+        group_by: Option<GroupBy>,
+
+        /// Order results by name, line, or reference count instead of the default order.
+        #[arg(long, value_enum)]
+        sort: Option<SortBy>,
     },
 
     /// Show the transitive blast radius (dependents) of changing a symbol.
@@ -233,12 +425,60 @@ pub enum Commands {
         /// Filter results by language (rust/rs, typescript/ts, javascript/js).
         #[arg(long = "language", alias = "lang")]
         language: Option<String>,
+
+        /// Group affected files by CODEOWNERS owner and print one ready-to-post message
+        /// per owner.
+        #[arg(long, value_enum)]
+        notify_format: Option<NotifyFormat>,
+
+        /// Only show affected files in this category, or containing symbols in it.
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Ignore type-only imports (`import type`), which are erased at compile time.
+        #[arg(long)]
+        runtime_only: bool,
+
+        /// Roll affected files up to their directories.
+        #[arg(long, conflicts_with = "notify_format")]
+        by_dir: bool,
+
+        /// Only show affected test files: the tests to re-run after the change.
+        #[arg(long)]
+        tests: bool,
+
+        /// Score each affected file by symbol count, lines, git churn, and test coverage
+        /// into a rough refactor-effort number, with a total.
+        #[arg(long, conflicts_with_all = ["notify_format", "by_dir"])]
+        estimate: bool,
+
+        /// Stop at this many hops from the defining files (1 = direct dependents only).
+        #[arg(long)]
+        max_depth: Option<usize>,
+
+        /// Dependency kinds that carry impact, comma-separated: imports, calls, reexports
+        /// (default: imports).
+        #[arg(long, value_enum, value_delimiter = ',')]
+        edges: Vec<ImpactEdge>,
+
+        /// Leave test files out of the affected files.
+        #[arg(long, conflicts_with = "tests")]
+        exclude_tests: bool,
+
+        /// Group results by file, kind, package, or language.
+        #[arg(long, value_enum)]
+        group_by: Option<GroupBy>,
+
+        /// Order results by name, line, or reference count instead of the default order.
+        #[arg(long, value_enum)]
+        sort: Option<SortBy>,
     },
 
     /// Detect circular dependencies in the import graph (file-level).
     ///
-    /// Uses Kosaraju's SCC algorithm. Each reported cycle is a set of files
-    /// that mutually import each other directly or transitively.
+    /// Uses Tarjan's SCC algorithm. Each reported group is a set of files
+    /// that mutually import each other directly or transitively, with its size
+    /// and up to --max-cycles representative import cycles through it.
     Circular {
         /// Path to the project root (auto-detected from cwd when omitted).
         path: Option<PathBuf>,
@@ -254,6 +494,19 @@ pub enum Commands {
         /// Filter results by language (rust/rs, typescript/ts, javascript/js).
         #[arg(long = "language", alias = "lang")]
         language: Option<String>,
+
+        /// Maximum representative cycles shown per group of mutually dependent files (default: 3).
+        #[arg(long, default_value_t = crate::query::circular::DEFAULT_MAX_CYCLES)]
+        max_cycles: usize,
+
+        /// Ignore type-only imports (`import type`), which cannot form runtime cycles.
+        #[arg(long)]
+        runtime_only: bool,
+
+        /// Find cycles between files (imports) or between symbols (calls, `extends`,
+        /// `implements`): mutually recursive functions and types, within and across files.
+        #[arg(long, value_enum, default_value_t = CycleGranularity::File)]
+        granularity: CycleGranularity,
     },
 
     /// Project statistics overview: file count, symbol breakdown, import summary.
@@ -269,9 +522,19 @@ pub enum Commands {
         #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
         format: OutputFormat,
 
-        /// Filter output to show only a specific language's stats section (rust/rs, typescript/ts, javascript/js).
+        /// Compute stats over one language's files only, showing just its section
+        /// (rust/rs, typescript/ts, javascript/js, ...).
         #[arg(long = "language", alias = "lang")]
         language: Option<String>,
+
+        /// Compute stats over files under this directory only (relative to project root).
+        #[arg(long)]
+        scope: Option<PathBuf>,
+
+        /// List the top N files (default 10) by symbol count, lines, fan-in, and fan-out
+        /// instead of the overview.
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+        files: Option<usize>,
     },
 
     /// 360-degree view of a symbol: definition, references, callers, and callees.
@@ -299,6 +562,11 @@ pub enum Commands {
         /// Filter results by language (rust/rs, typescript/ts, javascript/js).
         #[arg(long = "language", alias = "lang")]
         language: Option<String>,
+
+        /// Include the source of each definition and call site, with N (default 3) lines
+        /// of context around it.
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "3")]
+        source: Option<usize>,
     },
 
     /// Start a file watcher that monitors for changes and re-indexes incrementally.
@@ -367,6 +635,10 @@ pub enum Commands {
         /// Exclude paths matching glob patterns (comma-separated).
         #[arg(long, value_delimiter = ',')]
         exclude: Vec<String>,
+
+        /// Export only files and symbols in these categories (comma-separated).
+        #[arg(long, alias = "tag", value_delimiter = ',')]
+        category: Vec<String>,
     },
 
     /// Show file/directory tree structure with symbol outlines.
@@ -386,6 +658,11 @@ pub enum Commands {
         #[arg(long, default_value_t = 3)]
         depth: usize,
 
+        /// Show the Rust module tree (`crate::module::submodule`) instead of directories.
+        /// `--path` then names a module to scope to (e.g. `my_crate::parser`).
+        #[arg(long)]
+        modules: bool,
+
         /// Output format.
         #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
         format: OutputFormat,
@@ -426,7 +703,59 @@ pub enum Commands {
         format: OutputFormat,
     },
 
-    /// Detect dead code: unreachable files and unreferenced symbols.
+    /// List the files a file imports transitively, with their import depth.
+    Deps {
+        /// Path to the file to inspect (relative to project root).
+        file: PathBuf,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Maximum number of import hops to follow (unlimited when omitted).
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Ignore type-only imports (`import type`), which are erased at compile time.
+        #[arg(long)]
+        runtime_only: bool,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// List the files that import a file transitively, with their import depth.
+    Rdeps {
+        /// Path to the file to inspect (relative to project root).
+        file: PathBuf,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Maximum number of import hops to follow (unlimited when omitted).
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Ignore type-only imports (`import type`), which are erased at compile time.
+        #[arg(long)]
+        runtime_only: bool,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Detect dead code: unreferenced private symbols, unreachable files and unused exports.
+    ///
+    /// Findings are grouped into severity tiers, safest to delete first.
     #[command(name = "dead-code")]
     DeadCode {
         /// Path to the project root (auto-detected from cwd when omitted).
@@ -440,6 +769,55 @@ pub enum Commands {
         #[arg(long)]
         scope: Option<PathBuf>,
 
+        /// Only report findings of one severity tier: private (unreferenced non-exported
+        /// symbols), file (unreachable files) or exported (exported but never called).
+        #[arg(long, value_enum)]
+        tier: Option<crate::query::dead_code::DeadTier>,
+
+        /// Print a deletion fix list instead: one `path:start-end` line range per line,
+        /// bottom-up within each file. Unreachable files are never listed whole, and only
+        /// functions and methods whose name appears nowhere else in the project are listed.
+        #[arg(long)]
+        fix_list: bool,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// List exported TS/JS symbols and `pub` Rust items that no other file imports,
+    /// calls or otherwise references.
+    ///
+    /// Entry-point files (index.ts, lib.rs, main.rs) and tests are skipped.
+    #[command(name = "unused-exports")]
+    UnusedExports {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Only report exports declared under this directory (relative to project root).
+        #[arg(long)]
+        scope: Option<PathBuf>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// List declared dependencies (package.json, Cargo.toml) that no file of the declaring
+    /// package imports, per workspace package.
+    #[command(name = "unused-deps")]
+    UnusedDeps {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
         /// Output format.
         #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
         format: OutputFormat,
@@ -470,6 +848,35 @@ pub enum Commands {
         format: OutputFormat,
     },
 
+    /// Find same-named symbols in different files with near-identical bodies: copy-pasted
+    /// utilities that should be consolidated.
+    ///
+    /// Bodies are compared by token-shingle (Jaccard) similarity, ignoring whitespace.
+    Similar {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Minimum body similarity, from 0 to 1 (default: 0.8).
+        #[arg(long, default_value_t = 0.8)]
+        threshold: f64,
+
+        /// Ignore symbols shorter than this many lines (default: 5).
+        #[arg(long, default_value_t = 5)]
+        min_lines: usize,
+
+        /// Scope analysis to a specific directory (relative to project root).
+        #[arg(long)]
+        scope: Option<PathBuf>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
     /// Compare two graph snapshots and show structural differences.
     Diff {
         /// Path to the project root (auto-detected from cwd when omitted).
@@ -479,11 +886,11 @@ pub enum Commands {
         #[arg(long)]
         project: Option<String>,
 
-        /// Name of the base snapshot.
+        /// Name of the base snapshot, or a git revision captured by `snapshot.auto = "per-commit"`.
         #[arg(long)]
         from: String,
 
-        /// Name of the target snapshot (defaults to current graph state).
+        /// Name of the target snapshot or git revision (defaults to current graph state).
         #[arg(long)]
         to: Option<String>,
 
@@ -492,6 +899,89 @@ pub enum Commands {
         format: OutputFormat,
     },
 
+    /// Compare the graphs of two git revisions, built in temporary worktrees.
+    #[command(name = "diff-rev")]
+    DiffRev {
+        /// Base git revision (commit, branch, or tag).
+        from: String,
+
+        /// Target git revision.
+        to: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+
+        /// Print indexing progress for each revision.
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// List the symbols a commit or range added, removed, or modified, with their
+    /// reference counts: a symbol-level view of a diff for reviewers.
+    ///
+    /// The diff hunks are mapped onto symbol ranges of both revisions, each indexed in a
+    /// temporary worktree.
+    Touched {
+        /// A commit (compared with its first parent) or a range `a..b` / `a...b`.
+        rev: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+
+        /// Print indexing progress for each revision.
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// Changelog of exported symbols between two git revisions, grouped by package, with
+    /// the semver release it calls for: major (breaking), minor (additive), or patch.
+    ///
+    /// Compact output is a markdown changelog section.
+    #[command(name = "api-diff")]
+    ApiDiff {
+        /// Base git revision (usually the previous release tag), or snapshot with --snapshots.
+        from: String,
+
+        /// Target git revision, or snapshot with --snapshots.
+        to: String,
+
+        /// Compare the public API recorded in two stored snapshots instead of indexing git
+        /// revisions.
+        #[arg(long)]
+        snapshots: bool,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+
+        /// Print indexing progress for each revision.
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
     /// Analyze impact of git-changed files on the dependency graph.
     #[command(name = "diff-impact")]
     DiffImpact {
@@ -510,6 +1000,32 @@ pub enum Commands {
         format: OutputFormat,
     },
 
+    /// Test files affected by changes since a base revision, for selective CI runs.
+    ///
+    /// Compact output is one test path per line (or `cargo test` arguments with --cargo),
+    /// ready to pass to jest, vitest, pytest, or cargo.
+    #[command(name = "select-tests")]
+    SelectTests {
+        /// Git ref the change is measured from (its merge base with HEAD).
+        #[arg(long)]
+        base: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Print `cargo test` arguments, one invocation per line, instead of test paths.
+        #[arg(long)]
+        cargo: bool,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
     /// Find symbols decorated with a specific decorator/attribute pattern.
     Decorators {
         /// Decorator/attribute name or regex pattern (e.g. "@Component", "derive(Debug)").
@@ -581,16 +1097,73 @@ pub enum Commands {
         format: OutputFormat,
     },
 
-    /// Manage the project registry (add, remove, list, show).
-    Project {
-        #[command(subcommand)]
-        action: ProjectAction,
-    },
+    /// Show the call tree of a symbol: what it calls, what calls it, or both, over
+    /// several hops.
+    Callgraph {
+        /// Symbol name.
+        symbol: String,
 
-    /// Install Claude Code hooks for transparent code-graph integration.
-    ///
-    /// Installs PreToolUse hooks into .claude/hooks/ and merges hook configuration
-    /// into .claude/settings.json. Cleans up stale MCP configuration automatically.
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Maximum number of call hops to follow (default: 3).
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+
+        /// Follow callees (out), callers (in), or both.
+        #[arg(long, value_enum, default_value_t = CallDirection::Out)]
+        direction: CallDirection,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Show why one file or symbol depends on another: the shortest chains of imports,
+    /// re-exports, and calls between them.
+    Path {
+        /// File path or symbol name that depends on `to`.
+        from: String,
+
+        /// File path or symbol name depended on.
+        to: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Maximum number of shortest paths to show (default: 3).
+        #[arg(long, default_value_t = 3)]
+        max_paths: usize,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Manage the project registry (add, remove, list, show).
+    Project {
+        #[command(subcommand)]
+        action: ProjectAction,
+    },
+
+    /// Inspect the project configuration (code-graph.toml and CODE_GRAPH_* overrides).
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Install Claude Code hooks for transparent code-graph integration.
+    ///
+    /// Installs PreToolUse hooks into .claude/hooks/ and merges hook configuration
+    /// into .claude/settings.json. Cleans up stale MCP configuration automatically.
     Setup {
         /// Install hooks globally (~/.claude/) instead of project-level (.claude/).
         #[arg(long)]
@@ -601,6 +1174,36 @@ pub enum Commands {
         uninstall: bool,
     },
 
+    /// Print the version; with --verbose, the cache format version, target, enabled
+    /// features and supported languages.
+    Version {
+        /// Print build information.
+        #[arg(long)]
+        verbose: bool,
+
+        /// Output format (json always includes the build information).
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Describe what this binary supports: languages, commands, output and export
+    /// formats, config keys, and enabled features. Use `--format json` from wrappers and
+    /// editor extensions.
+    Capabilities {
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Install the latest release from crates.io with `cargo install`, keeping the
+    /// features of this binary.
+    #[command(name = "self-update")]
+    SelfUpdate {
+        /// Only report whether a newer release exists.
+        #[arg(long)]
+        check: bool,
+    },
+
     /// Manage the background daemon (start, stop, status).
     Daemon {
         #[command(subcommand)]
@@ -614,6 +1217,391 @@ pub enum Commands {
         path: PathBuf,
     },
 
+    /// Show the workspace crates and their dependencies under a Cargo feature selection.
+    ///
+    /// Resolves `[features]` like Cargo does and reports which optional dependencies and
+    /// `#[cfg(feature = "...")]`-gated modules are enabled.
+    Packages {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Comma-separated features to enable (`feat`, or `crate/feat` for one crate).
+        #[arg(short = 'F', long, value_delimiter = ',')]
+        features: Vec<String>,
+
+        /// Enable every feature of every crate.
+        #[arg(long)]
+        all_features: bool,
+
+        /// Do not enable the `default` features.
+        #[arg(long)]
+        no_default_features: bool,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Filter symbols or files with a query expression.
+    ///
+    /// `symbols` or `files`, optionally followed by `where` and conditions on fields,
+    /// combined with and/or/not, e.g.
+    /// `code-graph query "symbols where kind=function and refs=0 and file~'src/api/**'"`.
+    Query {
+        /// Query expression.
+        query: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Interactive session: load the graph once and run find/refs/impact/path/query
+    /// against it, with Tab completion of symbol names.
+    Repl {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+    },
+
+    /// Query the package graph with Bazel-style operators.
+    ///
+    /// Supports deps(x [, depth]), rdeps(x [, depth]), somepath(a, b), allpaths(a, b),
+    /// and the set operators `+`, `-`, `^` (union, except, intersect), e.g.
+    /// `code-graph pkg-query 'deps(@app, 2) - @app'`.
+    PkgQuery {
+        /// Query expression.
+        query: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Find consolidation candidates across workspace packages: same-named utility files
+    /// exporting the same symbols, and external packages declared under several aliases or
+    /// version requirements.
+    Duplicates {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Show each package's afferent and efferent coupling (fan-in and fan-out) and its
+    /// instability, Ce / (Ca + Ce).
+    Metrics {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Find hidden coupling: files that often change in the same git commit but share no
+    /// import, call, or other edge in the graph.
+    Coupling {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Number of most recent commits to read (default: 500).
+        #[arg(long, default_value_t = 500)]
+        commits: usize,
+
+        /// Minimum number of shared commits for a pair (default: 3).
+        #[arg(long, default_value_t = 3)]
+        min_shared: usize,
+
+        /// Minimum coupling degree, the share of the two files' commits made together
+        /// (default: 0.5).
+        #[arg(long, default_value_t = 0.5)]
+        min_degree: f64,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// List the functions and methods with the highest cyclomatic complexity.
+    Complexity {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Number of functions to show (default: 20).
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Rank files or symbols by how many files depend on them, optionally weighted by git
+    /// churn, to find the riskiest code to touch.
+    Hotspots {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Rank files or symbols.
+        #[arg(long, value_enum, default_value_t = HotspotLevel::File)]
+        level: HotspotLevel,
+
+        /// Multiply dependents by the number of recent commits touching the file.
+        #[arg(long)]
+        churn: bool,
+
+        /// Number of most recent commits to read with --churn (default: 500).
+        #[arg(long, default_value_t = 500)]
+        commits: usize,
+
+        /// Maximum number of hotspots to show (default: 20).
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Audit barrel files (`export * from`): symbols flowing through each, importers, and
+    /// the longest re-export chains.
+    Barrels {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// List the roots of the dependency graph: Cargo bin and lib targets, package.json
+    /// main/bin entries, Next.js routes, Go main packages, and files nothing imports.
+    Entrypoints {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Find chokepoint files: files whose removal disconnects parts of the import graph,
+    /// and the files reached from the entry points only through them.
+    CutPoints {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Number of files to show.
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+
+        /// Only report files cutting off or dominating at least this many files.
+        #[arg(long, default_value_t = 2)]
+        min_size: usize,
+
+        /// Keep test files in the graph (each tested file then cuts off its tests).
+        #[arg(long)]
+        include_tests: bool,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Generate a markdown report composed from the other queries.
+    ///
+    /// `--audience onboarding` is an architecture primer: project size and languages, the
+    /// directory layout, packages and their dependencies, the most imported files, entry
+    /// points, and import cycles.
+    Report {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Who the report is for.
+        #[arg(long, value_enum, default_value_t = ReportAudience::Onboarding)]
+        audience: ReportAudience,
+
+        /// Entries kept in each ranked section (packages, files, entry points, cycles).
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        /// Output format: markdown, or JSON with `json`.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// List everything a package exports from its entry points (following barrels,
+    /// named re-exports, `pub use`, and `pub mod`), with the files outside the package
+    /// that use each item.
+    Exports {
+        /// Workspace package name, crate name, or directory relative to the project root.
+        target: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Print the consumable API of a workspace package or crate: every symbol reachable
+    /// from its entry points through barrels and `pub use` re-exports, with signatures.
+    Api {
+        /// Workspace package name, crate name, or directory relative to the project root.
+        target: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Lint import grouping and ordering (builtin, external, workspace, internal, relative)
+    /// and deep relative imports, per the `[lint_imports]` rules of code-graph.toml.
+    /// Exits 1 when any import breaks a rule.
+    LintImports {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Check architecture layering rules: the `[[rules]]` tables of code-graph.toml forbid
+    /// files matching `from` to import files or packages matching `forbid`.
+    /// Exits 1 when any import breaks a rule.
+    Check {
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Preflight a deletion: report whether a file or symbol is safe to delete, blocked by
+    /// listed references (imports, calls, re-exports, tests, docs, config), or unknown
+    /// because it may be reached dynamically.
+    CanDelete {
+        /// File path (relative to the project root) or exact symbol name.
+        target: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Hover bundle for the identifier at a source position, for editor plugins.
+    ///
+    /// Returns name, kind, signature, doc comment, definition location, reference count,
+    /// and top callers in one payload, e.g. `code-graph hover src/main.rs:42:13 --format json`.
+    Hover {
+        /// Position as `<file>:<line>:<col>` (1-based; file relative to the project root).
+        position: String,
+
+        /// Path to the project root (auto-detected from cwd when omitted).
+        path: Option<PathBuf>,
+
+        /// Use a registered project alias instead of a path.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Maximum number of callers to list.
+        #[arg(long, default_value_t = 5)]
+        callers: usize,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
     /// Plan a symbol rename: list all files and lines that reference the symbol.
     Rename {
         /// Current symbol name to rename.
@@ -622,6 +1610,11 @@ pub enum Commands {
         /// New name for the symbol.
         new_name: String,
 
+        /// Preview the rename grouped by file, labelling each site as a definition,
+        /// reference, import, or re-export. No files are changed either way.
+        #[arg(long)]
+        dry_run: bool,
+
         /// Path to the project root (auto-detected from cwd when omitted).
         path: Option<PathBuf>,
 
@@ -635,14 +1628,32 @@ pub enum Commands {
     },
 }
 
+/// Run `f` on a thread with an 8 MiB stack. Debug builds of the clap-derived `Commands`
+/// tree need more than the 2 MiB default test-thread stack to build.
+#[cfg(test)]
+pub(crate) fn with_cli_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .stack_size(8 << 20)
+            .spawn_scoped(scope, f)
+            .expect("spawn CLI parser thread")
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use clap::Parser;
 
+    fn parse(args: &[&str]) -> Cli {
+        with_cli_stack(|| Cli::parse_from(args))
+    }
+
     #[test]
     fn test_snapshot_create_parses() {
-        let cli = Cli::parse_from(["code-graph", "snapshot", "create", "my-snap"]);
+        let cli = parse(&["code-graph", "snapshot", "create", "my-snap"]);
         match cli.command {
             Commands::Snapshot { action } => match action {
                 SnapshotAction::Create { name, path } => {
@@ -657,7 +1668,7 @@ mod tests {
 
     #[test]
     fn test_snapshot_list_parses() {
-        let cli = Cli::parse_from(["code-graph", "snapshot", "list"]);
+        let cli = parse(&["code-graph", "snapshot", "list"]);
         match cli.command {
             Commands::Snapshot { action } => match action {
                 SnapshotAction::List { path } => {
@@ -671,7 +1682,7 @@ mod tests {
 
     #[test]
     fn test_snapshot_delete_parses() {
-        let cli = Cli::parse_from(["code-graph", "snapshot", "delete", "my-snap"]);
+        let cli = parse(&["code-graph", "snapshot", "delete", "my-snap"]);
         match cli.command {
             Commands::Snapshot { action } => match action {
                 SnapshotAction::Delete { name, path } => {
@@ -688,7 +1699,7 @@ mod tests {
     #[test]
     #[cfg(feature = "rag")]
     fn test_index_no_embeddings_flag() {
-        let cli = Cli::parse_from(["code-graph", "index", ".", "--no-embeddings"]);
+        let cli = parse(&["code-graph", "index", ".", "--no-embeddings"]);
         match cli.command {
             Commands::Index { no_embeddings, .. } => {
                 assert!(no_embeddings, "--no-embeddings flag should be true");
@@ -701,7 +1712,7 @@ mod tests {
     #[test]
     #[cfg(feature = "rag")]
     fn test_index_embeddings_enabled_by_default() {
-        let cli = Cli::parse_from(["code-graph", "index", "."]);
+        let cli = parse(&["code-graph", "index", "."]);
         match cli.command {
             Commands::Index { no_embeddings, .. } => {
                 assert!(!no_embeddings, "--no-embeddings should default to false");
@@ -714,7 +1725,7 @@ mod tests {
     #[test]
     #[cfg(feature = "web")]
     fn test_serve_parses_defaults() {
-        let cli = Cli::parse_from(["code-graph", "serve"]);
+        let cli = parse(&["code-graph", "serve"]);
         match cli.command {
             Commands::Serve { path, port, .. } => {
                 assert_eq!(path, PathBuf::from("."));
@@ -728,7 +1739,7 @@ mod tests {
     #[test]
     #[cfg(feature = "web")]
     fn test_serve_custom_port_and_path() {
-        let cli = Cli::parse_from(["code-graph", "serve", "/tmp", "--port", "8080"]);
+        let cli = parse(&["code-graph", "serve", "/tmp", "--port", "8080"]);
         match cli.command {
             Commands::Serve { path, port, .. } => {
                 assert_eq!(path, PathBuf::from("/tmp"));
@@ -742,7 +1753,7 @@ mod tests {
     #[test]
     #[cfg(all(feature = "web", feature = "rag"))]
     fn test_serve_ollama_flag() {
-        let cli = Cli::parse_from(["code-graph", "serve", "--ollama"]);
+        let cli = parse(&["code-graph", "serve", "--ollama"]);
         match cli.command {
             Commands::Serve { ollama, .. } => {
                 assert!(ollama, "--ollama flag should be true");
@@ -755,7 +1766,7 @@ mod tests {
     #[test]
     #[cfg(all(feature = "web", feature = "rag"))]
     fn test_serve_ollama_defaults_to_false() {
-        let cli = Cli::parse_from(["code-graph", "serve"]);
+        let cli = parse(&["code-graph", "serve"]);
         match cli.command {
             Commands::Serve { ollama, .. } => {
                 assert!(!ollama, "--ollama should default to false");
@@ -766,7 +1777,7 @@ mod tests {
 
     #[test]
     fn test_daemon_start_parses() {
-        let cli = Cli::parse_from(["code-graph", "daemon", "start"]);
+        let cli = parse(&["code-graph", "daemon", "start"]);
         match cli.command {
             Commands::Daemon { action } => match action {
                 DaemonAction::Start { path } => {
@@ -780,7 +1791,7 @@ mod tests {
 
     #[test]
     fn test_daemon_start_with_path_parses() {
-        let cli = Cli::parse_from(["code-graph", "daemon", "start", "/tmp/myproject"]);
+        let cli = parse(&["code-graph", "daemon", "start", "/tmp/myproject"]);
         match cli.command {
             Commands::Daemon { action } => match action {
                 DaemonAction::Start { path } => {
@@ -794,7 +1805,7 @@ mod tests {
 
     #[test]
     fn test_daemon_stop_parses() {
-        let cli = Cli::parse_from(["code-graph", "daemon", "stop"]);
+        let cli = parse(&["code-graph", "daemon", "stop"]);
         match cli.command {
             Commands::Daemon { action } => match action {
                 DaemonAction::Stop { path } => {
@@ -808,7 +1819,7 @@ mod tests {
 
     #[test]
     fn test_daemon_status_parses() {
-        let cli = Cli::parse_from(["code-graph", "daemon", "status"]);
+        let cli = parse(&["code-graph", "daemon", "status"]);
         match cli.command {
             Commands::Daemon { action } => match action {
                 DaemonAction::Status { path } => {
@@ -822,7 +1833,7 @@ mod tests {
 
     #[test]
     fn test_daemon_run_parses() {
-        let cli = Cli::parse_from(["code-graph", "daemon-run", "/tmp/myproject"]);
+        let cli = parse(&["code-graph", "daemon-run", "/tmp/myproject"]);
         match cli.command {
             Commands::DaemonRun { path } => {
                 assert_eq!(path, PathBuf::from("/tmp/myproject"));
@@ -835,7 +1846,7 @@ mod tests {
 
     #[test]
     fn test_project_add_parses() {
-        let cli = Cli::parse_from(["code-graph", "project", "add", "myproj", "/path/to/proj"]);
+        let cli = parse(&["code-graph", "project", "add", "myproj", "/path/to/proj"]);
         match cli.command {
             Commands::Project { action } => match action {
                 ProjectAction::Add { alias, path } => {
@@ -848,9 +1859,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_index_all_parses_project_list() {
+        let cli = parse(&[
+            "code-graph",
+            "index-all",
+            "--projects",
+            "api,web",
+            "--projects",
+            "../tools",
+            "--jobs",
+            "4",
+        ]);
+        match cli.command {
+            Commands::IndexAll { projects, jobs, .. } => {
+                assert_eq!(projects, vec!["api", "web", "../tools"]);
+                assert_eq!(jobs, Some(4));
+            }
+            _ => panic!("expected IndexAll command"),
+        }
+    }
+
     #[test]
     fn test_project_remove_parses() {
-        let cli = Cli::parse_from(["code-graph", "project", "remove", "myproj"]);
+        let cli = parse(&["code-graph", "project", "remove", "myproj"]);
         match cli.command {
             Commands::Project { action } => match action {
                 ProjectAction::Remove { alias } => {
@@ -864,7 +1896,7 @@ mod tests {
 
     #[test]
     fn test_project_list_parses() {
-        let cli = Cli::parse_from(["code-graph", "project", "list"]);
+        let cli = parse(&["code-graph", "project", "list"]);
         match cli.command {
             Commands::Project { action } => match action {
                 ProjectAction::List => {}
@@ -876,7 +1908,7 @@ mod tests {
 
     #[test]
     fn test_project_show_parses() {
-        let cli = Cli::parse_from(["code-graph", "project", "show", "myproj"]);
+        let cli = parse(&["code-graph", "project", "show", "myproj"]);
         match cli.command {
             Commands::Project { action } => match action {
                 ProjectAction::Show { alias } => {
@@ -892,7 +1924,7 @@ mod tests {
 
     #[test]
     fn test_find_with_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "find", "MySymbol", "--project", "myproj"]);
+        let cli = parse(&["code-graph", "find", "MySymbol", "--project", "myproj"]);
         match cli.command {
             Commands::Find {
                 symbol,
@@ -900,7 +1932,7 @@ mod tests {
                 path,
                 ..
             } => {
-                assert_eq!(symbol, "MySymbol");
+                assert_eq!(symbol.as_deref(), Some("MySymbol"));
                 assert_eq!(project, Some("myproj".to_string()));
                 assert!(path.is_none());
             }
@@ -908,9 +1940,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_find_kind_without_pattern() {
+        let cli = parse(&["code-graph", "find", "--kind", "trait"]);
+        match cli.command {
+            Commands::Find { symbol, kind, .. } => {
+                assert!(symbol.is_none());
+                assert_eq!(kind, vec!["trait".to_string()]);
+            }
+            _ => panic!("expected Find command"),
+        }
+    }
+
+    #[test]
+    fn test_find_group_by_and_sort() {
+        let cli = parse(&[
+            "code-graph",
+            "find",
+            "User",
+            "--group-by",
+            "package",
+            "--sort",
+            "refs",
+        ]);
+        match cli.command {
+            Commands::Find { group_by, sort, .. } => {
+                assert_eq!(group_by, Some(GroupBy::Package));
+                assert_eq!(sort, Some(SortBy::Refs));
+            }
+            _ => panic!("expected Find command"),
+        }
+    }
+
     #[test]
     fn test_find_without_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "find", "MySymbol"]);
+        let cli = parse(&["code-graph", "find", "MySymbol"]);
         match cli.command {
             Commands::Find { project, .. } => {
                 assert!(project.is_none());
@@ -921,7 +1985,7 @@ mod tests {
 
     #[test]
     fn test_refs_with_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "refs", "MySymbol", "--project", "myproj"]);
+        let cli = parse(&["code-graph", "refs", "MySymbol", "--project", "myproj"]);
         match cli.command {
             Commands::Refs { project, .. } => {
                 assert_eq!(project, Some("myproj".to_string()));
@@ -932,7 +1996,7 @@ mod tests {
 
     #[test]
     fn test_impact_with_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "impact", "MySymbol", "--project", "myproj"]);
+        let cli = parse(&["code-graph", "impact", "MySymbol", "--project", "myproj"]);
         match cli.command {
             Commands::Impact { project, .. } => {
                 assert_eq!(project, Some("myproj".to_string()));
@@ -943,7 +2007,7 @@ mod tests {
 
     #[test]
     fn test_stats_with_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "stats", "--project", "myproj"]);
+        let cli = parse(&["code-graph", "stats", "--project", "myproj"]);
         match cli.command {
             Commands::Stats { project, .. } => {
                 assert_eq!(project, Some("myproj".to_string()));
@@ -954,7 +2018,7 @@ mod tests {
 
     #[test]
     fn test_context_with_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "context", "MySymbol", "--project", "myproj"]);
+        let cli = parse(&["code-graph", "context", "MySymbol", "--project", "myproj"]);
         match cli.command {
             Commands::Context { project, .. } => {
                 assert_eq!(project, Some("myproj".to_string()));
@@ -963,9 +2027,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_report_defaults_to_onboarding() {
+        let cli = parse(&["code-graph", "report", "--top", "5"]);
+        match cli.command {
+            Commands::Report { audience, top, .. } => {
+                assert!(matches!(audience, ReportAudience::Onboarding));
+                assert_eq!(top, 5);
+            }
+            _ => panic!("expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_context_source_lines() {
+        let cli = parse(&["code-graph", "context", "MySymbol", "--source"]);
+        match cli.command {
+            Commands::Context { source, .. } => assert_eq!(source, Some(3)),
+            _ => panic!("expected Context command"),
+        }
+        let cli = parse(&["code-graph", "context", "MySymbol", "--source", "0"]);
+        match cli.command {
+            Commands::Context { source, .. } => assert_eq!(source, Some(0)),
+            _ => panic!("expected Context command"),
+        }
+    }
+
+    #[test]
+    fn test_callgraph_direction_and_depth() {
+        let cli = parse(&[
+            "code-graph",
+            "callgraph",
+            "run",
+            "--direction",
+            "both",
+            "--depth",
+            "5",
+        ]);
+        match cli.command {
+            Commands::Callgraph {
+                direction, depth, ..
+            } => {
+                assert!(matches!(direction, CallDirection::Both));
+                assert_eq!(depth, 5);
+            }
+            _ => panic!("expected Callgraph command"),
+        }
+    }
+
     #[test]
     fn test_circular_with_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "circular", "--project", "myproj"]);
+        let cli = parse(&["code-graph", "circular", "--project", "myproj"]);
         match cli.command {
             Commands::Circular { project, .. } => {
                 assert_eq!(project, Some("myproj".to_string()));
@@ -976,7 +2088,7 @@ mod tests {
 
     #[test]
     fn test_dead_code_with_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "dead-code", "--project", "myproj"]);
+        let cli = parse(&["code-graph", "dead-code", "--project", "myproj"]);
         match cli.command {
             Commands::DeadCode { project, .. } => {
                 assert_eq!(project, Some("myproj".to_string()));
@@ -985,9 +2097,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_check_parses() {
+        let cli = parse(&["code-graph", "config", "check", "--json"]);
+        match cli.command {
+            Commands::Config {
+                action: ConfigAction::Check { path, json },
+            } => {
+                assert!(path.is_none());
+                assert!(json);
+            }
+            _ => panic!("expected Config check command"),
+        }
+    }
+
     #[test]
     fn test_rename_with_project_flag() {
-        let cli = Cli::parse_from(["code-graph", "rename", "old", "new", "--project", "myproj"]);
+        let cli = parse(&["code-graph", "rename", "old", "new", "--project", "myproj"]);
         match cli.command {
             Commands::Rename { project, .. } => {
                 assert_eq!(project, Some("myproj".to_string()));
@@ -1,7 +1,27 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
+/// Name of the configuration file at the project root.
+const CONFIG_FILE: &str = "code-graph.toml";
+
+/// Every key `code-graph.toml` accepts, as `(section, keys)`. An empty key list marks a
+/// top-level key rather than a section.
+const KNOWN_KEYS: &[(&str, &[&str])] = &[
+    ("exclude", &[]),
+    ("impact", &["high_threshold", "medium_threshold"]),
+    ("snapshot", &["auto"]),
+    (
+        "grammar",
+        &["name", "library", "extensions", "query", "symbol"],
+    ),
+    ("category", &["name", "paths", "symbols"]),
+    ("lint_imports", &["order", "max_parent_depth"]),
+    ("rules", &["name", "from", "forbid"]),
+    ("symbols", &["exclude"]),
+    ("string_refs", &["enabled", "min_length", "calls"]),
+];
+
 /// Impact analysis configuration parsed from the `[impact]` section of `code-graph.toml`.
 #[derive(Debug, Deserialize, Clone)]
 pub struct ImpactConfig {
@@ -29,7 +49,250 @@ impl Default for ImpactConfig {
     }
 }
 
+/// When watch mode captures snapshots on its own (`[snapshot] auto` in `code-graph.toml`).
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SnapshotAuto {
+    /// Snapshots are only created by `code-graph snapshot create` (default).
+    #[default]
+    Off,
+    /// Capture a `commit-<sha>` snapshot whenever git HEAD moves.
+    PerCommit,
+}
+
+impl SnapshotAuto {
+    fn as_str(self) -> &'static str {
+        match self {
+            SnapshotAuto::Off => "off",
+            SnapshotAuto::PerCommit => "per-commit",
+        }
+    }
+}
+
+/// Group of an import for `lint-imports` ordering rules.
+#[derive(Debug, Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportGroup {
+    /// Standard library: `std`/`core`/`alloc`, Node builtins, Python and Go stdlib.
+    Builtin,
+    /// Third-party package or crate.
+    External,
+    /// Another package or crate of the same workspace.
+    Workspace,
+    /// Same package by absolute path: `crate::`, path aliases, absolute Python imports.
+    Internal,
+    /// Relative path: `./x`, `../x`, `super::`, `self::`, `from . import x`.
+    Relative,
+}
+
+impl ImportGroup {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImportGroup::Builtin => "builtin",
+            ImportGroup::External => "external",
+            ImportGroup::Workspace => "workspace",
+            ImportGroup::Internal => "internal",
+            ImportGroup::Relative => "relative",
+        }
+    }
+}
+
+/// Import lint rules parsed from the `[lint_imports]` section of `code-graph.toml`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LintImportsConfig {
+    /// Required order of import groups within a file (default: builtin, external,
+    /// workspace, internal, relative). Groups left out are not checked.
+    #[serde(default = "default_import_order")]
+    pub order: Vec<ImportGroup>,
+    /// Most `../` (or `super::`) steps a relative import may take (default: 2).
+    #[serde(default = "default_max_parent_depth")]
+    pub max_parent_depth: usize,
+}
+
+fn default_import_order() -> Vec<ImportGroup> {
+    vec![
+        ImportGroup::Builtin,
+        ImportGroup::External,
+        ImportGroup::Workspace,
+        ImportGroup::Internal,
+        ImportGroup::Relative,
+    ]
+}
+fn default_max_parent_depth() -> usize {
+    2
+}
+
+impl Default for LintImportsConfig {
+    fn default() -> Self {
+        Self {
+            order: default_import_order(),
+            max_parent_depth: default_max_parent_depth(),
+        }
+    }
+}
+
+/// Where an effective configuration value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Built-in default.
+    Default,
+    /// `code-graph.toml`.
+    File,
+    /// A `CODE_GRAPH_*` environment variable (its name).
+    Env(String),
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File => write!(f, "{CONFIG_FILE}"),
+            ConfigSource::Env(var) => write!(f, "env {var}"),
+        }
+    }
+}
+
+/// One effective configuration value, for `config check`.
+#[derive(Debug, Clone)]
+pub struct ConfigValue {
+    /// Dotted key, e.g. `impact.high_threshold`.
+    pub key: &'static str,
+    /// The value as it would be written in TOML.
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+/// The resolved configuration of a project and how it was resolved.
+#[derive(Debug)]
+pub struct ConfigReport {
+    /// Path of `code-graph.toml` (which may not exist).
+    pub path: PathBuf,
+    /// Whether `code-graph.toml` exists.
+    pub file_found: bool,
+    /// Read or parse error of `code-graph.toml`; the file is then ignored.
+    pub error: Option<String>,
+    /// Unknown keys and ignored environment variables.
+    pub warnings: Vec<String>,
+    /// Effective configuration after defaults, the file, and environment overrides.
+    pub config: CodeGraphConfig,
+    /// Every effective value with its source, in [`KNOWN_KEYS`] order.
+    pub values: Vec<ConfigValue>,
+}
+
+impl ConfigReport {
+    /// `true` when the file parsed cleanly and every key and override was recognized.
+    pub fn is_clean(&self) -> bool {
+        self.error.is_none() && self.warnings.is_empty()
+    }
+}
+
+/// Snapshot configuration parsed from the `[snapshot]` section of `code-graph.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SnapshotConfig {
+    /// Automatic capture mode for `watch` and the daemon (default: off).
+    #[serde(default)]
+    pub auto: SnapshotAuto,
+}
+
+/// An external tree-sitter grammar declared with a `[[grammar]]` table in
+/// `code-graph.toml`. Grammars are only loaded by builds with the `dynamic-grammars`
+/// feature.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(feature = "dynamic-grammars"), allow(dead_code))]
+pub struct GrammarConfig {
+    /// Language name, used as the graph language of the files it parses (`elixir`).
+    pub name: String,
+    /// Shared library built from the grammar (`.so`/`.dylib`/`.dll`), relative to the
+    /// project root.
+    pub library: PathBuf,
+    /// File extensions parsed with this grammar, without the dot.
+    pub extensions: Vec<String>,
+    /// Extraction query (`.scm`), relative to the project root: `@definition.<kind>`
+    /// captures with a `@name`, and `@import` captures holding a module path.
+    pub query: PathBuf,
+    /// Exported language function (default: `tree_sitter_<name>`).
+    pub symbol: Option<String>,
+}
+
+/// A classification rule declared with a `[[category]]` table in `code-graph.toml`.
+///
+/// A file belongs to the first path-only rule whose `paths` match it. A symbol belongs to
+/// the first rule whose `paths` (if any) match its file and whose `symbols` (if any) match
+/// its name.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CategoryConfig {
+    /// Category name used by `--category` filters (`components`, `pages`).
+    pub name: String,
+    /// Glob patterns on project-relative file paths (`src/components/**`).
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Glob patterns on symbol names (`use*`, `*Page`).
+    #[serde(default)]
+    pub symbols: Vec<String>,
+}
+
+/// Symbol noise filters parsed from the `[symbols]` section of `code-graph.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SymbolsConfig {
+    /// Globs on symbol names (`__*`) left out of the symbol index, so name lookups in
+    /// `find`, `refs`, `context` and search skip them. A `kind:` prefix restricts a pattern
+    /// to one symbol kind (`type:*Props`, `function:make*`).
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// Heuristic string references parsed from the `[string_refs]` section of
+/// `code-graph.toml`: string literals naming a symbol, as in reflection or
+/// dependency-injection lookups (`container.get("UserService")`).
+#[derive(Debug, Deserialize, Clone)]
+pub struct StringRefsConfig {
+    /// Scan string literals for symbol names at index time (default: false).
+    #[serde(default)]
+    pub enabled: bool,
+    /// Shortest symbol name a literal must spell to count (default: 4).
+    #[serde(default = "default_string_ref_min_length")]
+    pub min_length: usize,
+    /// Globs on the call a literal must be an argument of (`container.get`, `*.resolve`,
+    /// `getattr`). Empty accepts every literal.
+    #[serde(default)]
+    pub calls: Vec<String>,
+}
+
+fn default_string_ref_min_length() -> usize {
+    4
+}
+
+impl Default for StringRefsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_length: default_string_ref_min_length(),
+            calls: Vec::new(),
+        }
+    }
+}
+
+/// An architecture layering rule declared with a `[[rules]]` table in `code-graph.toml`,
+/// checked by `code-graph check`: files matching `from` must not import anything matching
+/// `forbid`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RuleConfig {
+    /// Rule name shown with each violation (`domain-is-pure`).
+    pub name: String,
+    /// Globs on project-relative paths of the importing files (`src/domain/**`). A pattern
+    /// without glob characters also matches everything under it as a directory.
+    pub from: Vec<String>,
+    /// Globs on project-relative paths of forbidden import targets, or names of forbidden
+    /// external packages (`src/infrastructure`, `axios`).
+    pub forbid: Vec<String>,
+}
+
 /// Configuration loaded from `code-graph.toml` at the project root.
+///
+/// Every key can be overridden with a `CODE_GRAPH_<SECTION>_<KEY>` environment variable
+/// (`CODE_GRAPH_IMPACT_HIGH_THRESHOLD`, `CODE_GRAPH_SNAPSHOT_AUTO`), and the list keys
+/// `exclude`, `symbols.exclude`, and `string_refs.calls` with a comma-separated one
+/// (`CODE_GRAPH_EXCLUDE`), so CI can adjust a run without editing the file.
 #[derive(Debug, Deserialize, Default)]
 pub struct CodeGraphConfig {
     /// Additional path patterns to exclude from indexing (beyond .gitignore and node_modules).
@@ -38,33 +301,368 @@ pub struct CodeGraphConfig {
     /// Impact analysis configuration (thresholds for risk tiers).
     #[serde(default)]
     pub impact: ImpactConfig,
+
+    /// Snapshot configuration (automatic per-commit capture).
+    #[serde(default)]
+    pub snapshot: SnapshotConfig,
+
+    /// External tree-sitter grammars (`[[grammar]]` tables).
+    #[serde(default)]
+    pub grammar: Vec<GrammarConfig>,
+
+    /// Symbol and file classification rules (`[[category]]` tables).
+    #[serde(default)]
+    pub category: Vec<CategoryConfig>,
+
+    /// Import grouping and depth rules for `lint-imports`.
+    #[serde(default)]
+    pub lint_imports: LintImportsConfig,
+
+    /// Architecture layering rules for `check` (`[[rules]]` tables).
+    #[serde(default)]
+    pub rules: Vec<RuleConfig>,
+
+    /// Symbols left out of the symbol index.
+    #[serde(default)]
+    pub symbols: SymbolsConfig,
+
+    /// Opt-in string-literal references (`[string_refs]`).
+    #[serde(default)]
+    pub string_refs: StringRefsConfig,
 }
 
 impl CodeGraphConfig {
-    /// Load configuration from `code-graph.toml` in the given root directory.
+    /// Load configuration from `code-graph.toml` in the given root directory, with
+    /// `CODE_GRAPH_*` environment overrides applied.
     ///
-    /// Returns a default (empty) configuration if the file does not exist or cannot be parsed.
+    /// Falls back to defaults if the file does not exist or cannot be parsed. Problems are
+    /// reported on stderr as warnings.
     pub fn load(root: &Path) -> Self {
-        let config_path = root.join("code-graph.toml");
+        let report = Self::check(root);
+        if let Some(err) = &report.error {
+            eprintln!("warning: {err}. Using defaults.");
+        }
+        for warning in &report.warnings {
+            eprintln!("warning: {warning}");
+        }
+        report.config
+    }
+
+    /// Resolve the configuration of `root` from the process environment, recording where
+    /// each value came from.
+    pub fn check(root: &Path) -> ConfigReport {
+        Self::check_with_env(root, |var| std::env::var(var).ok())
+    }
+
+    fn check_with_env(root: &Path, env: impl Fn(&str) -> Option<String>) -> ConfigReport {
+        let path = root.join(CONFIG_FILE);
+        let file_found = path.exists();
+        let mut error = None;
+        let mut warnings = Vec::new();
+        let mut config = Self::default();
+        let mut table = toml::Table::new();
+
+        if file_found {
+            match std::fs::read_to_string(&path) {
+                Ok(contents) => {
+                    match toml::from_str::<Self>(&contents) {
+                        Ok(parsed) => {
+                            config = parsed;
+                            table = toml::from_str(&contents).unwrap_or_default();
+                            warnings.extend(unknown_keys(&table).into_iter().map(|key| {
+                                format!("unknown key '{key}' in {CONFIG_FILE} (ignored)")
+                            }));
+                        }
+                        Err(err) => error = Some(format!("failed to parse {CONFIG_FILE}: {err}")),
+                    }
+                }
+                Err(err) => error = Some(format!("failed to read {CONFIG_FILE}: {err}")),
+            }
+        }
+
+        let in_file = |section: &str, key: Option<&str>| -> bool {
+            match (table.get(section), key) {
+                (Some(_), None) => true,
+                (Some(toml::Value::Table(t)), Some(key)) => t.contains_key(key),
+                _ => false,
+            }
+        };
+        let file_or_default = |present: bool| {
+            if present {
+                ConfigSource::File
+            } else {
+                ConfigSource::Default
+            }
+        };
+
+        let mut exclude_source = file_or_default(in_file("exclude", None));
+        if let Some(raw) = env("CODE_GRAPH_EXCLUDE") {
+            config.exclude = Some(
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            );
+            exclude_source = ConfigSource::Env("CODE_GRAPH_EXCLUDE".into());
+        }
+
+        let mut symbols_source = file_or_default(in_file("symbols", Some("exclude")));
+        if let Some(raw) = env("CODE_GRAPH_SYMBOLS_EXCLUDE") {
+            config.symbols.exclude = raw
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect();
+            symbols_source = ConfigSource::Env("CODE_GRAPH_SYMBOLS_EXCLUDE".into());
+        }
+
+        let mut threshold = |key: &'static str, var: &str, slot: &mut usize| -> ConfigSource {
+            let mut source = file_or_default(in_file("impact", Some(key)));
+            if let Some(raw) = env(var) {
+                match raw.trim().parse::<usize>() {
+                    Ok(value) => {
+                        *slot = value;
+                        source = ConfigSource::Env(var.to_string());
+                    }
+                    Err(_) => warnings.push(format!(
+                        "ignoring {var}={raw}: expected a non-negative integer"
+                    )),
+                }
+            }
+            source
+        };
+        let high_source = threshold(
+            "high_threshold",
+            "CODE_GRAPH_IMPACT_HIGH_THRESHOLD",
+            &mut config.impact.high_threshold,
+        );
+        let medium_source = threshold(
+            "medium_threshold",
+            "CODE_GRAPH_IMPACT_MEDIUM_THRESHOLD",
+            &mut config.impact.medium_threshold,
+        );
+
+        let mut auto_source = file_or_default(in_file("snapshot", Some("auto")));
+        if let Some(raw) = env("CODE_GRAPH_SNAPSHOT_AUTO") {
+            match toml::Value::String(raw.trim().to_string()).try_into::<SnapshotAuto>() {
+                Ok(auto) => {
+                    config.snapshot.auto = auto;
+                    auto_source = ConfigSource::Env("CODE_GRAPH_SNAPSHOT_AUTO".into());
+                }
+                Err(_) => warnings.push(format!(
+                    "ignoring CODE_GRAPH_SNAPSHOT_AUTO={raw}: expected off or per-commit"
+                )),
+            }
+        }
+
+        let mut order_source = file_or_default(in_file("lint_imports", Some("order")));
+        if let Some(raw) = env("CODE_GRAPH_LINT_IMPORTS_ORDER") {
+            let groups: Result<Vec<ImportGroup>, _> = raw
+                .split(',')
+                .map(str::trim)
+                .filter(|g| !g.is_empty())
+                .map(|g| toml::Value::String(g.to_string()).try_into::<ImportGroup>())
+                .collect();
+            match groups {
+                Ok(groups) => {
+                    config.lint_imports.order = groups;
+                    order_source = ConfigSource::Env("CODE_GRAPH_LINT_IMPORTS_ORDER".into());
+                }
+                Err(_) => warnings.push(format!(
+                    "ignoring CODE_GRAPH_LINT_IMPORTS_ORDER={raw}: expected a comma-separated \
+                     list of builtin, external, workspace, internal, relative"
+                )),
+            }
+        }
+
+        let mut depth_source = file_or_default(in_file("lint_imports", Some("max_parent_depth")));
+        if let Some(raw) = env("CODE_GRAPH_LINT_IMPORTS_MAX_PARENT_DEPTH") {
+            match raw.trim().parse::<usize>() {
+                Ok(value) => {
+                    config.lint_imports.max_parent_depth = value;
+                    depth_source =
+                        ConfigSource::Env("CODE_GRAPH_LINT_IMPORTS_MAX_PARENT_DEPTH".into());
+                }
+                Err(_) => warnings.push(format!(
+                    "ignoring CODE_GRAPH_LINT_IMPORTS_MAX_PARENT_DEPTH={raw}: expected a \
+                     non-negative integer"
+                )),
+            }
+        }
 
-        if !config_path.exists() {
-            return Self::default();
+        let mut enabled_source = file_or_default(in_file("string_refs", Some("enabled")));
+        if let Some(raw) = env("CODE_GRAPH_STRING_REFS_ENABLED") {
+            match raw.trim().parse::<bool>() {
+                Ok(value) => {
+                    config.string_refs.enabled = value;
+                    enabled_source = ConfigSource::Env("CODE_GRAPH_STRING_REFS_ENABLED".into());
+                }
+                Err(_) => warnings.push(format!(
+                    "ignoring CODE_GRAPH_STRING_REFS_ENABLED={raw}: expected true or false"
+                )),
+            }
         }
 
-        match std::fs::read_to_string(&config_path) {
-            Ok(contents) => match toml::from_str::<Self>(&contents) {
-                Ok(config) => config,
-                Err(err) => {
-                    eprintln!("warning: failed to parse code-graph.toml: {err}. Using defaults.");
-                    Self::default()
+        let mut min_length_source = file_or_default(in_file("string_refs", Some("min_length")));
+        if let Some(raw) = env("CODE_GRAPH_STRING_REFS_MIN_LENGTH") {
+            match raw.trim().parse::<usize>() {
+                Ok(value) => {
+                    config.string_refs.min_length = value;
+                    min_length_source =
+                        ConfigSource::Env("CODE_GRAPH_STRING_REFS_MIN_LENGTH".into());
                 }
+                Err(_) => warnings.push(format!(
+                    "ignoring CODE_GRAPH_STRING_REFS_MIN_LENGTH={raw}: expected a non-negative \
+                     integer"
+                )),
+            }
+        }
+
+        let mut calls_source = file_or_default(in_file("string_refs", Some("calls")));
+        if let Some(raw) = env("CODE_GRAPH_STRING_REFS_CALLS") {
+            config.string_refs.calls = raw
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect();
+            calls_source = ConfigSource::Env("CODE_GRAPH_STRING_REFS_CALLS".into());
+        }
+
+        let exclude_value = match &config.exclude {
+            Some(patterns) => toml::Value::from(patterns.clone()).to_string(),
+            None => "[]".to_string(),
+        };
+        let grammar_names: Vec<String> = config.grammar.iter().map(|g| g.name.clone()).collect();
+        let category_names: Vec<String> = config.category.iter().map(|c| c.name.clone()).collect();
+        let rule_names: Vec<String> = config.rules.iter().map(|r| r.name.clone()).collect();
+        let values = vec![
+            ConfigValue {
+                key: "exclude",
+                value: exclude_value,
+                source: exclude_source,
+            },
+            ConfigValue {
+                key: "impact.high_threshold",
+                value: config.impact.high_threshold.to_string(),
+                source: high_source,
+            },
+            ConfigValue {
+                key: "impact.medium_threshold",
+                value: config.impact.medium_threshold.to_string(),
+                source: medium_source,
+            },
+            ConfigValue {
+                key: "snapshot.auto",
+                value: format!("\"{}\"", config.snapshot.auto.as_str()),
+                source: auto_source,
             },
-            Err(err) => {
-                eprintln!("warning: failed to read code-graph.toml: {err}. Using defaults.");
-                Self::default()
+            ConfigValue {
+                key: "grammar",
+                value: toml::Value::from(grammar_names).to_string(),
+                source: file_or_default(in_file("grammar", None)),
+            },
+            ConfigValue {
+                key: "category",
+                value: toml::Value::from(category_names).to_string(),
+                source: file_or_default(in_file("category", None)),
+            },
+            ConfigValue {
+                key: "lint_imports.order",
+                value: toml::Value::from(
+                    config
+                        .lint_imports
+                        .order
+                        .iter()
+                        .map(|g| g.as_str())
+                        .collect::<Vec<_>>(),
+                )
+                .to_string(),
+                source: order_source,
+            },
+            ConfigValue {
+                key: "lint_imports.max_parent_depth",
+                value: config.lint_imports.max_parent_depth.to_string(),
+                source: depth_source,
+            },
+            ConfigValue {
+                key: "rules",
+                value: toml::Value::from(rule_names).to_string(),
+                source: file_or_default(in_file("rules", None)),
+            },
+            ConfigValue {
+                key: "symbols.exclude",
+                value: toml::Value::from(config.symbols.exclude.clone()).to_string(),
+                source: symbols_source,
+            },
+            ConfigValue {
+                key: "string_refs.enabled",
+                value: config.string_refs.enabled.to_string(),
+                source: enabled_source,
+            },
+            ConfigValue {
+                key: "string_refs.min_length",
+                value: config.string_refs.min_length.to_string(),
+                source: min_length_source,
+            },
+            ConfigValue {
+                key: "string_refs.calls",
+                value: toml::Value::from(config.string_refs.calls.clone()).to_string(),
+                source: calls_source,
+            },
+        ];
+
+        ConfigReport {
+            path,
+            file_found,
+            error,
+            warnings,
+            config,
+            values,
+        }
+    }
+}
+
+/// Dotted names of every key `code-graph.toml` accepts, in [`KNOWN_KEYS`] order.
+pub fn known_keys() -> Vec<String> {
+    KNOWN_KEYS
+        .iter()
+        .flat_map(|(section, keys)| {
+            if keys.is_empty() {
+                vec![section.to_string()]
+            } else {
+                keys.iter().map(|key| format!("{section}.{key}")).collect()
             }
+        })
+        .collect()
+}
+
+/// Dotted names of the keys in `table` that are not in [`KNOWN_KEYS`].
+fn unknown_keys(table: &toml::Table) -> Vec<String> {
+    let mut unknown = Vec::new();
+    for (name, value) in table {
+        let Some((_, keys)) = KNOWN_KEYS.iter().find(|(section, _)| section == name) else {
+            unknown.push(name.clone());
+            continue;
+        };
+        // A section is a table, or an array of tables (`[[grammar]]`).
+        let sections: Vec<&toml::Table> = match value {
+            toml::Value::Table(section) => vec![section],
+            toml::Value::Array(items) => items.iter().filter_map(|v| v.as_table()).collect(),
+            _ => Vec::new(),
+        };
+        for section in sections.into_iter().filter(|_| !keys.is_empty()) {
+            unknown.extend(
+                section
+                    .keys()
+                    .filter(|key| !keys.contains(&key.as_str()))
+                    .map(|key| format!("{name}.{key}")),
+            );
         }
     }
+    unknown
 }
 
 // ---------------------------------------------------------------------------
@@ -126,4 +724,174 @@ high_threshold = 50
             "medium_threshold should default to 5"
         );
     }
+
+    #[test]
+    fn test_check_reports_unknown_keys_and_sources() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("code-graph.toml"),
+            "exclude = [\"gen/**\"]\nverbose = true\n\n[impact]\nhigh_treshold = 40\nmedium_threshold = 8\n",
+        )
+        .unwrap();
+
+        let report = CodeGraphConfig::check_with_env(tmp.path(), |_| None);
+        assert!(report.file_found && report.error.is_none());
+        assert_eq!(
+            report.warnings,
+            vec![
+                "unknown key 'impact.high_treshold' in code-graph.toml (ignored)",
+                "unknown key 'verbose' in code-graph.toml (ignored)",
+            ]
+        );
+        let sources: Vec<(&str, &str, String)> = report
+            .values
+            .iter()
+            .map(|v| (v.key, v.value.as_str(), v.source.to_string()))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("exclude", "[\"gen/**\"]", "code-graph.toml".to_string()),
+                ("impact.high_threshold", "20", "default".to_string()),
+                (
+                    "impact.medium_threshold",
+                    "8",
+                    "code-graph.toml".to_string()
+                ),
+                ("snapshot.auto", "\"off\"", "default".to_string()),
+                ("grammar", "[]", "default".to_string()),
+                ("category", "[]", "default".to_string()),
+                (
+                    "lint_imports.order",
+                    "[\"builtin\", \"external\", \"workspace\", \"internal\", \"relative\"]",
+                    "default".to_string()
+                ),
+                ("lint_imports.max_parent_depth", "2", "default".to_string()),
+                ("rules", "[]", "default".to_string()),
+                ("symbols.exclude", "[]", "default".to_string()),
+                ("string_refs.enabled", "false", "default".to_string()),
+                ("string_refs.min_length", "4", "default".to_string()),
+                ("string_refs.calls", "[]", "default".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rules_section() {
+        let cfg = parse_config(
+            "[[rules]]\nname = \"domain-is-pure\"\nfrom = [\"src/domain\"]\nforbid = [\"src/infrastructure\", \"axios\"]\n",
+        );
+        assert_eq!(cfg.rules.len(), 1);
+        assert_eq!(cfg.rules[0].name, "domain-is-pure");
+        assert_eq!(cfg.rules[0].from, vec!["src/domain"]);
+        assert_eq!(cfg.rules[0].forbid, vec!["src/infrastructure", "axios"]);
+        assert!(parse_config("").rules.is_empty());
+    }
+
+    #[test]
+    fn test_lint_imports_section() {
+        let cfg = parse_config(
+            "[lint_imports]\norder = [\"builtin\", \"external\", \"relative\"]\nmax_parent_depth = 1\n",
+        );
+        assert_eq!(
+            cfg.lint_imports.order,
+            vec![
+                ImportGroup::Builtin,
+                ImportGroup::External,
+                ImportGroup::Relative
+            ]
+        );
+        assert_eq!(cfg.lint_imports.max_parent_depth, 1);
+        assert_eq!(parse_config("").lint_imports.max_parent_depth, 2);
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("code-graph.toml"),
+            "[impact]\nhigh_threshold = 40\n",
+        )
+        .unwrap();
+        let env = |var: &str| match var {
+            "CODE_GRAPH_EXCLUDE" => Some("dist/**, fixtures/**".to_string()),
+            "CODE_GRAPH_IMPACT_HIGH_THRESHOLD" => Some("100".to_string()),
+            "CODE_GRAPH_IMPACT_MEDIUM_THRESHOLD" => Some("many".to_string()),
+            "CODE_GRAPH_SNAPSHOT_AUTO" => Some("per-commit".to_string()),
+            "CODE_GRAPH_SYMBOLS_EXCLUDE" => Some("__*, type:*Props".to_string()),
+            _ => None,
+        };
+
+        let report = CodeGraphConfig::check_with_env(tmp.path(), env);
+        let config = &report.config;
+        assert_eq!(
+            config.exclude.as_deref(),
+            Some(&["dist/**".to_string(), "fixtures/**".to_string()][..])
+        );
+        assert_eq!(config.impact.high_threshold, 100);
+        assert_eq!(config.impact.medium_threshold, 5);
+        assert_eq!(config.snapshot.auto, SnapshotAuto::PerCommit);
+        assert_eq!(config.symbols.exclude, ["__*", "type:*Props"]);
+        assert_eq!(
+            report.values[1].source,
+            ConfigSource::Env("CODE_GRAPH_IMPACT_HIGH_THRESHOLD".into())
+        );
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_grammar_tables() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("code-graph.toml"),
+            r#"[[grammar]]
+name = "elixir"
+library = "grammars/libtree-sitter-elixir.so"
+extensions = ["ex", "exs"]
+query = "grammars/elixir.scm"
+symbl = "tree_sitter_elixir"
+"#,
+        )
+        .unwrap();
+
+        let report = CodeGraphConfig::check_with_env(tmp.path(), |_| None);
+        let grammar = &report.config.grammar[0];
+        assert_eq!(grammar.name, "elixir");
+        assert_eq!(grammar.extensions, ["ex", "exs"]);
+        assert_eq!(grammar.symbol, None);
+        assert_eq!(
+            report.warnings,
+            vec!["unknown key 'grammar.symbl' in code-graph.toml (ignored)"]
+        );
+        let grammar_value = report.values.iter().find(|v| v.key == "grammar").unwrap();
+        assert_eq!(grammar_value.value, "[\"elixir\"]");
+    }
+
+    #[test]
+    fn test_category_tables() {
+        let cfg = parse_config(
+            r#"[[category]]
+name = "pages"
+paths = ["src/pages/**"]
+
+[[category]]
+name = "hooks"
+symbols = ["use*"]
+"#,
+        );
+        let rules: Vec<(&str, usize, usize)> = cfg
+            .category
+            .iter()
+            .map(|c| (c.name.as_str(), c.paths.len(), c.symbols.len()))
+            .collect();
+        assert_eq!(rules, vec![("pages", 1, 0), ("hooks", 0, 1)]);
+    }
+
+    #[test]
+    fn test_snapshot_auto_per_commit() {
+        assert_eq!(parse_config("").snapshot.auto, SnapshotAuto::Off);
+        let cfg = parse_config("[snapshot]\nauto = \"per-commit\"\n");
+        assert_eq!(cfg.snapshot.auto, SnapshotAuto::PerCommit);
+    }
 }
@@ -98,7 +98,7 @@ pub fn is_daemon_running(project_root: &Path) -> bool {
 
 /// Check if a process with the given PID is alive.
 #[cfg(unix)]
-fn process_is_alive(pid: u32) -> bool {
+pub(crate) fn process_is_alive(pid: u32) -> bool {
     // kill(pid, 0) checks existence without sending a signal.
     // Returns 0 if the process exists and we have permission to signal it.
     // SAFETY: This is a standard POSIX call, no actual signal is sent.
@@ -106,7 +106,7 @@ fn process_is_alive(pid: u32) -> bool {
 }
 
 #[cfg(not(unix))]
-fn process_is_alive(_pid: u32) -> bool {
+pub(crate) fn process_is_alive(_pid: u32) -> bool {
     // Non-Unix fallback: assume alive if PID file exists.
     true
 }
@@ -3,7 +3,24 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 /// Protocol version for forward compatibility.
-pub const PROTOCOL_VERSION: u32 = 1;
+/// Bumped to 2 when query requests gained `format` and compact text responses. A daemon
+/// speaking another version ignores or misreads newer request fields, so the CLI runs
+/// the query locally instead of trusting its response.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Response encoding for query commands that have a compact text form.
+///
+/// `Json` (the default, and the only encoding of protocol version 1) returns structured
+/// data from the `query::output` JSON serializers, for clients that post-process results
+/// programmatically. `Compact` returns the same text the CLI prints for
+/// `--format compact`, as a JSON string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormat {
+    Compact,
+    #[default]
+    Json,
+}
 
 /// A request from the CLI client to the daemon.
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,6 +40,8 @@ pub enum DaemonRequest {
         kind: Vec<String>,
         file: Option<PathBuf>,
         language: Option<String>,
+        #[serde(default)]
+        format: ResponseFormat,
     },
     Refs {
         symbol: String,
@@ -32,6 +51,8 @@ pub enum DaemonRequest {
         kind: Vec<String>,
         file: Option<PathBuf>,
         language: Option<String>,
+        #[serde(default)]
+        format: ResponseFormat,
     },
     Impact {
         symbol: String,
@@ -40,21 +61,37 @@ pub enum DaemonRequest {
         #[serde(default)]
         tree: bool,
         language: Option<String>,
+        #[serde(default)]
+        format: ResponseFormat,
     },
     Context {
         symbol: String,
         #[serde(default)]
         case_insensitive: bool,
         language: Option<String>,
+        #[serde(default)]
+        format: ResponseFormat,
     },
     Stats {
         language: Option<String>,
+        #[serde(default)]
+        scope: Option<PathBuf>,
+        #[serde(default)]
+        format: ResponseFormat,
     },
     Circular {
         language: Option<String>,
+        #[serde(default)]
+        format: ResponseFormat,
+        #[serde(default = "default_max_cycles")]
+        max_cycles: usize,
     },
     DeadCode {
         scope: Option<PathBuf>,
+        #[serde(default)]
+        tier: Option<crate::query::dead_code::DeadTier>,
+        #[serde(default)]
+        fix_list: bool,
     },
     Clones {
         scope: Option<PathBuf>,
@@ -135,6 +172,9 @@ fn default_max_paths() -> usize {
 fn default_max_depth() -> usize {
     20
 }
+fn default_max_cycles() -> usize {
+    crate::query::circular::DEFAULT_MAX_CYCLES
+}
 
 /// A response from the daemon to the CLI client.
 #[derive(Debug, Serialize, Deserialize)]
@@ -202,6 +242,7 @@ mod tests {
             kind: vec!["function".into()],
             file: Some(PathBuf::from("src/main.rs")),
             language: Some("rust".into()),
+            format: ResponseFormat::Json,
         };
         let json = serde_json::to_string(&req).unwrap();
         let parsed: DaemonRequest = serde_json::from_str(&json).unwrap();
@@ -212,17 +253,38 @@ mod tests {
                 kind,
                 file,
                 language,
+                format,
             } => {
                 assert_eq!(symbol, "UserService");
                 assert!(case_insensitive);
                 assert_eq!(kind, vec!["function"]);
                 assert_eq!(file, Some(PathBuf::from("src/main.rs")));
                 assert_eq!(language, Some("rust".into()));
+                assert_eq!(format, ResponseFormat::Json);
             }
             _ => panic!("expected Find"),
         }
     }
 
+    #[test]
+    fn request_format_defaults_to_json() {
+        // Version 1 clients send no format and expect JSON data back.
+        let parsed: DaemonRequest =
+            serde_json::from_str(r#"{"cmd":"stats","language":null}"#).unwrap();
+        match parsed {
+            DaemonRequest::Stats { format, .. } => assert_eq!(format, ResponseFormat::Json),
+            _ => panic!("expected Stats"),
+        }
+        let parsed: DaemonRequest =
+            serde_json::from_str(r#"{"cmd":"circular","format":"compact"}"#).unwrap();
+        match parsed {
+            DaemonRequest::Circular { format, .. } => {
+                assert_eq!(format, ResponseFormat::Compact)
+            }
+            _ => panic!("expected Circular"),
+        }
+    }
+
     #[test]
     fn response_success_roundtrip() {
         let resp = DaemonResponse::success(serde_json::json!({"symbols": []}));
@@ -263,6 +325,7 @@ mod tests {
                 kind: vec![],
                 file: None,
                 language: None,
+                format: ResponseFormat::Compact,
             },
             DaemonRequest::Refs {
                 symbol: "X".into(),
@@ -270,21 +333,36 @@ mod tests {
                 kind: vec![],
                 file: None,
                 language: None,
+                format: ResponseFormat::Compact,
             },
             DaemonRequest::Impact {
                 symbol: "X".into(),
                 case_insensitive: false,
                 tree: false,
                 language: None,
+                format: ResponseFormat::Compact,
             },
             DaemonRequest::Context {
                 symbol: "X".into(),
                 case_insensitive: false,
                 language: None,
+                format: ResponseFormat::Compact,
+            },
+            DaemonRequest::Stats {
+                language: None,
+                scope: None,
+                format: ResponseFormat::Compact,
+            },
+            DaemonRequest::Circular {
+                language: None,
+                format: ResponseFormat::Compact,
+                max_cycles: 3,
+            },
+            DaemonRequest::DeadCode {
+                scope: None,
+                tier: None,
+                fix_list: false,
             },
-            DaemonRequest::Stats { language: None },
-            DaemonRequest::Circular { language: None },
-            DaemonRequest::DeadCode { scope: None },
             DaemonRequest::Clones {
                 scope: None,
                 min_group: 2,
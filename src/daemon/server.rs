@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -8,12 +9,53 @@ use tokio::net::UnixListener;
 use tokio::sync::{RwLock, watch};
 
 use crate::daemon::pid;
-use crate::daemon::protocol::{DaemonRequest, DaemonResponse, PROTOCOL_VERSION};
+use crate::daemon::protocol::{DaemonRequest, DaemonResponse, PROTOCOL_VERSION, ResponseFormat};
 use crate::graph::CodeGraph;
+use crate::query::output;
 
 /// Maximum allowed request size in bytes (1 MB).
 const MAX_REQUEST_BYTES: usize = 1_048_576;
 
+/// Quiet period after the last incremental update before the graph is re-resolved.
+const RERESOLVE_QUIET: Duration = Duration::from_secs(30);
+
+/// Incremental updates after which the graph is re-resolved even without a quiet period.
+const RERESOLVE_AFTER_UPDATES: usize = 50;
+
+/// Decides when to re-resolve the whole graph after incremental updates.
+///
+/// Incremental updates only re-wire the changed file, so cross-file edges (calls into a
+/// renamed function, barrel re-exports, symbol bindings) slowly drift from what a full
+/// build would produce. A re-resolution is due once updates have been quiet for
+/// [`RERESOLVE_QUIET`] or [`RERESOLVE_AFTER_UPDATES`] have piled up.
+struct ReresolveSchedule {
+    updates: usize,
+    last_update: std::time::Instant,
+}
+
+impl ReresolveSchedule {
+    fn new() -> Self {
+        Self {
+            updates: 0,
+            last_update: std::time::Instant::now(),
+        }
+    }
+
+    fn record_update(&mut self, now: std::time::Instant) {
+        self.updates += 1;
+        self.last_update = now;
+    }
+
+    fn is_due(&self, now: std::time::Instant) -> bool {
+        self.updates >= RERESOLVE_AFTER_UPDATES
+            || (self.updates > 0 && now.duration_since(self.last_update) >= RERESOLVE_QUIET)
+    }
+
+    fn reset(&mut self) {
+        self.updates = 0;
+    }
+}
+
 /// Run the daemon server: build graph, watch for changes, serve queries over Unix socket.
 ///
 /// This function does not return under normal operation. It runs until a Shutdown
@@ -21,10 +63,10 @@ const MAX_REQUEST_BYTES: usize = 1_048_576;
 pub async fn run_daemon(project_root: PathBuf) -> Result<()> {
     eprintln!("[daemon] starting for project: {}", project_root.display());
 
-    // 1. Build initial graph.
-    let graph = tokio::task::spawn_blocking({
+    // 1. Build initial graph, keeping the parse results for background re-resolution.
+    let (graph, parsed) = tokio::task::spawn_blocking({
         let root = project_root.clone();
-        move || crate::build_graph(&root, false)
+        move || crate::build_graph_parsed(&root, false)
     })
     .await
     .context("build_graph task panicked")?
@@ -75,6 +117,7 @@ pub async fn run_daemon(project_root: PathBuf) -> Result<()> {
     let watcher_handle = spawn_watcher(
         project_root.clone(),
         Arc::clone(&graph),
+        parsed,
         shutdown_rx.clone(),
     );
 
@@ -104,8 +147,7 @@ pub async fn run_daemon(project_root: PathBuf) -> Result<()> {
 
     // Save cache.
     {
-        let g = graph.read().await;
-        if let Err(e) = crate::cache::save_cache(&project_root, &g) {
+        if let Err(e) = save_cache_blocking(&graph, &project_root).await {
             eprintln!("[daemon] failed to save cache on shutdown: {}", e);
         } else {
             eprintln!("[daemon] cache saved");
@@ -277,6 +319,7 @@ async fn handle_connection(
 fn spawn_watcher(
     project_root: PathBuf,
     graph: Arc<RwLock<CodeGraph>>,
+    parsed: crate::ParsedFiles,
     shutdown_rx: watch::Receiver<bool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
@@ -302,7 +345,7 @@ fn spawn_watcher(
 
         // Relay std mpsc events to a tokio mpsc channel using a dedicated
         // blocking thread, then process events asynchronously.
-        run_watcher_relay(rx, graph, project_root, shutdown_rx).await;
+        run_watcher_relay(rx, graph, parsed, project_root, shutdown_rx).await;
     })
 }
 
@@ -311,6 +354,7 @@ fn spawn_watcher(
 async fn run_watcher_relay(
     rx: std::sync::mpsc::Receiver<crate::watcher::event::WatchEvent>,
     graph: Arc<RwLock<CodeGraph>>,
+    parsed: crate::ParsedFiles,
     project_root: PathBuf,
     mut shutdown_rx: watch::Receiver<bool>,
 ) {
@@ -334,24 +378,129 @@ async fn run_watcher_relay(
     // Skip the first immediate tick.
     save_interval.tick().await;
 
+    // Per-commit snapshots: HEAD is checked only after the watcher has been idle for a
+    // poll interval, so the file events of a checkout land before the snapshot is taken.
+    let mut commit_snapshots = (crate::config::CodeGraphConfig::load(&project_root)
+        .snapshot
+        .auto
+        == crate::config::SnapshotAuto::PerCommit)
+        .then(crate::query::diff::CommitSnapshotter::default);
+    let mut head_interval = tokio::time::interval(crate::query::diff::HEAD_POLL_INTERVAL);
+    head_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_event = std::time::Instant::now();
+
+    // Background re-resolution: the graph is re-resolved off-lock on a blocking thread
+    // while queries keep reading the live graph. It starts from the parse results of the
+    // last build, re-parsing only the files changed since, so the project is not parsed
+    // again. Incremental events that arrive meanwhile are applied to the live graph and
+    // remembered, then replayed onto the re-resolved graph before it is swapped in.
+    let mut schedule = ReresolveSchedule::new();
+    let mut reresolve_interval = tokio::time::interval(Duration::from_secs(1));
+    reresolve_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut rebuild: Option<tokio::task::JoinHandle<Result<(CodeGraph, crate::ParsedFiles)>>> =
+        None;
+    let mut replay: Vec<crate::watcher::event::WatchEvent> = Vec::new();
+    // Taken by a running re-resolution and handed back when it finishes.
+    let mut parsed = Some(parsed);
+    let mut changed: HashSet<PathBuf> = HashSet::new();
+
     loop {
         tokio::select! {
             event = relay_rx.recv() => {
                 match event {
                     Some(ev) => {
-                        handle_watcher_event(&ev, &graph, &project_root).await;
+                        let reindexed = handle_watcher_event(&ev, &graph, &project_root).await;
                         dirty = true;
+                        last_event = std::time::Instant::now();
+                        match ev {
+                            crate::watcher::event::WatchEvent::Modified(ref p)
+                            | crate::watcher::event::WatchEvent::Deleted(ref p) => {
+                                schedule.record_update(last_event);
+                                changed.insert(p.clone());
+                                if rebuild.is_some() {
+                                    replay.push(ev);
+                                }
+                            }
+                            // A full re-index supersedes any pending or running
+                            // re-resolution.
+                            _ => {
+                                schedule.reset();
+                                rebuild = None;
+                                replay.clear();
+                                if reindexed.is_some() {
+                                    parsed = reindexed;
+                                    changed.clear();
+                                }
+                            }
+                        }
                     }
                     None => break, // bridge thread finished
                 }
             }
+            _ = reresolve_interval.tick(), if rebuild.is_none() => {
+                if schedule.is_due(std::time::Instant::now()) {
+                    eprintln!(
+                        "[daemon] re-resolving after {} incremental update(s)...",
+                        schedule.updates
+                    );
+                    schedule.reset();
+                    let root = project_root.clone();
+                    let cached = parsed.take();
+                    let changed: Vec<PathBuf> = changed.drain().collect();
+                    rebuild = Some(tokio::task::spawn_blocking(move || match cached {
+                        Some(mut cached) => {
+                            crate::reparse_changed(&mut cached, &changed);
+                            crate::resolve_parsed(&root, cached, false)
+                        }
+                        // The last re-resolution failed: start over from the files.
+                        None => crate::build_graph_parsed(&root, false),
+                    }));
+                }
+            }
+            result = async { rebuild.as_mut().expect("guarded by is_some").await }, if rebuild.is_some() => {
+                rebuild = None;
+                match result {
+                    Ok(Ok((mut new_graph, files))) => {
+                        parsed = Some(files);
+                        let mut g = graph.write().await;
+                        for ev in replay.drain(..) {
+                            crate::watcher::incremental::handle_file_event(
+                                &mut new_graph,
+                                &ev,
+                                &project_root,
+                            );
+                        }
+                        *g = new_graph;
+                        dirty = true;
+                        eprintln!(
+                            "[daemon] re-resolved ({} files, {} symbols)",
+                            g.file_count(),
+                            g.symbol_count(),
+                        );
+                    }
+                    Ok(Err(e)) => eprintln!("[daemon] re-resolution failed: {}", e),
+                    Err(e) => eprintln!("[daemon] re-resolution task panicked: {}", e),
+                }
+                replay.clear();
+            }
             _ = save_interval.tick(), if dirty => {
-                let g = graph.read().await;
-                if let Err(e) = crate::cache::save_cache(&project_root, &g) {
+                if let Err(e) = save_cache_blocking(&graph, &project_root).await {
                     eprintln!("[daemon] cache save error: {}", e);
                 }
                 dirty = false;
             }
+            _ = head_interval.tick(), if commit_snapshots.is_some() => {
+                if last_event.elapsed() >= crate::query::diff::HEAD_POLL_INTERVAL
+                    && let Some(snapshotter) = commit_snapshots.as_mut()
+                {
+                    let g = graph.read().await;
+                    match snapshotter.poll(&g, &project_root) {
+                        Ok(Some(name)) => eprintln!("[daemon] snapshot '{}' captured", name),
+                        Ok(None) => {}
+                        Err(e) => eprintln!("[daemon] snapshot failed: {}", e),
+                    }
+                }
+            }
             _ = shutdown_rx.changed() => {
                 if *shutdown_rx.borrow() {
                     break;
@@ -362,8 +511,7 @@ async fn run_watcher_relay(
 
     // Final cache save if dirty.
     if dirty {
-        let g = graph.read().await;
-        let _ = crate::cache::save_cache(&project_root, &g);
+        let _ = save_cache_blocking(&graph, &project_root).await;
     }
 
     // Clean up bridge thread.
@@ -371,13 +519,29 @@ async fn run_watcher_relay(
     let _ = bridge.await;
 }
 
+/// Save a snapshot of the shared graph on a blocking thread.
+///
+/// Saving waits on the cache lock for up to [`crate::cache::lock::LOCK_WAIT`], which must
+/// not stall a runtime worker.
+async fn save_cache_blocking(graph: &Arc<RwLock<CodeGraph>>, project_root: &Path) -> Result<()> {
+    let snapshot = graph.read().await.clone();
+    let root = project_root.to_path_buf();
+    tokio::task::spawn_blocking(move || crate::cache::save_cache(&root, &snapshot)).await?
+}
+
 /// Perform a full graph re-index, replacing the shared graph under a write lock.
-async fn full_reindex(graph: &Arc<RwLock<CodeGraph>>, project_root: &Path, reason: &str) {
+///
+/// Returns the new parse results, or `None` when the re-index failed.
+async fn full_reindex(
+    graph: &Arc<RwLock<CodeGraph>>,
+    project_root: &Path,
+    reason: &str,
+) -> Option<crate::ParsedFiles> {
     eprintln!("[daemon] {} -- full re-index...", reason);
     let start = std::time::Instant::now();
     let root = project_root.to_path_buf();
-    match tokio::task::spawn_blocking(move || crate::build_graph(&root, false)).await {
-        Ok(Ok(new_graph)) => {
+    match tokio::task::spawn_blocking(move || crate::build_graph_parsed(&root, false)).await {
+        Ok(Ok((new_graph, parsed))) => {
             let mut g = graph.write().await;
             *g = new_graph;
             let elapsed = start.elapsed();
@@ -387,40 +551,53 @@ async fn full_reindex(graph: &Arc<RwLock<CodeGraph>>, project_root: &Path, reaso
                 g.file_count(),
                 g.symbol_count(),
             );
+            Some(parsed)
         }
         Ok(Err(e)) => {
             eprintln!("[daemon] full re-index failed: {}", e);
+            None
         }
         Err(e) => {
             eprintln!("[daemon] re-index task panicked: {}", e);
+            None
         }
     }
 }
 
 /// Process a single watcher event, updating the graph.
+///
+/// Returns the new parse results when the event triggered a full re-index.
 async fn handle_watcher_event(
     event: &crate::watcher::event::WatchEvent,
     graph: &Arc<RwLock<CodeGraph>>,
     project_root: &Path,
-) {
+) -> Option<crate::ParsedFiles> {
     use crate::watcher::event::WatchEvent;
 
     match event {
         WatchEvent::Modified(p) => {
             let start = std::time::Instant::now();
-            {
+            let changes = {
                 let mut g = graph.write().await;
+                let before = crate::query::diff::file_snapshot(&g, project_root, p);
                 crate::watcher::incremental::handle_file_event(&mut g, event, project_root);
-            }
+                let after = crate::query::diff::file_snapshot(&g, project_root, p);
+                crate::query::diff::diff_snapshots(&before, &after)
+            };
             let elapsed = start.elapsed();
             eprintln!(
                 "[daemon] incremental: {} ({:.1}ms)",
                 p.strip_prefix(project_root).unwrap_or(p).display(),
                 elapsed.as_secs_f64() * 1000.0,
             );
+            for line in output::format_file_diff_to_string(&changes).lines() {
+                eprintln!("[daemon]   {}", line);
+            }
+            None
         }
         WatchEvent::Deleted(p) => {
             let mut g = graph.write().await;
+            let before = crate::query::diff::file_snapshot(&g, project_root, p);
             crate::watcher::incremental::handle_file_event(&mut g, event, project_root);
             eprintln!(
                 "[daemon] deleted: {} ({} files, {} symbols)",
@@ -428,13 +605,17 @@ async fn handle_watcher_event(
                 g.file_count(),
                 g.symbol_count(),
             );
+            let after = crate::query::diff::file_snapshot(&g, project_root, p);
+            let changes = crate::query::diff::diff_snapshots(&before, &after);
+            for line in output::format_file_diff_to_string(&changes).lines() {
+                eprintln!("[daemon]   {}", line);
+            }
+            None
         }
-        WatchEvent::ConfigChanged => {
-            full_reindex(graph, project_root, "config changed").await;
-        }
+        WatchEvent::ConfigChanged => full_reindex(graph, project_root, "config changed").await,
         WatchEvent::CrateRootChanged(p) => {
             let filename = p.file_name().unwrap_or_default().to_string_lossy();
-            full_reindex(graph, project_root, &format!("{} changed", filename)).await;
+            full_reindex(graph, project_root, &format!("{} changed", filename)).await
         }
     }
 }
@@ -491,6 +672,7 @@ fn dispatch_query(
             kind,
             file,
             language,
+            format,
         } => dispatch_find(
             graph,
             project_root,
@@ -499,6 +681,7 @@ fn dispatch_query(
             kind,
             file.as_deref(),
             language.as_deref(),
+            *format,
         ),
 
         DaemonRequest::Refs {
@@ -507,6 +690,7 @@ fn dispatch_query(
             kind,
             file,
             language,
+            format,
         } => dispatch_refs(
             graph,
             project_root,
@@ -515,6 +699,7 @@ fn dispatch_query(
             kind,
             file.as_deref(),
             language.as_deref(),
+            *format,
         ),
 
         DaemonRequest::Impact {
@@ -522,35 +707,59 @@ fn dispatch_query(
             case_insensitive,
             tree: _,
             language,
+            format,
         } => dispatch_impact(
             graph,
             project_root,
             symbol,
             *case_insensitive,
             language.as_deref(),
+            *format,
         ),
 
         DaemonRequest::Context {
             symbol,
             case_insensitive,
             language,
+            format,
         } => dispatch_context(
             graph,
             project_root,
             symbol,
             *case_insensitive,
             language.as_deref(),
+            *format,
         ),
 
-        DaemonRequest::Stats { language } => dispatch_stats(graph, language.as_deref()),
+        DaemonRequest::Stats {
+            language,
+            scope,
+            format,
+        } => dispatch_stats(
+            graph,
+            project_root,
+            language.as_deref(),
+            scope.as_deref(),
+            *format,
+        ),
 
-        DaemonRequest::Circular { language } => {
-            dispatch_circular(graph, project_root, language.as_deref())
-        }
+        DaemonRequest::Circular {
+            language,
+            format,
+            max_cycles,
+        } => dispatch_circular(
+            graph,
+            project_root,
+            language.as_deref(),
+            *format,
+            *max_cycles,
+        ),
 
-        DaemonRequest::DeadCode { scope } => {
-            dispatch_dead_code(graph, project_root, scope.as_deref())
-        }
+        DaemonRequest::DeadCode {
+            scope,
+            tier,
+            fix_list,
+        } => dispatch_dead_code(graph, project_root, scope.as_deref(), *tier, *fix_list),
 
         DaemonRequest::Clones { scope, min_group } => {
             dispatch_clones(graph, project_root, scope.as_deref(), *min_group)
@@ -626,6 +835,7 @@ fn dispatch_query(
 // Individual dispatch helpers
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 fn dispatch_find(
     graph: &CodeGraph,
     project_root: &Path,
@@ -634,6 +844,7 @@ fn dispatch_find(
     kind_filter: &[String],
     file_filter: Option<&Path>,
     language: Option<&str>,
+    format: ResponseFormat,
 ) -> DaemonResponse {
     let language_filter = match parse_lang(language) {
         Ok(f) => f,
@@ -649,17 +860,17 @@ fn dispatch_find(
         project_root,
         language_filter,
     ) {
-        Ok(results) => {
-            let data: Vec<serde_json::Value> = results
-                .iter()
-                .map(|r| find_result_to_json(r, project_root))
-                .collect();
-            DaemonResponse::success(serde_json::json!(data))
-        }
+        Ok(results) => DaemonResponse::success(match format {
+            ResponseFormat::Compact => {
+                output::format_find_to_string(&results, project_root, symbol).into()
+            }
+            ResponseFormat::Json => output::find_results_to_json(&results, project_root),
+        }),
         Err(e) => DaemonResponse::error(format!("{}", e)),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn dispatch_refs(
     graph: &CodeGraph,
     project_root: &Path,
@@ -668,6 +879,7 @@ fn dispatch_refs(
     kind_filter: &[String],
     file_filter: Option<&Path>,
     language: Option<&str>,
+    format: ResponseFormat,
 ) -> DaemonResponse {
     let language_filter = match parse_lang(language) {
         Ok(f) => f,
@@ -693,10 +905,7 @@ fn dispatch_refs(
     // Apply kind filter (e.g. "import", "call")
     if !kind_filter.is_empty() {
         results.retain(|r| {
-            let kind_str = match r.ref_kind {
-                crate::query::refs::RefKind::Import => "import",
-                crate::query::refs::RefKind::Call => "call",
-            };
+            let kind_str = r.ref_kind.as_str();
             kind_filter.iter().any(|k| k.eq_ignore_ascii_case(kind_str))
         });
     }
@@ -715,11 +924,12 @@ fn dispatch_refs(
         results.retain(|r| file_language_matches(&r.file_path, lang));
     }
 
-    let data: Vec<serde_json::Value> = results
-        .iter()
-        .map(|r| ref_result_to_json(r, project_root))
-        .collect();
-    DaemonResponse::success(serde_json::json!(data))
+    DaemonResponse::success(match format {
+        ResponseFormat::Compact => {
+            output::format_refs_to_string(&results, project_root, symbol).into()
+        }
+        ResponseFormat::Json => output::refs_results_to_json(&results, project_root),
+    })
 }
 
 fn dispatch_impact(
@@ -728,6 +938,7 @@ fn dispatch_impact(
     symbol: &str,
     case_insensitive: bool,
     language: Option<&str>,
+    format: ResponseFormat,
 ) -> DaemonResponse {
     let language_filter = match parse_lang(language) {
         Ok(f) => f,
@@ -748,16 +959,18 @@ fn dispatch_impact(
         .flat_map(|(_, indices)| indices.iter().copied())
         .collect();
 
-    let mut results = crate::query::impact::blast_radius(graph, &all_indices, project_root);
+    let mut results = crate::query::impact::cached_blast_radius(graph, &all_indices, project_root);
 
     if let Some(lang) = language_filter {
         results.retain(|r| file_language_matches(&r.file_path, lang));
     }
 
-    match serde_json::to_value(&results) {
-        Ok(data) => DaemonResponse::success(data),
-        Err(e) => DaemonResponse::error(format!("serialization error: {}", e)),
-    }
+    DaemonResponse::success(match format {
+        ResponseFormat::Compact => {
+            output::format_impact_to_string(&results, project_root, symbol).into()
+        }
+        ResponseFormat::Json => output::impact_results_to_json(&results, project_root),
+    })
 }
 
 fn dispatch_context(
@@ -766,6 +979,7 @@ fn dispatch_context(
     symbol: &str,
     case_insensitive: bool,
     language: Option<&str>,
+    format: ResponseFormat,
 ) -> DaemonResponse {
     let language_filter = match parse_lang(language) {
         Ok(f) => f,
@@ -802,64 +1016,80 @@ fn dispatch_context(
         results.retain(|ctx| !ctx.definitions.is_empty());
     }
 
-    let data: Vec<serde_json::Value> = results
-        .iter()
-        .map(|ctx| context_to_json(ctx, project_root))
-        .collect();
-    DaemonResponse::success(serde_json::json!(data))
+    DaemonResponse::success(match format {
+        ResponseFormat::Compact => {
+            output::format_context_to_string(&results, project_root, None).into()
+        }
+        ResponseFormat::Json => output::context_results_to_json(&results, project_root),
+    })
 }
 
-fn dispatch_stats(graph: &CodeGraph, language: Option<&str>) -> DaemonResponse {
+fn dispatch_stats(
+    graph: &CodeGraph,
+    project_root: &Path,
+    language: Option<&str>,
+    scope: Option<&Path>,
+    format: ResponseFormat,
+) -> DaemonResponse {
     let language_filter = match parse_lang(language) {
         Ok(f) => f,
         Err(e) => return DaemonResponse::error(e),
     };
 
-    let stats = crate::query::stats::project_stats(graph);
-    DaemonResponse::success(stats_to_json(&stats, language_filter))
+    let abs_scope = scope.map(|s| project_root.join(s));
+    let stats = crate::query::stats::project_stats_for_files(graph, project_root, |file| {
+        abs_scope.as_ref().is_none_or(|s| file.starts_with(s))
+            && language_filter.is_none_or(|lang| file_language_matches(file, lang))
+    });
+    DaemonResponse::success(match format {
+        ResponseFormat::Compact => output::format_stats_to_string(&stats, language_filter).into(),
+        ResponseFormat::Json => output::stats_to_json(&stats),
+    })
 }
 
 fn dispatch_circular(
     graph: &CodeGraph,
     project_root: &Path,
     language: Option<&str>,
+    format: ResponseFormat,
+    max_cycles: usize,
 ) -> DaemonResponse {
     let language_filter = match parse_lang(language) {
         Ok(f) => f,
         Err(e) => return DaemonResponse::error(e),
     };
 
-    let mut cycles = crate::query::circular::find_circular(graph, project_root);
+    let mut cycles = crate::query::circular::find_circular(graph, project_root, max_cycles, false);
 
     if let Some(lang) = language_filter {
         cycles.retain(|c| c.files.iter().all(|f| file_language_matches(f, lang)));
     }
 
-    let data: Vec<serde_json::Value> = cycles
-        .iter()
-        .map(|c| {
-            let files: Vec<String> = c
-                .files
-                .iter()
-                .map(|f| {
-                    f.strip_prefix(project_root)
-                        .unwrap_or(f)
-                        .to_string_lossy()
-                        .into_owned()
-                })
-                .collect();
-            serde_json::json!({ "files": files })
-        })
-        .collect();
-    DaemonResponse::success(serde_json::json!(data))
+    DaemonResponse::success(match format {
+        ResponseFormat::Compact => output::format_circular_to_string(&cycles, project_root).into(),
+        ResponseFormat::Json => output::circular_results_to_json(&cycles, project_root),
+    })
 }
 
 fn dispatch_dead_code(
     graph: &CodeGraph,
     project_root: &Path,
     scope: Option<&Path>,
+    tier: Option<crate::query::dead_code::DeadTier>,
+    fix_list: bool,
 ) -> DaemonResponse {
-    let result = crate::query::dead_code::find_dead_code(graph, project_root, scope);
+    let mut result = crate::query::dead_code::find_dead_code(graph, project_root, scope);
+    if let Some(tier) = tier {
+        result.retain_tier(tier);
+    }
+    if fix_list {
+        result.retain_unnamed_elsewhere(graph);
+        let mut text = output::format_dead_code_fix_list(&result, project_root, tier);
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        return DaemonResponse::success(text.into());
+    }
     match serde_json::to_value(&result) {
         Ok(data) => DaemonResponse::success(data),
         Err(e) => DaemonResponse::error(format!("serialization error: {}", e)),
@@ -908,9 +1138,10 @@ fn dispatch_export(
         "symbol" => crate::export::model::Granularity::Symbol,
         "file" => crate::export::model::Granularity::File,
         "package" => crate::export::model::Granularity::Package,
+        "dir" => crate::export::model::Granularity::Dir,
         other => {
             return DaemonResponse::error(format!(
-                "unknown granularity '{}'. Valid: symbol, file, package",
+                "unknown granularity '{}'. Valid: symbol, file, package, dir",
                 other
             ));
         }
@@ -923,13 +1154,15 @@ fn dispatch_export(
         symbol_filter: args.symbol_filter.map(|s| s.to_string()),
         depth: args.depth,
         exclude_patterns: args.exclude.to_vec(),
+        category_filter: Vec::new(),
         project_root: project_root.to_path_buf(),
         stdout: true,
     };
 
-    match crate::export::export_graph(graph, &params) {
+    let mut content: Vec<u8> = Vec::new();
+    match crate::export::export_graph(graph, &params, &mut content) {
         Ok(result) => DaemonResponse::success(serde_json::json!({
-            "content": result.content,
+            "content": String::from_utf8_lossy(&content),
             "node_count": result.node_count,
             "edge_count": result.edge_count,
         })),
@@ -1118,120 +1351,6 @@ fn dispatch_snapshot_delete(project_root: &Path, name: &str) -> DaemonResponse {
     }
 }
 
-// ---------------------------------------------------------------------------
-// JSON serialization helpers for types without Serialize derive
-// ---------------------------------------------------------------------------
-
-fn find_result_to_json(
-    r: &crate::query::find::FindResult,
-    project_root: &Path,
-) -> serde_json::Value {
-    let rel = r
-        .file_path
-        .strip_prefix(project_root)
-        .unwrap_or(&r.file_path);
-    serde_json::json!({
-        "name": r.symbol_name,
-        "kind": crate::query::find::kind_to_str(&r.kind),
-        "file": rel.to_string_lossy(),
-        "line": r.line,
-        "line_end": r.line_end,
-        "col": r.col,
-        "exported": r.is_exported,
-        "default": r.is_default,
-    })
-}
-
-fn ref_result_to_json(r: &crate::query::refs::RefResult, project_root: &Path) -> serde_json::Value {
-    let rel = r
-        .file_path
-        .strip_prefix(project_root)
-        .unwrap_or(&r.file_path);
-    serde_json::json!({
-        "file": rel.to_string_lossy(),
-        "ref_kind": format!("{:?}", r.ref_kind).to_lowercase(),
-        "symbol_name": r.symbol_name,
-        "line": r.line,
-    })
-}
-
-fn context_to_json(
-    ctx: &crate::query::context::SymbolContext,
-    project_root: &Path,
-) -> serde_json::Value {
-    serde_json::json!({
-        "symbol_name": ctx.symbol_name,
-        "definitions": ctx.definitions.iter().map(|d| find_result_to_json(d, project_root)).collect::<Vec<_>>(),
-        "references": ctx.references.iter().map(|r| ref_result_to_json(r, project_root)).collect::<Vec<_>>(),
-        "callers": ctx.callers.iter().map(|c| call_info_to_json(c, project_root)).collect::<Vec<_>>(),
-        "callees": ctx.callees.iter().map(|c| call_info_to_json(c, project_root)).collect::<Vec<_>>(),
-        "extends": ctx.extends.iter().map(|c| call_info_to_json(c, project_root)).collect::<Vec<_>>(),
-        "implements": ctx.implements.iter().map(|c| call_info_to_json(c, project_root)).collect::<Vec<_>>(),
-        "extended_by": ctx.extended_by.iter().map(|c| call_info_to_json(c, project_root)).collect::<Vec<_>>(),
-        "implemented_by": ctx.implemented_by.iter().map(|c| call_info_to_json(c, project_root)).collect::<Vec<_>>(),
-    })
-}
-
-fn call_info_to_json(
-    c: &crate::query::context::CallInfo,
-    project_root: &Path,
-) -> serde_json::Value {
-    let rel = c
-        .file_path
-        .strip_prefix(project_root)
-        .unwrap_or(&c.file_path);
-    serde_json::json!({
-        "symbol_name": c.symbol_name,
-        "file": rel.to_string_lossy(),
-        "line": c.line,
-    })
-}
-
-fn stats_to_json(
-    stats: &crate::query::stats::ProjectStats,
-    language_filter: Option<&str>,
-) -> serde_json::Value {
-    let mut obj = serde_json::Map::new();
-
-    // Always include aggregate counts.
-    obj.insert("file_count".into(), stats.file_count.into());
-    obj.insert("symbol_count".into(), stats.symbol_count.into());
-
-    let show_ts = language_filter.is_none()
-        || language_filter == Some("typescript")
-        || language_filter == Some("javascript");
-    let show_rust = language_filter.is_none() || language_filter == Some("rust");
-
-    if show_ts {
-        obj.insert("functions".into(), stats.functions.into());
-        obj.insert("classes".into(), stats.classes.into());
-        obj.insert("interfaces".into(), stats.interfaces.into());
-        obj.insert("type_aliases".into(), stats.type_aliases.into());
-        obj.insert("enums".into(), stats.enums.into());
-        obj.insert("variables".into(), stats.variables.into());
-        obj.insert("components".into(), stats.components.into());
-        obj.insert("methods".into(), stats.methods.into());
-        obj.insert("properties".into(), stats.properties.into());
-        obj.insert("import_edges".into(), stats.import_edges.into());
-        obj.insert("external_packages".into(), stats.external_packages.into());
-        obj.insert("unresolved_imports".into(), stats.unresolved_imports.into());
-    }
-
-    if show_rust {
-        obj.insert("rust_fns".into(), stats.rust_fns.into());
-        obj.insert("rust_structs".into(), stats.rust_structs.into());
-        obj.insert("rust_enums".into(), stats.rust_enums.into());
-        obj.insert("rust_traits".into(), stats.rust_traits.into());
-        obj.insert("rust_impl_methods".into(), stats.rust_impl_methods.into());
-        obj.insert("rust_type_aliases".into(), stats.rust_type_aliases.into());
-        obj.insert("rust_consts".into(), stats.rust_consts.into());
-        obj.insert("rust_statics".into(), stats.rust_statics.into());
-        obj.insert("rust_macros".into(), stats.rust_macros.into());
-    }
-
-    serde_json::Value::Object(obj)
-}
-
 // ---------------------------------------------------------------------------
 // Shared helpers
 // ---------------------------------------------------------------------------
@@ -1248,8 +1367,15 @@ fn parse_lang(lang: Option<&str>) -> Result<Option<&'static str>, String> {
                 Some(LanguageKind::JavaScript) => Ok(Some("javascript")),
                 Some(LanguageKind::Python) => Ok(Some("python")),
                 Some(LanguageKind::Go) => Ok(Some("go")),
+                Some(LanguageKind::Proto) => Ok(Some("proto")),
+                Some(LanguageKind::Sql) => Ok(Some("sql")),
+                Some(LanguageKind::Astro) => Ok(Some("astro")),
+                Some(LanguageKind::Zig) => Ok(Some("zig")),
+                Some(LanguageKind::Shell) => Ok(Some("shell")),
+                Some(LanguageKind::Jupyter) => Ok(Some("jupyter")),
+                Some(LanguageKind::Css) => Ok(Some("css")),
                 None => Err(format!(
-                    "unknown language '{}'. Valid: rust/rs, typescript/ts, javascript/js, python/py, go/golang",
+                    "unknown language '{}'. Valid: rust/rs, typescript/ts, javascript/js, python/py, go/golang, proto/protobuf, sql, astro, zig, shell/sh, jupyter/ipynb, css/scss",
                     s
                 )),
             }
@@ -1266,6 +1392,13 @@ fn file_language_matches(path: &Path, lang: &str) -> bool {
         "javascript" => matches!(ext, "js" | "jsx"),
         "python" => ext == "py",
         "go" => ext == "go",
+        "proto" => ext == "proto",
+        "sql" => ext == "sql",
+        "astro" => ext == "astro",
+        "zig" => ext == "zig",
+        "shell" => matches!(ext, "sh" | "bash"),
+        "jupyter" => ext == "ipynb",
+        "css" => matches!(ext, "css" | "scss"),
         _ => false,
     }
 }
@@ -1293,7 +1426,15 @@ mod tests {
     fn dispatch_stats_returns_success() {
         let graph = CodeGraph::new();
         let root = PathBuf::from("/tmp/test");
-        let response = dispatch_query(&DaemonRequest::Stats { language: None }, &graph, &root);
+        let response = dispatch_query(
+            &DaemonRequest::Stats {
+                language: None,
+                scope: None,
+                format: ResponseFormat::Json,
+            },
+            &graph,
+            &root,
+        );
         match response {
             DaemonResponse::Success { version, data } => {
                 assert_eq!(version, PROTOCOL_VERSION);
@@ -1304,6 +1445,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dispatch_stats_compact_returns_text() {
+        let graph = CodeGraph::new();
+        let root = PathBuf::from("/tmp/test");
+        let response = dispatch_query(
+            &DaemonRequest::Stats {
+                language: None,
+                scope: None,
+                format: ResponseFormat::Compact,
+            },
+            &graph,
+            &root,
+        );
+        match response {
+            DaemonResponse::Success { data, .. } => {
+                let text = data.as_str().expect("compact response should be a string");
+                assert!(text.contains("files 0"));
+                assert!(text.contains("symbols 0"));
+            }
+            DaemonResponse::Error { .. } => panic!("expected Success for Stats"),
+        }
+    }
+
     #[test]
     fn dispatch_find_no_results() {
         let graph = CodeGraph::new();
@@ -1315,6 +1479,7 @@ mod tests {
                 kind: vec![],
                 file: None,
                 language: None,
+                format: ResponseFormat::Json,
             },
             &graph,
             &root,
@@ -1331,7 +1496,15 @@ mod tests {
     fn dispatch_circular_empty_graph() {
         let graph = CodeGraph::new();
         let root = PathBuf::from("/tmp/test");
-        let response = dispatch_query(&DaemonRequest::Circular { language: None }, &graph, &root);
+        let response = dispatch_query(
+            &DaemonRequest::Circular {
+                language: None,
+                format: ResponseFormat::Json,
+                max_cycles: 3,
+            },
+            &graph,
+            &root,
+        );
         match response {
             DaemonResponse::Success { data, .. } => {
                 assert!(data.as_array().unwrap().is_empty());
@@ -1351,6 +1524,7 @@ mod tests {
                 kind: vec![],
                 file: None,
                 language: None,
+                format: ResponseFormat::Compact,
             },
             &graph,
             &root,
@@ -1371,6 +1545,8 @@ mod tests {
         let response = dispatch_query(
             &DaemonRequest::Stats {
                 language: Some("invalid_lang".into()),
+                scope: None,
+                format: ResponseFormat::Compact,
             },
             &graph,
             &root,
@@ -1383,6 +1559,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn reresolve_schedule_waits_for_quiet_or_burst() {
+        let start = std::time::Instant::now();
+        let mut schedule = ReresolveSchedule::new();
+        assert!(!schedule.is_due(start + RERESOLVE_QUIET * 2));
+
+        schedule.record_update(start);
+        assert!(!schedule.is_due(start + Duration::from_secs(1)));
+        assert!(schedule.is_due(start + RERESOLVE_QUIET));
+
+        schedule.reset();
+        for _ in 0..RERESOLVE_AFTER_UPDATES {
+            schedule.record_update(start);
+        }
+        assert!(schedule.is_due(start));
+    }
+
     #[test]
     fn parse_lang_valid() {
         assert_eq!(parse_lang(None), Ok(None));
@@ -9,7 +9,7 @@ use crate::daemon::client::query_daemon;
 use crate::daemon::pid::{
     is_daemon_running, pid_path, remove_pid_file, remove_socket_file, socket_path,
 };
-use crate::daemon::protocol::{DaemonRequest, DaemonResponse};
+use crate::daemon::protocol::{DaemonRequest, DaemonResponse, ResponseFormat};
 use crate::daemon::server::run_daemon;
 
 // ---------------------------------------------------------------------------
@@ -91,6 +91,7 @@ async fn test_daemon_lifecycle() {
             kind: vec![],
             file: None,
             language: None,
+            format: ResponseFormat::Json,
         },
     )
     .await
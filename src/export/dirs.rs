@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::export::model::ExportParams;
+use crate::graph::CodeGraph;
+use crate::graph::node::GraphNode;
+use crate::query::util::relative_dir;
+
+/// Directory overlay for `--granularity dir`: visible files rolled up to their parent
+/// directories, with inter-file dependency edges aggregated between directories.
+///
+/// Computed on demand from file paths, so the graph itself stores no directory nodes.
+pub struct DirOverlay {
+    /// Project-relative directories with at least one visible file, sorted.
+    pub dirs: Vec<String>,
+    /// `(source dir, target dir)` positions in `dirs` → summed weight of file edges.
+    pub edges: BTreeMap<(usize, usize), usize>,
+}
+
+impl DirOverlay {
+    pub fn build(
+        graph: &CodeGraph,
+        params: &ExportParams,
+        visible_nodes: &HashSet<NodeIndex>,
+    ) -> Self {
+        let file_dirs: HashMap<NodeIndex, String> = visible_nodes
+            .iter()
+            .filter_map(|&idx| match &graph.graph[idx] {
+                GraphNode::File(fi) => Some((idx, relative_dir(&fi.path, &params.project_root))),
+                _ => None,
+            })
+            .collect();
+        let dirs: Vec<String> = file_dirs
+            .values()
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let position: HashMap<&str, usize> = dirs
+            .iter()
+            .enumerate()
+            .map(|(i, dir)| (dir.as_str(), i))
+            .collect();
+
+        let mut edges: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        for edge in graph.graph.edge_references() {
+            if !super::is_dependency_edge_for_count(edge.weight()) {
+                continue;
+            }
+            let (Some(src), Some(tgt)) =
+                (file_dirs.get(&edge.source()), file_dirs.get(&edge.target()))
+            else {
+                continue;
+            };
+            if src != tgt {
+                *edges
+                    .entry((position[src.as_str()], position[tgt.as_str()]))
+                    .or_insert(0) += edge.weight().weight();
+            }
+        }
+        Self { dirs, edges }
+    }
+}
+
+/// Edge label for `count` aggregated imports.
+pub fn import_label(count: usize) -> String {
+    if count == 1 {
+        "1 import".to_string()
+    } else {
+        format!("{} imports", count)
+    }
+}
+
+/// Aggregated edges from this many imports or calls on render as thick Mermaid links.
+pub const THICK_EDGE_WEIGHT: usize = 5;
+
+/// DOT `penwidth` for an edge of `weight`: grows with its logarithm so heavily coupled
+/// pairs stand out without single edges vanishing, capped at 8.
+pub fn penwidth(weight: usize) -> String {
+    let width = 1.0 + (weight.max(1) as f64).log2();
+    format!("{:.1}", width.min(8.0))
+}
+
+/// Mermaid link for an edge of `weight`: thick (`==>`) from [`THICK_EDGE_WEIGHT`] on.
+pub fn mermaid_link(weight: usize) -> &'static str {
+    if weight >= THICK_EDGE_WEIGHT {
+        "==>"
+    } else {
+        "-->"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::model::{ExportFormat, Granularity};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_dir_overlay_rolls_up_file_edges() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let main = graph.add_file(root.join("src/main.rs"), "rust");
+        let find = graph.add_file(root.join("src/query/find.rs"), "rust");
+        let refs = graph.add_file(root.join("src/query/refs.rs"), "rust");
+        let build = graph.add_file(root.join("build.rs"), "rust");
+        graph.add_resolved_import(main, find, "crate::query::find");
+        graph.add_resolved_import(main, refs, "crate::query::refs");
+        graph.add_resolved_import(find, refs, "super::refs");
+        graph.add_resolved_import(build, main, "main");
+
+        let params = ExportParams {
+            format: ExportFormat::Dot,
+            granularity: Granularity::Dir,
+            root_filter: None,
+            symbol_filter: None,
+            depth: 1,
+            exclude_patterns: Vec::new(),
+            category_filter: Vec::new(),
+            project_root: root.clone(),
+            stdout: true,
+        };
+        let visible: HashSet<NodeIndex> = [main, find, refs, build].into_iter().collect();
+        let overlay = DirOverlay::build(&graph, &params, &visible);
+
+        assert_eq!(overlay.dirs, vec![".", "src", "src/query"]);
+        // src -> src/query has two file edges; the intra-directory edge is dropped.
+        let edges: Vec<((usize, usize), usize)> = overlay.edges.into_iter().collect();
+        assert_eq!(edges, vec![((0, 1), 1), ((1, 2), 2)]);
+    }
+
+    #[test]
+    fn test_edge_thickness() {
+        assert_eq!(penwidth(1), "1.0");
+        assert_eq!(penwidth(4), "3.0");
+        assert_eq!(penwidth(100_000), "8.0");
+        assert_eq!(mermaid_link(1), "-->");
+        assert_eq!(mermaid_link(THICK_EDGE_WEIGHT), "==>");
+    }
+}
@@ -1,10 +1,12 @@
 use std::collections::{HashMap, HashSet};
-use std::fmt::Write;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::Path;
 
 use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 
+use crate::export::dirs::{DirOverlay, import_label, penwidth};
+use crate::export::legend::CategoryLegend;
 use crate::export::model::{ExportParams, Granularity};
 use crate::graph::CodeGraph;
 use crate::graph::edge::EdgeKind;
@@ -37,11 +39,15 @@ pub fn sanitize_dot_id(s: &str) -> String {
 /// Get the DOT fillcolor for a symbol kind.
 fn symbol_fillcolor(kind: &SymbolKind) -> &'static str {
     match kind {
-        SymbolKind::Function | SymbolKind::ImplMethod => "#AED6F1",
-        SymbolKind::Struct | SymbolKind::Class => "#A9DFBF",
-        SymbolKind::Trait | SymbolKind::Interface => "#F9E79F",
+        SymbolKind::Function | SymbolKind::ImplMethod | SymbolKind::Rpc => "#AED6F1",
+        SymbolKind::Struct | SymbolKind::Class | SymbolKind::Message | SymbolKind::Table => {
+            "#A9DFBF"
+        }
+        SymbolKind::Trait | SymbolKind::Interface | SymbolKind::Service | SymbolKind::Endpoint => {
+            "#F9E79F"
+        }
         SymbolKind::Enum => "#F1948A",
-        SymbolKind::TypeAlias => "#D7BDE2",
+        SymbolKind::TypeAlias | SymbolKind::View => "#D7BDE2",
         SymbolKind::Const | SymbolKind::Static | SymbolKind::Variable => "#FAD7A0",
         SymbolKind::Macro => "#FDFEFE",
         _ => "#EAECEE",
@@ -55,6 +61,7 @@ fn kind_label(kind: &SymbolKind) -> &'static str {
         SymbolKind::Class => "class",
         SymbolKind::Interface => "interface",
         SymbolKind::TypeAlias => "type",
+        SymbolKind::Namespace => "namespace",
         SymbolKind::Enum => "enum",
         SymbolKind::Variable => "var",
         SymbolKind::Component => "component",
@@ -66,6 +73,12 @@ fn kind_label(kind: &SymbolKind) -> &'static str {
         SymbolKind::Const => "const",
         SymbolKind::Static => "static",
         SymbolKind::Macro => "macro",
+        SymbolKind::Message => "message",
+        SymbolKind::Service => "service",
+        SymbolKind::Rpc => "rpc",
+        SymbolKind::Table => "table",
+        SymbolKind::View => "view",
+        SymbolKind::Endpoint => "endpoint",
     }
 }
 
@@ -76,7 +89,7 @@ fn is_dependency_edge(kind: &EdgeKind) -> bool {
     matches!(
         kind,
         EdgeKind::ResolvedImport { .. }
-            | EdgeKind::Calls
+            | EdgeKind::Calls { .. }
             | EdgeKind::Extends
             | EdgeKind::Implements
             | EdgeKind::BarrelReExportAll
@@ -90,7 +103,7 @@ fn edge_style(kind: &EdgeKind) -> &'static str {
     match kind {
         EdgeKind::ResolvedImport { .. } => "style=solid",
         EdgeKind::ReExport { .. } | EdgeKind::BarrelReExportAll => "style=dashed",
-        EdgeKind::Calls => "style=solid color=blue",
+        EdgeKind::Calls { .. } => "style=solid color=blue",
         EdgeKind::Extends => "style=solid arrowhead=onormal",
         EdgeKind::Implements => "style=dashed arrowhead=onormal",
         EdgeKind::RustImport { .. } => "style=dotted",
@@ -98,38 +111,61 @@ fn edge_style(kind: &EdgeKind) -> &'static str {
     }
 }
 
-/// Render the code graph as DOT format.
+/// Write the code graph to `out` as DOT format.
 ///
-/// Supports symbol, file, and package granularity levels.
-/// Uses manual string generation for all levels (consistent approach, supports cluster subgraphs).
-pub fn render_dot(
+/// Supports symbol, file, package, and dir granularity levels.
+/// Uses manual text generation for all levels (consistent approach, supports cluster subgraphs).
+/// Lines are written as they are produced, so memory stays bounded by the graph itself
+/// rather than the size of the output.
+pub fn write_dot(
     graph: &CodeGraph,
     params: &ExportParams,
-    module_path_map: &HashMap<PathBuf, String>,
     visible_nodes: &HashSet<NodeIndex>,
-) -> String {
-    let mut out = String::new();
-    writeln!(out, "digraph code_graph {{").unwrap();
-    writeln!(out, "    rankdir=TB;").unwrap();
-    writeln!(out, "    node [style=filled fontname=monospace];").unwrap();
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    writeln!(out, "digraph code_graph {{")?;
+    writeln!(out, "    rankdir=TB;")?;
+    writeln!(out, "    node [style=filled fontname=monospace];")?;
 
+    let legend = CategoryLegend::new(graph, params.granularity, visible_nodes);
     match params.granularity {
-        Granularity::Symbol => render_dot_symbol(graph, module_path_map, visible_nodes, &mut out),
-        Granularity::File => render_dot_file(graph, params, visible_nodes, &mut out),
-        Granularity::Package => render_dot_package(graph, params, visible_nodes, &mut out),
+        Granularity::Symbol => render_dot_symbol(graph, visible_nodes, &legend, out)?,
+        Granularity::File => render_dot_file(graph, params, visible_nodes, &legend, out)?,
+        Granularity::Package => render_dot_package(graph, params, visible_nodes, &legend, out)?,
+        Granularity::Dir => render_dot_dir(graph, params, visible_nodes, out)?,
     }
+    render_dot_legend(&legend, out)?;
 
-    writeln!(out, "}}").unwrap();
-    out
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+/// Legend cluster mapping each category color to its name. Omitted without categories.
+fn render_dot_legend(legend: &CategoryLegend, out: &mut dyn Write) -> io::Result<()> {
+    if legend.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "    subgraph cluster_legend {{")?;
+    writeln!(out, "        label=\"Legend\";")?;
+    writeln!(out, "        style=dashed;")?;
+    for (i, (category, color)) in legend.entries.iter().enumerate() {
+        writeln!(
+            out,
+            "        legend_{} [label=\"{}\" shape=box fillcolor=\"{}\"];",
+            i, category, color
+        )?;
+    }
+    writeln!(out, "    }}")?;
+    Ok(())
 }
 
 /// Symbol-granularity DOT: one node per Symbol node in the graph.
 fn render_dot_symbol(
     graph: &CodeGraph,
-    module_path_map: &HashMap<PathBuf, String>,
     visible_nodes: &HashSet<NodeIndex>,
-    out: &mut String,
-) {
+    legend: &CategoryLegend,
+    out: &mut dyn Write,
+) -> io::Result<()> {
     // Emit symbol nodes.
     for idx in graph.graph.node_indices() {
         if !visible_nodes.contains(&idx) {
@@ -144,24 +180,24 @@ fn render_dot_symbol(
                     .edges_directed(idx, petgraph::Direction::Incoming)
                 {
                     if let EdgeKind::Contains = edge.weight()
-                        && let GraphNode::File(ref fi) = graph.graph[edge.source()]
-                        && let Some(mod_path) = module_path_map.get(&fi.path)
+                        && let Some(module) = graph.module_of_file(edge.source())
                     {
-                        annotation = format!("\\n{}", mod_path);
+                        annotation = format!("\\n{}", module.path);
                     }
                 }
                 annotation
             };
 
             let label = format!("{} ({}){}", s.name, kind_label(&s.kind), module_annotation);
-            let color = symbol_fillcolor(&s.kind);
+            let color = legend
+                .color(s.category.as_deref())
+                .unwrap_or_else(|| symbol_fillcolor(&s.kind));
             let node_id = format!("n{}", idx.index());
             writeln!(
                 out,
                 "    {} [label=\"{}\" fillcolor=\"{}\"];",
                 node_id, label, color
-            )
-            .unwrap();
+            )?;
         }
     }
 
@@ -186,8 +222,22 @@ fn render_dot_symbol(
             continue;
         }
         let style = edge_style(edge.weight());
-        writeln!(out, "    n{} -> n{} [{}];", src.index(), tgt.index(), style).unwrap();
+        let weight = edge.weight().weight();
+        if weight > 1 {
+            writeln!(
+                out,
+                "    n{} -> n{} [{} label=\"{}\" penwidth={}];",
+                src.index(),
+                tgt.index(),
+                style,
+                weight,
+                penwidth(weight)
+            )?;
+        } else {
+            writeln!(out, "    n{} -> n{} [{}];", src.index(), tgt.index(), style)?;
+        }
     }
+    Ok(())
 }
 
 /// File-granularity DOT: one node per File node, aggregated inter-file edges.
@@ -195,8 +245,9 @@ fn render_dot_file(
     graph: &CodeGraph,
     params: &ExportParams,
     visible_nodes: &HashSet<NodeIndex>,
-    out: &mut String,
-) {
+    legend: &CategoryLegend,
+    out: &mut dyn Write,
+) -> io::Result<()> {
     // Emit file nodes.
     for idx in graph.graph.node_indices() {
         if !visible_nodes.contains(&idx) {
@@ -211,10 +262,11 @@ fn render_dot_file(
             let node_id = format!("n{}", idx.index());
             writeln!(
                 out,
-                "    {} [label=\"{}\" fillcolor=\"#AED6F1\"];",
-                node_id, label
-            )
-            .unwrap();
+                "    {} [label=\"{}\" fillcolor=\"{}\"];",
+                node_id,
+                label,
+                legend.color(fi.category.as_deref()).unwrap_or("#AED6F1")
+            )?;
         }
     }
 
@@ -238,24 +290,21 @@ fn render_dot_file(
         if !is_dependency_edge(edge.weight()) {
             continue;
         }
-        *edge_counts.entry((src, tgt)).or_insert(0) += 1;
+        *edge_counts.entry((src, tgt)).or_insert(0) += edge.weight().weight();
     }
 
     for ((src, tgt), count) in &edge_counts {
-        let label = if *count == 1 {
-            "1 import".to_string()
-        } else {
-            format!("{} imports", count)
-        };
+        let label = import_label(*count);
         writeln!(
             out,
-            "    n{} -> n{} [label=\"{}\"];",
+            "    n{} -> n{} [label=\"{}\" penwidth={}];",
             src.index(),
             tgt.index(),
-            label
-        )
-        .unwrap();
+            label,
+            penwidth(*count)
+        )?;
     }
+    Ok(())
 }
 
 /// Package-granularity DOT: subgraph cluster_* blocks per package, inter-package edges only.
@@ -263,8 +312,9 @@ fn render_dot_package(
     graph: &CodeGraph,
     params: &ExportParams,
     visible_nodes: &HashSet<NodeIndex>,
-    out: &mut String,
-) {
+    legend: &CategoryLegend,
+    out: &mut dyn Write,
+) -> io::Result<()> {
     // Determine package membership for visible file nodes.
     let package_map = build_package_map(graph, params, visible_nodes);
 
@@ -280,10 +330,10 @@ fn render_dot_package(
     // Emit subgraph cluster blocks.
     for (pkg_name, file_nodes) in &packages {
         let cluster_id = sanitize_dot_id(pkg_name);
-        writeln!(out, "    subgraph cluster_{} {{", cluster_id).unwrap();
-        writeln!(out, "        label=\"{}\";", pkg_name).unwrap();
-        writeln!(out, "        color=lightgrey;").unwrap();
-        writeln!(out, "        style=filled;").unwrap();
+        writeln!(out, "    subgraph cluster_{} {{", cluster_id)?;
+        writeln!(out, "        label=\"{}\";", pkg_name)?;
+        writeln!(out, "        color=lightgrey;")?;
+        writeln!(out, "        style=filled;")?;
         for &node_idx in file_nodes {
             if let GraphNode::File(ref fi) = graph.graph[node_idx] {
                 let rel_path = fi
@@ -293,14 +343,14 @@ fn render_dot_package(
                 let label = rel_path.display().to_string();
                 writeln!(
                     out,
-                    "        n{} [label=\"{}\" fillcolor=\"#AED6F1\"];",
+                    "        n{} [label=\"{}\" fillcolor=\"{}\"];",
                     node_idx.index(),
-                    label
-                )
-                .unwrap();
+                    label,
+                    legend.color(fi.category.as_deref()).unwrap_or("#AED6F1")
+                )?;
             }
         }
-        writeln!(out, "    }}").unwrap();
+        writeln!(out, "    }}")?;
     }
 
     // Emit inter-package edges only (aggregate by package pair).
@@ -340,7 +390,7 @@ fn render_dot_package(
         if src_pkg == tgt_pkg {
             continue; // intra-package edge: skip
         }
-        *inter_pkg_edges.entry((src_pkg, tgt_pkg)).or_insert(0) += 1;
+        *inter_pkg_edges.entry((src_pkg, tgt_pkg)).or_insert(0) += edge.weight().weight();
     }
 
     for ((src_pkg, tgt_pkg), count) in &inter_pkg_edges {
@@ -352,27 +402,49 @@ fn render_dot_package(
             Some(n) => n,
             None => continue,
         };
-        let label = if *count == 1 {
-            "1 import".to_string()
-        } else {
-            format!("{} imports", count)
-        };
+        let label = import_label(*count);
         writeln!(
             out,
-            "    n{} -> n{} [label=\"{}\"];",
+            "    n{} -> n{} [label=\"{}\" penwidth={}];",
             src_node.index(),
             tgt_node.index(),
-            label
-        )
-        .unwrap();
+            label,
+            penwidth(*count)
+        )?;
     }
+    Ok(())
 }
 
-/// Build a map from file NodeIndex to package name for all visible file nodes.
-///
-/// For Rust projects: uses FileInfo.crate_name if available.
-/// For non-Rust or missing crate_name: groups by top-level directory under src/.
-/// Files not under src/ go into a "root" package.
+/// Dir-granularity DOT: one node per directory, inter-directory edges aggregated.
+fn render_dot_dir(
+    graph: &CodeGraph,
+    params: &ExportParams,
+    visible_nodes: &HashSet<NodeIndex>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let overlay = DirOverlay::build(graph, params, visible_nodes);
+    for (i, dir) in overlay.dirs.iter().enumerate() {
+        writeln!(
+            out,
+            "    d{} [label=\"{}/\" shape=folder fillcolor=\"#AED6F1\"];",
+            i, dir
+        )?;
+    }
+    for ((src, tgt), count) in &overlay.edges {
+        writeln!(
+            out,
+            "    d{} -> d{} [label=\"{}\" penwidth={}];",
+            src,
+            tgt,
+            import_label(*count),
+            penwidth(*count)
+        )?;
+    }
+    Ok(())
+}
+
+/// Build a map from file NodeIndex to package name for all visible file nodes, as named
+/// by [`package_of_file`].
 pub fn build_package_map(
     graph: &CodeGraph,
     params: &ExportParams,
@@ -384,35 +456,53 @@ pub fn build_package_map(
         if !visible_nodes.contains(&idx) {
             continue;
         }
-        if let GraphNode::File(ref fi) = graph.graph[idx] {
-            let pkg_name = if let Some(ref crate_name) = fi.crate_name {
-                // Rust file with known crate name.
-                crate_name.clone()
-            } else {
-                // Group by top-level directory relative to project root.
-                let rel = fi
-                    .path
-                    .strip_prefix(&params.project_root)
-                    .unwrap_or(&fi.path);
-
-                // Try to get the first path component under src/.
-                let mut components = rel.components();
-                let first = components
-                    .next()
-                    .map(|c| c.as_os_str().to_string_lossy().into_owned());
-                let second = components
-                    .next()
-                    .map(|c| c.as_os_str().to_string_lossy().into_owned());
-
-                match (first.as_deref(), second.as_deref()) {
-                    (Some("src"), Some(dir)) => dir.trim_end_matches(".rs").to_string(),
-                    (Some(dir), _) if dir != "src" => dir.to_string(),
-                    _ => "root".to_string(),
-                }
-            };
+        if let Some(pkg_name) = package_of_file(graph, idx, &params.project_root) {
             map.insert(idx, pkg_name);
         }
     }
 
     map
 }
+
+/// Package of the file at `idx`, or `None` for non-file nodes.
+///
+/// For Rust projects: groups by top-level module (`my_crate::parser`) when the file is in
+/// a module tree, otherwise by FileInfo.crate_name if available.
+/// For non-Rust or missing crate_name: groups by top-level directory under src/.
+/// Files not under src/ go into a "root" package.
+pub fn package_of_file(graph: &CodeGraph, idx: NodeIndex, project_root: &Path) -> Option<String> {
+    let GraphNode::File(ref fi) = graph.graph[idx] else {
+        return None;
+    };
+    let pkg_name = if let Some(module) = graph.module_of_file(idx) {
+        // Rust file in a module tree: its top-level module (`my_crate::parser`), or
+        // the crate itself for the crate root.
+        let mut segments = module.path.splitn(3, "::");
+        match (segments.next(), segments.next()) {
+            (Some(krate), Some(top)) => format!("{krate}::{top}"),
+            _ => module.path.clone(),
+        }
+    } else if let Some(ref crate_name) = fi.crate_name {
+        // Rust file with known crate name.
+        crate_name.clone()
+    } else {
+        // Group by top-level directory relative to project root.
+        let rel = fi.path.strip_prefix(project_root).unwrap_or(&fi.path);
+
+        // Try to get the first path component under src/.
+        let mut components = rel.components();
+        let first = components
+            .next()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned());
+        let second = components
+            .next()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned());
+
+        match (first.as_deref(), second.as_deref()) {
+            (Some("src"), Some(dir)) => dir.trim_end_matches(".rs").to_string(),
+            (Some(dir), _) if dir != "src" => dir.to_string(),
+            _ => "root".to_string(),
+        }
+    };
+    Some(pkg_name)
+}
@@ -0,0 +1,104 @@
+use std::collections::{BTreeSet, HashSet};
+
+use petgraph::stable_graph::NodeIndex;
+
+use crate::export::model::Granularity;
+use crate::graph::CodeGraph;
+use crate::graph::node::GraphNode;
+
+/// Fill colors assigned to categories in legend order.
+const PALETTE: &[&str] = &[
+    "#F5B7B1", "#A3E4D7", "#F9E79F", "#D2B4DE", "#AED6F1", "#FAD7A0", "#ABEBC6", "#E6B0AA",
+];
+
+/// Color → category mapping for the nodes of one export.
+///
+/// Colors are assigned to the categories of the rendered nodes in name order, so every
+/// category in a diagram has its own color (up to the palette size, after which colors
+/// repeat) and the legend lists exactly the categories that appear.
+pub struct CategoryLegend {
+    /// `(category, fill color)` in name order.
+    pub entries: Vec<(String, &'static str)>,
+}
+
+impl CategoryLegend {
+    /// Collect the categories of the nodes rendered at `granularity`: symbol nodes for
+    /// symbol granularity, file nodes otherwise.
+    pub fn new(
+        graph: &CodeGraph,
+        granularity: Granularity,
+        visible_nodes: &HashSet<NodeIndex>,
+    ) -> Self {
+        let names: BTreeSet<&str> = visible_nodes
+            .iter()
+            .filter_map(|&idx| match (&graph.graph[idx], granularity) {
+                (GraphNode::Symbol(info), Granularity::Symbol) => info.category.as_deref(),
+                (GraphNode::File(fi), Granularity::File | Granularity::Package) => {
+                    fi.category.as_deref()
+                }
+                _ => None,
+            })
+            .collect();
+        let entries = names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| (name.to_string(), PALETTE[i % PALETTE.len()]))
+            .collect();
+        Self { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Position of `category` in the legend.
+    pub fn index_of(&self, category: &str) -> Option<usize> {
+        self.entries.iter().position(|(name, _)| name == category)
+    }
+
+    /// Fill color of `category`, if it appears in the legend.
+    pub fn color(&self, category: Option<&str>) -> Option<&'static str> {
+        self.index_of(category?).map(|i| self.entries[i].1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_legend_lists_rendered_categories() {
+        let mut graph = CodeGraph::new();
+        let page = graph.add_file(PathBuf::from("/p/pages/home.ts"), "typescript");
+        let hooks = graph.add_file(PathBuf::from("/p/hooks.ts"), "typescript");
+        let plain = graph.add_file(PathBuf::from("/p/util.ts"), "typescript");
+        let sym = graph.add_symbol(
+            hooks,
+            SymbolInfo {
+                name: "useAuth".into(),
+                kind: SymbolKind::Function,
+                category: Some("hooks".into()),
+                ..Default::default()
+            },
+        );
+        if let GraphNode::File(fi) = &mut graph.graph[page] {
+            fi.category = Some("pages".into());
+        }
+        if let GraphNode::File(fi) = &mut graph.graph[hooks] {
+            fi.category = Some("api".into());
+        }
+        let visible: HashSet<NodeIndex> = [page, hooks, plain, sym].into_iter().collect();
+
+        let files = CategoryLegend::new(&graph, Granularity::File, &visible);
+        let names: Vec<&str> = files.entries.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["api", "pages"]);
+        assert_ne!(files.color(Some("api")), files.color(Some("pages")));
+        assert_eq!(files.color(None), None);
+
+        let symbols = CategoryLegend::new(&graph, Granularity::Symbol, &visible);
+        assert_eq!(symbols.entries.len(), 1);
+        assert_eq!(symbols.index_of("hooks"), Some(0));
+    }
+}
@@ -1,12 +1,13 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fmt::Write;
-use std::path::PathBuf;
+use std::io::{self, Write};
 
 use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 
+use crate::export::dirs::{DirOverlay, import_label, mermaid_link};
 use crate::export::dot::build_package_map;
+use crate::export::legend::CategoryLegend;
 use crate::export::model::{ExportParams, Granularity};
 use crate::graph::CodeGraph;
 use crate::graph::edge::EdgeKind;
@@ -17,7 +18,7 @@ fn is_dependency_edge(kind: &EdgeKind) -> bool {
     matches!(
         kind,
         EdgeKind::ResolvedImport { .. }
-            | EdgeKind::Calls
+            | EdgeKind::Calls { .. }
             | EdgeKind::Extends
             | EdgeKind::Implements
             | EdgeKind::BarrelReExportAll
@@ -33,6 +34,7 @@ fn kind_label(kind: &SymbolKind) -> &'static str {
         SymbolKind::Class => "class",
         SymbolKind::Interface => "interface",
         SymbolKind::TypeAlias => "type",
+        SymbolKind::Namespace => "namespace",
         SymbolKind::Enum => "enum",
         SymbolKind::Variable => "var",
         SymbolKind::Component => "component",
@@ -43,6 +45,12 @@ fn kind_label(kind: &SymbolKind) -> &'static str {
         SymbolKind::Const => "const",
         SymbolKind::Static => "static",
         SymbolKind::Macro => "macro",
+        SymbolKind::Message => "message",
+        SymbolKind::Service => "service",
+        SymbolKind::Rpc => "rpc",
+        SymbolKind::Table => "table",
+        SymbolKind::View => "view",
+        SymbolKind::Endpoint => "endpoint",
     }
 }
 
@@ -55,36 +63,69 @@ fn escape_mermaid_label(s: &str) -> String {
         .replace('}', "&#125;")
 }
 
-/// Render the code graph as Mermaid flowchart format.
+/// Write the code graph to `out` as Mermaid flowchart format.
 ///
-/// Supports symbol, file, and package granularity levels.
-pub fn render_mermaid(
+/// Supports symbol, file, package, and dir granularity levels. Like [`write_dot`], lines
+/// are written as they are produced.
+///
+/// [`write_dot`]: crate::export::dot::write_dot
+pub fn write_mermaid(
     graph: &CodeGraph,
     params: &ExportParams,
-    module_path_map: &HashMap<PathBuf, String>,
     visible_nodes: &HashSet<NodeIndex>,
-) -> String {
-    let mut out = String::new();
-    writeln!(out, "flowchart TB").unwrap();
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    writeln!(out, "flowchart TB")?;
 
+    let legend = CategoryLegend::new(graph, params.granularity, visible_nodes);
     match params.granularity {
-        Granularity::Symbol => {
-            render_mermaid_symbol(graph, module_path_map, visible_nodes, &mut out)
-        }
-        Granularity::File => render_mermaid_file(graph, params, visible_nodes, &mut out),
-        Granularity::Package => render_mermaid_package(graph, params, visible_nodes, &mut out),
+        Granularity::Symbol => render_mermaid_symbol(graph, visible_nodes, &legend, out)?,
+        Granularity::File => render_mermaid_file(graph, params, visible_nodes, &legend, out)?,
+        Granularity::Package => render_mermaid_package(graph, params, visible_nodes, &legend, out)?,
+        Granularity::Dir => render_mermaid_dir(graph, params, visible_nodes, out)?,
+    }
+    render_mermaid_legend(&legend, out)?;
+    Ok(())
+}
+
+/// `:::catN` class suffix styling a node of `category`, or an empty string.
+fn category_class(legend: &CategoryLegend, category: Option<&str>) -> String {
+    match category.and_then(|c| legend.index_of(c)) {
+        Some(i) => format!(":::cat{}", i),
+        None => String::new(),
     }
+}
 
-    out
+/// Category class definitions and a legend subgraph mapping each color to its name.
+/// Omitted without categories.
+fn render_mermaid_legend(legend: &CategoryLegend, out: &mut dyn Write) -> io::Result<()> {
+    if legend.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "    subgraph legend[\"Legend\"]")?;
+    for (i, (category, _)) in legend.entries.iter().enumerate() {
+        writeln!(
+            out,
+            "        legend_{}[\"{}\"]:::cat{}",
+            i,
+            escape_mermaid_label(category),
+            i
+        )?;
+    }
+    writeln!(out, "    end")?;
+    for (i, (_, color)) in legend.entries.iter().enumerate() {
+        writeln!(out, "    classDef cat{} fill:{}", i, color)?;
+    }
+    Ok(())
 }
 
 /// Symbol-granularity Mermaid: one node per Symbol, shaped by kind.
 fn render_mermaid_symbol(
     graph: &CodeGraph,
-    module_path_map: &HashMap<PathBuf, String>,
     visible_nodes: &HashSet<NodeIndex>,
-    out: &mut String,
-) {
+    legend: &CategoryLegend,
+    out: &mut dyn Write,
+) -> io::Result<()> {
     for idx in graph.graph.node_indices() {
         if !visible_nodes.contains(&idx) {
             continue;
@@ -98,10 +139,9 @@ fn render_mermaid_symbol(
                     .edges_directed(idx, petgraph::Direction::Incoming)
                 {
                     if let EdgeKind::Contains = edge.weight()
-                        && let GraphNode::File(ref fi) = graph.graph[edge.source()]
-                        && let Some(mod_path) = module_path_map.get(&fi.path)
+                        && let Some(module) = graph.module_of_file(edge.source())
                     {
-                        annotation = format!(" ({})", mod_path);
+                        annotation = format!(" ({})", module.path);
                     }
                 }
                 annotation
@@ -138,7 +178,8 @@ fn render_mermaid_symbol(
                     format!("    {}[\"{}\"]", node_id, label)
                 }
             };
-            writeln!(out, "{}", node_def).unwrap();
+            let class = category_class(legend, s.category.as_deref());
+            writeln!(out, "{}{}", node_def, class)?;
         }
     }
 
@@ -166,11 +207,12 @@ fn render_mermaid_symbol(
             EdgeKind::ReExport { .. } | EdgeKind::BarrelReExportAll | EdgeKind::Implements => {
                 "-.->".to_string()
             }
-            _ => "-->".to_string(),
+            kind => mermaid_link(kind.weight()).to_string(),
         };
 
-        writeln!(out, "    n{} {} n{}", src.index(), arrow, tgt.index()).unwrap();
+        writeln!(out, "    n{} {} n{}", src.index(), arrow, tgt.index())?;
     }
+    Ok(())
 }
 
 /// File-granularity Mermaid: one node per file, aggregated edges with counts.
@@ -178,8 +220,9 @@ fn render_mermaid_file(
     graph: &CodeGraph,
     params: &ExportParams,
     visible_nodes: &HashSet<NodeIndex>,
-    out: &mut String,
-) {
+    legend: &CategoryLegend,
+    out: &mut dyn Write,
+) -> io::Result<()> {
     // Emit file nodes.
     for idx in graph.graph.node_indices() {
         if !visible_nodes.contains(&idx) {
@@ -191,7 +234,8 @@ fn render_mermaid_file(
                 .strip_prefix(&params.project_root)
                 .unwrap_or(&fi.path);
             let label = escape_mermaid_label(&rel_path.display().to_string());
-            writeln!(out, "    n{}[\"{}\"]", idx.index(), label).unwrap();
+            let class = category_class(legend, fi.category.as_deref());
+            writeln!(out, "    n{}[\"{}\"]{}", idx.index(), label, class)?;
         }
     }
 
@@ -215,24 +259,45 @@ fn render_mermaid_file(
         if !is_dependency_edge(edge.weight()) {
             continue;
         }
-        *edge_counts.entry((src, tgt)).or_insert(0) += 1;
+        *edge_counts.entry((src, tgt)).or_insert(0) += edge.weight().weight();
     }
 
     for ((src, tgt), count) in &edge_counts {
-        let label = if *count == 1 {
-            "1 import".to_string()
-        } else {
-            format!("{} imports", count)
-        };
+        let label = import_label(*count);
         writeln!(
             out,
-            "    n{} -->|\"{}\"|n{}",
+            "    n{} {}|\"{}\"|n{}",
             src.index(),
+            mermaid_link(*count),
             label,
             tgt.index()
-        )
-        .unwrap();
+        )?;
     }
+    Ok(())
+}
+
+/// Dir-granularity Mermaid: one node per directory, inter-directory edges aggregated.
+fn render_mermaid_dir(
+    graph: &CodeGraph,
+    params: &ExportParams,
+    visible_nodes: &HashSet<NodeIndex>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let overlay = DirOverlay::build(graph, params, visible_nodes);
+    for (i, dir) in overlay.dirs.iter().enumerate() {
+        writeln!(out, "    d{}[\"{}/\"]", i, escape_mermaid_label(dir))?;
+    }
+    for ((src, tgt), count) in &overlay.edges {
+        writeln!(
+            out,
+            "    d{} {}|\"{}\"|d{}",
+            src,
+            mermaid_link(*count),
+            import_label(*count),
+            tgt
+        )?;
+    }
+    Ok(())
 }
 
 /// Package-granularity Mermaid: subgraph blocks per package, inter-package edges only.
@@ -240,8 +305,9 @@ fn render_mermaid_package(
     graph: &CodeGraph,
     params: &ExportParams,
     visible_nodes: &HashSet<NodeIndex>,
-    out: &mut String,
-) {
+    legend: &CategoryLegend,
+    out: &mut dyn Write,
+) -> io::Result<()> {
     let package_map = build_package_map(graph, params, visible_nodes);
 
     // Group file nodes by package.
@@ -271,8 +337,7 @@ fn render_mermaid_package(
             "    subgraph {}[\"{}\"]",
             subgraph_id,
             escape_mermaid_label(pkg_name)
-        )
-        .unwrap();
+        )?;
         for &node_idx in file_nodes {
             if let GraphNode::File(ref fi) = graph.graph[node_idx] {
                 let rel_path = fi
@@ -280,10 +345,11 @@ fn render_mermaid_package(
                     .strip_prefix(&params.project_root)
                     .unwrap_or(&fi.path);
                 let label = escape_mermaid_label(&rel_path.display().to_string());
-                writeln!(out, "        n{}[\"{}\"]", node_idx.index(), label).unwrap();
+                let class = category_class(legend, fi.category.as_deref());
+                writeln!(out, "        n{}[\"{}\"]{}", node_idx.index(), label, class)?;
             }
         }
-        writeln!(out, "    end").unwrap();
+        writeln!(out, "    end")?;
     }
 
     // Inter-package edges only, aggregated by package pair.
@@ -322,7 +388,7 @@ fn render_mermaid_package(
         if src_pkg == tgt_pkg {
             continue;
         }
-        *inter_pkg_edges.entry((src_pkg, tgt_pkg)).or_insert(0) += 1;
+        *inter_pkg_edges.entry((src_pkg, tgt_pkg)).or_insert(0) += edge.weight().weight();
     }
 
     for ((src_pkg, tgt_pkg), count) in &inter_pkg_edges {
@@ -334,18 +400,15 @@ fn render_mermaid_package(
             Some(n) => n,
             None => continue,
         };
-        let label = if *count == 1 {
-            "1 import".to_string()
-        } else {
-            format!("{} imports", count)
-        };
+        let label = import_label(*count);
         writeln!(
             out,
-            "    n{} -->|\"{}\"|n{}",
+            "    n{} {}|\"{}\"|n{}",
             src_node.index(),
+            mermaid_link(*count),
             label,
             tgt_node.index()
-        )
-        .unwrap();
+        )?;
     }
+    Ok(())
 }
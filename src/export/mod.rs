@@ -1,33 +1,33 @@
+pub mod dirs;
 pub mod dot;
+pub mod legend;
 pub mod mermaid;
 pub mod model;
 
-use std::collections::{HashMap, HashSet};
-use std::path::{Path, PathBuf};
+use std::collections::HashSet;
 
 use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 
 use crate::graph::CodeGraph;
 use crate::graph::node::GraphNode;
-use crate::resolver::cargo_workspace::discover_rust_workspace_members;
-use crate::resolver::rust_mod_tree::build_mod_tree;
 
 use model::{ExportFormat, ExportParams, ExportResult, Granularity};
 
-/// Export the code graph to DOT or Mermaid format.
+/// Export the code graph to DOT or Mermaid format, writing it to `out`.
 ///
 /// Steps:
-/// 1. Build a module path map (file → Rust module path) for Rust projects.
+/// 1. Rust symbols are annotated with their file's `Module` node.
 /// 2. Apply filters: exclusions, --root path prefix, --symbol neighborhood BFS.
 /// 3. Count visible nodes/edges for the chosen granularity.
 /// 4. Check scale guards and emit warnings if thresholds are exceeded.
-/// 5. Dispatch to the appropriate renderer.
-/// 6. Return ExportResult with content, counts, and warnings.
-pub fn export_graph(graph: &CodeGraph, params: &ExportParams) -> anyhow::Result<ExportResult> {
-    // Step 1: Build module path map from Rust workspace members.
-    let module_path_map = build_module_path_map(graph, &params.project_root);
-
+/// 5. Dispatch to the appropriate renderer, which streams to `out`.
+/// 6. Return ExportResult with counts and warnings.
+pub fn export_graph(
+    graph: &CodeGraph,
+    params: &ExportParams,
+    out: &mut dyn std::io::Write,
+) -> anyhow::Result<ExportResult> {
     // Step 2: Build the set of visible nodes (applying all filters).
     let visible_nodes = build_visible_nodes(graph, params)?;
 
@@ -58,15 +58,13 @@ pub fn export_graph(graph: &CodeGraph, params: &ExportParams) -> anyhow::Result<
     }
 
     // Step 5: Dispatch to renderer.
-    let content = match params.format {
-        ExportFormat::Dot => dot::render_dot(graph, params, &module_path_map, &visible_nodes),
-        ExportFormat::Mermaid => {
-            mermaid::render_mermaid(graph, params, &module_path_map, &visible_nodes)
-        }
-    };
+    match params.format {
+        ExportFormat::Dot => dot::write_dot(graph, params, &visible_nodes, out)?,
+        ExportFormat::Mermaid => mermaid::write_mermaid(graph, params, &visible_nodes, out)?,
+    }
+    out.flush()?;
 
     Ok(ExportResult {
-        content,
         node_count,
         edge_count,
         warnings,
@@ -77,41 +75,13 @@ pub fn export_graph(graph: &CodeGraph, params: &ExportParams) -> anyhow::Result<
 // Internal helpers
 // ---------------------------------------------------------------------------
 
-/// Build a map from file path → Rust module path string by walking workspace crate roots.
-///
-/// Used to annotate Rust file/symbol nodes with their canonical module path (e.g. `crate::parser`).
-fn build_module_path_map(graph: &CodeGraph, project_root: &Path) -> HashMap<PathBuf, String> {
-    let mut map: HashMap<PathBuf, String> = HashMap::new();
-
-    let workspace_members = discover_rust_workspace_members(project_root);
-    if workspace_members.is_empty() {
-        return map;
-    }
-
-    for (crate_name, crate_root) in &workspace_members {
-        let tree = build_mod_tree(crate_name, crate_root);
-        // reverse_map: PathBuf (file) → String (module path).
-        for (file_path, mod_path) in &tree.reverse_map {
-            map.insert(file_path.clone(), mod_path.clone());
-        }
-        // Also fill from mod_map in case reverse_map missed any entries.
-        for (_mod_path, file_path) in &tree.mod_map {
-            map.entry(file_path.clone())
-                .or_insert_with(|| _mod_path.clone());
-        }
-    }
-
-    // Suppress unused graph variable warning (graph is passed for potential future use).
-    let _ = graph;
-    map
-}
-
 /// Determine which nodes are visible given the current filter params.
 ///
 /// Order of operations (per research recommendation):
 /// 1. Build excluded set from --exclude glob patterns.
 /// 2. Apply --root path prefix filter.
 /// 3. Apply --symbol BFS neighborhood filter.
+/// 4. Apply --category filter.
 ///
 /// All filters are applied to file nodes; symbol/package granularity inherits
 /// visibility from their parent file nodes.
@@ -172,6 +142,21 @@ fn build_visible_nodes(
         }
     }
 
+    // Apply --category: files stay when they or one of their symbols are in a category.
+    if !params.category_filter.is_empty() {
+        visible.retain(|&idx| match &graph.graph[idx] {
+            GraphNode::File(_) => params
+                .category_filter
+                .iter()
+                .any(|c| crate::query::categories::file_in_category(graph, idx, c)),
+            GraphNode::Symbol(info) => info
+                .category
+                .as_ref()
+                .is_some_and(|c| params.category_filter.contains(c)),
+            _ => false,
+        });
+    }
+
     Ok(visible)
 }
 
@@ -258,7 +243,7 @@ fn apply_symbol_bfs_filter(
                 if matches!(
                     edge.weight(),
                     crate::graph::edge::EdgeKind::ResolvedImport { .. }
-                        | crate::graph::edge::EdgeKind::Calls
+                        | crate::graph::edge::EdgeKind::Calls { .. }
                         | crate::graph::edge::EdgeKind::Extends
                         | crate::graph::edge::EdgeKind::Implements
                         | crate::graph::edge::EdgeKind::RustImport { .. }
@@ -384,6 +369,11 @@ fn count_nodes_edges(
 
             (node_count, inter_pkg_pairs.len())
         }
+
+        Granularity::Dir => {
+            let overlay = dirs::DirOverlay::build(graph, params, visible_nodes);
+            (overlay.dirs.len(), overlay.edges.len())
+        }
     }
 }
 
@@ -392,7 +382,7 @@ fn is_dependency_edge_for_count(kind: &crate::graph::edge::EdgeKind) -> bool {
     matches!(
         kind,
         crate::graph::edge::EdgeKind::ResolvedImport { .. }
-            | crate::graph::edge::EdgeKind::Calls
+            | crate::graph::edge::EdgeKind::Calls { .. }
             | crate::graph::edge::EdgeKind::Extends
             | crate::graph::edge::EdgeKind::Implements
             | crate::graph::edge::EdgeKind::BarrelReExportAll
@@ -400,3 +390,87 @@ fn is_dependency_edge_for_count(kind: &crate::graph::edge::EdgeKind) -> bool {
             | crate::graph::edge::EdgeKind::RustImport { .. }
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::path::PathBuf;
+
+    fn params(format: ExportFormat, granularity: Granularity) -> ExportParams {
+        ExportParams {
+            format,
+            granularity,
+            root_filter: None,
+            symbol_filter: None,
+            depth: 1,
+            exclude_patterns: Vec::new(),
+            category_filter: Vec::new(),
+            project_root: PathBuf::from("/proj"),
+            stdout: true,
+        }
+    }
+
+    /// A sink that accepts `limit` bytes, then fails.
+    struct Limited {
+        written: usize,
+        limit: usize,
+    }
+
+    impl io::Write for Limited {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.written + buf.len() > self.limit {
+                return Err(io::Error::other("sink full"));
+            }
+            self.written += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_export_streams_to_writer() {
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(PathBuf::from("/proj/src/app.ts"), "typescript");
+        let db = graph.add_file(PathBuf::from("/proj/src/db.ts"), "typescript");
+        graph.add_resolved_import(app, db, "./db");
+
+        let mut out: Vec<u8> = Vec::new();
+        let result = export_graph(
+            &graph,
+            &params(ExportFormat::Dot, Granularity::File),
+            &mut out,
+        )
+        .unwrap();
+        let dot = String::from_utf8(out).unwrap();
+        assert!(dot.starts_with("digraph code_graph {"), "{dot}");
+        assert!(dot.contains("[label=\"src/db.ts\""), "{dot}");
+        assert_eq!((result.node_count, result.edge_count), (2, 1));
+
+        let mut out: Vec<u8> = Vec::new();
+        export_graph(
+            &graph,
+            &params(ExportFormat::Mermaid, Granularity::File),
+            &mut out,
+        )
+        .unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("flowchart TB"));
+
+        // A failing sink surfaces as an error instead of a truncated success.
+        let mut sink = Limited {
+            written: 0,
+            limit: 40,
+        };
+        assert!(
+            export_graph(
+                &graph,
+                &params(ExportFormat::Dot, Granularity::File),
+                &mut sink
+            )
+            .is_err()
+        );
+    }
+}
@@ -31,13 +31,15 @@ pub enum Granularity {
     File,
     /// One node per package/crate. Best for high-level architecture overview.
     Package,
+    /// One node per directory, with file edges rolled up between folders.
+    Dir,
 }
 
 /// Parameters controlling a graph export operation.
 pub struct ExportParams {
     /// Output format: DOT or Mermaid.
     pub format: ExportFormat,
-    /// Granularity level: symbol, file, or package.
+    /// Granularity level: symbol, file, package, or dir.
     pub granularity: Granularity,
     /// Restrict export to nodes whose file paths start with this prefix.
     pub root_filter: Option<PathBuf>,
@@ -47,6 +49,8 @@ pub struct ExportParams {
     pub depth: usize,
     /// Exclude files/symbols matching these glob patterns.
     pub exclude_patterns: Vec<String>,
+    /// Keep only files/symbols in any of these categories (`[[category]]` rules).
+    pub category_filter: Vec<String>,
     /// Absolute path to the project root (used for relative path labels and workspace discovery).
     pub project_root: PathBuf,
     /// Write output to stdout instead of a file (read by caller, not export_graph).
@@ -57,9 +61,9 @@ pub struct ExportParams {
 }
 
 /// Result of a graph export operation.
+///
+/// The rendered DOT or Mermaid text itself goes to the writer passed to `export_graph`.
 pub struct ExportResult {
-    /// The rendered graph content (DOT or Mermaid text).
-    pub content: String,
     /// Number of nodes in the exported graph (at chosen granularity).
     pub node_count: usize,
     /// Number of edges in the exported graph.
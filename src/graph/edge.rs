@@ -16,9 +16,31 @@ pub enum EdgeKind {
     // Phase 2 additions:
     /// Resolved import edge: importing file -> resolved target file.
     /// specifier is the original raw import string from source.
-    ResolvedImport { specifier: String },
+    /// `is_type_only` marks TypeScript `import type` edges, which are erased at compile
+    /// time and never create a runtime dependency.
+    /// `count` is the number of identical imports (same specifier) collapsed into the edge.
+    /// `line` and `col` are the 1-based position of the first of those import statements,
+    /// 0 when unknown.
+    ResolvedImport {
+        specifier: String,
+        #[serde(default)]
+        is_type_only: bool,
+        #[serde(default)]
+        count: usize,
+        #[serde(default)]
+        line: usize,
+        #[serde(default)]
+        col: usize,
+    },
     /// Symbol -> symbol: direct function/method call (foo() or obj.method()).
-    Calls,
+    /// All calls between the same pair collapse into one edge: `count` calls at the sorted,
+    /// distinct 1-based `lines` of the caller's file (empty when the sites are unknown).
+    Calls {
+        #[serde(default)]
+        count: usize,
+        #[serde(default)]
+        lines: Vec<usize>,
+    },
     /// Symbol -> symbol: class extends class, or interface extends interface.
     Extends,
     /// Symbol -> symbol: class implements interface.
@@ -50,4 +72,85 @@ pub enum EdgeKind {
     /// Symbol has a decorator/attribute. `name` is the decorator name.
     /// Used for graph-level "has any decorator" traversal queries.
     HasDecorator { name: String },
+
+    // SQL additions:
+    /// File -> Symbol: code references a SQL table/view by name (a `.sql` statement or a
+    /// SQL string literal in TS/JS/Rust).
+    ReferencesTable,
+
+    // Documentation additions:
+    /// Symbol/File -> File: a Markdown doc mentions the symbol in a code span or fenced
+    /// code block, or links to the file.
+    DocumentedBy,
+
+    // OpenAPI additions:
+    /// Symbol -> Symbol: an OpenAPI endpoint is implemented by a TS/JS route handler.
+    HandledBy,
+
+    // Import binding additions:
+    /// File -> Symbol: a named import (`import { Foo } from './x'`, `from x import Foo`)
+    /// binds the top-level symbol it names in the imported file.
+    /// `line` and `col` are the 1-based position of the binding import statement (the
+    /// first one, when several bind the symbol), 0 when unknown.
+    ImportsSymbol {
+        #[serde(default)]
+        line: usize,
+        #[serde(default)]
+        col: usize,
+    },
+
+    // Test additions:
+    /// File -> File/Symbol: a test file imports the file, or binds the symbol, it tests.
+    Tests,
+
+    // JSX additions:
+    /// Symbol/File -> Symbol: a component renders another as a JSX element (`<Button />`).
+    /// The source is the enclosing top-level symbol, or the file for module-level JSX.
+    Renders,
+
+    // Alias additions:
+    /// File -> Symbol: the file re-exports the symbol under another name
+    /// (`export { Foo as Bar } from './x'`, `pub use x::Foo as Bar;`).
+    ExportsAs { alias: String },
+
+    // Override additions:
+    /// Symbol -> Symbol: a class method overrides (or implements) the same-named method
+    /// declared by the nearest ancestor class or interface that has one.
+    Overrides,
+
+    // String reference additions:
+    /// File -> Symbol: a string literal on `line` of the file spells the symbol's name, as
+    /// in reflection or dependency-injection lookups (`container.get("UserService")`). A
+    /// weak, heuristic reference, recorded only when `[string_refs]` is enabled.
+    StringRef { line: usize },
+
+    // Cargo manifest additions:
+    /// File -> ExternalPackage: a crate's root file declares the dependency in its
+    /// `Cargo.toml`, in `section`, with the features it requests.
+    DeclaresDependency {
+        section: DependencySection,
+        optional: bool,
+        features: Vec<String>,
+    },
+}
+
+/// The `Cargo.toml` table a dependency is declared in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DependencySection {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl EdgeKind {
+    /// Coupling strength of the edge: the number of imports or calls collapsed into it,
+    /// and 1 for every other kind.
+    pub fn weight(&self) -> usize {
+        match self {
+            EdgeKind::ResolvedImport { count, .. } | EdgeKind::Calls { count, .. } => {
+                (*count).max(1)
+            }
+            _ => 1,
+        }
+    }
 }
@@ -3,14 +3,16 @@ pub mod node;
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use petgraph::Directed;
 use petgraph::stable_graph::{NodeIndex, StableGraph};
 use petgraph::visit::EdgeRef;
 
+use crate::query::impact::ImpactCache;
 use bm25::SearchEngineBuilder;
 use edge::EdgeKind;
-use node::{ExternalPackageInfo, FileInfo, GraphNode, SymbolInfo, SymbolKind};
+use node::{ExternalPackageInfo, FileInfo, GraphNode, ModuleInfo, SymbolInfo, SymbolKind};
 
 /// The in-memory code graph: a directed petgraph StableGraph with O(1) lookup indexes.
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -21,28 +23,48 @@ pub struct CodeGraph {
     pub file_index: HashMap<PathBuf, NodeIndex>,
     /// Maps symbol names to all node indices bearing that name (one name may appear in many files).
     pub symbol_index: HashMap<String, Vec<NodeIndex>>,
+    /// Maps symbol kinds to all node indices of that kind, so listing every symbol of a
+    /// kind does not scan every name.
+    pub kind_index: HashMap<SymbolKind, Vec<NodeIndex>>,
     /// Maps external package names to their node indices for deduplication.
     pub external_index: HashMap<String, NodeIndex>,
     /// Maps Rust built-in crate names (`"std"`, `"core"`, `"alloc"`) to their node indices.
     /// Used to deduplicate `GraphNode::Builtin` nodes — one per crate name.
     pub builtin_index: HashMap<String, NodeIndex>,
+    /// Maps crate-qualified Rust module paths (`"my_crate::parser"`) to their module nodes.
+    pub module_index: HashMap<String, NodeIndex>,
+    /// Maps re-export aliases (`Bar` in `export { Foo as Bar }`) to the symbols they name.
+    pub alias_index: HashMap<String, Vec<NodeIndex>>,
     /// Transient BM25 full-text search index over symbol names.
     /// Not serialized — rebuilt after cache load and watcher events. Used by plan 20-01.
     #[serde(skip)]
     pub bm25_index: Option<bm25::SearchEngine<u32>>,
+    /// Incremented by the watcher after every incremental update. Not serialized.
+    #[serde(skip)]
+    pub generation: u64,
+    /// Transient blast-radius cache, valid for the graph generation it was filled at.
+    /// Not serialized — see `query::impact::cached_blast_radius`.
+    #[serde(skip)]
+    pub impact_cache: Mutex<ImpactCache>,
 }
 
 impl Clone for CodeGraph {
-    /// Clone the graph data structures but reset bm25_index to None.
+    /// Clone the graph data structures but reset bm25_index to None and start with an
+    /// empty impact cache.
     /// Call `rebuild_bm25_index()` after cloning if BM25 search is needed.
     fn clone(&self) -> Self {
         Self {
             graph: self.graph.clone(),
             file_index: self.file_index.clone(),
             symbol_index: self.symbol_index.clone(),
+            kind_index: self.kind_index.clone(),
             external_index: self.external_index.clone(),
             builtin_index: self.builtin_index.clone(),
+            module_index: self.module_index.clone(),
+            alias_index: self.alias_index.clone(),
             bm25_index: None,
+            generation: self.generation,
+            impact_cache: Mutex::default(),
         }
     }
 }
@@ -54,9 +76,14 @@ impl CodeGraph {
             graph: StableGraph::new(),
             file_index: HashMap::new(),
             symbol_index: HashMap::new(),
+            kind_index: HashMap::new(),
             external_index: HashMap::new(),
             builtin_index: HashMap::new(),
+            module_index: HashMap::new(),
+            alias_index: HashMap::new(),
             bm25_index: None,
+            generation: 0,
+            impact_cache: Mutex::default(),
         }
     }
 
@@ -74,12 +101,49 @@ impl CodeGraph {
             language: language.to_owned(),
             crate_name: None,
             kind: node::FileKind::Source,
+            category: None,
+            is_test: false,
+            line_count: 0,
+            content_hash: None,
         };
         let idx = self.graph.add_node(GraphNode::File(info));
         self.file_index.insert(path, idx);
         idx
     }
 
+    /// Record the line count and content hash of a parsed file.
+    pub fn set_file_source(
+        &mut self,
+        file_idx: NodeIndex,
+        line_count: usize,
+        content_hash: Option<u64>,
+    ) {
+        if let GraphNode::File(fi) = &mut self.graph[file_idx] {
+            fi.line_count = line_count;
+            fi.content_hash = content_hash;
+        }
+    }
+
+    /// Move every file under `from` to the same relative place under `to`, updating file
+    /// nodes and `file_index`. An empty `from` matches every relative path, so
+    /// `rebase_paths(root, "")` makes paths project-relative and `rebase_paths("", root)`
+    /// makes them absolute again; files outside `from` keep their path.
+    pub fn rebase_paths(&mut self, from: &Path, to: &Path) {
+        let mut file_index = HashMap::with_capacity(self.file_index.len());
+        for (path, idx) in self.file_index.drain() {
+            let path = match path.strip_prefix(from) {
+                Ok(rel) if to.as_os_str().is_empty() => rel.to_path_buf(),
+                Ok(rel) => to.join(rel),
+                Err(_) => path,
+            };
+            if let GraphNode::File(fi) = &mut self.graph[idx] {
+                fi.path = path.clone();
+            }
+            file_index.insert(path, idx);
+        }
+        self.file_index = file_index;
+    }
+
     /// Add a non-parsed file node to the graph. Returns the new node's index.
     /// If the file has already been added, returns the existing index.
     ///
@@ -94,6 +158,10 @@ impl CodeGraph {
             language: String::new(),
             crate_name: None,
             kind,
+            category: None,
+            is_test: false,
+            line_count: 0,
+            content_hash: None,
         };
         let idx = self.graph.add_node(GraphNode::File(info));
         self.file_index.insert(path, idx);
@@ -104,9 +172,11 @@ impl CodeGraph {
     /// Returns the symbol's node index.
     pub fn add_symbol(&mut self, file_idx: NodeIndex, info: SymbolInfo) -> NodeIndex {
         let name = info.name.clone();
+        let kind = info.kind.clone();
         let sym_idx = self.graph.add_node(GraphNode::Symbol(info));
         self.graph.add_edge(file_idx, sym_idx, EdgeKind::Contains);
         self.symbol_index.entry(name).or_default().push(sym_idx);
+        self.kind_index.entry(kind).or_default().push(sym_idx);
         sym_idx
     }
 
@@ -115,10 +185,12 @@ impl CodeGraph {
     /// Returns the child symbol's node index.
     pub fn add_child_symbol(&mut self, parent_idx: NodeIndex, info: SymbolInfo) -> NodeIndex {
         let name = info.name.clone();
+        let kind = info.kind.clone();
         let child_idx = self.graph.add_node(GraphNode::Symbol(info));
         self.graph
             .add_edge(child_idx, parent_idx, EdgeKind::ChildOf);
         self.symbol_index.entry(name).or_default().push(child_idx);
+        self.kind_index.entry(kind).or_default().push(child_idx);
         child_idx
     }
 
@@ -153,11 +225,65 @@ impl CodeGraph {
     /// Add a `ResolvedImport` edge from `from` to `to`.
     /// `specifier` is the original raw import string as written in source.
     pub fn add_resolved_import(&mut self, from: NodeIndex, to: NodeIndex, specifier: &str) {
+        self.add_import_edge(from, to, specifier, false, (0, 0));
+    }
+
+    /// Add a `ResolvedImport` edge for the import statement at the 1-based `line` and `col`
+    /// of `from`; `type_only` marks a TypeScript `import type`.
+    pub fn add_import_at(
+        &mut self,
+        from: NodeIndex,
+        to: NodeIndex,
+        specifier: &str,
+        type_only: bool,
+        line: usize,
+        col: usize,
+    ) {
+        self.add_import_edge(from, to, specifier, type_only, (line, col));
+    }
+
+    /// Add a `ResolvedImport` edge, or bump the `count` of an identical one (same target,
+    /// specifier and type-only flag) so repeated imports collapse into one weighted edge.
+    /// The edge keeps the earliest known `(line, col)` site; `(0, 0)` is unknown.
+    fn add_import_edge(
+        &mut self,
+        from: NodeIndex,
+        to: NodeIndex,
+        specifier: &str,
+        type_only: bool,
+        site: (usize, usize),
+    ) {
+        let existing = self
+            .graph
+            .edges_connecting(from, to)
+            .find_map(|e| match e.weight() {
+                EdgeKind::ResolvedImport {
+                    specifier: s,
+                    is_type_only,
+                    ..
+                } if s == specifier && *is_type_only == type_only => Some(e.id()),
+                _ => None,
+            });
+        if let Some(edge_idx) = existing
+            && let Some(EdgeKind::ResolvedImport {
+                count, line, col, ..
+            }) = self.graph.edge_weight_mut(edge_idx)
+        {
+            *count = (*count).max(1) + 1;
+            if site.0 > 0 && (*line == 0 || site < (*line, *col)) {
+                (*line, *col) = site;
+            }
+            return;
+        }
         self.graph.add_edge(
             from,
             to,
             EdgeKind::ResolvedImport {
                 specifier: specifier.to_owned(),
+                is_type_only: type_only,
+                count: 1,
+                line: site.0,
+                col: site.1,
             },
         );
     }
@@ -186,13 +312,7 @@ impl CodeGraph {
             self.external_index.insert(name.to_owned(), idx);
             idx
         };
-        self.graph.add_edge(
-            from,
-            pkg_idx,
-            EdgeKind::ResolvedImport {
-                specifier: specifier.to_owned(),
-            },
-        );
+        self.add_import_edge(from, pkg_idx, specifier, false, (0, 0));
         pkg_idx
     }
 
@@ -214,16 +334,64 @@ impl CodeGraph {
             self.builtin_index.insert(name.to_owned(), idx);
             idx
         };
-        self.graph.add_edge(
-            from,
-            node_idx,
-            EdgeKind::ResolvedImport {
-                specifier: specifier.to_owned(),
-            },
-        );
+        self.add_import_edge(from, node_idx, specifier, false, (0, 0));
         node_idx
     }
 
+    /// Add (or reuse) the `Module` node for the crate-qualified module `path`, creating its
+    /// ancestors up to the crate root and linking each to its parent with a `ChildOf` edge.
+    ///
+    /// Returns the `NodeIndex` of the module node (deduped by path).
+    pub fn add_module(&mut self, path: &str, crate_name: &str) -> NodeIndex {
+        if let Some(&existing) = self.module_index.get(path) {
+            return existing;
+        }
+        let idx = self.graph.add_node(GraphNode::Module(ModuleInfo {
+            path: path.to_owned(),
+            crate_name: crate_name.to_owned(),
+            is_test: false,
+        }));
+        self.module_index.insert(path.to_owned(), idx);
+        if let Some((parent, _)) = path.rsplit_once("::") {
+            let parent_idx = self.add_module(parent, crate_name);
+            self.graph.add_edge(idx, parent_idx, EdgeKind::ChildOf);
+        }
+        idx
+    }
+
+    /// Place a file in a module with a `ChildOf` edge from the file to the module,
+    /// replacing any module the file was in before.
+    pub fn set_file_module(&mut self, file_idx: NodeIndex, module_idx: NodeIndex) {
+        let previous: Vec<_> = self
+            .graph
+            .edges_directed(file_idx, petgraph::Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+            .map(|e| e.id())
+            .collect();
+        for edge in previous {
+            self.graph.remove_edge(edge);
+        }
+        self.graph.add_edge(file_idx, module_idx, EdgeKind::ChildOf);
+    }
+
+    /// Remove every `Module` node and its edges, before the module tree is rebuilt.
+    pub fn clear_modules(&mut self) {
+        for (_, idx) in self.module_index.drain() {
+            self.graph.remove_node(idx);
+        }
+    }
+
+    /// The module a file belongs to, if it is part of a Rust module tree.
+    pub fn module_of_file(&self, file_idx: NodeIndex) -> Option<&ModuleInfo> {
+        self.graph
+            .edges_directed(file_idx, petgraph::Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+            .find_map(|e| match &self.graph[e.target()] {
+                GraphNode::Module(info) => Some(info),
+                _ => None,
+            })
+    }
+
     /// Add an `UnresolvedImport` node (a sentinel capturing an unresolvable import) and a
     /// `ResolvedImport` edge from `from` to it.
     ///
@@ -244,14 +412,85 @@ impl CodeGraph {
             idx,
             EdgeKind::ResolvedImport {
                 specifier: specifier.to_owned(),
+                is_type_only: false,
+                count: 1,
+                line: 0,
+                col: 0,
             },
         );
         idx
     }
 
-    /// Add a `Calls` edge from `caller` to `callee`.
-    pub fn add_calls_edge(&mut self, caller: NodeIndex, callee: NodeIndex) {
-        self.graph.add_edge(caller, callee, EdgeKind::Calls);
+    /// Add a call from `caller` to `callee` at the 1-based `line`. Calls between the same
+    /// pair collapse into one `Calls` edge counting them and listing their lines.
+    pub fn add_call_site(&mut self, caller: NodeIndex, callee: NodeIndex, line: usize) {
+        let existing = self
+            .graph
+            .edges_connecting(caller, callee)
+            .find(|e| matches!(e.weight(), EdgeKind::Calls { .. }))
+            .map(|e| e.id());
+        match existing.and_then(|edge_idx| self.graph.edge_weight_mut(edge_idx)) {
+            Some(EdgeKind::Calls { count, lines }) => {
+                *count += 1;
+                if let Err(pos) = lines.binary_search(&line) {
+                    lines.insert(pos, line);
+                }
+            }
+            _ => {
+                self.graph.add_edge(
+                    caller,
+                    callee,
+                    EdgeKind::Calls {
+                        count: 1,
+                        lines: vec![line],
+                    },
+                );
+            }
+        }
+    }
+
+    /// Add a `Renders` edge from `from` (a component symbol or file) to the rendered
+    /// component, once per pair.
+    pub fn add_renders(&mut self, from: NodeIndex, component: NodeIndex) {
+        let exists = self
+            .graph
+            .edges_connecting(from, component)
+            .any(|e| matches!(e.weight(), EdgeKind::Renders));
+        if !exists {
+            self.graph.add_edge(from, component, EdgeKind::Renders);
+        }
+    }
+
+    /// Add an `ExportsAs` edge from `file_idx` to `symbol` and index the alias, once per
+    /// (file, symbol, alias).
+    pub fn add_export_alias(&mut self, file_idx: NodeIndex, symbol: NodeIndex, alias: &str) {
+        let exists = self
+            .graph
+            .edges_connecting(file_idx, symbol)
+            .any(|e| matches!(e.weight(), EdgeKind::ExportsAs { alias: a } if a == alias));
+        if exists {
+            return;
+        }
+        self.graph.add_edge(
+            file_idx,
+            symbol,
+            EdgeKind::ExportsAs {
+                alias: alias.to_owned(),
+            },
+        );
+        let indices = self.alias_index.entry(alias.to_owned()).or_default();
+        if !indices.contains(&symbol) {
+            indices.push(symbol);
+        }
+    }
+
+    /// Symbols named `name`, or failing that, the symbols re-exported under the alias
+    /// `name`.
+    pub fn symbols_named(&self, name: &str) -> Option<&Vec<NodeIndex>> {
+        self.symbol_index
+            .get(name)
+            .filter(|c| !c.is_empty())
+            .or_else(|| self.alias_index.get(name))
     }
 
     /// Add an `Extends` edge from `child` to `parent`.
@@ -265,6 +504,86 @@ impl CodeGraph {
             .add_edge(class_idx, iface_idx, EdgeKind::Implements);
     }
 
+    /// Add an `Overrides` edge from a class method to the ancestor method it overrides.
+    pub fn add_overrides_edge(&mut self, method_idx: NodeIndex, base_idx: NodeIndex) {
+        self.graph
+            .add_edge(method_idx, base_idx, EdgeKind::Overrides);
+    }
+
+    /// Add a `ReferencesTable` edge from `file_idx` to a SQL table/view symbol.
+    pub fn add_table_reference(&mut self, file_idx: NodeIndex, table_idx: NodeIndex) {
+        self.graph
+            .add_edge(file_idx, table_idx, EdgeKind::ReferencesTable);
+    }
+
+    /// Add a `DocumentedBy` edge from a symbol or file to the Markdown doc mentioning it.
+    pub fn add_documented_by(&mut self, subject_idx: NodeIndex, doc_idx: NodeIndex) {
+        self.graph
+            .add_edge(subject_idx, doc_idx, EdgeKind::DocumentedBy);
+    }
+
+    /// Add a `StringRef` edge from a file to the symbol a string literal on `line` names.
+    pub fn add_string_ref(&mut self, file_idx: NodeIndex, symbol_idx: NodeIndex, line: usize) {
+        self.graph
+            .add_edge(file_idx, symbol_idx, EdgeKind::StringRef { line });
+    }
+
+    /// Add a `HandledBy` edge from an OpenAPI endpoint symbol to its route handler.
+    pub fn add_handled_by(&mut self, endpoint_idx: NodeIndex, handler_idx: NodeIndex) {
+        self.graph
+            .add_edge(endpoint_idx, handler_idx, EdgeKind::HandledBy);
+    }
+
+    /// Add an `ImportsSymbol` edge from an importing file to the symbol a named import binds,
+    /// for the import statement at the 1-based `line` and `col` (0 when unknown).
+    pub fn add_imports_symbol(
+        &mut self,
+        file_idx: NodeIndex,
+        symbol_idx: NodeIndex,
+        line: usize,
+        col: usize,
+    ) {
+        self.graph
+            .add_edge(file_idx, symbol_idx, EdgeKind::ImportsSymbol { line, col });
+    }
+
+    /// Add a `Tests` edge from a test file to a file or symbol it imports.
+    pub fn add_tests(&mut self, test_file_idx: NodeIndex, subject_idx: NodeIndex) {
+        self.graph
+            .add_edge(test_file_idx, subject_idx, EdgeKind::Tests);
+    }
+
+    /// Add (or reuse) the `ExternalPackage` node for a dependency declared in `Cargo.toml`
+    /// and link it from the crate root file `crate_root` with `declaration`, which should
+    /// be a `DeclaresDependency` edge. Fills in the node's version when it has none.
+    pub fn add_declared_dependency(
+        &mut self,
+        crate_root: NodeIndex,
+        name: &str,
+        version: Option<&str>,
+        declaration: EdgeKind,
+    ) -> NodeIndex {
+        let pkg_idx = match self.external_index.get(name) {
+            Some(&existing) => existing,
+            None => {
+                let info = ExternalPackageInfo {
+                    name: name.to_owned(),
+                    version: None,
+                };
+                let idx = self.graph.add_node(GraphNode::ExternalPackage(info));
+                self.external_index.insert(name.to_owned(), idx);
+                idx
+            }
+        };
+        if let GraphNode::ExternalPackage(info) = &mut self.graph[pkg_idx]
+            && info.version.is_none()
+        {
+            info.version = version.map(str::to_owned);
+        }
+        self.graph.add_edge(crate_root, pkg_idx, declaration);
+        pkg_idx
+    }
+
     /// Add a `BarrelReExportAll` edge from `barrel` to `source`.
     pub fn add_barrel_reexport_all(&mut self, barrel: NodeIndex, source: NodeIndex) {
         self.graph
@@ -275,7 +594,7 @@ impl CodeGraph {
     ///
     /// Removes: the file node, all Symbol nodes connected via Contains edges,
     /// all child symbols (via ChildOf edges from those symbols), and all edges
-    /// to/from any of these nodes. Also cleans up file_index and symbol_index.
+    /// to/from any of these nodes. Also cleans up file_index, symbol_index, and kind_index.
     pub fn remove_file_from_graph(&mut self, path: &Path) {
         let file_idx = match self.file_index.remove(path) {
             Some(idx) => idx,
@@ -303,7 +622,7 @@ impl CodeGraph {
             nodes_to_remove.extend(children);
         }
 
-        // Clean up symbol_index for all symbol nodes being removed
+        // Clean up symbol_index and kind_index for all symbol nodes being removed
         for &node_idx in &nodes_to_remove {
             if let Some(GraphNode::Symbol(info)) = self.graph.node_weight(node_idx) {
                 let name = info.name.clone();
@@ -313,6 +632,38 @@ impl CodeGraph {
                         self.symbol_index.remove(&name);
                     }
                 }
+                let kind = info.kind.clone();
+                if let Some(indices) = self.kind_index.get_mut(&kind) {
+                    indices.retain(|&i| i != node_idx);
+                    if indices.is_empty() {
+                        self.kind_index.remove(&kind);
+                    }
+                }
+            }
+        }
+
+        // Drop the aliases the file exports and the aliases of its symbols.
+        let aliases: Vec<(String, NodeIndex)> = nodes_to_remove
+            .iter()
+            .flat_map(|&idx| {
+                self.graph
+                    .edges_directed(idx, petgraph::Direction::Outgoing)
+                    .chain(
+                        self.graph
+                            .edges_directed(idx, petgraph::Direction::Incoming),
+                    )
+            })
+            .filter_map(|e| match e.weight() {
+                EdgeKind::ExportsAs { alias } => Some((alias.clone(), e.target())),
+                _ => None,
+            })
+            .collect();
+        for (alias, symbol) in aliases {
+            if let Some(indices) = self.alias_index.get_mut(&alias) {
+                indices.retain(|&i| i != symbol);
+                if indices.is_empty() {
+                    self.alias_index.remove(&alias);
+                }
             }
         }
 
@@ -545,6 +896,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_repeated_edges_collapse_with_counts() {
+        let mut graph = CodeGraph::new();
+        let f1 = graph.add_file(PathBuf::from("src/app.ts"), "typescript");
+        let f2 = graph.add_file(PathBuf::from("src/utils.ts"), "typescript");
+        graph.add_resolved_import(f1, f2, "./utils");
+        graph.add_resolved_import(f1, f2, "./utils");
+        graph.add_resolved_import(f1, f2, "./utils/index");
+
+        let sym = |graph: &mut CodeGraph, file, name: &str| {
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Function,
+                    ..Default::default()
+                },
+            )
+        };
+        let main = sym(&mut graph, f1, "main");
+        let helper = sym(&mut graph, f2, "helper");
+        graph.add_call_site(main, helper, 5);
+        graph.add_call_site(main, helper, 2);
+        graph.add_call_site(main, helper, 5);
+
+        let mut imports: Vec<(String, usize)> = graph
+            .graph
+            .edges_connecting(f1, f2)
+            .filter_map(|e| match e.weight() {
+                EdgeKind::ResolvedImport {
+                    specifier, count, ..
+                } => Some((specifier.clone(), *count)),
+                _ => None,
+            })
+            .collect();
+        imports.sort();
+        assert_eq!(
+            imports,
+            vec![("./utils".to_string(), 2), ("./utils/index".to_string(), 1)]
+        );
+
+        let calls: Vec<&EdgeKind> = graph
+            .graph
+            .edges_connecting(main, helper)
+            .map(|e| e.weight())
+            .collect();
+        assert_eq!(calls.len(), 1);
+        assert!(
+            matches!(calls[0], EdgeKind::Calls { count: 3, lines } if lines == &[2, 5]),
+            "repeated call sites should collapse into one edge, got {:?}",
+            calls[0]
+        );
+        assert_eq!(calls[0].weight(), 3);
+    }
+
     #[test]
     fn test_add_builtin_node_dedup() {
         let mut graph = CodeGraph::new();
@@ -585,6 +991,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_module_nodes() {
+        let mut graph = CodeGraph::new();
+        let file = graph.add_file(PathBuf::from("src/parser/imports.rs"), "rust");
+        let imports = graph.add_module("my_crate::parser::imports", "my_crate");
+        let parser = graph.module_index["my_crate::parser"];
+        assert!(graph.graph.contains_edge(imports, parser));
+        assert!(graph.module_index.contains_key("my_crate"));
+        assert_eq!(graph.add_module("my_crate::parser", "my_crate"), parser);
+
+        graph.set_file_module(file, imports);
+        graph.set_file_module(file, parser);
+        assert_eq!(
+            graph.module_of_file(file).map(|m| m.name()),
+            Some("parser"),
+            "set_file_module replaces the previous module"
+        );
+
+        graph.clear_modules();
+        assert!(graph.module_index.is_empty());
+        assert!(graph.module_of_file(file).is_none());
+        assert_eq!(graph.graph.node_count(), 1);
+    }
+
     // -------------------------------------------------------------------------
     // BM25 index tests (Phase 20 Plan 01)
     // -------------------------------------------------------------------------
@@ -1,17 +1,21 @@
 use std::path::PathBuf;
 
-/// Visibility level of a Rust symbol.
+/// Visibility level of a symbol.
 ///
-/// TypeScript/JavaScript symbols always use `Private` here; their export status is tracked
-/// separately via `SymbolInfo::is_exported`.
+/// TypeScript/JavaScript top-level symbols are `Pub` when exported and `Private` to their
+/// module otherwise (the export itself is still tracked by `SymbolInfo::is_exported`);
+/// class members are `Pub`, `Protected`, or `Private` (`private` and `#name`).
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum SymbolVisibility {
     /// `pub` — visible everywhere.
     Pub,
     /// `pub(crate)`, `pub(super)`, `pub(in path)` — all collapse to this variant.
     PubCrate,
-    /// No visibility modifier (default in Rust).
+    /// No visibility modifier (default in Rust), a non-exported TS/JS symbol, or a TS
+    /// `private`/`#name` class member.
     Private,
+    /// TS `protected` class member: visible to the class and its subclasses.
+    Protected,
 }
 
 /// The kind of symbol extracted from source code.
@@ -25,6 +29,8 @@ pub enum SymbolKind {
     Interface,
     /// A TypeScript type alias declaration.
     TypeAlias,
+    /// A TypeScript namespace (`namespace Foo {}`, `declare namespace Foo {}`, `module Foo {}`).
+    Namespace,
     /// A TypeScript or JavaScript enum declaration.
     Enum,
     /// An exported variable (non-arrow-function const/let/var).
@@ -47,6 +53,21 @@ pub enum SymbolKind {
     Static,
     /// A Rust macro_rules! definition.
     Macro,
+    // Protobuf kinds
+    /// A protobuf `message` definition.
+    Message,
+    /// A protobuf `service` definition.
+    Service,
+    /// A protobuf `rpc` method (child of a Service).
+    Rpc,
+    // SQL kinds
+    /// A SQL `CREATE TABLE` definition.
+    Table,
+    /// A SQL `CREATE VIEW` (or materialized view) definition.
+    View,
+    // OpenAPI kinds
+    /// An OpenAPI operation (`GET /users/{id}`), declared by a spec file.
+    Endpoint,
 }
 
 /// A decorator or attribute applied to a symbol.
@@ -65,6 +86,24 @@ pub struct DecoratorInfo {
     pub framework: Option<String>,
 }
 
+/// A parameter of a function or method signature.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ParamInfo {
+    /// Parameter name or pattern as written: `id`, `opts?`, `...rest`, `&self`.
+    pub name: String,
+    /// Declared type, if annotated (`string`, `&Path`).
+    #[serde(rename = "type")]
+    pub type_annotation: Option<String>,
+}
+
+/// Parameters and return type of a TS/JS or Rust function or method.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SignatureInfo {
+    pub params: Vec<ParamInfo>,
+    /// Declared return type, if any (`Promise<User>`, `Result<()>`).
+    pub return_type: Option<String>,
+}
+
 /// Metadata about a symbol extracted from source code.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SymbolInfo {
@@ -78,6 +117,17 @@ pub struct SymbolInfo {
     pub col: usize,
     /// 1-based line number where the symbol ends (inclusive).
     pub line_end: usize,
+    /// 0-based column offset where the symbol ends (exclusive).
+    #[serde(default)]
+    pub col_end: usize,
+    /// Byte offset of the start of the symbol's declaration in its file.
+    #[serde(default)]
+    pub byte_start: usize,
+    /// Byte offset just past the end of the symbol's declaration, so
+    /// `source[byte_start..byte_end]` is its full text. `0` when the parser does not
+    /// track byte offsets (the hand-written SQL, CSS, Protobuf, Zig, and shell parsers).
+    #[serde(default)]
+    pub byte_end: usize,
     /// Whether the symbol is explicitly exported.
     pub is_exported: bool,
     /// Whether the symbol is a default export.
@@ -90,6 +140,32 @@ pub struct SymbolInfo {
     pub trait_impl: Option<String>,
     /// Decorators/attributes applied to this symbol.
     pub decorators: Vec<DecoratorInfo>,
+    /// Category assigned by the project's `[[category]]` rules, if any.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Parameters and return type, for functions and methods.
+    #[serde(default)]
+    pub signature: Option<SignatureInfo>,
+    /// Rust `#[cfg(...)]` predicate the symbol is compiled under — from its own attributes
+    /// and those of enclosing `mod`, `impl`, and `trait` items — e.g. `test`,
+    /// `feature = "web"`, or `all(unix, test)`. `None` when always compiled.
+    #[serde(default)]
+    pub cfg: Option<String>,
+    /// TypeScript: an `abstract` class, or an abstract method declared without a body.
+    #[serde(default)]
+    pub is_abstract: bool,
+    /// TypeScript: a class member marked `override`.
+    #[serde(default)]
+    pub is_override: bool,
+    /// Cyclomatic complexity of a function or method (see `parser::complexity`); `None`
+    /// for other symbols and for parsers that do not measure it.
+    #[serde(default)]
+    pub complexity: Option<u32>,
+    /// Rust impl methods generated by a derive macro: the derived trait (`Debug`,
+    /// `Serialize`). Detected on impl blocks marked `#[automatically_derived]`, as in
+    /// expanded or generated code. `None` for hand-written code.
+    #[serde(default)]
+    pub derived: Option<String>,
 }
 
 impl Default for SymbolInfo {
@@ -100,15 +176,91 @@ impl Default for SymbolInfo {
             line: 0,
             col: 0,
             line_end: 0,
+            col_end: 0,
+            byte_start: 0,
+            byte_end: 0,
             is_exported: false,
             is_default: false,
             visibility: SymbolVisibility::Private,
             trait_impl: None,
             decorators: Vec::new(),
+            category: None,
+            signature: None,
+            cfg: None,
+            is_abstract: false,
+            is_override: false,
+            complexity: None,
+            derived: None,
         }
     }
 }
 
+impl SymbolInfo {
+    /// Lines the symbol's declaration spans, from `line` to `line_end` inclusive.
+    pub fn line_count(&self) -> usize {
+        (self.line_end + 1).saturating_sub(self.line).max(1)
+    }
+
+    /// Traits named in the symbol's `#[derive(...)]` attributes, paths stripped:
+    /// `#[derive(Debug, serde::Serialize)]` yields `Debug` and `Serialize`.
+    pub fn derive_traits(&self) -> Vec<&str> {
+        self.decorators
+            .iter()
+            .filter(|d| d.name == "derive")
+            .filter_map(|d| d.args_raw.as_deref())
+            .flat_map(|args| {
+                args.trim_start_matches('(')
+                    .trim_end_matches(')')
+                    .split(',')
+            })
+            .map(|t| t.trim().rsplit("::").next().unwrap_or_default())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    /// Whether the symbol only exists in `#[cfg(test)]` builds.
+    pub fn is_cfg_test(&self) -> bool {
+        self.cfg
+            .as_deref()
+            .is_some_and(|cfg| cfg_requires(cfg, &|atom| atom == "test"))
+    }
+
+    /// Whether the symbol only exists when a Cargo feature is enabled.
+    pub fn is_cfg_feature(&self) -> bool {
+        self.cfg.as_deref().is_some_and(|cfg| {
+            cfg_requires(cfg, &|atom| {
+                atom.strip_prefix("feature")
+                    .is_some_and(|rest| rest.trim_start().starts_with('='))
+            })
+        })
+    }
+}
+
+/// Whether every configuration satisfying the cfg predicate `cfg` satisfies an atom
+/// matching `atom`: the predicate is that atom or an `all(...)` containing it.
+fn cfg_requires(cfg: &str, atom: &dyn Fn(&str) -> bool) -> bool {
+    let cfg = cfg.trim();
+    if let Some(inner) = cfg.strip_prefix("all(").and_then(|r| r.strip_suffix(')')) {
+        let mut depth = 0usize;
+        let mut start = 0;
+        let mut parts = Vec::new();
+        for (i, c) in inner.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    parts.push(&inner[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(&inner[start..]);
+        return parts.into_iter().any(|part| cfg_requires(part, atom));
+    }
+    atom(cfg)
+}
+
 /// Classification of a file's role in the project.
 ///
 /// Source files have full symbol extraction and import resolution.
@@ -146,9 +298,10 @@ pub fn classify_file_kind(path: &std::path::Path) -> FileKind {
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
     match ext {
         // Source files
-        "ts" | "tsx" | "js" | "jsx" | "rs" | "py" | "go" => FileKind::Source,
+        "ts" | "tsx" | "js" | "jsx" | "rs" | "py" | "go" | "proto" | "sql" | "astro" | "zig"
+        | "sh" | "bash" | "ipynb" | "css" | "scss" => FileKind::Source,
         // Documentation
-        "md" | "txt" | "rst" | "adoc" => FileKind::Doc,
+        "md" | "markdown" | "txt" | "rst" | "adoc" => FileKind::Doc,
         // Configuration
         "toml" | "yaml" | "yml" | "json" | "ini" | "env" | "cfg" | "conf" | "properties"
         | "xml" => FileKind::Config,
@@ -183,6 +336,20 @@ pub struct FileInfo {
     pub crate_name: Option<String>,
     /// Classification of this file's role (source, doc, config, ci, asset, other).
     pub kind: FileKind,
+    /// Category assigned by the project's `[[category]]` rules, if any.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Test file: `*.test.ts` / `*.spec.ts`-style names, a `tests/` directory, or a Rust
+    /// module declared under `#[cfg(test)]`. Set by `link_tests` after every build.
+    #[serde(default)]
+    pub is_test: bool,
+    /// Lines in the file, recorded when it is parsed; 0 for non-parsed files.
+    #[serde(default)]
+    pub line_count: usize,
+    /// Hash of the file's bytes, recorded when it is parsed; `None` for non-parsed files.
+    /// Byte-identical copies (vendored files) share it.
+    #[serde(default)]
+    pub content_hash: Option<u64>,
 }
 
 /// Metadata about an external package (node_modules dependency).
@@ -191,12 +358,36 @@ pub struct FileInfo {
 pub struct ExternalPackageInfo {
     /// The npm package name (e.g. "react", "@org/utils").
     pub name: String,
-    /// Package version, if available from package.json.
+    /// Package version, if available from package.json or a `Cargo.toml` declaration.
     pub version: Option<String>,
 }
 
+/// A Rust module in a crate's module tree.
+///
+/// Module nodes form the crate hierarchy: each module has a `ChildOf` edge to its parent
+/// module, and each file-backed module's file has a `ChildOf` edge to its module. The
+/// crate root module has no parent.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ModuleInfo {
+    /// Crate-qualified module path (e.g. `"my_crate::parser::imports"`); the crate root
+    /// module is just the crate name.
+    pub path: String,
+    /// The owning crate's normalized name.
+    pub crate_name: String,
+    /// Declared under `#[cfg(test)]`, directly or through an enclosing module.
+    #[serde(default)]
+    pub is_test: bool,
+}
+
+impl ModuleInfo {
+    /// Last segment of the module path (the crate name for a crate root).
+    pub fn name(&self) -> &str {
+        self.path.rsplit("::").next().unwrap_or(&self.path)
+    }
+}
+
 /// A node in the code graph — a file, a symbol within a file, an external package,
-/// a Rust built-in crate, or an unresolved import.
+/// a Rust built-in crate, a Rust module, or an unresolved import.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum GraphNode {
     /// A source file node.
@@ -212,12 +403,30 @@ pub enum GraphNode {
     Builtin { name: String },
     /// An import specifier that could not be resolved to a file or known package.
     UnresolvedImport { specifier: String, reason: String },
+    /// A Rust module; see [`ModuleInfo`].
+    Module(ModuleInfo),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cfg_classification() {
+        let with_cfg = |cfg: &str| SymbolInfo {
+            cfg: Some(cfg.into()),
+            ..Default::default()
+        };
+        assert!(with_cfg("test").is_cfg_test());
+        assert!(with_cfg("all(unix, test)").is_cfg_test());
+        assert!(!with_cfg("not(test)").is_cfg_test());
+        assert!(!with_cfg("any(test, unix)").is_cfg_test());
+        assert!(with_cfg(r#"feature = "web""#).is_cfg_feature());
+        assert!(with_cfg(r#"all(feature="web", unix)"#).is_cfg_feature());
+        assert!(!with_cfg("unix").is_cfg_feature());
+        assert!(!SymbolInfo::default().is_cfg_test());
+    }
+
     #[test]
     fn test_classify_source_files() {
         assert_eq!(
@@ -14,6 +14,13 @@ pub enum LanguageKind {
     Rust,
     Python,
     Go,
+    Proto,
+    Sql,
+    Astro,
+    Zig,
+    Shell,
+    Jupyter,
+    Css,
 }
 
 impl LanguageKind {
@@ -25,6 +32,13 @@ impl LanguageKind {
             LanguageKind::Rust => ext == "rs",
             LanguageKind::Python => ext == "py",
             LanguageKind::Go => ext == "go",
+            LanguageKind::Proto => ext == "proto",
+            LanguageKind::Sql => ext == "sql",
+            LanguageKind::Astro => ext == "astro",
+            LanguageKind::Zig => ext == "zig",
+            LanguageKind::Shell => matches!(ext, "sh" | "bash"),
+            LanguageKind::Jupyter => ext == "ipynb",
+            LanguageKind::Css => matches!(ext, "css" | "scss"),
         }
     }
 
@@ -41,6 +55,13 @@ impl LanguageKind {
             "rust" | "rs" => Some(LanguageKind::Rust),
             "python" | "py" => Some(LanguageKind::Python),
             "go" | "golang" => Some(LanguageKind::Go),
+            "proto" | "protobuf" => Some(LanguageKind::Proto),
+            "sql" => Some(LanguageKind::Sql),
+            "astro" => Some(LanguageKind::Astro),
+            "zig" => Some(LanguageKind::Zig),
+            "shell" | "sh" | "bash" => Some(LanguageKind::Shell),
+            "jupyter" | "ipynb" | "notebook" => Some(LanguageKind::Jupyter),
+            "css" | "scss" => Some(LanguageKind::Css),
             _ => None,
         }
     }
@@ -54,6 +75,7 @@ const CONFIG_FILES: &[(&str, LanguageKind)] = &[
     ("pyproject.toml", LanguageKind::Python),
     ("setup.py", LanguageKind::Python),
     ("go.mod", LanguageKind::Go),
+    ("build.zig", LanguageKind::Zig),
 ];
 
 /// Detect which languages are present in a project root.
@@ -6,14 +6,17 @@ mod export;
 mod graph;
 mod language;
 mod output;
+mod pager;
 mod parser;
 mod project;
 mod query;
 #[cfg(feature = "rag")]
 mod rag;
 mod registry;
+mod repl;
 mod resolver;
 mod setup;
+mod version;
 mod walker;
 mod watcher;
 #[cfg(feature = "web")]
@@ -27,7 +30,7 @@ use clap::Parser;
 use petgraph::visit::EdgeRef;
 use rayon::prelude::*;
 
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, OutputFormat};
 use config::CodeGraphConfig;
 use graph::node::classify_file_kind;
 use graph::{CodeGraph, edge::EdgeKind, node::SymbolKind};
@@ -126,19 +129,22 @@ fn count_rust_symbols(graph: &CodeGraph) -> RustSymbolCounts {
     counts
 }
 
-/// Populate `FileInfo.crate_name` for all Rust files in the graph.
+/// Populate `FileInfo.crate_name` for all Rust files in the graph and rebuild the crate
+/// module hierarchy.
 ///
 /// Calls `discover_rust_workspace_members` to get crate name → root file mappings, then builds
 /// a `RustModTree` per crate, and for each file in the graph whose path appears in a mod tree,
-/// sets the `crate_name` field on the corresponding `FileInfo` node.
+/// sets the `crate_name` field on the corresponding `FileInfo` node. The mod trees also
+/// become `Module` nodes (replacing any from a previous build) linked to their files.
 ///
 /// This is called AFTER graph population (so all file nodes exist) and BEFORE `resolve_all`
 /// (so the resolver can use crate_name for classification).
 pub(crate) fn populate_rust_crate_names(graph: &mut CodeGraph, project_root: &Path) {
     use graph::node::GraphNode;
     use resolver::cargo_workspace::discover_rust_workspace_members;
-    use resolver::rust_mod_tree::build_mod_tree;
+    use resolver::rust_mod_tree::{add_module_nodes, build_mod_tree};
 
+    graph.clear_modules();
     let workspace_members = discover_rust_workspace_members(project_root);
     if workspace_members.is_empty() {
         return;
@@ -149,6 +155,7 @@ pub(crate) fn populate_rust_crate_names(graph: &mut CodeGraph, project_root: &Pa
         std::collections::HashMap::new();
     for (crate_name, crate_root) in &workspace_members {
         let tree = build_mod_tree(crate_name, crate_root);
+        add_module_nodes(graph, crate_name, &tree);
         // mod_map: String (module path) → PathBuf (file); iterate values for file paths.
         for file_path in tree.mod_map.values() {
             file_to_crate.insert(file_path.clone(), crate_name.clone());
@@ -198,8 +205,15 @@ fn parse_language_filter(lang_str: Option<&str>) -> Result<Option<&'static str>>
             Some(LanguageKind::JavaScript) => Ok(Some("javascript")),
             Some(LanguageKind::Python) => Ok(Some("python")),
             Some(LanguageKind::Go) => Ok(Some("go")),
+            Some(LanguageKind::Proto) => Ok(Some("proto")),
+            Some(LanguageKind::Sql) => Ok(Some("sql")),
+            Some(LanguageKind::Astro) => Ok(Some("astro")),
+            Some(LanguageKind::Zig) => Ok(Some("zig")),
+            Some(LanguageKind::Shell) => Ok(Some("shell")),
+            Some(LanguageKind::Jupyter) => Ok(Some("jupyter")),
+            Some(LanguageKind::Css) => Ok(Some("css")),
             None => anyhow::bail!(
-                "unknown language '{}'. Valid: rust/rs, typescript/ts, javascript/js, python/py, go/golang",
+                "unknown language '{}'. Valid: rust/rs, typescript/ts, javascript/js, python/py, go/golang, proto/protobuf, sql, astro, zig, shell/sh, jupyter/ipynb, css/scss",
                 s
             ),
         },
@@ -280,10 +294,44 @@ fn file_language_matches(path: &Path, lang: &str) -> bool {
         "javascript" => matches!(ext, "js" | "jsx"),
         "python" => ext == "py",
         "go" => ext == "go",
+        "proto" => ext == "proto",
+        "sql" => ext == "sql",
+        "astro" => ext == "astro",
+        "zig" => ext == "zig",
+        "shell" => matches!(ext, "sh" | "bash"),
+        "jupyter" => ext == "ipynb",
+        "css" => matches!(ext, "css" | "scss"),
         _ => false,
     }
 }
 
+/// The `--group-by` / `--sort` arrangement of `find`, `refs`, or `impact` results, with
+/// the package of every file filled in when grouping by package.
+fn result_arrangement(
+    graph: &CodeGraph,
+    root: &Path,
+    group_by: Option<cli::GroupBy>,
+    sort: Option<cli::SortBy>,
+) -> query::output::Arrangement {
+    let packages = if group_by == Some(cli::GroupBy::Package) {
+        query::pkgql::file_packages(graph, root)
+            .into_iter()
+            .filter_map(|(idx, package)| match &graph.graph[idx] {
+                graph::node::GraphNode::File(fi) => Some((fi.path.clone(), package)),
+                _ => None,
+            })
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    query::output::Arrangement {
+        group_by,
+        sort,
+        packages,
+        refs: HashMap::new(),
+    }
+}
+
 /// Map a file extension to its language string for the graph.
 ///
 /// Returns `None` for unsupported extensions. Used by both `build_graph` and
@@ -296,6 +344,12 @@ fn ext_to_language(ext: &str) -> Option<&'static str> {
         "rs" => Some("rust"),
         "py" => Some("python"),
         "go" => Some("go"),
+        "proto" => Some("proto"),
+        "sql" => Some("sql"),
+        "astro" => Some("astro"),
+        "zig" => Some("zig"),
+        "sh" | "bash" => Some("shell"),
+        "css" | "scss" => Some("css"),
         _ => None,
     }
 }
@@ -310,8 +364,13 @@ fn parse_files_parallel(files: &[PathBuf]) -> Vec<(PathBuf, &'static str, ParseR
         .filter_map(|file_path| {
             let source = std::fs::read(file_path).ok()?;
             let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            let language_str = ext_to_language(ext)?;
-            let result = parser::parse_file_parallel(file_path, &source).ok()?;
+            let language_str = match ext {
+                "ipynb" => parser::notebook::kernel_language(&source),
+                _ => ext_to_language(ext).or(parser::dynamic::language_for_path(file_path))?,
+            };
+            let mut result = parser::parse_file_parallel(file_path, &source).ok()?;
+            result.line_count = parser::count_lines(&source);
+            result.content_hash = Some(parser::content_hash(&source));
             Some((file_path.clone(), language_str, result))
         })
         .collect()
@@ -329,6 +388,7 @@ fn insert_parsed_into_graph(
 
     for (file_path, language_str, result) in raw_results {
         let file_idx = graph.add_file(file_path.clone(), language_str);
+        graph.set_file_source(file_idx, result.line_count, result.content_hash);
 
         for (symbol, children) in &result.symbols {
             let sym_idx = graph.add_symbol(file_idx, symbol.clone());
@@ -379,13 +439,45 @@ fn insert_parsed_into_graph(
 /// This is the shared pipeline used by all query subcommands. The Index command
 /// calls the same parse/insert helpers but also accumulates detailed stats.
 pub(crate) fn build_graph(path: &Path, verbose: bool) -> Result<CodeGraph> {
+    Ok(build_graph_parsed(path, verbose)?.0)
+}
+
+/// Parse results of every parsed file with its language, kept by the daemon so it can
+/// re-resolve the graph without parsing the whole project again.
+pub(crate) type ParsedFiles = Vec<(PathBuf, &'static str, ParseResult)>;
+
+/// Like [`build_graph`], also returning the parse results for [`resolve_parsed`].
+pub(crate) fn build_graph_parsed(path: &Path, verbose: bool) -> Result<(CodeGraph, ParsedFiles)> {
     let config = CodeGraphConfig::load(path);
+    parser::dynamic::install(path, &config.grammar);
     let files = walk_project(path, &config, verbose, None)?;
 
     let raw_results = parse_files_parallel(&files);
+    resolve_parsed(path, raw_results, verbose)
+}
+
+/// Re-parse `changed` files in `parsed`, dropping the ones that can no longer be read.
+pub(crate) fn reparse_changed(parsed: &mut ParsedFiles, changed: &[PathBuf]) {
+    let changed_set: HashSet<&PathBuf> = changed.iter().collect();
+    parsed.retain(|(path, _, _)| !changed_set.contains(path));
+    parsed.extend(parse_files_parallel(changed));
+}
+
+/// Build the graph of `path` from already parsed files: insert them, resolve imports and
+/// relationships, and run the linking passes. The parse results are handed back.
+pub(crate) fn resolve_parsed(
+    path: &Path,
+    parsed: ParsedFiles,
+    verbose: bool,
+) -> Result<(CodeGraph, ParsedFiles)> {
+    let config = CodeGraphConfig::load(path);
+    let languages: Vec<(PathBuf, &'static str)> = parsed
+        .iter()
+        .map(|(file, language, _)| (file.clone(), *language))
+        .collect();
 
     let mut graph = CodeGraph::new();
-    let parse_results = insert_parsed_into_graph(&mut graph, raw_results, verbose);
+    let mut parse_results = insert_parsed_into_graph(&mut graph, parsed, verbose);
 
     // Populate crate_name on FileInfo for all Rust files.
     populate_rust_crate_names(&mut graph, path);
@@ -403,7 +495,59 @@ pub(crate) fn build_graph(path: &Path, verbose: bool) -> Result<CodeGraph> {
         graph.add_non_parsed_file(file_path, kind);
     }
 
-    Ok(graph)
+    // Link Markdown docs to the files they link to and the symbols they mention.
+    let doc_links = resolver::docs_resolver::link_docs(&mut graph, path);
+    if verbose {
+        eprintln!("  Docs: {} DocumentedBy links", doc_links);
+    }
+
+    // Add OpenAPI endpoint symbols and link them to their TS/JS route handlers.
+    let openapi = resolver::openapi_resolver::link_openapi(&mut graph);
+    if verbose && openapi.endpoints > 0 {
+        eprintln!(
+            "  OpenAPI: {} endpoints, {} HandledBy links",
+            openapi.endpoints, openapi.handled_by
+        );
+    }
+
+    // Link class methods to the base-class and interface methods they override, and Rust
+    // impl methods to the trait methods they implement.
+    let overrides = resolver::overrides::link_overrides(&mut graph);
+    if verbose && overrides > 0 {
+        eprintln!("  Overrides: {} links", overrides);
+    }
+
+    // Mark test files and link them to the files and symbols they import.
+    let test_links = resolver::tests_resolver::link_tests(&mut graph, path);
+    if verbose {
+        eprintln!("  Tests: {} Tests links", test_links);
+    }
+
+    // Link crate roots to the dependencies their `Cargo.toml` declares.
+    let declared = resolver::cargo_deps::link_cargo_dependencies(&mut graph, path);
+    if verbose && declared > 0 {
+        eprintln!("  Cargo: {} declared dependencies", declared);
+    }
+
+    // Opt-in: link files to the symbols their string literals name (`[string_refs]`).
+    let string_refs = resolver::string_refs::link_string_refs(&mut graph, &config.string_refs);
+    if verbose && string_refs > 0 {
+        eprintln!("  String refs: {} StringRef links", string_refs);
+    }
+
+    // Tag files and symbols with the project's `[[category]]` rules, and unindex the
+    // symbols `[symbols] exclude` filters out.
+    crate::query::categories::apply_categories(&mut graph, path, &config.category);
+    crate::query::exclusions::apply_symbol_exclusions(&mut graph, &config.symbols.exclude);
+
+    let parsed = languages
+        .into_iter()
+        .filter_map(|(file, language)| {
+            let result = parse_results.remove(&file)?;
+            Some((file, language, result))
+        })
+        .collect();
+    Ok((graph, parsed))
 }
 
 /// Try to query the daemon first; if unavailable, fall back to local graph execution.
@@ -418,10 +562,39 @@ fn try_daemon_query(
     daemon::client::query_daemon(project_root, request).ok()
 }
 
+/// Map a CLI output format onto the daemon response format.
+///
+/// Returns `None` for `--format table`, which the daemon does not render; those queries
+/// run locally instead.
+fn daemon_response_format(format: &OutputFormat) -> Option<daemon::protocol::ResponseFormat> {
+    match format {
+        OutputFormat::Compact => Some(daemon::protocol::ResponseFormat::Compact),
+        OutputFormat::Json => Some(daemon::protocol::ResponseFormat::Json),
+        OutputFormat::Table => None,
+    }
+}
+
 /// Handle a daemon response: print success data and return Ok(()),
 /// or print error and return None to fall through to local execution.
+///
+/// Compact-format responses arrive as a JSON string and are printed verbatim. A daemon
+/// on another protocol version may have ignored request fields it does not know, so its
+/// responses are discarded and the query runs locally.
 fn handle_daemon_response(resp: Option<daemon::protocol::DaemonResponse>) -> Option<Result<()>> {
-    match resp? {
+    let resp = resp?;
+    let (daemon::protocol::DaemonResponse::Success { version, .. }
+    | daemon::protocol::DaemonResponse::Error { version, .. }) = &resp;
+    if *version != daemon::protocol::PROTOCOL_VERSION {
+        return None;
+    }
+    match resp {
+        daemon::protocol::DaemonResponse::Success {
+            data: serde_json::Value::String(text),
+            ..
+        } => {
+            print!("{}", text);
+            Some(Ok(()))
+        }
         daemon::protocol::DaemonResponse::Success { data, .. } => {
             match serde_json::to_string_pretty(&data) {
                 Ok(json) => {
@@ -441,10 +614,12 @@ fn handle_daemon_response(resp: Option<daemon::protocol::DaemonResponse>) -> Opt
 /// Resolve the project root path from either a `--project` alias or the standard `path` option.
 ///
 /// When `--project <alias>` is provided, look up the alias in the registry and use that path.
-/// Otherwise, fall back to `project::resolve_project_root(path)` (auto-detect from cwd).
+/// Otherwise, fall back to `project::resolve_project_root(path)` (auto-detect from cwd), and
+/// refuse roots that do not look like a project unless `force` is set.
 fn resolve_project_or_path(
     project_alias: Option<String>,
     path: Option<PathBuf>,
+    force: bool,
 ) -> Result<PathBuf> {
     if let Some(alias) = project_alias {
         let reg = registry::ProjectRegistry::new();
@@ -457,12 +632,26 @@ fn resolve_project_or_path(
             ),
         }
     } else {
-        Ok(project::resolve_project_root(path))
+        let root = project::resolve_project_root(path);
+        if !force {
+            project::check_index_root(&root)?;
+        }
+        Ok(root)
     }
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    pager::configure(cli.no_pager, cli.max_path_width);
+    let result = run(cli);
+    pager::finish();
+    result
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let force = cli.force;
+    cache::envelope::set_paranoid(cli.paranoid);
+    cache::lock::set_disabled(cli.no_lock);
 
     match cli.command {
         Commands::Index {
@@ -470,11 +659,19 @@ fn main() -> Result<()> {
             verbose,
             json,
             language,
+            dry_run,
+            explain_ignores,
             #[cfg(feature = "rag")]
             no_embeddings,
         } => {
+            let path = project::resolve_project_root(Some(path));
+            if !force {
+                project::check_index_root(&path)?;
+            }
+
             // 1. Load config (always succeeds — defaults when file is absent).
             let config = CodeGraphConfig::load(&path);
+            parser::dynamic::install(&path, &config.grammar);
 
             // 2. Parse --language flag values into a language filter set.
             // When --language is not specified, auto-detect from config files at project root.
@@ -500,6 +697,15 @@ fn main() -> Result<()> {
                 Some(set)
             };
 
+            if dry_run {
+                let entries = walker::explain_walk(&path, &config, allowed_languages.as_ref())?;
+                output::print_dry_run(&entries, &path, explain_ignores, json);
+                return Ok(());
+            }
+
+            // Held until the cache is saved, so concurrent commands wait for this build.
+            let _lock = cache::lock::acquire(&path)?;
+
             // 3. Start timer.
             let start = std::time::Instant::now();
 
@@ -551,14 +757,19 @@ fn main() -> Result<()> {
                         ImportKind::Esm => esm_imports += 1,
                         ImportKind::Cjs => cjs_imports += 1,
                         ImportKind::DynamicImport => dynamic_imports += 1,
-                        // Python and Go import kinds — counted in total_imports but not in per-kind counters
+                        // Python, Go, proto, Zig, shell, stylesheet, and external grammar import kinds — counted in total_imports but not in per-kind counters
                         ImportKind::PythonAbsolute
                         | ImportKind::PythonRelative { .. }
                         | ImportKind::PythonConditionalAbsolute
                         | ImportKind::PythonConditionalRelative { .. }
                         | ImportKind::GoAbsolute
                         | ImportKind::GoBlank
-                        | ImportKind::GoDot => {}
+                        | ImportKind::GoDot
+                        | ImportKind::ProtoImport
+                        | ImportKind::ZigImport
+                        | ImportKind::ShellSource
+                        | ImportKind::CssImport
+                        | ImportKind::GrammarImport => {}
                     }
                 }
                 for rust_use in &result.rust_uses {
@@ -597,6 +808,24 @@ fn main() -> Result<()> {
             crate::query::decorators::enrich_decorator_frameworks(&mut graph);
             crate::query::decorators::add_has_decorator_edges(&mut graph);
 
+            // Link class methods to the base-class and interface methods they override, and
+            // Rust impl methods to the trait methods they implement.
+            resolver::overrides::link_overrides(&mut graph);
+
+            // Mark test files and link them to the files and symbols they import.
+            resolver::tests_resolver::link_tests(&mut graph, &path);
+
+            // Link crate roots to the dependencies their `Cargo.toml` declares.
+            resolver::cargo_deps::link_cargo_dependencies(&mut graph, &path);
+
+            // Opt-in: link files to the symbols their string literals name.
+            resolver::string_refs::link_string_refs(&mut graph, &config.string_refs);
+
+            // Tag files and symbols with the project's `[[category]]` rules, and unindex the
+            // symbols `[symbols] exclude` filters out.
+            crate::query::categories::apply_categories(&mut graph, &path, &config.category);
+            crate::query::exclusions::apply_symbol_exclusions(&mut graph, &config.symbols.exclude);
+
             // 8. Compute stats from graph.
             let elapsed_secs = start.elapsed().as_secs_f64();
             let breakdown: HashMap<SymbolKind, usize> = graph.symbols_by_kind();
@@ -762,6 +991,83 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::IndexAll {
+            projects,
+            jobs,
+            json,
+        } => {
+            let registry = registry::ProjectRegistry::new();
+            let targets: Vec<(String, PathBuf)> = if projects.is_empty() {
+                registry
+                    .list()
+                    .into_iter()
+                    .map(|entry| (entry.alias, entry.path))
+                    .collect()
+            } else {
+                projects
+                    .iter()
+                    .map(|name| match registry.get(name) {
+                        Some(entry) => (entry.alias, entry.path),
+                        None => (
+                            name.clone(),
+                            project::resolve_project_root(Some(name.into())),
+                        ),
+                    })
+                    .collect()
+            };
+            if targets.is_empty() {
+                anyhow::bail!(
+                    "no projects to index — pass --projects or register some with: code-graph project add <alias> <path>"
+                );
+            }
+
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs.unwrap_or(0))
+                .build()?;
+            // Per-project parsing runs on the same pool, sharing its threads.
+            let rows: Vec<output::IndexAllRow> = pool.install(|| {
+                targets
+                    .par_iter()
+                    .map(|(name, root)| {
+                        let start = std::time::Instant::now();
+                        let indexed = (|| -> Result<CodeGraph> {
+                            if !force {
+                                project::check_index_root(root)?;
+                            }
+                            let _lock = cache::lock::acquire(root)?;
+                            let graph = build_graph(root, false)?;
+                            cache::save_cache(root, &graph)?;
+                            Ok(graph)
+                        })();
+                        let (files, symbols, edges, error) = match indexed {
+                            Ok(graph) => (
+                                graph.file_count(),
+                                graph.symbol_count(),
+                                graph.graph.edge_count(),
+                                None,
+                            ),
+                            Err(e) => (0, 0, 0, Some(format!("{e:#}"))),
+                        };
+                        output::IndexAllRow {
+                            project: name.clone(),
+                            path: root.clone(),
+                            files,
+                            symbols,
+                            edges,
+                            elapsed_secs: start.elapsed().as_secs_f64(),
+                            error,
+                        }
+                    })
+                    .collect()
+            });
+
+            output::print_index_all_summary(&rows, json);
+            let failed = rows.iter().filter(|r| r.error.is_some()).count();
+            if failed > 0 {
+                anyhow::bail!("{} of {} projects failed to index", failed, rows.len());
+            }
+        }
+
         Commands::Find {
             path,
             project,
@@ -771,32 +1077,55 @@ fn main() -> Result<()> {
             file,
             format,
             language,
+            category,
+            visibility,
+            group_by,
+            sort,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
+            let visibility = visibility
+                .iter()
+                .map(|v| query::find::parse_visibility(v))
+                .collect::<Result<Vec<_>>>()?;
+
+            let symbol = match symbol {
+                Some(symbol) => symbol,
+                None if !kind.is_empty() => query::find::MATCH_ALL.to_string(),
+                None => anyhow::bail!("find needs a symbol pattern or --kind"),
+            };
 
             // Validate regex FIRST before the expensive index pipeline (Research Pitfall 4).
-            regex::RegexBuilder::new(&symbol)
-                .case_insensitive(case_insensitive)
-                .build()
-                .map_err(|e| anyhow::anyhow!("invalid symbol pattern '{}': {}", symbol, e))?;
+            if symbol != query::find::MATCH_ALL {
+                regex::RegexBuilder::new(&symbol)
+                    .case_insensitive(case_insensitive)
+                    .build()
+                    .map_err(|e| anyhow::anyhow!("invalid symbol pattern '{}': {}", symbol, e))?;
+            }
 
             let language_filter = parse_language_filter(language.as_deref())?;
 
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &path,
-                &daemon::protocol::DaemonRequest::Find {
-                    symbol: symbol.clone(),
-                    case_insensitive,
-                    kind: kind.clone(),
-                    file: file.clone(),
-                    language: language.clone(),
-                },
-            )) {
+            if category.is_none()
+                && visibility.is_empty()
+                && group_by.is_none()
+                && sort.is_none()
+                && let Some(response_format) = daemon_response_format(&format)
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &path,
+                    &daemon::protocol::DaemonRequest::Find {
+                        symbol: symbol.clone(),
+                        case_insensitive,
+                        kind: kind.clone(),
+                        file: file.clone(),
+                        language: language.clone(),
+                        format: response_format,
+                    },
+                ))
+            {
                 return result;
             }
 
             let graph = cache::load_or_build(&path, false)?;
-            let results = query::find::find_symbol(
+            let mut results = query::find::find_symbol(
                 &graph,
                 &symbol,
                 case_insensitive,
@@ -805,6 +1134,20 @@ fn main() -> Result<()> {
                 &path,
                 language_filter,
             )?;
+            if let Some(ref category) = category {
+                results.retain(|r| {
+                    query::categories::symbol_in_category(
+                        &graph,
+                        &r.file_path,
+                        &r.symbol_name,
+                        r.line,
+                        category,
+                    )
+                });
+            }
+            if !visibility.is_empty() {
+                results.retain(|r| visibility.contains(&r.visibility));
+            }
 
             if results.is_empty() {
                 if let Some(lang) = language_filter {
@@ -812,13 +1155,19 @@ fn main() -> Result<()> {
                         "No {} symbols found. Run `code-graph stats` to see indexed languages.",
                         lang
                     );
+                } else if symbol == query::find::MATCH_ALL && !kind.is_empty() {
+                    eprintln!("no {} symbols found", kind.join("/"));
                 } else {
                     eprintln!("no symbols matching '{}' found", symbol);
                 }
                 std::process::exit(1);
             }
 
-            query::output::format_find_results(&results, &format, &path, &symbol);
+            let mut arrangement = result_arrangement(&graph, &path, group_by, sort);
+            if sort == Some(cli::SortBy::Refs) {
+                arrangement.refs = query::refs::reference_counts(&graph, &results, &path);
+            }
+            query::output::format_find_results(&results, &format, &path, &symbol, &arrangement);
         }
 
         Commands::Stats {
@@ -826,21 +1175,39 @@ fn main() -> Result<()> {
             project,
             format,
             language,
+            scope,
+            files,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
             let language_filter = parse_language_filter(language.as_deref())?;
 
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &path,
-                &daemon::protocol::DaemonRequest::Stats {
-                    language: language.clone(),
-                },
-            )) {
+            if files.is_none()
+                && let Some(response_format) = daemon_response_format(&format)
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &path,
+                    &daemon::protocol::DaemonRequest::Stats {
+                        language: language.clone(),
+                        scope: scope.clone(),
+                        format: response_format,
+                    },
+                ))
+            {
                 return result;
             }
 
             let graph = cache::load_or_build(&path, false)?;
-            let stats = query::stats::project_stats(&graph);
+            let abs_scope = scope.map(|s| path.join(s));
+            let in_scope = |file: &Path| {
+                abs_scope.as_ref().is_none_or(|s| file.starts_with(s))
+                    && language_filter.is_none_or(|lang| file_language_matches(file, lang))
+            };
+            if let Some(n) = files {
+                let mut file_stats = query::stats::file_stats(&graph);
+                file_stats.retain(|f| in_scope(&f.path));
+                query::output::format_file_rankings(&file_stats, n, &format, &path);
+                return Ok(());
+            }
+            let stats = query::stats::project_stats_for_files(&graph, &path, in_scope);
             query::output::format_stats(&stats, &format, language_filter);
         }
 
@@ -853,8 +1220,12 @@ fn main() -> Result<()> {
             file: _,
             format,
             language,
+            ref_kind,
+            exclude_derived,
+            group_by,
+            sort,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             // Validate regex FIRST before the expensive index pipeline.
             regex::RegexBuilder::new(&symbol)
@@ -863,17 +1234,33 @@ fn main() -> Result<()> {
                 .map_err(|e| anyhow::anyhow!("invalid symbol pattern '{}': {}", symbol, e))?;
 
             let language_filter = parse_language_filter(language.as_deref())?;
+            const REF_KINDS: &[&str] = &["import", "call", "render", "read", "write"];
+            if let Some(bad) = ref_kind.iter().find(|k| !REF_KINDS.contains(&k.as_str())) {
+                anyhow::bail!(
+                    "unknown ref kind '{}' (expected one of: {})",
+                    bad,
+                    REF_KINDS.join(", ")
+                );
+            }
 
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &path,
-                &daemon::protocol::DaemonRequest::Refs {
-                    symbol: symbol.clone(),
-                    case_insensitive,
-                    kind: vec![],
-                    file: None,
-                    language: language.clone(),
-                },
-            )) {
+            // The daemon does not filter by reference kind or provenance, or arrange results.
+            if ref_kind.is_empty()
+                && !exclude_derived
+                && group_by.is_none()
+                && sort.is_none()
+                && let Some(response_format) = daemon_response_format(&format)
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &path,
+                    &daemon::protocol::DaemonRequest::Refs {
+                        symbol: symbol.clone(),
+                        case_insensitive,
+                        kind: vec![],
+                        file: None,
+                        language: language.clone(),
+                        format: response_format,
+                    },
+                ))
+            {
                 return result;
             }
 
@@ -897,6 +1284,12 @@ fn main() -> Result<()> {
             if let Some(lang) = language_filter {
                 results.retain(|r| file_language_matches(&r.file_path, lang));
             }
+            if !ref_kind.is_empty() {
+                results.retain(|r| ref_kind.iter().any(|k| k == r.ref_kind.as_str()));
+            }
+            if exclude_derived {
+                results.retain(|r| !r.derived);
+            }
 
             if results.is_empty() {
                 if let Some(lang) = language_filter {
@@ -908,7 +1301,8 @@ fn main() -> Result<()> {
                     eprintln!("no references to '{}' found", symbol);
                 }
             } else {
-                query::output::format_refs_results(&results, &format, &path, &symbol);
+                let arrangement = result_arrangement(&graph, &path, group_by, sort);
+                query::output::format_refs_results(&results, &format, &path, &symbol, &arrangement);
             }
         }
 
@@ -920,8 +1314,19 @@ fn main() -> Result<()> {
             tree,
             format,
             language,
+            notify_format,
+            category,
+            runtime_only,
+            by_dir,
+            tests,
+            estimate,
+            max_depth,
+            edges,
+            exclude_tests,
+            group_by,
+            sort,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             // Validate regex FIRST.
             regex::RegexBuilder::new(&symbol)
@@ -931,15 +1336,29 @@ fn main() -> Result<()> {
 
             let language_filter = parse_language_filter(language.as_deref())?;
 
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &path,
-                &daemon::protocol::DaemonRequest::Impact {
-                    symbol: symbol.clone(),
-                    case_insensitive,
-                    tree,
-                    language: language.clone(),
-                },
-            )) {
+            if notify_format.is_none()
+                && category.is_none()
+                && !runtime_only
+                && !by_dir
+                && !tests
+                && !estimate
+                && max_depth.is_none()
+                && edges.is_empty()
+                && !exclude_tests
+                && group_by.is_none()
+                && sort.is_none()
+                && let Some(response_format) = daemon_response_format(&format)
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &path,
+                    &daemon::protocol::DaemonRequest::Impact {
+                        symbol: symbol.clone(),
+                        case_insensitive,
+                        tree,
+                        language: language.clone(),
+                        format: response_format,
+                    },
+                ))
+            {
                 return result;
             }
 
@@ -956,14 +1375,95 @@ fn main() -> Result<()> {
                 .flat_map(|(_, indices)| indices.iter().copied())
                 .collect();
 
-            let mut results = query::impact::blast_radius(&graph, &all_indices, &path);
+            let options = query::impact::ImpactOptions {
+                max_depth,
+                edges: if edges.is_empty() {
+                    query::impact::ImpactEdges::default()
+                } else {
+                    query::impact::ImpactEdges {
+                        imports: edges.contains(&cli::ImpactEdge::Imports),
+                        calls: edges.contains(&cli::ImpactEdge::Calls),
+                        reexports: edges.contains(&cli::ImpactEdge::Reexports),
+                    }
+                },
+                runtime_only,
+                exclude_tests,
+            };
+            let mut results =
+                query::impact::blast_radius_with(&graph, &all_indices, &path, &options);
+            if tests {
+                results = query::impact::affected_tests(&graph, &all_indices, results);
+            }
 
             // Apply language filter as post-filter on file path extension.
             if let Some(lang) = language_filter {
                 results.retain(|r| file_language_matches(&r.file_path, lang));
             }
+            if let Some(ref category) = category {
+                results.retain(|r| {
+                    graph.file_index.get(&r.file_path).is_some_and(|&idx| {
+                        query::categories::file_in_category(&graph, idx, category)
+                    })
+                });
+            }
+
+            if let Some(notify) = notify_format {
+                let owners = query::owners::CodeOwners::load(&path);
+                if owners.is_none() {
+                    eprintln!("no CODEOWNERS file found; all affected files are unowned");
+                }
+                let groups = query::owners::group_by_owner(&results, owners.as_ref());
+                let mut changed_files: Vec<PathBuf> = all_indices
+                    .iter()
+                    .filter_map(|&idx| query::util::find_containing_file_idx(&graph, idx))
+                    .filter_map(|file_idx| match &graph.graph[file_idx] {
+                        graph::node::GraphNode::File(fi) => Some(fi.path.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                changed_files.sort();
+                changed_files.dedup();
+                print!(
+                    "{}",
+                    query::output::format_impact_notifications(
+                        &groups,
+                        &notify,
+                        &format,
+                        &path,
+                        &symbol,
+                        &changed_files,
+                    )
+                );
+                return Ok(());
+            }
 
-            query::output::format_impact_results(&results, &format, &path, tree, &symbol);
+            if estimate {
+                let estimate = query::impact::estimate_effort(&graph, &results, &path);
+                query::output::format_impact_estimate(&estimate, &format, &symbol);
+                return Ok(());
+            }
+
+            if by_dir {
+                let dirs = query::impact::group_by_dir(&results, &path);
+                query::output::format_impact_dirs(&dirs, &format, &symbol);
+                return Ok(());
+            }
+
+            let mut arrangement = result_arrangement(&graph, &path, group_by, sort);
+            if sort == Some(cli::SortBy::Refs) {
+                arrangement.refs = query::stats::file_stats(&graph)
+                    .into_iter()
+                    .map(|f| ((f.path, 0), f.fan_in))
+                    .collect();
+            }
+            query::output::format_impact_results(
+                &results,
+                &format,
+                &path,
+                tree,
+                &symbol,
+                &arrangement,
+            );
         }
 
         Commands::Circular {
@@ -971,21 +1471,44 @@ fn main() -> Result<()> {
             project,
             format,
             language,
+            max_cycles,
+            runtime_only,
+            granularity,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
             let language_filter = parse_language_filter(language.as_deref())?;
 
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &path,
-                &daemon::protocol::DaemonRequest::Circular {
-                    language: language.clone(),
-                },
-            )) {
+            if let cli::CycleGranularity::Symbol = granularity {
+                let graph = cache::load_or_build(&path, false)?;
+                let mut groups = query::circular::find_symbol_circular(&graph, &path, max_cycles);
+                if let Some(lang) = language_filter {
+                    groups.retain(|g| {
+                        g.symbols
+                            .iter()
+                            .all(|s| file_language_matches(Path::new(&s.file), lang))
+                    });
+                }
+                query::output::format_symbol_circular_results(&groups, &format);
+                return Ok(());
+            }
+
+            if !runtime_only
+                && let Some(response_format) = daemon_response_format(&format)
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &path,
+                    &daemon::protocol::DaemonRequest::Circular {
+                        language: language.clone(),
+                        format: response_format,
+                        max_cycles,
+                    },
+                ))
+            {
                 return result;
             }
 
             let graph = cache::load_or_build(&path, false)?;
-            let mut cycles = query::circular::find_circular(&graph, &path);
+            let mut cycles =
+                query::circular::find_circular(&graph, &path, max_cycles, runtime_only);
 
             // Apply language filter: retain cycles where all files match the language.
             if let Some(lang) = language_filter {
@@ -1006,8 +1529,9 @@ fn main() -> Result<()> {
             case_insensitive,
             format,
             language,
+            source,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             // Validate regex FIRST before the expensive index pipeline.
             regex::RegexBuilder::new(&symbol)
@@ -1017,14 +1541,19 @@ fn main() -> Result<()> {
 
             let language_filter = parse_language_filter(language.as_deref())?;
 
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &path,
-                &daemon::protocol::DaemonRequest::Context {
-                    symbol: symbol.clone(),
-                    case_insensitive,
-                    language: language.clone(),
-                },
-            )) {
+            // The daemon answers without source snippets.
+            if source.is_none()
+                && let Some(response_format) = daemon_response_format(&format)
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &path,
+                    &daemon::protocol::DaemonRequest::Context {
+                        symbol: symbol.clone(),
+                        case_insensitive,
+                        language: language.clone(),
+                        format: response_format,
+                    },
+                ))
+            {
                 return result;
             }
 
@@ -1042,6 +1571,14 @@ fn main() -> Result<()> {
                 .map(|(name, indices)| query::context::symbol_context(&graph, name, indices, &path))
                 .collect();
 
+            // Attach source before the language filter drops contexts, while `results` still
+            // lines up with `matches`.
+            if let Some(context_lines) = source {
+                for (ctx, (_, indices)) in results.iter_mut().zip(&matches) {
+                    query::context::attach_source(&graph, ctx, indices, context_lines);
+                }
+            }
+
             // Apply language filter to context results: filter definition/reference file paths.
             if let Some(lang) = language_filter {
                 for ctx in &mut results {
@@ -1053,6 +1590,8 @@ fn main() -> Result<()> {
                         .retain(|c| file_language_matches(&c.file_path, lang));
                     ctx.callees
                         .retain(|c| file_language_matches(&c.file_path, lang));
+                    ctx.source
+                        .retain(|s| file_language_matches(&s.file_path, lang));
                 }
                 results.retain(|ctx| !ctx.definitions.is_empty());
             }
@@ -1119,21 +1658,24 @@ fn main() -> Result<()> {
             symbol,
             depth,
             exclude,
+            category,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &path,
-                &daemon::protocol::DaemonRequest::Export {
-                    format: format!("{:?}", format).to_lowercase(),
-                    granularity: format!("{:?}", granularity).to_lowercase(),
-                    stdout,
-                    root: root.clone(),
-                    symbol: symbol.clone(),
-                    depth,
-                    exclude: exclude.clone(),
-                },
-            )) {
+            if category.is_empty()
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &path,
+                    &daemon::protocol::DaemonRequest::Export {
+                        format: format!("{:?}", format).to_lowercase(),
+                        granularity: format!("{:?}", granularity).to_lowercase(),
+                        stdout,
+                        root: root.clone(),
+                        symbol: symbol.clone(),
+                        depth,
+                        exclude: exclude.clone(),
+                    },
+                ))
+            {
                 return result;
             }
 
@@ -1145,15 +1687,16 @@ fn main() -> Result<()> {
                 symbol_filter: symbol,
                 depth,
                 exclude_patterns: exclude,
+                category_filter: category,
                 project_root: path.clone(),
                 stdout,
             };
-            let result = export::export_graph(&graph, &params)?;
 
-            if stdout {
-                print!("{}", result.content);
+            let result = if stdout {
+                let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+                export::export_graph(&graph, &params, &mut out)?
             } else {
-                // Write to .code-graph/graph.{dot|mmd}
+                // Stream to .code-graph/graph.{dot|mmd}
                 let output_dir = path.join(".code-graph");
                 std::fs::create_dir_all(&output_dir)?;
                 let ext = match params.format {
@@ -1161,7 +1704,8 @@ fn main() -> Result<()> {
                     export::model::ExportFormat::Mermaid => "mmd",
                 };
                 let output_path = output_dir.join(format!("graph.{}", ext));
-                std::fs::write(&output_path, &result.content)?;
+                let mut out = std::io::BufWriter::new(std::fs::File::create(&output_path)?);
+                let result = export::export_graph(&graph, &params, &mut out)?;
                 // Summary to stderr (keeps stdout clean for --stdout piping).
                 eprintln!(
                     "Exported {} nodes, {} edges to {}",
@@ -1169,7 +1713,8 @@ fn main() -> Result<()> {
                     result.edge_count,
                     output_path.display()
                 );
-            }
+                result
+            };
 
             // Print any advisory warnings from scale guards.
             for warning in &result.warnings {
@@ -1186,6 +1731,9 @@ fn main() -> Result<()> {
             ..
         } => {
             let root = std::fs::canonicalize(path)?;
+            if !force {
+                project::check_index_root(&root)?;
+            }
             #[cfg(feature = "rag")]
             let use_ollama = ollama;
             #[cfg(not(feature = "rag"))]
@@ -1195,6 +1743,9 @@ fn main() -> Result<()> {
 
         Commands::Watch { path } => {
             let path = project::resolve_project_root(path);
+            if !force {
+                project::check_index_root(&path)?;
+            }
             eprintln!("Indexing {}...", path.display());
             let mut graph = build_graph(&path, false)?;
             eprintln!(
@@ -1215,12 +1766,33 @@ fn main() -> Result<()> {
             // Keep handle alive — dropping it stops the watcher
             let _handle = handle;
 
+            // Per-commit snapshots: HEAD is checked only once the watcher has been idle,
+            // so the file events of a checkout are applied before the snapshot is taken.
+            let mut commit_snapshots = (CodeGraphConfig::load(&path).snapshot.auto
+                == config::SnapshotAuto::PerCommit)
+                .then(query::diff::CommitSnapshotter::default);
+
             eprintln!("Watching for changes... (press Ctrl+C to stop)");
 
             // Process events — terminal status output goes to stderr (Phase 1 convention)
-            while let Ok(event) = rx.recv() {
+            loop {
+                let event = match rx.recv_timeout(query::diff::HEAD_POLL_INTERVAL) {
+                    Ok(event) => event,
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                        if let Some(snapshotter) = commit_snapshots.as_mut() {
+                            match snapshotter.poll(&graph, &path) {
+                                Ok(Some(name)) => eprintln!("[watch] snapshot '{}' captured", name),
+                                Ok(None) => {}
+                                Err(e) => eprintln!("[watch] snapshot failed: {}", e),
+                            }
+                        }
+                        continue;
+                    }
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+                };
                 match &event {
                     watcher::event::WatchEvent::Modified(p) => {
+                        let before = query::diff::file_snapshot(&graph, &path, p);
                         let start = std::time::Instant::now();
                         watcher::incremental::handle_file_event(&mut graph, &event, &path);
                         let elapsed = start.elapsed();
@@ -1229,9 +1801,16 @@ fn main() -> Result<()> {
                             p.strip_prefix(&path).unwrap_or(p).display(),
                             elapsed.as_secs_f64() * 1000.0,
                         );
+                        // Announce which symbols this edit added, removed, or moved.
+                        let after = query::diff::file_snapshot(&graph, &path, p);
+                        let changes = query::diff::diff_snapshots(&before, &after);
+                        for line in query::output::format_file_diff_to_string(&changes).lines() {
+                            eprintln!("[watch]   {}", line);
+                        }
                         let _ = cache::save_cache(&path, &graph);
                     }
                     watcher::event::WatchEvent::Deleted(p) => {
+                        let before = query::diff::file_snapshot(&graph, &path, p);
                         watcher::incremental::handle_file_event(&mut graph, &event, &path);
                         eprintln!(
                             "[watch] deleted: {} ({} files, {} symbols)",
@@ -1239,6 +1818,12 @@ fn main() -> Result<()> {
                             graph.file_count(),
                             graph.symbol_count()
                         );
+                        // Announce the symbols that went away with the file.
+                        let after = query::diff::file_snapshot(&graph, &path, p);
+                        let changes = query::diff::diff_snapshots(&before, &after);
+                        for line in query::output::format_file_diff_to_string(&changes).lines() {
+                            eprintln!("[watch]   {}", line);
+                        }
                         let _ = cache::save_cache(&path, &graph);
                     }
                     watcher::event::WatchEvent::ConfigChanged => {
@@ -1277,23 +1862,30 @@ fn main() -> Result<()> {
             root,
             project,
             depth,
+            modules,
             format,
         } => {
-            let project_root = resolve_project_or_path(project, root)?;
-
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &project_root,
-                &daemon::protocol::DaemonRequest::Structure {
-                    path: path.clone(),
-                    depth,
-                },
-            )) {
+            let project_root = resolve_project_or_path(project, root, force)?;
+
+            if !modules
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &project_root,
+                    &daemon::protocol::DaemonRequest::Structure {
+                        path: path.clone(),
+                        depth,
+                    },
+                ))
+            {
                 return result;
             }
 
             let graph = cache::load_or_build(&project_root, false)?;
-            let tree =
-                query::structure::file_structure(&graph, &project_root, path.as_deref(), depth);
+            let tree = if modules {
+                let module = path.as_ref().map(|p| p.to_string_lossy().into_owned());
+                query::structure::module_structure(&graph, module.as_deref(), depth)
+            } else {
+                query::structure::file_structure(&graph, &project_root, path.as_deref(), depth)
+            };
             match format {
                 cli::OutputFormat::Json => {
                     println!("{}", serde_json::to_string_pretty(&tree)?);
@@ -1311,7 +1903,7 @@ fn main() -> Result<()> {
             project,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
@@ -1344,7 +1936,7 @@ fn main() -> Result<()> {
             project,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
@@ -1374,36 +1966,117 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::Deps {
+            file,
+            path,
+            project,
+            depth,
+            runtime_only,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let result = query::deps::file_deps(
+                &graph,
+                &path,
+                &file,
+                petgraph::Direction::Outgoing,
+                depth,
+                runtime_only,
+            )?;
+            query::output::format_file_deps(&result, "deps", &format);
+        }
+
+        Commands::Rdeps {
+            file,
+            path,
+            project,
+            depth,
+            runtime_only,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let result = query::deps::file_deps(
+                &graph,
+                &path,
+                &file,
+                petgraph::Direction::Incoming,
+                depth,
+                runtime_only,
+            )?;
+            query::output::format_file_deps(&result, "rdeps", &format);
+        }
+
         Commands::DeadCode {
             path,
             project,
             scope,
+            tier,
+            fix_list,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
                 &daemon::protocol::DaemonRequest::DeadCode {
                     scope: scope.clone(),
+                    tier,
+                    fix_list,
                 },
             )) {
                 return result;
             }
 
             let graph = cache::load_or_build(&path, false)?;
-            let result = query::dead_code::find_dead_code(&graph, &path, scope.as_deref());
+            let mut result = query::dead_code::find_dead_code(&graph, &path, scope.as_deref());
+            if let Some(tier) = tier {
+                result.retain_tier(tier);
+            }
+            if fix_list {
+                result.retain_unnamed_elsewhere(&graph);
+                let output = query::output::format_dead_code_fix_list(&result, &path, tier);
+                if !output.is_empty() {
+                    println!("{}", output);
+                }
+                return Ok(());
+            }
             match format {
                 cli::OutputFormat::Json => {
                     println!("{}", serde_json::to_string_pretty(&result)?);
                 }
                 _ => {
-                    let output = query::output::format_dead_code_to_string(&result, &path);
+                    let output = query::output::format_dead_code_to_string(&result, &path, tier);
                     println!("{}", output);
                 }
             }
         }
 
+        Commands::UnusedExports {
+            path,
+            project,
+            scope,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let report =
+                query::unused_exports::find_unused_exports(&graph, &path, scope.as_deref());
+            query::output::format_unused_exports(&report, &format);
+        }
+
+        Commands::UnusedDeps {
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let report = query::unused_deps::find_unused_dependencies(&graph, &path);
+            query::output::format_unused_deps(&report, &format);
+        }
+
         Commands::Clones {
             path,
             project,
@@ -1411,7 +2084,7 @@ fn main() -> Result<()> {
             scope,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
@@ -1430,6 +2103,7 @@ fn main() -> Result<()> {
                     println!("{}", serde_json::to_string_pretty(&result)?);
                 }
                 cli::OutputFormat::Table => {
+                    pager::start();
                     let output = query::output::format_clones_table(&result, &path);
                     println!("{}", output);
                 }
@@ -1440,6 +2114,24 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::Similar {
+            path,
+            project,
+            threshold,
+            min_lines,
+            scope,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            if !(0.0..=1.0).contains(&threshold) {
+                anyhow::bail!("--threshold must be between 0 and 1, got {}", threshold);
+            }
+            let graph = cache::load_or_build(&path, false)?;
+            let report =
+                query::similar::find_similar(&graph, &path, scope.as_deref(), threshold, min_lines);
+            query::output::format_similar(&report, &format);
+        }
+
         Commands::Diff {
             path,
             project,
@@ -1447,7 +2139,7 @@ fn main() -> Result<()> {
             to,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
@@ -1477,13 +2169,70 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::DiffRev {
+            from,
+            to,
+            path,
+            project,
+            format,
+            verbose,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let diff = query::diff::compute_rev_diff(&path, &from, &to, verbose)?;
+            match format {
+                cli::OutputFormat::Json => {
+                    println!("{}", serde_json::to_string_pretty(&diff)?);
+                }
+                _ => {
+                    println!("{}", query::output::format_diff_to_string(&diff));
+                }
+            }
+        }
+
+        Commands::Touched {
+            rev,
+            path,
+            project,
+            format,
+            verbose,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let report = query::touched::touched_symbols(&path, &rev, verbose)?;
+            query::output::format_touched(&report, &format);
+        }
+
+        Commands::ApiDiff {
+            from,
+            to,
+            snapshots,
+            path,
+            project,
+            format,
+            verbose,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let diff = if snapshots {
+                query::api_diff::compute_snapshot_api_diff(&path, &from, &to)?
+            } else {
+                query::api_diff::compute_api_diff(&path, &from, &to, verbose)?
+            };
+            match format {
+                cli::OutputFormat::Json => {
+                    println!("{}", serde_json::to_string_pretty(&diff)?);
+                }
+                _ => {
+                    print!("{}", query::output::format_api_diff_markdown(&diff));
+                }
+            }
+        }
+
         Commands::DiffImpact {
             base_ref,
             path,
             project,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
@@ -1537,6 +2286,20 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::SelectTests {
+            base,
+            path,
+            project,
+            cargo,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let changed = query::select_tests::changed_files(&path, &base)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let selection = query::select_tests::select_tests(&graph, &path, &base, &changed);
+            query::output::format_select_tests(&selection, cargo, &format);
+        }
+
         Commands::Decorators {
             pattern,
             path,
@@ -1545,7 +2308,7 @@ fn main() -> Result<()> {
             framework,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
@@ -1583,7 +2346,7 @@ fn main() -> Result<()> {
             scope,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
@@ -1621,7 +2384,7 @@ fn main() -> Result<()> {
             max_depth,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
             if let Some(result) = handle_daemon_response(try_daemon_query(
                 &path,
@@ -1648,6 +2411,49 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::Callgraph {
+            symbol,
+            path,
+            project,
+            depth,
+            direction,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let Some(indices) = graph.symbols_named(&symbol) else {
+                eprintln!("no symbols matching '{}' found", symbol);
+                std::process::exit(1);
+            };
+            let directions = query::callgraph::CallDirections {
+                callees: matches!(
+                    direction,
+                    cli::CallDirection::Out | cli::CallDirection::Both
+                ),
+                callers: matches!(direction, cli::CallDirection::In | cli::CallDirection::Both),
+            };
+            let roots = query::callgraph::call_graph(&graph, indices, depth, directions, &path);
+            query::output::format_callgraph(&roots, &format);
+        }
+
+        Commands::Path {
+            from,
+            to,
+            path,
+            project,
+            max_paths,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let result = query::dep_path::dependency_paths(&graph, &path, &from, &to, max_paths)?;
+            if result.paths.is_empty() && !matches!(format, cli::OutputFormat::Json) {
+                eprintln!("no dependency path from '{}' to '{}'", from, to);
+                std::process::exit(1);
+            }
+            query::output::format_dependency_paths(&result, &format);
+        }
+
         Commands::Project { action } => match action {
             cli::ProjectAction::Add { alias, path } => {
                 let reg = registry::ProjectRegistry::new();
@@ -1685,35 +2491,107 @@ fn main() -> Result<()> {
             }
         },
 
+        Commands::Config { action } => match action {
+            cli::ConfigAction::Check { path, json } => {
+                let path = project::resolve_project_root(path);
+                let report = CodeGraphConfig::check(&path);
+                if json {
+                    let values: Vec<serde_json::Value> = report
+                        .values
+                        .iter()
+                        .map(|v| {
+                            serde_json::json!({
+                                "key": v.key,
+                                "value": v.value,
+                                "source": v.source.to_string(),
+                            })
+                        })
+                        .collect();
+                    let out = serde_json::json!({
+                        "file": report.path,
+                        "found": report.file_found,
+                        "error": report.error,
+                        "warnings": report.warnings,
+                        "values": values,
+                    });
+                    println!("{}", serde_json::to_string_pretty(&out)?);
+                } else {
+                    let status = if report.file_found {
+                        ""
+                    } else {
+                        " (not found)"
+                    };
+                    println!("config {}{}", report.path.display(), status);
+                    if let Some(err) = &report.error {
+                        println!("error: {}", err);
+                    }
+                    for warning in &report.warnings {
+                        println!("warning: {}", warning);
+                    }
+                    let width = report.values.iter().map(|v| v.key.len()).max().unwrap_or(0);
+                    for v in &report.values {
+                        println!("{:<width$} = {}  # {}", v.key, v.value, v.source);
+                    }
+                }
+                if !report.is_clean() {
+                    std::process::exit(1);
+                }
+            }
+        },
+
         Commands::Setup { global, uninstall } => {
             setup::run(global, uninstall)?;
         }
 
+        Commands::Version { verbose, format } => {
+            version::print_version(verbose, &format);
+        }
+
+        Commands::Capabilities { format } => {
+            version::print_capabilities(&format);
+        }
+
+        Commands::SelfUpdate { check } => {
+            version::self_update(check)?;
+        }
+
         Commands::Rename {
             symbol,
             new_name,
+            dry_run,
             path,
             project,
             format,
         } => {
-            let path = resolve_project_or_path(project, path)?;
+            let path = resolve_project_or_path(project, path, force)?;
 
-            if let Some(result) = handle_daemon_response(try_daemon_query(
-                &path,
-                &daemon::protocol::DaemonRequest::Rename {
-                    symbol: symbol.clone(),
-                    new_name: new_name.clone(),
-                },
-            )) {
+            // The daemon answers with the flat plan; the grouped preview is built locally.
+            if !dry_run
+                && let Some(result) = handle_daemon_response(try_daemon_query(
+                    &path,
+                    &daemon::protocol::DaemonRequest::Rename {
+                        symbol: symbol.clone(),
+                        new_name: new_name.clone(),
+                    },
+                ))
+            {
                 return result;
             }
 
             let graph = cache::load_or_build(&path, false)?;
             let items = query::rename::plan_rename(&graph, &symbol, &new_name, &path);
             match format {
+                cli::OutputFormat::Json if dry_run => {
+                    let files = query::rename::group_by_file(&items);
+                    println!("{}", serde_json::to_string_pretty(&files)?);
+                }
                 cli::OutputFormat::Json => {
                     println!("{}", serde_json::to_string_pretty(&items)?);
                 }
+                _ if dry_run => {
+                    let output = query::output::format_rename_preview_to_string(&items, &path);
+                    println!("{}", output);
+                }
                 _ => {
                     let output = query::output::format_rename_to_string(&items, &path);
                     println!("{}", output);
@@ -1721,6 +2599,282 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::Packages {
+            path,
+            project,
+            features,
+            all_features,
+            no_default_features,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let selection = resolver::cargo_features::FeatureSelection {
+                features,
+                all_features,
+                no_default_features,
+            };
+            let reports = resolver::cargo_features::package_reports(&path, &selection);
+            if reports.is_empty() {
+                anyhow::bail!("no Cargo crates found under {}", path.display());
+            }
+            match format {
+                cli::OutputFormat::Json => {
+                    println!("{}", serde_json::to_string_pretty(&reports)?);
+                }
+                _ => print!("{}", query::output::format_packages_to_string(&reports)),
+            }
+        }
+
+        Commands::Query {
+            query,
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let result = query::dsl::run_query(&graph, &path, &query)
+                .map_err(|e| anyhow::anyhow!("invalid query '{}': {}", query, e))?;
+            query::output::format_dsl_query_results(&result, &format, &path);
+        }
+
+        Commands::Repl { path, project } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            repl::run(&path, graph)?;
+        }
+
+        Commands::PkgQuery {
+            query,
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let packages = query::pkgql::PackageGraph::build(&graph, &path);
+            let results = query::pkgql::run_query(&packages, &query)
+                .map_err(|e| anyhow::anyhow!("invalid query '{}': {}", query, e))?;
+            query::output::format_pkg_query_results(&results, &format);
+        }
+
+        Commands::Duplicates {
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let report = query::duplicates::find_duplicates(&graph, &path);
+            query::output::format_duplicates(&report, &format);
+        }
+
+        Commands::Metrics {
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let metrics = query::metrics::package_metrics(&graph, &path);
+            query::output::format_package_metrics(&metrics, &format);
+        }
+
+        Commands::Coupling {
+            path,
+            project,
+            commits,
+            min_shared,
+            min_degree,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let options = query::coupling::CouplingOptions {
+                commits,
+                min_shared,
+                min_degree,
+            };
+            let report = query::coupling::find_coupling(&graph, &path, &options)?;
+            query::output::format_coupling(&report, &format);
+        }
+
+        Commands::Complexity {
+            path,
+            project,
+            top,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let functions = query::complexity::most_complex(&graph, &path, top);
+            query::output::format_complexity(&functions, &format);
+        }
+
+        Commands::Hotspots {
+            path,
+            project,
+            level,
+            churn,
+            commits,
+            limit,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let level = match level {
+                cli::HotspotLevel::File => query::hotspots::HotspotLevel::File,
+                cli::HotspotLevel::Symbol => query::hotspots::HotspotLevel::Symbol,
+            };
+            let report = query::hotspots::find_hotspots(
+                &graph,
+                &path,
+                level,
+                churn.then_some(commits),
+                limit,
+            )?;
+            query::output::format_hotspots(&report, &format);
+        }
+
+        Commands::Barrels {
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let report = query::barrels::audit_barrels(&graph, &path);
+            query::output::format_barrels(&report, &format);
+        }
+
+        Commands::Entrypoints {
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let report = query::entrypoints::find_entrypoints(&graph, &path);
+            query::output::format_entrypoints(&report, &format);
+        }
+
+        Commands::CutPoints {
+            path,
+            project,
+            top,
+            min_size,
+            include_tests,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let mut report =
+                query::cut_points::find_cut_points(&graph, &path, min_size, include_tests);
+            report.cut_points.truncate(top);
+            query::output::format_cut_points(&report, &format);
+        }
+
+        Commands::Report {
+            path,
+            project,
+            audience,
+            top,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            match audience {
+                cli::ReportAudience::Onboarding => {
+                    let report = query::report::onboarding_report(&graph, &path, top);
+                    query::output::format_onboarding_report(&report, &format);
+                }
+            }
+        }
+
+        Commands::Exports {
+            target,
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let report = query::exports::list_exports(&graph, &path, &target)?;
+            query::output::format_exports(&report, &format);
+        }
+
+        Commands::Api {
+            target,
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let report = query::exports::package_api(&graph, &path, &target)?;
+            query::output::format_api(&report, &format);
+        }
+
+        Commands::LintImports {
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let config = CodeGraphConfig::load(&path);
+            let report = query::lint_imports::lint_imports(&graph, &path, &config.lint_imports);
+            query::output::format_lint_imports(&report, &format);
+            if !report.violations.is_empty() {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Check {
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let config = CodeGraphConfig::load(&path);
+            if config.rules.is_empty() {
+                eprintln!("warning: no [[rules]] tables in code-graph.toml; nothing to check");
+            }
+            let graph = cache::load_or_build(&path, false)?;
+            let report = query::rules::check_rules(&graph, &path, &config.rules);
+            query::output::format_rules_check(&report, &format);
+            if !report.violations.is_empty() {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::CanDelete {
+            target,
+            path,
+            project,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let graph = cache::load_or_build(&path, false)?;
+            let report = query::can_delete::can_delete(&graph, &path, &target)?;
+            query::output::format_can_delete(&report, &format);
+        }
+
+        Commands::Hover {
+            position,
+            path,
+            project,
+            callers,
+            format,
+        } => {
+            let path = resolve_project_or_path(project, path, force)?;
+            let (file, line, col) = query::hover::parse_position(&position)
+                .map_err(|e| anyhow::anyhow!("invalid position '{}': {}", position, e))?;
+            let graph = cache::load_or_build(&path, false)?;
+            let info = query::hover::hover(&graph, &path, &file, line, col, callers)?;
+            query::output::format_hover(&info, &format);
+        }
+
         Commands::DaemonRun { path } => {
             let rt = tokio::runtime::Runtime::new()?;
             rt.block_on(daemon::server::run_daemon(path))?;
@@ -1730,6 +2884,9 @@ fn main() -> Result<()> {
             match action {
                 cli::DaemonAction::Start { path } => {
                     let path = project::resolve_project_root(path);
+                    if !force {
+                        project::check_index_root(&path)?;
+                    }
 
                     if daemon::pid::is_daemon_running(&path) {
                         let pid = daemon::pid::read_pid_file(&path).unwrap_or(0);
@@ -1883,4 +3040,28 @@ mod tests {
         assert_eq!(ext_to_language("go"), Some("go"));
         assert_eq!(ext_to_language("txt"), None);
     }
+
+    #[test]
+    fn test_resolve_parsed_reparses_only_changed_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::write(root.join("a.ts"), "import { b } from './b';\nb();\n").unwrap();
+        std::fs::write(root.join("b.ts"), "export function b() {}\n").unwrap();
+        let (_, mut parsed) = build_graph_parsed(&root, false).unwrap();
+
+        // `c.ts` is new and `b.ts` gone; `a.ts` changed on disk but was not reported, so
+        // its cached parse result is reused.
+        std::fs::write(root.join("a.ts"), "").unwrap();
+        std::fs::remove_file(root.join("b.ts")).unwrap();
+        std::fs::write(root.join("c.ts"), "export function c() {}\n").unwrap();
+        reparse_changed(&mut parsed, &[root.join("b.ts"), root.join("c.ts")]);
+        let (graph, parsed) = resolve_parsed(&root, parsed, false).unwrap();
+
+        let mut files: Vec<_> = parsed.iter().map(|(path, _, _)| path.clone()).collect();
+        files.sort();
+        assert_eq!(files, vec![root.join("a.ts"), root.join("c.ts")]);
+        assert_eq!(graph.file_count(), 2);
+        assert!(graph.symbols_named("b").is_none());
+        assert!(graph.symbols_named("c").is_some());
+    }
 }
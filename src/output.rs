@@ -143,3 +143,117 @@ pub fn print_summary(stats: &IndexStats, json: bool) {
         eprintln!("  {} files skipped (parse errors)", stats.skipped);
     }
 }
+
+/// Print the result of `index --dry-run`: the files that would be indexed and, with
+/// `explain`, every skipped path with the reason it was skipped.
+pub fn print_dry_run(
+    entries: &[crate::walker::WalkEntry],
+    root: &std::path::Path,
+    explain: bool,
+    json: bool,
+) {
+    let shown: Vec<&crate::walker::WalkEntry> = entries
+        .iter()
+        .filter(|e| explain || e.skipped.is_none())
+        .collect();
+    let indexed = entries.iter().filter(|e| e.skipped.is_none()).count();
+    let skipped = entries.len() - indexed;
+
+    if json {
+        let files: Vec<serde_json::Value> = shown
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "path": e.path.strip_prefix(root).unwrap_or(&e.path),
+                    "is_dir": e.is_dir,
+                    "skipped": e.skipped,
+                })
+            })
+            .collect();
+        let value = serde_json::json!({
+            "indexed": indexed,
+            "skipped": skipped,
+            "files": files,
+        });
+        match serde_json::to_string_pretty(&value) {
+            Ok(s) => println!("{}", s),
+            Err(e) => eprintln!("error serialising dry run: {}", e),
+        }
+        return;
+    }
+
+    for entry in shown {
+        let rel = entry.path.strip_prefix(root).unwrap_or(&entry.path);
+        let slash = if entry.is_dir { "/" } else { "" };
+        match &entry.skipped {
+            None => println!("  index  {}", rel.display()),
+            Some(reason) => println!("  skip   {}{}  ({})", rel.display(), slash, reason),
+        }
+    }
+    if explain {
+        println!("Would index {} files, skip {} paths", indexed, skipped);
+    } else {
+        println!(
+            "Would index {} files ({} paths skipped; --explain-ignores lists them)",
+            indexed, skipped
+        );
+    }
+}
+
+/// One project of an `index-all` run.
+#[derive(Debug, Serialize)]
+pub struct IndexAllRow {
+    /// Registry alias, or the path as given.
+    pub project: String,
+    pub path: std::path::PathBuf,
+    pub files: usize,
+    pub symbols: usize,
+    pub edges: usize,
+    /// Wall-clock time for this project in seconds.
+    pub elapsed_secs: f64,
+    /// Why indexing failed; `None` on success.
+    pub error: Option<String>,
+}
+
+/// Print the combined summary of an `index-all` run: one row per project, then totals.
+pub fn print_index_all_summary(rows: &[IndexAllRow], json: bool) {
+    if json {
+        match serde_json::to_string_pretty(rows) {
+            Ok(s) => println!("{}", s),
+            Err(e) => eprintln!("error serialising summary: {}", e),
+        }
+        return;
+    }
+
+    let width = rows
+        .iter()
+        .map(|r| r.project.len())
+        .max()
+        .unwrap_or(0)
+        .max("project".len());
+    println!(
+        "{:<width$}  {:>7}  {:>8}  {:>8}  {:>7}",
+        "project", "files", "symbols", "edges", "time"
+    );
+    for row in rows {
+        match &row.error {
+            None => println!(
+                "{:<width$}  {:>7}  {:>8}  {:>8}  {:>6.2}s",
+                row.project, row.files, row.symbols, row.edges, row.elapsed_secs
+            ),
+            Some(error) => println!(
+                "{:<width$}  failed: {}",
+                row.project,
+                error.lines().next().unwrap_or_default()
+            ),
+        }
+    }
+    let ok: Vec<&IndexAllRow> = rows.iter().filter(|r| r.error.is_none()).collect();
+    println!(
+        "Indexed {} of {} projects: {} files, {} symbols",
+        ok.len(),
+        rows.len(),
+        ok.iter().map(|r| r.files).sum::<usize>(),
+        ok.iter().map(|r| r.symbols).sum::<usize>(),
+    );
+}
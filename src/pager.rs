@@ -0,0 +1,151 @@
+//! Paging and path truncation for `--format table` output.
+//!
+//! Table renderers call [`start`] before printing. When stdout is a terminal, stdout is
+//! redirected into `$PAGER` (default `less`, run with `LESS=FRX` unless `LESS` is set, so
+//! output that fits on one screen is printed without waiting) until [`finish`] restores
+//! it at exit. `--no-pager`, an empty `$PAGER`, or `PAGER=cat` disable paging.
+//!
+//! `--max-path-width` shortens file paths in table columns with a middle ellipsis
+//! (`packages/…/src/index.ts`), so deep monorepo paths no longer wrap and break the
+//! column alignment.
+
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Set by `--no-pager`.
+static NO_PAGER: AtomicBool = AtomicBool::new(false);
+/// Set by `--max-path-width`; 0 leaves paths untouched.
+static MAX_PATH_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// The running pager and the duplicated original stdout to restore in [`finish`].
+#[cfg(unix)]
+static PAGER: Mutex<Option<(std::process::Child, libc::c_int)>> = Mutex::new(None);
+
+/// Apply the global `--no-pager` and `--max-path-width` flags for the rest of the process.
+pub fn configure(no_pager: bool, max_path_width: Option<usize>) {
+    NO_PAGER.store(no_pager, Ordering::Relaxed);
+    MAX_PATH_WIDTH.store(max_path_width.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Pager command from `$PAGER`, or `None` when paging is disabled.
+fn pager_command() -> Option<String> {
+    if NO_PAGER.load(Ordering::Relaxed) {
+        return None;
+    }
+    match std::env::var("PAGER") {
+        Ok(pager) if pager.trim().is_empty() || pager.trim() == "cat" => None,
+        Ok(pager) => Some(pager),
+        Err(_) => Some("less".to_string()),
+    }
+}
+
+/// Send the rest of stdout through the pager when stdout is a terminal. Later calls are
+/// no-ops.
+///
+/// Returns `true` when output ends up on a terminal, directly or through the pager, so
+/// callers can decide whether to emit ANSI color.
+pub fn start() -> bool {
+    #[cfg(unix)]
+    if std::io::stdout().is_terminal()
+        && let Some(command) = pager_command()
+    {
+        spawn(&command);
+    }
+    use_color()
+}
+
+/// `true` when stdout is a terminal or is being paged.
+pub fn use_color() -> bool {
+    #[cfg(unix)]
+    if PAGER.lock().is_ok_and(|pager| pager.is_some()) {
+        return true;
+    }
+    std::io::stdout().is_terminal()
+}
+
+#[cfg(unix)]
+fn spawn(command: &str) {
+    use std::os::fd::AsRawFd;
+    use std::process::{Command, Stdio};
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command).stdin(Stdio::piped());
+    if std::env::var_os("LESS").is_none() {
+        cmd.env("LESS", "FRX");
+    }
+    let Ok(mut child) = cmd.spawn() else {
+        return;
+    };
+    let Some(stdin) = child.stdin.take() else {
+        return;
+    };
+    let _ = std::io::stdout().flush();
+    // SAFETY: plain fd operations on stdout and a pipe this process owns. Restoring the
+    // default SIGPIPE action makes quitting the pager early end the process quietly, as
+    // for any command piped into `less`, instead of failing the next `println!`.
+    unsafe {
+        let original = libc::dup(libc::STDOUT_FILENO);
+        if original < 0 || libc::dup2(stdin.as_raw_fd(), libc::STDOUT_FILENO) < 0 {
+            return;
+        }
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+        if let Ok(mut pager) = PAGER.lock() {
+            *pager = Some((child, original));
+        }
+    }
+}
+
+/// Restore stdout and wait for the pager to exit. Does nothing when no pager runs.
+pub fn finish() {
+    #[cfg(unix)]
+    {
+        let Some((mut child, original)) = PAGER.lock().ok().and_then(|mut p| p.take()) else {
+            return;
+        };
+        let _ = std::io::stdout().flush();
+        // SAFETY: `original` is the stdout fd duplicated in `spawn`; putting it back closes
+        // the last write end of the pipe, so the pager sees end of input.
+        unsafe {
+            libc::dup2(original, libc::STDOUT_FILENO);
+            libc::close(original);
+        }
+        let _ = child.wait();
+    }
+}
+
+/// Shorten `path` to at most `max` characters by replacing its middle with `…`.
+fn truncate_middle(path: &str, max: usize) -> String {
+    let len = path.chars().count();
+    if max == 0 || len <= max {
+        return path.to_string();
+    }
+    let keep = max.saturating_sub(1);
+    let head = keep / 2;
+    let tail = keep - head;
+    let mut out: String = path.chars().take(head).collect();
+    out.push('…');
+    out.extend(path.chars().skip(len - tail));
+    out
+}
+
+/// `path` shortened to the `--max-path-width` limit for a table column.
+pub fn fit_path(path: &str) -> String {
+    truncate_middle(path, MAX_PATH_WIDTH.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_middle() {
+        let path = "packages/web/src/components/forms/Input.tsx";
+        assert_eq!(truncate_middle(path, 0), path);
+        assert_eq!(truncate_middle(path, 100), path);
+        let short = truncate_middle(path, 20);
+        assert_eq!(short, "packages/…/Input.tsx");
+        assert_eq!(short.chars().count(), 20);
+        assert_eq!(truncate_middle(path, 1), "…");
+    }
+}
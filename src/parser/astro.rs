@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use crate::graph::node::{SymbolInfo, SymbolKind};
+
+/// Return a copy of an `.astro` source in which everything outside the `---` frontmatter
+/// fence is blanked out, so it can be parsed as TypeScript.
+///
+/// Newlines are kept so line numbers in the blanked source match the original file.
+/// Files without frontmatter yield an all-blank source (no imports, no symbols).
+pub fn frontmatter_source(source: &[u8]) -> Vec<u8> {
+    let mut out: Vec<u8> = source
+        .iter()
+        .map(|&b| if b == b'\n' { b'\n' } else { b' ' })
+        .collect();
+
+    // The opening fence must be the first non-blank line; the script runs to the next fence.
+    let mut offset = 0;
+    let mut in_script = false;
+    let mut script_start = 0;
+    for line in source.split_inclusive(|&b| b == b'\n') {
+        let is_fence = line.trim_ascii() == b"---";
+        if in_script {
+            if is_fence {
+                out[script_start..offset].copy_from_slice(&source[script_start..offset]);
+                return out;
+            }
+        } else if is_fence {
+            in_script = true;
+            script_start = offset + line.len();
+        } else if !line.trim_ascii().is_empty() {
+            break;
+        }
+        offset += line.len();
+    }
+    out
+}
+
+/// The component symbol registered for an `.astro` file: named after the file stem,
+/// spanning the whole file, and exported as the module's default export.
+pub fn component_symbol(path: &Path, source: &[u8]) -> SymbolInfo {
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Component")
+        .to_string();
+    let line_count = String::from_utf8_lossy(source).lines().count().max(1);
+    SymbolInfo {
+        name,
+        kind: SymbolKind::Component,
+        line: 1,
+        line_end: line_count,
+        byte_end: source.len(),
+        is_exported: true,
+        is_default: true,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frontmatter_source_keeps_script_and_line_numbers() {
+        let src = b"---\nimport Card from './Card.astro';\nconst title = 'Hi';\n---\n<h1>{title}</h1>\n<Card />\n";
+        let script = frontmatter_source(src);
+        assert_eq!(script.len(), src.len());
+        let text = String::from_utf8(script).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "import Card from './Card.astro';");
+        assert_eq!(lines[2], "const title = 'Hi';");
+        assert!(lines[0].trim().is_empty() && lines[4].trim().is_empty());
+    }
+
+    #[test]
+    fn test_no_frontmatter_is_blank() {
+        let script = frontmatter_source(b"<h1>static</h1>\n---\nnot a script\n---\n");
+        assert!(script.iter().all(|b| b.is_ascii_whitespace()));
+    }
+
+    #[test]
+    fn test_component_symbol_named_after_file() {
+        let sym = component_symbol(Path::new("/site/src/components/Header.astro"), b"a\nb\n");
+        assert_eq!(sym.name, "Header");
+        assert_eq!(sym.kind, SymbolKind::Component);
+        assert!(sym.is_exported && sym.is_default);
+        assert_eq!(sym.line_end, 2);
+    }
+}
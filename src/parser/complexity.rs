@@ -0,0 +1,142 @@
+//! Cyclomatic complexity of functions and methods.
+//!
+//! A function's complexity is 1 plus its decision points: each `if`/`else if`, loop,
+//! ternary, `catch`/`except`, short-circuit `&&`/`||`/`??`, `switch` case, and every
+//! `match` arm but the last. Closures and arrow functions count toward the function they
+//! appear in; nested named functions and classes are measured on their own.
+
+use tree_sitter::{Node, Tree};
+
+use crate::graph::node::{SymbolInfo, SymbolKind};
+
+/// Node kinds that add one decision point each, across the TS/JS, Rust, Python, and Go
+/// grammars.
+const BRANCH_KINDS: &[&str] = &[
+    // TS/JS
+    "if_statement",
+    "for_statement",
+    "for_in_statement",
+    "while_statement",
+    "do_statement",
+    "catch_clause",
+    "ternary_expression",
+    "switch_case",
+    // Rust
+    "if_expression",
+    "for_expression",
+    "while_expression",
+    "loop_expression",
+    // Python
+    "elif_clause",
+    "except_clause",
+    "conditional_expression",
+    "boolean_operator",
+    "case_clause",
+    "for_in_clause",
+    "if_clause",
+    // Go
+    "expression_case",
+    "type_case",
+    "communication_case",
+];
+
+/// Nested declarations measured separately from the function containing them.
+const NESTED_KINDS: &[&str] = &[
+    "function_declaration",
+    "generator_function_declaration",
+    "method_definition",
+    "class_declaration",
+    "function_item",
+    "impl_item",
+    "function_definition",
+    "class_definition",
+    "method_declaration",
+];
+
+/// Set `complexity` on every function and method among `symbols` (and their children),
+/// using the symbols' byte ranges to find their nodes in `tree`.
+pub fn annotate(tree: &Tree, source: &[u8], symbols: &mut [(SymbolInfo, Vec<SymbolInfo>)]) {
+    let root = tree.root_node();
+    for (symbol, children) in symbols.iter_mut() {
+        for info in std::iter::once(symbol).chain(children.iter_mut()) {
+            if !matches!(
+                info.kind,
+                SymbolKind::Function
+                    | SymbolKind::Component
+                    | SymbolKind::Method
+                    | SymbolKind::ImplMethod
+            ) || info.byte_end <= info.byte_start
+            {
+                continue;
+            }
+            if let Some(node) = root.descendant_for_byte_range(info.byte_start, info.byte_end) {
+                info.complexity = Some(1 + decision_points(node, source, true));
+            }
+        }
+    }
+}
+
+fn decision_points(node: Node, source: &[u8], is_root: bool) -> u32 {
+    let kind = node.kind();
+    if !is_root && NESTED_KINDS.contains(&kind) {
+        return 0;
+    }
+    let mut count = match kind {
+        k if BRANCH_KINDS.contains(&k) => 1,
+        "binary_expression" => node
+            .child_by_field_name("operator")
+            .and_then(|op| op.utf8_text(source).ok())
+            .map_or(0, |op| u32::from(matches!(op, "&&" | "||" | "??"))),
+        "match_block" => {
+            let mut cursor = node.walk();
+            let arms = node
+                .named_children(&mut cursor)
+                .filter(|c| c.kind() == "match_arm")
+                .count() as u32;
+            arms.saturating_sub(1)
+        }
+        _ => 0,
+    };
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        count += decision_points(child, source, false);
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::parser::parse_file;
+
+    fn complexity_of(file: &str, source: &str, name: &str) -> Option<u32> {
+        let result = parse_file(Path::new(file), source.as_bytes()).unwrap();
+        result
+            .symbols
+            .iter()
+            .flat_map(|(s, children)| std::iter::once(s).chain(children))
+            .find(|s| s.name == name)
+            .and_then(|s| s.complexity)
+    }
+
+    #[test]
+    fn test_complexity_counts_branches_per_language() {
+        let ts = "export function route(a: number, b?: string) {\n  if (a > 1 && b) {\n    return 1;\n  } else if (a < 0) {\n    return b ?? 'x';\n  }\n  const f = (x: number) => (x ? 1 : 2);\n  for (const c of [1, 2]) { f(c); }\n  function inner() { if (a) {} }\n  return 0;\n}\nexport const VALUE = 1;\n";
+        // if, &&, else-if, ??, ternary, for; `inner` is measured on its own.
+        assert_eq!(complexity_of("a.ts", ts, "route"), Some(7));
+        assert_eq!(complexity_of("a.ts", ts, "VALUE"), None);
+
+        let rs = "struct S;\nimpl S {\n    fn pick(&self, n: i32) -> i32 {\n        match n {\n            0 => 1,\n            1 | 2 => 2,\n            _ => { while n > 0 || false {} 3 }\n        }\n    }\n}\nfn plain() {}\n";
+        // Two of three arms, while, ||.
+        assert_eq!(complexity_of("a.rs", rs, "S::pick"), Some(5));
+        assert_eq!(complexity_of("a.rs", rs, "plain"), Some(1));
+
+        let py = "def check(x):\n    if x and x > 1:\n        return 1\n    elif x:\n        return 2\n    return [y for y in x if y]\n";
+        // if, and, elif, for-in clause, if clause.
+        assert_eq!(complexity_of("a.py", py, "check"), Some(6));
+
+        let go = "package main\n\nfunc Classify(n int) string {\n\tswitch {\n\tcase n < 0:\n\t\treturn \"neg\"\n\tcase n == 0:\n\t\treturn \"zero\"\n\tdefault:\n\t\treturn \"pos\"\n\t}\n}\n";
+        assert_eq!(complexity_of("a.go", go, "Classify"), Some(3));
+    }
+}
@@ -0,0 +1,387 @@
+use crate::graph::node::{SymbolInfo, SymbolKind};
+
+use super::ParseResult;
+use super::imports::{ImportInfo, ImportKind};
+
+/// Scanner state over one stylesheet.
+struct Scanner<'a> {
+    chars: &'a [char],
+    i: usize,
+    /// 1-based line of `chars[i]`.
+    line: usize,
+    /// Index of the first character of the current line.
+    line_start: usize,
+    /// SCSS also has `//` line comments.
+    scss: bool,
+}
+
+impl Scanner<'_> {
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.i + offset).copied()
+    }
+
+    fn bump(&mut self) {
+        if self.chars[self.i] == '\n' {
+            self.line += 1;
+            self.line_start = self.i + 1;
+        }
+        self.i += 1;
+    }
+
+    /// Skip a comment starting at `i`. Returns `false` when there is none.
+    fn skip_comment(&mut self) -> bool {
+        match (self.peek(0), self.peek(1)) {
+            (Some('/'), Some('*')) => {
+                self.i += 2;
+                while self.i < self.chars.len()
+                    && !(self.peek(0) == Some('*') && self.peek(1) == Some('/'))
+                {
+                    self.bump();
+                }
+                self.i = (self.i + 2).min(self.chars.len());
+                true
+            }
+            (Some('/'), Some('/')) if self.scss => {
+                while self.i < self.chars.len() && self.chars[self.i] != '\n' {
+                    self.i += 1;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn skip_trivia(&mut self) {
+        while self.i < self.chars.len() {
+            if self.chars[self.i].is_whitespace() {
+                self.bump();
+            } else if !self.skip_comment() {
+                break;
+            }
+        }
+    }
+
+    /// Read an identifier (`[A-Za-z0-9_-]*`), possibly empty.
+    fn read_ident(&mut self) -> String {
+        let start = self.i;
+        while self
+            .peek(0)
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            self.i += 1;
+        }
+        self.chars[start..self.i].iter().collect()
+    }
+
+    /// Read a quoted string starting at the opening quote; leaves `i` past the closing one.
+    fn read_string(&mut self) -> String {
+        let quote = self.chars[self.i];
+        let mut value = String::new();
+        self.i += 1;
+        while let Some(c) = self.peek(0) {
+            if c == quote || c == '\n' {
+                break;
+            }
+            if c == '\\' && self.peek(1).is_some() {
+                self.i += 1;
+            }
+            value.push(self.chars[self.i]);
+            self.i += 1;
+        }
+        self.i = (self.i + 1).min(self.chars.len());
+        value
+    }
+
+    /// Read the body of `url(...)` with `i` just past the `(`; leaves `i` past the `)`.
+    fn read_url(&mut self) -> String {
+        self.skip_trivia();
+        let value = if matches!(self.peek(0), Some('"' | '\'')) {
+            self.read_string()
+        } else {
+            let start = self.i;
+            while self.peek(0).is_some_and(|c| c != ')' && c != '\n') {
+                self.i += 1;
+            }
+            self.chars[start..self.i]
+                .iter()
+                .collect::<String>()
+                .trim()
+                .to_string()
+        };
+        while self.peek(0).is_some_and(|c| c != ')' && c != ';') {
+            self.bump();
+        }
+        if self.peek(0) == Some(')') {
+            self.i += 1;
+        }
+        value
+    }
+
+    /// Read one import target: a quoted string or `url(...)`.
+    fn read_import_target(&mut self) -> Option<String> {
+        self.skip_trivia();
+        match self.peek(0)? {
+            '"' | '\'' => Some(self.read_string()),
+            'u' | 'U' => {
+                let start = self.i;
+                let word = self.read_ident();
+                if word.eq_ignore_ascii_case("url") && self.peek(0) == Some('(') {
+                    self.i += 1;
+                    Some(self.read_url())
+                } else {
+                    self.i = start;
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse a `.css` or `.scss` stylesheet.
+///
+/// Hand-written scanner (no tree-sitter grammar):
+/// - `@import` targets (quoted or `url(...)`, comma-separated in SCSS), `@use`, and
+///   `@forward` become `ImportKind::CssImport` imports.
+/// - SCSS `@mixin` and `@function` declarations become `Function` symbols spanning their
+///   block, and top-level `$variable:` declarations become `Variable` symbols named with
+///   the `$`. Members starting with `-` or `_` are module-private in Sass and are not
+///   exported.
+pub fn parse_css(source: &[u8], scss: bool) -> ParseResult {
+    let text = String::from_utf8_lossy(source);
+    let chars: Vec<char> = text.chars().collect();
+    let mut sc = Scanner {
+        chars: &chars,
+        i: 0,
+        line: 1,
+        line_start: 0,
+        scss,
+    };
+    let mut result = ParseResult::default();
+    let mut depth = 0usize;
+    let mut parens = 0usize;
+    // At the start of a statement (after `;`, `{`, `}`, or at the top of the file).
+    let mut statement_start = true;
+    // (index into `result.symbols`, brace depth its block closes back to)
+    let mut open_blocks: Vec<(usize, usize)> = Vec::new();
+
+    let public = |name: &str| !name.starts_with(['-', '_']);
+
+    while sc.i < chars.len() {
+        if sc.skip_comment() {
+            continue;
+        }
+        let c = chars[sc.i];
+        if c.is_whitespace() {
+            sc.bump();
+            continue;
+        }
+        let was_statement_start = statement_start;
+        statement_start = false;
+        match c {
+            '"' | '\'' => {
+                sc.read_string();
+            }
+            '{' => {
+                depth += 1;
+                statement_start = true;
+                sc.i += 1;
+            }
+            '}' => {
+                depth = depth.saturating_sub(1);
+                statement_start = true;
+                if let Some(&(idx, close_depth)) = open_blocks.last()
+                    && close_depth == depth
+                {
+                    result.symbols[idx].0.line_end = sc.line;
+                    open_blocks.pop();
+                }
+                sc.i += 1;
+            }
+            ';' => {
+                statement_start = true;
+                sc.i += 1;
+            }
+            '(' => {
+                parens += 1;
+                sc.i += 1;
+            }
+            ')' => {
+                parens = parens.saturating_sub(1);
+                sc.i += 1;
+            }
+            '@' => {
+                sc.i += 1;
+                let line = sc.line;
+                let col = sc.i - 1 - sc.line_start;
+                let keyword = sc.read_ident().to_ascii_lowercase();
+                match keyword.as_str() {
+                    "import" | "use" | "forward" => {
+                        while let Some(target) = sc.read_import_target() {
+                            result.imports.push(ImportInfo {
+                                kind: ImportKind::CssImport,
+                                module_path: target,
+                                specifiers: Vec::new(),
+                                line,
+                                col: col + 1,
+                                is_type_only: false,
+                            });
+                            sc.skip_trivia();
+                            if keyword != "import" || sc.peek(0) != Some(',') {
+                                break;
+                            }
+                            sc.i += 1;
+                        }
+                    }
+                    "mixin" | "function" if scss => {
+                        sc.skip_trivia();
+                        let name = sc.read_ident();
+                        if !name.is_empty() {
+                            open_blocks.push((result.symbols.len(), depth));
+                            result.symbols.push((
+                                SymbolInfo {
+                                    is_exported: public(&name),
+                                    name,
+                                    kind: SymbolKind::Function,
+                                    line,
+                                    col,
+                                    line_end: line,
+                                    ..Default::default()
+                                },
+                                Vec::new(),
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            '$' if scss && was_statement_start && depth == 0 && parens == 0 => {
+                let line = sc.line;
+                let col = sc.i - sc.line_start;
+                sc.i += 1;
+                let name = sc.read_ident();
+                sc.skip_trivia();
+                if !name.is_empty() && sc.peek(0) == Some(':') {
+                    result.symbols.push((
+                        SymbolInfo {
+                            is_exported: public(&name),
+                            name: format!("${name}"),
+                            kind: SymbolKind::Variable,
+                            line,
+                            col,
+                            line_end: line,
+                            ..Default::default()
+                        },
+                        Vec::new(),
+                    ));
+                }
+            }
+            c if c.is_alphabetic() => {
+                // Skip `url(...)` bodies whole: an unquoted `//host/x.png` is not a comment.
+                let word = sc.read_ident();
+                if word.eq_ignore_ascii_case("url") && sc.peek(0) == Some('(') {
+                    sc.i += 1;
+                    sc.read_url();
+                }
+            }
+            _ => sc.i += 1,
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_css_imports() {
+        let src = br#"/* @import "commented.css"; */
+@import "reset.css";
+@import url(./theme.css) screen;
+@import url("https://fonts.example.com/inter.css");
+.button { background: url(//cdn.example.com/bg.png); color: red; }
+"#;
+        let result = parse_css(src, false);
+        let imports: Vec<(&str, usize)> = result
+            .imports
+            .iter()
+            .map(|i| (i.module_path.as_str(), i.line))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("reset.css", 2),
+                ("./theme.css", 3),
+                ("https://fonts.example.com/inter.css", 4)
+            ]
+        );
+        assert!(result.symbols.is_empty());
+    }
+
+    #[test]
+    fn test_parse_scss_modules_and_symbols() {
+        let src = br#"@use "sass:math";
+@use 'src/tokens' as t;
+@forward "mixins/buttons";
+@import "base", "layout/grid";
+// $commented: 1;
+
+$primary: #333 !default;
+$_private-gap: 4px;
+
+@mixin button-base($size: 1rem) {
+  padding: math.div($size, 2);
+  &:hover { color: $primary; }
+}
+
+@function rem($px) {
+  @return math.div($px, 16px) * 1rem;
+}
+
+.card {
+  $local: 2px;
+  @include button-base;
+}
+"#;
+        let result = parse_css(src, true);
+        let imports: Vec<&str> = result
+            .imports
+            .iter()
+            .map(|i| i.module_path.as_str())
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                "sass:math",
+                "src/tokens",
+                "mixins/buttons",
+                "base",
+                "layout/grid"
+            ]
+        );
+
+        let symbols: Vec<(&str, SymbolKind, usize, usize, bool)> = result
+            .symbols
+            .iter()
+            .map(|(s, _)| {
+                (
+                    s.name.as_str(),
+                    s.kind.clone(),
+                    s.line,
+                    s.line_end,
+                    s.is_exported,
+                )
+            })
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                ("$primary", SymbolKind::Variable, 7, 7, true),
+                ("$_private-gap", SymbolKind::Variable, 8, 8, false),
+                ("button-base", SymbolKind::Function, 10, 13, true),
+                ("rem", SymbolKind::Function, 15, 17, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! External tree-sitter grammars loaded at runtime (`dynamic-grammars` feature).
+//!
+//! Each `[[grammar]]` table in `code-graph.toml` names a grammar shared library, the file
+//! extensions it parses, and an extraction query in the tree-sitter tags format:
+//!
+//! ```scheme
+//! (call target: (identifier) @_def (arguments (alias) @name)
+//!   (#eq? @_def "defmodule")) @definition.module
+//! (call target: (identifier) @_imp (arguments (alias) @import)
+//!   (#any-of? @_imp "alias" "import" "use" "require"))
+//! ```
+//!
+//! `@definition.<kind>` captures become symbols named by the `@name` capture of the same
+//! match, and `@import` captures become `ImportKind::GrammarImport` imports. Symbols are
+//! flat (no parent/child nesting) and always exported. Built-in languages take precedence
+//! over a grammar claiming the same extension.
+//!
+//! Loading a library runs its code, so a repository cannot opt itself in: a library is
+//! only loaded when the user lists it in the `CODE_GRAPH_TRUSTED_GRAMMARS` environment
+//! variable (paths separated like `PATH`). Builds without the feature ignore
+//! `[[grammar]]` tables with a warning.
+
+use std::path::Path;
+
+use crate::config::GrammarConfig;
+
+use super::ParseResult;
+
+#[cfg(feature = "dynamic-grammars")]
+mod imp {
+    use std::collections::HashMap;
+    use std::ffi::OsStr;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, LazyLock, Mutex, PoisonError, RwLock};
+
+    use anyhow::{Context, Result, anyhow, bail};
+    use tree_sitter::{Language, Parser, Query, QueryCursor, StreamingIterator};
+    use tree_sitter_language::LanguageFn;
+
+    use crate::config::GrammarConfig;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use crate::parser::ParseResult;
+    use crate::parser::imports::{ImportInfo, ImportKind};
+
+    /// A loaded grammar with its compiled extraction query.
+    pub struct DynamicGrammar {
+        pub name: String,
+        pub extensions: Vec<String>,
+        language: Language,
+        query: Query,
+        /// Keeps the shared library mapped for as long as `language` points into it.
+        _library: libloading::Library,
+    }
+
+    /// Grammars installed for one project root.
+    #[derive(Clone)]
+    struct Installed {
+        /// The `[[grammar]]` tables they were loaded from, so a changed config reloads them.
+        configs: Vec<GrammarConfig>,
+        grammars: Arc<Vec<DynamicGrammar>>,
+    }
+
+    /// Installed grammars by project root, both as given and canonical. A file is parsed
+    /// with the grammars of the innermost root containing it, so projects indexed in one
+    /// process (`index-all`, the daemon, revision worktrees) each keep their own.
+    static GRAMMARS: LazyLock<RwLock<HashMap<PathBuf, Installed>>> =
+        LazyLock::new(|| RwLock::new(HashMap::new()));
+
+    /// Grammar names handed out as `&'static` graph languages, each leaked once.
+    static NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+    /// Environment variable listing the grammar libraries the user allows to be loaded.
+    pub const TRUSTED_GRAMMARS_VAR: &str = "CODE_GRAPH_TRUSTED_GRAMMARS";
+
+    /// Whether `library` is one of the paths in `allowlist`, compared after resolving
+    /// symlinks and `..`.
+    fn is_trusted(library: &Path, allowlist: Option<&OsStr>) -> bool {
+        let (Some(allowlist), Ok(library)) = (allowlist, library.canonicalize()) else {
+            return false;
+        };
+        std::env::split_paths(allowlist)
+            .filter_map(|trusted| trusted.canonicalize().ok())
+            .any(|trusted| trusted == library)
+    }
+
+    /// Load one grammar: open the library, look up its language function, check the ABI,
+    /// and compile the extraction query.
+    pub fn load(root: &Path, config: &GrammarConfig) -> Result<DynamicGrammar> {
+        let library_path = root.join(&config.library);
+        if !is_trusted(
+            &library_path,
+            std::env::var_os(TRUSTED_GRAMMARS_VAR).as_deref(),
+        ) {
+            bail!(
+                "{} is not listed in {}; add it there to allow loading it",
+                library_path.display(),
+                TRUSTED_GRAMMARS_VAR
+            );
+        }
+        let symbol = config
+            .symbol
+            .clone()
+            .unwrap_or_else(|| format!("tree_sitter_{}", config.name.replace('-', "_")));
+
+        // SAFETY: the user trusts this library as a tree-sitter grammar; loading it runs
+        // its initializers like any other grammar linked into the binary.
+        let library = unsafe { libloading::Library::new(&library_path) }
+            .with_context(|| format!("failed to load {}", library_path.display()))?;
+        // SAFETY: tree-sitter grammars export `const TSLanguage *tree_sitter_<name>(void)`.
+        let language = unsafe {
+            let func = library
+                .get::<unsafe extern "C" fn() -> *const ()>(symbol.as_bytes())
+                .with_context(|| format!("{} does not export {symbol}", library_path.display()))?;
+            Language::new(LanguageFn::from_raw(*func))
+        };
+        Parser::new().set_language(&language).with_context(|| {
+            format!(
+                "{} was generated for an incompatible tree-sitter version",
+                library_path.display()
+            )
+        })?;
+
+        let query_path = root.join(&config.query);
+        let query_source = std::fs::read_to_string(&query_path)
+            .with_context(|| format!("failed to read {}", query_path.display()))?;
+        let query = Query::new(&language, &query_source)
+            .map_err(|err| anyhow!("invalid query {}: {err}", query_path.display()))?;
+
+        Ok(DynamicGrammar {
+            name: config.name.clone(),
+            extensions: config.extensions.clone(),
+            language,
+            query,
+            _library: library,
+        })
+    }
+
+    /// Whether `root` already has grammars loaded from exactly `configs`.
+    pub fn is_installed(root: &Path, configs: &[GrammarConfig]) -> bool {
+        GRAMMARS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(root)
+            .is_some_and(|installed| installed.configs == configs)
+    }
+
+    /// Install `grammars`, loaded from `configs`, for the files under `root`.
+    pub fn install(root: &Path, configs: &[GrammarConfig], grammars: Vec<DynamicGrammar>) {
+        let installed = Installed {
+            configs: configs.to_vec(),
+            grammars: Arc::new(grammars),
+        };
+        let mut map = GRAMMARS.write().unwrap_or_else(PoisonError::into_inner);
+        if let Ok(canonical) = root.canonicalize()
+            && canonical != root
+        {
+            map.insert(canonical, installed.clone());
+        }
+        map.insert(root.to_path_buf(), installed);
+    }
+
+    /// The grammars of the innermost project containing `path`, and the index of the one
+    /// claiming its extension.
+    pub fn grammar_for_path(path: &Path) -> Option<(Arc<Vec<DynamicGrammar>>, usize)> {
+        let ext = path.extension()?.to_str()?;
+        let map = GRAMMARS.read().unwrap_or_else(PoisonError::into_inner);
+        let (_, installed) = map
+            .iter()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())?;
+        let index = installed
+            .grammars
+            .iter()
+            .position(|g| g.extensions.iter().any(|e| e == ext))?;
+        Some((Arc::clone(&installed.grammars), index))
+    }
+
+    /// `name` as a `&'static str`, leaking it only the first time it is seen.
+    pub fn intern(name: &str) -> &'static str {
+        let mut names = NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(&interned) = names.iter().find(|&&n| n == name) {
+            return interned;
+        }
+        let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+        names.push(interned);
+        interned
+    }
+
+    /// Symbol kind of a `@definition.<kind>` capture (tree-sitter tags names).
+    fn definition_kind(kind: &str) -> Option<SymbolKind> {
+        Some(match kind {
+            "function" => SymbolKind::Function,
+            "method" => SymbolKind::Method,
+            "class" | "module" | "namespace" => SymbolKind::Class,
+            "interface" => SymbolKind::Interface,
+            "type" => SymbolKind::TypeAlias,
+            "enum" => SymbolKind::Enum,
+            "struct" => SymbolKind::Struct,
+            "trait" => SymbolKind::Trait,
+            "constant" => SymbolKind::Const,
+            "variable" => SymbolKind::Variable,
+            "macro" => SymbolKind::Macro,
+            "field" | "property" => SymbolKind::Property,
+            _ => return None,
+        })
+    }
+
+    /// Parse `source` with `grammar` and run its extraction query.
+    pub fn parse(grammar: &DynamicGrammar, source: &[u8]) -> Result<ParseResult> {
+        let mut parser = Parser::new();
+        parser.set_language(&grammar.language)?;
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| anyhow!("tree-sitter returned None for a {} file", grammar.name))?;
+
+        let capture_names = grammar.query.capture_names();
+        let mut result = ParseResult::default();
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&grammar.query, tree.root_node(), source);
+        while let Some(m) = matches.next() {
+            let mut name = None;
+            let mut definition = None;
+            for capture in m.captures {
+                let capture_name = capture_names[capture.index as usize];
+                if capture_name == "name" {
+                    name = Some(capture.node);
+                } else if capture_name == "import" {
+                    let text = capture.node.utf8_text(source).unwrap_or("");
+                    let module_path = text.trim_matches(|c| matches!(c, '"' | '\'' | '`'));
+                    if !module_path.is_empty() {
+                        result.imports.push(ImportInfo {
+                            kind: ImportKind::GrammarImport,
+                            module_path: module_path.to_string(),
+                            specifiers: Vec::new(),
+                            line: capture.node.start_position().row + 1,
+                            col: capture.node.start_position().column + 1,
+                            is_type_only: false,
+                        });
+                    }
+                } else if let Some(kind) = capture_name.strip_prefix("definition.") {
+                    definition = definition_kind(kind).map(|k| (k, capture.node));
+                }
+            }
+            let (Some(name_node), Some((kind, def_node))) = (name, definition) else {
+                continue;
+            };
+            let Ok(name) = name_node.utf8_text(source) else {
+                continue;
+            };
+            result.symbols.push((
+                SymbolInfo {
+                    name: name.to_string(),
+                    kind,
+                    line: def_node.start_position().row + 1,
+                    col: name_node.start_position().column,
+                    line_end: def_node.end_position().row + 1,
+                    col_end: def_node.end_position().column,
+                    byte_start: def_node.start_byte(),
+                    byte_end: def_node.end_byte(),
+                    is_exported: true,
+                    ..Default::default()
+                },
+                Vec::new(),
+            ));
+        }
+        Ok(result)
+    }
+
+    #[cfg(all(test, unix))]
+    mod tests {
+        use super::*;
+
+        /// The Python grammar linked into the test binary stands in for an external one.
+        fn python_grammar(query: &str) -> DynamicGrammar {
+            let language: Language = tree_sitter_python::LANGUAGE.into();
+            DynamicGrammar {
+                name: "pythonish".into(),
+                extensions: vec!["pyx".into()],
+                query: Query::new(&language, query).unwrap(),
+                language,
+                _library: libloading::os::unix::Library::this().into(),
+            }
+        }
+
+        #[test]
+        fn test_grammars_are_kept_per_project_root() {
+            let outer = Path::new("/code-graph-test/outer");
+            let inner = Path::new("/code-graph-test/outer/vendor/inner");
+            let configs: Vec<GrammarConfig> = Vec::new();
+            let mut outer_grammar = python_grammar("(identifier) @name");
+            outer_grammar.name = "outer".into();
+            install(outer, &configs, vec![outer_grammar]);
+            install(inner, &configs, Vec::new());
+
+            let name = |path: &str| {
+                grammar_for_path(Path::new(path)).map(|(grammars, i)| grammars[i].name.clone())
+            };
+            assert_eq!(
+                name("/code-graph-test/outer/lib/a.pyx").as_deref(),
+                Some("outer")
+            );
+            assert_eq!(name("/code-graph-test/outer/vendor/inner/b.pyx"), None);
+            assert_eq!(name("/code-graph-test/elsewhere/c.pyx"), None);
+            assert!(is_installed(inner, &configs));
+            assert!(!is_installed(Path::new("/code-graph-test/other"), &configs));
+        }
+
+        #[test]
+        fn test_parse_with_extraction_query() {
+            let grammar = python_grammar(
+                r#"(class_definition name: (identifier) @name) @definition.class
+(function_definition name: (identifier) @name) @definition.function
+(import_statement name: (dotted_name) @import)"#,
+            );
+            let source = b"import os.path\n\nclass Greeter:\n    def hello(self):\n        pass\n";
+            let result = parse(&grammar, source).unwrap();
+
+            let symbols: Vec<(&str, SymbolKind, usize, usize)> = result
+                .symbols
+                .iter()
+                .map(|(s, _)| (s.name.as_str(), s.kind.clone(), s.line, s.line_end))
+                .collect();
+            assert_eq!(
+                symbols,
+                vec![
+                    ("Greeter", SymbolKind::Class, 3, 5),
+                    ("hello", SymbolKind::Function, 4, 5),
+                ]
+            );
+            let imports: Vec<&str> = result
+                .imports
+                .iter()
+                .map(|i| i.module_path.as_str())
+                .collect();
+            assert_eq!(imports, vec!["os.path"]);
+        }
+
+        #[test]
+        fn test_only_listed_libraries_are_trusted() {
+            let tmp = tempfile::tempdir().unwrap();
+            let library = tmp.path().join("grammars/libfoo.so");
+            std::fs::create_dir_all(library.parent().unwrap()).unwrap();
+            std::fs::write(&library, b"").unwrap();
+            let other = tmp.path().join("libother.so");
+            std::fs::write(&other, b"").unwrap();
+
+            assert!(!is_trusted(&library, None));
+            let allowlist = std::env::join_paths([
+                other.as_path(),
+                &tmp.path().join("grammars/../grammars/libfoo.so"),
+            ])
+            .unwrap();
+            assert!(is_trusted(&library, Some(&allowlist)));
+            let allowlist = std::env::join_paths([other.as_path()]).unwrap();
+            assert!(!is_trusted(&library, Some(&allowlist)));
+        }
+    }
+}
+
+/// Load the grammars declared in the `code-graph.toml` of `root` for the files under it.
+/// Grammars that fail to load are reported on stderr and skipped. Nothing is loaded again
+/// while the declarations stay the same.
+pub fn install(root: &Path, grammars: &[GrammarConfig]) {
+    #[cfg(feature = "dynamic-grammars")]
+    {
+        if imp::is_installed(root, grammars) {
+            return;
+        }
+        let loaded = grammars
+            .iter()
+            .filter_map(|config| match imp::load(root, config) {
+                Ok(grammar) => Some(grammar),
+                Err(err) => {
+                    eprintln!("warning: grammar '{}' not loaded: {err:#}", config.name);
+                    None
+                }
+            })
+            .collect();
+        imp::install(root, grammars, loaded);
+    }
+    #[cfg(not(feature = "dynamic-grammars"))]
+    if !grammars.is_empty() {
+        let _ = root;
+        eprintln!(
+            "warning: code-graph was built without the dynamic-grammars feature; \
+             ignoring {} [[grammar]] table(s)",
+            grammars.len()
+        );
+    }
+}
+
+/// Graph language of `path` when a grammar installed for its project parses it.
+pub fn language_for_path(path: &Path) -> Option<&'static str> {
+    #[cfg(feature = "dynamic-grammars")]
+    return imp::grammar_for_path(path).map(|(grammars, i)| imp::intern(&grammars[i].name));
+    #[cfg(not(feature = "dynamic-grammars"))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Parse `path` with the grammar installed for its project and extension. Returns `None`
+/// when no grammar claims the file.
+pub fn parse(path: &Path, source: &[u8]) -> Option<anyhow::Result<ParseResult>> {
+    #[cfg(feature = "dynamic-grammars")]
+    return imp::grammar_for_path(path).map(|(grammars, i)| imp::parse(&grammars[i], source));
+    #[cfg(not(feature = "dynamic-grammars"))]
+    {
+        let _ = (path, source);
+        None
+    }
+}
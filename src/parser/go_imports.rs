@@ -73,6 +73,7 @@ fn process_import_spec(spec: Node, source: &[u8]) -> Option<ImportInfo> {
     };
 
     let line = spec.start_position().row + 1;
+    let col = spec.start_position().column + 1;
 
     // name field: optional, determines kind
     let name_node = spec.child_by_field_name("name");
@@ -113,6 +114,8 @@ fn process_import_spec(spec: Node, source: &[u8]) -> Option<ImportInfo> {
         module_path,
         specifiers,
         line,
+        col,
+        is_type_only: false,
     })
 }
 
@@ -330,11 +330,21 @@ fn extract_interface_methods(interface_node: Node, source: &[u8]) -> Vec<SymbolI
                     line: pos.row + 1,
                     col: pos.column,
                     line_end: child.end_position().row + 1,
+                    col_end: child.end_position().column,
+                    byte_start: child.start_byte(),
+                    byte_end: child.end_byte(),
                     is_exported,
                     is_default: false,
                     visibility,
                     trait_impl: None,
                     decorators: Vec::new(),
+                    category: None,
+                    signature: None,
+                    cfg: None,
+                    is_abstract: false,
+                    is_override: false,
+                    complexity: None,
+                    derived: None,
                 });
             }
         }
@@ -457,11 +467,21 @@ fn extract_spec_names(
             line: pos.row + 1,
             col: pos.column,
             line_end: spec_node.end_position().row + 1,
+            col_end: spec_node.end_position().column,
+            byte_start: spec_node.start_byte(),
+            byte_end: spec_node.end_byte(),
             is_exported,
             is_default: false,
             visibility,
             trait_impl: None,
             decorators: Vec::new(),
+            category: None,
+            signature: None,
+            cfg: None,
+            is_abstract: false,
+            is_override: false,
+            complexity: None,
+            derived: None,
         };
         results.push((symbol, Vec::new()));
     }
@@ -541,11 +561,21 @@ pub fn extract_go_symbols(
                     line: pos.row + 1,
                     col: pos.column,
                     line_end: outer_node.end_position().row + 1,
+                    col_end: outer_node.end_position().column,
+                    byte_start: outer_node.start_byte(),
+                    byte_end: outer_node.end_byte(),
                     is_exported,
                     is_default: false,
                     visibility,
                     trait_impl: None,
                     decorators: extract_go_directives(sym_n, source),
+                    category: None,
+                    signature: None,
+                    cfg: None,
+                    is_abstract: false,
+                    is_override: false,
+                    complexity: None,
+                    derived: None,
                 };
                 results.push((symbol, Vec::new()));
             }
@@ -557,11 +587,21 @@ pub fn extract_go_symbols(
                     line: pos.row + 1,
                     col: pos.column,
                     line_end: outer_node.end_position().row + 1,
+                    col_end: outer_node.end_position().column,
+                    byte_start: outer_node.start_byte(),
+                    byte_end: outer_node.end_byte(),
                     is_exported,
                     is_default: false,
                     visibility,
                     trait_impl: receiver,
                     decorators: extract_go_directives(sym_n, source),
+                    category: None,
+                    signature: None,
+                    cfg: None,
+                    is_abstract: false,
+                    is_override: false,
+                    complexity: None,
+                    derived: None,
                 };
                 results.push((symbol, Vec::new()));
             }
@@ -611,11 +651,21 @@ pub fn extract_go_symbols(
                                 line: spec_pos.row + 1,
                                 col: spec_pos.column,
                                 line_end: sym_n.end_position().row + 1,
+                                col_end: sym_n.end_position().column,
+                                byte_start: sym_n.start_byte(),
+                                byte_end: sym_n.end_byte(),
                                 is_exported: spec_exported,
                                 is_default: false,
                                 visibility: spec_vis,
                                 trait_impl: None,
                                 decorators,
+                                category: None,
+                                signature: None,
+                                cfg: None,
+                                is_abstract: false,
+                                is_override: false,
+                                complexity: None,
+                                derived: None,
                             };
                             results.push((symbol, children));
                         }
@@ -635,11 +685,21 @@ pub fn extract_go_symbols(
                                 line: alias_pos.row + 1,
                                 col: alias_pos.column,
                                 line_end: sym_n.end_position().row + 1,
+                                col_end: sym_n.end_position().column,
+                                byte_start: sym_n.start_byte(),
+                                byte_end: sym_n.end_byte(),
                                 is_exported: alias_exported,
                                 is_default: false,
                                 visibility: alias_vis,
                                 trait_impl: None,
                                 decorators,
+                                category: None,
+                                signature: None,
+                                cfg: None,
+                                is_abstract: false,
+                                is_override: false,
+                                complexity: None,
+                                derived: None,
                             };
                             results.push((symbol, Vec::new()));
                         }
@@ -29,6 +29,17 @@ pub enum ImportKind {
     GoBlank,
     /// Go dot import: `import . "pkg"` — all names imported.
     GoDot,
+    /// Protobuf import: `import "common/page.proto";` (also `import public` / `import weak`).
+    ProtoImport,
+    /// Zig import: `@import("std")`, `@import("util.zig")`, or a `build.zig` module name.
+    ZigImport,
+    /// Shell include: `source lib.sh` or `. ./lib.sh`.
+    ShellSource,
+    /// Stylesheet import: CSS `@import "x.css"` or SCSS `@use`/`@forward`/`@import`.
+    CssImport,
+    /// `@import` capture of an external grammar's extraction query (`code-graph.toml`
+    /// `[[grammar]]`).
+    GrammarImport,
 }
 
 /// A single imported name from a module.
@@ -55,9 +66,15 @@ pub struct ImportInfo {
     pub specifiers: Vec<ImportSpecifier>,
     /// 1-based line number where the import statement begins.
     /// 0 for imports extracted from older code paths that do not set this field.
-    /// Used by Python import extraction (Plan 17-02); consumed by pipeline in Plan 17-03.
-    #[allow(dead_code)]
+    /// Used by Python import extraction (Plan 17-02); consumed by pipeline in Plan 17-03
+    /// and by `lint-imports`.
     pub line: usize,
+    /// 1-based column where the import statement begins; 0 when the parser does not
+    /// record it.
+    pub col: usize,
+    /// True for TypeScript `import type { X }` (or an import whose specifiers are all
+    /// `type`-qualified), which is erased at compile time and has no runtime effect.
+    pub is_type_only: bool,
 }
 
 /// The kind of export statement.
@@ -78,10 +95,25 @@ pub enum ExportKind {
 pub struct ExportInfo {
     /// Kind of export.
     pub kind: ExportKind,
-    /// The names being exported (empty for Default and ReExportAll).
+    /// The names being exported (empty for Default and ReExportAll). For `X as Y`
+    /// specifiers this is the original name `X`.
     pub names: Vec<String>,
+    /// `(original, alias)` pairs for the `X as Y` specifiers among `names`.
+    pub aliases: Vec<(String, String)>,
     /// The source module for re-exports.
     pub source: Option<String>,
+    /// True for TypeScript `export type { X }` / `export type { X } from './module'`.
+    pub is_type_only: bool,
+}
+
+impl ExportInfo {
+    /// The name `original` is exported under: its alias, or the name itself.
+    pub fn exported_name<'a>(&'a self, original: &'a str) -> &'a str {
+        self.aliases
+            .iter()
+            .find(|(name, _)| name == original)
+            .map_or(original, |(_, alias)| alias)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -350,6 +382,39 @@ fn extract_named_imports(
     }
 }
 
+/// `true` when `node` has a direct `type` keyword child (`import type ...`,
+/// `export type { ... }`, or the `type B` in `import { type B }`).
+fn has_type_keyword(node: Node) -> bool {
+    let mut cursor = node.walk();
+    node.children(&mut cursor).any(|c| c.kind() == "type")
+}
+
+/// `true` for `import type { X } from '...'` and for imports whose named specifiers are
+/// all `type`-qualified (`import { type X, type Y } from '...'`).
+fn is_type_only_import(import_node: Node) -> bool {
+    if has_type_keyword(import_node) {
+        return true;
+    }
+    let Some(clause) = find_child_of_kind(import_node, "import_clause") else {
+        return false;
+    };
+    let mut cursor = clause.walk();
+    let parts: Vec<Node> = clause.named_children(&mut cursor).collect();
+    let [named] = parts[..] else {
+        // A default or namespace binding is a runtime value.
+        return false;
+    };
+    if named.kind() != "named_imports" {
+        return false;
+    }
+    let mut cursor = named.walk();
+    let specifiers: Vec<Node> = named
+        .named_children(&mut cursor)
+        .filter(|c| c.kind() == "import_specifier")
+        .collect();
+    !specifiers.is_empty() && specifiers.into_iter().all(has_type_keyword)
+}
+
 /// Find the variable name from a CJS require statement's parent variable_declarator.
 /// e.g. `const fs = require('fs')` → "fs"
 fn find_require_binding(call_node: Node, source: &[u8]) -> Option<String> {
@@ -410,7 +475,9 @@ pub fn extract_imports(
                     kind: ImportKind::Esm,
                     module_path: path,
                     specifiers,
-                    line: 0,
+                    line: imp_node.start_position().row + 1,
+                    col: imp_node.start_position().column + 1,
+                    is_type_only: is_type_only_import(imp_node),
                 });
             }
         }
@@ -482,7 +549,9 @@ pub fn extract_imports(
                     kind: ImportKind::Cjs,
                     module_path: path,
                     specifiers,
-                    line: 0,
+                    line: call_node.map_or(0, |n| n.start_position().row + 1),
+                    col: call_node.map_or(0, |n| n.start_position().column + 1),
+                    is_type_only: false,
                 });
             }
         }
@@ -499,20 +568,25 @@ pub fn extract_imports(
         let mut matches = cursor.matches(query, tree.root_node(), source);
 
         while let Some(m) = matches.next() {
-            let mut module_path: Option<String> = None;
+            let mut module_path: Option<(String, tree_sitter::Point)> = None;
 
             for capture in m.captures {
                 if capture.index == module_path_idx {
-                    module_path = Some(node_text(capture.node, source).to_owned());
+                    module_path = Some((
+                        node_text(capture.node, source).to_owned(),
+                        capture.node.start_position(),
+                    ));
                 }
             }
 
-            if let Some(path) = module_path {
+            if let Some((path, start)) = module_path {
                 imports.push(ImportInfo {
                     kind: ImportKind::DynamicImport,
                     module_path: path,
                     specifiers: Vec::new(),
-                    line: 0,
+                    line: start.row + 1,
+                    col: start.column + 1,
+                    is_type_only: false,
                 });
             }
         }
@@ -531,7 +605,7 @@ pub fn extract_imports(
 
 /// Extract all Rust `use` and `pub use` declarations from a parsed syntax tree.
 ///
-/// Returns a `Vec<RustUseInfo>` with the raw use path string and `is_pub_use` flag.
+/// Returns a `Vec<RustUseInfo>` with the raw use path string, `is_pub_use` flag, and line.
 /// Phase 8 stores raw source text; Phase 9 handles use-tree expansion.
 pub fn extract_rust_use(tree: &Tree, source: &[u8]) -> Vec<crate::parser::RustUseInfo> {
     let mut results = Vec::new();
@@ -557,7 +631,11 @@ pub fn extract_rust_use(tree: &Tree, source: &[u8]) -> Vec<crate::parser::RustUs
             None => continue,
         };
 
-        results.push(crate::parser::RustUseInfo { path, is_pub_use });
+        results.push(crate::parser::RustUseInfo {
+            path,
+            is_pub_use,
+            line: child.start_position().row + 1,
+        });
     }
 
     results
@@ -606,6 +684,8 @@ pub fn extract_exports(
 fn classify_export(node: Node, source: &[u8]) -> Option<ExportInfo> {
     // Check if this is a re-export (has a `source` field).
     let source_str = find_export_source(node, source);
+    // `export type { X }` — a `type` keyword before the export clause.
+    let is_type_only = has_type_keyword(node);
 
     // Check for wildcard export: `export * from './module'`
     let has_star = (0..node.child_count()).any(|i| {
@@ -619,7 +699,9 @@ fn classify_export(node: Node, source: &[u8]) -> Option<ExportInfo> {
         return Some(ExportInfo {
             kind: ExportKind::ReExportAll,
             names: Vec::new(),
+            aliases: Vec::new(),
             source: source_str,
+            is_type_only: false,
         });
     }
 
@@ -627,20 +709,24 @@ fn classify_export(node: Node, source: &[u8]) -> Option<ExportInfo> {
     let export_clause = find_child_of_kind(node, "export_clause");
 
     if let Some(clause) = export_clause {
-        let names = extract_export_clause_names(clause, source);
+        let (names, aliases) = extract_export_clause_names(clause, source);
         if source_str.is_some() {
             // `export { X } from './module'`
             return Some(ExportInfo {
                 kind: ExportKind::ReExport,
                 names,
+                aliases,
                 source: source_str,
+                is_type_only,
             });
         } else {
             // `export { X, Y }`
             return Some(ExportInfo {
                 kind: ExportKind::Named,
                 names,
+                aliases,
                 source: None,
+                is_type_only,
             });
         }
     }
@@ -656,7 +742,9 @@ fn classify_export(node: Node, source: &[u8]) -> Option<ExportInfo> {
         return Some(ExportInfo {
             kind: ExportKind::Default,
             names: Vec::new(),
+            aliases: Vec::new(),
             source: None,
+            is_type_only: false,
         });
     }
 
@@ -686,19 +774,31 @@ fn find_child_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
         .find(|child| child.kind() == kind)
 }
 
-/// Extract the exported names from an export_clause node.
-fn extract_export_clause_names(clause_node: Node, source: &[u8]) -> Vec<String> {
+/// Extract the exported names from an export_clause node, with the `(original, alias)`
+/// pairs of its `X as Y` specifiers.
+fn extract_export_clause_names(
+    clause_node: Node,
+    source: &[u8],
+) -> (Vec<String>, Vec<(String, String)>) {
     let mut names = Vec::new();
+    let mut aliases = Vec::new();
     let mut cursor = clause_node.walk();
     for child in clause_node.children(&mut cursor) {
         if child.kind() == "export_specifier" {
             // The `name` field holds the original name being exported.
             if let Some(name_node) = child.child_by_field_name("name") {
-                names.push(node_text(name_node, source).to_owned());
+                let name = node_text(name_node, source).to_owned();
+                if let Some(alias_node) = child.child_by_field_name("alias") {
+                    let alias = node_text(alias_node, source).to_owned();
+                    if alias != name {
+                        aliases.push((name.clone(), alias));
+                    }
+                }
+                names.push(name);
             }
         }
     }
-    names
+    (names, aliases)
 }
 
 // ---------------------------------------------------------------------------
@@ -852,6 +952,21 @@ mod tests {
         assert_eq!(exp.source.as_deref(), Some("./utils"));
     }
 
+    #[test]
+    fn test_reexport_aliases() {
+        let src = "export { helper as assist, format } from './utils';";
+        let (tree, lang) = parse_ts(src);
+        let exports = extract_exports(&tree, src.as_bytes(), &lang, false);
+        let exp = &exports[0];
+        assert_eq!(exp.names, vec!["helper", "format"]);
+        assert_eq!(
+            exp.aliases,
+            vec![("helper".to_string(), "assist".to_string())]
+        );
+        assert_eq!(exp.exported_name("helper"), "assist");
+        assert_eq!(exp.exported_name("format"), "format");
+    }
+
     // Test 9: Re-export all
     #[test]
     fn test_reexport_all() {
@@ -903,4 +1018,33 @@ mod tests {
             "TS file after TSX should still find 1 import"
         );
     }
+
+    #[test]
+    fn test_type_only_imports_and_exports() {
+        let src = r#"
+import type { A } from './a';
+import { type B, type C } from './b';
+import { type D, E } from './d';
+import F from './f';
+export type { G } from './g';
+export { H } from './h';
+"#;
+        let (tree, lang) = parse_ts(src);
+        let imports = extract_imports(&tree, src.as_bytes(), &lang, false);
+        let type_only: Vec<(&str, bool)> = imports
+            .iter()
+            .map(|i| (i.module_path.as_str(), i.is_type_only))
+            .collect();
+        assert_eq!(
+            type_only,
+            vec![("./a", true), ("./b", true), ("./d", false), ("./f", false)]
+        );
+
+        let exports = extract_exports(&tree, src.as_bytes(), &lang, false);
+        let type_only: Vec<(Option<&str>, bool)> = exports
+            .iter()
+            .map(|e| (e.source.as_deref(), e.is_type_only))
+            .collect();
+        assert_eq!(type_only, vec![(Some("./g"), true), (Some("./h"), false)]);
+    }
 }
@@ -1,11 +1,20 @@
+pub mod astro;
+pub mod complexity;
+pub mod css;
+pub mod dynamic;
 pub mod go_imports;
 pub mod go_symbols;
 pub mod imports;
 pub mod languages;
+pub mod notebook;
+pub mod proto;
 pub mod python_imports;
 pub mod python_symbols;
 pub mod relationships;
+pub mod shell;
+pub mod sql;
 pub mod symbols;
+pub mod zig;
 
 use std::cell::RefCell;
 use std::path::Path;
@@ -21,8 +30,10 @@ use imports::{ExportInfo, ImportInfo, extract_exports, extract_imports, extract_
 use languages::language_for_extension;
 use python_imports::extract_python_imports;
 use python_symbols::extract_python_symbols;
-use relationships::{RelationshipInfo, extract_relationships};
-use symbols::{extract_impl_methods, extract_rust_symbols, extract_symbols};
+use relationships::{RelationshipInfo, extract_relationships, extract_rust_calls};
+use symbols::{
+    extract_impl_methods, extract_rust_symbols, extract_rust_trait_impls, extract_symbols,
+};
 
 // Thread-local Parser instances — one per rayon worker thread, zero lock contention.
 // Each Parser is initialised once per thread with the appropriate grammar.
@@ -65,6 +76,8 @@ pub struct RustUseInfo {
     pub path: String,
     /// `true` for `pub use` re-exports, `false` for regular `use`.
     pub is_pub_use: bool,
+    /// 1-based line of the `use` declaration.
+    pub line: usize,
 }
 
 /// The result of parsing a single source file.
@@ -72,11 +85,13 @@ pub struct RustUseInfo {
 /// - `symbols`: extracted top-level and child symbols (see [`extract_symbols`])
 /// - `imports`: ESM / CJS / dynamic imports extracted from the file
 /// - `exports`: named / default / re-export statements extracted from the file
-/// - `relationships`: symbol-level relationships (calls, extends, implements, type refs)
+/// - `relationships`: symbol-level relationships (calls, extends, implements, type refs,
+///   SQL table references)
 /// - `rust_uses`: Rust `use`/`pub use` declarations (empty for TS/JS files)
 ///
 /// Note: the tree-sitter `Tree` is NOT retained — ASTs are dropped after extraction
 /// to keep RSS well under the 100 MB budget for large codebases (Phase 6 memory opt).
+#[derive(Default)]
 pub struct ParseResult {
     /// Each entry is `(parent_symbol, child_symbols)`.
     pub symbols: Vec<(SymbolInfo, Vec<SymbolInfo>)>,
@@ -91,6 +106,32 @@ pub struct ParseResult {
     /// Rust `use` and `pub use` declarations. Always empty for TS/JS files.
     /// Phase 8 populates this for `.rs` files; Plan 02 adds actual extraction logic.
     pub rust_uses: Vec<RustUseInfo>,
+    /// Lines in the source file. Set by the callers that read the file, via
+    /// [`count_lines`], and recorded on its `FileInfo`.
+    pub line_count: usize,
+    /// Hash of the source bytes, set alongside `line_count` via [`content_hash`].
+    pub content_hash: Option<u64>,
+}
+
+/// Hash of `source`, used to spot byte-identical copies of a file.
+///
+/// FNV-1a, so hashes persisted in caches and snapshots stay comparable across runs and
+/// Rust releases (unlike `DefaultHasher`, whose algorithm is unspecified).
+pub fn content_hash(source: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf29ce484222325;
+    for &byte in source {
+        h ^= u64::from(byte);
+        h = h.wrapping_mul(0x100000001b3);
+    }
+    h
+}
+
+/// Number of lines in `source`, counting a final line without a trailing newline.
+pub fn count_lines(source: &[u8]) -> usize {
+    if source.is_empty() {
+        return 0;
+    }
+    source.iter().filter(|&&b| b == b'\n').count() + usize::from(!source.ends_with(b"\n"))
 }
 
 /// Parse a source file and extract all symbols, imports, exports, and relationships.
@@ -110,6 +151,57 @@ pub struct ParseResult {
 pub fn parse_file(path: &Path, source: &[u8]) -> Result<ParseResult> {
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
+    // "proto" arm: hand-written tokenizer, no tree-sitter grammar involved.
+    if ext == "proto" {
+        return Ok(proto::parse_proto(source));
+    }
+
+    // "sql" arm: hand-written tokenizer, no tree-sitter grammar involved.
+    if ext == "sql" {
+        return Ok(sql::parse_sql(source));
+    }
+
+    // "zig" arm: hand-written tokenizer, no tree-sitter grammar involved.
+    if ext == "zig" {
+        return Ok(zig::parse_zig(source));
+    }
+
+    // "sh"/"bash" arm: hand-written scanner, no tree-sitter grammar involved.
+    if matches!(ext, "sh" | "bash") {
+        return Ok(shell::parse_shell(source));
+    }
+
+    // "css"/"scss" arm: hand-written scanner for `@import`/`@use` and SCSS members.
+    if matches!(ext, "css" | "scss") {
+        return Ok(css::parse_css(source, ext == "scss"));
+    }
+
+    // External grammar arm: extensions claimed by a `[[grammar]]` in code-graph.toml.
+    if let Some(result) = dynamic::parse(path, source) {
+        return result;
+    }
+
+    // "ipynb" arm: parse the code cells with the kernel's backend, then map lines back.
+    if ext == "ipynb" {
+        let code = notebook::extract_code(source);
+        let Some(code_ext) = code.ext else {
+            return Ok(ParseResult::default());
+        };
+        let mut result = parse_file(&path.with_extension(code_ext), &code.script)?;
+        notebook::remap_lines(&mut result, &code);
+        return Ok(result);
+    }
+
+    // "astro" arm: the frontmatter is a TypeScript script; the file itself is a component.
+    if ext == "astro" {
+        let script = astro::frontmatter_source(source);
+        let mut result = parse_file(&path.with_extension("ts"), &script)?;
+        result
+            .symbols
+            .insert(0, (astro::component_symbol(path, source), Vec::new()));
+        return Ok(result);
+    }
+
     // "go" arm: parse with a fresh parser.
     if ext == "go" {
         let language = language_for_extension("go").expect("go language is always Some");
@@ -120,7 +212,8 @@ pub fn parse_file(path: &Path, source: &[u8]) -> Result<ParseResult> {
         let tree = parser
             .parse(source, None)
             .ok_or_else(|| anyhow!("tree-sitter returned None for {:?}", path))?;
-        let symbols = extract_go_symbols(&tree, source, &language);
+        let mut symbols = extract_go_symbols(&tree, source, &language);
+        complexity::annotate(&tree, source, &mut symbols);
         let imports = extract_go_imports(&tree, source);
         return Ok(ParseResult {
             symbols,
@@ -128,6 +221,7 @@ pub fn parse_file(path: &Path, source: &[u8]) -> Result<ParseResult> {
             exports: Vec::new(),
             relationships: Vec::new(),
             rust_uses: Vec::new(),
+            ..Default::default()
         });
     }
 
@@ -142,7 +236,8 @@ pub fn parse_file(path: &Path, source: &[u8]) -> Result<ParseResult> {
         let tree = parser
             .parse(source, None)
             .ok_or_else(|| anyhow!("tree-sitter returned None for {:?}", path))?;
-        let symbols = extract_python_symbols(&tree, source, &language);
+        let mut symbols = extract_python_symbols(&tree, source, &language);
+        complexity::annotate(&tree, source, &mut symbols);
         let imports = extract_python_imports(&tree, source);
         return Ok(ParseResult {
             symbols,
@@ -150,6 +245,7 @@ pub fn parse_file(path: &Path, source: &[u8]) -> Result<ParseResult> {
             exports: Vec::new(),
             relationships: Vec::new(),
             rust_uses: Vec::new(),
+            ..Default::default()
         });
     }
 
@@ -168,12 +264,17 @@ pub fn parse_file(path: &Path, source: &[u8]) -> Result<ParseResult> {
         let rust_uses = extract_rust_use(&tree, source);
         let mut all_symbols = top_level;
         all_symbols.extend(impl_methods);
+        complexity::annotate(&tree, source, &mut all_symbols);
+        let mut relationships = extract_rust_trait_impls(&tree, source);
+        relationships.extend(extract_rust_calls(&tree, source));
+        relationships.extend(sql::extract_table_refs(&tree, source));
         return Ok(ParseResult {
             symbols: all_symbols,
             imports: Vec::new(),
             exports: Vec::new(),
-            relationships: Vec::new(),
+            relationships,
             rust_uses,
+            ..Default::default()
         });
     }
 
@@ -191,10 +292,12 @@ pub fn parse_file(path: &Path, source: &[u8]) -> Result<ParseResult> {
         .parse(source, None)
         .ok_or_else(|| anyhow!("tree-sitter returned None for {:?}", path))?;
 
-    let symbols = extract_symbols(&tree, source, &language, is_tsx);
+    let mut symbols = extract_symbols(&tree, source, &language, is_tsx);
+    complexity::annotate(&tree, source, &mut symbols);
     let imports = extract_imports(&tree, source, &language, is_tsx);
     let exports = extract_exports(&tree, source, &language, is_tsx);
-    let relationships_vec = extract_relationships(&tree, source, &language, is_tsx);
+    let mut relationships_vec = extract_relationships(&tree, source, &language, is_tsx);
+    relationships_vec.extend(sql::extract_table_refs(&tree, source));
 
     Ok(ParseResult {
         symbols,
@@ -202,6 +305,7 @@ pub fn parse_file(path: &Path, source: &[u8]) -> Result<ParseResult> {
         exports,
         relationships: relationships_vec,
         rust_uses: Vec::new(),
+        ..Default::default()
     })
 }
 
@@ -224,13 +328,65 @@ pub fn parse_file_parallel(path: &Path, source: &[u8]) -> Result<ParseResult> {
 
     let is_tsx = matches!(ext, "tsx" | "jsx");
 
+    // "proto" arm: hand-written tokenizer, no thread-local parser needed.
+    if ext == "proto" {
+        return Ok(proto::parse_proto(source));
+    }
+
+    // "sql" arm: hand-written tokenizer, no thread-local parser needed.
+    if ext == "sql" {
+        return Ok(sql::parse_sql(source));
+    }
+
+    // "zig" arm: hand-written tokenizer, no thread-local parser needed.
+    if ext == "zig" {
+        return Ok(zig::parse_zig(source));
+    }
+
+    // "sh"/"bash" arm: hand-written scanner, no thread-local parser needed.
+    if matches!(ext, "sh" | "bash") {
+        return Ok(shell::parse_shell(source));
+    }
+
+    // "css"/"scss" arm: hand-written scanner for `@import`/`@use` and SCSS members.
+    if matches!(ext, "css" | "scss") {
+        return Ok(css::parse_css(source, ext == "scss"));
+    }
+
+    // External grammar arm: extensions claimed by a `[[grammar]]` in code-graph.toml.
+    if let Some(result) = dynamic::parse(path, source) {
+        return result;
+    }
+
+    // "ipynb" arm: parse the code cells with the kernel's thread-local parser.
+    if ext == "ipynb" {
+        let code = notebook::extract_code(source);
+        let Some(code_ext) = code.ext else {
+            return Ok(ParseResult::default());
+        };
+        let mut result = parse_file_parallel(&path.with_extension(code_ext), &code.script)?;
+        notebook::remap_lines(&mut result, &code);
+        return Ok(result);
+    }
+
+    // "astro" arm: parse the frontmatter script with PARSER_TS, then register the component.
+    if ext == "astro" {
+        let script = astro::frontmatter_source(source);
+        let mut result = parse_file_parallel(&path.with_extension("ts"), &script)?;
+        result
+            .symbols
+            .insert(0, (astro::component_symbol(path, source), Vec::new()));
+        return Ok(result);
+    }
+
     // "go" arm: parse with PARSER_GO.
     if ext == "go" {
         let language = language_for_extension("go").expect("go language is always Some");
         let tree = PARSER_GO
             .with(|p| p.borrow_mut().parse(source, None))
             .ok_or_else(|| anyhow!("tree-sitter returned None for {:?}", path))?;
-        let symbols = extract_go_symbols(&tree, source, &language);
+        let mut symbols = extract_go_symbols(&tree, source, &language);
+        complexity::annotate(&tree, source, &mut symbols);
         let imports = extract_go_imports(&tree, source);
         return Ok(ParseResult {
             symbols,
@@ -238,6 +394,7 @@ pub fn parse_file_parallel(path: &Path, source: &[u8]) -> Result<ParseResult> {
             exports: Vec::new(),
             relationships: Vec::new(),
             rust_uses: Vec::new(),
+            ..Default::default()
         });
     }
 
@@ -248,7 +405,8 @@ pub fn parse_file_parallel(path: &Path, source: &[u8]) -> Result<ParseResult> {
         let tree = PARSER_PY
             .with(|p| p.borrow_mut().parse(source, None))
             .ok_or_else(|| anyhow!("tree-sitter returned None for {:?}", path))?;
-        let symbols = extract_python_symbols(&tree, source, &language);
+        let mut symbols = extract_python_symbols(&tree, source, &language);
+        complexity::annotate(&tree, source, &mut symbols);
         let imports = extract_python_imports(&tree, source);
         return Ok(ParseResult {
             symbols,
@@ -256,6 +414,7 @@ pub fn parse_file_parallel(path: &Path, source: &[u8]) -> Result<ParseResult> {
             exports: Vec::new(),
             relationships: Vec::new(),
             rust_uses: Vec::new(),
+            ..Default::default()
         });
     }
 
@@ -270,12 +429,17 @@ pub fn parse_file_parallel(path: &Path, source: &[u8]) -> Result<ParseResult> {
         let rust_uses = extract_rust_use(&tree, source);
         let mut all_symbols = top_level;
         all_symbols.extend(impl_methods);
+        complexity::annotate(&tree, source, &mut all_symbols);
+        let mut relationships = extract_rust_trait_impls(&tree, source);
+        relationships.extend(extract_rust_calls(&tree, source));
+        relationships.extend(sql::extract_table_refs(&tree, source));
         return Ok(ParseResult {
             symbols: all_symbols,
             imports: Vec::new(),
             exports: Vec::new(),
-            relationships: Vec::new(),
+            relationships,
             rust_uses,
+            ..Default::default()
         });
     }
 
@@ -290,10 +454,12 @@ pub fn parse_file_parallel(path: &Path, source: &[u8]) -> Result<ParseResult> {
     let language = language_for_extension(ext)
         .ok_or_else(|| anyhow!("unsupported file extension: {:?}", ext))?;
 
-    let symbols = extract_symbols(&tree, source, &language, is_tsx);
+    let mut symbols = extract_symbols(&tree, source, &language, is_tsx);
+    complexity::annotate(&tree, source, &mut symbols);
     let imports = extract_imports(&tree, source, &language, is_tsx);
     let exports = extract_exports(&tree, source, &language, is_tsx);
-    let relationships_vec = extract_relationships(&tree, source, &language, is_tsx);
+    let mut relationships_vec = extract_relationships(&tree, source, &language, is_tsx);
+    relationships_vec.extend(sql::extract_table_refs(&tree, source));
 
     Ok(ParseResult {
         symbols,
@@ -301,5 +467,6 @@ pub fn parse_file_parallel(path: &Path, source: &[u8]) -> Result<ParseResult> {
         exports,
         relationships: relationships_vec,
         rust_uses: Vec::new(),
+        ..Default::default()
     })
 }
@@ -0,0 +1,220 @@
+use super::ParseResult;
+
+/// The code cells of a Jupyter notebook, joined into one script for a language backend.
+pub struct NotebookCode {
+    /// Extension of the backend that parses the script (`py`, `ts`, ...), or `None` when
+    /// the kernel language is not supported.
+    pub ext: Option<&'static str>,
+    /// Code cells in order, each ending with a newline. IPython magics (`%cmd`, `!cmd`,
+    /// `%%cell`) are blanked so the script stays valid.
+    pub script: Vec<u8>,
+    /// `line_map[i]` is the 1-based line of the `.ipynb` file holding script line `i + 1`.
+    line_map: Vec<usize>,
+}
+
+impl NotebookCode {
+    /// The notebook line holding 1-based script line `line`.
+    pub fn notebook_line(&self, line: usize) -> usize {
+        line.checked_sub(1)
+            .and_then(|i| self.line_map.get(i))
+            .or(self.line_map.last())
+            .copied()
+            .unwrap_or(1)
+    }
+}
+
+/// Backend extension for a kernel language name (`metadata.kernelspec.language` or
+/// `metadata.language_info.name`).
+fn kernel_ext(language: &str) -> Option<&'static str> {
+    match language.to_ascii_lowercase().as_str() {
+        "python" | "python3" => Some("py"),
+        "typescript" => Some("ts"),
+        "javascript" => Some("js"),
+        "rust" => Some("rs"),
+        "go" => Some("go"),
+        _ => None,
+    }
+}
+
+/// Graph language for a notebook: the language its kernel runs, or `"jupyter"` when
+/// the kernel is not one code-graph parses (R, Julia, ...).
+pub fn kernel_language(source: &[u8]) -> &'static str {
+    match extract_code(source).ext {
+        Some("py") => "python",
+        Some("ts") => "typescript",
+        Some("js") => "javascript",
+        Some("rs") => "rust",
+        Some("go") => "go",
+        _ => "jupyter",
+    }
+}
+
+/// Extract the code cells of an `.ipynb` document.
+///
+/// Notebooks without kernel metadata are assumed to be Python. Invalid JSON yields an
+/// empty script.
+pub fn extract_code(source: &[u8]) -> NotebookCode {
+    let mut code = NotebookCode {
+        ext: Some("py"),
+        script: Vec::new(),
+        line_map: Vec::new(),
+    };
+    let Ok(text) = std::str::from_utf8(source) else {
+        return code;
+    };
+    let Ok(doc) = serde_json::from_str::<serde_json::Value>(text) else {
+        return code;
+    };
+    let metadata = &doc["metadata"];
+    if let Some(language) = metadata["kernelspec"]["language"]
+        .as_str()
+        .or(metadata["language_info"]["name"].as_str())
+    {
+        code.ext = kernel_ext(language);
+    }
+
+    // nbformat stores each source line as its own JSON string, one per line of the file.
+    // Find each one after the previous to map script lines back to notebook lines; a line
+    // that cannot be found (a cell stored as one string) reuses the last position.
+    let line_of = |offset: usize| text[..offset].matches('\n').count() + 1;
+    let mut cursor = 0usize;
+    let cells = doc["cells"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    for cell in cells {
+        if cell["cell_type"] != "code" {
+            continue;
+        }
+        let source_text = match &cell["source"] {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Array(parts) => parts.iter().filter_map(|p| p.as_str()).collect(),
+            _ => continue,
+        };
+
+        let is_cell_magic = source_text.trim_start().starts_with("%%");
+        for line in source_text.lines() {
+            if !line.trim().is_empty()
+                && let Ok(encoded) = serde_json::to_string(line)
+                && let Some(found) = text[cursor..].find(encoded.trim_end_matches('"'))
+            {
+                cursor += found;
+            }
+            let trimmed = line.trim_start();
+            if is_cell_magic || trimmed.starts_with('%') || trimmed.starts_with('!') {
+                code.script.push(b'\n');
+            } else {
+                code.script.extend_from_slice(line.as_bytes());
+                code.script.push(b'\n');
+            }
+            code.line_map.push(line_of(cursor));
+        }
+    }
+    code
+}
+
+/// Rewrite the line numbers of a result parsed from [`NotebookCode::script`] to lines of
+/// the `.ipynb` file.
+pub fn remap_lines(result: &mut ParseResult, code: &NotebookCode) {
+    for (symbol, children) in &mut result.symbols {
+        for sym in std::iter::once(symbol).chain(children.iter_mut()) {
+            sym.line = code.notebook_line(sym.line);
+            sym.line_end = code.notebook_line(sym.line_end).max(sym.line);
+        }
+    }
+    for import in &mut result.imports {
+        import.line = code.notebook_line(import.line);
+    }
+    for rel in &mut result.relationships {
+        rel.line = code.notebook_line(rel.line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTEBOOK: &str = r##"{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "metadata": {},
+   "source": [
+    "# Analysis\n"
+   ]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 1,
+   "metadata": {},
+   "outputs": [],
+   "source": [
+    "%matplotlib inline\n",
+    "import pandas as pd\n",
+    "from src.clean import normalize\n"
+   ]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 2,
+   "metadata": {},
+   "outputs": [],
+   "source": [
+    "def load(path):\n",
+    "    return normalize(pd.read_csv(path))\n"
+   ]
+  }
+ ],
+ "metadata": {
+  "kernelspec": {
+   "display_name": "Python 3",
+   "language": "python",
+   "name": "python3"
+  }
+ },
+ "nbformat": 4,
+ "nbformat_minor": 5
+}
+"##;
+
+    #[test]
+    fn test_extract_code_blanks_magics_and_maps_lines() {
+        let code = extract_code(NOTEBOOK.as_bytes());
+        assert_eq!(code.ext, Some("py"));
+        let script = String::from_utf8(code.script.clone()).unwrap();
+        assert_eq!(
+            script,
+            "\nimport pandas as pd\nfrom src.clean import normalize\ndef load(path):\n    return normalize(pd.read_csv(path))\n"
+        );
+        assert_eq!(code.notebook_line(2), 17);
+        assert_eq!(code.notebook_line(4), 27);
+        assert_eq!(kernel_language(NOTEBOOK.as_bytes()), "python");
+    }
+
+    #[test]
+    fn test_parse_notebook_attributes_symbols_to_notebook_lines() {
+        let result =
+            crate::parser::parse_file(std::path::Path::new("analysis.ipynb"), NOTEBOOK.as_bytes())
+                .unwrap();
+        let load = result
+            .symbols
+            .iter()
+            .find(|(s, _)| s.name == "load")
+            .map(|(s, _)| s)
+            .expect("load should be extracted");
+        assert_eq!((load.line, load.line_end), (27, 28));
+        let modules: Vec<(&str, usize)> = result
+            .imports
+            .iter()
+            .map(|i| (i.module_path.as_str(), i.line))
+            .collect();
+        assert_eq!(modules, vec![("pandas", 17), ("src.clean", 18)]);
+    }
+
+    #[test]
+    fn test_unsupported_kernel() {
+        let nb = br#"{"cells": [{"cell_type": "code", "source": "x <- 1"}],
+                      "metadata": {"kernelspec": {"language": "R"}}}"#;
+        assert_eq!(extract_code(nb).ext, None);
+        assert_eq!(kernel_language(nb), "jupyter");
+        let result = crate::parser::parse_file(std::path::Path::new("r.ipynb"), nb).unwrap();
+        assert!(result.symbols.is_empty() && result.imports.is_empty());
+    }
+}
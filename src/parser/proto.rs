@@ -0,0 +1,600 @@
+use crate::graph::node::{SymbolInfo, SymbolKind, SymbolVisibility};
+
+use super::ParseResult;
+use super::imports::{ImportInfo, ImportKind};
+use super::relationships::{RelationshipInfo, RelationshipKind};
+
+/// Protobuf scalar value types — never emitted as type references.
+const SCALAR_TYPES: &[&str] = &[
+    "double", "float", "int32", "int64", "uint32", "uint64", "sint32", "sint64", "fixed32",
+    "fixed64", "sfixed32", "sfixed64", "bool", "string", "bytes",
+];
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    /// Identifier, keyword, number, or dotted full name (e.g. `google.protobuf.Empty`).
+    Word(String),
+    /// Single- or double-quoted string literal (quotes stripped).
+    Str(String),
+    /// Any other single character (`{`, `}`, `;`, `=`, `<`, ...).
+    Punct(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    /// 1-based line number.
+    line: usize,
+    /// 0-based column.
+    col: usize,
+}
+
+/// Split `.proto` source into tokens, dropping whitespace and `//` / `/* */` comments.
+fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+    let mut line_start = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            line_start = i;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                    line_start = i + 1;
+                }
+                i += 1;
+            }
+            i += 2;
+            continue;
+        }
+
+        let col = i - line_start;
+        if c == '"' || c == '\'' {
+            let mut value = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != c && chars[i] != '\n' {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                }
+                value.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Token {
+                tok: Tok::Str(value),
+                line,
+                col,
+            });
+        } else if c.is_alphanumeric() || c == '_' || c == '.' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            tokens.push(Token {
+                tok: Tok::Word(chars[start..i].iter().collect()),
+                line,
+                col,
+            });
+        } else {
+            tokens.push(Token {
+                tok: Tok::Punct(c),
+                line,
+                col,
+            });
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+struct ProtoParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    symbols: Vec<(SymbolInfo, Vec<SymbolInfo>)>,
+    imports: Vec<ImportInfo>,
+    relationships: Vec<RelationshipInfo>,
+}
+
+impl ProtoParser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    fn peek_word(&self) -> Option<&str> {
+        match self.peek() {
+            Some(Tok::Word(w)) => Some(w.as_str()),
+            _ => None,
+        }
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.peek() == Some(&Tok::Punct(c))
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        let word = self.peek_word().map(str::to_owned);
+        if word.is_some() {
+            self.pos += 1;
+        }
+        word
+    }
+
+    /// Line of the most recently consumed token (1 when nothing was consumed yet).
+    fn last_line(&self) -> usize {
+        self.pos
+            .checked_sub(1)
+            .and_then(|p| self.tokens.get(p))
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    /// Skip a `{ ... }` block, assuming the cursor sits on the opening brace.
+    fn skip_block(&mut self) {
+        let mut depth = 0usize;
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Punct('{') => depth += 1,
+                Tok::Punct('}') => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        self.pos += 1;
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Skip to the end of the current statement: the next top-level `;`, or a trailing
+    /// `{ ... }` block (e.g. `option (x) = { ... };` or `extend Foo { ... }`).
+    fn skip_statement(&mut self) {
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Punct(';') => {
+                    self.pos += 1;
+                    return;
+                }
+                Tok::Punct('{') => {
+                    self.skip_block();
+                    if self.is_punct(';') {
+                        self.pos += 1;
+                    }
+                    return;
+                }
+                Tok::Punct('}') => return,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn symbol(&self, name: String, kind: SymbolKind, start: usize) -> SymbolInfo {
+        let tok = &self.tokens[start];
+        SymbolInfo {
+            name,
+            kind,
+            line: tok.line,
+            col: tok.col,
+            line_end: tok.line,
+            is_exported: true,
+            visibility: SymbolVisibility::Pub,
+            ..Default::default()
+        }
+    }
+
+    fn type_reference(&mut self, from: &str, type_name: &str, line: usize) {
+        if SCALAR_TYPES.contains(&type_name) {
+            return;
+        }
+        // Strip package qualification: `.pkg.Outer.Inner` -> `Inner`.
+        let short = type_name.rsplit('.').next().unwrap_or(type_name);
+        if short.is_empty() {
+            return;
+        }
+        self.relationships.push(RelationshipInfo {
+            from_name: Some(from.to_owned()),
+            to_name: short.to_owned(),
+            kind: RelationshipKind::TypeReference,
+            line,
+        });
+    }
+
+    fn parse_file(&mut self) {
+        while self.pos < self.tokens.len() {
+            let start = self.pos;
+            match self.peek_word() {
+                Some("import") => {
+                    self.pos += 1;
+                    if matches!(self.peek_word(), Some("public") | Some("weak")) {
+                        self.pos += 1;
+                    }
+                    if let Some(Tok::Str(path)) = self.peek().cloned() {
+                        self.imports.push(ImportInfo {
+                            kind: ImportKind::ProtoImport,
+                            module_path: path,
+                            specifiers: Vec::new(),
+                            line: self.tokens[start].line,
+                            col: 0,
+                            is_type_only: false,
+                        });
+                    }
+                    self.skip_statement();
+                }
+                Some("message") => {
+                    let mut children = Vec::new();
+                    if let Some(sym) = self.parse_message(&mut children) {
+                        self.symbols.push((sym, children));
+                    }
+                }
+                Some("enum") => {
+                    if let Some(sym) = self.parse_enum() {
+                        self.symbols.push((sym, Vec::new()));
+                    }
+                }
+                Some("service") => {
+                    if let Some(entry) = self.parse_service() {
+                        self.symbols.push(entry);
+                    }
+                }
+                Some(_) => self.skip_statement(),
+                None => {
+                    // Stray punctuation (e.g. an unbalanced `}`) — step over it.
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Parse `message Name { ... }`. Nested messages and enums are appended to `children`
+    /// (flattened, so deeply nested types are still reachable by name).
+    fn parse_message(&mut self, children: &mut Vec<SymbolInfo>) -> Option<SymbolInfo> {
+        let start = self.pos;
+        self.pos += 1; // `message`
+        let name = self.next_word()?;
+        let mut sym = self.symbol(name.clone(), SymbolKind::Message, start);
+        if !self.is_punct('{') {
+            self.skip_statement();
+            return Some(sym);
+        }
+        self.pos += 1;
+        self.parse_message_body(&name, children);
+        sym.line_end = self.last_line();
+        Some(sym)
+    }
+
+    /// Parse the fields of a message (or `oneof`) body up to and including the closing brace.
+    fn parse_message_body(&mut self, owner: &str, children: &mut Vec<SymbolInfo>) {
+        while let Some(tok) = self.peek().cloned() {
+            match tok {
+                Tok::Punct('}') => {
+                    self.pos += 1;
+                    return;
+                }
+                Tok::Punct(';') => self.pos += 1,
+                Tok::Word(word) => match word.as_str() {
+                    "message" => {
+                        if let Some(nested) = self.parse_message(children) {
+                            children.push(nested);
+                        }
+                    }
+                    "enum" => {
+                        if let Some(nested) = self.parse_enum() {
+                            children.push(nested);
+                        }
+                    }
+                    "oneof" => {
+                        self.pos += 1;
+                        self.next_word();
+                        if self.is_punct('{') {
+                            self.pos += 1;
+                            self.parse_message_body(owner, children);
+                        }
+                    }
+                    "option" | "reserved" | "extensions" | "extend" => self.skip_statement(),
+                    "map" => {
+                        let line = self.tokens[self.pos].line;
+                        // map < KeyType , ValueType > name = N ;
+                        let mut value_type = None;
+                        while let Some(tok) = self.peek() {
+                            match tok {
+                                Tok::Punct('>') | Tok::Punct(';') => break,
+                                Tok::Word(w) => value_type = Some(w.clone()),
+                                _ => {}
+                            }
+                            self.pos += 1;
+                        }
+                        if let Some(value_type) = value_type {
+                            self.type_reference(owner, &value_type, line);
+                        }
+                        self.skip_statement();
+                    }
+                    _ => {
+                        let line = self.tokens[self.pos].line;
+                        self.pos += 1;
+                        let type_name =
+                            if matches!(word.as_str(), "repeated" | "optional" | "required") {
+                                self.next_word()
+                            } else {
+                                Some(word)
+                            };
+                        if let Some(type_name) = type_name {
+                            self.type_reference(owner, &type_name, line);
+                        }
+                        self.skip_statement();
+                    }
+                },
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    /// Parse `enum Name { ... }` — values are not extracted.
+    fn parse_enum(&mut self) -> Option<SymbolInfo> {
+        let start = self.pos;
+        self.pos += 1; // `enum`
+        let name = self.next_word()?;
+        let mut sym = self.symbol(name, SymbolKind::Enum, start);
+        if self.is_punct('{') {
+            self.skip_block();
+        } else {
+            self.skip_statement();
+        }
+        sym.line_end = self.last_line();
+        Some(sym)
+    }
+
+    /// Parse `service Name { rpc ... }` into a Service symbol with one Rpc child per method.
+    fn parse_service(&mut self) -> Option<(SymbolInfo, Vec<SymbolInfo>)> {
+        let start = self.pos;
+        self.pos += 1; // `service`
+        let name = self.next_word()?;
+        let mut service = self.symbol(name, SymbolKind::Service, start);
+        let mut rpcs = Vec::new();
+        if !self.is_punct('{') {
+            self.skip_statement();
+            return Some((service, rpcs));
+        }
+        self.pos += 1;
+
+        while let Some(tok) = self.peek().cloned() {
+            match tok {
+                Tok::Punct('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Tok::Word(word) if word == "rpc" => {
+                    let rpc_start = self.pos;
+                    self.pos += 1;
+                    let Some(rpc_name) = self.next_word() else {
+                        self.skip_statement();
+                        continue;
+                    };
+                    let mut rpc = self.symbol(rpc_name.clone(), SymbolKind::Rpc, rpc_start);
+                    // rpc Name ( [stream] Request ) returns ( [stream] Response ) ...
+                    let mut in_parens = false;
+                    while let Some(tok) = self.peek().cloned() {
+                        match tok {
+                            Tok::Punct('(') => in_parens = true,
+                            Tok::Punct(')') => in_parens = false,
+                            Tok::Word(w) if in_parens && w != "stream" => {
+                                let line = self.tokens[self.pos].line;
+                                self.type_reference(&rpc_name, &w, line);
+                            }
+                            Tok::Punct(';') | Tok::Punct('{') | Tok::Punct('}') => break,
+                            _ => {}
+                        }
+                        self.pos += 1;
+                    }
+                    self.skip_statement();
+                    rpc.line_end = self.last_line();
+                    rpcs.push(rpc);
+                }
+                Tok::Punct(';') => self.pos += 1,
+                _ => self.skip_statement(),
+            }
+        }
+
+        service.line_end = self.last_line();
+        Some((service, rpcs))
+    }
+}
+
+/// Parse a `.proto` file into messages, enums, services, and rpc methods.
+///
+/// - `message` → [`SymbolKind::Message`]; nested messages/enums become children
+/// - `enum` → [`SymbolKind::Enum`]
+/// - `service` → [`SymbolKind::Service`] with one [`SymbolKind::Rpc`] child per `rpc`
+/// - `import "x.proto"` → [`ImportKind::ProtoImport`]
+/// - field, map value, and rpc request/response types → `TypeReference` relationships
+///
+/// Protobuf has a small, regular grammar, so a hand-written tokenizer is used instead of
+/// a tree-sitter grammar. Malformed input never fails — unknown statements are skipped.
+pub fn parse_proto(source: &[u8]) -> ParseResult {
+    let text = String::from_utf8_lossy(source);
+    let mut parser = ProtoParser {
+        tokens: tokenize(&text),
+        pos: 0,
+        symbols: Vec::new(),
+        imports: Vec::new(),
+        relationships: Vec::new(),
+    };
+    parser.parse_file();
+
+    ParseResult {
+        symbols: parser.symbols,
+        imports: parser.imports,
+        exports: Vec::new(),
+        relationships: parser.relationships,
+        rust_uses: Vec::new(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_PROTO: &str = r#"
+syntax = "proto3";
+package acme.user.v1;
+
+import "google/protobuf/timestamp.proto";
+import public "common/page.proto";
+
+// A user account.
+message User {
+  string id = 1;
+  google.protobuf.Timestamp created_at = 2;
+  repeated Role roles = 3;
+  map<string, Address> addresses = 4;
+  oneof contact {
+    Email email = 5;
+    string phone = 6;
+  }
+
+  message Address {
+    string street = 1;
+  }
+
+  enum Status { STATUS_UNSPECIFIED = 0; ACTIVE = 1; }
+}
+
+enum Role {
+  ROLE_UNSPECIFIED = 0;
+}
+
+/* The user service. */
+service UserService {
+  option (acme.api) = { scope: "users" };
+  rpc GetUser(GetUserRequest) returns (User);
+  rpc WatchUsers(stream WatchRequest) returns (stream User) {
+    option deprecated = true;
+  }
+}
+"#;
+
+    fn parse(src: &str) -> ParseResult {
+        parse_proto(src.as_bytes())
+    }
+
+    #[test]
+    fn test_proto_symbols() {
+        let result = parse(USER_PROTO);
+        let names: Vec<(&str, &SymbolKind)> = result
+            .symbols
+            .iter()
+            .map(|(s, _)| (s.name.as_str(), &s.kind))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("User", &SymbolKind::Message),
+                ("Role", &SymbolKind::Enum),
+                ("UserService", &SymbolKind::Service),
+            ]
+        );
+
+        let (user, children) = &result.symbols[0];
+        assert_eq!(user.line, 9);
+        assert_eq!(user.line_end, 24);
+        assert!(user.is_exported);
+        let child_names: Vec<&str> = children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(child_names, vec!["Address", "Status"]);
+    }
+
+    #[test]
+    fn test_proto_service_rpcs() {
+        let result = parse(USER_PROTO);
+        let (service, rpcs) = result
+            .symbols
+            .iter()
+            .find(|(s, _)| s.kind == SymbolKind::Service)
+            .unwrap();
+        assert_eq!(service.name, "UserService");
+        let rpc_names: Vec<&str> = rpcs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(rpc_names, vec!["GetUser", "WatchUsers"]);
+        assert!(rpcs.iter().all(|r| r.kind == SymbolKind::Rpc));
+        assert_eq!(rpcs[1].line_end, 36);
+    }
+
+    #[test]
+    fn test_proto_imports() {
+        let result = parse(USER_PROTO);
+        let paths: Vec<&str> = result
+            .imports
+            .iter()
+            .map(|i| i.module_path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["google/protobuf/timestamp.proto", "common/page.proto"]
+        );
+        assert!(
+            result
+                .imports
+                .iter()
+                .all(|i| i.kind == ImportKind::ProtoImport)
+        );
+    }
+
+    #[test]
+    fn test_proto_type_references() {
+        let result = parse(USER_PROTO);
+        let refs: Vec<(&str, &str)> = result
+            .relationships
+            .iter()
+            .map(|r| (r.from_name.as_deref().unwrap(), r.to_name.as_str()))
+            .collect();
+        assert!(refs.contains(&("User", "Timestamp")));
+        assert!(refs.contains(&("User", "Role")));
+        assert!(refs.contains(&("User", "Address")));
+        assert!(refs.contains(&("User", "Email")));
+        assert!(refs.contains(&("GetUser", "GetUserRequest")));
+        assert!(refs.contains(&("GetUser", "User")));
+        assert!(refs.contains(&("WatchUsers", "WatchRequest")));
+        // Scalars and the `stream` keyword are never references.
+        assert!(
+            !refs
+                .iter()
+                .any(|(_, to)| *to == "string" || *to == "stream")
+        );
+    }
+
+    #[test]
+    fn test_proto_malformed_input_does_not_panic() {
+        let result = parse("message { } } service Broken { rpc ; message Trailing");
+        assert!(result.symbols.iter().all(|(s, _)| !s.name.is_empty()));
+    }
+}
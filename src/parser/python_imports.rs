@@ -23,6 +23,7 @@ fn node_text<'a>(node: Node<'a>, source: &'a [u8]) -> &'a str {
 fn extract_import_statement(node: Node, source: &[u8]) -> Vec<ImportInfo> {
     let mut results = Vec::new();
     let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
 
     // Iterate over `name` field children
     let mut i = 0u32;
@@ -42,6 +43,8 @@ fn extract_import_statement(node: Node, source: &[u8]) -> Vec<ImportInfo> {
                         is_namespace: false,
                     }],
                     line,
+                    col,
+                    is_type_only: false,
                 });
             }
             "aliased_import" => {
@@ -68,6 +71,8 @@ fn extract_import_statement(node: Node, source: &[u8]) -> Vec<ImportInfo> {
                         is_namespace: false,
                     }],
                     line,
+                    col,
+                    is_type_only: false,
                 });
             }
             _ => {}
@@ -104,6 +109,7 @@ fn extract_import_from_statement(
     is_conditional: bool,
 ) -> Option<ImportInfo> {
     let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
 
     let mut dot_count = 0usize;
     let mut module_name = String::new();
@@ -202,6 +208,8 @@ fn extract_import_from_statement(
         module_path: module_name,
         specifiers,
         line,
+        col,
+        is_type_only: false,
     })
 }
 
@@ -269,6 +269,9 @@ fn extract_python_class_members(class_node: Node, source: &[u8]) -> Vec<SymbolIn
                         line: pos.row + 1,
                         col: pos.column,
                         line_end: child.end_position().row + 1,
+                        col_end: child.end_position().column,
+                        byte_start: child.start_byte(),
+                        byte_end: child.end_byte(),
                         visibility: python_visibility(&name),
                         ..Default::default()
                     });
@@ -290,6 +293,9 @@ fn extract_python_class_members(class_node: Node, source: &[u8]) -> Vec<SymbolIn
                                     line: pos.row + 1,
                                     col: pos.column,
                                     line_end: child.end_position().row + 1,
+                                    col_end: child.end_position().column,
+                                    byte_start: child.start_byte(),
+                                    byte_end: child.end_byte(),
                                     visibility: python_visibility(&name),
                                     decorators,
                                     ..Default::default()
@@ -307,6 +313,9 @@ fn extract_python_class_members(class_node: Node, source: &[u8]) -> Vec<SymbolIn
                                     line: pos.row + 1,
                                     col: pos.column,
                                     line_end: child.end_position().row + 1,
+                                    col_end: child.end_position().column,
+                                    byte_start: child.start_byte(),
+                                    byte_end: child.end_byte(),
                                     visibility: python_visibility(&name),
                                     decorators,
                                     ..Default::default()
@@ -327,6 +336,9 @@ fn extract_python_class_members(class_node: Node, source: &[u8]) -> Vec<SymbolIn
                         line: pos.row + 1,
                         col: pos.column,
                         line_end: child.end_position().row + 1,
+                        col_end: child.end_position().column,
+                        byte_start: child.start_byte(),
+                        byte_end: child.end_byte(),
                         visibility: python_visibility(&name),
                         ..Default::default()
                     });
@@ -481,11 +493,21 @@ pub fn extract_python_symbols(
             line,
             col,
             line_end,
+            col_end: def_node.end_position().column,
+            byte_start: def_node.start_byte(),
+            byte_end: def_node.end_byte(),
             is_exported,
             is_default: false,
             visibility,
             trait_impl: None,
             decorators,
+            category: None,
+            signature: None,
+            cfg: None,
+            is_abstract: false,
+            is_override: false,
+            complexity: None,
+            derived: None,
         };
 
         // Extract children for class definitions
@@ -571,11 +593,21 @@ fn extract_type_aliases(
                     line: pos.row + 1,
                     col: pos.column,
                     line_end: child.end_position().row + 1,
+                    col_end: child.end_position().column,
+                    byte_start: child.start_byte(),
+                    byte_end: child.end_byte(),
                     is_exported,
                     is_default: false,
                     visibility,
                     trait_impl: None,
                     decorators: Vec::new(),
+                    category: None,
+                    signature: None,
+                    cfg: None,
+                    is_abstract: false,
+                    is_override: false,
+                    complexity: None,
+                    derived: None,
                 },
                 Vec::new(),
             ));
@@ -21,6 +21,12 @@ pub enum RelationshipKind {
     InterfaceExtends,
     /// Type reference in annotation: `const x: SomeType`, `param: SomeType`
     TypeReference,
+    /// SQL table/view referenced by name: `FROM users` in a `.sql` file or in a
+    /// string literal such as `db.query("SELECT * FROM users")`.
+    TableReference,
+    /// JSX element rendering a component: `<Button />`. `from_name` is the enclosing
+    /// top-level declaration, `None` for module-level JSX.
+    Renders,
 }
 
 /// A single symbol-level relationship extracted from a source file.
@@ -44,17 +50,17 @@ pub struct RelationshipInfo {
 /// Query for direct function calls and method calls.
 ///
 /// Pattern 1: `foo(...)` — direct call to an identifier.
-/// Pattern 2: `obj.method(...)` — method call on any object.
+/// Pattern 2: `obj.method(...)` — method call on any object, including `this.#method()`.
 const CALLS_QUERY: &str = r#"
     ; Direct call: foo(...)
     (call_expression
       function: (identifier) @callee_name
       arguments: (arguments))
 
-    ; Method call: obj.method(...)
+    ; Method call: obj.method(...), this.#method(...)
     (call_expression
       function: (member_expression
-        property: (property_identifier) @method_name)
+        property: [(property_identifier) (private_property_identifier)] @method_name)
       arguments: (arguments))
 "#;
 
@@ -62,6 +68,7 @@ const CALLS_QUERY: &str = r#"
 ///
 /// Pattern 1: `class Foo extends Bar` — class-to-class inheritance.
 /// Pattern 2: `class Foo implements IBar` — class-to-interface implementation.
+/// Both are repeated for `abstract class` declarations.
 /// Pattern 3: `interface IFoo extends IBar` — interface-to-interface inheritance.
 ///
 /// Note: In the TypeScript tree-sitter grammar (0.23), interface extends uses
@@ -82,6 +89,20 @@ const INHERITANCE_QUERY: &str = r#"
         (implements_clause
           (type_identifier) @implements_name)))
 
+    ; abstract class Foo extends Bar
+    (abstract_class_declaration
+      name: (type_identifier) @class_name
+      (class_heritage
+        (extends_clause
+          value: (identifier) @extends_name)))
+
+    ; abstract class Foo implements IBar
+    (abstract_class_declaration
+      name: (type_identifier) @class_name
+      (class_heritage
+        (implements_clause
+          (type_identifier) @implements_name)))
+
     ; interface IFoo extends IBar
     (interface_declaration
       name: (type_identifier) @iface_name
@@ -377,9 +398,318 @@ pub fn extract_relationships(
         }
     }
 
+    // --- JSX renders (TSX / JSX only) ---
+    if !matches!(lang_group(language, is_tsx), LangGroup::TypeScript) {
+        for info in extract_jsx_renders(tree, source) {
+            push_rel!(info);
+        }
+    }
+
     results
 }
 
+// ---------------------------------------------------------------------------
+// JSX render extraction
+// ---------------------------------------------------------------------------
+
+/// Extract a `Renders` relationship for every JSX element naming a component (a
+/// capitalized identifier; lowercase tags are intrinsic HTML elements), attributed to
+/// the top-level function, class, or variable declaration it appears in.
+fn extract_jsx_renders(tree: &Tree, source: &[u8]) -> Vec<RelationshipInfo> {
+    let mut renders = Vec::new();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    for child in root.children(&mut cursor) {
+        let decl = if child.kind() == "export_statement" {
+            child.child_by_field_name("declaration").unwrap_or(child)
+        } else {
+            child
+        };
+        match decl.kind() {
+            "lexical_declaration" | "variable_declaration" => {
+                let mut decl_cursor = decl.walk();
+                for declarator in decl
+                    .named_children(&mut decl_cursor)
+                    .filter(|n| n.kind() == "variable_declarator")
+                {
+                    let name = declarator
+                        .child_by_field_name("name")
+                        .filter(|n| n.kind() == "identifier")
+                        .map(|n| node_text(n, source));
+                    walk_jsx(declarator, source, name, &mut renders);
+                }
+            }
+            "function_declaration"
+            | "generator_function_declaration"
+            | "class_declaration"
+            | "abstract_class_declaration" => {
+                let name = decl
+                    .child_by_field_name("name")
+                    .map(|n| node_text(n, source));
+                walk_jsx(decl, source, name, &mut renders);
+            }
+            _ => walk_jsx(child, source, None, &mut renders),
+        }
+    }
+    renders
+}
+
+fn walk_jsx(node: Node, source: &[u8], component: Option<&str>, out: &mut Vec<RelationshipInfo>) {
+    if matches!(
+        node.kind(),
+        "jsx_opening_element" | "jsx_self_closing_element"
+    ) && let Some(name) = node.child_by_field_name("name")
+        && name.kind() == "identifier"
+    {
+        let tag = node_text(name, source);
+        if tag.starts_with(|c: char| c.is_ascii_uppercase()) {
+            out.push(RelationshipInfo {
+                from_name: component.map(str::to_owned),
+                to_name: tag.to_owned(),
+                kind: RelationshipKind::Renders,
+                line: name.start_position().row + 1,
+            });
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        walk_jsx(child, source, component, out);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rust call extraction
+// ---------------------------------------------------------------------------
+
+/// Scope state while walking a Rust syntax tree for calls.
+#[derive(Default)]
+struct RustCallScope {
+    /// Self type of the enclosing `impl` block.
+    impl_type: Option<String>,
+    /// Name of the enclosing function.
+    function: Option<String>,
+    /// Local variables and parameters with a known type, by name.
+    bindings: std::collections::HashMap<String, String>,
+}
+
+impl RustCallScope {
+    /// Resolve `Self` to the enclosing impl type.
+    fn type_name(&self, name: &str) -> Option<String> {
+        if name == "Self" {
+            self.impl_type.clone()
+        } else {
+            Some(name.to_owned())
+        }
+    }
+}
+
+/// Bare type name of a Rust type node: `&mut a::Foo<T>` → `Foo`. `None` for tuples,
+/// slices, function pointers, and other types without a single name.
+fn rust_type_name(node: Node, source: &[u8], scope: &RustCallScope) -> Option<String> {
+    match node.kind() {
+        "type_identifier" => scope.type_name(node_text(node, source)),
+        "scoped_type_identifier" => {
+            scope.type_name(node_text(node.child_by_field_name("name")?, source))
+        }
+        "generic_type" | "reference_type" => {
+            rust_type_name(node.child_by_field_name("type")?, source, scope)
+        }
+        _ => None,
+    }
+}
+
+/// `true` for path segments naming a type (`Foo`, `Self`) rather than a module.
+fn is_type_segment(segment: &str) -> bool {
+    segment.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Type of the value an expression evaluates to, for the forms whose type is evident:
+/// struct literals (`Foo { .. }`) and associated function calls (`Foo::new(..)`).
+fn rust_value_type(node: Node, source: &[u8], scope: &RustCallScope) -> Option<String> {
+    match node.kind() {
+        "struct_expression" => rust_type_name(node.child_by_field_name("name")?, source, scope),
+        "reference_expression" | "parenthesized_expression" | "try_expression" => {
+            let inner = node.named_children(&mut node.walk()).last()?;
+            rust_value_type(inner, source, scope)
+        }
+        "call_expression" => {
+            let function = node.child_by_field_name("function")?;
+            if function.kind() != "scoped_identifier" {
+                return None;
+            }
+            let path = node_text(function.child_by_field_name("path")?, source);
+            let owner = path.rsplit("::").next()?;
+            is_type_segment(owner)
+                .then(|| scope.type_name(owner))
+                .flatten()
+        }
+        "self" => scope.impl_type.clone(),
+        "identifier" => scope.bindings.get(node_text(node, source)).cloned(),
+        _ => None,
+    }
+}
+
+/// Bind the parameters of a function to their declared types.
+fn bind_rust_parameters(params: Node, source: &[u8], scope: &mut RustCallScope) {
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        if param.kind() != "parameter" {
+            continue;
+        }
+        let (Some(pattern), Some(ty)) = (
+            param.child_by_field_name("pattern"),
+            param.child_by_field_name("type"),
+        ) else {
+            continue;
+        };
+        if pattern.kind() == "identifier"
+            && let Some(type_name) = rust_type_name(ty, source, scope)
+        {
+            scope
+                .bindings
+                .insert(node_text(pattern, source).to_owned(), type_name);
+        }
+    }
+}
+
+/// Relationship for a call expression, if its callee can be named.
+///
+/// - `foo()` → `Calls foo`
+/// - `Foo::bar()` / `Self::bar()` → `Calls Foo::bar` (the `ImplMethod` naming scheme)
+/// - `module::foo()` → `Calls foo`
+/// - `recv.bar()` with a known receiver type → `MethodCall Foo::bar`; calls on receivers
+///   of unknown type are dropped rather than guessed.
+fn rust_call(node: Node, source: &[u8], scope: &RustCallScope) -> Option<RelationshipInfo> {
+    let mut function = node.child_by_field_name("function")?;
+    if function.kind() == "generic_function" {
+        function = function.child_by_field_name("function")?;
+    }
+    let (to_name, kind) = match function.kind() {
+        "identifier" => (
+            node_text(function, source).to_owned(),
+            RelationshipKind::Calls,
+        ),
+        "scoped_identifier" => {
+            let name = node_text(function.child_by_field_name("name")?, source);
+            let owner = function
+                .child_by_field_name("path")
+                .map(|p| node_text(p, source).rsplit("::").next().unwrap_or(""))
+                .unwrap_or("");
+            let to_name = if is_type_segment(owner) {
+                format!("{}::{}", scope.type_name(owner)?, name)
+            } else {
+                name.to_owned()
+            };
+            (to_name, RelationshipKind::Calls)
+        }
+        "field_expression" => {
+            let receiver = function.child_by_field_name("value")?;
+            let method = node_text(function.child_by_field_name("field")?, source);
+            let receiver_type = rust_value_type(receiver, source, scope)?;
+            (
+                format!("{}::{}", receiver_type, method),
+                RelationshipKind::MethodCall,
+            )
+        }
+        _ => return None,
+    };
+    Some(RelationshipInfo {
+        from_name: scope.function.clone(),
+        to_name,
+        kind,
+        line: node.start_position().row + 1,
+    })
+}
+
+fn walk_rust_calls(
+    node: Node,
+    source: &[u8],
+    scope: &mut RustCallScope,
+    out: &mut Vec<RelationshipInfo>,
+) {
+    match node.kind() {
+        "impl_item" => {
+            let outer = scope.impl_type.take();
+            scope.impl_type = node
+                .child_by_field_name("type")
+                .and_then(|t| rust_type_name(t, source, scope));
+            if let Some(body) = node.child_by_field_name("body") {
+                walk_rust_calls(body, source, scope, out);
+            }
+            scope.impl_type = outer;
+            return;
+        }
+        "function_item" => {
+            let outer_function = scope.function.take();
+            let outer_bindings = std::mem::take(&mut scope.bindings);
+            scope.function = node
+                .child_by_field_name("name")
+                .map(|n| node_text(n, source).to_owned());
+            if let Some(params) = node.child_by_field_name("parameters") {
+                bind_rust_parameters(params, source, scope);
+            }
+            if let Some(body) = node.child_by_field_name("body") {
+                walk_rust_calls(body, source, scope, out);
+            }
+            scope.function = outer_function;
+            scope.bindings = outer_bindings;
+            return;
+        }
+        "let_declaration" => {
+            if let Some(value) = node.child_by_field_name("value") {
+                walk_rust_calls(value, source, scope, out);
+            }
+            if let Some(pattern) = node.child_by_field_name("pattern")
+                && pattern.kind() == "identifier"
+            {
+                let name = node_text(pattern, source).to_owned();
+                let type_name = match node.child_by_field_name("type") {
+                    Some(ty) => rust_type_name(ty, source, scope),
+                    None => node
+                        .child_by_field_name("value")
+                        .and_then(|v| rust_value_type(v, source, scope)),
+                };
+                // A shadowing `let` of unknown type hides the outer binding.
+                match type_name {
+                    Some(type_name) => scope.bindings.insert(name, type_name),
+                    None => scope.bindings.remove(&name),
+                };
+            }
+            return;
+        }
+        "call_expression" => {
+            if let Some(rel) = rust_call(node, source, scope) {
+                out.push(rel);
+            }
+        }
+        _ => {}
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        walk_rust_calls(child, source, scope, out);
+    }
+}
+
+/// Extract function and method calls from a Rust syntax tree.
+///
+/// Method calls are only kept when the receiver's type is known: `self` inside an `impl`
+/// block, a parameter with a declared type, or a `let` binding with a type annotation or
+/// an evident value type (`Foo { .. }`, `Foo::new()`). Their target is named like the
+/// `ImplMethod` symbols (`Foo::bar`), so the resolver can bind them to the right impl
+/// even when several types define a method of the same name. Calls inside macro
+/// invocations are not visible to the parser and are not extracted.
+pub fn extract_rust_calls(tree: &Tree, source: &[u8]) -> Vec<RelationshipInfo> {
+    let mut out = Vec::new();
+    walk_rust_calls(
+        tree.root_node(),
+        source,
+        &mut RustCallScope::default(),
+        &mut out,
+    );
+    out
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -443,7 +773,7 @@ mod tests {
     // Test 2: Method call extraction
     #[test]
     fn test_method_call_extraction() {
-        let src = "obj.method(); this.render();";
+        let src = "obj.method(); this.render(); this.#tick();";
         let (tree, lang) = parse_ts(src);
         let rels = extract_relationships(&tree, src.as_bytes(), &lang, false);
 
@@ -451,11 +781,48 @@ mod tests {
             .iter()
             .filter(|r| r.kind == RelationshipKind::MethodCall)
             .collect();
-        assert_eq!(method_calls.len(), 2, "expected 2 MethodCall relationships");
+        assert_eq!(method_calls.len(), 3, "expected 3 MethodCall relationships");
 
         let names: Vec<&str> = method_calls.iter().map(|r| r.to_name.as_str()).collect();
         assert!(names.contains(&"method"), "missing 'method' call");
         assert!(names.contains(&"render"), "missing 'render' call");
+        assert!(
+            names.contains(&"#tick"),
+            "missing '#tick' private method call"
+        );
+    }
+
+    #[test]
+    fn test_jsx_renders_extraction() {
+        let src = r#"
+export function App() {
+  return <Layout><Button label="x" /><div /></Layout>;
+}
+const Card = () => <Button />;
+export default class Page { render() { return <Card />; } }
+render(<App />);
+"#;
+        let (tree, lang) = parse_tsx(src);
+        let rels = extract_relationships(&tree, src.as_bytes(), &lang, true);
+        let renders: Vec<(Option<&str>, &str, usize)> = rels
+            .iter()
+            .filter(|r| r.kind == RelationshipKind::Renders)
+            .map(|r| (r.from_name.as_deref(), r.to_name.as_str(), r.line))
+            .collect();
+        assert_eq!(
+            renders,
+            vec![
+                (Some("App"), "Layout", 3),
+                (Some("App"), "Button", 3),
+                (Some("Card"), "Button", 5),
+                (Some("Page"), "Card", 6),
+                (None, "App", 7),
+            ]
+        );
+
+        let (tree, lang) = parse_ts("const x = 1;");
+        let rels = extract_relationships(&tree, b"const x = 1;", &lang, false);
+        assert!(rels.iter().all(|r| r.kind != RelationshipKind::Renders));
     }
 
     // Test 3: Class extends extraction
@@ -700,4 +1067,55 @@ const owner: Person = {};
         assert_eq!(extends[0].from_name.as_deref(), Some("Foo"));
         assert_eq!(extends[0].to_name, "Bar");
     }
+
+    #[test]
+    fn test_rust_calls_resolve_receiver_types() {
+        let src = r#"
+struct Foo;
+impl Foo {
+    fn new() -> Self { Foo }
+    fn run(&self) { self.step(); Self::helper(); }
+}
+fn main(bar: &Bar, n: usize) {
+    let foo = Foo::new();
+    let other: Baz = make();
+    foo.run();
+    bar.run();
+    other.run();
+    n.pow(2);
+    util::log();
+    unknown().run();
+}
+"#;
+        let lang = language_for_extension("rs").unwrap();
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&lang).unwrap();
+        let tree = parser.parse(src.as_bytes(), None).unwrap();
+        let rels = extract_rust_calls(&tree, src.as_bytes());
+
+        let calls: Vec<(&str, &str, &RelationshipKind)> = rels
+            .iter()
+            .map(|r| {
+                (
+                    r.from_name.as_deref().unwrap_or(""),
+                    r.to_name.as_str(),
+                    &r.kind,
+                )
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("run", "Foo::step", &RelationshipKind::MethodCall),
+                ("run", "Foo::helper", &RelationshipKind::Calls),
+                ("main", "Foo::new", &RelationshipKind::Calls),
+                ("main", "make", &RelationshipKind::Calls),
+                ("main", "Foo::run", &RelationshipKind::MethodCall),
+                ("main", "Bar::run", &RelationshipKind::MethodCall),
+                ("main", "Baz::run", &RelationshipKind::MethodCall),
+                ("main", "log", &RelationshipKind::Calls),
+                ("main", "unknown", &RelationshipKind::Calls),
+            ]
+        );
+    }
 }
@@ -0,0 +1,567 @@
+use crate::graph::node::{SymbolInfo, SymbolKind};
+
+use super::ParseResult;
+use super::imports::{ImportInfo, ImportKind};
+use super::relationships::{RelationshipInfo, RelationshipKind};
+
+/// Reserved words and builtins that can stand in command position but never name a
+/// user-defined function call worth recording.
+const KEYWORDS: &[&str] = &[
+    "if", "then", "else", "elif", "fi", "do", "done", "while", "until", "for", "in", "case",
+    "esac", "select", "function", "time", "!", "[[", "]]", "{", "}",
+];
+
+/// Words after which the next word is again in command position (`if cmd`, `then cmd`).
+const COMMAND_PREFIXES: &[&str] = &[
+    "if", "then", "else", "elif", "do", "while", "until", "!", "time", "exec", "command",
+];
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    /// A shell word with quotes removed (`"$DIR"/lib.sh` → `$DIR/lib.sh`).
+    Word(String),
+    /// A control operator or grouping character: `;`, `&`, `|`, `(`, `)`, `&&`, `||`.
+    Op(&'static str),
+}
+
+/// Split one logical line into words and operators, dropping comments.
+///
+/// Command substitutions (`$(...)` and backticks) stay inside the word they appear in;
+/// their inner text is pushed onto `nested` so calls made inside them are scanned too.
+fn split_line(line: &str, nested: &mut Vec<String>) -> Vec<Piece> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut pieces = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut i = 0;
+
+    let flush = |word: &mut String, in_word: &mut bool, pieces: &mut Vec<Piece>| {
+        if *in_word {
+            pieces.push(Piece::Word(std::mem::take(word)));
+            *in_word = false;
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '#' if !in_word => break,
+            ' ' | '\t' | '\r' => {
+                flush(&mut word, &mut in_word, &mut pieces);
+                i += 1;
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(&next) = chars.get(i + 1) {
+                    word.push(next);
+                }
+                i += 2;
+            }
+            '\'' => {
+                in_word = true;
+                i += 1;
+                while i < chars.len() && chars[i] != '\'' {
+                    word.push(chars[i]);
+                    i += 1;
+                }
+                i += 1;
+            }
+            '"' => {
+                in_word = true;
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        word.push(chars[i + 1]);
+                        i += 2;
+                    } else if chars[i] == '$' && chars.get(i + 1) == Some(&'(') {
+                        i = read_substitution(&chars, i, &mut word, nested);
+                    } else {
+                        word.push(chars[i]);
+                        i += 1;
+                    }
+                }
+                i += 1;
+            }
+            '$' if chars.get(i + 1) == Some(&'(') => {
+                in_word = true;
+                i = read_substitution(&chars, i, &mut word, nested);
+            }
+            '$' if chars.get(i + 1) == Some(&'{') => {
+                in_word = true;
+                let start = i;
+                let mut depth = 0usize;
+                while i < chars.len() {
+                    match chars[i] {
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                i += 1;
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                word.extend(&chars[start..i.min(chars.len())]);
+            }
+            '`' => {
+                in_word = true;
+                let start = i + 1;
+                i += 1;
+                while i < chars.len() && chars[i] != '`' {
+                    i += 1;
+                }
+                let inner: String = chars[start..i.min(chars.len())].iter().collect();
+                word.push('`');
+                word.push_str(&inner);
+                word.push('`');
+                nested.push(inner);
+                i += 1;
+            }
+            ';' | '&' | '|' | '(' | ')' => {
+                flush(&mut word, &mut in_word, &mut pieces);
+                let doubled = chars.get(i + 1) == Some(&c);
+                let op = match (c, doubled) {
+                    ('&', true) => "&&",
+                    ('|', true) => "||",
+                    (';', true) => ";;",
+                    (';', false) => ";",
+                    ('&', false) => "&",
+                    ('|', false) => "|",
+                    ('(', _) => "(",
+                    _ => ")",
+                };
+                i += op.len();
+                pieces.push(Piece::Op(op));
+            }
+            _ => {
+                in_word = true;
+                word.push(c);
+                i += 1;
+            }
+        }
+    }
+    flush(&mut word, &mut in_word, &mut pieces);
+    pieces
+}
+
+/// Read `$( ... )` starting at the `$`; appends its raw text to `word`, records the inner
+/// command text in `nested`, and returns the index just past the closing paren.
+fn read_substitution(
+    chars: &[char],
+    start: usize,
+    word: &mut String,
+    nested: &mut Vec<String>,
+) -> usize {
+    let mut i = start + 1;
+    let mut depth = 0usize;
+    while i < chars.len() {
+        match chars[i] {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    let end = i.min(chars.len());
+    word.extend(&chars[start..(end + 1).min(chars.len())]);
+    nested.push(chars[start + 2..end].iter().collect());
+    end + 1
+}
+
+/// The heredoc delimiter introduced on this line (`<<EOF`, `<<-'EOF'`, `<< "EOF"`), if any.
+/// Here-strings (`<<<`) are not heredocs.
+fn heredoc_delimiter(line: &str) -> Option<String> {
+    let mut rest = line;
+    while let Some(pos) = rest.find("<<") {
+        let after = &rest[pos + 2..];
+        if after.starts_with('<') {
+            rest = after.trim_start_matches('<');
+            continue;
+        }
+        let after = after.strip_prefix('-').unwrap_or(after).trim_start();
+        let delim: String = after
+            .chars()
+            .filter(|c| *c != '\'' && *c != '"')
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if !delim.is_empty() {
+            return Some(delim);
+        }
+        rest = after;
+    }
+    None
+}
+
+/// Join backslash-continued lines. Each logical line keeps the 1-based number of its
+/// first physical line.
+fn logical_lines(text: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (idx, raw) in text.lines().enumerate() {
+        let (start, mut buf) = current.take().unwrap_or((idx + 1, String::new()));
+        match raw.strip_suffix('\\') {
+            Some(head) => {
+                buf.push_str(head);
+                buf.push(' ');
+                current = Some((start, buf));
+            }
+            None => {
+                buf.push_str(raw);
+                lines.push((start, buf));
+            }
+        }
+    }
+    lines.extend(current);
+    lines
+}
+
+/// Characters allowed in a function name (bash accepts more, but these cover real scripts).
+fn is_function_name(word: &str) -> bool {
+    !word.is_empty()
+        && !word.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        && word
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | ':' | '.'))
+        && word != "."
+}
+
+/// `NAME=value` assignment prefixes (`FOO=1 cmd`) are not command words.
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && name
+                .trim_end_matches('+')
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+/// A function whose body is still open.
+struct OpenFunction {
+    symbol: SymbolInfo,
+    /// Brace depth outside the body; the body closes when depth returns to it.
+    depth: usize,
+}
+
+#[derive(Default)]
+struct ShellScanner {
+    symbols: Vec<SymbolInfo>,
+    imports: Vec<ImportInfo>,
+    relationships: Vec<RelationshipInfo>,
+    open: Vec<OpenFunction>,
+    /// A function header (`name()` / `function name`) waiting for its opening `{`.
+    pending: Option<(String, usize)>,
+    depth: usize,
+}
+
+impl ShellScanner {
+    fn enclosing(&self) -> Option<String> {
+        self.open.last().map(|f| f.symbol.name.clone())
+    }
+
+    fn open_brace(&mut self, line: usize) {
+        if let Some((name, start)) = self.pending.take() {
+            self.open.push(OpenFunction {
+                symbol: SymbolInfo {
+                    name,
+                    kind: SymbolKind::Function,
+                    line: start,
+                    line_end: line,
+                    ..Default::default()
+                },
+                depth: self.depth,
+            });
+        }
+        self.depth += 1;
+    }
+
+    fn close_brace(&mut self, line: usize) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.open.last().is_some_and(|f| f.depth == self.depth) {
+            let mut function = self.open.pop().expect("checked above").symbol;
+            function.line_end = line;
+            self.symbols.push(function);
+        }
+    }
+
+    /// Scan the pieces of one logical line (or of a command substitution within it).
+    fn scan(&mut self, pieces: &[Piece], line: usize) {
+        let mut command_position = true;
+        let mut i = 0;
+        while i < pieces.len() {
+            let word = match &pieces[i] {
+                Piece::Op(_) => {
+                    // List operators, pipes, subshell parens, `;;`, and the `)` closing a
+                    // case pattern all start a new command.
+                    command_position = true;
+                    i += 1;
+                    continue;
+                }
+                Piece::Word(w) => w.as_str(),
+            };
+
+            // Braces are only reserved words when they stand alone.
+            if word == "{" {
+                self.open_brace(line);
+                command_position = true;
+                i += 1;
+                continue;
+            }
+            if word == "}" {
+                self.close_brace(line);
+                command_position = true;
+                i += 1;
+                continue;
+            }
+            if !command_position {
+                i += 1;
+                continue;
+            }
+
+            // `function name [()]` and `name ()` headers.
+            if word == "function" {
+                if let Some(Piece::Word(name)) = pieces.get(i + 1)
+                    && is_function_name(name)
+                {
+                    self.pending = Some((name.clone(), line));
+                    i += 2;
+                    if pieces.get(i) == Some(&Piece::Op("("))
+                        && pieces.get(i + 1) == Some(&Piece::Op(")"))
+                    {
+                        i += 2;
+                    }
+                    continue;
+                }
+                i += 1;
+                continue;
+            }
+            if pieces.get(i + 1) == Some(&Piece::Op("("))
+                && pieces.get(i + 2) == Some(&Piece::Op(")"))
+                && is_function_name(word)
+            {
+                self.pending = Some((word.to_string(), line));
+                i += 3;
+                continue;
+            }
+
+            if is_assignment(word) {
+                i += 1;
+                continue;
+            }
+            if COMMAND_PREFIXES.contains(&word) {
+                i += 1;
+                continue;
+            }
+            command_position = false;
+
+            // `source path` / `. path`
+            if word == "source" || word == "." {
+                if let Some(Piece::Word(path)) = pieces.get(i + 1) {
+                    self.imports.push(ImportInfo {
+                        kind: ImportKind::ShellSource,
+                        module_path: path.clone(),
+                        specifiers: Vec::new(),
+                        line,
+                        col: 0,
+                        is_type_only: false,
+                    });
+                }
+                i += 1;
+                continue;
+            }
+
+            // A case pattern (`start)`) is followed by `)` without a matching `(`.
+            let is_case_pattern = pieces.get(i + 1) == Some(&Piece::Op(")"));
+            if !is_case_pattern && !KEYWORDS.contains(&word) && is_function_name(word) {
+                self.relationships.push(RelationshipInfo {
+                    from_name: self.enclosing(),
+                    to_name: word.to_string(),
+                    kind: RelationshipKind::Calls,
+                    line,
+                });
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Parse a shell script (`.sh` / `.bash`) into function definitions, `source` includes,
+/// and calls.
+///
+/// - `name() { ... }`, `function name { ... }` → [`SymbolKind::Function`]
+/// - `source path` / `. path` → [`ImportKind::ShellSource`]
+/// - every other command word → a [`RelationshipKind::Calls`] candidate; the shell
+///   resolver keeps only those naming a shell function
+///
+/// Like the protobuf, SQL, and Zig parsers, this is a hand-written scanner rather than a
+/// tree-sitter grammar. Heredoc bodies and comments are skipped; malformed input never
+/// fails — unterminated function bodies run to the end of the file.
+pub fn parse_shell(source: &[u8]) -> ParseResult {
+    let text = String::from_utf8_lossy(source);
+    let mut scanner = ShellScanner::default();
+    let mut heredoc: Option<String> = None;
+    let mut last_line = 1;
+
+    for (line, content) in logical_lines(&text) {
+        last_line = line;
+        if let Some(delim) = &heredoc {
+            if content.trim() == delim {
+                heredoc = None;
+            }
+            continue;
+        }
+        heredoc = heredoc_delimiter(&content);
+
+        let mut nested = Vec::new();
+        let pieces = split_line(&content, &mut nested);
+        scanner.scan(&pieces, line);
+        // Substitutions may nest; scan until none are left.
+        while let Some(inner) = nested.pop() {
+            let pieces = split_line(&inner, &mut nested);
+            scanner.scan(&pieces, line);
+        }
+    }
+
+    while let Some(open) = scanner.open.pop() {
+        let mut function = open.symbol;
+        function.line_end = last_line;
+        scanner.symbols.push(function);
+    }
+    scanner.symbols.sort_by_key(|s| s.line);
+
+    ParseResult {
+        symbols: scanner
+            .symbols
+            .into_iter()
+            .map(|s| (s, Vec::new()))
+            .collect(),
+        imports: scanner.imports,
+        exports: Vec::new(),
+        relationships: scanner.relationships,
+        rust_uses: Vec::new(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"#!/usr/bin/env bash
+set -euo pipefail
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+source "$SCRIPT_DIR/lib/log.sh"
+. ./common.sh
+
+# build() { this is a comment }
+build() {
+  log_info "building"
+  local out=$(compute_version)
+  if [[ -n "$out" ]]; then run_step "$out"; fi
+}
+
+function deploy {
+  cat <<EOF
+cleanup() { not a function }
+EOF
+  case "$1" in
+    prod) build ;;
+    *) usage ;;
+  esac
+}
+
+function usage() { echo "usage"; }
+
+deploy "$@"
+"#;
+
+    #[test]
+    fn test_shell_functions() {
+        let result = parse_shell(SCRIPT.as_bytes());
+        let names: Vec<(&str, usize, usize)> = result
+            .symbols
+            .iter()
+            .map(|(s, _)| (s.name.as_str(), s.line, s.line_end))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("build", 8, 12), ("deploy", 14, 22), ("usage", 24, 24)]
+        );
+        assert!(
+            result
+                .symbols
+                .iter()
+                .all(|(s, _)| s.kind == SymbolKind::Function)
+        );
+    }
+
+    #[test]
+    fn test_shell_source_includes() {
+        let result = parse_shell(SCRIPT.as_bytes());
+        let paths: Vec<&str> = result
+            .imports
+            .iter()
+            .map(|i| i.module_path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["$SCRIPT_DIR/lib/log.sh", "./common.sh"]);
+        assert!(
+            result
+                .imports
+                .iter()
+                .all(|i| i.kind == ImportKind::ShellSource)
+        );
+    }
+
+    #[test]
+    fn test_shell_calls() {
+        let result = parse_shell(SCRIPT.as_bytes());
+        let calls: Vec<(Option<&str>, &str)> = result
+            .relationships
+            .iter()
+            .map(|r| (r.from_name.as_deref(), r.to_name.as_str()))
+            .collect();
+        assert!(calls.contains(&(Some("build"), "log_info")));
+        assert!(calls.contains(&(Some("build"), "compute_version")));
+        assert!(calls.contains(&(Some("build"), "run_step")));
+        assert!(calls.contains(&(Some("deploy"), "build")));
+        assert!(calls.contains(&(Some("deploy"), "usage")));
+        assert!(calls.contains(&(None, "deploy")));
+        // Heredoc bodies, case patterns, and assignments are not calls.
+        assert!(
+            !calls
+                .iter()
+                .any(|(_, to)| *to == "cleanup" || *to == "prod")
+        );
+        assert!(!calls.iter().any(|(_, to)| to.contains('=')));
+    }
+
+    #[test]
+    fn test_shell_malformed_input_does_not_panic() {
+        for src in [
+            "f() {",
+            "}}}",
+            "echo \"unterminated",
+            "$(",
+            "cat <<",
+            "function",
+        ] {
+            let _ = parse_shell(src.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,677 @@
+use std::collections::HashSet;
+
+use tree_sitter::Tree;
+
+use crate::graph::node::{SymbolInfo, SymbolKind};
+
+use super::ParseResult;
+use super::relationships::{RelationshipInfo, RelationshipKind};
+
+/// Keywords that introduce a table name: `FROM users`, `JOIN orders`, `INSERT INTO audit`,
+/// `UPDATE users`, `REFERENCES users(id)`, `ALTER TABLE users`.
+const TABLE_KEYWORDS: &[&str] = &["from", "join", "into", "update", "references", "table"];
+
+/// Leading words that mark a string literal as a SQL statement. Strings that do not
+/// start with one of these are never scanned, so prose such as "error from server"
+/// does not produce table references.
+const STATEMENT_VERBS: &[&str] = &[
+    "select", "insert", "update", "delete", "with", "create", "alter", "drop", "truncate", "merge",
+    "replace", "upsert",
+];
+
+/// Keywords that may directly follow a table name and are therefore never an alias
+/// or a table name themselves.
+const RESERVED: &[&str] = &[
+    "where",
+    "join",
+    "inner",
+    "left",
+    "right",
+    "full",
+    "outer",
+    "cross",
+    "natural",
+    "on",
+    "using",
+    "group",
+    "order",
+    "limit",
+    "offset",
+    "having",
+    "union",
+    "except",
+    "intersect",
+    "set",
+    "values",
+    "returning",
+    "window",
+    "fetch",
+    "for",
+    "default",
+    "as",
+    "select",
+    "only",
+    "lateral",
+    "if",
+    "not",
+    "exists",
+];
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    /// Bare identifier, keyword, or number.
+    Word(String),
+    /// Quoted identifier: `"users"`, `` `users` ``, or `[users]` (quotes stripped).
+    Quoted(String),
+    /// String literal (`'...'` or dollar-quoted `$$...$$`); contents are not needed.
+    Str,
+    /// Any other single character (`(`, `)`, `,`, `;`, `.`, ...).
+    Punct(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    /// 1-based line number.
+    line: usize,
+    /// 0-based column.
+    col: usize,
+}
+
+impl Token {
+    /// Lowercased keyword text, or `None` for non-word tokens.
+    fn keyword(&self) -> Option<String> {
+        match &self.tok {
+            Tok::Word(w) => Some(w.to_ascii_lowercase()),
+            _ => None,
+        }
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(&self.tok, Tok::Word(w) if w.eq_ignore_ascii_case(kw))
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.tok == Tok::Punct(c)
+    }
+
+    /// The identifier text if this token can name a table.
+    fn name(&self) -> Option<&str> {
+        match &self.tok {
+            Tok::Quoted(q) if !q.is_empty() => Some(q),
+            Tok::Word(w)
+                if w.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                    && !is_reserved(w)
+                    && !is_statement_verb(w) =>
+            {
+                Some(w)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn is_reserved(word: &str) -> bool {
+    RESERVED.iter().any(|kw| word.eq_ignore_ascii_case(kw))
+}
+
+fn is_statement_verb(word: &str) -> bool {
+    STATEMENT_VERBS
+        .iter()
+        .any(|kw| word.eq_ignore_ascii_case(kw))
+}
+
+/// Split SQL source into tokens, dropping whitespace and `--` / `/* */` comments.
+fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+    let mut line_start = 0;
+
+    // Advance `i` to `end`, keeping line bookkeeping correct across multi-line tokens.
+    let skip_to = |i: &mut usize, end: usize, line: &mut usize, line_start: &mut usize| {
+        while *i < end.min(chars.len()) {
+            if chars[*i] == '\n' {
+                *line += 1;
+                *line_start = *i + 1;
+            }
+            *i += 1;
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            line_start = i;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '-' && chars.get(i + 1) == Some(&'-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let end = (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .map_or(chars.len(), |j| j + 2);
+            skip_to(&mut i, end, &mut line, &mut line_start);
+            continue;
+        }
+
+        let (start_line, col) = (line, i - line_start);
+        let tok = match c {
+            '\'' => {
+                // '' is an escaped quote inside a string literal.
+                let mut j = i + 1;
+                while j < chars.len() {
+                    if chars[j] == '\'' {
+                        if chars.get(j + 1) == Some(&'\'') {
+                            j += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    j += 1;
+                }
+                skip_to(&mut i, j + 1, &mut line, &mut line_start);
+                Tok::Str
+            }
+            '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                let end = (i + 1..chars.len())
+                    .find(|&j| chars[j] == close)
+                    .unwrap_or(chars.len());
+                let value: String = chars[i + 1..end].iter().collect();
+                skip_to(&mut i, end + 1, &mut line, &mut line_start);
+                Tok::Quoted(value)
+            }
+            '$' if chars[i + 1..]
+                .iter()
+                .position(|&ch| ch == '$')
+                .is_some_and(|n| {
+                    chars[i + 1..i + 1 + n]
+                        .iter()
+                        .all(|ch| ch.is_alphanumeric() || *ch == '_')
+                }) =>
+            {
+                // Dollar-quoted body: $$ ... $$ or $tag$ ... $tag$.
+                let tag_len = chars[i + 1..].iter().position(|&ch| ch == '$').unwrap_or(0) + 2;
+                let tag: Vec<char> = chars[i..i + tag_len].to_vec();
+                let body_start = i + tag_len;
+                let end = (body_start..chars.len())
+                    .find(|&j| chars[j..].starts_with(&tag))
+                    .map_or(chars.len(), |j| j + tag_len);
+                skip_to(&mut i, end, &mut line, &mut line_start);
+                Tok::Str
+            }
+            _ if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                {
+                    i += 1;
+                }
+                Tok::Word(chars[start..i].iter().collect())
+            }
+            _ => {
+                i += 1;
+                Tok::Punct(c)
+            }
+        };
+        tokens.push(Token {
+            tok,
+            line: start_line,
+            col,
+        });
+    }
+
+    tokens
+}
+
+// ---------------------------------------------------------------------------
+// Statement scanning
+// ---------------------------------------------------------------------------
+
+/// Split tokens into statements at top-level `;`.
+fn statements(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut out = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, t) in tokens.iter().enumerate() {
+        match t.tok {
+            Tok::Punct('(') => depth += 1,
+            Tok::Punct(')') => depth = depth.saturating_sub(1),
+            Tok::Punct(';') if depth == 0 => {
+                if i > start {
+                    out.push(&tokens[start..i]);
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < tokens.len() {
+        out.push(&tokens[start..]);
+    }
+    out
+}
+
+/// Read a possibly schema-qualified name (`public.users`, `"app"."users"`) starting at `pos`.
+///
+/// Returns the index of the last segment's token (the unqualified table name) and the
+/// position just after the name.
+fn qualified_name(tokens: &[Token], pos: usize) -> Option<(usize, usize)> {
+    tokens.get(pos)?.name()?;
+    let mut last = pos;
+    let mut next = pos + 1;
+    while tokens.get(next).is_some_and(|t| t.is_punct('.'))
+        && tokens.get(next + 1).and_then(Token::name).is_some()
+    {
+        last = next + 1;
+        next += 2;
+    }
+    Some((last, next))
+}
+
+/// Names bound by `WITH name AS (...)` — queries against them are not table references.
+fn cte_names(tokens: &[Token]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for i in 1..tokens.len() {
+        let opens_cte = tokens[i - 1].is_keyword("with")
+            || tokens[i - 1].is_keyword("recursive")
+            || tokens[i - 1].is_punct(',');
+        if opens_cte
+            && let Some(name) = tokens[i].name()
+            && tokens.get(i + 1).is_some_and(|t| t.is_keyword("as"))
+            && tokens.get(i + 2).is_some_and(|t| t.is_punct('('))
+        {
+            names.insert(name.to_ascii_lowercase());
+        }
+    }
+    names
+}
+
+/// Collect `(token index, name)` for every table referenced in a statement.
+fn table_refs(tokens: &[Token]) -> Vec<(usize, String)> {
+    let ctes = cte_names(tokens);
+    let mut refs = Vec::new();
+    let mut k = 0;
+    while k < tokens.len() {
+        let Some(kw) = tokens[k]
+            .keyword()
+            .filter(|w| TABLE_KEYWORDS.contains(&w.as_str()))
+        else {
+            k += 1;
+            continue;
+        };
+        let mut j = k + 1;
+        loop {
+            while tokens.get(j).is_some_and(|t| {
+                ["only", "lateral", "if", "not", "exists"]
+                    .iter()
+                    .any(|m| t.is_keyword(m))
+            }) {
+                j += 1;
+            }
+            let Some((name_idx, next)) = qualified_name(tokens, j) else {
+                break;
+            };
+            // `FROM generate_series(1, 10)` is a table function, not a table.
+            let is_call =
+                (kw == "from" || kw == "join") && tokens.get(next).is_some_and(|t| t.is_punct('('));
+            let name = tokens[name_idx].name().unwrap_or_default();
+            if !is_call && !ctes.contains(&name.to_ascii_lowercase()) {
+                refs.push((name_idx, name.to_owned()));
+            }
+            j = next;
+            // Optional alias: `users AS u` / `users u`.
+            if tokens.get(j).is_some_and(|t| t.is_keyword("as")) {
+                j += 1;
+            }
+            if tokens.get(j).and_then(Token::name).is_some() {
+                j += 1;
+            }
+            // `FROM a, b` lists several tables.
+            if kw == "from" && tokens.get(j).is_some_and(|t| t.is_punct(',')) {
+                j += 1;
+                continue;
+            }
+            break;
+        }
+        k = j.max(k + 1);
+    }
+    refs
+}
+
+/// Match `CREATE [OR REPLACE] [TEMP] [MATERIALIZED] TABLE|VIEW [IF NOT EXISTS] name`.
+///
+/// Returns the symbol kind, the index of the name token, and the position after the name.
+fn create_statement(tokens: &[Token]) -> Option<(SymbolKind, usize, usize)> {
+    if !tokens.first()?.is_keyword("create") {
+        return None;
+    }
+    const MODIFIERS: &[&str] = &[
+        "or",
+        "replace",
+        "temp",
+        "temporary",
+        "unlogged",
+        "global",
+        "local",
+        "materialized",
+        "recursive",
+        "virtual",
+        "external",
+        "foreign",
+    ];
+    let mut j = 1;
+    while tokens
+        .get(j)
+        .is_some_and(|t| MODIFIERS.iter().any(|m| t.is_keyword(m)))
+    {
+        j += 1;
+    }
+    let kind = if tokens.get(j)?.is_keyword("table") {
+        SymbolKind::Table
+    } else if tokens.get(j)?.is_keyword("view") {
+        SymbolKind::View
+    } else {
+        return None;
+    };
+    j += 1;
+    if tokens.get(j).is_some_and(|t| t.is_keyword("if")) {
+        j += 3; // IF NOT EXISTS
+    }
+    let (name_idx, next) = qualified_name(tokens, j)?;
+    Some((kind, name_idx, next))
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Parse a `.sql` file into table and view symbols.
+///
+/// - `CREATE TABLE` → [`SymbolKind::Table`], `CREATE [MATERIALIZED] VIEW` → [`SymbolKind::View`]
+///   (schema qualifiers are dropped: `public.users` → `users`)
+/// - tables referenced from a view body or a foreign key (`REFERENCES users(id)`) →
+///   `TableReference` relationships from that symbol
+/// - tables referenced by any other statement (`ALTER TABLE`, `INSERT INTO`, ...) →
+///   file-level `TableReference` relationships
+///
+/// Like the protobuf parser this uses a small hand-written tokenizer; dialect-specific
+/// syntax that is not understood is skipped rather than rejected.
+pub fn parse_sql(source: &[u8]) -> ParseResult {
+    let text = String::from_utf8_lossy(source);
+    let tokens = tokenize(&text);
+    let mut symbols = Vec::new();
+    let mut relationships = Vec::new();
+
+    for stmt in statements(&tokens) {
+        let (from_name, refs) = match create_statement(stmt) {
+            Some((kind, name_idx, next)) => {
+                let name = stmt[name_idx].name().unwrap_or_default().to_owned();
+                symbols.push((
+                    SymbolInfo {
+                        name: name.clone(),
+                        kind,
+                        line: stmt[0].line,
+                        col: stmt[0].col,
+                        line_end: stmt.last().map_or(stmt[0].line, |t| t.line),
+                        is_exported: true,
+                        ..SymbolInfo::default()
+                    },
+                    Vec::new(),
+                ));
+                let refs: Vec<(usize, String)> = table_refs(&stmt[next..])
+                    .into_iter()
+                    .filter(|(_, r)| !r.eq_ignore_ascii_case(&name))
+                    .map(|(idx, r)| (idx + next, r))
+                    .collect();
+                (Some(name), refs)
+            }
+            None => (None, table_refs(stmt)),
+        };
+        let mut seen = HashSet::new();
+        for (idx, to_name) in refs {
+            if seen.insert(to_name.to_ascii_lowercase()) {
+                relationships.push(RelationshipInfo {
+                    from_name: from_name.clone(),
+                    to_name,
+                    kind: RelationshipKind::TableReference,
+                    line: stmt[idx].line,
+                });
+            }
+        }
+    }
+
+    ParseResult {
+        symbols,
+        imports: Vec::new(),
+        exports: Vec::new(),
+        relationships,
+        rust_uses: Vec::new(),
+        ..Default::default()
+    }
+}
+
+/// Strip string delimiters: `"..."`, `'...'`, `` `...` ``, `r#"..."#`, `b"..."`.
+fn string_contents(text: &str) -> &str {
+    let t = text
+        .trim_start_matches(['r', 'b', 'c'])
+        .trim_start_matches('#');
+    let t = t.strip_prefix(['"', '\'', '`']).unwrap_or(t);
+    let t = t.trim_end_matches('#');
+    t.strip_suffix(['"', '\'', '`']).unwrap_or(t)
+}
+
+/// Extract SQL table references from string literals in a TS/JS or Rust syntax tree.
+///
+/// Only literals whose first word is a SQL statement verb (`SELECT`, `INSERT`, `UPDATE`,
+/// `WITH`, ...) are scanned. Each referenced table yields a file-level `TableReference`
+/// relationship, which the resolver links to a `Table`/`View` symbol of the same name.
+pub fn extract_table_refs(tree: &Tree, source: &[u8]) -> Vec<RelationshipInfo> {
+    let mut results = Vec::new();
+    let mut seen: HashSet<(String, usize)> = HashSet::new();
+    let mut cursor = tree.walk();
+
+    loop {
+        let node = cursor.node();
+        let is_string = matches!(
+            node.kind(),
+            "string" | "template_string" | "string_literal" | "raw_string_literal"
+        );
+        if is_string && let Ok(text) = node.utf8_text(source) {
+            let tokens = tokenize(string_contents(text));
+            let is_sql = tokens
+                .first()
+                .and_then(Token::keyword)
+                .is_some_and(|w| is_statement_verb(&w));
+            if is_sql {
+                for stmt in statements(&tokens) {
+                    for (idx, to_name) in table_refs(stmt) {
+                        let line = node.start_position().row + stmt[idx].line;
+                        if seen.insert((to_name.to_ascii_lowercase(), line)) {
+                            results.push(RelationshipInfo {
+                                from_name: None,
+                                to_name,
+                                kind: RelationshipKind::TableReference,
+                                line,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        // Depth-first walk; string literals are leaves for our purposes.
+        if !is_string && cursor.goto_first_child() {
+            continue;
+        }
+        loop {
+            if cursor.goto_next_sibling() {
+                break;
+            }
+            if !cursor.goto_parent() {
+                return results;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA_SQL: &str = r#"
+-- Core tables
+CREATE TABLE IF NOT EXISTS public.users (
+  id SERIAL PRIMARY KEY,
+  email TEXT NOT NULL DEFAULT 'n/a; none'
+);
+
+CREATE TABLE "orders" (
+  id SERIAL PRIMARY KEY,
+  user_id INT REFERENCES users(id)
+);
+
+/* Reporting */
+CREATE OR REPLACE VIEW active_users AS
+  SELECT u.id FROM users u JOIN orders o ON o.user_id = u.id;
+
+CREATE FUNCTION touch() RETURNS trigger AS $$
+BEGIN
+  UPDATE audit SET at = now();
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE orders ADD COLUMN total INT;
+"#;
+
+    fn names(result: &ParseResult) -> Vec<(&str, &SymbolKind)> {
+        result
+            .symbols
+            .iter()
+            .map(|(s, _)| (s.name.as_str(), &s.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_sql_tables_and_views() {
+        let result = parse_sql(SCHEMA_SQL.as_bytes());
+        assert_eq!(
+            names(&result),
+            vec![
+                ("users", &SymbolKind::Table),
+                ("orders", &SymbolKind::Table),
+                ("active_users", &SymbolKind::View),
+            ]
+        );
+        let users = &result.symbols[0].0;
+        assert_eq!((users.line, users.line_end), (3, 6));
+        let view = &result.symbols[2].0;
+        assert_eq!((view.line, view.line_end), (14, 15));
+    }
+
+    #[test]
+    fn test_sql_table_references() {
+        let result = parse_sql(SCHEMA_SQL.as_bytes());
+        let rels: Vec<(Option<&str>, &str)> = result
+            .relationships
+            .iter()
+            .map(|r| (r.from_name.as_deref(), r.to_name.as_str()))
+            .collect();
+        // The dollar-quoted function body is opaque; ALTER TABLE is a file-level reference.
+        assert_eq!(
+            rels,
+            vec![
+                (Some("orders"), "users"),
+                (Some("active_users"), "users"),
+                (Some("active_users"), "orders"),
+                (None, "orders"),
+            ]
+        );
+        assert!(
+            result
+                .relationships
+                .iter()
+                .all(|r| r.kind == RelationshipKind::TableReference)
+        );
+    }
+
+    #[test]
+    fn test_table_refs_skip_ctes_functions_and_aliases() {
+        let tokens = tokenize(
+            "WITH recent AS (SELECT * FROM orders) \
+             SELECT * FROM recent r, users AS u, generate_series(1, 3) \
+             LEFT JOIN app.accounts a ON a.id = u.id WHERE u.id IN (SELECT id FROM bans)",
+        );
+        let refs: Vec<String> = table_refs(&tokens).into_iter().map(|(_, n)| n).collect();
+        assert_eq!(refs, vec!["orders", "users", "accounts", "bans"]);
+    }
+
+    fn ts_refs(source: &str) -> Vec<(String, usize)> {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        extract_table_refs(&tree, source.as_bytes())
+            .into_iter()
+            .map(|r| (r.to_name, r.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_table_refs_typescript() {
+        let source = r#"
+const msg = "failed to read from cache";
+export async function load(id: string) {
+  return db.query(`
+    SELECT * FROM users
+    WHERE id = ${id}`);
+}
+db.run('INSERT INTO audit_log (msg) VALUES ($1)', [msg]);
+"#;
+        assert_eq!(
+            ts_refs(source),
+            vec![("users".to_owned(), 5), ("audit_log".to_owned(), 8)]
+        );
+    }
+
+    #[test]
+    fn test_extract_table_refs_rust() {
+        let source = r##"
+fn load(pool: &Pool) {
+    sqlx::query!("UPDATE users SET seen = now()");
+    let q = r#"DELETE FROM "sessions" WHERE expired"#;
+    let msg = "rows from the list";
+}
+"##;
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let refs: Vec<(String, usize)> = extract_table_refs(&tree, source.as_bytes())
+            .into_iter()
+            .map(|r| (r.to_name, r.line))
+            .collect();
+        assert_eq!(
+            refs,
+            vec![("users".to_owned(), 3), ("sessions".to_owned(), 4),]
+        );
+    }
+}
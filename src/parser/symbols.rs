@@ -1,8 +1,12 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::OnceLock;
 
 use tree_sitter::{Language, Node, Query, QueryCursor, StreamingIterator, Tree};
 
-use crate::graph::node::{DecoratorInfo, SymbolInfo, SymbolKind, SymbolVisibility};
+use crate::graph::node::{
+    DecoratorInfo, ParamInfo, SignatureInfo, SymbolInfo, SymbolKind, SymbolVisibility,
+};
+use crate::parser::relationships::{RelationshipInfo, RelationshipKind};
 
 // ---------------------------------------------------------------------------
 // Rust query string
@@ -35,6 +39,10 @@ const SYMBOL_QUERY_TS: &str = r#"
     (class_declaration
       name: (type_identifier) @name) @symbol
 
+    ; Abstract class declarations (TS-only)
+    (abstract_class_declaration
+      name: (type_identifier) @name) @symbol
+
     ; Interface declarations (TS-only)
     (interface_declaration
       name: (type_identifier) @name) @symbol
@@ -79,6 +87,10 @@ const SYMBOL_QUERY_TSX: &str = r#"
     (class_declaration
       name: (type_identifier) @name) @symbol
 
+    ; Abstract class declarations (TS-only)
+    (abstract_class_declaration
+      name: (type_identifier) @name) @symbol
+
     ; Interface declarations (TS-only but TSX grammar supports it)
     (interface_declaration
       name: (type_identifier) @name) @symbol
@@ -289,7 +301,7 @@ fn find_declaration_kind(symbol_node: Node, _name_node: Node) -> Option<String>
     let kind = symbol_node.kind();
     match kind {
         "function_declaration" => Some("function_declaration".into()),
-        "class_declaration" => Some("class_declaration".into()),
+        "class_declaration" | "abstract_class_declaration" => Some("class_declaration".into()),
         "interface_declaration" => Some("interface_declaration".into()),
         "type_alias_declaration" => Some("type_alias_declaration".into()),
         "enum_declaration" => Some("enum_declaration".into()),
@@ -298,7 +310,9 @@ fn find_declaration_kind(symbol_node: Node, _name_node: Node) -> Option<String>
             for child in symbol_node.children(&mut cursor) {
                 match child.kind() {
                     "function_declaration" => return Some("function_declaration".into()),
-                    "class_declaration" => return Some("class_declaration".into()),
+                    "class_declaration" | "abstract_class_declaration" => {
+                        return Some("class_declaration".into());
+                    }
                     "interface_declaration" => return Some("interface_declaration".into()),
                     "type_alias_declaration" => return Some("type_alias_declaration".into()),
                     "enum_declaration" => return Some("enum_declaration".into()),
@@ -368,6 +382,123 @@ fn find_arrow_body<'a>(node: Node<'a>, name_node: Node<'a>) -> Option<Node<'a>>
     None
 }
 
+// ---------------------------------------------------------------------------
+// Signature extraction
+// ---------------------------------------------------------------------------
+
+/// Source text of a type with whitespace collapsed and a leading `:` (TS type
+/// annotations) removed: `: Promise<\n  User\n>` → `Promise< User >`.
+fn type_text(node: Node, source: &[u8]) -> String {
+    let text = node_text(node, source).trim_start_matches(':');
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Walk down from `node` to the first function-like node (declaration, expression, arrow
+/// function, or method).
+fn find_function_node(node: Node) -> Option<Node> {
+    if matches!(
+        node.kind(),
+        "function_declaration"
+            | "generator_function_declaration"
+            | "function_expression"
+            | "function"
+            | "generator_function"
+            | "arrow_function"
+            | "method_definition"
+            | "method_signature"
+    ) {
+        return Some(node);
+    }
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .find_map(find_function_node)
+}
+
+/// Parameters and return type of a TS/JS function-like node.
+fn ts_signature(func: Node, source: &[u8]) -> SignatureInfo {
+    let mut params = Vec::new();
+    if let Some(list) = func.child_by_field_name("parameters") {
+        let mut cursor = list.walk();
+        for param in list.named_children(&mut cursor) {
+            let param_info = match param.kind() {
+                "required_parameter" | "optional_parameter" => {
+                    let Some(pattern) = param.child_by_field_name("pattern") else {
+                        continue;
+                    };
+                    let mut name = node_text(pattern, source).to_owned();
+                    if param.kind() == "optional_parameter" {
+                        name.push('?');
+                    }
+                    ParamInfo {
+                        name,
+                        type_annotation: param
+                            .child_by_field_name("type")
+                            .map(|t| type_text(t, source)),
+                    }
+                }
+                // `x = 1` in JS: the name is the left side.
+                "assignment_pattern" => ParamInfo {
+                    name: param
+                        .child_by_field_name("left")
+                        .map(|l| node_text(l, source).to_owned())
+                        .unwrap_or_default(),
+                    type_annotation: None,
+                },
+                "comment" | "decorator" => continue,
+                _ => ParamInfo {
+                    name: node_text(param, source).to_owned(),
+                    type_annotation: None,
+                },
+            };
+            params.push(param_info);
+        }
+    } else if let Some(param) = func.child_by_field_name("parameter") {
+        // `x => ...`: a single unparenthesized arrow function parameter.
+        params.push(ParamInfo {
+            name: node_text(param, source).to_owned(),
+            type_annotation: None,
+        });
+    }
+    SignatureInfo {
+        params,
+        return_type: func
+            .child_by_field_name("return_type")
+            .map(|t| type_text(t, source)),
+    }
+}
+
+/// Parameters and return type of a Rust `function_item` / `function_signature_item`.
+fn rust_signature(func: Node, source: &[u8]) -> SignatureInfo {
+    let mut params = Vec::new();
+    if let Some(list) = func.child_by_field_name("parameters") {
+        let mut cursor = list.walk();
+        for param in list.named_children(&mut cursor) {
+            match param.kind() {
+                "self_parameter" => params.push(ParamInfo {
+                    name: node_text(param, source).to_owned(),
+                    type_annotation: None,
+                }),
+                "parameter" => params.push(ParamInfo {
+                    name: param
+                        .child_by_field_name("pattern")
+                        .map(|p| node_text(p, source).to_owned())
+                        .unwrap_or_default(),
+                    type_annotation: param
+                        .child_by_field_name("type")
+                        .map(|t| type_text(t, source)),
+                }),
+                _ => {}
+            }
+        }
+    }
+    SignatureInfo {
+        params,
+        return_type: func
+            .child_by_field_name("return_type")
+            .map(|t| type_text(t, source)),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Child-symbol extraction
 // ---------------------------------------------------------------------------
@@ -405,6 +536,11 @@ fn extract_interface_children(iface_node: Node, source: &[u8]) -> Vec<SymbolInfo
                         line: pos.row + 1,
                         col: pos.column,
                         line_end: child.end_position().row + 1,
+                        col_end: child.end_position().column,
+                        byte_start: child.start_byte(),
+                        byte_end: child.end_byte(),
+                        signature: (child.kind() == "method_signature")
+                            .then(|| ts_signature(child, source)),
                         ..Default::default()
                     });
                 }
@@ -436,26 +572,90 @@ fn extract_class_children(class_node: Node, source: &[u8]) -> Vec<SymbolInfo> {
 
     let mut cursor = body.walk();
     for child in body.children(&mut cursor) {
-        if child.kind() == "method_definition"
-            && let Some(name_node) = child.child_by_field_name("name")
-        {
-            let name = node_text(name_node, source).to_owned();
-            let pos = name_node.start_position();
-            let decorators = extract_ts_decorators(child, source);
-            children.push(SymbolInfo {
-                name,
-                kind: SymbolKind::Method,
-                line: pos.row + 1,
-                col: pos.column,
-                line_end: child.end_position().row + 1,
-                decorators,
-                ..Default::default()
-            });
-        }
+        let (kind, name_node) = match child.kind() {
+            "method_definition" | "abstract_method_signature" => {
+                (SymbolKind::Method, child.child_by_field_name("name"))
+            }
+            "public_field_definition" => (SymbolKind::Property, child.child_by_field_name("name")),
+            "field_definition" => (SymbolKind::Property, child.child_by_field_name("property")),
+            _ => continue,
+        };
+        let Some(name_node) = name_node else {
+            continue;
+        };
+        let pos = name_node.start_position();
+        children.push(SymbolInfo {
+            name: node_text(name_node, source).to_owned(),
+            line: pos.row + 1,
+            col: pos.column,
+            line_end: child.end_position().row + 1,
+            col_end: child.end_position().column,
+            byte_start: child.start_byte(),
+            byte_end: child.end_byte(),
+            decorators: extract_ts_decorators(child, source),
+            signature: (kind == SymbolKind::Method).then(|| ts_signature(child, source)),
+            visibility: ts_member_visibility(child, name_node, source),
+            is_abstract: child.kind() == "abstract_method_signature",
+            is_override: {
+                let mut cursor = child.walk();
+                child
+                    .children(&mut cursor)
+                    .any(|c| c.kind() == "override_modifier")
+            },
+            kind,
+            ..Default::default()
+        });
     }
     children
 }
 
+/// Visibility of a class member: `private` and `#name` members are `Private`, `protected`
+/// members `Protected`, everything else `Pub`.
+fn ts_member_visibility(member: Node, name_node: Node, source: &[u8]) -> SymbolVisibility {
+    if name_node.kind() == "private_property_identifier" {
+        return SymbolVisibility::Private;
+    }
+    let mut cursor = member.walk();
+    let modifier = member
+        .children(&mut cursor)
+        .find(|c| c.kind() == "accessibility_modifier")
+        .map(|m| node_text(m, source));
+    match modifier {
+        Some("private") => SymbolVisibility::Private,
+        Some("protected") => SymbolVisibility::Protected,
+        _ => SymbolVisibility::Pub,
+    }
+}
+
+/// Module visibility of top-level TS/JS symbols: exported symbols are `Pub`, the rest
+/// `Private` to their module. Namespace members follow their own `export`; interface
+/// members are always `Pub`, and class members keep their modifier.
+fn apply_ts_visibility(results: &mut [(SymbolInfo, Vec<SymbolInfo>)]) {
+    let module_visibility = |info: &SymbolInfo| {
+        if info.is_exported {
+            SymbolVisibility::Pub
+        } else {
+            SymbolVisibility::Private
+        }
+    };
+    for (info, children) in results {
+        info.visibility = module_visibility(info);
+        match info.kind {
+            SymbolKind::Namespace => {
+                for child in children {
+                    child.visibility = module_visibility(child);
+                }
+            }
+            SymbolKind::Interface => {
+                for child in children {
+                    child.visibility = SymbolVisibility::Pub;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Decorator / attribute extraction helpers
 // ---------------------------------------------------------------------------
@@ -604,25 +804,78 @@ fn parse_decorator_node(decorator_node: tree_sitter::Node, source: &[u8]) -> Dec
     }
 }
 
-/// Walk previous siblings of `item_node` in its parent to find `attribute_item` nodes (Rust).
+/// Walk previous siblings of `item_node` to find the `attribute_item` nodes directly above
+/// it (Rust), in source order. Comments between them are skipped; any other item ends the
+/// run, so attributes of earlier items are not picked up.
 fn extract_rust_attributes(item_node: tree_sitter::Node, source: &[u8]) -> Vec<DecoratorInfo> {
     let mut attrs = Vec::new();
-    let parent = match item_node.parent() {
-        Some(p) => p,
-        None => return attrs,
-    };
-    for i in 0..parent.child_count() {
-        let child = parent.child(i as u32).unwrap();
-        if child.id() == item_node.id() {
-            break;
-        }
-        if child.kind() == "attribute_item" {
-            attrs.push(parse_rust_attribute(child, source));
+    let mut prev = item_node.prev_sibling();
+    while let Some(sibling) = prev {
+        match sibling.kind() {
+            "attribute_item" => attrs.push(parse_rust_attribute(sibling, source)),
+            "line_comment" | "block_comment" => {}
+            _ => break,
         }
+        prev = sibling.prev_sibling();
     }
+    attrs.reverse();
     attrs
 }
 
+/// The `#[cfg(...)]` predicate gating a Rust item: the outer `cfg` attributes directly above
+/// it and above every enclosing `mod`/`impl`/`trait` item, plus inner `#![cfg(...)]`
+/// attributes of the enclosing bodies and file. Several predicates combine into `all(...)`,
+/// outermost first.
+fn extract_rust_cfg(item_node: Node, source: &[u8]) -> Option<String> {
+    fn predicate(attr: Node, source: &[u8]) -> Option<String> {
+        let text = node_text(attr, source);
+        let inner = text
+            .strip_prefix("#!")
+            .or_else(|| text.strip_prefix('#'))?
+            .trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .trim();
+        let args = inner.strip_prefix("cfg")?.trim_start();
+        let args = args.strip_prefix('(')?.strip_suffix(')')?;
+        Some(args.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
+    let mut predicates: Vec<String> = Vec::new();
+    let mut node = Some(item_node);
+    while let Some(current) = node {
+        if current.id() == item_node.id() || current.kind().ends_with("_item") {
+            let mut prev = current.prev_sibling();
+            while let Some(sibling) = prev {
+                match sibling.kind() {
+                    "attribute_item" => predicates.extend(predicate(sibling, source)),
+                    "line_comment" | "block_comment" => {}
+                    _ => break,
+                }
+                prev = sibling.prev_sibling();
+            }
+        }
+        if matches!(current.kind(), "declaration_list" | "source_file") {
+            let mut cursor = current.walk();
+            for child in current.children(&mut cursor) {
+                if child.kind() == "inner_attribute_item" {
+                    predicates.extend(predicate(child, source));
+                }
+            }
+        }
+        node = current.parent();
+    }
+
+    predicates.reverse();
+    let mut seen = std::collections::HashSet::new();
+    predicates.retain(|p| seen.insert(p.clone()));
+    match predicates.len() {
+        0 => None,
+        1 => predicates.pop(),
+        _ => Some(format!("all({})", predicates.join(", "))),
+    }
+}
+
 /// Parse a Rust `attribute_item` node (e.g. `#[derive(Clone, Debug)]`) into a `DecoratorInfo`.
 fn parse_rust_attribute(attr_item: tree_sitter::Node, source: &[u8]) -> DecoratorInfo {
     let full_text = node_text(attr_item, source);
@@ -754,6 +1007,14 @@ pub fn extract_symbols(
 
         let (is_exported, is_default) = detect_export(sym_node, source);
         let decorators = extract_ts_decorators(sym_node, source);
+        let signature = if matches!(kind, SymbolKind::Function | SymbolKind::Component) {
+            find_function_node(sym_node).map(|func| ts_signature(func, source))
+        } else {
+            None
+        };
+
+        let is_abstract = kind == SymbolKind::Class
+            && find_declaration_node(sym_node, "abstract_class_declaration").is_some();
 
         let info = SymbolInfo {
             name,
@@ -761,9 +1022,14 @@ pub fn extract_symbols(
             line: pos.row + 1,
             col: pos.column,
             line_end: sym_node.end_position().row + 1,
+            col_end: sym_node.end_position().column,
+            byte_start: sym_node.start_byte(),
+            byte_end: sym_node.end_byte(),
             is_exported,
             is_default,
             decorators,
+            signature,
+            is_abstract,
             ..Default::default()
         };
 
@@ -776,7 +1042,8 @@ pub fn extract_symbols(
                     .unwrap_or_default()
             }
             SymbolKind::Class => {
-                let class_node = find_declaration_node(sym_node, "class_declaration");
+                let class_node = find_declaration_node(sym_node, "class_declaration")
+                    .or_else(|| find_declaration_node(sym_node, "abstract_class_declaration"));
                 class_node
                     .map(|n| extract_class_children(n, source))
                     .unwrap_or_default()
@@ -787,9 +1054,307 @@ pub fn extract_symbols(
         results.push((info, children));
     }
 
+    if !matches!(lang_kind(language, is_tsx), LangKind::JavaScript) {
+        fold_ts_namespaces(tree.root_node(), source, &mut results);
+        merge_ts_declarations(&mut results);
+    }
+    attribute_commonjs_exports(tree.root_node(), source, &mut results);
+    apply_ts_visibility(&mut results);
     results
 }
 
+/// Top-level TypeScript namespaces — `namespace Foo {}`, `export namespace A.B {}`,
+/// `declare namespace Foo {}`, `module Foo {}` — as (declaration, name) nodes. Ambient
+/// module declarations (`declare module 'pkg' {}`) are not namespaces.
+fn ts_namespaces(root: Node<'_>) -> Vec<(Node<'_>, Node<'_>)> {
+    let mut namespaces = Vec::new();
+    let mut cursor = root.walk();
+    for child in root.children(&mut cursor) {
+        let mut node = child;
+        while matches!(
+            node.kind(),
+            "export_statement" | "expression_statement" | "ambient_declaration"
+        ) {
+            match node
+                .child_by_field_name("declaration")
+                .or_else(|| node.named_child(0))
+            {
+                Some(inner) => node = inner,
+                None => break,
+            }
+        }
+        if matches!(node.kind(), "internal_module" | "module")
+            && let Some(name) = node.child_by_field_name("name")
+            && matches!(name.kind(), "identifier" | "nested_identifier")
+        {
+            namespaces.push((node, name));
+        }
+    }
+    namespaces
+}
+
+/// Add a `Namespace` symbol for each top-level namespace and move the declarations inside
+/// it from the top level to its children. Members of a nested class or interface are
+/// not kept.
+fn fold_ts_namespaces(root: Node, source: &[u8], results: &mut Vec<(SymbolInfo, Vec<SymbolInfo>)>) {
+    for (node, name) in ts_namespaces(root) {
+        let (start, end) = (node.start_byte(), node.end_byte());
+        let (members, rest): (Vec<_>, Vec<_>) = std::mem::take(results)
+            .into_iter()
+            .partition(|(info, _)| info.byte_start >= start && info.byte_end <= end);
+        *results = rest;
+
+        let (is_exported, is_default) = detect_export(node, source);
+        let pos = name.start_position();
+        let info = SymbolInfo {
+            name: node_text(name, source).to_owned(),
+            kind: SymbolKind::Namespace,
+            line: pos.row + 1,
+            col: pos.column,
+            line_end: node.end_position().row + 1,
+            col_end: node.end_position().column,
+            byte_start: start,
+            byte_end: end,
+            is_exported,
+            is_default,
+            ..Default::default()
+        };
+        results.push((info, members.into_iter().map(|(m, _)| m).collect()));
+    }
+    results.sort_by_key(|(info, _)| info.byte_start);
+}
+
+/// Merge order of a declaration kind: values (classes, functions, enums) absorb
+/// interfaces, which absorb namespaces. `None` for kinds TypeScript never merges.
+fn merge_rank(kind: &SymbolKind) -> Option<u8> {
+    match kind {
+        SymbolKind::Class | SymbolKind::Function | SymbolKind::Component | SymbolKind::Enum => {
+            Some(0)
+        }
+        SymbolKind::Interface => Some(1),
+        SymbolKind::Namespace => Some(2),
+        _ => None,
+    }
+}
+
+/// Fold TypeScript declaration merging into one symbol per name: repeated interfaces,
+/// repeated namespaces or enums, and a namespace or interface merged into the class,
+/// function, or enum of the same name. The highest-ranked (then first) declaration
+/// keeps its location; the members of the others are appended to its children.
+fn merge_ts_declarations(results: &mut Vec<(SymbolInfo, Vec<SymbolInfo>)>) {
+    let mut merged: Vec<(SymbolInfo, Vec<SymbolInfo>)> = Vec::with_capacity(results.len());
+    let mut by_name: HashMap<String, usize> = HashMap::new();
+    for (info, mut children) in std::mem::take(results) {
+        let Some(rank) = merge_rank(&info.kind) else {
+            merged.push((info, children));
+            continue;
+        };
+        let Some(&i) = by_name.get(&info.name) else {
+            by_name.insert(info.name.clone(), merged.len());
+            merged.push((info, children));
+            continue;
+        };
+        let (primary, members) = &mut merged[i];
+        let primary_rank = merge_rank(&primary.kind).unwrap_or(0);
+        let both_enums = primary.kind == SymbolKind::Enum && info.kind == SymbolKind::Enum;
+        if rank == 0 && primary_rank == 0 && !both_enums {
+            // Two classes or functions never merge (overloads have no body).
+            merged.push((info, children));
+            continue;
+        }
+        let is_exported = primary.is_exported || info.is_exported;
+        if rank < primary_rank {
+            *primary = info;
+            children.append(members);
+            *members = children;
+        } else {
+            members.append(&mut children);
+        }
+        primary.is_exported = is_exported;
+        let mut seen = HashSet::new();
+        members.retain(|m| seen.insert((m.name.clone(), m.line)));
+    }
+    *results = merged;
+}
+
+/// A top-level CommonJS export assignment: `module.exports = …`, `exports.name = …`, or
+/// `module.exports.name = …`.
+enum CommonJsExport<'a> {
+    /// `module.exports = value`.
+    Module(Node<'a>),
+    /// `exports.name = value` — the property node and the value.
+    Named(Node<'a>, Node<'a>),
+}
+
+/// Classify a top-level statement as a CommonJS export assignment.
+fn commonjs_export<'a>(stmt: Node<'a>, source: &[u8]) -> Option<CommonJsExport<'a>> {
+    if stmt.kind() != "expression_statement" {
+        return None;
+    }
+    let assign = stmt
+        .named_child(0)
+        .filter(|n| n.kind() == "assignment_expression")?;
+    let (left, right) = (
+        assign.child_by_field_name("left")?,
+        assign.child_by_field_name("right")?,
+    );
+    if left.kind() != "member_expression" {
+        return None;
+    }
+    let text = node_text(left, source);
+    if text == "module.exports" {
+        return Some(CommonJsExport::Module(right));
+    }
+    let object = node_text(left.child_by_field_name("object")?, source);
+    let property = left.child_by_field_name("property")?;
+    (matches!(object, "exports" | "module.exports") && property.kind() == "property_identifier")
+        .then_some(CommonJsExport::Named(property, right))
+}
+
+/// A symbol declared by a CommonJS export assignment, named by `name` and spanning `node`.
+fn commonjs_symbol(name: Node, node: Node, value: Node, source: &[u8]) -> SymbolInfo {
+    let kind = match value.kind() {
+        "class" => SymbolKind::Class,
+        k if is_arrow_or_function_value(value)
+            || matches!(k, "function_expression" | "method_definition") =>
+        {
+            SymbolKind::Function
+        }
+        _ => SymbolKind::Variable,
+    };
+    let pos = name.start_position();
+    SymbolInfo {
+        name: node_text(name, source).to_owned(),
+        kind,
+        line: pos.row + 1,
+        col: pos.column,
+        line_end: node.end_position().row + 1,
+        col_end: node.end_position().column,
+        byte_start: node.start_byte(),
+        byte_end: node.end_byte(),
+        is_exported: true,
+        ..Default::default()
+    }
+}
+
+/// The top-level `const`/`let`/`var` declarator of `name`, if any.
+fn top_level_declarator<'a>(root: Node<'a>, name: &str, source: &[u8]) -> Option<Node<'a>> {
+    let mut cursor = root.walk();
+    root.children(&mut cursor)
+        .filter(|n| matches!(n.kind(), "lexical_declaration" | "variable_declaration"))
+        .flat_map(|decl| {
+            let mut c = decl.walk();
+            decl.named_children(&mut c).collect::<Vec<_>>()
+        })
+        .find(|d| {
+            d.kind() == "variable_declarator"
+                && d.child_by_field_name("name")
+                    .is_some_and(|n| node_text(n, source) == name)
+        })
+}
+
+/// Attribute CommonJS exports to named symbols: a local exported through
+/// `module.exports = { a, b: c }`, `module.exports = a`, or `exports.x = a` is marked
+/// exported (a top-level variable not otherwise indexed becomes a symbol), and a
+/// function, class, or value assigned directly to an export becomes a symbol of the
+/// export's name.
+fn attribute_commonjs_exports(
+    root: Node,
+    source: &[u8],
+    results: &mut Vec<(SymbolInfo, Vec<SymbolInfo>)>,
+) {
+    // Mark the local `name` exported, indexing its top-level declaration if needed.
+    let export_local =
+        |results: &mut Vec<(SymbolInfo, Vec<SymbolInfo>)>, name: &str, is_default: bool| {
+            if let Some((info, _)) = results.iter_mut().find(|(info, _)| info.name == name) {
+                info.is_exported = true;
+                info.is_default |= is_default;
+            } else if let Some(decl) = top_level_declarator(root, name, source)
+                && let (Some(id), Some(value)) = (
+                    decl.child_by_field_name("name"),
+                    decl.child_by_field_name("value"),
+                )
+            {
+                let mut info = commonjs_symbol(id, decl.parent().unwrap_or(decl), value, source);
+                info.is_default = is_default;
+                results.push((info, vec![]));
+            }
+        };
+    // Index a value assigned straight to the export `name`, unless it is already a symbol.
+    let declare =
+        |results: &mut Vec<(SymbolInfo, Vec<SymbolInfo>)>, name: Node, node: Node, value: Node| {
+            if !results
+                .iter()
+                .any(|(info, _)| info.name == node_text(name, source))
+            {
+                results.push((commonjs_symbol(name, node, value, source), vec![]));
+            }
+        };
+
+    let count = results.len();
+    let mut cursor = root.walk();
+    for stmt in root.children(&mut cursor) {
+        match commonjs_export(stmt, source) {
+            Some(CommonJsExport::Named(property, value)) => {
+                if value.kind() == "identifier" {
+                    export_local(results, node_text(value, source), false);
+                } else {
+                    declare(results, property, stmt, value);
+                }
+            }
+            Some(CommonJsExport::Module(value)) => match value.kind() {
+                "identifier" => export_local(results, node_text(value, source), true),
+                "object" => {
+                    let mut c = value.walk();
+                    for entry in value.named_children(&mut c) {
+                        match entry.kind() {
+                            "shorthand_property_identifier" => {
+                                export_local(results, node_text(entry, source), false)
+                            }
+                            "pair" => {
+                                let (Some(key), Some(val)) = (
+                                    entry.child_by_field_name("key"),
+                                    entry.child_by_field_name("value"),
+                                ) else {
+                                    continue;
+                                };
+                                if val.kind() == "identifier" {
+                                    export_local(results, node_text(val, source), false);
+                                } else if key.kind() == "property_identifier" {
+                                    declare(results, key, entry, val);
+                                }
+                            }
+                            "method_definition" => {
+                                if let Some(key) = entry.child_by_field_name("name") {
+                                    declare(results, key, entry, entry);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                "class" | "function_expression" => {
+                    if let Some(name) = value.child_by_field_name("name") {
+                        declare(results, name, stmt, value);
+                        if let Some((info, _)) = results
+                            .iter_mut()
+                            .find(|(info, _)| info.name == node_text(name, source))
+                        {
+                            info.is_exported = true;
+                            info.is_default = true;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            None => {}
+        }
+    }
+    if results.len() != count {
+        results.sort_by_key(|(info, _)| info.byte_start);
+    }
+}
+
 /// Walk down from `node` to find a child (or the node itself) of kind `target_kind`.
 fn find_declaration_node<'a>(node: Node<'a>, target_kind: &str) -> Option<Node<'a>> {
     if node.kind() == target_kind {
@@ -891,8 +1456,13 @@ fn extract_trait_methods(trait_node: Node, trait_name: &str, source: &[u8]) -> V
                         line: pos.row + 1,
                         col: pos.column,
                         line_end: child.end_position().row + 1,
+                        col_end: child.end_position().column,
+                        byte_start: child.start_byte(),
+                        byte_end: child.end_byte(),
                         visibility,
                         decorators,
+                        signature: Some(rust_signature(child, source)),
+                        cfg: extract_rust_cfg(child, source),
                         ..Default::default()
                     });
                 }
@@ -980,8 +1550,13 @@ pub fn extract_rust_symbols(
             line: pos.row + 1,
             col: pos.column,
             line_end: sym_node.end_position().row + 1,
+            col_end: sym_node.end_position().column,
+            byte_start: sym_node.start_byte(),
+            byte_end: sym_node.end_byte(),
             visibility,
             decorators,
+            signature: (kind == SymbolKind::Function).then(|| rust_signature(sym_node, source)),
+            cfg: extract_rust_cfg(sym_node, source),
             ..Default::default()
         };
 
@@ -1023,6 +1598,17 @@ pub fn extract_impl_methods(tree: &Tree, source: &[u8]) -> Vec<(SymbolInfo, Vec<
         let trait_name: Option<String> = child
             .child_by_field_name("trait")
             .map(|trait_node| extract_simple_type_name(trait_node, source).to_owned());
+        // `#[automatically_derived]` marks impls a derive macro expanded to.
+        let derived = extract_rust_attributes(child, source)
+            .iter()
+            .any(|attr| attr.name == "automatically_derived")
+            .then(|| {
+                child
+                    .child_by_field_name("trait")
+                    .and_then(|t| impl_path_name(t, source))
+                    .unwrap_or("derive")
+                    .to_owned()
+            });
 
         // Find the declaration_list body
         let decl_list = {
@@ -1065,9 +1651,15 @@ pub fn extract_impl_methods(tree: &Tree, source: &[u8]) -> Vec<(SymbolInfo, Vec<
                     line: pos.row + 1,
                     col: pos.column,
                     line_end: method_node.end_position().row + 1,
+                    col_end: method_node.end_position().column,
+                    byte_start: method_node.start_byte(),
+                    byte_end: method_node.end_byte(),
                     visibility,
                     trait_impl: trait_name.clone(),
                     decorators,
+                    signature: Some(rust_signature(method_node, source)),
+                    cfg: extract_rust_cfg(method_node, source),
+                    derived: derived.clone(),
                     ..Default::default()
                 },
                 vec![],
@@ -1078,6 +1670,43 @@ pub fn extract_impl_methods(tree: &Tree, source: &[u8]) -> Vec<(SymbolInfo, Vec<
     results
 }
 
+/// Bare name of the type or trait in an impl header: `fmt::Display` → `Display`,
+/// `From<T>` → `From`. Returns `None` for references, tuples, and other non-path types.
+fn impl_path_name<'a>(node: Node<'a>, source: &'a [u8]) -> Option<&'a str> {
+    match node.kind() {
+        "type_identifier" => Some(node_text(node, source)),
+        "scoped_type_identifier" => node
+            .child_by_field_name("name")
+            .map(|n| node_text(n, source)),
+        "generic_type" => impl_path_name(node.child_by_field_name("type")?, source),
+        _ => None,
+    }
+}
+
+/// Extract `impl Trait for Type` blocks from a Rust syntax tree as `Implements`
+/// relationships from the type to the trait.
+///
+/// Like [`extract_impl_methods`], only top-level impl blocks are considered. Paths and
+/// generic arguments are stripped, so `impl fmt::Display for Wrapper<T>` yields
+/// `Wrapper → Display`; the resolver drops traits that are not defined in the project.
+pub fn extract_rust_trait_impls(tree: &Tree, source: &[u8]) -> Vec<RelationshipInfo> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    root.children(&mut cursor)
+        .filter(|child| child.kind() == "impl_item")
+        .filter_map(|child| {
+            let trait_name = impl_path_name(child.child_by_field_name("trait")?, source)?;
+            let type_name = impl_path_name(child.child_by_field_name("type")?, source)?;
+            Some(RelationshipInfo {
+                from_name: Some(type_name.to_owned()),
+                to_name: trait_name.to_owned(),
+                kind: RelationshipKind::Implements,
+                line: child.start_position().row + 1,
+            })
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1222,6 +1851,179 @@ mod tests {
         assert!(children.iter().all(|c| c.kind == SymbolKind::Method));
     }
 
+    #[test]
+    fn test_ts_namespaces_and_declaration_merging() {
+        let src = r#"
+export namespace Geo {
+  export function area() {}
+  export const unit = "m";
+}
+namespace Geo { export class Shape {} }
+interface Point { x: number; }
+interface Point { y: number; }
+class Album {}
+namespace Album { export interface Track {} }
+declare namespace Lib.Util { function log(): void; }
+declare module "pkg" { export interface Augmented {} }
+"#;
+        let (tree, lang) = parse_ts(src);
+        let results = extract_symbols(&tree, src.as_bytes(), &lang, false);
+        let summary: Vec<(String, SymbolKind, usize, Vec<String>)> = results
+            .iter()
+            .map(|(s, children)| {
+                let mut names: Vec<String> = children.iter().map(|c| c.name.clone()).collect();
+                names.sort();
+                (s.name.clone(), s.kind.clone(), s.line, names)
+            })
+            .collect();
+        let names = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Geo".into(),
+                    SymbolKind::Namespace,
+                    2,
+                    names(&["Shape", "area", "unit"])
+                ),
+                ("Point".into(), SymbolKind::Interface, 7, names(&["x", "y"])),
+                ("Album".into(), SymbolKind::Class, 9, names(&["Track"])),
+                ("Lib.Util".into(), SymbolKind::Namespace, 11, names(&[])),
+                ("Augmented".into(), SymbolKind::Interface, 12, names(&[])),
+            ]
+        );
+        assert!(results[0].0.is_exported, "export namespace is exported");
+    }
+
+    #[test]
+    fn test_commonjs_exports_attributed_to_symbols() {
+        let src = r#"
+function parse() {}
+function internal() {}
+const LIMIT = 10;
+var format = function () {};
+class Reader {}
+module.exports = { parse, LIMIT, fmt: format, build() {} };
+exports.Reader = Reader;
+exports.version = "1.0";
+module.exports.run = () => {};
+"#;
+        let lang = language_for_extension("js").unwrap();
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&lang).unwrap();
+        let tree = parser.parse(src.as_bytes(), None).unwrap();
+        let results = extract_symbols(&tree, src.as_bytes(), &lang, false);
+        let summary: Vec<(&str, SymbolKind, bool)> = results
+            .iter()
+            .map(|(s, _)| (s.name.as_str(), s.kind.clone(), s.is_exported))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("parse", SymbolKind::Function, true),
+                ("internal", SymbolKind::Function, false),
+                ("LIMIT", SymbolKind::Variable, true),
+                ("format", SymbolKind::Function, true),
+                ("Reader", SymbolKind::Class, true),
+                ("build", SymbolKind::Function, true),
+                ("version", SymbolKind::Variable, true),
+                ("run", SymbolKind::Function, true),
+            ]
+        );
+
+        let src = "module.exports = function handler() {};\n";
+        let tree = parser.parse(src.as_bytes(), None).unwrap();
+        let results = extract_symbols(&tree, src.as_bytes(), &lang, false);
+        assert_eq!(results.len(), 1);
+        let sym = &results[0].0;
+        assert_eq!(sym.name, "handler");
+        assert!(sym.is_exported && sym.is_default);
+    }
+
+    #[test]
+    fn test_ts_visibility_of_symbols_and_members() {
+        let src = r#"
+export class Service {
+  private cache = new Map();
+  protected retries = 3;
+  #secret = 1;
+  name = "svc";
+  private helper() {}
+  protected hook() {}
+  public run() {}
+  #tick() {}
+}
+function local() {}
+export interface Options { verbose: boolean }
+"#;
+        let (tree, lang) = parse_ts(src);
+        let results = extract_symbols(&tree, src.as_bytes(), &lang, false);
+        let top: Vec<(&str, SymbolVisibility)> = results
+            .iter()
+            .map(|(s, _)| (s.name.as_str(), s.visibility.clone()))
+            .collect();
+        assert_eq!(
+            top,
+            vec![
+                ("Service", SymbolVisibility::Pub),
+                ("local", SymbolVisibility::Private),
+                ("Options", SymbolVisibility::Pub),
+            ]
+        );
+        let members: Vec<(&str, SymbolKind, SymbolVisibility)> = results[0]
+            .1
+            .iter()
+            .map(|m| (m.name.as_str(), m.kind.clone(), m.visibility.clone()))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("cache", SymbolKind::Property, SymbolVisibility::Private),
+                ("retries", SymbolKind::Property, SymbolVisibility::Protected),
+                ("#secret", SymbolKind::Property, SymbolVisibility::Private),
+                ("name", SymbolKind::Property, SymbolVisibility::Pub),
+                ("helper", SymbolKind::Method, SymbolVisibility::Private),
+                ("hook", SymbolKind::Method, SymbolVisibility::Protected),
+                ("run", SymbolKind::Method, SymbolVisibility::Pub),
+                ("#tick", SymbolKind::Method, SymbolVisibility::Private),
+            ]
+        );
+        assert_eq!(results[2].1[0].visibility, SymbolVisibility::Pub);
+    }
+
+    #[test]
+    fn test_ts_abstract_classes_and_override_members() {
+        let src = r#"
+export abstract class Report {
+  abstract title(): string;
+  render(): string { return this.title(); }
+}
+class Sales extends Report {
+  override title(): string { return "sales"; }
+}
+"#;
+        let (tree, lang) = parse_ts(src);
+        let results = extract_symbols(&tree, src.as_bytes(), &lang, false);
+        let (report, members) = &results[0];
+        assert_eq!(report.name, "Report");
+        assert_eq!(report.kind, SymbolKind::Class);
+        assert!(report.is_exported);
+        assert!(report.is_abstract);
+        let flags: Vec<(&str, bool, bool)> = members
+            .iter()
+            .map(|m| (m.name.as_str(), m.is_abstract, m.is_override))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![("title", true, false), ("render", false, false)]
+        );
+        assert!(members[0].signature.is_some());
+
+        let (sales, members) = &results[1];
+        assert!(!sales.is_abstract);
+        assert!(members[0].is_override);
+    }
+
     fn parse_rs(source: &str) -> (tree_sitter::Tree, Language) {
         let lang = language_for_extension("rs").unwrap();
         let mut parser = tree_sitter::Parser::new();
@@ -1309,6 +2111,93 @@ mod tests {
         );
     }
 
+    // Test: Rust #[cfg(...)] predicates, own and inherited from enclosing items
+    #[test]
+    fn test_rust_cfg_predicates() {
+        let src = r#"
+pub fn always() {}
+
+#[cfg(feature = "web")]
+/// Serve.
+#[inline]
+pub fn serve() {}
+
+#[derive(Debug)]
+pub struct Plain;
+
+#[cfg(unix)]
+mod platform {
+    #[cfg(test)]
+    fn helper() {}
+}
+
+#[cfg(test)]
+mod tests {
+    fn make() {}
+}
+"#;
+        let (tree, lang) = parse_rs(src);
+        let results = extract_rust_symbols(&tree, src.as_bytes(), &lang);
+        let cfg_of = |name: &str| {
+            results
+                .iter()
+                .find(|(s, _)| s.name == name)
+                .unwrap_or_else(|| panic!("{name} not extracted"))
+                .0
+                .cfg
+                .clone()
+        };
+        assert_eq!(cfg_of("always"), None);
+        assert_eq!(cfg_of("serve").as_deref(), Some(r#"feature = "web""#));
+        assert_eq!(cfg_of("Plain"), None);
+        assert_eq!(cfg_of("helper").as_deref(), Some("all(unix, test)"));
+        assert_eq!(cfg_of("make").as_deref(), Some("test"));
+
+        let src = "#![cfg(windows)]
+impl Foo {
+    #[cfg(test)]
+    fn check(&self) {}
+}
+";
+        let (tree, _) = parse_rs(src);
+        let methods = extract_impl_methods(&tree, src.as_bytes());
+        assert_eq!(methods[0].0.cfg.as_deref(), Some("all(windows, test)"));
+    }
+
+    // Test: methods of #[automatically_derived] impls are marked with the derived trait
+    #[test]
+    fn test_rust_derived_impl_methods() {
+        let src = "#[derive(Clone, serde::Serialize)]
+pub struct Point { x: i32 }
+#[automatically_derived]
+impl ::core::fmt::Debug for Point {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result { Ok(()) }
+}
+impl Point {
+    fn norm(&self) -> i32 { self.x }
+}
+fn origin() -> Point { Point { x: 0 } }
+";
+        let (tree, lang) = parse_rs(src);
+        let symbols = extract_rust_symbols(&tree, src.as_bytes(), &lang);
+        assert_eq!(
+            first_symbol(&symbols).derive_traits(),
+            vec!["Clone", "Serialize"]
+        );
+        // Attributes of earlier items do not carry over.
+        let origin = symbols.iter().find(|(s, _)| s.name == "origin").unwrap();
+        assert!(origin.0.decorators.is_empty(), "{:?}", origin.0.decorators);
+        let methods = extract_impl_methods(&tree, src.as_bytes());
+        let derived: Vec<(&str, Option<&str>)> = methods
+            .iter()
+            .map(|(m, _)| (m.name.as_str(), m.derived.as_deref()))
+            .collect();
+        assert_eq!(
+            derived,
+            vec![("Point::fmt", Some("Debug")), ("Point::norm", None)]
+        );
+    }
+
     // Test: Rust #[get("/path")] attribute extraction
     #[test]
     fn test_rust_route_decorator() {
@@ -1379,4 +2268,161 @@ mod tests {
         assert_eq!(sym.decorators[0].name, "Controller");
         assert_eq!(sym.decorators[1].name, "Injectable");
     }
+
+    #[test]
+    fn test_rust_trait_impls() {
+        let src = r#"
+impl Shape for Circle {}
+impl<T> fmt::Display for Wrapper<T> {}
+impl Circle {}
+impl Shape for &Square {}
+"#;
+        let (tree, _) = parse_rs(src);
+        let rels = extract_rust_trait_impls(&tree, src.as_bytes());
+        let impls: Vec<(Option<&str>, &str, usize)> = rels
+            .iter()
+            .map(|r| (r.from_name.as_deref(), r.to_name.as_str(), r.line))
+            .collect();
+        assert_eq!(
+            impls,
+            vec![
+                (Some("Circle"), "Shape", 2),
+                (Some("Wrapper"), "Display", 3)
+            ]
+        );
+    }
+
+    /// `(name, type)` pairs and return type of a signature, for compact assertions.
+    fn sig(info: &SymbolInfo) -> (Vec<(String, Option<String>)>, Option<String>) {
+        let sig = info.signature.as_ref().expect("expected a signature");
+        (
+            sig.params
+                .iter()
+                .map(|p| (p.name.clone(), p.type_annotation.clone()))
+                .collect(),
+            sig.return_type.clone(),
+        )
+    }
+
+    fn param(name: &str, ty: Option<&str>) -> (String, Option<String>) {
+        (name.to_string(), ty.map(str::to_string))
+    }
+
+    #[test]
+    fn test_ts_signatures() {
+        let src = r#"
+export async function load(id: string, opts?: Options, ...rest: unknown[]): Promise<User> {}
+export const double = x => x * 2;
+class Repo {
+  save(user: User, force = false): void {}
+}
+interface Api {
+  get(path: string): Response;
+  name: string;
+}
+"#;
+        let (tree, lang) = parse_ts(src);
+        let results = extract_symbols(&tree, src.as_bytes(), &lang, false);
+        let find = |name: &str| results.iter().find(|(s, _)| s.name == name).unwrap();
+
+        assert_eq!(
+            sig(&find("load").0),
+            (
+                vec![
+                    param("id", Some("string")),
+                    param("opts?", Some("Options")),
+                    param("...rest", Some("unknown[]")),
+                ],
+                Some("Promise<User>".to_string())
+            )
+        );
+        assert_eq!(sig(&find("double").0), (vec![param("x", None)], None));
+
+        let save = &find("Repo").1[0];
+        assert_eq!(
+            sig(save),
+            (
+                vec![param("user", Some("User")), param("force", None)],
+                Some("void".to_string())
+            )
+        );
+
+        let api = &find("Api").1;
+        assert_eq!(
+            sig(&api[0]),
+            (
+                vec![param("path", Some("string"))],
+                Some("Response".to_string())
+            )
+        );
+        assert!(api[1].signature.is_none(), "properties have no signature");
+    }
+
+    #[test]
+    fn test_rust_signatures() {
+        let src = r#"
+pub fn resolve(root: &Path, names: Vec<String>) -> Result<()> { Ok(()) }
+struct Cache;
+impl Cache {
+    fn get(&self, key: &str) -> Option<&Entry> { None }
+}
+trait Store {
+    fn put(&mut self, value: u32);
+}
+"#;
+        let (tree, lang) = parse_rs(src);
+        let symbols = extract_rust_symbols(&tree, src.as_bytes(), &lang);
+        let resolve = &symbols.iter().find(|(s, _)| s.name == "resolve").unwrap().0;
+        assert_eq!(
+            sig(resolve),
+            (
+                vec![
+                    param("root", Some("&Path")),
+                    param("names", Some("Vec<String>"))
+                ],
+                Some("Result<()>".to_string())
+            )
+        );
+        let cache = &symbols.iter().find(|(s, _)| s.name == "Cache").unwrap().0;
+        assert!(cache.signature.is_none());
+        let store = &symbols.iter().find(|(s, _)| s.name == "Store").unwrap().1;
+        assert_eq!(
+            sig(&store[0]),
+            (
+                vec![param("&mut self", None), param("value", Some("u32"))],
+                None
+            )
+        );
+
+        let methods = extract_impl_methods(&tree, src.as_bytes());
+        assert_eq!(
+            sig(&methods[0].0),
+            (
+                vec![param("&self", None), param("key", Some("&str"))],
+                Some("Option<&Entry>".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_symbol_byte_ranges() {
+        let src = "const a = 1;\nfunction greet(name: string) {\n  return name;\n}\n";
+        let (tree, lang) = parse_ts(src);
+        let results = extract_symbols(&tree, src.as_bytes(), &lang, false);
+        let greet = &results.iter().find(|(s, _)| s.name == "greet").unwrap().0;
+        assert_eq!(
+            &src[greet.byte_start..greet.byte_end],
+            "function greet(name: string) {\n  return name;\n}"
+        );
+        assert_eq!((greet.line, greet.line_end, greet.col_end), (2, 4, 1));
+
+        let src = "struct S;\nimpl S {\n    fn get(&self) -> u32 { 1 }\n}\n";
+        let (tree, _) = parse_rs(src);
+        let methods = extract_impl_methods(&tree, src.as_bytes());
+        let get = &methods[0].0;
+        assert_eq!(
+            &src[get.byte_start..get.byte_end],
+            "fn get(&self) -> u32 { 1 }"
+        );
+    }
 }
@@ -0,0 +1,657 @@
+use crate::graph::node::{SymbolInfo, SymbolKind, SymbolVisibility};
+
+use super::ParseResult;
+use super::imports::{ImportInfo, ImportKind};
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    /// Identifier, keyword, number, or builtin (`@import`). `@"quoted"` identifiers are
+    /// unquoted.
+    Word(String),
+    /// String literal (quotes stripped). Multiline `\\` strings are dropped.
+    Str(String),
+    /// Any other single character (`{`, `}`, `;`, `=`, `(`, ...).
+    Punct(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    /// 1-based line number.
+    line: usize,
+    /// 0-based column.
+    col: usize,
+}
+
+/// Read a quoted literal starting at the opening quote `chars[*i]`; leaves `*i` past the
+/// closing quote.
+fn read_quoted(chars: &[char], i: &mut usize) -> String {
+    let quote = chars[*i];
+    let mut value = String::new();
+    *i += 1;
+    while *i < chars.len() && chars[*i] != quote && chars[*i] != '\n' {
+        if chars[*i] == '\\' && *i + 1 < chars.len() {
+            *i += 1;
+        }
+        value.push(chars[*i]);
+        *i += 1;
+    }
+    *i += 1;
+    value
+}
+
+/// Split `.zig` source into tokens, dropping whitespace, `//` comments (including `///`
+/// doc comments), and `\\` multiline string lines.
+fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+    let mut line_start = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            line_start = i;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if (c == '/' && chars.get(i + 1) == Some(&'/'))
+            || (c == '\\' && chars.get(i + 1) == Some(&'\\'))
+        {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        let col = i - line_start;
+        let tok = if c == '"' {
+            Tok::Str(read_quoted(&chars, &mut i))
+        } else if c == '\'' {
+            // Character literal: not interesting, but must not be split into tokens.
+            read_quoted(&chars, &mut i);
+            continue;
+        } else if c == '@' && chars.get(i + 1) == Some(&'"') {
+            i += 1;
+            Tok::Word(read_quoted(&chars, &mut i))
+        } else if c.is_alphanumeric() || c == '_' || c == '@' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            Tok::Word(chars[start..i].iter().collect())
+        } else {
+            i += 1;
+            Tok::Punct(c)
+        };
+        tokens.push(Token { tok, line, col });
+    }
+
+    tokens
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+/// Declaration modifiers that may precede `fn`, `const`, or `var`.
+const MODIFIERS: &[&str] = &[
+    "pub",
+    "export",
+    "extern",
+    "inline",
+    "noinline",
+    "threadlocal",
+    "comptime",
+];
+
+struct ZigParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl ZigParser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    fn peek_word(&self) -> Option<&str> {
+        match self.peek() {
+            Some(Tok::Word(w)) => Some(w.as_str()),
+            _ => None,
+        }
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.peek() == Some(&Tok::Punct(c))
+    }
+
+    fn next_word(&mut self) -> Option<String> {
+        let word = self.peek_word().map(str::to_owned);
+        if word.is_some() {
+            self.pos += 1;
+        }
+        word
+    }
+
+    /// Line of the most recently consumed token (1 when nothing was consumed yet).
+    fn last_line(&self) -> usize {
+        self.pos
+            .checked_sub(1)
+            .and_then(|p| self.tokens.get(p))
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    /// Skip a balanced `{ ... }`, `( ... )`, or `[ ... ]` group, assuming the cursor sits
+    /// on the opening delimiter.
+    fn skip_group(&mut self) {
+        let mut depth = 0usize;
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Punct('{' | '(' | '[') => depth += 1,
+                Tok::Punct('}' | ')' | ']') => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        self.pos += 1;
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Skip to the end of the current declaration or field: the next `;` or `,` outside any
+    /// group. Stops before a `}` that closes the enclosing container.
+    fn skip_statement(&mut self) {
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Punct(';' | ',') => {
+                    self.pos += 1;
+                    return;
+                }
+                Tok::Punct('{' | '(' | '[') => self.skip_group(),
+                Tok::Punct('}' | ')' | ']') => return,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn symbol(&self, name: String, kind: SymbolKind, start: usize, is_pub: bool) -> SymbolInfo {
+        let tok = &self.tokens[start];
+        SymbolInfo {
+            name,
+            kind,
+            line: tok.line,
+            col: tok.col,
+            line_end: tok.line,
+            is_exported: is_pub,
+            visibility: if is_pub {
+                SymbolVisibility::Pub
+            } else {
+                SymbolVisibility::Private
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Parse the declarations of a container (the file itself, or a `struct`/`enum`/`union`
+    /// body) up to and including its closing brace, if any.
+    ///
+    /// Top-level entries are returned with their children: the methods and nested
+    /// containers of a container declaration, flattened so nested types stay reachable by
+    /// name. Functions declared inside a container body are reported as methods.
+    fn parse_container(&mut self, in_body: bool) -> Vec<(SymbolInfo, Vec<SymbolInfo>)> {
+        let mut decls = Vec::new();
+        while let Some(tok) = self.peek().cloned() {
+            let start = self.pos;
+            match tok {
+                Tok::Punct('}') => {
+                    self.pos += 1;
+                    if in_body {
+                        return decls;
+                    }
+                }
+                Tok::Word(_) => {
+                    let mut is_pub = false;
+                    while let Some(word) = self.peek_word()
+                        && MODIFIERS.contains(&word)
+                    {
+                        is_pub |= word == "pub";
+                        self.pos += 1;
+                        // `extern "c" fn ...`
+                        if matches!(self.peek(), Some(Tok::Str(_))) {
+                            self.pos += 1;
+                        }
+                    }
+                    match self.peek_word() {
+                        Some("fn") => {
+                            let kind = if in_body {
+                                SymbolKind::Method
+                            } else {
+                                SymbolKind::Function
+                            };
+                            if let Some(sym) = self.parse_fn(kind, start, is_pub) {
+                                decls.push((sym, Vec::new()));
+                            }
+                        }
+                        Some("const" | "var") => {
+                            if let Some(decl) = self.parse_var_decl(start, is_pub) {
+                                decls.push(decl);
+                            }
+                        }
+                        Some("test") => {
+                            // `test "name" { ... }` — tests are not symbols.
+                            while self.peek().is_some() && !self.is_punct('{') {
+                                self.pos += 1;
+                            }
+                            self.skip_group();
+                        }
+                        _ if self.is_punct('{') => self.skip_group(),
+                        _ => self.skip_statement(),
+                    }
+                }
+                _ => self.skip_statement(),
+            }
+            if self.pos == start {
+                // Stray punctuation (e.g. an unbalanced `)`) — step over it.
+                self.pos += 1;
+            }
+        }
+        decls
+    }
+
+    /// Parse `fn name(...) Ret { ... }` (or an `extern` prototype ending in `;`).
+    fn parse_fn(&mut self, kind: SymbolKind, start: usize, is_pub: bool) -> Option<SymbolInfo> {
+        self.pos += 1; // `fn`
+        let name = self.next_word()?;
+        let mut sym = self.symbol(name, kind, start, is_pub);
+        // The signature may contain groups (params, anonymous struct return types); the
+        // body is the first `{` that follows the parameter list at depth 0.
+        let mut seen_params = false;
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Punct('(') if !seen_params => {
+                    seen_params = true;
+                    self.skip_group();
+                }
+                Tok::Punct('{') if seen_params => {
+                    self.skip_group();
+                    break;
+                }
+                Tok::Punct('(' | '[' | '{') => self.skip_group(),
+                Tok::Punct(';') => {
+                    self.pos += 1;
+                    break;
+                }
+                Tok::Punct('}') => break,
+                _ => self.pos += 1,
+            }
+        }
+        sym.line_end = self.last_line();
+        Some(sym)
+    }
+
+    /// Parse `const Name = <value>;` / `var name = <value>;`.
+    ///
+    /// `struct`/`union`/`opaque` values become [`SymbolKind::Struct`] and `enum` values
+    /// [`SymbolKind::Enum`], with their methods and nested containers as children.
+    /// `@import(...)` aliases are imports, not symbols. Any other `const` is a
+    /// [`SymbolKind::Const`] and any other `var` a [`SymbolKind::Variable`].
+    fn parse_var_decl(
+        &mut self,
+        start: usize,
+        is_pub: bool,
+    ) -> Option<(SymbolInfo, Vec<SymbolInfo>)> {
+        let is_const = self.next_word().as_deref() == Some("const");
+        let name = self.next_word()?;
+
+        // Optional type annotation, then `=`.
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Punct('=') => break,
+                Tok::Punct(';' | ',' | '}') => {
+                    self.skip_statement();
+                    return None;
+                }
+                Tok::Punct('(' | '[' | '{') => self.skip_group(),
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1; // `=`
+
+        while matches!(self.peek_word(), Some("extern" | "packed")) {
+            self.pos += 1;
+        }
+        let container_kind = match self.peek_word() {
+            Some("struct" | "union" | "opaque") => Some(SymbolKind::Struct),
+            Some("enum") => Some(SymbolKind::Enum),
+            _ => None,
+        };
+        if self.peek_word() == Some("@import") {
+            self.skip_statement();
+            return None;
+        }
+
+        let Some(kind) = container_kind else {
+            let kind = if is_const {
+                SymbolKind::Const
+            } else {
+                SymbolKind::Variable
+            };
+            let mut sym = self.symbol(name, kind, start, is_pub);
+            self.skip_statement();
+            sym.line_end = self.last_line();
+            return Some((sym, Vec::new()));
+        };
+
+        let mut sym = self.symbol(name, kind, start, is_pub);
+        self.pos += 1; // container keyword
+        if self.is_punct('(') {
+            // `enum(u8)`, `union(enum)`
+            self.skip_group();
+        }
+        let mut children = Vec::new();
+        if self.is_punct('{') {
+            self.pos += 1;
+            for (member, nested) in self.parse_container(true) {
+                children.push(member);
+                children.extend(nested);
+            }
+        }
+        sym.line_end = self.last_line();
+        if self.is_punct(';') {
+            self.pos += 1;
+        }
+        Some((sym, children))
+    }
+
+    /// Every `@import("...")` in the file, wherever it appears.
+    fn imports(&self) -> Vec<ImportInfo> {
+        self.tokens
+            .windows(3)
+            .filter_map(|w| match (&w[0].tok, &w[1].tok, &w[2].tok) {
+                (Tok::Word(b), Tok::Punct('('), Tok::Str(path)) if b == "@import" => {
+                    Some(ImportInfo {
+                        kind: ImportKind::ZigImport,
+                        module_path: path.clone(),
+                        specifiers: Vec::new(),
+                        line: w[0].line,
+                        col: 0,
+                        is_type_only: false,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Index of the token closing the group opened at `tokens[open]`.
+fn group_end(tokens: &[Token], open: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, t) in tokens.iter().enumerate().skip(open) {
+        match t.tok {
+            Tok::Punct('{' | '(' | '[') => depth += 1,
+            Tok::Punct('}' | ')' | ']') => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// The first string literal after a `root_source_file` field in `tokens`: the path in
+/// both `b.path("src/x.zig")` and the older `.{ .path = "src/x.zig" }` forms.
+fn root_source_file(tokens: &[Token]) -> Option<String> {
+    let field = tokens
+        .iter()
+        .position(|t| t.tok == Tok::Word("root_source_file".into()))?;
+    tokens[field..].iter().find_map(|t| match &t.tok {
+        Tok::Str(path) => Some(path.clone()),
+        _ => None,
+    })
+}
+
+/// Named modules declared by a `build.zig` script, as `(module name, root source file)`
+/// pairs with paths relative to the script's directory.
+///
+/// Recognizes `b.addModule("name", .{ .root_source_file = ... })`,
+/// `addAnonymousImport("name", .{ ... })`, and `addImport("name", module)` where `module`
+/// was bound by `const module = b.createModule(.{ .root_source_file = ... })`.
+pub fn build_modules(source: &[u8]) -> Vec<(String, String)> {
+    let text = String::from_utf8_lossy(source);
+    let tokens = tokenize(&text);
+    let word = |i: usize| match tokens.get(i).map(|t| &t.tok) {
+        Some(Tok::Word(w)) => Some(w.as_str()),
+        _ => None,
+    };
+
+    // `const name = b.createModule(...)` bindings.
+    let mut bindings: Vec<(String, String)> = Vec::new();
+    for i in 0..tokens.len() {
+        if matches!(word(i), Some("const" | "var"))
+            && let Some(name) = word(i + 1)
+            && tokens.get(i + 2).map(|t| &t.tok) == Some(&Tok::Punct('='))
+        {
+            let end = tokens[i..]
+                .iter()
+                .position(|t| t.tok == Tok::Punct(';'))
+                .map_or(tokens.len(), |p| i + p);
+            let value = &tokens[i + 3..end];
+            if value
+                .iter()
+                .any(|t| t.tok == Tok::Word("createModule".into()))
+                && let Some(path) = root_source_file(value)
+            {
+                bindings.push((name.to_owned(), path));
+            }
+        }
+    }
+
+    let mut modules = Vec::new();
+    for i in 0..tokens.len() {
+        if !matches!(
+            word(i),
+            Some("addModule" | "addImport" | "addAnonymousImport")
+        ) || tokens.get(i + 1).map(|t| &t.tok) != Some(&Tok::Punct('('))
+        {
+            continue;
+        }
+        let Some(Tok::Str(name)) = tokens.get(i + 2).map(|t| &t.tok) else {
+            continue;
+        };
+        let args = &tokens[i + 2..group_end(&tokens, i + 1).min(tokens.len())];
+        let path = root_source_file(args).or_else(|| {
+            // `addImport("name", module)`: the second argument names a binding.
+            let bound = match args.get(2).map(|t| &t.tok) {
+                Some(Tok::Word(w)) => w,
+                _ => return None,
+            };
+            bindings
+                .iter()
+                .find(|(binding, _)| binding == bound)
+                .map(|(_, path)| path.clone())
+        });
+        if let Some(path) = path {
+            modules.push((name.clone(), path));
+        }
+    }
+    modules
+}
+
+/// Parse a `.zig` file into functions, containers, and `@import` statements.
+///
+/// - `fn` → [`SymbolKind::Function`] (or [`SymbolKind::Method`] inside a container)
+/// - `const X = struct/union/opaque { ... }` → [`SymbolKind::Struct`]; `enum` →
+///   [`SymbolKind::Enum`]; methods and nested containers become children
+/// - other `const` / `var` declarations → [`SymbolKind::Const`] / [`SymbolKind::Variable`]
+/// - `@import("...")` → [`ImportKind::ZigImport`]
+///
+/// `pub` declarations are exported. Like the protobuf and SQL parsers, this is a
+/// hand-written tokenizer rather than a tree-sitter grammar; it only needs declaration
+/// structure. Malformed input never fails — unknown statements are skipped.
+pub fn parse_zig(source: &[u8]) -> ParseResult {
+    let text = String::from_utf8_lossy(source);
+    let mut parser = ZigParser {
+        tokens: tokenize(&text),
+        pos: 0,
+    };
+    let imports = parser.imports();
+    let symbols = parser.parse_container(false);
+
+    ParseResult {
+        symbols,
+        imports,
+        exports: Vec::new(),
+        relationships: Vec::new(),
+        rust_uses: Vec::new(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER_ZIG: &str = r#"const std = @import("std");
+const util = @import("util.zig");
+
+/// A TCP server.
+pub const Server = struct {
+    port: u16,
+    name: []const u8 = "srv",
+
+    pub const Options = struct { backlog: u32 = 128 };
+
+    pub fn init(port: u16) Server {
+        return .{ .port = port };
+    }
+
+    fn handle(self: *Server) !void {
+        const msg = "}";
+        _ = msg;
+        try util.log(self.name);
+    }
+};
+
+pub const Mode = enum(u8) { fast, safe };
+
+const max_clients: usize = 64;
+var counter = 0;
+
+extern "c" fn write(fd: c_int, buf: [*]const u8, n: usize) isize;
+
+pub fn main() !void {
+    var server = Server.init(8080);
+    _ = &server;
+}
+
+test "init" {
+    const s = Server.init(1);
+    _ = s;
+}
+"#;
+
+    #[test]
+    fn test_zig_symbols() {
+        let result = parse_zig(SERVER_ZIG.as_bytes());
+        let names: Vec<(&str, &SymbolKind, bool)> = result
+            .symbols
+            .iter()
+            .map(|(s, _)| (s.name.as_str(), &s.kind, s.is_exported))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Server", &SymbolKind::Struct, true),
+                ("Mode", &SymbolKind::Enum, true),
+                ("max_clients", &SymbolKind::Const, false),
+                ("counter", &SymbolKind::Variable, false),
+                ("write", &SymbolKind::Function, false),
+                ("main", &SymbolKind::Function, true),
+            ]
+        );
+
+        let (server, children) = &result.symbols[0];
+        assert_eq!((server.line, server.line_end), (5, 20));
+        let child_names: Vec<(&str, &SymbolKind)> = children
+            .iter()
+            .map(|c| (c.name.as_str(), &c.kind))
+            .collect();
+        assert_eq!(
+            child_names,
+            vec![
+                ("Options", &SymbolKind::Struct),
+                ("init", &SymbolKind::Method),
+                ("handle", &SymbolKind::Method),
+            ]
+        );
+        assert_eq!((children[2].line, children[2].line_end), (15, 19));
+
+        let (main, _) = &result.symbols[5];
+        assert_eq!((main.line, main.line_end), (29, 32));
+    }
+
+    #[test]
+    fn test_zig_imports() {
+        let result = parse_zig(SERVER_ZIG.as_bytes());
+        let paths: Vec<(&str, usize)> = result
+            .imports
+            .iter()
+            .map(|i| (i.module_path.as_str(), i.line))
+            .collect();
+        assert_eq!(paths, vec![("std", 1), ("util.zig", 2)]);
+        assert!(
+            result
+                .imports
+                .iter()
+                .all(|i| i.kind == ImportKind::ZigImport)
+        );
+    }
+
+    #[test]
+    fn test_zig_malformed_input_does_not_panic() {
+        let result = parse_zig(b"pub fn ( { } } const = struct { fn ; const Trailing = enum");
+        assert!(result.symbols.iter().all(|(s, _)| !s.name.is_empty()));
+    }
+
+    #[test]
+    fn test_build_modules() {
+        let build = br#"
+const std = @import("std");
+pub fn build(b: *std.Build) void {
+    const core = b.createModule(.{ .root_source_file = b.path("src/core/root.zig") });
+    _ = b.addModule("net", .{ .root_source_file = b.path("src/net.zig"), .target = target });
+    const exe = b.addExecutable(.{ .name = "app", .root_source_file = b.path("src/main.zig") });
+    exe.root_module.addImport("core", core);
+    exe.root_module.addAnonymousImport("assets", .{ .root_source_file = .{ .path = "assets/all.zig" } });
+}
+"#;
+        assert_eq!(
+            build_modules(build),
+            vec![
+                ("net".to_string(), "src/net.zig".to_string()),
+                ("core".to_string(), "src/core/root.zig".to_string()),
+                ("assets".to_string(), "assets/all.zig".to_string()),
+            ]
+        );
+    }
+}
@@ -14,6 +14,32 @@ const PROJECT_MARKERS: &[&str] = &[
     "pyproject.toml",
 ];
 
+/// Entries whose presence marks a directory as a plausible root to index.
+///
+/// Broader than [`PROJECT_MARKERS`]: a git checkout or a bare `src/` layout is enough to
+/// index, even though neither is used to auto-detect the root.
+const ROOT_EVIDENCE: &[&str] = &[
+    ".code-graph",
+    "code-graph.toml",
+    "Cargo.toml",
+    "package.json",
+    "go.mod",
+    "pyproject.toml",
+    "setup.py",
+    "requirements.txt",
+    "build.zig",
+    "deno.json",
+    "tsconfig.json",
+    ".git",
+    "src",
+];
+
+/// Directory names never suggested as the intended root.
+const SKIPPED_SUGGESTION_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// Maximum number of subdirectories suggested by [`check_index_root`].
+const MAX_SUGGESTIONS: usize = 5;
+
 /// Walk parent directories from `cwd` looking for a project root.
 ///
 /// Returns the first directory that contains any of the [`PROJECT_MARKERS`].
@@ -55,6 +81,75 @@ pub fn resolve_project_root(path: Option<PathBuf>) -> PathBuf {
     }
 }
 
+/// Returns `true` if `dir` contains any of the [`ROOT_EVIDENCE`] entries.
+fn looks_like_project(dir: &Path) -> bool {
+    ROOT_EVIDENCE.iter().any(|entry| dir.join(entry).exists())
+}
+
+/// Subdirectories of `root` (up to two levels down) that look like projects, sorted.
+/// Matching directories are not searched further.
+fn suggest_project_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut level = vec![root.to_path_buf()];
+    for _ in 0..2 {
+        let mut next = Vec::new();
+        for dir in &level {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if !path.is_dir()
+                    || name.starts_with('.')
+                    || SKIPPED_SUGGESTION_DIRS.contains(&name.as_ref())
+                {
+                    continue;
+                }
+                if looks_like_project(&path) {
+                    found.push(path);
+                } else {
+                    next.push(path);
+                }
+            }
+        }
+        level = next;
+    }
+    found.sort();
+    found.truncate(MAX_SUGGESTIONS);
+    found
+}
+
+/// Refuse to index a directory that does not look like a project.
+///
+/// Indexing the wrong directory (an empty folder, `$HOME`, a parent of several checkouts)
+/// otherwise yields an empty or tiny graph and a confusing run of "symbol not found"
+/// results. The error names subdirectories that do look like projects; callers skip this
+/// check when the user passes `--force`.
+pub fn check_index_root(root: &Path) -> anyhow::Result<()> {
+    if !root.is_dir() {
+        anyhow::bail!("'{}' is not a directory", root.display());
+    }
+    if looks_like_project(root) {
+        return Ok(());
+    }
+
+    let mut message = format!(
+        "'{}' does not look like a project root (no package.json, Cargo.toml, go.mod, pyproject.toml, .git, or src/)",
+        root.display()
+    );
+    let suggestions = suggest_project_dirs(root);
+    if !suggestions.is_empty() {
+        message.push_str("\ndid you mean one of:");
+        for dir in &suggestions {
+            message.push_str(&format!("\n  {}", dir.display()));
+        }
+    }
+    message.push_str("\npass --force to index it anyway");
+    anyhow::bail!(message)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,6 +198,34 @@ mod tests {
         assert!(result.exists());
     }
 
+    #[test]
+    fn test_check_index_root_accepts_project_markers() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        assert!(check_index_root(tmp.path()).is_ok());
+    }
+
+    #[test]
+    fn test_check_index_root_refuses_and_suggests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let web = tmp.path().join("web");
+        let api = tmp.path().join("services").join("api");
+        let deps = tmp.path().join("node_modules").join("left-pad");
+        for dir in [&web, &api, &deps] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(web.join("package.json"), "{}").unwrap();
+        std::fs::write(api.join("go.mod"), "module api").unwrap();
+        std::fs::write(deps.join("package.json"), "{}").unwrap();
+
+        let err = check_index_root(tmp.path()).unwrap_err().to_string();
+        assert!(err.contains("does not look like a project root"), "{err}");
+        assert!(err.contains("pass --force"), "{err}");
+        assert_eq!(suggest_project_dirs(tmp.path()), vec![api, web]);
+
+        assert!(check_index_root(&tmp.path().join("missing")).is_err());
+    }
+
     #[test]
     fn test_resolve_project_root_with_none_falls_back() {
         // When None is passed, it tries detect_project_root() then cwd.
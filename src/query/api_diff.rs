@@ -0,0 +1,532 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use petgraph::Direction;
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{GraphNode, SymbolInfo, SymbolVisibility};
+use crate::query::find::kind_to_str;
+use crate::query::util::dedup_impl_method_twins;
+
+/// Longest declaration header kept as a signature, in characters.
+const MAX_SIGNATURE_LEN: usize = 200;
+/// Most source lines read when looking for the end of a declaration header.
+const MAX_SIGNATURE_LINES: usize = 8;
+
+/// One exported symbol of a package's public API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiSymbol {
+    /// Symbol name, qualified by its parent for members (`Server.listen`).
+    pub name: String,
+    /// Kind string via `kind_to_str` (e.g. "function", "struct").
+    pub kind: String,
+    /// Declaration header with whitespace collapsed, e.g. `pub fn parse(src: &str) -> Ast`.
+    pub signature: String,
+    /// File path relative to the project root.
+    pub file: String,
+}
+
+/// An exported symbol whose declaration header differs between the two revisions.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiChange {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub from_signature: String,
+    pub to_signature: String,
+}
+
+/// The release a set of API changes calls for under semantic versioning.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SemverLevel {
+    /// No public API change.
+    #[default]
+    Patch,
+    /// Additive: exported symbols were only added.
+    Minor,
+    /// Breaking: an exported symbol was removed or its declaration changed.
+    Major,
+}
+
+impl SemverLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SemverLevel::Patch => "patch",
+            SemverLevel::Minor => "minor",
+            SemverLevel::Major => "major",
+        }
+    }
+}
+
+/// Public API changes of a single package.
+#[derive(Debug, Default, Serialize)]
+pub struct PackageApiDiff {
+    pub package: String,
+    /// Release this package's changes call for.
+    pub level: SemverLevel,
+    pub added: Vec<ApiSymbol>,
+    pub removed: Vec<ApiSymbol>,
+    pub changed: Vec<ApiChange>,
+}
+
+/// Public API changes between two git revisions, grouped by package.
+#[derive(Debug, Serialize)]
+pub struct ApiDiff {
+    pub from: String,
+    pub to: String,
+    /// Highest level across packages.
+    pub level: SemverLevel,
+    /// Only packages with at least one change, sorted by name.
+    pub packages: Vec<PackageApiDiff>,
+}
+
+/// Package name -> (symbol key -> symbol). Keys are `file::qualified name::kind`, with a
+/// `#n` suffix for repeated names in one file (e.g. several inherent `new` methods).
+pub type PublicApi = BTreeMap<String, BTreeMap<String, ApiSymbol>>;
+
+/// Returns true if the symbol belongs to the public API: `pub` Rust items and exported
+/// symbols in every other language. `pub(crate)` items are not public API.
+pub(crate) fn is_public(sym: &SymbolInfo, language: &str) -> bool {
+    if language == "rust" {
+        sym.visibility == SymbolVisibility::Pub
+    } else {
+        sym.is_exported
+    }
+}
+
+/// Name of the package owning `file`: the `name` declared by the nearest `Cargo.toml`,
+/// `package.json`, `pyproject.toml`, or `go.mod` between the file and `root`. Manifests
+/// without a name (virtual workspaces) are skipped. Falls back to the root directory's name.
+fn package_name(file: &Path, root: &Path, cache: &mut HashMap<PathBuf, Option<String>>) -> String {
+    for dir in file.ancestors().skip(1) {
+        if !dir.starts_with(root) {
+            break;
+        }
+        let name = cache
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| manifest_name(dir))
+            .clone();
+        if let Some(name) = name {
+            return name;
+        }
+    }
+    root.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(".")
+        .to_string()
+}
+
+/// The package name declared by a manifest in `dir`, if any.
+fn manifest_name(dir: &Path) -> Option<String> {
+    let read_toml = |file: &str| -> Option<toml::Value> {
+        toml::from_str(&std::fs::read_to_string(dir.join(file)).ok()?).ok()
+    };
+    if let Some(cargo) = read_toml("Cargo.toml")
+        && let Some(name) = cargo
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+    {
+        return Some(name.to_string());
+    }
+    if let Ok(text) = std::fs::read_to_string(dir.join("package.json"))
+        && let Ok(json) = serde_json::from_str::<serde_json::Value>(&text)
+        && let Some(name) = json.get("name").and_then(|n| n.as_str())
+    {
+        return Some(name.to_string());
+    }
+    if let Some(pyproject) = read_toml("pyproject.toml") {
+        let name = pyproject
+            .get("project")
+            .and_then(|p| p.get("name"))
+            .or_else(|| {
+                pyproject
+                    .get("tool")
+                    .and_then(|t| t.get("poetry"))
+                    .and_then(|p| p.get("name"))
+            })
+            .and_then(|n| n.as_str());
+        if let Some(name) = name {
+            return Some(name.to_string());
+        }
+    }
+    if let Ok(text) = std::fs::read_to_string(dir.join("go.mod"))
+        && let Some(module) = text.lines().find_map(|l| l.trim().strip_prefix("module "))
+    {
+        return Some(module.trim().to_string());
+    }
+    None
+}
+
+/// The declaration header of the symbol starting at 1-based `line`: source text up to the
+/// first `{`, `;`, or `=>` outside brackets, with whitespace collapsed.
+pub(crate) fn declaration_header(lines: &[&str], line: usize, line_end: usize) -> String {
+    let start = line.saturating_sub(1);
+    let end = line_end
+        .max(line)
+        .min(start + MAX_SIGNATURE_LINES)
+        .min(lines.len());
+    let mut header = String::new();
+    let mut depth = 0i32;
+    'outer: for text in lines.get(start..end).unwrap_or_default() {
+        let mut chars = text.trim().chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '(' | '[' | '<' => depth += 1,
+                ')' | ']' => depth -= 1,
+                '>' if depth > 0 && !header.ends_with('-') => depth -= 1,
+                '{' | ';' if depth <= 0 => break 'outer,
+                '=' if depth <= 0 && chars.peek() == Some(&'>') => break 'outer,
+                _ => {}
+            }
+            header.push(c);
+        }
+        header.push(' ');
+    }
+    let collapsed = header.split_whitespace().collect::<Vec<_>>().join(" ");
+    let trimmed = collapsed.trim_end_matches([':', '=']).trim_end();
+    trimmed.chars().take(MAX_SIGNATURE_LEN).collect()
+}
+
+/// Collect the public API of every package in `graph`, reading declaration headers from the
+/// source files under `root`.
+pub(crate) fn public_api(graph: &CodeGraph, root: &Path) -> PublicApi {
+    let mut api: PublicApi = BTreeMap::new();
+    let mut packages: HashMap<PathBuf, Option<String>> = HashMap::new();
+
+    let mut files: Vec<(&PathBuf, _)> = graph.file_index.iter().collect();
+    files.sort();
+    for (path, &file_idx) in files {
+        let GraphNode::File(file_info) = &graph.graph[file_idx] else {
+            continue;
+        };
+        let mut symbols: Vec<(String, &SymbolInfo)> = Vec::new();
+        for edge in graph.graph.edges(file_idx) {
+            if !matches!(edge.weight(), EdgeKind::Contains) {
+                continue;
+            }
+            let GraphNode::Symbol(parent) = &graph.graph[edge.target()] else {
+                continue;
+            };
+            // Members are listed under their parent, qualified by its name.
+            let is_member = graph
+                .graph
+                .edges(edge.target())
+                .any(|e| matches!(e.weight(), EdgeKind::ChildOf));
+            if is_member || !is_public(parent, &file_info.language) {
+                continue;
+            }
+            symbols.push((parent.name.clone(), parent));
+            for child in graph
+                .graph
+                .edges_directed(edge.target(), Direction::Incoming)
+                .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+            {
+                if let GraphNode::Symbol(member) = &graph.graph[child.source()]
+                    && (is_public(member, &file_info.language) || file_info.language != "rust")
+                    && !member.name.starts_with('#')
+                {
+                    symbols.push((format!("{}.{}", parent.name, member.name), member));
+                }
+            }
+        }
+        if symbols.is_empty() {
+            continue;
+        }
+        symbols.sort_by_key(|(_, s)| (s.line, s.col));
+        dedup_impl_method_twins(&mut symbols, |(_, s)| ((s.line, s.col), s.name.as_str()));
+
+        let source = std::fs::read_to_string(path).unwrap_or_default();
+        let lines: Vec<&str> = source.lines().collect();
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string();
+        let package = package_name(path, root, &mut packages);
+        let entries = api.entry(package).or_default();
+        let mut seen: HashMap<(String, &str), usize> = HashMap::new();
+
+        for (name, sym) in symbols {
+            let kind = kind_to_str(&sym.kind);
+            let count = seen.entry((name.clone(), kind)).or_insert(0);
+            *count += 1;
+            let mut key = format!("{}::{}::{}", rel, name, kind);
+            if *count > 1 {
+                key.push_str(&format!("#{}", count));
+            }
+            entries.insert(
+                key,
+                ApiSymbol {
+                    name,
+                    kind: kind.to_string(),
+                    signature: declaration_header(&lines, sym.line, sym.line_end),
+                    file: rel.clone(),
+                },
+            );
+        }
+    }
+    api
+}
+
+/// Compare two public API maps.
+fn diff_api(from_rev: &str, to_rev: &str, from: PublicApi, mut to: PublicApi) -> ApiDiff {
+    let mut packages: BTreeMap<String, PackageApiDiff> = BTreeMap::new();
+
+    for (package, mut from_symbols) in from {
+        let to_symbols = to.remove(&package).unwrap_or_default();
+        let entry = packages.entry(package.clone()).or_default();
+        for (key, after) in to_symbols {
+            match from_symbols.remove(&key) {
+                None => entry.added.push(after),
+                Some(before) if before.signature != after.signature => {
+                    entry.changed.push(ApiChange {
+                        name: after.name,
+                        kind: after.kind,
+                        file: after.file,
+                        from_signature: before.signature,
+                        to_signature: after.signature,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        entry.removed.extend(from_symbols.into_values());
+    }
+    for (package, to_symbols) in to {
+        packages
+            .entry(package)
+            .or_default()
+            .added
+            .extend(to_symbols.into_values());
+    }
+
+    let packages: Vec<PackageApiDiff> = packages
+        .into_iter()
+        .filter(|(_, d)| !d.added.is_empty() || !d.removed.is_empty() || !d.changed.is_empty())
+        .map(|(package, mut d)| {
+            d.package = package;
+            d.level = if !d.removed.is_empty() || !d.changed.is_empty() {
+                SemverLevel::Major
+            } else {
+                SemverLevel::Minor
+            };
+            d
+        })
+        .collect();
+    ApiDiff {
+        from: from_rev.to_string(),
+        to: to_rev.to_string(),
+        level: packages.iter().map(|d| d.level).max().unwrap_or_default(),
+        packages,
+    }
+}
+
+/// Report added, removed, and changed exported symbols between two git revisions
+/// (`code-graph api-diff <from> <to>`).
+///
+/// Each revision is indexed in a temporary worktree (see
+/// [`crate::query::diff::with_revision_graph`]). Symbols are matched by file, qualified
+/// name, and kind; a symbol counts as changed when its declaration header differs. A
+/// symbol moved to another file is reported as removed and added.
+pub fn compute_api_diff(
+    root: &Path,
+    from: &str,
+    to: &str,
+    verbose: bool,
+) -> anyhow::Result<ApiDiff> {
+    let from_api = crate::query::diff::with_revision_graph(root, from, verbose, public_api)?;
+    let to_api = crate::query::diff::with_revision_graph(root, to, verbose, public_api)?;
+    Ok(diff_api(from, to, from_api, to_api))
+}
+
+/// Report the public API changes between two stored snapshots (names, or git revisions
+/// with a per-commit snapshot), as recorded when each snapshot was taken.
+pub fn compute_snapshot_api_diff(root: &Path, from: &str, to: &str) -> anyhow::Result<ApiDiff> {
+    let load = |name: &str| -> anyhow::Result<PublicApi> {
+        let resolved = crate::query::diff::resolve_snapshot_name(root, name);
+        let snapshot = crate::query::diff::load_snapshot(root, &resolved)?;
+        snapshot.api.ok_or_else(|| {
+            anyhow::anyhow!(
+                "snapshot '{}' has no public API recorded; recreate it with `snapshot create`",
+                resolved
+            )
+        })
+    };
+    Ok(diff_api(from, to, load(from)?, load(to)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::SymbolKind;
+
+    #[test]
+    fn test_declaration_header() {
+        let src = "pub fn parse(\n    src: &str,\n) -> Result<Ast, Error> {\n    todo!()\n}\n";
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!(
+            declaration_header(&lines, 1, 5),
+            "pub fn parse( src: &str, ) -> Result<Ast, Error>"
+        );
+        let ts = ["export const add = (a: number, b: number): number => a + b;"];
+        assert_eq!(
+            declaration_header(&ts, 1, 1),
+            "export const add = (a: number, b: number): number"
+        );
+        assert_eq!(
+            declaration_header(&["pub struct Config;"], 1, 1),
+            "pub struct Config"
+        );
+        assert_eq!(declaration_header(&[], 3, 3), "");
+    }
+
+    #[test]
+    fn test_public_api_skips_private_and_crate_items() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        let lib = root.join("src/lib.rs");
+        std::fs::write(&lib, "pub fn a() {}\npub(crate) fn b() {}\nfn c() {}\n").unwrap();
+
+        let mut graph = CodeGraph::new();
+        let file = graph.add_file(lib, "rust");
+        for (line, name, visibility) in [
+            (1, "a", SymbolVisibility::Pub),
+            (2, "b", SymbolVisibility::PubCrate),
+            (3, "c", SymbolVisibility::Private),
+        ] {
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Function,
+                    line,
+                    line_end: line,
+                    visibility,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let api = public_api(&graph, root);
+        let demo = &api["demo"];
+        assert_eq!(demo.len(), 1);
+        let a = demo.values().next().unwrap();
+        assert_eq!((a.name.as_str(), a.signature.as_str()), ("a", "pub fn a()"));
+    }
+
+    #[test]
+    fn test_snapshot_api_diff_classifies_release() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        let lib = root.join("src/lib.rs");
+        let graph_with = |source: &str, names: &[&str]| {
+            std::fs::write(&lib, source).unwrap();
+            let mut graph = CodeGraph::new();
+            let file = graph.add_file(lib.clone(), "rust");
+            for (i, name) in names.iter().enumerate() {
+                graph.add_symbol(
+                    file,
+                    SymbolInfo {
+                        name: name.to_string(),
+                        kind: SymbolKind::Function,
+                        line: i + 1,
+                        line_end: i + 1,
+                        visibility: SymbolVisibility::Pub,
+                        ..Default::default()
+                    },
+                );
+            }
+            graph
+        };
+        let v1 = graph_with("pub fn a() {}\n", &["a"]);
+        crate::query::diff::create_snapshot(&v1, root, "v1").unwrap();
+        let v2 = graph_with("pub fn a() {}\npub fn b() {}\n", &["a", "b"]);
+        crate::query::diff::create_snapshot(&v2, root, "v2").unwrap();
+        let v3 = graph_with("pub fn a(n: u8) {}\npub fn b() {}\n", &["a", "b"]);
+        crate::query::diff::create_snapshot(&v3, root, "v3").unwrap();
+
+        let diff = compute_snapshot_api_diff(root, "v1", "v2").unwrap();
+        assert_eq!(diff.level, SemverLevel::Minor);
+        assert_eq!(diff.packages[0].added[0].name, "b");
+        let diff = compute_snapshot_api_diff(root, "v2", "v3").unwrap();
+        assert_eq!(diff.level, SemverLevel::Major);
+        assert_eq!(diff.packages[0].changed[0].to_signature, "pub fn a(n: u8)");
+        assert_eq!(
+            compute_snapshot_api_diff(root, "v3", "v3").unwrap().level,
+            SemverLevel::Patch
+        );
+
+        // Snapshots written before API capture carry no `api` field.
+        let path = crate::query::diff::snapshot_path(root, "v1");
+        let mut json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        json.as_object_mut().unwrap().remove("api");
+        std::fs::write(&path, json.to_string()).unwrap();
+        let err = compute_snapshot_api_diff(root, "v1", "v2").unwrap_err();
+        assert!(err.to_string().contains("no public API recorded"), "{err}");
+    }
+
+    #[test]
+    fn test_diff_api_groups_by_package() {
+        let sym = |name: &str, signature: &str| ApiSymbol {
+            name: name.into(),
+            kind: "function".into(),
+            signature: signature.into(),
+            file: "src/lib.rs".into(),
+        };
+        let api = |entries: &[(&str, &str, &str)]| -> PublicApi {
+            let mut api = PublicApi::new();
+            for (package, name, signature) in entries {
+                api.entry(package.to_string()).or_default().insert(
+                    format!("src/lib.rs::{}::function", name),
+                    sym(name, signature),
+                );
+            }
+            api
+        };
+        let from = api(&[
+            ("core", "keep", "pub fn keep()"),
+            ("core", "gone", "pub fn gone()"),
+            ("core", "grow", "pub fn grow()"),
+            ("cli", "run", "pub fn run()"),
+        ]);
+        let to = api(&[
+            ("core", "keep", "pub fn keep()"),
+            ("core", "grow", "pub fn grow(n: usize)"),
+            ("core", "fresh", "pub fn fresh()"),
+            ("cli", "run", "pub fn run()"),
+        ]);
+
+        let diff = diff_api("v1", "v2", from, to);
+        assert_eq!(diff.packages.len(), 1, "unchanged packages are omitted");
+        let core = &diff.packages[0];
+        assert_eq!(core.package, "core");
+        assert_eq!(core.added[0].name, "fresh");
+        assert_eq!(core.removed[0].name, "gone");
+        assert_eq!(core.changed[0].to_signature, "pub fn grow(n: usize)");
+        assert_eq!(core.level, SemverLevel::Major);
+        assert_eq!(diff.level, SemverLevel::Major);
+
+        let additive = diff_api(
+            "v2",
+            "v3",
+            api(&[("cli", "run", "pub fn run()")]),
+            api(&[
+                ("cli", "run", "pub fn run()"),
+                ("cli", "stop", "pub fn stop()"),
+            ]),
+        );
+        assert_eq!(additive.level, SemverLevel::Minor);
+        let unchanged = diff_api("v3", "v4", api(&[]), api(&[]));
+        assert_eq!(unchanged.level, SemverLevel::Patch);
+    }
+}
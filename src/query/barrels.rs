@@ -0,0 +1,223 @@
+//! Barrel file audit (`code-graph barrels`).
+//!
+//! A barrel is a file with `export * from` re-exports (`BarrelReExportAll` edges); a pure
+//! barrel declares nothing of its own. For each barrel the audit counts the exported
+//! symbols that flow through it (the exports of every file its `export *` chains reach)
+//! and finds its longest re-export chain: deep chains make every importer of the barrel
+//! load, and depend on, files it never uses.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+
+/// One barrel file.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Barrel {
+    /// Project-relative path.
+    pub file: String,
+    /// Declares no symbols of its own: it only re-exports.
+    pub pure: bool,
+    /// Exported symbols reachable through its `export *` chains.
+    pub symbols: usize,
+    /// Files importing the barrel.
+    pub importers: usize,
+    /// Longest `export *` chain from the barrel, as project-relative paths starting with
+    /// the barrel itself.
+    pub chain: Vec<String>,
+}
+
+/// Result of [`audit_barrels`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BarrelsReport {
+    /// Barrels by longest chain, then symbols flowing through, then path.
+    pub barrels: Vec<Barrel>,
+}
+
+fn barrel_sources(graph: &CodeGraph, file_idx: NodeIndex) -> Vec<NodeIndex> {
+    let mut sources: Vec<NodeIndex> = graph
+        .graph
+        .edges_directed(file_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::BarrelReExportAll))
+        .map(|e| e.target())
+        .collect();
+    sources.sort();
+    sources.dedup();
+    sources
+}
+
+/// Top-level symbols declared in `file_idx`: (all, exported).
+fn declared_symbols(graph: &CodeGraph, file_idx: NodeIndex) -> (usize, usize) {
+    graph
+        .graph
+        .edges_directed(file_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .filter_map(|e| match &graph.graph[e.target()] {
+            GraphNode::Symbol(s) => Some(s.is_exported),
+            _ => None,
+        })
+        .fold((0, 0), |(all, exported), is_exported| {
+            (all + 1, exported + usize::from(is_exported))
+        })
+}
+
+/// Longest `export *` chain from `file_idx` that does not revisit a file on `path`.
+fn longest_chain(
+    graph: &CodeGraph,
+    file_idx: NodeIndex,
+    path: &mut Vec<NodeIndex>,
+) -> Vec<NodeIndex> {
+    path.push(file_idx);
+    let mut best: Vec<NodeIndex> = Vec::new();
+    for source in barrel_sources(graph, file_idx) {
+        if path.contains(&source) {
+            continue;
+        }
+        let chain = longest_chain(graph, source, path);
+        if chain.len() > best.len() {
+            best = chain;
+        }
+    }
+    path.pop();
+    best.insert(0, file_idx);
+    best
+}
+
+/// Audit every barrel file in `graph`.
+pub fn audit_barrels(graph: &CodeGraph, root: &Path) -> BarrelsReport {
+    let rel = |idx: NodeIndex| -> String {
+        match &graph.graph[idx] {
+            GraphNode::File(fi) => fi
+                .path
+                .strip_prefix(root)
+                .unwrap_or(&fi.path)
+                .to_string_lossy()
+                .to_string(),
+            _ => String::new(),
+        }
+    };
+
+    let mut barrels: Vec<Barrel> = Vec::new();
+    for &file_idx in graph.file_index.values() {
+        if barrel_sources(graph, file_idx).is_empty() {
+            continue;
+        }
+
+        // Exports of every file the `export *` chains reach, each file counted once.
+        let mut reached: HashSet<NodeIndex> = HashSet::from([file_idx]);
+        let mut stack = barrel_sources(graph, file_idx);
+        let mut symbols = 0;
+        while let Some(source) = stack.pop() {
+            if !reached.insert(source) {
+                continue;
+            }
+            symbols += declared_symbols(graph, source).1;
+            stack.extend(barrel_sources(graph, source));
+        }
+
+        let importers = graph
+            .graph
+            .edges_directed(file_idx, Direction::Incoming)
+            .filter(|e| {
+                matches!(
+                    e.weight(),
+                    EdgeKind::ResolvedImport { .. } | EdgeKind::BarrelReExportAll
+                ) && e.source() != file_idx
+            })
+            .map(|e| e.source())
+            .collect::<HashSet<_>>()
+            .len();
+
+        barrels.push(Barrel {
+            file: rel(file_idx),
+            pure: declared_symbols(graph, file_idx).0 == 0,
+            symbols,
+            importers,
+            chain: longest_chain(graph, file_idx, &mut Vec::new())
+                .into_iter()
+                .map(rel)
+                .collect(),
+        });
+    }
+    barrels.sort_by(|a, b| {
+        b.chain
+            .len()
+            .cmp(&a.chain.len())
+            .then(b.symbols.cmp(&a.symbols))
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    BarrelsReport { barrels }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use std::path::PathBuf;
+
+    fn exported(graph: &mut CodeGraph, file: NodeIndex, name: &str) {
+        graph.add_symbol(
+            file,
+            SymbolInfo {
+                name: name.into(),
+                kind: SymbolKind::Function,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_barrel_symbols_and_longest_chain() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let index = graph.add_file(root.join("src/index.ts"), "typescript");
+        let ui = graph.add_file(root.join("src/ui/index.ts"), "typescript");
+        let button = graph.add_file(root.join("src/ui/button.ts"), "typescript");
+        let utils = graph.add_file(root.join("src/utils.ts"), "typescript");
+        let app = graph.add_file(root.join("src/app.ts"), "typescript");
+        exported(&mut graph, button, "Button");
+        exported(&mut graph, button, "IconButton");
+        exported(&mut graph, utils, "clamp");
+        exported(&mut graph, ui, "theme");
+        graph.add_barrel_reexport_all(index, ui);
+        graph.add_barrel_reexport_all(index, utils);
+        graph.add_barrel_reexport_all(ui, button);
+        // A cycle back to the root barrel must not loop.
+        graph.add_barrel_reexport_all(button, index);
+        graph.add_resolved_import(app, index, "./index");
+
+        let report = audit_barrels(&graph, &root);
+        let summary: Vec<(&str, bool, usize, usize, usize)> = report
+            .barrels
+            .iter()
+            .map(|b| {
+                (
+                    b.file.as_str(),
+                    b.pure,
+                    b.symbols,
+                    b.importers,
+                    b.chain.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/ui/index.ts", false, 3, 1, 4),
+                ("src/index.ts", true, 4, 2, 3),
+                ("src/ui/button.ts", false, 2, 1, 3),
+            ]
+        );
+        assert_eq!(
+            report.barrels[1].chain,
+            vec!["src/index.ts", "src/ui/index.ts", "src/ui/button.ts"]
+        );
+    }
+}
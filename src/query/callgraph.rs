@@ -0,0 +1,307 @@
+//! Multi-hop call trees (`code-graph callgraph`).
+//!
+//! Walks `Calls` edges from every definition of a symbol, outward to what it calls, inward
+//! to what calls it, or both, up to a depth limit. `context` shows one hop of callers and
+//! callees; this follows the chain.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::{CodeGraph, edge::EdgeKind, node::GraphNode};
+use crate::query::util::{find_containing_file_idx, innermost_symbol_at};
+
+/// One function in a call tree.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CallNode {
+    /// Symbol name, or the file path for a top-level call outside any function.
+    pub name: String,
+    /// Symbol kind (`function`, `method`, ...), or `file` for a top-level call.
+    pub kind: String,
+    /// Project-relative path of the defining file.
+    pub file: String,
+    /// 1-based definition line (0 for a file).
+    pub line: usize,
+    /// True when this symbol already appears higher up the same branch; its subtree is not
+    /// repeated.
+    pub cycle: bool,
+    /// Next hop: what this symbol calls (outward trees) or what calls it (inward trees).
+    pub children: Vec<CallNode>,
+}
+
+/// Call trees rooted at one definition of the queried symbol.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CallGraphRoot {
+    /// The definition itself, without children.
+    pub symbol: CallNode,
+    /// What the symbol calls, transitively. `None` when only callers were requested.
+    pub callees: Option<Vec<CallNode>>,
+    /// What calls the symbol, transitively. `None` when only callees were requested.
+    pub callers: Option<Vec<CallNode>>,
+}
+
+/// Which way `call_graph` walks `Calls` edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallDirections {
+    pub callees: bool,
+    pub callers: bool,
+}
+
+/// Build call trees for each definition in `symbol_indices`, following `Calls` edges up
+/// to `depth` hops in the requested directions.
+pub fn call_graph(
+    graph: &CodeGraph,
+    symbol_indices: &[NodeIndex],
+    depth: usize,
+    directions: CallDirections,
+    root: &Path,
+) -> Vec<CallGraphRoot> {
+    let mut roots: Vec<CallGraphRoot> = symbol_indices
+        .iter()
+        .filter_map(|&sym_idx| {
+            let symbol = call_node(graph, sym_idx, root)?;
+            let walk = |direction: Direction| {
+                let mut path = vec![sym_idx];
+                expand(graph, sym_idx, direction, depth, &mut path, root)
+            };
+            Some(CallGraphRoot {
+                symbol,
+                callees: directions.callees.then(|| walk(Direction::Outgoing)),
+                callers: directions.callers.then(|| walk(Direction::Incoming)),
+            })
+        })
+        .collect();
+    roots.sort_by(|a, b| (&a.symbol.file, a.symbol.line).cmp(&(&b.symbol.file, b.symbol.line)));
+    roots
+}
+
+/// Children of `node` one `Calls` hop away in `direction`, expanded until `depth` runs out.
+/// `path` holds the branch from the root, so a recursive call is reported once as a cycle.
+fn expand(
+    graph: &CodeGraph,
+    node: NodeIndex,
+    direction: Direction,
+    depth: usize,
+    path: &mut Vec<NodeIndex>,
+    root: &Path,
+) -> Vec<CallNode> {
+    if depth == 0 {
+        return Vec::new();
+    }
+    let mut next: Vec<NodeIndex> = neighbours(graph, node, direction).into_iter().collect();
+    next.sort();
+
+    let mut children: Vec<CallNode> = next
+        .into_iter()
+        .filter_map(|idx| {
+            let mut child = call_node(graph, idx, root)?;
+            if path.contains(&idx) {
+                child.cycle = true;
+            } else {
+                path.push(idx);
+                child.children = expand(graph, idx, direction, depth - 1, path, root);
+                path.pop();
+            }
+            Some(child)
+        })
+        .collect();
+    children.sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+    children
+}
+
+/// Symbols one `Calls` hop from `node` in `direction`.
+///
+/// TypeScript, JavaScript, and Python calls hang off the calling file, with the call lines
+/// on the edge; those are attributed to the innermost symbol spanning the line, so a
+/// function's callees include the file-level calls made from its body, and a file-level
+/// caller is replaced by the function the call sits in (or kept as the file for a
+/// top-level call).
+fn neighbours(graph: &CodeGraph, node: NodeIndex, direction: Direction) -> HashSet<NodeIndex> {
+    let mut found: HashSet<NodeIndex> = HashSet::new();
+    for edge in graph.graph.edges_directed(node, direction) {
+        let EdgeKind::Calls { lines, .. } = edge.weight() else {
+            continue;
+        };
+        match direction {
+            Direction::Outgoing => {
+                found.insert(edge.target());
+            }
+            Direction::Incoming => {
+                let caller = edge.source();
+                if !matches!(graph.graph[caller], GraphNode::File(_)) || lines.is_empty() {
+                    found.insert(caller);
+                    continue;
+                }
+                for &line in lines {
+                    found.insert(innermost_symbol_at(graph, caller, line).unwrap_or(caller));
+                }
+            }
+        }
+    }
+
+    if direction == Direction::Outgoing
+        && let Some(file_idx) = find_containing_file_idx(graph, node)
+    {
+        for edge in graph.graph.edges_directed(file_idx, Direction::Outgoing) {
+            let EdgeKind::Calls { lines, .. } = edge.weight() else {
+                continue;
+            };
+            if lines
+                .iter()
+                .any(|&line| innermost_symbol_at(graph, file_idx, line) == Some(node))
+            {
+                found.insert(edge.target());
+            }
+        }
+    }
+    found
+}
+
+/// A childless `CallNode` for a symbol or file node.
+fn call_node(graph: &CodeGraph, idx: NodeIndex, root: &Path) -> Option<CallNode> {
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let (name, kind, file, line) = match &graph.graph[idx] {
+        GraphNode::Symbol(info) => {
+            let file_idx = find_containing_file_idx(graph, idx)?;
+            let GraphNode::File(fi) = &graph.graph[file_idx] else {
+                return None;
+            };
+            (
+                info.name.clone(),
+                crate::query::find::kind_to_str(&info.kind).to_string(),
+                relative(&fi.path),
+                info.line,
+            )
+        }
+        GraphNode::File(fi) => {
+            let file = relative(&fi.path);
+            (file.clone(), "file".to_string(), file, 0)
+        }
+        _ => return None,
+    };
+    Some(CallNode {
+        name,
+        kind,
+        file,
+        line,
+        cycle: false,
+        children: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+
+    fn function(graph: &mut CodeGraph, file: NodeIndex, name: &str, line: usize) -> NodeIndex {
+        graph.add_symbol(
+            file,
+            SymbolInfo {
+                name: name.into(),
+                kind: SymbolKind::Function,
+                line,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn names(nodes: &[CallNode]) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|n| {
+                if n.cycle {
+                    format!("{}*", n.name)
+                } else {
+                    n.name.clone()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_file_level_calls_attributed_by_line() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let file = graph.add_file(root.join("src/app.ts"), "typescript");
+        let mut span = |name: &str, line: usize, line_end: usize| {
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Function,
+                    line,
+                    line_end,
+                    ..Default::default()
+                },
+            )
+        };
+        let run = span("run", 1, 3);
+        let log = span("log", 5, 5);
+        // `log()` called from inside run (line 2) and at the top level (line 7).
+        graph.add_call_site(file, log, 2);
+        graph.add_call_site(file, log, 7);
+
+        let both = CallDirections {
+            callees: true,
+            callers: true,
+        };
+        let roots = call_graph(&graph, &[run], 2, both, &root);
+        assert_eq!(names(roots[0].callees.as_ref().unwrap()), vec!["log"]);
+        let roots = call_graph(&graph, &[log], 2, both, &root);
+        let callers = roots[0].callers.as_ref().unwrap();
+        assert_eq!(names(callers), vec!["src/app.ts", "run"]);
+        assert_eq!(callers[0].kind, "file");
+        assert!(roots[0].callees.as_ref().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_call_graph_walks_depth_and_marks_cycles() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let file = graph.add_file(root.join("src/app.ts"), "typescript");
+        let main = function(&mut graph, file, "main", 1);
+        let run = function(&mut graph, file, "run", 5);
+        let step = function(&mut graph, file, "step", 9);
+        let log = function(&mut graph, file, "log", 13);
+        graph.add_call_site(main, run, 2);
+        graph.add_call_site(run, step, 6);
+        graph.add_call_site(step, run, 10);
+        graph.add_call_site(step, log, 11);
+
+        let both = CallDirections {
+            callees: true,
+            callers: true,
+        };
+        let roots = call_graph(&graph, &[run], 5, both, &root);
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].symbol.file, "src/app.ts");
+
+        let callees = roots[0].callees.as_ref().unwrap();
+        assert_eq!(names(callees), vec!["step"]);
+        assert_eq!(names(&callees[0].children), vec!["run*", "log"]);
+
+        let callers = roots[0].callers.as_ref().unwrap();
+        assert_eq!(names(callers), vec!["main", "step"]);
+        assert_eq!(names(&callers[1].children), vec!["run*"]);
+
+        // Depth 1 stops after the first hop; callers were not requested.
+        let out = CallDirections {
+            callees: true,
+            callers: false,
+        };
+        let roots = call_graph(&graph, &[run], 1, out, &root);
+        assert!(roots[0].callers.is_none());
+        assert!(roots[0].callees.as_ref().unwrap()[0].children.is_empty());
+    }
+}
@@ -0,0 +1,570 @@
+//! Refactor-safety preflight (`code-graph can-delete <file|symbol>`).
+//!
+//! Combines every way the graph knows a file or symbol is used — imports and named
+//! bindings, calls, inheritance and embedding, `export *` barrel chains, tests, OpenAPI
+//! routes, SQL table references, and Markdown docs — with config files that name the file
+//! and patterns the graph cannot follow (entry points, attributes and decorators, names in
+//! string literals, template-literal dynamic imports) into one verdict:
+//!
+//! - **safe**: nothing references the target and no dynamic usage is suspected;
+//! - **blocked**: the listed references must go first;
+//! - **unknown**: nothing references it, but it may be reached dynamically.
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Result, bail};
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use regex::Regex;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode, SymbolVisibility};
+use crate::query::dead_code::{is_entry_point_file, is_entry_point_symbol};
+use crate::query::refs::find_refs;
+use crate::query::util::find_containing_file_idx;
+
+/// Whether the target can be deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Safe,
+    Blocked,
+    Unknown,
+}
+
+/// A reference that must be removed before the target can be.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+pub struct BlockingRef {
+    /// `import`, `call`, `extends`, `implements`, `embeds`, `re-export`, `test`, `route`,
+    /// `table`, `doc`, or `config`.
+    pub kind: &'static str,
+    /// Project-relative path of the referencing file.
+    pub file: String,
+    /// 1-based line of the referencing symbol, when known.
+    pub line: Option<usize>,
+    /// Referencing symbol, when the reference comes from one.
+    pub symbol: Option<String>,
+}
+
+/// The `can-delete` verdict for one file or symbol name.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CanDeleteReport {
+    pub target: String,
+    /// `file` or `symbol`.
+    pub target_kind: &'static str,
+    pub verdict: Verdict,
+    pub references: Vec<BlockingRef>,
+    /// Reasons the target may be used in ways the graph does not record.
+    pub dynamic: Vec<String>,
+}
+
+fn relative(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn file_path(graph: &CodeGraph, idx: NodeIndex) -> Option<&Path> {
+    match &graph.graph[idx] {
+        GraphNode::File(fi) => Some(fi.path.as_path()),
+        _ => None,
+    }
+}
+
+/// A reference from `source` (a file or symbol node) of `kind`.
+fn reference_from(
+    graph: &CodeGraph,
+    root: &Path,
+    kind: &'static str,
+    source: NodeIndex,
+) -> Option<BlockingRef> {
+    match &graph.graph[source] {
+        GraphNode::File(fi) => Some(BlockingRef {
+            kind,
+            file: relative(&fi.path, root),
+            line: None,
+            symbol: None,
+        }),
+        GraphNode::Symbol(sym) => {
+            let file = file_path(graph, find_containing_file_idx(graph, source)?)?;
+            Some(BlockingRef {
+                kind,
+                file: relative(file, root),
+                line: Some(sym.line),
+                symbol: Some(sym.name.clone()),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// References from outside `owned` into `symbols` through symbol-level edges.
+fn symbol_edge_refs(
+    graph: &CodeGraph,
+    root: &Path,
+    symbols: &[NodeIndex],
+    owned: &HashSet<NodeIndex>,
+) -> Vec<BlockingRef> {
+    let mut refs = Vec::new();
+    for &sym in symbols {
+        for edge in graph.graph.edges_directed(sym, Direction::Incoming) {
+            // Import bindings carry their statement's line; file-level sources have none.
+            let line = match edge.weight() {
+                EdgeKind::ImportsSymbol { line, .. } if *line > 0 => Some(*line),
+                _ => None,
+            };
+            let kind = match edge.weight() {
+                EdgeKind::Calls { .. } => "call",
+                EdgeKind::Renders => "render",
+                EdgeKind::Extends => "extends",
+                EdgeKind::Implements => "implements",
+                EdgeKind::Embeds => "embeds",
+                EdgeKind::ImportsSymbol { .. } => "import",
+                EdgeKind::ExportsAs { .. } => "re-export",
+                EdgeKind::Tests => "test",
+                EdgeKind::HandledBy => "route",
+                EdgeKind::ReferencesTable => "table",
+                _ => continue,
+            };
+            if !owned.contains(&edge.source()) {
+                refs.extend(reference_from(graph, root, kind, edge.source()).map(|r| {
+                    BlockingRef {
+                        line: line.or(r.line),
+                        ..r
+                    }
+                }));
+            }
+        }
+        for edge in graph.graph.edges(sym) {
+            if matches!(edge.weight(), EdgeKind::DocumentedBy) {
+                refs.extend(reference_from(graph, root, "doc", edge.target()));
+            }
+        }
+    }
+    refs
+}
+
+/// Sort `refs` and keep one entry per (kind, file, line): the same import site reaches the
+/// target both as a file import and as a symbol binding. An entry without a line is dropped
+/// when another of the same kind and file has one.
+fn dedupe_refs(refs: &mut Vec<BlockingRef>) {
+    refs.sort();
+    refs.dedup_by(|b, a| {
+        let same = a.kind == b.kind && a.file == b.file && a.line == b.line;
+        if same && a.symbol.is_none() {
+            a.symbol = b.symbol.take();
+        }
+        same
+    });
+    let with_line: HashSet<(&'static str, String)> = refs
+        .iter()
+        .filter(|r| r.line.is_some())
+        .map(|r| (r.kind, r.file.clone()))
+        .collect();
+    refs.retain(|r| r.line.is_some() || !with_line.contains(&(r.kind, r.file.clone())));
+}
+
+/// `sym` and every symbol nested under it through `ChildOf` edges.
+fn with_descendants(graph: &CodeGraph, sym: NodeIndex) -> Vec<NodeIndex> {
+    let mut all = vec![sym];
+    let mut i = 0;
+    while i < all.len() {
+        let children: Vec<NodeIndex> = graph
+            .graph
+            .edges_directed(all[i], Direction::Incoming)
+            .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+            .map(|e| e.source())
+            .collect();
+        all.extend(children);
+        i += 1;
+    }
+    all
+}
+
+/// Text of every source file except `exclude`, with its path.
+fn source_texts(graph: &CodeGraph, exclude: &HashSet<PathBuf>) -> Vec<(PathBuf, String)> {
+    graph
+        .graph
+        .node_weights()
+        .filter_map(|n| match n {
+            GraphNode::File(fi) if fi.kind == FileKind::Source && !exclude.contains(&fi.path) => {
+                Some(fi.path.clone())
+            }
+            _ => None,
+        })
+        .filter_map(|path| std::fs::read_to_string(&path).ok().map(|text| (path, text)))
+        .collect()
+}
+
+/// Config files (`package.json`, `tsconfig.json`, `Cargo.toml`, CI workflows, ...) whose
+/// text contains `needle`.
+fn config_mentions(graph: &CodeGraph, root: &Path, needle: &str) -> Vec<BlockingRef> {
+    graph
+        .graph
+        .node_weights()
+        .filter_map(|n| match n {
+            GraphNode::File(fi) if matches!(fi.kind, FileKind::Config | FileKind::Ci) => Some(fi),
+            _ => None,
+        })
+        .filter(|fi| std::fs::read_to_string(&fi.path).is_ok_and(|text| text.contains(needle)))
+        .map(|fi| BlockingRef {
+            kind: "config",
+            file: relative(&fi.path, root),
+            line: None,
+            symbol: None,
+        })
+        .collect()
+}
+
+/// Static directory prefixes of template-literal dynamic imports and requires
+/// (`import(`./locales/${lang}`)`).
+fn dynamic_import_prefixes(text: &str) -> Vec<&str> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"(?:import|require)\(\s*`([^`$]*)\$\{").expect("valid dynamic import regex")
+    });
+    re.captures_iter(text)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+        .collect()
+}
+
+/// Lexically resolve a relative `prefix` against `dir`, keeping a trailing partial name.
+fn join_prefix(dir: &Path, prefix: &str) -> PathBuf {
+    let mut path = dir.to_path_buf();
+    for part in prefix.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                path.pop();
+            }
+            _ => path.push(part),
+        }
+    }
+    path
+}
+
+/// Check a file: everything importing, re-exporting, testing, documenting, configuring, or
+/// reaching into it.
+fn check_file(
+    graph: &CodeGraph,
+    root: &Path,
+    file_idx: NodeIndex,
+) -> (Vec<BlockingRef>, Vec<String>) {
+    let GraphNode::File(fi) = &graph.graph[file_idx] else {
+        return (Vec::new(), Vec::new());
+    };
+    let top_level: Vec<NodeIndex> = graph
+        .graph
+        .edges(file_idx)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .map(|e| e.target())
+        .collect();
+    let symbols: Vec<NodeIndex> = top_level
+        .iter()
+        .flat_map(|&s| with_descendants(graph, s))
+        .collect();
+    let mut owned: HashSet<NodeIndex> = symbols.iter().copied().collect();
+    owned.insert(file_idx);
+
+    let mut refs: Vec<BlockingRef> = Vec::new();
+    for edge in graph.graph.edges_directed(file_idx, Direction::Incoming) {
+        if edge.source() == file_idx {
+            continue;
+        }
+        let (kind, line) = match edge.weight() {
+            EdgeKind::ResolvedImport { line, .. } => ("import", (*line > 0).then_some(*line)),
+            EdgeKind::BarrelReExportAll => ("re-export", None),
+            EdgeKind::Tests => ("test", None),
+            _ => continue,
+        };
+        refs.extend(
+            reference_from(graph, root, kind, edge.source()).map(|r| BlockingRef { line, ..r }),
+        );
+    }
+    for edge in graph.graph.edges(file_idx) {
+        if matches!(edge.weight(), EdgeKind::DocumentedBy) {
+            refs.extend(reference_from(graph, root, "doc", edge.target()));
+        }
+    }
+    refs.extend(symbol_edge_refs(graph, root, &symbols, &owned));
+    refs.extend(config_mentions(graph, root, &relative(&fi.path, root)));
+
+    let mut dynamic: Vec<String> = Vec::new();
+    if is_entry_point_file(fi) {
+        dynamic.push("entry point file".to_string());
+    }
+    let exclude: HashSet<PathBuf> = [fi.path.clone()].into_iter().collect();
+    for (path, text) in source_texts(graph, &exclude) {
+        let dir = path.parent().unwrap_or(root);
+        for prefix in dynamic_import_prefixes(&text) {
+            if prefix.starts_with('.')
+                && fi
+                    .path
+                    .to_string_lossy()
+                    .starts_with(&*join_prefix(dir, prefix).to_string_lossy())
+            {
+                dynamic.push(format!(
+                    "dynamic import `{}${{...}}` in {} may load it",
+                    prefix,
+                    relative(&path, root)
+                ));
+            }
+        }
+    }
+    (refs, dynamic)
+}
+
+/// Check every definition of the symbol `name`.
+fn check_symbol(
+    graph: &CodeGraph,
+    root: &Path,
+    name: &str,
+    defs: &[NodeIndex],
+) -> (Vec<BlockingRef>, Vec<String>) {
+    let symbols: Vec<NodeIndex> = defs
+        .iter()
+        .flat_map(|&s| with_descendants(graph, s))
+        .collect();
+    let owned: HashSet<NodeIndex> = symbols.iter().copied().collect();
+
+    let mut refs: Vec<BlockingRef> = find_refs(graph, name, defs, root)
+        .into_iter()
+        .map(|r| BlockingRef {
+            kind: r.ref_kind.as_str(),
+            file: relative(&r.file_path, root),
+            line: r.line,
+            symbol: r.symbol_name,
+        })
+        .collect();
+    refs.extend(symbol_edge_refs(graph, root, &symbols, &owned));
+
+    let mut dynamic: Vec<String> = Vec::new();
+    for &def in defs {
+        let (GraphNode::Symbol(sym), Some(file_idx)) =
+            (&graph.graph[def], find_containing_file_idx(graph, def))
+        else {
+            continue;
+        };
+        let GraphNode::File(fi) = &graph.graph[file_idx] else {
+            continue;
+        };
+        let location = format!("{}:{}", relative(&fi.path, root), sym.line);
+        if is_entry_point_symbol(sym, fi) {
+            dynamic.push(format!("entry point, trait impl, or test at {}", location));
+        }
+        // Only decorators from a known framework: `#[derive]`, `#[cfg]` and the rest of the
+        // `std` group do not make a symbol reachable.
+        let frameworks: BTreeSet<&str> = sym
+            .decorators
+            .iter()
+            .filter_map(|d| d.framework.as_deref())
+            .filter(|&fw| fw != "std")
+            .collect();
+        if !frameworks.is_empty() {
+            dynamic.push(format!(
+                "{} decorator at {}; the framework may register it",
+                frameworks.into_iter().collect::<Vec<_>>().join(", "),
+                location
+            ));
+        }
+        if is_entry_point_file(fi) && (sym.is_exported || sym.visibility == SymbolVisibility::Pub) {
+            dynamic.push(format!(
+                "exported from package entry {}; may be used outside the project",
+                relative(&fi.path, root)
+            ));
+        }
+    }
+    let quoted = Regex::new(&format!(r#"["'`]{}["'`]"#, regex::escape(name))).expect("valid regex");
+    for (path, text) in source_texts(graph, &HashSet::new()) {
+        if quoted.is_match(&text) {
+            dynamic.push(format!(
+                "name appears in a string literal in {}",
+                relative(&path, root)
+            ));
+        }
+    }
+    (refs, dynamic)
+}
+
+/// Decide whether `target` — a file path (absolute or relative to `root`) or an exact
+/// symbol name — can be deleted.
+pub fn can_delete(graph: &CodeGraph, root: &Path, target: &str) -> Result<CanDeleteReport> {
+    let as_path = Path::new(target);
+    let abs = if as_path.is_absolute() {
+        as_path.to_path_buf()
+    } else {
+        root.join(as_path)
+    };
+    let (target_kind, (mut references, mut dynamic)) =
+        if let Some(&file_idx) = graph.file_index.get(&abs) {
+            ("file", check_file(graph, root, file_idx))
+        } else if let Some(defs) = graph.symbol_index.get(target).filter(|d| !d.is_empty()) {
+            ("symbol", check_symbol(graph, root, target, defs))
+        } else {
+            bail!("'{}' is neither an indexed file nor a symbol", target);
+        };
+
+    dedupe_refs(&mut references);
+    dynamic.sort();
+    dynamic.dedup();
+    let verdict = if !references.is_empty() {
+        Verdict::Blocked
+    } else if !dynamic.is_empty() {
+        Verdict::Unknown
+    } else {
+        Verdict::Safe
+    };
+    Ok(CanDeleteReport {
+        target: target.to_string(),
+        target_kind,
+        verdict,
+        references,
+        dynamic,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use std::fs;
+
+    fn function(name: &str, line: usize) -> SymbolInfo {
+        SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line,
+            is_exported: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_file_blocked_by_import_and_call() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let util = graph.add_file(root.join("src/util.ts"), "typescript");
+        let helper = graph.add_symbol(util, function("helper", 1));
+        let app = graph.add_file(root.join("src/app.ts"), "typescript");
+        let run = graph.add_symbol(app, function("run", 3));
+        graph.add_resolved_import(app, util, "./util");
+        graph.add_call_site(run, helper, 4);
+
+        let report = can_delete(&graph, &root, "src/util.ts").unwrap();
+        assert_eq!(report.target_kind, "file");
+        assert_eq!(report.verdict, Verdict::Blocked);
+        let refs: Vec<String> = report
+            .references
+            .iter()
+            .map(|r| format!("{} {} {:?} {:?}", r.kind, r.file, r.line, r.symbol))
+            .collect();
+        assert_eq!(
+            refs,
+            vec![
+                r#"call src/app.ts Some(3) Some("run")"#,
+                "import src/app.ts None None",
+            ]
+        );
+
+        let report = can_delete(&graph, &root, "run").unwrap();
+        assert_eq!(report.target_kind, "symbol");
+        assert_eq!(report.verdict, Verdict::Safe);
+    }
+
+    #[test]
+    fn test_import_site_reported_once() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let util = graph.add_file(root.join("src/util.ts"), "typescript");
+        let helper = graph.add_symbol(util, function("helper", 1));
+        let app = graph.add_file(root.join("src/app.ts"), "typescript");
+        graph.add_import_at(app, util, "./util", false, 2, 1);
+        graph.add_imports_symbol(app, helper, 0, 0);
+
+        for target in ["helper", "src/util.ts"] {
+            let report = can_delete(&graph, &root, target).unwrap();
+            let refs: Vec<String> = report
+                .references
+                .iter()
+                .map(|r| format!("{} {} {:?}", r.kind, r.file, r.line))
+                .collect();
+            assert_eq!(refs, vec!["import src/app.ts Some(2)"], "{target}");
+        }
+    }
+
+    #[test]
+    fn test_symbol_named_in_string_literal_is_unknown() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::write(
+            root.join("registry.ts"),
+            "export const names = ['helper'];\n",
+        )
+        .unwrap();
+        let mut graph = CodeGraph::new();
+        let util = graph.add_file(root.join("util.ts"), "typescript");
+        graph.add_symbol(util, function("helper", 1));
+        graph.add_file(root.join("registry.ts"), "typescript");
+
+        let report = can_delete(&graph, root, "helper").unwrap();
+        assert_eq!(report.verdict, Verdict::Unknown);
+        assert!(report.references.is_empty());
+        assert_eq!(
+            report.dynamic,
+            vec!["name appears in a string literal in registry.ts"]
+        );
+    }
+
+    #[test]
+    fn test_file_loaded_by_dynamic_import_is_unknown() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("src/locales")).unwrap();
+        fs::write(
+            root.join("src/i18n.ts"),
+            "export const load = (lang) => import(`./locales/${lang}.ts`);\n",
+        )
+        .unwrap();
+        let mut graph = CodeGraph::new();
+        graph.add_file(root.join("src/i18n.ts"), "typescript");
+        graph.add_file(root.join("src/locales/en.ts"), "typescript");
+        graph.add_file(root.join("src/other.ts"), "typescript");
+
+        let report = can_delete(&graph, root, "src/locales/en.ts").unwrap();
+        assert_eq!(report.verdict, Verdict::Unknown);
+        assert_eq!(
+            report.dynamic,
+            vec!["dynamic import `./locales/${...}` in src/i18n.ts may load it"]
+        );
+
+        let report = can_delete(&graph, root, "src/other.ts").unwrap();
+        assert_eq!(report.verdict, Verdict::Safe);
+    }
+
+    #[test]
+    fn test_file_named_in_config_is_blocked() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::write(root.join("package.json"), r#"{"bin": "src/cli.ts"}"#).unwrap();
+        let mut graph = CodeGraph::new();
+        graph.add_file(root.join("src/cli.ts"), "typescript");
+        graph.add_non_parsed_file(root.join("package.json"), FileKind::Config);
+
+        let report = can_delete(&graph, root, "src/cli.ts").unwrap();
+        assert_eq!(report.verdict, Verdict::Blocked);
+        assert_eq!(report.references.len(), 1);
+        assert_eq!(report.references[0].kind, "config");
+        assert_eq!(report.references[0].file, "package.json");
+    }
+
+    #[test]
+    fn test_unknown_target_errors() {
+        let graph = CodeGraph::new();
+        assert!(can_delete(&graph, Path::new("/proj"), "missing").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+//! Project-defined categories for files and symbols (`[[category]]` in `code-graph.toml`).
+//!
+//! Categories are assigned after every build or incremental update by
+//! [`apply_categories`] and stored on `FileInfo` / `SymbolInfo`, so `find`, `impact`, and
+//! `export` can filter on them with `--category` and the DOT export can color by them.
+
+use std::path::Path;
+
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::config::CategoryConfig;
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+
+use super::util::find_containing_file_idx;
+
+/// A `[[category]]` rule with its globs compiled.
+struct Rule {
+    name: String,
+    paths: Vec<glob::Pattern>,
+    symbols: Vec<glob::Pattern>,
+}
+
+impl Rule {
+    fn matches_path(&self, rel_path: &str) -> bool {
+        self.paths.is_empty() || self.paths.iter().any(|p| p.matches(rel_path))
+    }
+
+    fn matches_symbol(&self, name: &str) -> bool {
+        self.symbols.is_empty() || self.symbols.iter().any(|p| p.matches(name))
+    }
+}
+
+/// Compile the configured rules. Invalid globs are reported on stderr and skipped; rules
+/// left without any pattern match nothing and are dropped.
+fn compile_rules(configs: &[CategoryConfig]) -> Vec<Rule> {
+    let compile = |name: &str, patterns: &[String]| -> Vec<glob::Pattern> {
+        patterns
+            .iter()
+            .filter_map(|p| match glob::Pattern::new(p) {
+                Ok(pattern) => Some(pattern),
+                Err(err) => {
+                    eprintln!("warning: category '{name}': invalid pattern '{p}': {err}");
+                    None
+                }
+            })
+            .collect()
+    };
+    configs
+        .iter()
+        .map(|c| Rule {
+            name: c.name.clone(),
+            paths: compile(&c.name, &c.paths),
+            symbols: compile(&c.name, &c.symbols),
+        })
+        .filter(|r| !r.paths.is_empty() || !r.symbols.is_empty())
+        .collect()
+}
+
+/// Path of `file_idx` relative to `project_root`, with `/` separators.
+fn relative_path(graph: &CodeGraph, file_idx: NodeIndex, project_root: &Path) -> String {
+    match &graph.graph[file_idx] {
+        GraphNode::File(fi) => fi
+            .path
+            .strip_prefix(project_root)
+            .unwrap_or(&fi.path)
+            .to_string_lossy()
+            .replace('\\', "/"),
+        _ => String::new(),
+    }
+}
+
+/// Assign every file and symbol the category of the first rule that matches it, clearing
+/// categories that no rule matches any more.
+///
+/// Files only match rules without `symbols` patterns; symbols match any rule whose
+/// patterns all accept them.
+pub fn apply_categories(graph: &mut CodeGraph, project_root: &Path, configs: &[CategoryConfig]) {
+    let rules = compile_rules(configs);
+
+    let assignments: Vec<(NodeIndex, Option<String>)> = graph
+        .graph
+        .node_indices()
+        .filter_map(|idx| {
+            let category = match &graph.graph[idx] {
+                GraphNode::File(_) => {
+                    let rel = relative_path(graph, idx, project_root);
+                    rules
+                        .iter()
+                        .find(|r| r.symbols.is_empty() && r.matches_path(&rel))
+                }
+                GraphNode::Symbol(info) => {
+                    let rel = find_containing_file_idx(graph, idx)
+                        .map(|file_idx| relative_path(graph, file_idx, project_root))
+                        .unwrap_or_default();
+                    rules
+                        .iter()
+                        .find(|r| r.matches_path(&rel) && r.matches_symbol(&info.name))
+                }
+                _ => return None,
+            };
+            Some((idx, category.map(|r| r.name.clone())))
+        })
+        .collect();
+
+    for (idx, category) in assignments {
+        match &mut graph.graph[idx] {
+            GraphNode::File(fi) => fi.category = category,
+            GraphNode::Symbol(info) => info.category = category,
+            _ => {}
+        }
+    }
+}
+
+/// `true` when the file at `file_idx`, or one of its symbols, is in `category`.
+pub fn file_in_category(graph: &CodeGraph, file_idx: NodeIndex, category: &str) -> bool {
+    if let GraphNode::File(fi) = &graph.graph[file_idx]
+        && fi.category.as_deref() == Some(category)
+    {
+        return true;
+    }
+    graph
+        .graph
+        .edges_directed(file_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .any(|e| {
+            matches!(&graph.graph[e.target()],
+                GraphNode::Symbol(info) if info.category.as_deref() == Some(category))
+        })
+}
+
+/// `true` when the symbol named `name` defined at `line` of `file_path` is in `category`.
+pub fn symbol_in_category(
+    graph: &CodeGraph,
+    file_path: &Path,
+    name: &str,
+    line: usize,
+    category: &str,
+) -> bool {
+    let Some(candidates) = graph.symbol_index.get(name) else {
+        return false;
+    };
+    candidates.iter().any(|&idx| {
+        matches!(&graph.graph[idx],
+            GraphNode::Symbol(info) if info.line == line && info.category.as_deref() == Some(category))
+            && find_containing_file_idx(graph, idx)
+                .is_some_and(|file_idx| graph.file_index.get(file_path) == Some(&file_idx))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use std::path::PathBuf;
+
+    fn rule(name: &str, paths: &[&str], symbols: &[&str]) -> CategoryConfig {
+        CategoryConfig {
+            name: name.into(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn symbol(name: &str, line: usize) -> SymbolInfo {
+        SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line,
+            ..Default::default()
+        }
+    }
+
+    fn category_of(graph: &CodeGraph, idx: NodeIndex) -> Option<String> {
+        match &graph.graph[idx] {
+            GraphNode::File(fi) => fi.category.clone(),
+            GraphNode::Symbol(info) => info.category.clone(),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_apply_categories() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let page = graph.add_file(root.join("src/pages/Home.tsx"), "tsx");
+        let util = graph.add_file(root.join("src/lib/hooks.ts"), "typescript");
+        let home = graph.add_symbol(page, symbol("HomePage", 1));
+        let use_auth = graph.add_symbol(util, symbol("useAuth", 1));
+        let format = graph.add_symbol(util, symbol("format", 5));
+
+        let rules = [
+            rule("pages", &["src/pages/**"], &[]),
+            rule("hooks", &[], &["use*"]),
+            rule("components", &["src/components/**"], &["[A-Z]*"]),
+        ];
+        apply_categories(&mut graph, &root, &rules);
+
+        assert_eq!(category_of(&graph, page).as_deref(), Some("pages"));
+        assert_eq!(category_of(&graph, home).as_deref(), Some("pages"));
+        assert_eq!(category_of(&graph, util), None);
+        assert_eq!(category_of(&graph, use_auth).as_deref(), Some("hooks"));
+        assert_eq!(category_of(&graph, format), None);
+
+        assert!(file_in_category(&graph, util, "hooks"));
+        assert!(!file_in_category(&graph, util, "pages"));
+        let hooks_path = root.join("src/lib/hooks.ts");
+        assert!(symbol_in_category(
+            &graph,
+            &hooks_path,
+            "useAuth",
+            1,
+            "hooks"
+        ));
+        assert!(!symbol_in_category(
+            &graph,
+            &hooks_path,
+            "format",
+            5,
+            "hooks"
+        ));
+
+        // Removing the rules clears the stored categories.
+        apply_categories(&mut graph, &root, &[]);
+        assert_eq!(category_of(&graph, page), None);
+        assert_eq!(category_of(&graph, use_auth), None);
+    }
+}
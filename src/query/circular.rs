@@ -1,50 +1,95 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 
 use petgraph::Directed;
-use petgraph::algo::kosaraju_scc;
+use petgraph::algo::tarjan_scc;
 use petgraph::graph::Graph;
 use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 
 use crate::graph::{CodeGraph, edge::EdgeKind, node::GraphNode};
 
-/// A set of files forming a circular dependency cycle.
+/// Default number of representative cycles reported per strongly connected component.
+pub const DEFAULT_MAX_CYCLES: usize = 3;
+
+/// Start files tried per requested cycle before giving up on finding more distinct ones.
+/// Bounds the work on huge components to `max_cycles * START_ATTEMPTS_PER_CYCLE` BFS runs.
+const START_ATTEMPTS_PER_CYCLE: usize = 4;
+
+/// A strongly connected component of the import graph: a group of files that mutually
+/// import each other directly or transitively.
 #[derive(Debug, Clone)]
 pub struct CircularDep {
-    /// Files forming the cycle, ordered deterministically by path.
-    /// The first file is repeated at the end to close the visual cycle.
+    /// Every file in the component, sorted by path.
     pub files: Vec<PathBuf>,
+    /// Representative cycles through the component (at most `max_cycles`), shortest first
+    /// per start file. Each cycle is in import order (`a` imports `b`, ...) and closed by
+    /// repeating its first file.
+    pub cycles: Vec<Vec<PathBuf>>,
+}
+
+/// A symbol taking part in a symbol-level cycle.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+pub struct CycleSymbol {
+    /// Project-relative path of the defining file.
+    pub file: String,
+    pub line: usize,
+    pub name: String,
+}
+
+/// A strongly connected component of the symbol graph: functions, methods, and types that
+/// reach each other through calls, `extends`, or `implements`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SymbolCircularDep {
+    /// Every symbol in the component, sorted by file, line, and name.
+    pub symbols: Vec<CycleSymbol>,
+    /// Representative cycles, as in [`CircularDep::cycles`]: `a` calls (or extends, or
+    /// implements) `b`, ..., closed by repeating the first symbol.
+    pub cycles: Vec<Vec<CycleSymbol>>,
 }
 
 /// Detect circular dependencies in the project's import graph.
 ///
-/// Uses Kosaraju's SCC algorithm on a file-only subgraph containing only
+/// Uses Tarjan's SCC algorithm on a file-only subgraph containing only
 /// `ResolvedImport` edges (not `BarrelReExportAll`, `Calls`, or others).
-/// SCCs with more than one node are circular dependency cycles.
+/// SCCs with more than one node are circular dependency groups. Instead of enumerating
+/// every elementary cycle (exponential on large SCCs), at most `max_cycles` shortest
+/// cycles are reported per SCC, found by a BFS from each of its first files.
+/// With `runtime_only`, type-only imports (`import type`) are left out: they are erased at
+/// compile time and cannot form a runtime cycle.
 ///
-/// Returns cycles sorted by the first file path in each cycle.
-pub fn find_circular(graph: &CodeGraph, project_root: &Path) -> Vec<CircularDep> {
+/// Returns groups sorted by the first file path in each group.
+pub fn find_circular(
+    graph: &CodeGraph,
+    project_root: &Path,
+    max_cycles: usize,
+    runtime_only: bool,
+) -> Vec<CircularDep> {
     let _ = project_root; // kept for API consistency
+    let max_cycles = max_cycles.max(1);
 
     // Step 1: Build a regular (non-stable) petgraph Graph containing ONLY file nodes
-    // and ResolvedImport edges. This is required for kosaraju_scc.
+    // and ResolvedImport edges. This is required for tarjan_scc.
     let mut file_graph: Graph<NodeIndex, (), Directed> = Graph::new();
     // Maps original StableGraph NodeIndex -> new Graph NodeIndex
     let mut orig_to_new: HashMap<NodeIndex, petgraph::graph::NodeIndex> = HashMap::new();
-    // Maps new Graph NodeIndex -> original NodeIndex (for path lookup)
-    let mut new_to_orig: HashMap<petgraph::graph::NodeIndex, NodeIndex> = HashMap::new();
+    // Maps new Graph NodeIndex -> file path
+    let mut new_to_path: HashMap<petgraph::graph::NodeIndex, PathBuf> = HashMap::new();
 
     // Add a node for each file in the original graph.
     for &orig_idx in graph.file_index.values() {
-        let new_idx = file_graph.add_node(orig_idx);
-        orig_to_new.insert(orig_idx, new_idx);
-        new_to_orig.insert(new_idx, orig_idx);
+        if let GraphNode::File(ref fi) = graph.graph[orig_idx] {
+            let new_idx = file_graph.add_node(orig_idx);
+            orig_to_new.insert(orig_idx, new_idx);
+            new_to_path.insert(new_idx, fi.path.clone());
+        }
     }
 
     // Add only ResolvedImport edges between file nodes.
     for edge_ref in graph.graph.edge_references() {
-        if matches!(edge_ref.weight(), EdgeKind::ResolvedImport { .. }) {
+        if let EdgeKind::ResolvedImport { is_type_only, .. } = edge_ref.weight()
+            && !(runtime_only && *is_type_only)
+        {
             let src_orig = edge_ref.source();
             let dst_orig = edge_ref.target();
             // Only add if both endpoints are file nodes (skip edges to ExternalPackage/Unresolved).
@@ -56,45 +101,185 @@ pub fn find_circular(graph: &CodeGraph, project_root: &Path) -> Vec<CircularDep>
         }
     }
 
-    // Step 2: Run Kosaraju's SCC algorithm.
-    let sccs = kosaraju_scc(&file_graph);
+    // Step 2: Run Tarjan's SCC algorithm.
+    let sccs = tarjan_scc(&file_graph);
 
-    // Step 3: Filter to SCCs with more than one node (actual cycles).
-    let mut cycles: Vec<CircularDep> = sccs
+    // Step 3: Keep SCCs with more than one node and pick representative cycles in each.
+    let mut groups: Vec<CircularDep> = sccs
         .into_iter()
         .filter(|scc| scc.len() > 1)
-        .filter_map(|scc| {
-            let mut file_paths: Vec<PathBuf> = scc
-                .iter()
-                .filter_map(|&new_idx| {
-                    let orig_idx = new_to_orig.get(&new_idx)?;
-                    if let GraphNode::File(ref fi) = graph.graph[*orig_idx] {
-                        Some(fi.path.clone())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-
-            if file_paths.is_empty() {
-                return None;
-            }
+        .map(|scc| {
+            let (files, cycles) = representative_cycles(&file_graph, scc, &new_to_path, max_cycles);
+            CircularDep { files, cycles }
+        })
+        .collect();
 
-            // Sort files within the cycle by path for deterministic output.
-            file_paths.sort();
+    // Sort groups by the first file in each group.
+    groups.sort_by(|a, b| a.files[0].cmp(&b.files[0]));
+
+    groups
+}
+
+/// Detect mutually dependent symbols: Tarjan's SCC algorithm over `Calls`, `Extends`, and
+/// `Implements` edges between symbols, within and across files.
+///
+/// Components of a single symbol (plain recursion) are not reported. As for files, at most
+/// `max_cycles` shortest cycles are reported per component. Returns groups sorted by their
+/// first symbol.
+pub fn find_symbol_circular(
+    graph: &CodeGraph,
+    project_root: &Path,
+    max_cycles: usize,
+) -> Vec<SymbolCircularDep> {
+    let max_cycles = max_cycles.max(1);
+
+    let mut symbol_graph: Graph<NodeIndex, (), Directed> = Graph::new();
+    let mut orig_to_new: HashMap<NodeIndex, petgraph::graph::NodeIndex> = HashMap::new();
+    let mut new_to_symbol: HashMap<petgraph::graph::NodeIndex, CycleSymbol> = HashMap::new();
+    let mut node_for = |orig: NodeIndex| -> Option<petgraph::graph::NodeIndex> {
+        if let Some(&idx) = orig_to_new.get(&orig) {
+            return Some(idx);
+        }
+        let GraphNode::Symbol(ref sym) = graph.graph[orig] else {
+            return None;
+        };
+        let file_idx = crate::query::util::find_containing_file_idx(graph, orig)?;
+        let GraphNode::File(ref fi) = graph.graph[file_idx] else {
+            return None;
+        };
+        let idx = symbol_graph.add_node(orig);
+        orig_to_new.insert(orig, idx);
+        new_to_symbol.insert(
+            idx,
+            CycleSymbol {
+                file: fi
+                    .path
+                    .strip_prefix(project_root)
+                    .unwrap_or(&fi.path)
+                    .to_string_lossy()
+                    .to_string(),
+                line: sym.line,
+                name: sym.name.clone(),
+            },
+        );
+        Some(idx)
+    };
 
-            // Close the visual cycle by appending the first file at the end: a -> b -> c -> a.
-            let first = file_paths[0].clone();
-            file_paths.push(first);
+    let mut edges: Vec<(petgraph::graph::NodeIndex, petgraph::graph::NodeIndex)> = Vec::new();
+    for edge_ref in graph.graph.edge_references() {
+        if matches!(
+            edge_ref.weight(),
+            EdgeKind::Calls { .. } | EdgeKind::Extends | EdgeKind::Implements
+        ) && let (Some(src), Some(dst)) =
+            (node_for(edge_ref.source()), node_for(edge_ref.target()))
+        {
+            edges.push((src, dst));
+        }
+    }
+    for (src, dst) in edges {
+        symbol_graph.add_edge(src, dst, ());
+    }
 
-            Some(CircularDep { files: file_paths })
+    let mut groups: Vec<SymbolCircularDep> = tarjan_scc(&symbol_graph)
+        .into_iter()
+        .filter(|scc| scc.len() > 1)
+        .map(|scc| {
+            let (symbols, cycles) =
+                representative_cycles(&symbol_graph, scc, &new_to_symbol, max_cycles);
+            SymbolCircularDep { symbols, cycles }
         })
         .collect();
+    groups.sort_by(|a, b| a.symbols[0].cmp(&b.symbols[0]));
+    groups
+}
 
-    // Sort cycles by the first file in each cycle.
-    cycles.sort_by(|a, b| a.files[0].cmp(&b.files[0]));
+/// The members of `scc` sorted by label, and at most `max_cycles` distinct shortest cycles
+/// through them, each closed by repeating its first label.
+fn representative_cycles<L: Ord + Clone>(
+    g: &Graph<NodeIndex, (), Directed>,
+    mut scc: Vec<petgraph::graph::NodeIndex>,
+    labels: &HashMap<petgraph::graph::NodeIndex, L>,
+    max_cycles: usize,
+) -> (Vec<L>, Vec<Vec<L>>) {
+    // Sort members by label so start nodes and output are deterministic.
+    scc.sort_by(|a, b| labels[a].cmp(&labels[b]));
+    let members: HashSet<petgraph::graph::NodeIndex> = scc.iter().copied().collect();
+
+    let mut cycles: Vec<Vec<L>> = Vec::new();
+    let mut seen: HashSet<Vec<petgraph::graph::NodeIndex>> = HashSet::new();
+    for &start in scc.iter().take(max_cycles * START_ATTEMPTS_PER_CYCLE) {
+        if cycles.len() >= max_cycles {
+            break;
+        }
+        let Some(cycle) = shortest_cycle_through(g, &members, labels, start) else {
+            continue;
+        };
+        if seen.insert(canonical_rotation(&cycle, labels)) {
+            let mut chain: Vec<L> = cycle.iter().map(|idx| labels[idx].clone()).collect();
+            // Close the visual cycle by appending the first node at the end: a -> b -> a.
+            chain.push(chain[0].clone());
+            cycles.push(chain);
+        }
+    }
+
+    (scc.iter().map(|idx| labels[idx].clone()).collect(), cycles)
+}
+
+/// Shortest cycle through `start` that stays inside `members`, in edge order starting at
+/// `start` (not closed). Neighbours are visited in label order for deterministic output.
+fn shortest_cycle_through<L: Ord>(
+    file_graph: &Graph<NodeIndex, (), Directed>,
+    members: &HashSet<petgraph::graph::NodeIndex>,
+    paths: &HashMap<petgraph::graph::NodeIndex, L>,
+    start: petgraph::graph::NodeIndex,
+) -> Option<Vec<petgraph::graph::NodeIndex>> {
+    let mut parent: HashMap<petgraph::graph::NodeIndex, petgraph::graph::NodeIndex> =
+        HashMap::new();
+    let mut queue: VecDeque<petgraph::graph::NodeIndex> = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        let mut neighbors: Vec<petgraph::graph::NodeIndex> = file_graph
+            .neighbors(current)
+            .filter(|n| members.contains(n))
+            .collect();
+        neighbors.sort_by(|a, b| paths[a].cmp(&paths[b]));
+        neighbors.dedup();
+
+        for next in neighbors {
+            if next == start {
+                // Walk parents back from `current` to rebuild start -> ... -> current.
+                let mut cycle = vec![current];
+                let mut node = current;
+                while node != start {
+                    node = parent[&node];
+                    cycle.push(node);
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if let std::collections::hash_map::Entry::Vacant(entry) = parent.entry(next) {
+                entry.insert(current);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
 
-    cycles
+/// Rotate a cycle so that its smallest label comes first, making the same cycle found from
+/// different start nodes compare equal.
+fn canonical_rotation<L: Ord>(
+    cycle: &[petgraph::graph::NodeIndex],
+    paths: &HashMap<petgraph::graph::NodeIndex, L>,
+) -> Vec<petgraph::graph::NodeIndex> {
+    let min_pos = (0..cycle.len())
+        .min_by(|&a, &b| paths[&cycle[a]].cmp(&paths[&cycle[b]]))
+        .unwrap_or(0);
+    cycle[min_pos..]
+        .iter()
+        .chain(&cycle[..min_pos])
+        .copied()
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
@@ -123,26 +308,28 @@ mod tests {
         graph.add_resolved_import(a_file, b_file, "./b");
         graph.add_resolved_import(b_file, a_file, "./a");
 
-        let cycles = find_circular(&graph, &root);
+        let cycles = find_circular(&graph, &root, DEFAULT_MAX_CYCLES, false);
         assert_eq!(cycles.len(), 1, "one cycle expected");
+        assert_eq!(
+            cycles[0].cycles.len(),
+            1,
+            "one representative cycle expected"
+        );
+        let cycle = &cycles[0].cycles[0];
         // The cycle should contain both a.ts and b.ts (plus one repeated to close it = 3 entries).
         assert_eq!(
-            cycles[0].files.len(),
+            cycle.len(),
             3,
             "cycle should have 3 entries (2 files + closing)"
         );
-        let paths: Vec<_> = cycles[0]
-            .files
+        let paths: Vec<_> = cycle
             .iter()
             .map(|p| p.file_name().unwrap().to_str().unwrap())
             .collect();
         assert!(paths.contains(&"a.ts"));
         assert!(paths.contains(&"b.ts"));
         // First and last should be the same (cycle closed).
-        assert_eq!(
-            cycles[0].files[0],
-            cycles[0].files[cycles[0].files.len() - 1]
-        );
+        assert_eq!(cycle[0], cycle[cycle.len() - 1]);
     }
 
     #[test]
@@ -159,10 +346,15 @@ mod tests {
         graph.add_resolved_import(b_file, c_file, "./c");
         graph.add_resolved_import(c_file, a_file, "./a");
 
-        let cycles = find_circular(&graph, &root);
+        let cycles = find_circular(&graph, &root, DEFAULT_MAX_CYCLES, false);
         assert_eq!(cycles.len(), 1, "one 3-cycle expected");
-        // 3 unique files + 1 closing = 4 entries.
-        assert_eq!(cycles[0].files.len(), 4);
+        assert_eq!(cycles[0].files.len(), 3);
+        // 3 unique files + 1 closing = 4 entries, in import order.
+        let names: Vec<_> = cycles[0].cycles[0]
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["a.ts", "b.ts", "c.ts", "a.ts"]);
     }
 
     #[test]
@@ -178,7 +370,7 @@ mod tests {
         graph.add_resolved_import(a_file, b_file, "./b");
         graph.add_resolved_import(b_file, c_file, "./c");
 
-        let cycles = find_circular(&graph, &root);
+        let cycles = find_circular(&graph, &root, DEFAULT_MAX_CYCLES, false);
         assert!(cycles.is_empty(), "no cycles expected in a DAG");
     }
 
@@ -198,7 +390,7 @@ mod tests {
 
         // Since BarrelReExportAll is excluded, there should be no cycle detected.
         // (utils -> index via ResolvedImport, but index -> utils only via BarrelReExportAll)
-        let cycles = find_circular(&graph, &root);
+        let cycles = find_circular(&graph, &root, DEFAULT_MAX_CYCLES, false);
         assert!(
             cycles.is_empty(),
             "BarrelReExportAll edges must not contribute to cycle detection"
@@ -216,7 +408,7 @@ mod tests {
         // but ExternalPackage nodes are NOT in orig_to_new (only file nodes are).
         graph.add_external_package(a_file, "react", "react");
 
-        let cycles = find_circular(&graph, &root);
+        let cycles = find_circular(&graph, &root, DEFAULT_MAX_CYCLES, false);
         assert!(
             cycles.is_empty(),
             "external package edges should not create cycles"
@@ -247,11 +439,93 @@ mod tests {
         graph.add_resolved_import(a_file, b_file, "./b");
         graph.add_resolved_import(b_file, a_file, "./a");
 
-        let cycles = find_circular(&graph, &root);
+        let cycles = find_circular(&graph, &root, DEFAULT_MAX_CYCLES, false);
         assert_eq!(
             cycles.len(),
             1,
             "symbols should not interfere with cycle detection"
         );
     }
+
+    #[test]
+    fn test_large_scc_reports_size_and_bounded_cycles() {
+        // Ten files all importing each other: one SCC with a huge number of elementary cycles.
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let files: Vec<NodeIndex> = (0..10)
+            .map(|i| graph.add_file(root.join(format!("f{i}.ts")), "typescript"))
+            .collect();
+        for &from in &files {
+            for &to in &files {
+                if from != to {
+                    graph.add_resolved_import(from, to, "./x");
+                }
+            }
+        }
+
+        let groups = find_circular(&graph, &root, 2, false);
+        assert_eq!(groups.len(), 1, "one strongly connected component expected");
+        assert_eq!(groups[0].files.len(), 10, "SCC size reported");
+        assert_eq!(groups[0].cycles.len(), 2, "cycle enumeration capped");
+        // Shortest cycles, deduplicated by rotation: f0 <-> f1, then f2 <-> f0.
+        for cycle in &groups[0].cycles {
+            assert_eq!(cycle.len(), 3);
+            assert_eq!(cycle[0], cycle[2]);
+        }
+        assert_ne!(groups[0].cycles[0], groups[0].cycles[1]);
+    }
+
+    #[test]
+    fn test_runtime_only_ignores_type_only_cycle() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let a_file = graph.add_file(root.join("a.ts"), "typescript");
+        let b_file = graph.add_file(root.join("b.ts"), "typescript");
+        graph.add_resolved_import(a_file, b_file, "./b");
+        graph.add_import_at(b_file, a_file, "./a", true, 0, 0);
+
+        assert_eq!(
+            find_circular(&graph, &root, DEFAULT_MAX_CYCLES, false).len(),
+            1
+        );
+        assert!(find_circular(&graph, &root, DEFAULT_MAX_CYCLES, true).is_empty());
+    }
+
+    #[test]
+    fn test_symbol_cycles_across_files() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let a_file = graph.add_file(root.join("a.ts"), "typescript");
+        let b_file = graph.add_file(root.join("b.ts"), "typescript");
+        let mut symbol = |file, name: &str, line| {
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Function,
+                    line,
+                    ..Default::default()
+                },
+            )
+        };
+        let ping = symbol(a_file, "ping", 1);
+        let walk = symbol(a_file, "walk", 5);
+        let pong = symbol(b_file, "pong", 2);
+        let helper = symbol(b_file, "helper", 8);
+        graph.add_call_site(ping, pong, 1);
+        graph.add_call_site(pong, ping, 3);
+        graph.add_call_site(pong, helper, 4);
+        // Plain recursion is not a cycle between symbols.
+        graph.add_call_site(walk, walk, 6);
+
+        let groups = find_symbol_circular(&graph, &root, DEFAULT_MAX_CYCLES);
+        assert_eq!(groups.len(), 1);
+        let chain: Vec<String> = groups[0].cycles[0]
+            .iter()
+            .map(|s| format!("{}@{}:{}", s.name, s.file, s.line))
+            .collect();
+        assert_eq!(chain, vec!["ping@a.ts:1", "pong@b.ts:2", "ping@a.ts:1"]);
+        // File-level detection sees no import cycle here.
+        assert!(find_circular(&graph, &root, DEFAULT_MAX_CYCLES, false).is_empty());
+    }
 }
@@ -48,6 +48,13 @@ fn deterministic_signature_hash(sig: &StructuralSignature) -> u64 {
         SymbolKind::Const => 13,
         SymbolKind::Static => 14,
         SymbolKind::Macro => 15,
+        SymbolKind::Message => 16,
+        SymbolKind::Service => 17,
+        SymbolKind::Rpc => 18,
+        SymbolKind::Table => 19,
+        SymbolKind::View => 20,
+        SymbolKind::Endpoint => 21,
+        SymbolKind::Namespace => 22,
     };
     // FNV-1a-style deterministic combine
     let mut h: u64 = 0xcbf29ce484222325;
@@ -120,8 +127,14 @@ pub fn find_clones(
         }
     });
 
+    // Byte-identical vendored copies are analysed once, through their canonical file.
+    let copies = crate::query::identical_files::identical_copies(graph);
+
     // Helper: check if a path is under the scope
     let in_scope = |path: &Path| -> bool {
+        if copies.contains(path) {
+            return false;
+        }
         match &abs_scope {
             None => true,
             Some(scope_path) => path.starts_with(scope_path),
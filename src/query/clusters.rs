@@ -108,8 +108,10 @@ pub fn find_clusters(
             // Outgoing edges: Calls (this symbol calls others), ChildOf (this is a child)
             for edge_ref in graph.graph.edges_directed(node, Direction::Outgoing) {
                 let neighbor = edge_ref.target();
-                if matches!(edge_ref.weight(), EdgeKind::Calls | EdgeKind::ChildOf)
-                    && let Some(nlabel) = snapshot.get(&neighbor)
+                if matches!(
+                    edge_ref.weight(),
+                    EdgeKind::Calls { .. } | EdgeKind::ChildOf
+                ) && let Some(nlabel) = snapshot.get(&neighbor)
                 {
                     *neighbor_labels.entry(nlabel.clone()).or_insert(0) += 1;
                 }
@@ -118,8 +120,10 @@ pub fn find_clusters(
             // Incoming edges: Calls (others call this), ChildOf (this has children)
             for edge_ref in graph.graph.edges_directed(node, Direction::Incoming) {
                 let neighbor = edge_ref.source();
-                if matches!(edge_ref.weight(), EdgeKind::Calls | EdgeKind::ChildOf)
-                    && let Some(nlabel) = snapshot.get(&neighbor)
+                if matches!(
+                    edge_ref.weight(),
+                    EdgeKind::Calls { .. } | EdgeKind::ChildOf
+                ) && let Some(nlabel) = snapshot.get(&neighbor)
                 {
                     *neighbor_labels.entry(nlabel.clone()).or_insert(0) += 1;
                 }
@@ -0,0 +1,92 @@
+//! Most complex functions (`code-graph complexity`).
+//!
+//! Ranks the functions and methods whose cyclomatic complexity was measured during
+//! parsing (see [`crate::parser::complexity`]).
+
+use std::path::Path;
+
+use crate::graph::CodeGraph;
+use crate::graph::node::GraphNode;
+use crate::query::util::find_containing_file_idx;
+
+/// A function or method and its cyclomatic complexity.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FunctionComplexity {
+    pub name: String,
+    pub kind: String,
+    /// Project-relative path of the defining file.
+    pub file: String,
+    pub line: usize,
+    pub complexity: u32,
+}
+
+/// The `top` most complex functions and methods, most complex first.
+pub fn most_complex(graph: &CodeGraph, root: &Path, top: usize) -> Vec<FunctionComplexity> {
+    let mut functions: Vec<FunctionComplexity> = graph
+        .graph
+        .node_indices()
+        .filter_map(|idx| {
+            let GraphNode::Symbol(info) = &graph.graph[idx] else {
+                return None;
+            };
+            let complexity = info.complexity?;
+            let GraphNode::File(fi) = &graph.graph[find_containing_file_idx(graph, idx)?] else {
+                return None;
+            };
+            Some(FunctionComplexity {
+                name: info.name.clone(),
+                kind: crate::query::find::kind_to_str(&info.kind).to_string(),
+                file: fi
+                    .path
+                    .strip_prefix(root)
+                    .unwrap_or(&fi.path)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                line: info.line,
+                complexity,
+            })
+        })
+        .collect();
+    functions.sort_by(|a, b| {
+        b.complexity
+            .cmp(&a.complexity)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    functions.truncate(top);
+    functions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+
+    #[test]
+    fn test_most_complex_ranks_measured_functions() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let file = graph.add_file(root.join("src/app.ts"), "typescript");
+        for (name, line, complexity) in [("a", 1, Some(3)), ("b", 5, Some(9)), ("C", 9, None)] {
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Function,
+                    line,
+                    complexity,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let ranked: Vec<(String, u32)> = most_complex(&graph, &root, 10)
+            .into_iter()
+            .map(|f| (f.name, f.complexity))
+            .collect();
+        assert_eq!(ranked, vec![("b".to_string(), 9), ("a".to_string(), 3)]);
+        assert_eq!(most_complex(&graph, &root, 1).len(), 1);
+    }
+}
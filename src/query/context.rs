@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use petgraph::Direction;
@@ -12,6 +12,7 @@ use crate::graph::{
 };
 use crate::query::find::FindResult;
 use crate::query::refs::RefResult;
+use crate::query::util::innermost_symbol_at;
 
 /// Information about a symbol involved in a call or inheritance relationship.
 #[derive(Debug, Clone)]
@@ -43,6 +44,50 @@ pub struct SymbolContext {
     pub extended_by: Vec<CallInfo>,
     /// Symbols that implement this (incoming Implements edges).
     pub implemented_by: Vec<CallInfo>,
+    /// Base-class, interface, or trait methods this method overrides (outgoing Overrides
+    /// edges).
+    pub overrides: Vec<CallInfo>,
+    /// Methods that override this one in subclasses, implementing classes, or trait impls
+    /// (incoming Overrides edges).
+    pub overridden_by: Vec<CallInfo>,
+    /// Markdown docs that mention this symbol (outgoing DocumentedBy edges), sorted by path.
+    pub documented_by: Vec<PathBuf>,
+    /// OpenAPI route links (HandledBy edges in either direction): the endpoints a handler
+    /// serves, or the handlers of an endpoint.
+    pub routes: Vec<CallInfo>,
+    /// Source around each definition and call site; empty unless filled by
+    /// [`attach_source`] (`context --source`).
+    pub source: Vec<SourceSnippet>,
+}
+
+/// What a [`SourceSnippet`] shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnippetKind {
+    /// A definition of the queried symbol.
+    Definition,
+    /// A call of the queried symbol.
+    CallSite,
+}
+
+impl SnippetKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SnippetKind::Definition => "def",
+            SnippetKind::CallSite => "call",
+        }
+    }
+}
+
+/// Source lines of a definition or call site, with surrounding context.
+#[derive(Debug, Clone)]
+pub struct SourceSnippet {
+    pub kind: SnippetKind,
+    pub file_path: PathBuf,
+    /// The calling symbol, for call sites inside one.
+    pub caller: Option<String>,
+    /// 1-based line of `lines[0]`.
+    pub line_start: usize,
+    pub lines: Vec<String>,
 }
 
 /// Build a 360-degree context view for a symbol.
@@ -82,10 +127,14 @@ pub fn symbol_context(
                     line: sym_info.line,
                     line_end: sym_info.line_end,
                     col: sym_info.col,
+                    col_end: sym_info.col_end,
+                    byte_start: sym_info.byte_start,
+                    byte_end: sym_info.byte_end,
                     is_exported: sym_info.is_exported,
                     is_default: sym_info.is_default,
                     visibility: sym_info.visibility.clone(),
                     decorators: sym_info.decorators.clone(),
+                    signature: sym_info.signature.clone(),
                 });
             }
         }
@@ -107,7 +156,7 @@ pub fn symbol_context(
 
     for &sym_idx in symbol_indices {
         for edge_ref in graph.graph.edges_directed(sym_idx, Direction::Incoming) {
-            if !matches!(edge_ref.weight(), EdgeKind::Calls) {
+            if !matches!(edge_ref.weight(), EdgeKind::Calls { .. }) {
                 continue;
             }
             let caller_idx = edge_ref.source();
@@ -137,7 +186,7 @@ pub fn symbol_context(
     for &sym_idx in symbol_indices {
         // Direct symbol -> symbol calls.
         for edge_ref in graph.graph.edges_directed(sym_idx, Direction::Outgoing) {
-            if !matches!(edge_ref.weight(), EdgeKind::Calls) {
+            if !matches!(edge_ref.weight(), EdgeKind::Calls { .. }) {
                 continue;
             }
             let callee_idx = edge_ref.target();
@@ -153,7 +202,7 @@ pub fn symbol_context(
         // File-level calls: outgoing Calls from the symbol's parent file.
         if let Some(file_idx) = find_containing_file_idx(graph, sym_idx) {
             for edge_ref in graph.graph.edges_directed(file_idx, Direction::Outgoing) {
-                if !matches!(edge_ref.weight(), EdgeKind::Calls) {
+                if !matches!(edge_ref.weight(), EdgeKind::Calls { .. }) {
                     continue;
                 }
                 let callee_idx = edge_ref.target();
@@ -242,6 +291,77 @@ pub fn symbol_context(
     extended_by.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
     implemented_by.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
 
+    // -------------------------------------------------------------------------
+    // Overrides / Overridden by: Overrides edges in either direction.
+    // -------------------------------------------------------------------------
+    let mut overrides: Vec<CallInfo> = Vec::new();
+    let mut overridden_by: Vec<CallInfo> = Vec::new();
+    let mut over_dedup: HashSet<(String, PathBuf, usize)> = HashSet::new();
+    let mut overby_dedup: HashSet<(String, PathBuf, usize)> = HashSet::new();
+    for &sym_idx in symbol_indices {
+        for edge_ref in graph.graph.edges_directed(sym_idx, Direction::Outgoing) {
+            if matches!(edge_ref.weight(), EdgeKind::Overrides)
+                && let Some(ci) = build_call_info(graph, edge_ref.target())
+                && over_dedup.insert((ci.symbol_name.clone(), ci.file_path.clone(), ci.line))
+            {
+                overrides.push(ci);
+            }
+        }
+        for edge_ref in graph.graph.edges_directed(sym_idx, Direction::Incoming) {
+            if matches!(edge_ref.weight(), EdgeKind::Overrides)
+                && let Some(ci) = build_call_info(graph, edge_ref.source())
+                && overby_dedup.insert((ci.symbol_name.clone(), ci.file_path.clone(), ci.line))
+            {
+                overridden_by.push(ci);
+            }
+        }
+    }
+    overrides.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
+    overridden_by.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
+
+    // -------------------------------------------------------------------------
+    // Documented by: Markdown docs mentioning the symbol.
+    // -------------------------------------------------------------------------
+    let mut documented_by: Vec<PathBuf> = symbol_indices
+        .iter()
+        .flat_map(|&sym_idx| graph.graph.edges_directed(sym_idx, Direction::Outgoing))
+        .filter(|e| matches!(e.weight(), EdgeKind::DocumentedBy))
+        .filter_map(|e| match &graph.graph[e.target()] {
+            GraphNode::File(fi) => Some(fi.path.clone()),
+            _ => None,
+        })
+        .collect();
+    documented_by.sort();
+    documented_by.dedup();
+
+    // -------------------------------------------------------------------------
+    // Routes: OpenAPI endpoints linked to handlers, seen from either side.
+    // -------------------------------------------------------------------------
+    let mut routes: Vec<CallInfo> = Vec::new();
+    let mut route_dedup: HashSet<(String, PathBuf, usize)> = HashSet::new();
+    for &sym_idx in symbol_indices {
+        let linked = graph
+            .graph
+            .edges_directed(sym_idx, Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::HandledBy))
+            .map(|e| e.target())
+            .chain(
+                graph
+                    .graph
+                    .edges_directed(sym_idx, Direction::Incoming)
+                    .filter(|e| matches!(e.weight(), EdgeKind::HandledBy))
+                    .map(|e| e.source()),
+            );
+        for other in linked {
+            if let Some(ci) = build_call_info(graph, other)
+                && route_dedup.insert((ci.symbol_name.clone(), ci.file_path.clone(), ci.line))
+            {
+                routes.push(ci);
+            }
+        }
+    }
+    routes.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
+
     SymbolContext {
         symbol_name: symbol_name.to_string(),
         definitions,
@@ -252,7 +372,137 @@ pub fn symbol_context(
         implements,
         extended_by,
         implemented_by,
+        overrides,
+        overridden_by,
+        documented_by,
+        routes,
+        source: Vec::new(),
+    }
+}
+
+/// Fill `ctx.source` with the source of each definition (its byte range, widened to whole
+/// lines) and each call site of `symbol_indices` (the call line), with `context_lines`
+/// lines before and after. Call sites of one caller whose windows overlap share a snippet.
+/// Files that cannot be read are skipped.
+pub fn attach_source(
+    graph: &CodeGraph,
+    ctx: &mut SymbolContext,
+    symbol_indices: &[NodeIndex],
+    context_lines: usize,
+) {
+    let mut files: HashMap<PathBuf, Option<String>> = HashMap::new();
+    let mut read = |path: &Path| -> Option<String> {
+        files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| std::fs::read_to_string(path).ok())
+            .clone()
+    };
+    let window = |text: &str, first: usize, last: usize| -> (usize, Vec<String>) {
+        let start = first.saturating_sub(context_lines).max(1);
+        let lines: Vec<String> = text
+            .lines()
+            .skip(start - 1)
+            .take(last + context_lines + 1 - start)
+            .map(str::to_string)
+            .collect();
+        (start, lines)
+    };
+
+    let mut snippets: Vec<SourceSnippet> = Vec::new();
+    for def in &ctx.definitions {
+        let Some(text) = read(&def.file_path) else {
+            continue;
+        };
+        let line_of = |byte: usize| text[..byte].matches('\n').count() + 1;
+        let (first, last) = if def.byte_end > def.byte_start
+            && def.byte_end <= text.len()
+            && text.is_char_boundary(def.byte_start)
+            && text.is_char_boundary(def.byte_end)
+        {
+            (line_of(def.byte_start), line_of(def.byte_end))
+        } else {
+            (def.line, def.line_end.max(def.line))
+        };
+        let (line_start, lines) = window(&text, first, last);
+        snippets.push(SourceSnippet {
+            kind: SnippetKind::Definition,
+            file_path: def.file_path.clone(),
+            caller: None,
+            line_start,
+            lines,
+        });
+    }
+
+    // (file, caller, call line); file-level calls are attributed to the symbol enclosing
+    // the line.
+    let mut sites: Vec<(PathBuf, Option<String>, usize)> = Vec::new();
+    for &sym_idx in symbol_indices {
+        for edge_ref in graph.graph.edges_directed(sym_idx, Direction::Incoming) {
+            let EdgeKind::Calls { lines, .. } = edge_ref.weight() else {
+                continue;
+            };
+            let caller_idx = edge_ref.source();
+            let (file_idx, caller, fallback) = match &graph.graph[caller_idx] {
+                GraphNode::Symbol(info) => (
+                    find_containing_file_idx(graph, caller_idx),
+                    Some(info.name.clone()),
+                    info.line,
+                ),
+                GraphNode::File(_) => (Some(caller_idx), None, 0),
+                _ => continue,
+            };
+            let Some(file_idx) = file_idx else {
+                continue;
+            };
+            let GraphNode::File(fi) = &graph.graph[file_idx] else {
+                continue;
+            };
+            let call_lines = if lines.is_empty() && fallback > 0 {
+                vec![fallback]
+            } else {
+                lines.clone()
+            };
+            for line in call_lines {
+                let caller = caller.clone().or_else(|| {
+                    innermost_symbol_at(graph, file_idx, line).and_then(|idx| {
+                        match &graph.graph[idx] {
+                            GraphNode::Symbol(info) => Some(info.name.clone()),
+                            _ => None,
+                        }
+                    })
+                });
+                sites.push((fi.path.clone(), caller, line));
+            }
+        }
+    }
+    sites.sort();
+    sites.dedup();
+
+    let mut ranges: Vec<(PathBuf, Option<String>, usize, usize)> = Vec::new();
+    for (path, caller, line) in sites {
+        match ranges.last_mut() {
+            Some(last)
+                if last.0 == path && last.1 == caller && line <= last.3 + 2 * context_lines + 1 =>
+            {
+                last.3 = line
+            }
+            _ => ranges.push((path, caller, line, line)),
+        }
+    }
+    for (path, caller, first, last) in ranges {
+        let Some(text) = read(&path) else {
+            continue;
+        };
+        let (line_start, lines) = window(&text, first, last);
+        snippets.push(SourceSnippet {
+            kind: SnippetKind::CallSite,
+            file_path: path,
+            caller,
+            line_start,
+            lines,
+        });
     }
+    ctx.source = snippets;
 }
 
 // ---------------------------------------------------------------------------
@@ -378,7 +628,7 @@ mod tests {
             },
         );
         // handleRequest calls UserService
-        graph.add_calls_edge(handle_request, user_service);
+        graph.add_call_site(handle_request, user_service, 1);
 
         (graph, root, user_service, handle_request)
     }
@@ -407,7 +657,7 @@ mod tests {
         let ctx = symbol_context(&graph, "handleRequest", &[handle_request], &root);
 
         // handleRequest calls UserService — should appear in callees (from file-level Calls walk)
-        // Note: add_calls_edge(handle_request, user_service) adds symbol-to-symbol Calls edge
+        // Note: add_call_site(handle_request, user_service, 1) adds symbol-to-symbol Calls edge
         assert_eq!(ctx.callees.len(), 1, "handleRequest should have one callee");
         assert_eq!(ctx.callees[0].symbol_name, "UserService");
     }
@@ -468,6 +718,39 @@ mod tests {
         assert!(ctx.implements.is_empty());
         assert!(ctx.extended_by.is_empty());
         assert!(ctx.implemented_by.is_empty());
+        assert!(ctx.documented_by.is_empty());
+    }
+
+    #[test]
+    fn test_documented_by_lists_docs() {
+        let root = root();
+        let mut graph = CodeGraph::new();
+
+        let file = graph.add_file(root.join("auth.ts"), "typescript");
+        let login = graph.add_symbol(
+            file,
+            SymbolInfo {
+                name: "login".into(),
+                kind: SymbolKind::Function,
+                line: 3,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        let guide = graph.add_non_parsed_file(
+            root.join("docs/guide.md"),
+            crate::graph::node::FileKind::Doc,
+        );
+        let readme =
+            graph.add_non_parsed_file(root.join("README.md"), crate::graph::node::FileKind::Doc);
+        graph.add_documented_by(login, guide);
+        graph.add_documented_by(login, readme);
+
+        let ctx = symbol_context(&graph, "login", &[login], &root);
+        assert_eq!(
+            ctx.documented_by,
+            vec![root.join("README.md"), root.join("docs/guide.md")]
+        );
     }
 
     #[test]
@@ -511,4 +794,73 @@ mod tests {
         assert_eq!(ctx2.implemented_by.len(), 1);
         assert_eq!(ctx2.implemented_by[0].symbol_name, "ServiceImpl");
     }
+
+    #[test]
+    fn test_attach_source_windows_definitions_and_call_sites() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        let lib = root.join("lib.ts");
+        let app = root.join("app.ts");
+        let lib_src = "// helpers\nexport function greet() {\n  return 1;\n}\n";
+        std::fs::write(&lib, lib_src).unwrap();
+        std::fs::write(
+            &app,
+            "function main() {\n  greet();\n  a();\n  greet();\n  b();\n  c();\n  d();\n  greet();\n}\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let lib_idx = graph.add_file(lib, "typescript");
+        let app_idx = graph.add_file(app, "typescript");
+        let greet = graph.add_symbol(
+            lib_idx,
+            SymbolInfo {
+                name: "greet".into(),
+                kind: SymbolKind::Function,
+                line: 2,
+                line_end: 4,
+                byte_start: lib_src.find("export").unwrap(),
+                byte_end: lib_src.len() - 1,
+                ..Default::default()
+            },
+        );
+        graph.add_symbol(
+            app_idx,
+            SymbolInfo {
+                name: "main".into(),
+                kind: SymbolKind::Function,
+                line: 1,
+                line_end: 9,
+                ..Default::default()
+            },
+        );
+        // File-level calls, attributed to `main` by line.
+        for line in [2, 4, 8] {
+            graph.add_call_site(app_idx, greet, line);
+        }
+
+        let mut ctx = symbol_context(&graph, "greet", &[greet], root);
+        attach_source(&graph, &mut ctx, &[greet], 1);
+        let summary: Vec<(&str, Option<&str>, usize, usize)> = ctx
+            .source
+            .iter()
+            .map(|s| {
+                (
+                    s.kind.as_str(),
+                    s.caller.as_deref(),
+                    s.line_start,
+                    s.lines.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("def", None, 1, 4),
+                ("call", Some("main"), 1, 5),
+                ("call", Some("main"), 7, 3),
+            ]
+        );
+        assert_eq!(ctx.source[2].lines, vec!["  d();", "  greet();", "}"]);
+    }
 }
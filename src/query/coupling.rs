@@ -0,0 +1,272 @@
+//! Temporal coupling from git history (`code-graph coupling`).
+//!
+//! Walks the most recent commits with `git log --name-only` and counts, for every pair of
+//! indexed source files, how often they change in the same commit. Pairs that change
+//! together often but share no edge in the graph — no import, call, render, binding,
+//! inheritance, or test link in either direction — are reported as hidden-coupling
+//! candidates: the dependency is real, but it lives in a convention, a duplicated
+//! constant, or a protocol the code does not spell out.
+//!
+//! The coupling degree of a pair is `2 * shared / (revisions_a + revisions_b)`, the share
+//! of the two files' commits they make together.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::graph::CodeGraph;
+use crate::graph::node::{FileKind, GraphNode};
+use crate::query::util::find_containing_file_idx;
+
+/// Commits touching more files than this are skipped: bulk renames, reformatting and
+/// dependency bumps say nothing about coupling.
+const MAX_FILES_PER_COMMIT: usize = 50;
+
+/// Thresholds for `coupling`.
+#[derive(Debug, Clone, Copy)]
+pub struct CouplingOptions {
+    /// Number of most recent non-merge commits to read.
+    pub commits: usize,
+    /// Minimum number of shared commits for a pair to be reported.
+    pub min_shared: usize,
+    /// Minimum coupling degree (0.0–1.0) for a pair to be reported.
+    pub min_degree: f64,
+}
+
+/// Two files that change together without a structural edge between them.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CoupledPair {
+    /// Project-relative paths, in lexical order.
+    pub file_a: String,
+    pub file_b: String,
+    /// Commits that touched both files.
+    pub shared: usize,
+    /// Commits that touched each file.
+    pub revisions_a: usize,
+    pub revisions_b: usize,
+    /// `2 * shared / (revisions_a + revisions_b)`.
+    pub degree: f64,
+}
+
+/// Result of `coupling`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CouplingReport {
+    /// Commits read, including those skipped for touching too many files.
+    pub commits_analyzed: usize,
+    /// Pairs above the thresholds that are already linked in the graph.
+    pub linked_pairs: usize,
+    /// Hidden-coupling candidates, strongest first.
+    pub pairs: Vec<CoupledPair>,
+}
+
+/// Files touched by each of the last `max_commits` non-merge commits, as paths relative
+/// to `root`.
+pub(crate) fn commit_file_sets(root: &Path, max_commits: usize) -> Result<Vec<Vec<String>>> {
+    let output = std::process::Command::new("git")
+        .args([
+            "log",
+            "--no-merges",
+            "--name-only",
+            "--relative",
+            "--format=%x00",
+        ])
+        .arg(format!("--max-count={}", max_commits))
+        .args(["--", "."])
+        .current_dir(root)
+        .output()?;
+    if !output.status.success() {
+        bail!(
+            "git log failed in {}: {}",
+            root.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .split('\0')
+        .map(|commit| {
+            commit
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .filter(|files| !files.is_empty())
+        .collect())
+}
+
+/// Pairs of source files (by index, lower first) joined by any edge between the files
+/// or their symbols.
+fn linked_file_pairs(graph: &CodeGraph) -> HashSet<(NodeIndex, NodeIndex)> {
+    let file_of = |idx: NodeIndex| match &graph.graph[idx] {
+        GraphNode::File(_) => Some(idx),
+        GraphNode::Symbol(_) => find_containing_file_idx(graph, idx),
+        _ => None,
+    };
+    graph
+        .graph
+        .edge_references()
+        .filter_map(|e| {
+            let (a, b) = (file_of(e.source())?, file_of(e.target())?);
+            (a != b).then(|| (a.min(b), a.max(b)))
+        })
+        .collect()
+}
+
+/// Count co-changes among the indexed source files in `commits` and keep the pairs above
+/// the thresholds, split into hidden pairs and the number already linked.
+fn coupled_pairs(
+    graph: &CodeGraph,
+    root: &Path,
+    commits: &[Vec<String>],
+    options: &CouplingOptions,
+) -> (Vec<CoupledPair>, usize) {
+    let sources: HashMap<String, NodeIndex> = graph
+        .file_index
+        .iter()
+        .filter(|&(_, &idx)| {
+            matches!(&graph.graph[idx], GraphNode::File(fi) if fi.kind == FileKind::Source)
+        })
+        .map(|(path, &idx)| {
+            let rel = path.strip_prefix(root).unwrap_or(path);
+            (rel.to_string_lossy().replace('\\', "/"), idx)
+        })
+        .collect();
+
+    let mut revisions: HashMap<&str, usize> = HashMap::new();
+    let mut shared: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for files in commits.iter().filter(|f| f.len() <= MAX_FILES_PER_COMMIT) {
+        let mut touched: Vec<&str> = files
+            .iter()
+            .map(String::as_str)
+            .filter(|f| sources.contains_key(*f))
+            .collect();
+        touched.sort_unstable();
+        touched.dedup();
+        for (i, &a) in touched.iter().enumerate() {
+            *revisions.entry(a).or_default() += 1;
+            for &b in &touched[i + 1..] {
+                *shared.entry((a, b)).or_default() += 1;
+            }
+        }
+    }
+
+    let linked = linked_file_pairs(graph);
+    let mut linked_count = 0;
+    let mut pairs: Vec<CoupledPair> = Vec::new();
+    for ((a, b), count) in shared {
+        let (revisions_a, revisions_b) = (revisions[a], revisions[b]);
+        let degree = 2.0 * count as f64 / (revisions_a + revisions_b) as f64;
+        if count < options.min_shared || degree < options.min_degree {
+            continue;
+        }
+        let (ia, ib) = (sources[a], sources[b]);
+        if linked.contains(&(ia.min(ib), ia.max(ib))) {
+            linked_count += 1;
+            continue;
+        }
+        pairs.push(CoupledPair {
+            file_a: a.to_string(),
+            file_b: b.to_string(),
+            shared: count,
+            revisions_a,
+            revisions_b,
+            degree,
+        });
+    }
+    pairs.sort_by(|x, y| {
+        y.shared
+            .cmp(&x.shared)
+            .then(y.degree.total_cmp(&x.degree))
+            .then_with(|| (&x.file_a, &x.file_b).cmp(&(&y.file_a, &y.file_b)))
+    });
+    (pairs, linked_count)
+}
+
+/// Find files under `root` that change together in git history without a structural edge
+/// between them.
+pub fn find_coupling(
+    graph: &CodeGraph,
+    root: &Path,
+    options: &CouplingOptions,
+) -> Result<CouplingReport> {
+    let commits = commit_file_sets(root, options.commits)?;
+    let (pairs, linked_pairs) = coupled_pairs(graph, root, &commits, options);
+    Ok(CouplingReport {
+        commits_analyzed: commits.len(),
+        linked_pairs,
+        pairs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn commit(files: &[&str]) -> Vec<String> {
+        files.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn test_hidden_pairs_skip_linked_and_weak_pairs() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let api = graph.add_file(root.join("src/api.ts"), "typescript");
+        let client = graph.add_file(root.join("src/client.ts"), "typescript");
+        graph.add_file(root.join("src/schema.ts"), "typescript");
+        graph.add_file(root.join("src/misc.ts"), "typescript");
+        graph.add_resolved_import(client, api, "./api");
+
+        let commits = vec![
+            commit(&["src/api.ts", "src/client.ts", "src/schema.ts"]),
+            commit(&["src/api.ts", "src/client.ts", "src/schema.ts", "README.md"]),
+            commit(&["src/api.ts", "src/schema.ts"]),
+            commit(&["src/api.ts", "src/misc.ts"]),
+        ];
+        let options = CouplingOptions {
+            commits: 100,
+            min_shared: 2,
+            min_degree: 0.5,
+        };
+        let (pairs, linked) = coupled_pairs(&graph, &root, &commits, &options);
+        // api<->client is imported; api<->misc shares one commit only.
+        assert_eq!(linked, 1);
+        let found: Vec<(&str, &str, usize)> = pairs
+            .iter()
+            .map(|p| (p.file_a.as_str(), p.file_b.as_str(), p.shared))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("src/api.ts", "src/schema.ts", 3),
+                ("src/client.ts", "src/schema.ts", 2),
+            ]
+        );
+        assert!((pairs[0].degree - 6.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bulk_commits_are_skipped() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        graph.add_file(root.join("a.ts"), "typescript");
+        graph.add_file(root.join("b.ts"), "typescript");
+        let mut bulk: Vec<String> = (0..MAX_FILES_PER_COMMIT)
+            .map(|i| format!("x{i}.ts"))
+            .collect();
+        bulk.extend(commit(&["a.ts", "b.ts"]));
+        let commits = vec![bulk.clone(), bulk];
+        let options = CouplingOptions {
+            commits: 100,
+            min_shared: 1,
+            min_degree: 0.0,
+        };
+        let (pairs, _) = coupled_pairs(&graph, &root, &commits, &options);
+        assert!(pairs.is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+//! Architectural chokepoints (`code-graph cut-points`).
+//!
+//! A cut point is an articulation file of the import graph: read without direction, the
+//! graph falls apart into several parts when the file is removed, so everything on the far
+//! side of it is tied to the rest of the code through that single file. Each cut point is
+//! scored by the files it cuts off — all parts but the largest.
+//!
+//! Each file also gets its dominator count: the files every import chain from an entry
+//! point (`code-graph entrypoints`) passes through it to reach. Rust modules below a crate
+//! root are reached through `mod` declarations rather than imports, so their counts stay
+//! low.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use petgraph::algo::dominators::simple_fast;
+use petgraph::graph::DiGraph;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+
+/// One chokepoint file.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CutPoint {
+    /// Project-relative path.
+    pub file: String,
+    /// Files cut off from the largest remaining part when the file is removed.
+    pub disconnects: usize,
+    /// Sizes of the parts its removal leaves, largest first; empty when it is not an
+    /// articulation file.
+    pub parts: Vec<usize>,
+    /// Files that every import chain from an entry point reaches through it.
+    pub dominates: usize,
+    /// Distinct files importing it.
+    pub fan_in: usize,
+    /// Distinct files it imports.
+    pub fan_out: usize,
+}
+
+/// Result of [`find_cut_points`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CutPointReport {
+    /// Files analysed.
+    pub files: usize,
+    /// Cut points by files cut off, then by files dominated, most first.
+    pub cut_points: Vec<CutPoint>,
+}
+
+/// For every node of the undirected graph `adjacency`, the sizes of the parts its removal
+/// splits its connected component into; empty for nodes that are not articulation points.
+fn articulation_parts(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = adjacency.len();
+    let mut parts = vec![Vec::new(); n];
+    let mut disc = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut size = vec![1; n];
+    let mut timer = 0;
+
+    for root in 0..n {
+        if disc[root] != usize::MAX {
+            continue;
+        }
+        disc[root] = timer;
+        low[root] = timer;
+        timer += 1;
+        let mut members = vec![root];
+        // (node, parent, next neighbor to visit); iterative to survive deep import chains.
+        let mut stack: Vec<(usize, usize, usize)> = vec![(root, usize::MAX, 0)];
+        while let Some(top) = stack.last_mut() {
+            let (v, parent, next) = *top;
+            if let Some(&w) = adjacency[v].get(next) {
+                top.2 += 1;
+                if disc[w] == usize::MAX {
+                    disc[w] = timer;
+                    low[w] = timer;
+                    timer += 1;
+                    members.push(w);
+                    stack.push((w, v, 0));
+                } else if w != parent {
+                    low[v] = low[v].min(disc[w]);
+                }
+                continue;
+            }
+            stack.pop();
+            if parent != usize::MAX {
+                low[parent] = low[parent].min(low[v]);
+                size[parent] += size[v];
+                // Nothing in `v`'s subtree links past `parent`, so removing `parent`
+                // separates it.
+                if low[v] >= disc[parent] {
+                    parts[parent].push(size[v]);
+                }
+            }
+        }
+
+        // The root splits into its DFS subtrees; any other node also leaves the part
+        // holding the root.
+        for &v in &members {
+            if v != root && !parts[v].is_empty() {
+                let separated: usize = parts[v].iter().sum();
+                parts[v].push(members.len() - 1 - separated);
+            }
+        }
+    }
+    for p in &mut parts {
+        if p.len() < 2 {
+            p.clear();
+        }
+        p.sort_unstable_by(|a, b| b.cmp(a));
+    }
+    parts
+}
+
+/// Find the cut points of the import graph between source files, keeping those that cut
+/// off or dominate at least `min_size` files. Test files are left out unless
+/// `include_tests`, since every tested file would otherwise cut off its test.
+pub fn find_cut_points(
+    graph: &CodeGraph,
+    root: &Path,
+    min_size: usize,
+    include_tests: bool,
+) -> CutPointReport {
+    let mut files: Vec<(&Path, NodeIndex)> = graph
+        .file_index
+        .iter()
+        .filter(|(_, idx)| {
+            matches!(&graph.graph[**idx], GraphNode::File(fi)
+                if fi.kind == FileKind::Source && (include_tests || !fi.is_test))
+        })
+        .map(|(path, &idx)| (path.as_path(), idx))
+        .collect();
+    files.sort();
+    let position: HashMap<NodeIndex, usize> = files
+        .iter()
+        .enumerate()
+        .map(|(i, &(_, idx))| (idx, i))
+        .collect();
+
+    let mut imports: Vec<Vec<usize>> = vec![Vec::new(); files.len()];
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); files.len()];
+    for edge in graph.graph.edge_references() {
+        if !matches!(edge.weight(), EdgeKind::ResolvedImport { .. }) {
+            continue;
+        }
+        let (Some(&from), Some(&to)) = (position.get(&edge.source()), position.get(&edge.target()))
+        else {
+            continue;
+        };
+        if from != to {
+            imports[from].push(to);
+            adjacency[from].push(to);
+            adjacency[to].push(from);
+        }
+    }
+    for list in imports.iter_mut().chain(adjacency.iter_mut()) {
+        list.sort_unstable();
+        list.dedup();
+    }
+    let mut importers = vec![0; files.len()];
+    for list in &imports {
+        for &to in list {
+            importers[to] += 1;
+        }
+    }
+
+    let parts = articulation_parts(&adjacency);
+    let dominated = dominated_counts(graph, root, &position, &imports);
+
+    let mut cut_points: Vec<CutPoint> = files
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &(path, _))| {
+            let disconnects = parts[i].iter().skip(1).sum();
+            if disconnects < min_size && dominated[i] < min_size {
+                return None;
+            }
+            Some(CutPoint {
+                file: path
+                    .strip_prefix(root)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                disconnects,
+                parts: parts[i].clone(),
+                dominates: dominated[i],
+                fan_in: importers[i],
+                fan_out: imports[i].len(),
+            })
+        })
+        .collect();
+    cut_points.sort_by(|a, b| {
+        b.disconnects
+            .cmp(&a.disconnects)
+            .then(b.dominates.cmp(&a.dominates))
+            .then_with(|| a.file.cmp(&b.file))
+    });
+
+    CutPointReport {
+        files: files.len(),
+        cut_points,
+    }
+}
+
+/// For every file, the number of other files it dominates in the import graph rooted at
+/// the project's entry points.
+fn dominated_counts(
+    graph: &CodeGraph,
+    root: &Path,
+    position: &HashMap<NodeIndex, usize>,
+    imports: &[Vec<usize>],
+) -> Vec<usize> {
+    let n = imports.len();
+    let mut flow: DiGraph<(), ()> = DiGraph::with_capacity(n + 1, n);
+    let nodes: Vec<_> = (0..=n).map(|_| flow.add_node(())).collect();
+    let start = nodes[n];
+    for entry in crate::query::entrypoints::find_entrypoints(graph, root).entrypoints {
+        if let Some(i) = graph
+            .file_index
+            .get(&root.join(&entry.file))
+            .and_then(|idx| position.get(idx))
+        {
+            flow.add_edge(start, nodes[*i], ());
+        }
+    }
+    for (from, list) in imports.iter().enumerate() {
+        for &to in list {
+            flow.add_edge(nodes[from], nodes[to], ());
+        }
+    }
+
+    let dominators = simple_fast(&flow, start);
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut tops: Vec<usize> = Vec::new();
+    for (i, &node) in nodes[..n].iter().enumerate() {
+        match dominators.immediate_dominator(node) {
+            Some(parent) if parent == start => tops.push(i),
+            Some(parent) => children[parent.index()].push(i),
+            None => {}
+        }
+    }
+    // Subtree sizes of the dominator tree, children before parents.
+    let mut order: Vec<usize> = Vec::new();
+    let mut stack = tops;
+    while let Some(v) = stack.pop() {
+        order.push(v);
+        stack.extend(&children[v]);
+    }
+    let mut counts = vec![0; n];
+    for &v in order.iter().rev() {
+        counts[v] = children[v].iter().map(|&c| counts[c] + 1).sum();
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_articulation_parts() {
+        // 0 - 1 - 2 - 3, with 2 - 4 and a triangle 3 - 5 - 6 - 3; 7 on its own.
+        let edges = [(0, 1), (1, 2), (2, 3), (2, 4), (3, 5), (5, 6), (6, 3)];
+        let mut adjacency = vec![Vec::new(); 8];
+        for (a, b) in edges {
+            adjacency[a].push(b);
+            adjacency[b].push(a);
+        }
+        let parts = articulation_parts(&adjacency);
+        assert!(parts[0].is_empty());
+        assert_eq!(parts[1], vec![5, 1]);
+        assert_eq!(parts[2], vec![3, 2, 1]);
+        assert_eq!(parts[3], vec![4, 2]);
+        assert!(parts[5].is_empty());
+        assert!(parts[7].is_empty());
+    }
+
+    #[test]
+    fn test_find_cut_points_ranks_chokepoints() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let file =
+            |graph: &mut CodeGraph, name: &str| graph.add_file(root.join(name), "typescript");
+        let main = file(&mut graph, "main.ts");
+        let api = file(&mut graph, "api.ts");
+        let db = file(&mut graph, "db.ts");
+        let pool = file(&mut graph, "pool.ts");
+        let sql = file(&mut graph, "sql.ts");
+        let test = file(&mut graph, "api.test.ts");
+        if let GraphNode::File(fi) = &mut graph.graph[test] {
+            fi.is_test = true;
+        }
+        graph.add_resolved_import(main, api, "./api");
+        graph.add_resolved_import(api, db, "./db");
+        graph.add_resolved_import(db, pool, "./pool");
+        graph.add_resolved_import(db, sql, "./sql");
+        graph.add_resolved_import(test, api, "./api");
+
+        let report = find_cut_points(&graph, &root, 1, false);
+        assert_eq!(report.files, 5);
+        let summary: Vec<(&str, usize, usize)> = report
+            .cut_points
+            .iter()
+            .map(|c| (c.file.as_str(), c.disconnects, c.dominates))
+            .collect();
+        // main.ts has no importers, so it is the entry point everything flows from.
+        assert_eq!(
+            summary,
+            vec![("db.ts", 2, 2), ("api.ts", 1, 3), ("main.ts", 0, 4)]
+        );
+        assert_eq!(report.cut_points[0].parts, vec![2, 1, 1]);
+
+        let with_tests = find_cut_points(&graph, &root, 1, true);
+        assert_eq!(with_tests.cut_points[0].file, "api.ts");
+        assert_eq!(with_tests.cut_points[0].parts, vec![3, 1, 1]);
+    }
+}
@@ -1,7 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 
 use crate::graph::{
@@ -9,69 +10,186 @@ use crate::graph::{
     edge::EdgeKind,
     node::{FileInfo, FileKind, GraphNode, SymbolInfo, SymbolKind, SymbolVisibility},
 };
+use crate::query::util::find_containing_file_idx;
 
 // ---------------------------------------------------------------------------
 // Data structures
 // ---------------------------------------------------------------------------
 
+/// Severity tier of a dead code finding, ordered from safest to delete to least certain.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    clap::ValueEnum,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeadTier {
+    /// Non-exported symbol, or TS/JS `private`/`#name` method, with no callers: nothing
+    /// outside its file (or class) can reach it.
+    Private,
+    /// File that no other file imports or references, that is not in a Rust module tree,
+    /// and that is not an entry point.
+    File,
+    /// Exported function or method nothing in the project calls. It may still be
+    /// consumed from outside the project, so review before deleting.
+    Exported,
+}
+
 /// A single unreferenced symbol within a file.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct DeadSymbol {
     pub name: String,
     pub kind: String,
     pub line: usize,
+    /// Last line of the symbol's body (inclusive); equals `line` when unknown.
+    pub line_end: usize,
+    /// Size of the symbol's declaration in bytes; `0` when unknown.
+    pub bytes: usize,
+    /// Rust `#[cfg(...)]` predicate the symbol is compiled under, so feature- and
+    /// platform-gated findings can be reviewed separately.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<String>,
 }
 
 /// Result of dead code analysis.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct DeadCodeResult {
-    /// Files with zero incoming import edges that are not entry points.
+    /// Files with zero incoming import edges that are not entry points ([`DeadTier::File`]).
     pub unreachable_files: Vec<PathBuf>,
-    /// Symbols with zero incoming Calls edges, grouped by file path.
-    /// Each entry is (file_path, vec_of_dead_symbols).
+    /// Non-exported symbols with zero incoming Calls edges, grouped by file path
+    /// ([`DeadTier::Private`]). Each entry is (file_path, vec_of_dead_symbols).
     pub unreferenced_symbols: Vec<(PathBuf, Vec<DeadSymbol>)>,
+    /// Exported functions and methods with no incoming Calls, Extends, Implements,
+    /// Embeds, ImportsSymbol or ExportsAs edges, grouped by file path
+    /// ([`DeadTier::Exported`]). Symbols in unreachable files are left to the file tier.
+    pub unused_exports: Vec<(PathBuf, Vec<DeadSymbol>)>,
+}
+
+impl DeadCodeResult {
+    /// Drop every finding that does not belong to `tier`.
+    pub fn retain_tier(&mut self, tier: DeadTier) {
+        if tier != DeadTier::File {
+            self.unreachable_files.clear();
+        }
+        if tier != DeadTier::Private {
+            self.unreferenced_symbols.clear();
+        }
+        if tier != DeadTier::Exported {
+            self.unused_exports.clear();
+        }
+    }
+
+    /// Drop every symbol finding whose name appears anywhere in the project's files other
+    /// than its own declaration line: as an identifier, in a path, or in a string literal.
+    ///
+    /// The graph misses some uses: a function passed by value (`.map(double)`), a method
+    /// called on an untyped closure parameter, a function named by `#[serde(default =
+    /// "...")]`. What is left has no textual mention at all, so deleting it cannot break
+    /// the build. Methods are matched by their bare name, so `Type::method` stays when any
+    /// other `method` exists.
+    pub fn retain_unnamed_elsewhere(&mut self, graph: &CodeGraph) {
+        let names: HashSet<String> = self
+            .unreferenced_symbols
+            .iter()
+            .chain(&self.unused_exports)
+            .flat_map(|(_, syms)| syms.iter().map(|s| bare_name(&s.name).to_string()))
+            .collect();
+        if names.is_empty() {
+            return;
+        }
+
+        // Every (file, line) each candidate name appears on.
+        let mut mentions: HashMap<&str, HashSet<(&Path, usize)>> = HashMap::new();
+        for path in graph.file_index.keys() {
+            let Ok(source) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            for (i, line) in source.lines().enumerate() {
+                for word in line.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')) {
+                    if let Some(name) = names.get(word) {
+                        mentions
+                            .entry(name.as_str())
+                            .or_default()
+                            .insert((path.as_path(), i + 1));
+                    }
+                }
+            }
+        }
+
+        let unnamed = |groups: &mut Vec<(PathBuf, Vec<DeadSymbol>)>| {
+            for (file, syms) in groups.iter_mut() {
+                syms.retain(|s| {
+                    mentions
+                        .get(bare_name(&s.name))
+                        .is_none_or(|at| at.iter().all(|&m| m == (file.as_path(), s.line)))
+                });
+            }
+            groups.retain(|(_, syms)| !syms.is_empty());
+        };
+        unnamed(&mut self.unreferenced_symbols);
+        unnamed(&mut self.unused_exports);
+    }
+}
+
+/// Last segment of a qualified symbol name: `method` for `Type::method` or `Class.method`.
+fn bare_name(name: &str) -> &str {
+    name.rsplit([':', '.']).next().unwrap_or(name)
 }
 
 // ---------------------------------------------------------------------------
 // Entry-point detection helpers
 // ---------------------------------------------------------------------------
 
-/// Returns true if the symbol should be excluded from dead code results.
+/// Returns true if the symbol is part of its file's exported surface: Pub/PubCrate Rust
+/// symbols and exported symbols in every other language.
+fn is_exported_symbol(sym: &SymbolInfo, file_info: &FileInfo) -> bool {
+    if file_info.language == "rust" {
+        sym.visibility == SymbolVisibility::Pub || sym.visibility == SymbolVisibility::PubCrate
+    } else {
+        sym.is_exported
+    }
+}
+
+/// Returns true if the symbol is live regardless of its references.
 ///
 /// Exclusion rules (ANALYSIS-02):
 /// - Functions named "main"
-/// - Trait implementations (`trait_impl.is_some()`)
-/// - Pub/PubCrate Rust symbols
-/// - Exported TS/JS symbols (`is_exported`)
-/// - Symbols in test files or with "test_" prefix
-fn is_entry_point_symbol(sym: &SymbolInfo, file_info: &FileInfo) -> bool {
+/// - Trait implementations (`trait_impl.is_some()`) and derive-generated methods
+///   (`derived.is_some()`)
+/// - Symbols in test files, with "test_" prefix, or compiled only under `#[cfg(test)]`
+/// - SCSS mixins, functions, and variables, used through `@include` and `$name`
+///   references that the graph does not record
+///
+/// Exported symbols are not excluded here; they are checked against the
+/// [`DeadTier::Exported`] rules instead.
+pub(crate) fn is_entry_point_symbol(sym: &SymbolInfo, file_info: &FileInfo) -> bool {
     // main function
     if sym.name == "main" && matches!(sym.kind, SymbolKind::Function) {
         return true;
     }
 
-    // Trait implementations
-    if sym.trait_impl.is_some() {
+    // Trait implementations, and methods a derive macro generated
+    if sym.trait_impl.is_some() || sym.derived.is_some() {
         return true;
     }
 
-    // Rust: pub or pub(crate) symbols
-    if file_info.language == "rust" {
-        if sym.visibility == SymbolVisibility::Pub || sym.visibility == SymbolVisibility::PubCrate {
-            return true;
-        }
-    } else {
-        // TS/JS: exported symbols
-        if sym.is_exported {
-            return true;
-        }
-    }
-
     // Symbols with test_ prefix
     if sym.name.starts_with("test_") {
         return true;
     }
 
+    // Test-only code (`#[cfg(test)]` items and everything in `mod tests`)
+    if sym.is_cfg_test() {
+        return true;
+    }
+
     // Symbols in test files (checked at the file level, but also here for completeness)
     let path_str = file_info.path.to_string_lossy();
     if path_str.contains("/tests/")
@@ -85,6 +203,10 @@ fn is_entry_point_symbol(sym: &SymbolInfo, file_info: &FileInfo) -> bool {
         return true;
     }
 
+    if file_info.language == "css" {
+        return true;
+    }
+
     false
 }
 
@@ -94,7 +216,10 @@ fn is_entry_point_symbol(sym: &SymbolInfo, file_info: &FileInfo) -> bool {
 /// - Files named main.rs, lib.rs
 /// - Files named index.ts, index.js, index.tsx, index.jsx (barrel entry points)
 /// - Files inside test directories
-fn is_entry_point_file(file_info: &FileInfo) -> bool {
+/// - Shell scripts and Jupyter notebooks, which are run directly rather than imported
+///   (their functions are still checked)
+/// - Stylesheets, which are usually loaded by HTML or bundler config rather than imported
+pub(crate) fn is_entry_point_file(file_info: &FileInfo) -> bool {
     let file_name = file_info
         .path
         .file_name()
@@ -127,6 +252,14 @@ fn is_entry_point_file(file_info: &FileInfo) -> bool {
         return true;
     }
 
+    if file_info.language == "shell" || file_name.ends_with(".ipynb") {
+        return true;
+    }
+
+    if file_info.language == "css" {
+        return true;
+    }
+
     // Non-source files (doc, config, ci, asset, other) are not dead code candidates
     if !matches!(file_info.kind, FileKind::Source) {
         return true;
@@ -139,13 +272,13 @@ fn is_entry_point_file(file_info: &FileInfo) -> bool {
 // Main query function
 // ---------------------------------------------------------------------------
 
-/// Detect dead code: unreachable files and unreferenced symbols.
+/// Detect dead code: unreachable files, unreferenced private symbols and unused exports.
 ///
 /// - `graph`: the code graph to analyze
 /// - `root`: the project root path (used for relative path computation)
 /// - `scope`: optional path scope; if provided, only analyze files under this path
 ///
-/// Returns a `DeadCodeResult` with unreachable files and unreferenced symbols.
+/// Returns a `DeadCodeResult` with one list per [`DeadTier`].
 pub fn find_dead_code(graph: &CodeGraph, root: &Path, scope: Option<&Path>) -> DeadCodeResult {
     // Compute absolute scope path if provided
     let abs_scope: Option<PathBuf> = scope.map(|s| {
@@ -156,8 +289,16 @@ pub fn find_dead_code(graph: &CodeGraph, root: &Path, scope: Option<&Path>) -> D
         }
     });
 
+    // Byte-identical vendored copies are analysed once, through their canonical file;
+    // imports of and calls into a copy count as usage of the canonical file.
+    let copies = crate::query::identical_files::identical_copies(graph);
+    let file_copies = crate::query::identical_files::copies_by_canonical(graph);
+
     // Helper: check if a path is under the scope
     let in_scope = |path: &Path| -> bool {
+        if copies.contains(path) {
+            return false;
+        }
         match &abs_scope {
             None => true,
             Some(scope_path) => path.starts_with(scope_path),
@@ -165,7 +306,8 @@ pub fn find_dead_code(graph: &CodeGraph, root: &Path, scope: Option<&Path>) -> D
     };
 
     // --- Unreachable files ---
-    // A file is unreachable if it has zero incoming ResolvedImport or BarrelReExportAll edges
+    // A file is unreachable if it has zero incoming ResolvedImport or BarrelReExportAll edges,
+    // nothing outside it references its symbols, it is not part of a Rust module tree,
     // AND it is not an entry point file.
     let mut unreachable_files: Vec<PathBuf> = Vec::new();
 
@@ -184,10 +326,15 @@ pub fn find_dead_code(graph: &CodeGraph, root: &Path, scope: Option<&Path>) -> D
             continue;
         }
 
+        // A Rust file in the module tree is compiled through its `mod` declaration (and one
+        // declared under `#[cfg(test)]` is test code), so it is never unreachable.
+        if graph.module_of_file(file_idx).is_some() {
+            continue;
+        }
+
         // Count incoming import edges
-        let importer_count = graph
-            .graph
-            .edges_directed(file_idx, Direction::Incoming)
+        let importer_count = with_copies(file_idx, &file_copies)
+            .flat_map(|idx| graph.graph.edges_directed(idx, Direction::Incoming))
             .filter(|e| {
                 matches!(
                     e.weight(),
@@ -196,7 +343,22 @@ pub fn find_dead_code(graph: &CodeGraph, root: &Path, scope: Option<&Path>) -> D
             })
             .count();
 
-        if importer_count == 0 {
+        // A symbol referenced from another file keeps its file reachable: a path-qualified
+        // call (`super::lock::acquire()`) or a string literal naming it (`[string_refs]`,
+        // reflection-style lookups) reaches the file without importing it.
+        let referenced_elsewhere = || {
+            graph
+                .graph
+                .edges_directed(file_idx, Direction::Outgoing)
+                .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+                .flat_map(|e| graph.graph.edges_directed(e.target(), Direction::Incoming))
+                .filter(|e| is_use(e.weight()))
+                .any(|e| {
+                    find_containing_file_idx(graph, e.source()).unwrap_or(e.source()) != file_idx
+                })
+        };
+
+        if importer_count == 0 && !referenced_elsewhere() {
             unreachable_files.push(file_path.clone());
         }
     }
@@ -233,8 +395,14 @@ pub fn find_dead_code(graph: &CodeGraph, root: &Path, scope: Option<&Path>) -> D
         }
     }
 
+    let unreachable: HashSet<&PathBuf> = unreachable_files.iter().collect();
+    let symbol_copies = symbol_copies(graph, &file_copies);
+    let twins = impl_method_twins(graph, &sym_to_file_idx);
+    let bare_twins: HashSet<NodeIndex> = twins.values().copied().collect();
+
     // Group dead symbols by file path
     let mut dead_by_file: HashMap<PathBuf, Vec<DeadSymbol>> = HashMap::new();
+    let mut unused_exports_by_file: HashMap<PathBuf, Vec<DeadSymbol>> = HashMap::new();
 
     for node_idx in graph.graph.node_indices() {
         let sym = match &graph.graph[node_idx] {
@@ -242,10 +410,20 @@ pub fn find_dead_code(graph: &CodeGraph, root: &Path, scope: Option<&Path>) -> D
             _ => continue,
         };
 
+        // The bare twin of a Rust impl method is checked through the qualified symbol,
+        // which carries the trait it implements.
+        if bare_twins.contains(&node_idx) {
+            continue;
+        }
+
         // Get file info for this symbol (look up on demand instead of cloning).
         let file_idx = match sym_to_file_idx.get(&node_idx) {
             Some(&idx) => idx,
-            None => continue, // orphan symbol, skip
+            // Class members are only checked when nothing outside the class can call them.
+            None => match private_method_file(graph, &sym, node_idx, &sym_to_file_idx) {
+                Some(idx) => idx,
+                None => continue,
+            },
         };
         let file_info = match &graph.graph[file_idx] {
             GraphNode::File(fi) => fi,
@@ -257,46 +435,206 @@ pub fn find_dead_code(graph: &CodeGraph, root: &Path, scope: Option<&Path>) -> D
             continue;
         }
 
-        // Skip entry-point symbols
-        if is_entry_point_symbol(&sym, file_info) {
+        // Skip entry-point symbols, and everything in a `#[cfg(test)]` module file
+        if is_entry_point_symbol(&sym, file_info)
+            || graph.module_of_file(file_idx).is_some_and(|m| m.is_test)
+        {
             continue;
         }
 
-        // Count incoming Calls edges
-        let call_count = graph
-            .graph
-            .edges_directed(node_idx, Direction::Incoming)
-            .filter(|e| matches!(e.weight(), EdgeKind::Calls))
+        // The symbol, its identical copies, and its bare twin: a use of any is a use of it.
+        let declaration = || {
+            with_copies(node_idx, &symbol_copies).chain(
+                twins
+                    .get(&node_idx)
+                    .into_iter()
+                    .flat_map(|&twin| with_copies(twin, &symbol_copies)),
+            )
+        };
+
+        let dead_sym = || DeadSymbol {
+            name: sym.name.clone(),
+            kind: crate::query::find::kind_to_str(&sym.kind).to_string(),
+            line: sym.line,
+            line_end: sym.line_end.max(sym.line),
+            bytes: sym.byte_end.saturating_sub(sym.byte_start),
+            cfg: sym.cfg.clone(),
+        };
+
+        if is_exported_symbol(&sym, file_info) {
+            // Exported tier: only callables, whose usage shows up as edges, in files
+            // that are reachable and not themselves a public entry point.
+            if !matches!(
+                sym.kind,
+                SymbolKind::Function | SymbolKind::Method | SymbolKind::ImplMethod
+            ) || is_entry_point_file(file_info)
+                || unreachable.contains(&file_info.path)
+            {
+                continue;
+            }
+            let used = declaration()
+                .flat_map(|idx| graph.graph.edges_directed(idx, Direction::Incoming))
+                .any(|e| is_use(e.weight()));
+            if !used {
+                unused_exports_by_file
+                    .entry(file_info.path.clone())
+                    .or_default()
+                    .push(dead_sym());
+            }
+            continue;
+        }
+
+        // Count incoming Calls, Renders, and StringRef edges
+        let call_count = declaration()
+            .flat_map(|idx| graph.graph.edges_directed(idx, Direction::Incoming))
+            .filter(|e| {
+                matches!(
+                    e.weight(),
+                    EdgeKind::Calls { .. } | EdgeKind::Renders | EdgeKind::StringRef { .. }
+                )
+            })
             .count();
 
         if call_count == 0 {
-            let dead_sym = DeadSymbol {
-                name: sym.name.clone(),
-                kind: crate::query::find::kind_to_str(&sym.kind).to_string(),
-                line: sym.line,
-            };
             dead_by_file
                 .entry(file_info.path.clone())
                 .or_default()
-                .push(dead_sym);
+                .push(dead_sym());
         }
     }
 
-    // Convert map to sorted vec of (path, symbols)
-    let mut unreferenced_symbols: Vec<(PathBuf, Vec<DeadSymbol>)> =
-        dead_by_file.into_iter().collect();
-    unreferenced_symbols.sort_by(|a, b| a.0.cmp(&b.0));
-    // Sort symbols within each file by line number
-    for (_, syms) in &mut unreferenced_symbols {
-        syms.sort_by_key(|s| s.line);
-    }
-
     DeadCodeResult {
+        unreferenced_symbols: sorted_by_file(dead_by_file),
+        unused_exports: sorted_by_file(unused_exports_by_file),
         unreachable_files,
-        unreferenced_symbols,
     }
 }
 
+/// Whether an incoming edge of `kind` uses its target symbol.
+fn is_use(kind: &EdgeKind) -> bool {
+    matches!(
+        kind,
+        EdgeKind::Calls { .. }
+            | EdgeKind::Renders
+            | EdgeKind::Extends
+            | EdgeKind::Implements
+            | EdgeKind::Embeds
+            | EdgeKind::StringRef { .. }
+            | EdgeKind::ImportsSymbol { .. }
+            | EdgeKind::ExportsAs { .. }
+    )
+}
+
+/// Rust impl methods mapped to their bare twins: the parser reports each impl method both
+/// as `Type::method` and as a `method` function at the same line, and calls resolve to
+/// either one.
+fn impl_method_twins(
+    graph: &CodeGraph,
+    sym_to_file_idx: &HashMap<NodeIndex, NodeIndex>,
+) -> HashMap<NodeIndex, NodeIndex> {
+    let mut bare: HashMap<(NodeIndex, usize, &str), NodeIndex> = HashMap::new();
+    for (&sym_idx, &file_idx) in sym_to_file_idx {
+        if let GraphNode::Symbol(s) = &graph.graph[sym_idx]
+            && s.kind == SymbolKind::Function
+        {
+            bare.insert((file_idx, s.line, s.name.as_str()), sym_idx);
+        }
+    }
+    sym_to_file_idx
+        .iter()
+        .filter_map(|(&sym_idx, &file_idx)| match &graph.graph[sym_idx] {
+            GraphNode::Symbol(s) if s.kind == SymbolKind::ImplMethod => {
+                let method = s.name.rsplit("::").next().unwrap_or(&s.name);
+                let twin = *bare.get(&(file_idx, s.line, method))?;
+                Some((sym_idx, twin))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// `node` followed by the nodes of its identical copies, if any.
+fn with_copies<'a>(
+    node: NodeIndex,
+    copies: &'a HashMap<NodeIndex, Vec<NodeIndex>>,
+) -> impl Iterator<Item = NodeIndex> + 'a {
+    std::iter::once(node).chain(copies.get(&node).into_iter().flatten().copied())
+}
+
+/// Top-level symbols of each canonical file mapped to the same symbols (same name and
+/// line) in its identical copies.
+fn symbol_copies(
+    graph: &CodeGraph,
+    file_copies: &HashMap<NodeIndex, Vec<NodeIndex>>,
+) -> HashMap<NodeIndex, Vec<NodeIndex>> {
+    let contained = |file: NodeIndex| {
+        graph
+            .graph
+            .edges_directed(file, Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+            .filter_map(|e| match &graph.graph[e.target()] {
+                GraphNode::Symbol(s) => Some(((s.name.as_str(), s.line), e.target())),
+                _ => None,
+            })
+    };
+    let mut out: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+    for (&canonical, copies) in file_copies {
+        let in_copies: Vec<HashMap<(&str, usize), NodeIndex>> = copies
+            .iter()
+            .map(|&copy| contained(copy).collect())
+            .collect();
+        for (key, sym) in contained(canonical) {
+            let twins: Vec<NodeIndex> = in_copies
+                .iter()
+                .filter_map(|m| m.get(&key))
+                .copied()
+                .collect();
+            if !twins.is_empty() {
+                out.insert(sym, twins);
+            }
+        }
+    }
+    out
+}
+
+/// File of a TS/JS `private` or `#name` method, found through its class; `None` for any
+/// other child symbol. Such a method is only callable from its own class, so an
+/// uncalled one is dead whether or not the class is exported.
+fn private_method_file(
+    graph: &CodeGraph,
+    sym: &SymbolInfo,
+    sym_idx: petgraph::stable_graph::NodeIndex,
+    sym_to_file_idx: &HashMap<petgraph::stable_graph::NodeIndex, petgraph::stable_graph::NodeIndex>,
+) -> Option<petgraph::stable_graph::NodeIndex> {
+    if sym.kind != SymbolKind::Method || sym.visibility != SymbolVisibility::Private {
+        return None;
+    }
+    let parent = graph
+        .graph
+        .edges_directed(sym_idx, Direction::Outgoing)
+        .find(|e| matches!(e.weight(), EdgeKind::ChildOf))?
+        .target();
+    let file_idx = *sym_to_file_idx.get(&parent)?;
+    match &graph.graph[file_idx] {
+        GraphNode::File(fi)
+            if matches!(fi.language.as_str(), "typescript" | "tsx" | "javascript") =>
+        {
+            Some(file_idx)
+        }
+        _ => None,
+    }
+}
+
+/// Convert a per-file map to a vec sorted by path, with symbols sorted by line number.
+fn sorted_by_file(by_file: HashMap<PathBuf, Vec<DeadSymbol>>) -> Vec<(PathBuf, Vec<DeadSymbol>)> {
+    let mut grouped: Vec<(PathBuf, Vec<DeadSymbol>)> = by_file.into_iter().collect();
+    grouped.sort_by(|a, b| a.0.cmp(&b.0));
+    for (_, syms) in &mut grouped {
+        syms.sort_by_key(|s| s.line);
+    }
+    grouped
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -360,6 +698,10 @@ mod tests {
             a_idx,
             EdgeKind::ResolvedImport {
                 specifier: "./utils".into(),
+                is_type_only: false,
+                count: 1,
+                line: 0,
+                col: 0,
             },
         );
 
@@ -370,6 +712,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_identical_copies_analysed_once() {
+        let mut graph = CodeGraph::new();
+        let root = PathBuf::from("/project");
+        let canonical = root.join("lib/util.rs");
+        let copy = root.join("vendor/x/lib/util.rs");
+        let main = root.join("src/main.rs");
+        let helper = make_symbol(
+            "helper",
+            SymbolKind::Function,
+            SymbolVisibility::Private,
+            false,
+            None,
+            3,
+        );
+        let mut add_copy = |path: &PathBuf| {
+            let idx = graph.add_file(path.clone(), "rust");
+            graph.set_file_source(idx, 20, Some(42));
+            (idx, graph.add_symbol(idx, helper.clone()))
+        };
+        add_copy(&canonical);
+        let (copy_idx, copy_helper) = add_copy(&copy);
+        let main_idx = graph.add_file(main, "rust");
+
+        // Only the vendored copy is imported and called.
+        graph.graph.add_edge(
+            main_idx,
+            copy_idx,
+            EdgeKind::ResolvedImport {
+                specifier: "vendor".into(),
+                is_type_only: false,
+                count: 1,
+                line: 0,
+                col: 0,
+            },
+        );
+        graph.graph.add_edge(
+            main_idx,
+            copy_helper,
+            EdgeKind::Calls {
+                count: 1,
+                lines: vec![],
+            },
+        );
+
+        let result = find_dead_code(&graph, &root, None);
+        assert!(
+            result.unreachable_files.is_empty(),
+            "an import of the copy keeps the canonical file reachable, and the copy is skipped: {:?}",
+            result.unreachable_files
+        );
+        assert!(
+            result.unreferenced_symbols.is_empty(),
+            "a call into the copy counts for the canonical symbol: {:?}",
+            result.unreferenced_symbols
+        );
+    }
+
     #[test]
     fn test_unreferenced_symbol() {
         let mut graph = CodeGraph::new();
@@ -401,6 +801,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_string_ref_keeps_symbol_and_file_alive() {
+        let mut graph = CodeGraph::new();
+        let root = PathBuf::from("/project");
+        let service_path = root.join("src/user_service.ts");
+        let service = graph.add_file(service_path.clone(), "typescript");
+        let app = graph.add_file(root.join("src/app.ts"), "typescript");
+        let class = graph.add_symbol(
+            service,
+            make_symbol(
+                "createUserService",
+                SymbolKind::Function,
+                SymbolVisibility::Private,
+                false,
+                None,
+                1,
+            ),
+        );
+        graph.add_string_ref(app, class, 3);
+
+        let result = find_dead_code(&graph, &root, None);
+        assert!(!result.unreachable_files.contains(&service_path));
+        assert!(
+            result
+                .unreferenced_symbols
+                .iter()
+                .all(|(_, syms)| syms.iter().all(|s| s.name != "createUserService"))
+        );
+    }
+
+    #[test]
+    fn test_ts_private_methods_checked_through_their_class() {
+        let mut graph = CodeGraph::new();
+        let root = PathBuf::from("/project");
+        let file_path = root.join("src/service.ts");
+        let file_idx = graph.add_file(file_path.clone(), "typescript");
+        let class_idx = graph.add_symbol(
+            file_idx,
+            make_symbol(
+                "Service",
+                SymbolKind::Class,
+                SymbolVisibility::Pub,
+                true,
+                None,
+                1,
+            ),
+        );
+        let member = |name: &str, vis: SymbolVisibility, line: usize| {
+            make_symbol(name, SymbolKind::Method, vis, false, None, line)
+        };
+        let used =
+            graph.add_child_symbol(class_idx, member("helper", SymbolVisibility::Private, 2));
+        graph.add_child_symbol(class_idx, member("unused", SymbolVisibility::Private, 3));
+        graph.add_child_symbol(class_idx, member("hook", SymbolVisibility::Protected, 4));
+        graph.add_child_symbol(class_idx, member("run", SymbolVisibility::Pub, 5));
+        graph.add_call_site(file_idx, used, 5);
+
+        let result = find_dead_code(&graph, &root, None);
+        let dead: Vec<&str> = result
+            .unreferenced_symbols
+            .iter()
+            .flat_map(|(_, syms)| syms.iter().map(|s| s.name.as_str()))
+            .collect();
+        assert_eq!(dead, vec!["unused"]);
+    }
+
     #[test]
     fn test_main_function_excluded() {
         let mut graph = CodeGraph::new();
@@ -576,6 +1042,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cfg_test_symbols_excluded_and_gated_symbols_tagged() {
+        let mut graph = CodeGraph::new();
+        let root = PathBuf::from("/project");
+        let file_idx = graph.add_file(root.join("src/helpers.rs"), "rust");
+        let mut fixture = make_symbol(
+            "make_fixture",
+            SymbolKind::Function,
+            SymbolVisibility::Private,
+            false,
+            None,
+            30,
+        );
+        fixture.cfg = Some("test".into());
+        graph.add_symbol(file_idx, fixture);
+        let mut gated = make_symbol(
+            "serve",
+            SymbolKind::Function,
+            SymbolVisibility::Private,
+            false,
+            None,
+            10,
+        );
+        gated.cfg = Some(r#"feature = "web""#.into());
+        graph.add_symbol(file_idx, gated);
+
+        let result = find_dead_code(&graph, &root, None);
+        let dead: Vec<(&str, Option<&str>)> = result
+            .unreferenced_symbols
+            .iter()
+            .flat_map(|(_, syms)| syms.iter().map(|s| (s.name.as_str(), s.cfg.as_deref())))
+            .collect();
+        assert_eq!(dead, vec![("serve", Some(r#"feature = "web""#))]);
+    }
+
     #[test]
     fn test_scope_filter() {
         let mut graph = CodeGraph::new();
@@ -640,4 +1141,60 @@ mod tests {
             "File outside scope should NOT be in unreachable list"
         );
     }
+
+    #[test]
+    fn test_unused_export_tier() {
+        let mut graph = CodeGraph::new();
+        let root = PathBuf::from("/project");
+        let app = graph.add_file(root.join("src/app.ts"), "typescript");
+        let util_path = root.join("src/util.ts");
+        let util = graph.add_file(util_path.clone(), "typescript");
+        graph.add_resolved_import(app, util, "./util");
+
+        let exported = |name: &str, line: usize| SymbolInfo {
+            line_end: line + 4,
+            byte_start: line * 100,
+            byte_end: line * 100 + 80,
+            ..make_symbol(
+                name,
+                SymbolKind::Function,
+                SymbolVisibility::Private,
+                true,
+                None,
+                line,
+            )
+        };
+        let used = graph.add_symbol(util, exported("formatDate", 1));
+        graph.add_symbol(util, exported("legacyFormat", 10));
+        // Imported (possibly under an alias) but only called through the local name.
+        let imported = graph.add_symbol(util, exported("parseDate", 20));
+        graph.add_imports_symbol(app, imported, 0, 0);
+        let caller = graph.add_symbol(
+            app,
+            make_symbol(
+                "render",
+                SymbolKind::Function,
+                SymbolVisibility::Private,
+                true,
+                None,
+                1,
+            ),
+        );
+        graph.add_call_site(caller, used, 1);
+
+        let mut result = find_dead_code(&graph, &root, None);
+        assert_eq!(result.unused_exports.len(), 1);
+        let (path, syms) = &result.unused_exports[0];
+        assert_eq!(path, &util_path);
+        let names: Vec<&str> = syms.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["legacyFormat"]);
+        assert_eq!((syms[0].line, syms[0].line_end), (10, 14));
+        assert_eq!(syms[0].bytes, 80);
+        // The unreachable app.ts belongs to the file tier, not the exported tier.
+        assert!(result.unreachable_files.contains(&root.join("src/app.ts")));
+
+        result.retain_tier(DeadTier::Exported);
+        assert!(result.unreachable_files.is_empty());
+        assert_eq!(result.unused_exports.len(), 1);
+    }
 }
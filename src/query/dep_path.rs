@@ -0,0 +1,337 @@
+//! Shortest dependency paths between two files or symbols (`code-graph path`).
+//!
+//! Answers "why does A depend on B": a breadth-first search from A over import, re-export,
+//! symbol-binding, and call edges, where a file also reaches the symbols it declares (its
+//! functions' calls are its dependencies). Every shortest path is kept, up to a limit.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::{CodeGraph, edge::EdgeKind, node::GraphNode};
+use crate::query::util::{find_containing_file_idx, innermost_symbol_at};
+
+/// One node on a dependency path, with the edge that led to it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct PathHop {
+    /// How the previous hop depends on this one (`imports`, `calls`, `re-exports`,
+    /// `uses`, `binds`, `contains`); `None` for the start of the path.
+    pub via: Option<&'static str>,
+    /// Symbol name, or the project-relative path for a file.
+    pub name: String,
+    /// `file` or the symbol kind.
+    pub kind: String,
+    /// Project-relative path of the file (the defining file for a symbol).
+    pub file: String,
+    /// 1-based definition line of a symbol; `None` for a file.
+    pub line: Option<usize>,
+}
+
+/// Result of `path`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DependencyPaths {
+    pub from: String,
+    pub to: String,
+    /// Shortest paths from `from` to `to`, each starting at a `from` node and ending at
+    /// `to` (or a symbol declared in the `to` file). Empty when `from` does not depend
+    /// on `to`.
+    pub paths: Vec<Vec<PathHop>>,
+}
+
+/// Nodes a file path (absolute or relative to `root`) or exact symbol name stands for.
+fn resolve_endpoint(graph: &CodeGraph, root: &Path, target: &str) -> Result<Vec<NodeIndex>> {
+    let as_path = Path::new(target);
+    let abs = if as_path.is_absolute() {
+        as_path.to_path_buf()
+    } else {
+        root.join(as_path)
+    };
+    if let Some(&file_idx) = graph.file_index.get(&abs) {
+        return Ok(vec![file_idx]);
+    }
+    match graph.symbols_named(target) {
+        Some(defs) if !defs.is_empty() => Ok(defs.clone()),
+        _ => bail!("'{}' is neither an indexed file nor a symbol", target),
+    }
+}
+
+/// Label of a dependency edge, or `None` for edges `path` does not follow.
+fn edge_label(kind: &EdgeKind) -> Option<&'static str> {
+    match kind {
+        EdgeKind::ResolvedImport { .. } => Some("imports"),
+        EdgeKind::BarrelReExportAll | EdgeKind::ReExport { .. } => Some("re-exports"),
+        EdgeKind::RustImport { .. } => Some("uses"),
+        EdgeKind::ImportsSymbol { .. } => Some("binds"),
+        EdgeKind::Calls { .. } => Some("calls"),
+        _ => None,
+    }
+}
+
+/// Dependencies of `node`: its outgoing dependency edges and, for a file or class, the
+/// symbols it declares. A symbol also depends on what its file calls from within its
+/// span (TypeScript, JavaScript, and Python calls hang off the calling file).
+fn dependencies(graph: &CodeGraph, node: NodeIndex) -> Vec<(NodeIndex, &'static str)> {
+    let mut deps: Vec<(NodeIndex, &'static str)> = graph
+        .graph
+        .edges_directed(node, Direction::Outgoing)
+        .filter_map(|e| match e.weight() {
+            EdgeKind::Contains => Some((e.target(), "contains")),
+            kind => edge_label(kind).map(|label| (e.target(), label)),
+        })
+        .collect();
+    deps.extend(
+        graph
+            .graph
+            .edges_directed(node, Direction::Incoming)
+            .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+            .map(|e| (e.source(), "contains")),
+    );
+    if matches!(graph.graph[node], GraphNode::Symbol(_))
+        && let Some(file_idx) = find_containing_file_idx(graph, node)
+    {
+        for edge in graph.graph.edges_directed(file_idx, Direction::Outgoing) {
+            let EdgeKind::Calls { lines, .. } = edge.weight() else {
+                continue;
+            };
+            if lines
+                .iter()
+                .any(|&line| innermost_symbol_at(graph, file_idx, line) == Some(node))
+            {
+                deps.push((edge.target(), "calls"));
+            }
+        }
+    }
+    deps.sort();
+    deps.dedup();
+    deps
+}
+
+/// The `to` nodes plus, for a file, every symbol declared in it.
+fn target_set(graph: &CodeGraph, to: &[NodeIndex]) -> HashSet<NodeIndex> {
+    let mut targets: HashSet<NodeIndex> = to.iter().copied().collect();
+    for &idx in to {
+        if matches!(graph.graph[idx], GraphNode::File(_)) {
+            targets.extend(graph.graph.node_indices().filter(|&n| {
+                matches!(graph.graph[n], GraphNode::Symbol(_))
+                    && find_containing_file_idx(graph, n) == Some(idx)
+            }));
+        }
+    }
+    targets
+}
+
+/// Shortest dependency paths from `from` to `to` (file paths or symbol names), at most
+/// `max_paths` of them.
+pub fn dependency_paths(
+    graph: &CodeGraph,
+    root: &Path,
+    from: &str,
+    to: &str,
+    max_paths: usize,
+) -> Result<DependencyPaths> {
+    let sources = resolve_endpoint(graph, root, from)?;
+    let to_nodes = resolve_endpoint(graph, root, to)?;
+    let targets = target_set(graph, &to_nodes);
+
+    // BFS recording every shortest-path predecessor of each node.
+    let mut depth: HashMap<NodeIndex, usize> = sources.iter().map(|&s| (s, 0)).collect();
+    let mut preds: HashMap<NodeIndex, Vec<(NodeIndex, &'static str)>> = HashMap::new();
+    let mut queue: VecDeque<NodeIndex> = sources.iter().copied().collect();
+    let mut found: Vec<NodeIndex> = Vec::new();
+    let mut found_depth: Option<usize> = None;
+    while let Some(node) = queue.pop_front() {
+        let d = depth[&node];
+        if found_depth.is_some_and(|fd| d >= fd) {
+            break;
+        }
+        for (next, label) in dependencies(graph, node) {
+            match depth.get(&next) {
+                Some(&nd) if nd == d + 1 => {
+                    preds.entry(next).or_default().push((node, label));
+                }
+                Some(_) => {}
+                None => {
+                    depth.insert(next, d + 1);
+                    preds.entry(next).or_default().push((node, label));
+                    if targets.contains(&next) {
+                        found.push(next);
+                        found_depth = Some(d + 1);
+                    } else {
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+    }
+    // A `from` node that is itself a target (A == B) is a zero-hop path.
+    if found.is_empty() {
+        found.extend(sources.iter().copied().filter(|s| targets.contains(s)));
+    }
+    // Reaching the `to` file itself says all there is; the same chain ending in its
+    // symbols would only repeat it.
+    if found.iter().any(|n| to_nodes.contains(n)) {
+        found.retain(|n| to_nodes.contains(n));
+    }
+    found.sort();
+
+    let mut paths: Vec<Vec<PathHop>> = Vec::new();
+    for end in found {
+        let mut stack: Vec<Vec<(NodeIndex, Option<&'static str>)>> = vec![vec![(end, None)]];
+        while let Some(partial) = stack.pop() {
+            if paths.len() >= max_paths {
+                break;
+            }
+            let (head, _) = partial[0];
+            match preds.get(&head) {
+                Some(ps) if depth[&head] > 0 => {
+                    for &(prev, label) in ps.iter().rev() {
+                        let mut longer = vec![(prev, None)];
+                        longer.push((head, Some(label)));
+                        longer.extend(partial[1..].iter().copied());
+                        stack.push(longer);
+                    }
+                }
+                _ => paths.push(
+                    partial
+                        .iter()
+                        .filter_map(|&(idx, via)| hop(graph, root, idx, via))
+                        .collect(),
+                ),
+            }
+        }
+    }
+
+    Ok(DependencyPaths {
+        from: from.to_string(),
+        to: to.to_string(),
+        paths,
+    })
+}
+
+fn hop(
+    graph: &CodeGraph,
+    root: &Path,
+    idx: NodeIndex,
+    via: Option<&'static str>,
+) -> Option<PathHop> {
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    match &graph.graph[idx] {
+        GraphNode::File(fi) => {
+            let file = relative(&fi.path);
+            Some(PathHop {
+                via,
+                name: file.clone(),
+                kind: "file".to_string(),
+                file,
+                line: None,
+            })
+        }
+        GraphNode::Symbol(info) => {
+            let file_idx = find_containing_file_idx(graph, idx)?;
+            let GraphNode::File(fi) = &graph.graph[file_idx] else {
+                return None;
+            };
+            Some(PathHop {
+                via,
+                name: info.name.clone(),
+                kind: crate::query::find::kind_to_str(&info.kind).to_string(),
+                file: relative(&fi.path),
+                line: Some(info.line),
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+
+    fn function(graph: &mut CodeGraph, file: NodeIndex, name: &str) -> NodeIndex {
+        graph.add_symbol(
+            file,
+            SymbolInfo {
+                name: name.into(),
+                kind: SymbolKind::Function,
+                line: 1,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn names(path: &[PathHop]) -> Vec<String> {
+        path.iter()
+            .map(|h| match h.via {
+                Some(via) => format!("{} {}", via, h.name),
+                None => h.name.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shortest_paths_between_files_and_symbols() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(root.join("app.ts"), "typescript");
+        let api = graph.add_file(root.join("api.ts"), "typescript");
+        let client = graph.add_file(root.join("client.ts"), "typescript");
+        let db = graph.add_file(root.join("db.ts"), "typescript");
+        let far = graph.add_file(root.join("far.ts"), "typescript");
+        let query = function(&mut graph, db, "query");
+        graph.add_resolved_import(app, api, "./api");
+        graph.add_resolved_import(app, client, "./client");
+        graph.add_resolved_import(api, db, "./db");
+        graph.add_resolved_import(client, db, "./db");
+        graph.add_resolved_import(app, far, "./far");
+        let handler = function(&mut graph, far, "handler");
+        graph.add_call_site(handler, query, 3);
+        // A file-level call made from inside `report` is attributed to it.
+        graph.add_symbol(
+            far,
+            SymbolInfo {
+                name: "report".into(),
+                kind: SymbolKind::Function,
+                line: 10,
+                line_end: 12,
+                ..Default::default()
+            },
+        );
+        graph.add_call_site(far, query, 11);
+
+        // Two shortest file paths through api and client; the call path is one hop longer.
+        let result = dependency_paths(&graph, &root, "app.ts", "db.ts", 5).unwrap();
+        let found: Vec<Vec<String>> = result.paths.iter().map(|p| names(p)).collect();
+        assert_eq!(
+            found,
+            vec![
+                vec!["app.ts", "imports api.ts", "imports db.ts"],
+                vec!["app.ts", "imports client.ts", "imports db.ts"],
+            ]
+        );
+
+        let result = dependency_paths(&graph, &root, "handler", "query", 5).unwrap();
+        assert_eq!(names(&result.paths[0]), vec!["handler", "calls query"]);
+
+        let result = dependency_paths(&graph, &root, "report", "query", 5).unwrap();
+        assert_eq!(names(&result.paths[0]), vec!["report", "calls query"]);
+
+        let result = dependency_paths(&graph, &root, "far.ts", "db.ts", 1).unwrap();
+        assert_eq!(names(&result.paths[0]), vec!["far.ts", "calls query"]);
+
+        let result = dependency_paths(&graph, &root, "db.ts", "app.ts", 5).unwrap();
+        assert!(result.paths.is_empty());
+        assert!(dependency_paths(&graph, &root, "missing", "db.ts", 5).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Transitive file dependencies (`code-graph deps`) and dependents (`code-graph rdeps`).
+//!
+//! Both walk the resolved import graph with the same breadth-first search `impact` uses,
+//! from a single file: `deps` follows imports outward, `rdeps` follows importers inward.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use petgraph::Direction;
+
+use crate::graph::{CodeGraph, node::GraphNode};
+use crate::query::impact::walk_imports;
+
+/// One file reached from the queried file.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DepFile {
+    /// Project-relative path.
+    pub file: String,
+    /// Import hops from the queried file (1 = imported directly, or importing it directly).
+    pub depth: usize,
+}
+
+/// Result of `deps` / `rdeps`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileDeps {
+    /// The queried file, project-relative.
+    pub file: String,
+    /// Reached files sorted by depth, then path.
+    pub files: Vec<DepFile>,
+}
+
+/// Files transitively imported by `file` (`Direction::Outgoing`) or importing it
+/// (`Direction::Incoming`), up to `max_depth` hops when given. `file` is absolute or
+/// relative to `root`.
+pub fn file_deps(
+    graph: &CodeGraph,
+    root: &Path,
+    file: &Path,
+    direction: Direction,
+    max_depth: Option<usize>,
+    runtime_only: bool,
+) -> Result<FileDeps> {
+    let abs_path: PathBuf = if file.is_absolute() {
+        file.to_path_buf()
+    } else {
+        root.join(file)
+    };
+    let file_idx = graph
+        .file_index
+        .get(&abs_path)
+        .copied()
+        .ok_or_else(|| anyhow!("File not found: {}", file.display()))?;
+
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let depths = walk_imports(
+        graph,
+        &[(file_idx, 0)],
+        direction,
+        max_depth,
+        runtime_only,
+        |_, _| true,
+    );
+    let mut files: Vec<DepFile> = depths
+        .into_iter()
+        .filter(|&(idx, _)| idx != file_idx)
+        .filter_map(|(idx, depth)| match &graph.graph[idx] {
+            GraphNode::File(fi) => Some(DepFile {
+                file: relative(&fi.path),
+                depth,
+            }),
+            _ => None,
+        })
+        .collect();
+    files.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.file.cmp(&b.file)));
+
+    Ok(FileDeps {
+        file: relative(&abs_path),
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deps_and_rdeps_walk_imports_with_depth_limit() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(root.join("app.ts"), "typescript");
+        let api = graph.add_file(root.join("api.ts"), "typescript");
+        let db = graph.add_file(root.join("db.ts"), "typescript");
+        let types = graph.add_file(root.join("types.ts"), "typescript");
+        graph.add_resolved_import(app, api, "./api");
+        graph.add_resolved_import(api, db, "./db");
+        graph.add_resolved_import(app, db, "./db");
+        graph.add_external_package(db, "pg", "pg");
+        graph.add_import_at(db, types, "./types", true, 0, 0);
+
+        let query = |file: &str, direction, depth, runtime_only| {
+            file_deps(
+                &graph,
+                &root,
+                Path::new(file),
+                direction,
+                depth,
+                runtime_only,
+            )
+            .map(|r| {
+                r.files
+                    .into_iter()
+                    .map(|f| (f.file, f.depth))
+                    .collect::<Vec<_>>()
+            })
+        };
+        let deps = query("app.ts", Direction::Outgoing, None, false).unwrap();
+        assert_eq!(
+            deps,
+            vec![
+                ("api.ts".to_string(), 1),
+                ("db.ts".to_string(), 1),
+                ("types.ts".to_string(), 2),
+            ]
+        );
+        let runtime = query("app.ts", Direction::Outgoing, None, true).unwrap();
+        assert_eq!(runtime.len(), 2);
+
+        let rdeps = query("types.ts", Direction::Incoming, Some(1), false).unwrap();
+        assert_eq!(rdeps, vec![("db.ts".to_string(), 1)]);
+        let rdeps = query("types.ts", Direction::Incoming, None, false).unwrap();
+        assert_eq!(rdeps.len(), 3);
+
+        assert!(query("nope.ts", Direction::Outgoing, None, false).is_err());
+    }
+}
@@ -12,6 +12,8 @@ use petgraph::visit::EdgeRef;
 pub const SNAPSHOTS_DIR: &str = "snapshots";
 /// Maximum number of stored snapshots before auto-rotation deletes oldest.
 pub const MAX_SNAPSHOTS: usize = 10;
+/// How long watch loops stay idle before checking git HEAD for per-commit snapshots.
+pub const HEAD_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
 
 // ---------------------------------------------------------------------------
 // Snapshot data types
@@ -26,6 +28,10 @@ pub struct GraphSnapshot {
     pub project_root: String,
     /// Key = relative path from project root.
     pub files: HashMap<String, SnapshotFile>,
+    /// Public API by package, for `api-diff --snapshots`. Only snapshots written by
+    /// `snapshot create` and per-commit capture record it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api: Option<crate::query::api_diff::PublicApi>,
 }
 
 /// Fingerprint of a single file within a snapshot.
@@ -37,6 +43,9 @@ pub struct SnapshotFile {
     /// Number of incoming ResolvedImport/BarrelReExportAll edges to this file.
     pub importer_count: usize,
     pub symbols: Vec<SnapshotSymbol>,
+    /// Hash of the file's source, for rename detection. Absent in older snapshots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<u64>,
 }
 
 /// Fingerprint of a single symbol within a snapshot file.
@@ -59,6 +68,8 @@ pub struct SnapshotSymbol {
 pub struct GraphDiff {
     pub added_files: Vec<String>,
     pub removed_files: Vec<String>,
+    /// Files moved to a new path; reported here instead of as a remove + add pair.
+    pub renamed_files: Vec<FileRename>,
     /// (file, symbol_name)
     pub added_symbols: Vec<(String, String)>,
     /// (file, symbol_name)
@@ -66,6 +77,16 @@ pub struct GraphDiff {
     pub modified_symbols: Vec<SymbolChange>,
 }
 
+/// A file that moved between two snapshots.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct FileRename {
+    pub from: String,
+    pub to: String,
+    /// `identical` when the content hash matches, `same symbols` when only the set of
+    /// (name, kind) symbols does.
+    pub similarity: &'static str,
+}
+
 /// A symbol that changed between two snapshots.
 #[derive(serde::Serialize)]
 pub struct SymbolChange {
@@ -92,60 +113,7 @@ pub fn graph_to_snapshot(graph: &CodeGraph, root: &Path, name: &str) -> GraphSna
         if let GraphNode::File(ref file_info) = graph.graph[idx] {
             // Compute relative path from root
             let rel = file_info.path.strip_prefix(root).unwrap_or(&file_info.path);
-            let rel_str = rel.to_string_lossy().to_string();
-
-            // Count incoming ResolvedImport / BarrelReExportAll edges (importers)
-            let importer_count = graph
-                .graph
-                .edges_directed(idx, Direction::Incoming)
-                .filter(|e| {
-                    matches!(
-                        e.weight(),
-                        EdgeKind::ResolvedImport { .. } | EdgeKind::BarrelReExportAll
-                    )
-                })
-                .count();
-
-            // Count outgoing ResolvedImport edges (imports)
-            let import_count = graph
-                .graph
-                .edges_directed(idx, Direction::Outgoing)
-                .filter(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
-                .count();
-
-            // Collect symbols via outgoing Contains edges
-            let mut symbols: Vec<SnapshotSymbol> = Vec::new();
-            for edge in graph.graph.edges_directed(idx, Direction::Outgoing) {
-                if let EdgeKind::Contains = edge.weight() {
-                    let sym_idx = edge.target();
-                    if let GraphNode::Symbol(ref sym_info) = graph.graph[sym_idx] {
-                        // Count incoming Calls edges
-                        let caller_count = graph
-                            .graph
-                            .edges_directed(sym_idx, Direction::Incoming)
-                            .filter(|e| matches!(e.weight(), EdgeKind::Calls))
-                            .count();
-
-                        symbols.push(SnapshotSymbol {
-                            name: sym_info.name.clone(),
-                            kind: crate::query::find::kind_to_str(&sym_info.kind).to_string(),
-                            line: sym_info.line,
-                            caller_count,
-                        });
-                    }
-                }
-            }
-
-            let symbol_count = symbols.len();
-            files.insert(
-                rel_str,
-                SnapshotFile {
-                    symbol_count,
-                    import_count,
-                    importer_count,
-                    symbols,
-                },
-            );
+            files.insert(rel.to_string_lossy().to_string(), snapshot_file(graph, idx));
         }
     }
 
@@ -154,6 +122,96 @@ pub fn graph_to_snapshot(graph: &CodeGraph, root: &Path, name: &str) -> GraphSna
         created_at,
         project_root: root.to_string_lossy().to_string(),
         files,
+        api: None,
+    }
+}
+
+/// Build a snapshot containing only `path`, for diffing a single incremental update.
+///
+/// The file is always present in the result (with no symbols when it is not in the
+/// graph), so diffing the before/after snapshots of a newly created file reports its
+/// symbols as added. Caller counts are zeroed: the watcher does not re-wire incoming
+/// `Calls` edges from other files, so comparing them would report spurious changes.
+pub fn file_snapshot(graph: &CodeGraph, root: &Path, path: &Path) -> GraphSnapshot {
+    let mut fingerprint = match graph.file_index.get(path) {
+        Some(&idx) => snapshot_file(graph, idx),
+        None => SnapshotFile {
+            symbol_count: 0,
+            import_count: 0,
+            importer_count: 0,
+            symbols: Vec::new(),
+            content_hash: None,
+        },
+    };
+    for sym in &mut fingerprint.symbols {
+        sym.caller_count = 0;
+    }
+
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    GraphSnapshot {
+        name: "__file__".to_string(),
+        created_at: 0,
+        project_root: root.to_string_lossy().to_string(),
+        files: HashMap::from([(rel.to_string_lossy().to_string(), fingerprint)]),
+        api: None,
+    }
+}
+
+/// Fingerprint a single file node: import counts and its symbols.
+fn snapshot_file(graph: &CodeGraph, idx: petgraph::stable_graph::NodeIndex) -> SnapshotFile {
+    // Count incoming ResolvedImport / BarrelReExportAll edges (importers)
+    let importer_count = graph
+        .graph
+        .edges_directed(idx, Direction::Incoming)
+        .filter(|e| {
+            matches!(
+                e.weight(),
+                EdgeKind::ResolvedImport { .. } | EdgeKind::BarrelReExportAll
+            )
+        })
+        .count();
+
+    // Count outgoing ResolvedImport edges (imports)
+    let import_count = graph
+        .graph
+        .edges_directed(idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+        .count();
+
+    // Collect symbols via outgoing Contains edges
+    let mut symbols: Vec<SnapshotSymbol> = Vec::new();
+    for edge in graph.graph.edges_directed(idx, Direction::Outgoing) {
+        if let EdgeKind::Contains = edge.weight() {
+            let sym_idx = edge.target();
+            if let GraphNode::Symbol(ref sym_info) = graph.graph[sym_idx] {
+                // Count incoming Calls edges
+                let caller_count = graph
+                    .graph
+                    .edges_directed(sym_idx, Direction::Incoming)
+                    .filter(|e| matches!(e.weight(), EdgeKind::Calls { .. }))
+                    .count();
+
+                symbols.push(SnapshotSymbol {
+                    name: sym_info.name.clone(),
+                    kind: crate::query::find::kind_to_str(&sym_info.kind).to_string(),
+                    line: sym_info.line,
+                    caller_count,
+                });
+            }
+        }
+    }
+
+    let content_hash = match &graph.graph[idx] {
+        GraphNode::File(fi) => fi.content_hash,
+        _ => None,
+    };
+
+    SnapshotFile {
+        symbol_count: symbols.len(),
+        import_count,
+        importer_count,
+        symbols,
+        content_hash,
     }
 }
 
@@ -213,13 +271,193 @@ pub fn create_snapshot(graph: &CodeGraph, root: &Path, name: &str) -> anyhow::Re
         }
     }
 
-    let snapshot = graph_to_snapshot(graph, root, name);
+    let mut snapshot = graph_to_snapshot(graph, root, name);
+    snapshot.api = Some(crate::query::api_diff::public_api(graph, root));
     let json = serde_json::to_string_pretty(&snapshot)?;
     std::fs::write(snapshot_path(root, name), json)?;
 
     Ok(())
 }
 
+/// Prefix of snapshots captured automatically by `snapshot.auto = "per-commit"`.
+pub const COMMIT_SNAPSHOT_PREFIX: &str = "commit-";
+/// Number of hex digits of the commit hash kept in a per-commit snapshot name.
+const COMMIT_SHA_LEN: usize = 12;
+
+/// Name of the per-commit snapshot for the commit `sha` (full or abbreviated hash).
+pub fn commit_snapshot_name(sha: &str) -> String {
+    let short = &sha[..sha.len().min(COMMIT_SHA_LEN)];
+    format!("{}{}", COMMIT_SNAPSHOT_PREFIX, short)
+}
+
+/// Resolve a git revision (`HEAD`, a branch, a tag, an abbreviated hash) to its full
+/// commit hash. Returns `None` outside a git repository or for unknown revisions.
+pub fn git_rev_parse(root: &Path, rev: &str) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{}^{{commit}}", rev))
+        .current_dir(root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!sha.is_empty()).then_some(sha)
+}
+
+/// Map a `diff --from/--to` argument to a stored snapshot name.
+///
+/// An existing snapshot name wins; otherwise the argument is treated as a git revision
+/// and resolved to its per-commit snapshot, so `--from HEAD~1` or `--from v1.2` work
+/// when `snapshot.auto = "per-commit"` captured that commit. Unresolvable arguments
+/// are returned unchanged so the load error names what the user typed.
+pub fn resolve_snapshot_name(root: &Path, name: &str) -> String {
+    if snapshot_path(root, name).exists() {
+        return name.to_string();
+    }
+    match git_rev_parse(root, name) {
+        Some(sha) if snapshot_path(root, &commit_snapshot_name(&sha)).exists() => {
+            commit_snapshot_name(&sha)
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Captures a snapshot keyed by git HEAD each time HEAD moves
+/// (`snapshot.auto = "per-commit"`).
+///
+/// Watch loops call [`CommitSnapshotter::poll`] while idle, after pending file events
+/// have been applied, so the snapshot reflects the working tree at that commit.
+#[derive(Debug, Default)]
+pub struct CommitSnapshotter {
+    last_head: Option<String>,
+}
+
+impl CommitSnapshotter {
+    /// Check HEAD and, if it changed since the last poll, capture `commit-<sha>` unless a
+    /// snapshot for that commit already exists. Returns the name of a newly written snapshot.
+    pub fn poll(&mut self, graph: &CodeGraph, root: &Path) -> anyhow::Result<Option<String>> {
+        let Some(head) = git_rev_parse(root, "HEAD") else {
+            return Ok(None);
+        };
+        if self.last_head.as_deref() == Some(head.as_str()) {
+            return Ok(None);
+        }
+        self.last_head = Some(head.clone());
+
+        let name = commit_snapshot_name(&head);
+        if snapshot_path(root, &name).exists() {
+            return Ok(None);
+        }
+        create_snapshot(graph, root, &name)?;
+        Ok(Some(name))
+    }
+}
+
+/// A detached `git worktree` checked out into a temporary directory, removed on drop.
+struct TempWorktree {
+    repo: PathBuf,
+    dir: tempfile::TempDir,
+}
+
+impl TempWorktree {
+    fn checkout(root: &Path, sha: &str) -> anyhow::Result<Self> {
+        let dir = tempfile::tempdir()?;
+        let output = std::process::Command::new("git")
+            .args(["worktree", "add", "--detach", "--quiet"])
+            .arg(dir.path().join("tree"))
+            .arg(sha)
+            .current_dir(root)
+            .output()
+            .map_err(|e| anyhow::anyhow!("failed to run git: {}. Ensure git is in PATH.", e))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git worktree add failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(Self {
+            repo: root.to_path_buf(),
+            dir,
+        })
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.path().join("tree")
+    }
+}
+
+impl Drop for TempWorktree {
+    fn drop(&mut self) {
+        let _ = std::process::Command::new("git")
+            .args(["worktree", "remove", "--force"])
+            .arg(self.path())
+            .current_dir(&self.repo)
+            .output();
+    }
+}
+
+/// Index the project as it was at git revision `rev` and pass the graph, together with the
+/// root it was built from, to `f`.
+///
+/// The revision is checked out into a temporary worktree, so the working tree, index, and
+/// graph cache of `root` are left untouched. When `root` is a subdirectory of the
+/// repository, the same subdirectory is indexed at `rev`. The worktree is removed once `f`
+/// returns, so `f` is the place to read any source text it needs.
+pub fn with_revision_graph<T>(
+    root: &Path,
+    rev: &str,
+    verbose: bool,
+    f: impl FnOnce(&CodeGraph, &Path) -> T,
+) -> anyhow::Result<T> {
+    let sha = git_rev_parse(root, rev)
+        .ok_or_else(|| anyhow::anyhow!("'{}' is not a git revision of {}", rev, root.display()))?;
+    let prefix = std::process::Command::new("git")
+        .args(["rev-parse", "--show-prefix"])
+        .current_dir(root)
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default();
+
+    let worktree = TempWorktree::checkout(root, &sha)?;
+    let tree_root = worktree.path().join(prefix);
+    if !tree_root.is_dir() {
+        anyhow::bail!("{} does not exist at revision '{}'", root.display(), rev);
+    }
+    if verbose {
+        eprintln!(
+            "[rev] indexing {} ({})",
+            rev,
+            &sha[..sha.len().min(COMMIT_SHA_LEN)]
+        );
+    }
+    let graph = crate::build_graph(&tree_root, verbose)?;
+    Ok(f(&graph, &tree_root))
+}
+
+/// Build a snapshot of the project as it was at git revision `rev`.
+///
+/// File keys are relative to the indexed directory, so snapshots of different revisions
+/// diff cleanly.
+pub fn revision_snapshot(root: &Path, rev: &str, verbose: bool) -> anyhow::Result<GraphSnapshot> {
+    with_revision_graph(root, rev, verbose, |graph, tree_root| {
+        graph_to_snapshot(graph, tree_root, rev)
+    })
+}
+
+/// Compare the graphs of two git revisions (`code-graph diff-rev <from> <to>`).
+pub fn compute_rev_diff(
+    root: &Path,
+    from: &str,
+    to: &str,
+    verbose: bool,
+) -> anyhow::Result<GraphDiff> {
+    let from_snap = revision_snapshot(root, from, verbose)?;
+    let to_snap = revision_snapshot(root, to, verbose)?;
+    Ok(diff_snapshots(&from_snap, &to_snap))
+}
+
 /// Load a named snapshot from disk.
 pub fn load_snapshot(project_root: &Path, name: &str) -> anyhow::Result<GraphSnapshot> {
     let path = snapshot_path(project_root, name);
@@ -263,7 +501,7 @@ pub fn list_snapshots(project_root: &Path) -> anyhow::Result<Vec<(String, u64)>>
     }
 
     // Sort newest first
-    results.sort_by(|a, b| b.1.cmp(&a.1));
+    results.sort_by_key(|b| std::cmp::Reverse(b.1));
     Ok(results)
 }
 
@@ -279,110 +517,190 @@ pub fn delete_snapshot(project_root: &Path, name: &str) -> anyhow::Result<()> {
 // Diff computation
 // ---------------------------------------------------------------------------
 
-/// Diff two snapshot structs directly, without disk I/O.
-///
-/// This is the core diff logic used by `compute_diff` and available for tests.
-pub(crate) fn diff_snapshots(from: &GraphSnapshot, to: &GraphSnapshot) -> GraphDiff {
-    let from_files = &from.files;
-    let to_files = &to.files;
+/// Sorted (name, kind) pairs of a file's symbols, for matching renamed files.
+fn symbol_set(file: &SnapshotFile) -> Vec<(&str, &str)> {
+    let mut set: Vec<(&str, &str)> = file
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.as_str()))
+        .collect();
+    set.sort_unstable();
+    set
+}
 
-    let mut added_files: Vec<String> = Vec::new();
-    let mut removed_files: Vec<String> = Vec::new();
-    let mut added_symbols: Vec<(String, String)> = Vec::new();
-    let mut removed_symbols: Vec<(String, String)> = Vec::new();
-    let mut modified_symbols: Vec<SymbolChange> = Vec::new();
+/// Pair removed files with added ones holding the same content (same hash) or, failing
+/// that, the same non-empty symbol set. Each file joins at most one pair; among several
+/// candidates the one keeping the file name wins, then the first by path.
+fn detect_renames(
+    from: &GraphSnapshot,
+    to: &GraphSnapshot,
+    removed: &mut Vec<String>,
+    added: &mut Vec<String>,
+) -> Vec<FileRename> {
+    let mut renames: Vec<FileRename> = Vec::new();
+    type Matcher = fn(&SnapshotFile, &SnapshotFile) -> bool;
+    let passes: [(&'static str, Matcher); 2] = [
+        // Every empty file shares a hash, which says nothing about where one moved.
+        ("identical", |a, b| {
+            a.content_hash
+                .is_some_and(|h| h != crate::parser::content_hash(b""))
+                && a.content_hash == b.content_hash
+        }),
+        ("same symbols", |a, b| {
+            !a.symbols.is_empty() && symbol_set(a) == symbol_set(b)
+        }),
+    ];
+    for (similarity, matches) in passes {
+        removed.retain(|old| {
+            let old_file = &from.files[old];
+            let file_name = Path::new(old).file_name();
+            let candidates: Vec<usize> = (0..added.len())
+                .filter(|&i| matches(old_file, &to.files[&added[i]]))
+                .collect();
+            let Some(&pick) = candidates
+                .iter()
+                .find(|&&i| Path::new(&added[i]).file_name() == file_name)
+                .or(candidates.first())
+            else {
+                return true;
+            };
+            renames.push(FileRename {
+                from: old.clone(),
+                to: added.remove(pick),
+                similarity,
+            });
+            false
+        });
+    }
+    renames
+}
 
-    // Files in `to` but not `from` = added
-    for key in to_files.keys() {
-        if !from_files.contains_key(key) {
-            added_files.push(key.clone());
+/// Compare the symbols of one file across two snapshots, reporting changes under `file_key`.
+fn diff_file_symbols(
+    file_key: &str,
+    from_file: &SnapshotFile,
+    to_file: &SnapshotFile,
+    diff: &mut GraphDiff,
+) {
+    // Build maps from symbol name -> snapshot symbol
+    let from_syms: HashMap<&str, &SnapshotSymbol> = from_file
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s))
+        .collect();
+    let to_syms: HashMap<&str, &SnapshotSymbol> = to_file
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s))
+        .collect();
+
+    // Added symbols: in `to` but not `from`
+    for name in to_syms.keys() {
+        if !from_syms.contains_key(name) {
+            diff.added_symbols
+                .push((file_key.to_string(), name.to_string()));
         }
     }
 
-    // Files in `from` but not `to` = removed
-    for key in from_files.keys() {
-        if !to_files.contains_key(key) {
-            removed_files.push(key.clone());
+    // Removed symbols: in `from` but not `to`
+    for name in from_syms.keys() {
+        if !to_syms.contains_key(name) {
+            diff.removed_symbols
+                .push((file_key.to_string(), name.to_string()));
         }
     }
 
-    // Files in both: compare symbols
-    for (file_key, from_file) in from_files {
-        if let Some(to_file) = to_files.get(file_key) {
-            // Build maps from symbol name -> snapshot symbol
-            let from_syms: HashMap<&str, &SnapshotSymbol> = from_file
-                .symbols
-                .iter()
-                .map(|s| (s.name.as_str(), s))
-                .collect();
-            let to_syms: HashMap<&str, &SnapshotSymbol> = to_file
-                .symbols
-                .iter()
-                .map(|s| (s.name.as_str(), s))
-                .collect();
-
-            // Added symbols: in `to` but not `from`
-            for name in to_syms.keys() {
-                if !from_syms.contains_key(name) {
-                    added_symbols.push((file_key.clone(), name.to_string()));
-                }
+    // Modified symbols: in both — check for differences
+    for (name, from_sym) in &from_syms {
+        if let Some(to_sym) = to_syms.get(name) {
+            let mut changes: Vec<String> = Vec::new();
+            if from_sym.kind != to_sym.kind {
+                changes.push(format!("kind {} → {}", from_sym.kind, to_sym.kind));
             }
-
-            // Removed symbols: in `from` but not `to`
-            for name in from_syms.keys() {
-                if !to_syms.contains_key(name) {
-                    removed_symbols.push((file_key.clone(), name.to_string()));
-                }
+            if from_sym.line != to_sym.line {
+                changes.push(format!("line {} → {}", from_sym.line, to_sym.line));
             }
-
-            // Modified symbols: in both — check for differences
-            for (name, from_sym) in &from_syms {
-                if let Some(to_sym) = to_syms.get(name) {
-                    let mut changes: Vec<String> = Vec::new();
-                    if from_sym.kind != to_sym.kind {
-                        changes.push(format!("kind {} → {}", from_sym.kind, to_sym.kind));
-                    }
-                    if from_sym.line != to_sym.line {
-                        changes.push(format!("line {} → {}", from_sym.line, to_sym.line));
-                    }
-                    if from_sym.caller_count != to_sym.caller_count {
-                        changes.push(format!(
-                            "callers {} → {}",
-                            from_sym.caller_count, to_sym.caller_count
-                        ));
-                    }
-                    if !changes.is_empty() {
-                        modified_symbols.push(SymbolChange {
-                            file: file_key.clone(),
-                            name: name.to_string(),
-                            changes,
-                        });
-                    }
-                }
+            if from_sym.caller_count != to_sym.caller_count {
+                changes.push(format!(
+                    "callers {} → {}",
+                    from_sym.caller_count, to_sym.caller_count
+                ));
+            }
+            if !changes.is_empty() {
+                diff.modified_symbols.push(SymbolChange {
+                    file: file_key.to_string(),
+                    name: name.to_string(),
+                    changes,
+                });
             }
         }
     }
+}
 
-    // Sort for deterministic output
+/// Diff two snapshot structs directly, without disk I/O.
+///
+/// This is the core diff logic used by `compute_diff` and available for tests. A file
+/// missing from `to` whose content or symbol set reappears at a new path is reported as
+/// renamed, and its symbols are compared under the new path.
+pub(crate) fn diff_snapshots(from: &GraphSnapshot, to: &GraphSnapshot) -> GraphDiff {
+    let from_files = &from.files;
+    let to_files = &to.files;
+
+    // Files in `to` but not `from` = added
+    let mut added_files: Vec<String> = to_files
+        .keys()
+        .filter(|key| !from_files.contains_key(*key))
+        .cloned()
+        .collect();
+    // Files in `from` but not `to` = removed
+    let mut removed_files: Vec<String> = from_files
+        .keys()
+        .filter(|key| !to_files.contains_key(*key))
+        .cloned()
+        .collect();
     added_files.sort();
     removed_files.sort();
-    added_symbols.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
-    removed_symbols.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
-    modified_symbols.sort_by(|a, b| a.file.cmp(&b.file).then(a.name.cmp(&b.name)));
+    let renamed_files = detect_renames(from, to, &mut removed_files, &mut added_files);
 
-    GraphDiff {
+    let mut diff = GraphDiff {
         added_files,
         removed_files,
-        added_symbols,
-        removed_symbols,
-        modified_symbols,
+        renamed_files,
+        added_symbols: Vec::new(),
+        removed_symbols: Vec::new(),
+        modified_symbols: Vec::new(),
+    };
+
+    // Files in both: compare symbols
+    for (file_key, from_file) in from_files {
+        if let Some(to_file) = to_files.get(file_key) {
+            diff_file_symbols(file_key, from_file, to_file, &mut diff);
+        }
+    }
+    let renames: Vec<(String, String)> = diff
+        .renamed_files
+        .iter()
+        .map(|r| (r.from.clone(), r.to.clone()))
+        .collect();
+    for (old, new) in renames {
+        diff_file_symbols(&new, &from_files[&old], &to_files[&new], &mut diff);
     }
+
+    // Sort for deterministic output
+    diff.added_symbols
+        .sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    diff.removed_symbols
+        .sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    diff.modified_symbols
+        .sort_by(|a, b| a.file.cmp(&b.file).then(a.name.cmp(&b.name)));
+
+    diff
 }
 
 /// Compare two snapshots (or a snapshot against the current live graph).
 ///
-/// - `from`: name of the base snapshot
-/// - `to`: optional name of the target snapshot; if None, uses the live graph
+/// - `from`: name of the base snapshot, or a git revision with a per-commit snapshot
+/// - `to`: optional target, resolved the same way; if None, uses the live graph
 /// - `graph`: the current live graph (used when `to` is None)
 pub fn compute_diff(
     root: &Path,
@@ -390,12 +708,16 @@ pub fn compute_diff(
     to: Option<&str>,
     graph: &CodeGraph,
 ) -> Result<GraphDiff, String> {
-    let from_snap =
-        load_snapshot(root, from).map_err(|e| format!("cannot load snapshot '{}': {}", from, e))?;
+    let from = resolve_snapshot_name(root, from);
+    let from_snap = load_snapshot(root, &from)
+        .map_err(|e| format!("cannot load snapshot '{}': {}", from, e))?;
 
     let to_snap: GraphSnapshot = match to {
-        Some(name) => load_snapshot(root, name)
-            .map_err(|e| format!("cannot load snapshot '{}': {}", name, e))?,
+        Some(name) => {
+            let name = resolve_snapshot_name(root, name);
+            load_snapshot(root, &name)
+                .map_err(|e| format!("cannot load snapshot '{}': {}", name, e))?
+        }
         None => graph_to_snapshot(graph, root, "__live__"),
     };
 
@@ -536,6 +858,7 @@ mod tests {
             created_at: 0,
             project_root: "/tmp".to_string(),
             files,
+            api: None,
         }
     }
 
@@ -545,6 +868,7 @@ mod tests {
             import_count: 0,
             importer_count: 0,
             symbols,
+            content_hash: None,
         }
     }
 
@@ -671,6 +995,127 @@ mod tests {
         assert!(diff.modified_symbols.is_empty());
     }
 
+    #[test]
+    fn test_diff_detects_renames() {
+        let with_hash = |symbols, hash| SnapshotFile {
+            content_hash: Some(hash),
+            ..make_file(symbols)
+        };
+        let from = make_snapshot(
+            "from",
+            HashMap::from([
+                ("src/a/util.rs".to_string(), with_hash(vec![], 7)),
+                (
+                    "src/parse.rs".to_string(),
+                    with_hash(vec![make_sym("parse", "function", 3, 0)], 1),
+                ),
+                ("src/gone.rs".to_string(), with_hash(vec![], 2)),
+            ]),
+        );
+        let to = make_snapshot(
+            "to",
+            HashMap::from([
+                ("src/b/util.rs".to_string(), with_hash(vec![], 7)),
+                (
+                    "src/parser/mod.rs".to_string(),
+                    with_hash(vec![make_sym("parse", "function", 5, 0)], 9),
+                ),
+                ("src/new.rs".to_string(), with_hash(vec![], 3)),
+            ]),
+        );
+
+        let diff = super::diff_snapshots(&from, &to);
+        let renames: Vec<(&str, &str, &str)> = diff
+            .renamed_files
+            .iter()
+            .map(|r| (r.from.as_str(), r.to.as_str(), r.similarity))
+            .collect();
+        assert_eq!(
+            renames,
+            vec![
+                ("src/a/util.rs", "src/b/util.rs", "identical"),
+                ("src/parse.rs", "src/parser/mod.rs", "same symbols"),
+            ]
+        );
+        assert_eq!(diff.added_files, vec!["src/new.rs"]);
+        assert_eq!(diff.removed_files, vec!["src/gone.rs"]);
+        assert!(diff.added_symbols.is_empty() && diff.removed_symbols.is_empty());
+        assert_eq!(diff.modified_symbols.len(), 1);
+        assert_eq!(diff.modified_symbols[0].file, "src/parser/mod.rs");
+        assert_eq!(diff.modified_symbols[0].changes, vec!["line 3 → 5"]);
+    }
+
+    #[test]
+    fn test_file_snapshot_diff_scoped_to_one_file() {
+        use crate::graph::node::{SymbolInfo, SymbolKind};
+
+        let (mut graph, tmp) = build_test_graph();
+        let root = tmp.path();
+        let lib = root.join("src").join("lib.rs");
+
+        // A file not yet in the graph reports all its symbols as added.
+        let new_file = root.join("src").join("new.rs");
+        let before_new = file_snapshot(&graph, root, &new_file);
+        let new_idx = graph.add_file(new_file.clone(), "rust");
+        graph.add_symbol(
+            new_idx,
+            SymbolInfo {
+                name: "fresh".into(),
+                kind: SymbolKind::Function,
+                line: 1,
+                ..Default::default()
+            },
+        );
+        let diff = diff_snapshots(&before_new, &file_snapshot(&graph, root, &new_file));
+        assert!(diff.added_files.is_empty());
+        assert_eq!(
+            diff.added_symbols,
+            vec![("src/new.rs".into(), "fresh".into())]
+        );
+
+        // Simulate an incremental update of lib.rs: `hello` moves, `world` is added.
+        let before = file_snapshot(&graph, root, &lib);
+        assert_eq!(
+            before.files.len(),
+            1,
+            "only the changed file is snapshotted"
+        );
+        graph.remove_file_from_graph(&lib);
+        let lib_idx = graph.add_file(lib.clone(), "rust");
+        for (name, line) in [("hello", 3), ("world", 5)] {
+            graph.add_symbol(
+                lib_idx,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Function,
+                    line,
+                    ..Default::default()
+                },
+            );
+        }
+        // An incoming call lost/gained across the update is not reported as a change.
+        graph.add_call_site(new_idx, graph.symbol_index["hello"][0], 1);
+
+        let diff = diff_snapshots(&before, &file_snapshot(&graph, root, &lib));
+        assert_eq!(
+            diff.added_symbols,
+            vec![("src/lib.rs".into(), "world".into())]
+        );
+        assert!(diff.removed_symbols.is_empty());
+        assert_eq!(diff.modified_symbols.len(), 1);
+        assert_eq!(diff.modified_symbols[0].changes, vec!["line 1 → 3"]);
+
+        // Deleting a file reports its symbols as removed, not the file.
+        let before = file_snapshot(&graph, root, &new_file);
+        graph.remove_file_from_graph(&new_file);
+        let diff = diff_snapshots(&before, &file_snapshot(&graph, root, &new_file));
+        assert!(diff.removed_files.is_empty());
+        assert_eq!(
+            diff.removed_symbols,
+            vec![("src/new.rs".into(), "fresh".into())]
+        );
+    }
+
     #[test]
     fn test_list_snapshots() {
         let (graph, tmp) = build_test_graph();
@@ -707,4 +1152,80 @@ mod tests {
             "deleting nonexistent snapshot should fail"
         );
     }
+
+    #[test]
+    fn test_commit_snapshotter_keys_by_head() {
+        let (graph, tmp) = build_test_graph();
+        let root = tmp.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(root)
+                .output()
+                .expect("git should run");
+            assert!(status.status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q"]);
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "initial"]);
+
+        let mut snapshotter = CommitSnapshotter::default();
+        let name = snapshotter
+            .poll(&graph, root)
+            .unwrap()
+            .expect("first poll captures HEAD");
+        assert!(name.starts_with(COMMIT_SNAPSHOT_PREFIX));
+        assert!(snapshot_path(root, &name).exists());
+        // HEAD has not moved: nothing new is captured.
+        assert_eq!(snapshotter.poll(&graph, root).unwrap(), None);
+
+        // A git revision resolves to its per-commit snapshot; unknown names pass through.
+        assert_eq!(resolve_snapshot_name(root, "HEAD"), name);
+        assert_eq!(resolve_snapshot_name(root, "nope"), "nope");
+        assert!(compute_diff(root, "HEAD", None, &graph).is_ok());
+    }
+
+    #[test]
+    fn test_compute_rev_diff_leaves_working_tree_alone() {
+        let tmp = TempDir::new().expect("tempdir");
+        let root = tmp.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(root)
+                .output()
+                .expect("git should run");
+            assert!(status.status.success(), "git {:?} failed", args);
+        };
+        std::fs::write(root.join("a.ts"), "export function alpha() {}\n").unwrap();
+        git(&["init", "-q"]);
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "first"]);
+        std::fs::write(root.join("b.ts"), "export function beta() {}\n").unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "second"]);
+        // Uncommitted edits must not leak into either revision.
+        std::fs::write(root.join("c.ts"), "export function gamma() {}\n").unwrap();
+
+        let diff = compute_rev_diff(root, "HEAD~1", "HEAD", false).unwrap();
+        assert_eq!(diff.added_files, vec!["b.ts".to_string()]);
+        assert!(diff.removed_files.is_empty());
+        assert!(root.join("c.ts").exists());
+
+        // The temporary worktrees are cleaned up.
+        let list = std::process::Command::new("git")
+            .args(["worktree", "list", "--porcelain"])
+            .current_dir(root)
+            .output()
+            .unwrap();
+        let worktrees = String::from_utf8_lossy(&list.stdout)
+            .lines()
+            .filter(|l| l.starts_with("worktree "))
+            .count();
+        assert_eq!(worktrees, 1);
+
+        assert!(compute_rev_diff(root, "no-such-rev", "HEAD", false).is_err());
+    }
 }
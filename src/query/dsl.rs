@@ -0,0 +1,647 @@
+//! Filter query language over symbols and files (`code-graph query`).
+//!
+//! Expresses filters the other commands have no flag for, evaluated directly against the
+//! graph:
+//!
+//! ```text
+//! query := source [where cond]          source: `symbols` | `files`
+//! cond  := conj (or conj)*
+//! conj  := unary (and unary)*
+//! unary := not unary | ( cond ) | field op value
+//! op    := = | != | < | <= | > | >= | ~ | !~
+//! ```
+//!
+//! Keywords are case-insensitive. Values may be quoted and must be when they contain
+//! spaces, parentheses, or operator characters. `~` and `!~` match a glob pattern
+//! (`file~'src/api/**'`); the ordering operators apply to numeric fields only. Fields
+//! computed from the graph, such as `refs`, are only evaluated for rows the cheaper
+//! conditions before them have not already ruled out.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Result, anyhow, bail};
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileInfo, GraphNode, SymbolInfo, SymbolVisibility};
+use crate::query::find::{FindResult, SYMBOL_KIND_NAMES, kind_to_str, parse_visibility};
+use crate::query::stats::FileStats;
+use crate::query::util::find_containing_file_idx;
+
+/// What a query selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Symbols,
+    Files,
+}
+
+/// A field a condition tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Name,
+    Kind,
+    File,
+    Language,
+    Category,
+    Line,
+    Lines,
+    Exported,
+    Visibility,
+    Refs,
+    Callers,
+    Callees,
+    Test,
+    Symbols,
+    Importers,
+    Imports,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldType {
+    Str,
+    Num,
+    Bool,
+}
+
+/// Fields of `symbols` queries.
+const SYMBOL_FIELDS: &[(&str, Field)] = &[
+    ("name", Field::Name),
+    ("kind", Field::Kind),
+    ("file", Field::File),
+    ("language", Field::Language),
+    ("category", Field::Category),
+    ("line", Field::Line),
+    ("lines", Field::Lines),
+    ("exported", Field::Exported),
+    ("visibility", Field::Visibility),
+    ("refs", Field::Refs),
+    ("callers", Field::Callers),
+    ("callees", Field::Callees),
+    ("test", Field::Test),
+];
+
+/// Fields of `files` queries.
+const FILE_FIELDS: &[(&str, Field)] = &[
+    ("file", Field::File),
+    ("path", Field::File),
+    ("language", Field::Language),
+    ("category", Field::Category),
+    ("lines", Field::Lines),
+    ("symbols", Field::Symbols),
+    ("importers", Field::Importers),
+    ("imports", Field::Imports),
+    ("test", Field::Test),
+];
+
+impl Field {
+    fn lookup(name: &str, source: Source) -> Result<Self> {
+        let fields = match source {
+            Source::Symbols => SYMBOL_FIELDS,
+            Source::Files => FILE_FIELDS,
+        };
+        fields
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, field)| *field)
+            .ok_or_else(|| {
+                let names: Vec<&str> = fields.iter().map(|(n, _)| *n).collect();
+                anyhow!(
+                    "unknown field '{}' (expected one of: {})",
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
+
+    fn ty(self) -> FieldType {
+        match self {
+            Field::Name
+            | Field::Kind
+            | Field::File
+            | Field::Language
+            | Field::Category
+            | Field::Visibility => FieldType::Str,
+            Field::Exported | Field::Test => FieldType::Bool,
+            _ => FieldType::Num,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Match,
+    NotMatch,
+}
+
+/// The value a field is compared with, checked against the field's type.
+#[derive(Debug, Clone)]
+enum Literal {
+    Str(String),
+    Glob(glob::Pattern),
+    Num(usize),
+    Bool(bool),
+}
+
+/// A field's value for one row.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Num(usize),
+    Bool(bool),
+}
+
+/// Compiled condition.
+#[derive(Debug, Clone)]
+enum Cond {
+    All,
+    Cmp(Field, Op, Literal),
+    Not(Box<Cond>),
+    And(Box<Cond>, Box<Cond>),
+    Or(Box<Cond>, Box<Cond>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(Op),
+    Open,
+    Close,
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '=' | '!' | '<' | '>' | '~' => {
+                chars.next();
+                let op = match (c, chars.peek()) {
+                    ('!', Some('=')) => Some(Op::Ne),
+                    ('!', Some('~')) => Some(Op::NotMatch),
+                    ('<', Some('=')) => Some(Op::Le),
+                    ('>', Some('=')) => Some(Op::Ge),
+                    _ => None,
+                };
+                let op = match op {
+                    Some(op) => {
+                        chars.next();
+                        op
+                    }
+                    None => match c {
+                        '=' => Op::Eq,
+                        '<' => Op::Lt,
+                        '>' => Op::Gt,
+                        '~' => Op::Match,
+                        _ => bail!("unexpected '!': expected '!=' or '!~'"),
+                    },
+                };
+                tokens.push(Token::Op(op));
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut word = String::new();
+                let mut closed = false;
+                for ch in chars.by_ref() {
+                    if ch == c {
+                        closed = true;
+                        break;
+                    }
+                    word.push(ch);
+                }
+                if !closed {
+                    bail!("unterminated quote in query");
+                }
+                tokens.push(Token::Quoted(word));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || "()=!<>~'\"".contains(ch) {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    source: Source,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// Consume the keyword `keyword` if it comes next.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn cond(&mut self) -> Result<Cond> {
+        let mut left = self.conj()?;
+        while self.keyword("or") {
+            left = Cond::Or(Box::new(left), Box::new(self.conj()?));
+        }
+        Ok(left)
+    }
+
+    fn conj(&mut self) -> Result<Cond> {
+        let mut left = self.unary()?;
+        while self.keyword("and") {
+            left = Cond::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Cond> {
+        if self.keyword("not") {
+            return Ok(Cond::Not(Box::new(self.unary()?)));
+        }
+        match self.next() {
+            Some(Token::Open) => {
+                let inner = self.cond()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => bail!("expected ')'"),
+                }
+            }
+            Some(Token::Word(name)) => {
+                let field = Field::lookup(&name, self.source)?;
+                let op = match self.next() {
+                    Some(Token::Op(op)) => op,
+                    _ => bail!("expected an operator after '{}'", name),
+                };
+                let value = match self.next() {
+                    Some(Token::Word(value) | Token::Quoted(value)) => value,
+                    _ => bail!("expected a value after '{}'", name),
+                };
+                Ok(Cond::Cmp(field, op, literal(&name, field, op, &value)?))
+            }
+            Some(_) => bail!("expected a condition"),
+            None => bail!("unexpected end of query"),
+        }
+    }
+}
+
+/// Check `op` and `value` against the type of `field`.
+fn literal(name: &str, field: Field, op: Op, value: &str) -> Result<Literal> {
+    match (field.ty(), op) {
+        (FieldType::Str, Op::Match | Op::NotMatch) => glob::Pattern::new(value)
+            .map(Literal::Glob)
+            .map_err(|e| anyhow!("invalid pattern '{}': {}", value, e)),
+        (FieldType::Str, Op::Eq | Op::Ne) if field == Field::Visibility => Ok(Literal::Str(
+            visibility_str(&parse_visibility(value)?).to_string(),
+        )),
+        (FieldType::Str, Op::Eq | Op::Ne)
+            if field == Field::Kind && !SYMBOL_KIND_NAMES.contains(&value) =>
+        {
+            bail!(
+                "unknown kind '{}' (expected one of: {})",
+                value,
+                SYMBOL_KIND_NAMES.join(", ")
+            )
+        }
+        (FieldType::Str, Op::Eq | Op::Ne) => Ok(Literal::Str(value.to_string())),
+        (FieldType::Num, Op::Match | Op::NotMatch) => {
+            bail!("'{}' is numeric: use =, !=, <, <=, > or >=", name)
+        }
+        (FieldType::Num, _) => value
+            .parse()
+            .map(Literal::Num)
+            .map_err(|_| anyhow!("'{}' expects a number, got '{}'", name, value)),
+        (FieldType::Bool, Op::Eq | Op::Ne) => match value.to_ascii_lowercase().as_str() {
+            "true" | "yes" => Ok(Literal::Bool(true)),
+            "false" | "no" => Ok(Literal::Bool(false)),
+            _ => bail!("'{}' expects true or false, got '{}'", name, value),
+        },
+        _ => bail!("'{}' only supports = and != comparisons", name),
+    }
+}
+
+fn parse(query: &str) -> Result<(Source, Cond)> {
+    let mut tokens = tokenize(query)?.into_iter();
+    let source = match tokens.next() {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("symbols") => Source::Symbols,
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("files") => Source::Files,
+        _ => bail!("a query starts with 'symbols' or 'files'"),
+    };
+    let mut parser = Parser {
+        tokens: tokens.collect(),
+        pos: 0,
+        source,
+    };
+    if parser.peek().is_none() {
+        return Ok((source, Cond::All));
+    }
+    if !parser.keyword("where") {
+        bail!("expected 'where' after the source");
+    }
+    let cond = parser.cond()?;
+    if parser.pos < parser.tokens.len() {
+        bail!("unexpected trailing input in query");
+    }
+    Ok((source, cond))
+}
+
+fn eval(cond: &Cond, value: &dyn Fn(Field) -> Value) -> bool {
+    match cond {
+        Cond::All => true,
+        Cond::Not(inner) => !eval(inner, value),
+        Cond::And(a, b) => eval(a, value) && eval(b, value),
+        Cond::Or(a, b) => eval(a, value) || eval(b, value),
+        Cond::Cmp(field, op, literal) => match (value(*field), literal) {
+            (Value::Str(s), Literal::Glob(pattern)) => pattern.matches(&s) == (*op == Op::Match),
+            (Value::Str(s), Literal::Str(lit)) => (s == *lit) == (*op == Op::Eq),
+            (Value::Bool(b), Literal::Bool(lit)) => (b == *lit) == (*op == Op::Eq),
+            (Value::Num(n), Literal::Num(lit)) => match op {
+                Op::Eq => n == *lit,
+                Op::Ne => n != *lit,
+                Op::Lt => n < *lit,
+                Op::Le => n <= *lit,
+                Op::Gt => n > *lit,
+                Op::Ge => n >= *lit,
+                Op::Match | Op::NotMatch => false,
+            },
+            _ => false,
+        },
+    }
+}
+
+fn visibility_str(vis: &SymbolVisibility) -> &'static str {
+    match vis {
+        SymbolVisibility::Pub => "pub",
+        SymbolVisibility::PubCrate => "pub(crate)",
+        SymbolVisibility::Protected => "protected",
+        SymbolVisibility::Private => "private",
+    }
+}
+
+/// Distinct symbols at the other end of `idx`'s `Calls` edges in `direction`.
+fn call_neighbors(graph: &CodeGraph, idx: NodeIndex, direction: Direction) -> usize {
+    graph
+        .graph
+        .edges_directed(idx, direction)
+        .filter(|e| matches!(e.weight(), EdgeKind::Calls { .. }))
+        .map(|e| match direction {
+            Direction::Incoming => e.source(),
+            Direction::Outgoing => e.target(),
+        })
+        .filter(|&other| other != idx)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Result of [`run_query`].
+#[derive(Debug, Clone)]
+pub enum QueryResult {
+    /// Matching symbols, sorted by file and line.
+    Symbols(Vec<FindResult>),
+    /// Matching source files, sorted by path.
+    Files(Vec<FileStats>),
+}
+
+/// Run `query` against the graph of the project at `project_root`.
+pub fn run_query(graph: &CodeGraph, project_root: &Path, query: &str) -> Result<QueryResult> {
+    let (source, cond) = parse(query)?;
+    let rel = |path: &Path| -> String {
+        path.strip_prefix(project_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+
+    match source {
+        Source::Symbols => {
+            let mut results = Vec::new();
+            for idx in graph.graph.node_indices() {
+                let GraphNode::Symbol(info) = &graph.graph[idx] else {
+                    continue;
+                };
+                let Some(GraphNode::File(fi)) =
+                    find_containing_file_idx(graph, idx).map(|f| &graph.graph[f])
+                else {
+                    continue;
+                };
+                let value = |field: Field| symbol_value(graph, project_root, idx, info, fi, field);
+                if !eval(&cond, &value) {
+                    continue;
+                }
+                results.push(FindResult {
+                    symbol_name: info.name.clone(),
+                    kind: info.kind.clone(),
+                    file_path: fi.path.clone(),
+                    line: info.line,
+                    line_end: info.line_end,
+                    col: info.col,
+                    col_end: info.col_end,
+                    byte_start: info.byte_start,
+                    byte_end: info.byte_end,
+                    is_exported: info.is_exported,
+                    is_default: info.is_default,
+                    visibility: info.visibility.clone(),
+                    decorators: info.decorators.clone(),
+                    signature: info.signature.clone(),
+                });
+            }
+            results.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
+            Ok(QueryResult::Symbols(results))
+        }
+        Source::Files => {
+            let mut results: Vec<FileStats> = crate::query::stats::file_stats(graph)
+                .into_iter()
+                .filter(|stats| {
+                    let Some(GraphNode::File(fi)) =
+                        graph.file_index.get(&stats.path).map(|&f| &graph.graph[f])
+                    else {
+                        return false;
+                    };
+                    let value = |field: Field| match field {
+                        Field::File => Value::Str(rel(&fi.path)),
+                        Field::Language => Value::Str(fi.language.clone()),
+                        Field::Category => Value::Str(fi.category.clone().unwrap_or_default()),
+                        Field::Lines => Value::Num(stats.lines),
+                        Field::Symbols => Value::Num(stats.symbols),
+                        Field::Importers => Value::Num(stats.fan_in),
+                        Field::Imports => Value::Num(stats.fan_out),
+                        Field::Test => Value::Bool(fi.is_test),
+                        _ => unreachable!("not a file field: {:?}", field),
+                    };
+                    eval(&cond, &value)
+                })
+                .collect();
+            results.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(QueryResult::Files(results))
+        }
+    }
+}
+
+/// Value of `field` for the symbol `idx`, defined in `fi`.
+fn symbol_value(
+    graph: &CodeGraph,
+    project_root: &Path,
+    idx: NodeIndex,
+    info: &SymbolInfo,
+    fi: &FileInfo,
+    field: Field,
+) -> Value {
+    match field {
+        Field::Name => Value::Str(info.name.clone()),
+        Field::Kind => Value::Str(kind_to_str(&info.kind).to_string()),
+        Field::File => Value::Str(
+            fi.path
+                .strip_prefix(project_root)
+                .unwrap_or(&fi.path)
+                .to_string_lossy()
+                .replace('\\', "/"),
+        ),
+        Field::Language => Value::Str(fi.language.clone()),
+        Field::Category => Value::Str(
+            info.category
+                .clone()
+                .or_else(|| fi.category.clone())
+                .unwrap_or_default(),
+        ),
+        Field::Line => Value::Num(info.line),
+        Field::Lines => Value::Num(info.line_end.max(info.line) - info.line + 1),
+        Field::Exported => Value::Bool(info.is_exported),
+        Field::Visibility => Value::Str(visibility_str(&info.visibility).to_string()),
+        Field::Refs => {
+            Value::Num(crate::query::refs::find_refs(graph, &info.name, &[idx], project_root).len())
+        }
+        Field::Callers => Value::Num(call_neighbors(graph, idx, Direction::Incoming)),
+        Field::Callees => Value::Num(call_neighbors(graph, idx, Direction::Outgoing)),
+        Field::Test => Value::Bool(fi.is_test),
+        Field::Symbols | Field::Importers | Field::Imports => {
+            unreachable!("not a symbol field: {:?}", field)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::SymbolKind;
+    use std::path::PathBuf;
+
+    fn names(result: QueryResult) -> Vec<String> {
+        match result {
+            QueryResult::Symbols(symbols) => symbols.into_iter().map(|s| s.symbol_name).collect(),
+            QueryResult::Files(files) => files
+                .into_iter()
+                .map(|f| f.path.to_string_lossy().into_owned())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_query_symbols_and_files() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let api = graph.add_file(root.join("src/api/users.ts"), "typescript");
+        let util = graph.add_file(root.join("src/util.ts"), "typescript");
+        let function = |name: &str, line: usize, is_exported: bool| SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line,
+            line_end: line + 4,
+            is_exported,
+            ..Default::default()
+        };
+        let list = graph.add_symbol(api, function("listUsers", 1, true));
+        graph.add_symbol(api, function("unusedHandler", 10, true));
+        let clamp = graph.add_symbol(util, function("clamp", 1, true));
+        graph.add_symbol(
+            util,
+            SymbolInfo {
+                name: "Limits".into(),
+                kind: SymbolKind::Interface,
+                line: 8,
+                ..Default::default()
+            },
+        );
+        graph.add_call_site(list, clamp, 2);
+        graph.add_resolved_import(api, util, "../util");
+
+        let run = |query: &str| names(run_query(&graph, &root, query).unwrap());
+        assert_eq!(
+            run("symbols where kind=function and refs=0 and file~'src/api/**'"),
+            ["listUsers", "unusedHandler"]
+        );
+        assert_eq!(run("symbols where callers >= 1"), ["clamp"]);
+        assert_eq!(
+            run("symbols where not (kind = function or name ~ 'L*')"),
+            Vec::<String>::new()
+        );
+        assert_eq!(run("SYMBOLS WHERE lines > 1 AND name != clamp").len(), 2);
+        assert_eq!(run("files where importers=0"), ["/proj/src/api/users.ts"]);
+        assert_eq!(run("files").len(), 2);
+    }
+
+    #[test]
+    fn test_query_errors() {
+        let graph = CodeGraph::new();
+        let root = PathBuf::from("/proj");
+        for (query, error) in [
+            ("functions", "starts with 'symbols' or 'files'"),
+            ("symbols kind=function", "expected 'where'"),
+            ("symbols where size>3", "unknown field 'size'"),
+            ("files where refs=0", "unknown field 'refs'"),
+            ("symbols where refs~1", "'refs' is numeric"),
+            ("symbols where lines>many", "expects a number"),
+            ("symbols where name<b", "only supports = and !="),
+            ("symbols where exported=maybe", "expects true or false"),
+            ("symbols where (kind=function", "expected ')'"),
+            ("symbols where name='x", "unterminated quote"),
+            ("symbols where kind=function name=x", "trailing input"),
+            (
+                "symbols where kind = functoin",
+                "unknown kind 'functoin' (expected one of: function, class,",
+            ),
+        ] {
+            let err = run_query(&graph, &root, query).unwrap_err().to_string();
+            assert!(err.contains(error), "{query}: {err}");
+        }
+    }
+}
@@ -0,0 +1,433 @@
+//! Workspace-level duplicate dependency detection (`code-graph duplicates`).
+//!
+//! Reports two kinds of consolidation candidates:
+//!
+//! - **Utilities**: files with the same name in different packages (as grouped by
+//!   `pkg-query`) that export the same symbol names — parallel copies of one internal
+//!   helper, such as `packages/web/src/utils/format.ts` and `packages/api/src/utils/format.ts`
+//!   both exporting `formatDate`.
+//! - **Dependencies**: one external package declared across the workspace under several
+//!   names (npm `"alias": "npm:real@^1"`, Cargo `package = "real"`), or with several
+//!   version requirements. Manifests are the `package.json` of every npm/yarn/pnpm
+//!   workspace package plus the root, and the `Cargo.toml` of every workspace crate.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+use crate::query::api_diff::is_public;
+use crate::query::pkgql::file_packages;
+use crate::resolver::cargo_features::CrateManifest;
+use crate::resolver::cargo_workspace::discover_workspace_manifests;
+use crate::resolver::workspace::discover_workspace_package_jsons;
+
+/// File stems too generic to suggest a copied utility.
+const GENERIC_STEMS: &[&str] = &["index", "mod", "lib", "main", "types", "__init__"];
+
+/// `package.json` tables holding dependencies.
+pub(crate) const NPM_DEPENDENCY_TABLES: &[&str] =
+    &["dependencies", "devDependencies", "optionalDependencies"];
+
+/// One copy of a duplicated utility file.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UtilityCopy {
+    pub package: String,
+    /// Project-relative path.
+    pub file: String,
+}
+
+/// Same-named files in several packages exporting the same symbols.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DuplicateUtility {
+    /// File stem shared by the copies (`format`).
+    pub name: String,
+    /// Exported symbol names found in more than one copy, sorted.
+    pub symbols: Vec<String>,
+    pub copies: Vec<UtilityCopy>,
+}
+
+/// One declaration of an external dependency in a workspace manifest.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+pub struct DependencyDeclaration {
+    /// Declaring workspace package.
+    pub package: String,
+    /// Name the dependency is declared (and imported) under.
+    pub alias: String,
+    pub version: Option<String>,
+}
+
+/// An external package declared under several names or version requirements.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DuplicateDependency {
+    /// Registry name of the package.
+    pub name: String,
+    /// Distinct names it is declared under, when more than one.
+    pub aliases: Vec<String>,
+    /// Distinct version requirements, when more than one.
+    pub versions: Vec<String>,
+    pub declarations: Vec<DependencyDeclaration>,
+}
+
+/// Everything `duplicates` reports.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct DuplicateReport {
+    pub utilities: Vec<DuplicateUtility>,
+    pub dependencies: Vec<DuplicateDependency>,
+}
+
+/// Find duplicated utilities in `graph` and duplicated dependency declarations in the
+/// manifests under `project_root`.
+pub fn find_duplicates(graph: &CodeGraph, project_root: &Path) -> DuplicateReport {
+    DuplicateReport {
+        utilities: duplicate_utilities(graph, project_root),
+        dependencies: duplicate_dependencies(&manifest_declarations(project_root)),
+    }
+}
+
+/// Group same-stem files of different packages and keep those sharing exported names.
+///
+/// A group is reported when its copies share at least two exported names, or when every
+/// export of every reported copy is shared (single-function helpers).
+fn duplicate_utilities(graph: &CodeGraph, project_root: &Path) -> Vec<DuplicateUtility> {
+    // stem -> (package, relative path, exported names)
+    let mut by_stem: BTreeMap<String, Vec<(String, String, BTreeSet<String>)>> = BTreeMap::new();
+    for (idx, package) in file_packages(graph, project_root) {
+        let GraphNode::File(fi) = &graph.graph[idx] else {
+            continue;
+        };
+        let Some(stem) = fi.path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if GENERIC_STEMS.contains(&stem) {
+            continue;
+        }
+        let exports: BTreeSet<String> = graph
+            .graph
+            .edges(idx)
+            .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+            .filter_map(|e| match &graph.graph[e.target()] {
+                GraphNode::Symbol(sym) if is_public(sym, &fi.language) => Some(sym.name.clone()),
+                _ => None,
+            })
+            .collect();
+        if exports.is_empty() {
+            continue;
+        }
+        let rel = fi.path.strip_prefix(project_root).unwrap_or(&fi.path);
+        by_stem.entry(stem.to_string()).or_default().push((
+            package,
+            rel.to_string_lossy().replace('\\', "/"),
+            exports,
+        ));
+    }
+
+    let mut utilities = Vec::new();
+    for (stem, files) in by_stem {
+        // Exported name -> packages exporting it from a file with this stem.
+        let mut exporters: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for (package, _, exports) in &files {
+            for name in exports {
+                exporters.entry(name).or_default().insert(package);
+            }
+        }
+        let shared: BTreeSet<&str> = exporters
+            .iter()
+            .filter(|(_, packages)| packages.len() > 1)
+            .map(|(name, _)| *name)
+            .collect();
+        if shared.is_empty() {
+            continue;
+        }
+        let mut copies: Vec<(&String, &String, &BTreeSet<String>)> = files
+            .iter()
+            .filter(|(_, _, exports)| exports.iter().any(|n| shared.contains(n.as_str())))
+            .map(|(package, file, exports)| (package, file, exports))
+            .collect();
+        let fully_shared = copies
+            .iter()
+            .all(|(_, _, exports)| exports.iter().all(|n| shared.contains(n.as_str())));
+        if shared.len() < 2 && !fully_shared {
+            continue;
+        }
+        copies.sort();
+        utilities.push(DuplicateUtility {
+            name: stem,
+            symbols: shared.iter().map(|s| s.to_string()).collect(),
+            copies: copies
+                .into_iter()
+                .map(|(package, file, _)| UtilityCopy {
+                    package: package.clone(),
+                    file: file.clone(),
+                })
+                .collect(),
+        });
+    }
+    utilities
+}
+
+/// Split an npm alias spec (`npm:lodash@^4`, `npm:@scope/pkg@1`) into name and version.
+fn npm_alias(spec: &str) -> Option<(&str, Option<&str>)> {
+    let target = spec.strip_prefix("npm:")?;
+    match target.get(1..)?.rfind('@') {
+        Some(at) => Some((&target[..at + 1], Some(&target[at + 2..]))),
+        None => Some((target, None)),
+    }
+}
+
+/// Every external dependency declaration in the workspace manifests, keyed by the
+/// registry name of the package. Workspace-internal (`workspace:`, `file:`, `link:`, path)
+/// dependencies are skipped.
+fn manifest_declarations(project_root: &Path) -> BTreeMap<String, Vec<DependencyDeclaration>> {
+    let mut declarations: BTreeMap<String, Vec<DependencyDeclaration>> = BTreeMap::new();
+
+    let mut package_jsons = discover_workspace_package_jsons(project_root);
+    package_jsons.push(project_root.join("package.json"));
+    for path in package_jsons {
+        let Some(json) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        else {
+            continue;
+        };
+        let package = json["name"].as_str().unwrap_or("(root)").to_string();
+        for table in NPM_DEPENDENCY_TABLES {
+            let Some(deps) = json[*table].as_object() else {
+                continue;
+            };
+            for (alias, spec) in deps {
+                let spec = spec.as_str().unwrap_or_default();
+                if ["workspace:", "file:", "link:"]
+                    .iter()
+                    .any(|p| spec.starts_with(p))
+                {
+                    continue;
+                }
+                let (name, version) = npm_alias(spec).unwrap_or((alias.as_str(), Some(spec)));
+                declarations
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(DependencyDeclaration {
+                        package: package.clone(),
+                        alias: alias.clone(),
+                        version: version.filter(|v| !v.is_empty()).map(str::to_string),
+                    });
+            }
+        }
+    }
+
+    let crates: Vec<CrateManifest> = discover_workspace_manifests(project_root)
+        .iter()
+        .filter_map(|path| CrateManifest::load(path))
+        .collect();
+    let members: BTreeSet<&str> = crates.iter().map(|c| c.name.as_str()).collect();
+    for krate in &crates {
+        let all = krate
+            .dependencies
+            .iter()
+            .chain(&krate.dev_dependencies)
+            .chain(&krate.build_dependencies);
+        for dep in all {
+            let name = dep.package.as_deref().unwrap_or(&dep.name);
+            if members.contains(name.replace('-', "_").as_str()) {
+                continue;
+            }
+            declarations
+                .entry(name.to_string())
+                .or_default()
+                .push(DependencyDeclaration {
+                    package: krate.name.clone(),
+                    alias: dep.name.clone(),
+                    version: dep.version.clone(),
+                });
+        }
+    }
+    declarations
+}
+
+/// Packages declared under more than one name or version requirement.
+fn duplicate_dependencies(
+    declarations: &BTreeMap<String, Vec<DependencyDeclaration>>,
+) -> Vec<DuplicateDependency> {
+    let mut duplicates = Vec::new();
+    for (name, decls) in declarations {
+        let aliases: BTreeSet<&str> = decls.iter().map(|d| d.alias.as_str()).collect();
+        let versions: BTreeSet<&str> = decls.iter().filter_map(|d| d.version.as_deref()).collect();
+        if aliases.len() < 2 && versions.len() < 2 {
+            continue;
+        }
+        let mut declarations = decls.clone();
+        declarations.sort();
+        declarations.dedup();
+        let many = |set: BTreeSet<&str>| {
+            if set.len() > 1 {
+                set.into_iter().map(str::to_string).collect()
+            } else {
+                Vec::new()
+            }
+        };
+        duplicates.push(DuplicateDependency {
+            name: name.clone(),
+            aliases: many(aliases),
+            versions: many(versions),
+            declarations,
+        });
+    }
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn add_crate_file(graph: &mut CodeGraph, path: &str, krate: &str, exports: &[&str]) {
+        let idx = graph.add_file(PathBuf::from("/proj").join(path), "typescript");
+        if let GraphNode::File(fi) = &mut graph.graph[idx] {
+            fi.crate_name = Some(krate.to_string());
+        }
+        for name in exports {
+            graph.add_symbol(
+                idx,
+                SymbolInfo {
+                    name: name.to_string(),
+                    kind: SymbolKind::Function,
+                    is_exported: true,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_duplicate_utilities() {
+        let mut graph = CodeGraph::new();
+        add_crate_file(
+            &mut graph,
+            "web/utils/format.ts",
+            "web",
+            &["formatDate", "parseDate"],
+        );
+        add_crate_file(
+            &mut graph,
+            "api/utils/format.ts",
+            "api",
+            &["formatDate", "parseDate"],
+        );
+        // Same stem, nothing in common.
+        add_crate_file(&mut graph, "cli/format.ts", "cli", &["formatTable"]);
+        // One shared helper, but the file exports more: not reported.
+        add_crate_file(
+            &mut graph,
+            "web/debounce.ts",
+            "web",
+            &["debounce", "throttle"],
+        );
+        add_crate_file(&mut graph, "api/debounce.ts", "api", &["debounce"]);
+        // A single-function helper copied verbatim.
+        add_crate_file(&mut graph, "web/slugify.ts", "web", &["slugify"]);
+        add_crate_file(&mut graph, "api/slugify.ts", "api", &["slugify"]);
+        // Same file in one package twice is not a cross-package duplicate.
+        add_crate_file(&mut graph, "web/a/clamp.ts", "web", &["clamp"]);
+        add_crate_file(&mut graph, "web/b/clamp.ts", "web", &["clamp"]);
+
+        let utilities = duplicate_utilities(&graph, Path::new("/proj"));
+        let summary: Vec<(String, Vec<String>, Vec<String>)> = utilities
+            .into_iter()
+            .map(|u| {
+                (
+                    u.name,
+                    u.symbols,
+                    u.copies.into_iter().map(|c| c.file).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "format".to_string(),
+                    vec!["formatDate".to_string(), "parseDate".to_string()],
+                    vec![
+                        "api/utils/format.ts".to_string(),
+                        "web/utils/format.ts".to_string()
+                    ],
+                ),
+                (
+                    "slugify".to_string(),
+                    vec!["slugify".to_string()],
+                    vec!["api/slugify.ts".to_string(), "web/slugify.ts".to_string()],
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_npm_alias() {
+        assert_eq!(
+            npm_alias("npm:lodash@^4.17.0"),
+            Some(("lodash", Some("^4.17.0")))
+        );
+        assert_eq!(
+            npm_alias("npm:@scope/pkg@1"),
+            Some(("@scope/pkg", Some("1")))
+        );
+        assert_eq!(npm_alias("npm:lodash"), Some(("lodash", None)));
+        assert_eq!(npm_alias("^4.17.0"), None);
+    }
+
+    #[test]
+    fn test_duplicate_dependencies_from_manifests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{ "name": "root", "workspaces": ["packages/*"] }"#,
+        )
+        .unwrap();
+        for (name, deps) in [
+            ("web", r#"{ "lodash": "^4.17.0", "react": "^18.2.0" }"#),
+            (
+                "api",
+                r#"{ "my-lodash": "npm:lodash@^4.17.0", "react": "^18.2.0", "web": "workspace:*" }"#,
+            ),
+            ("admin", r#"{ "react": "^17.0.0" }"#),
+        ] {
+            let dir = root.join("packages").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("package.json"),
+                format!(r#"{{ "name": "{name}", "dependencies": {deps} }}"#),
+            )
+            .unwrap();
+        }
+
+        let duplicates = duplicate_dependencies(&manifest_declarations(root));
+        let summary: Vec<(&str, &[String], &[String])> = duplicates
+            .iter()
+            .map(|d| (d.name.as_str(), d.aliases.as_slice(), d.versions.as_slice()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "lodash",
+                    &["lodash".to_string(), "my-lodash".to_string()][..],
+                    &[][..]
+                ),
+                (
+                    "react",
+                    &[][..],
+                    &["^17.0.0".to_string(), "^18.2.0".to_string()][..]
+                ),
+            ]
+        );
+        assert_eq!(duplicates[0].declarations.len(), 2);
+        assert_eq!(duplicates[1].declarations.len(), 3);
+    }
+}
@@ -0,0 +1,370 @@
+//! Roots of the dependency graph (`code-graph entrypoints`).
+//!
+//! A file is an entry point when a manifest or framework names it, or when nothing
+//! imports it:
+//!
+//! - Cargo: `src/main.rs`, `src/bin/*`, `[[bin]]` paths, `src/lib.rs` (or `[lib] path`),
+//!   `build.rs`, and `examples/*`;
+//! - npm: the `package.json` `main`/`module` fields and every `bin` entry;
+//! - Next.js: `pages/` routes and `app/` `page`/`route`/`layout` files of a project with a
+//!   `next.config.*`;
+//! - Go: files declaring `func main`;
+//! - TS/JS, Python, and shell files without importers.
+//!
+//! Test files are left out. Rust files below a crate root are reached through `mod`
+//! declarations rather than imports, so only the roots themselves are reported.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use petgraph::Direction;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode, SymbolKind};
+use crate::query::impact::walk_imports;
+use crate::resolver::cargo_workspace::discover_workspace_manifests;
+use crate::resolver::workspace::discover_workspace_package_jsons;
+
+/// Languages whose files are imported rather than declared, so a file without importers is
+/// a root.
+const IMPORTED_LANGUAGES: &[&str] = &["typescript", "tsx", "javascript", "python", "shell"];
+
+/// Source extensions a Next.js route file can have.
+const NEXT_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs"];
+
+/// One root of the dependency graph.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EntryPoint {
+    /// Project-relative path.
+    pub file: String,
+    /// Why the file is an entry point, most specific first (`cargo bin`, `package.json
+    /// main`, `next.js page`, `no importers`, ...).
+    pub reasons: Vec<String>,
+    /// Files reachable from it through imports, itself excluded.
+    pub reaches: usize,
+}
+
+/// Result of [`find_entrypoints`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EntrypointsReport {
+    /// Entry points sorted by path.
+    pub entrypoints: Vec<EntryPoint>,
+}
+
+/// Cargo targets of the crate whose manifest is `manifest`, as (file, reason).
+fn cargo_targets(manifest: &Path) -> Vec<(PathBuf, String)> {
+    let Some(dir) = manifest.parent() else {
+        return Vec::new();
+    };
+    let toml: toml::Value = match std::fs::read_to_string(manifest)
+        .ok()
+        .and_then(|text| toml::from_str(&text).ok())
+    {
+        Some(value) => value,
+        None => return Vec::new(),
+    };
+    if toml.get("package").is_none() {
+        return Vec::new();
+    }
+    let mut targets: Vec<(PathBuf, String)> = Vec::new();
+    for bin in toml
+        .get("bin")
+        .and_then(|b| b.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(path) = bin.get("path").and_then(|p| p.as_str()) {
+            targets.push((dir.join(path), "cargo bin".to_string()));
+        }
+    }
+    targets.push((dir.join("src/main.rs"), "cargo bin".to_string()));
+    let listed = |sub: &str| -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir.join(sub))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .map(|path| {
+                if path.is_dir() {
+                    path.join("main.rs")
+                } else {
+                    path
+                }
+            })
+            .collect();
+        paths.sort();
+        paths
+    };
+    targets.extend(
+        listed("src/bin")
+            .into_iter()
+            .map(|p| (p, "cargo bin".to_string())),
+    );
+    let lib = toml
+        .get("lib")
+        .and_then(|l| l.get("path"))
+        .and_then(|p| p.as_str())
+        .unwrap_or("src/lib.rs");
+    targets.push((dir.join(lib), "cargo lib".to_string()));
+    targets.push((dir.join("build.rs"), "cargo build script".to_string()));
+    targets.extend(
+        listed("examples")
+            .into_iter()
+            .map(|p| (p, "cargo example".to_string())),
+    );
+    targets
+}
+
+/// Entry files named by the `package.json` at `manifest`, as (file, reason).
+fn package_json_targets(manifest: &Path) -> Vec<(PathBuf, String)> {
+    let Some(dir) = manifest.parent() else {
+        return Vec::new();
+    };
+    let json: serde_json::Value = match std::fs::read_to_string(manifest)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+    {
+        Some(value) => value,
+        None => return Vec::new(),
+    };
+    let mut targets: Vec<(PathBuf, String)> = Vec::new();
+    let mut push = |entry: &str, reason: &str| {
+        targets.push((dir.join(entry.trim_start_matches("./")), reason.to_string()));
+    };
+    for field in ["main", "module"] {
+        if let Some(entry) = json[field].as_str() {
+            push(entry, &format!("package.json {}", field));
+        }
+    }
+    match &json["bin"] {
+        serde_json::Value::String(entry) => push(entry, "package.json bin"),
+        serde_json::Value::Object(bins) => {
+            for entry in bins.values().filter_map(|v| v.as_str()) {
+                push(entry, "package.json bin");
+            }
+        }
+        _ => {}
+    }
+    targets
+}
+
+/// The Next.js route kind of `rel`, a path relative to a Next.js project directory.
+fn next_route(rel: &Path) -> Option<&'static str> {
+    let ext = rel.extension()?.to_str()?;
+    if !NEXT_EXTENSIONS.contains(&ext) {
+        return None;
+    }
+    let rel = rel.strip_prefix("src").unwrap_or(rel);
+    if let Ok(page) = rel.strip_prefix("pages") {
+        return Some(if page.starts_with("api") {
+            "next.js api route"
+        } else {
+            "next.js page"
+        });
+    }
+    if rel.starts_with("app") {
+        return match rel.file_stem()?.to_str()? {
+            "page" => Some("next.js page"),
+            "route" => Some("next.js api route"),
+            "layout" => Some("next.js layout"),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// List the entry points of the project at `root`.
+pub fn find_entrypoints(graph: &CodeGraph, root: &Path) -> EntrypointsReport {
+    let mut found: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    let mut add = |path: PathBuf, reason: String| {
+        if graph.file_index.contains_key(&path) {
+            let reasons = found.entry(path).or_default();
+            if !reasons.contains(&reason) {
+                reasons.push(reason);
+            }
+        }
+    };
+
+    for manifest in discover_workspace_manifests(root) {
+        for (path, reason) in cargo_targets(&manifest) {
+            add(path, reason);
+        }
+    }
+    let mut package_jsons = discover_workspace_package_jsons(root);
+    package_jsons.push(root.join("package.json"));
+    for manifest in package_jsons {
+        for (path, reason) in package_json_targets(&manifest) {
+            add(path, reason);
+        }
+    }
+
+    let next_dirs: Vec<&Path> = graph
+        .file_index
+        .keys()
+        .filter(|p| {
+            p.file_stem().is_some_and(|s| {
+                s == "next.config" || s.to_string_lossy().starts_with("next.config.")
+            })
+        })
+        .filter_map(|p| p.parent())
+        .collect();
+
+    let mut files: Vec<(&PathBuf, petgraph::stable_graph::NodeIndex)> =
+        graph.file_index.iter().map(|(p, &idx)| (p, idx)).collect();
+    files.sort();
+    for (path, idx) in files {
+        let GraphNode::File(fi) = &graph.graph[idx] else {
+            continue;
+        };
+        if fi.kind != FileKind::Source || fi.is_test {
+            continue;
+        }
+        for dir in &next_dirs {
+            if let Some(route) = path.strip_prefix(dir).ok().and_then(next_route) {
+                add(path.clone(), route.to_string());
+            }
+        }
+        if fi.language == "go" {
+            let declares_main = graph
+                .graph
+                .edges_directed(idx, Direction::Outgoing)
+                .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+                .any(|e| {
+                    matches!(&graph.graph[e.target()], GraphNode::Symbol(s)
+                        if s.name == "main" && s.kind == SymbolKind::Function)
+                });
+            if declares_main {
+                add(path.clone(), "go main".to_string());
+            }
+        }
+        if IMPORTED_LANGUAGES.contains(&fi.language.as_str()) {
+            let imported = graph
+                .graph
+                .edges_directed(idx, Direction::Incoming)
+                .any(|e| {
+                    matches!(
+                        e.weight(),
+                        EdgeKind::ResolvedImport { .. } | EdgeKind::BarrelReExportAll
+                    ) && e.source() != idx
+                });
+            if !imported {
+                add(path.clone(), "no importers".to_string());
+            }
+        }
+    }
+
+    let entrypoints = found
+        .into_iter()
+        .map(|(path, reasons)| {
+            let idx = graph.file_index[&path];
+            let reaches = walk_imports(
+                graph,
+                &[(idx, 0)],
+                Direction::Outgoing,
+                None,
+                false,
+                |_, _| true,
+            )
+            .len()
+                - 1;
+            EntryPoint {
+                file: path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .to_string(),
+                reasons,
+                reaches,
+            }
+        })
+        .collect();
+    EntrypointsReport { entrypoints }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::SymbolInfo;
+    use std::fs;
+
+    #[test]
+    fn test_entrypoints_from_manifests_routes_and_roots() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::create_dir_all(root.join("web/pages/api")).unwrap();
+        fs::create_dir_all(root.join("web/lib")).unwrap();
+        fs::create_dir_all(root.join("tools")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{"main": "./web/lib/index.js", "bin": {"demo": "tools/cli.js"}}"#,
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let mut file = |rel: &str, language: &str| {
+            let path = root.join(rel);
+            fs::write(&path, "").unwrap();
+            graph.add_file(path, language)
+        };
+        file("src/main.rs", "rust");
+        file("src/bin/migrate.rs", "rust");
+        file("src/config.rs", "rust");
+        let index = file("web/lib/index.js", "javascript");
+        let util = file("web/lib/util.js", "javascript");
+        let cli = file("tools/cli.js", "javascript");
+        let page = file("web/pages/about.tsx", "tsx");
+        file("web/pages/api/hello.ts", "typescript");
+        file("web/next.config.js", "javascript");
+        let spec = file("web/lib/util.test.js", "javascript");
+        graph.add_resolved_import(index, util, "./util");
+        graph.add_resolved_import(cli, index, "../web/lib");
+        graph.add_resolved_import(page, util, "../lib/util");
+        graph.add_resolved_import(spec, util, "./util");
+        crate::resolver::tests_resolver::link_tests(&mut graph, root);
+        graph.add_symbol(
+            cli,
+            SymbolInfo {
+                name: "run".into(),
+                kind: SymbolKind::Function,
+                ..Default::default()
+            },
+        );
+
+        let report = find_entrypoints(&graph, root);
+        let summary: Vec<(&str, Vec<&str>, usize)> = report
+            .entrypoints
+            .iter()
+            .map(|e| {
+                (
+                    e.file.as_str(),
+                    e.reasons.iter().map(String::as_str).collect(),
+                    e.reaches,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/bin/migrate.rs", vec!["cargo bin"], 0),
+                ("src/main.rs", vec!["cargo bin"], 0),
+                ("tools/cli.js", vec!["package.json bin", "no importers"], 2),
+                ("web/lib/index.js", vec!["package.json main"], 1),
+                ("web/next.config.js", vec!["no importers"], 0),
+                (
+                    "web/pages/about.tsx",
+                    vec!["next.js page", "no importers"],
+                    1
+                ),
+                (
+                    "web/pages/api/hello.ts",
+                    vec!["next.js api route", "no importers"],
+                    0
+                ),
+            ]
+        );
+    }
+}
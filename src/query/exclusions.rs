@@ -0,0 +1,164 @@
+//! Symbol noise filters (`[symbols] exclude` in `code-graph.toml`).
+//!
+//! Excluded symbols stay in the graph, with their edges, so impact and dependency queries
+//! still see them; they are only dropped from the name and kind indexes, which keeps very
+//! common generated names out of `find`, `refs`, `context` and search results.
+
+use std::collections::HashSet;
+
+use petgraph::stable_graph::NodeIndex;
+
+use crate::graph::CodeGraph;
+use crate::graph::node::{GraphNode, SymbolInfo};
+use crate::query::find::kind_to_str;
+
+/// An exclusion pattern: a name glob, optionally restricted to one kind.
+struct Exclusion {
+    kind: Option<String>,
+    name: glob::Pattern,
+}
+
+impl Exclusion {
+    fn matches(&self, info: &SymbolInfo) -> bool {
+        self.kind
+            .as_deref()
+            .is_none_or(|kind| kind == kind_to_str(&info.kind))
+            && self.name.matches(&info.name)
+    }
+}
+
+/// Split `type:*Props` into its kind and name glob. A `::` (Rust paths such as
+/// `Builder::*`) is part of the name, not a kind prefix.
+fn split_kind(pattern: &str) -> (Option<&str>, &str) {
+    match pattern.split_once(':') {
+        Some((kind, name))
+            if !name.starts_with(':')
+                && !kind.is_empty()
+                && kind.chars().all(|c| c.is_ascii_lowercase() || c == '_') =>
+        {
+            (Some(kind), name)
+        }
+        _ => (None, pattern),
+    }
+}
+
+/// Compile the configured patterns. Invalid globs are reported on stderr and skipped.
+fn compile(patterns: &[String]) -> Vec<Exclusion> {
+    patterns
+        .iter()
+        .filter_map(|p| {
+            let (kind, name) = split_kind(p);
+            match glob::Pattern::new(name) {
+                Ok(name) => Some(Exclusion {
+                    kind: kind.map(str::to_string),
+                    name,
+                }),
+                Err(err) => {
+                    eprintln!("warning: [symbols] exclude: invalid pattern '{p}': {err}");
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Drop the symbols matching `patterns` from the symbol and kind indexes, and restore
+/// any symbol a pattern no longer matches (the indexes are cached with the graph).
+pub fn apply_symbol_exclusions(graph: &mut CodeGraph, patterns: &[String]) {
+    let exclusions = compile(patterns);
+
+    let mut excluded: HashSet<NodeIndex> = HashSet::new();
+    let mut kept: Vec<NodeIndex> = Vec::new();
+    for idx in graph.graph.node_indices() {
+        if let GraphNode::Symbol(info) = &graph.graph[idx] {
+            if exclusions.iter().any(|e| e.matches(info)) {
+                excluded.insert(idx);
+            } else {
+                kept.push(idx);
+            }
+        }
+    }
+
+    graph.symbol_index.retain(|_, indices| {
+        indices.retain(|idx| !excluded.contains(idx));
+        !indices.is_empty()
+    });
+    graph.kind_index.retain(|_, indices| {
+        indices.retain(|idx| !excluded.contains(idx));
+        !indices.is_empty()
+    });
+
+    let named: HashSet<NodeIndex> = graph.symbol_index.values().flatten().copied().collect();
+    let kinded: HashSet<NodeIndex> = graph.kind_index.values().flatten().copied().collect();
+    for idx in kept {
+        let GraphNode::Symbol(info) = &graph.graph[idx] else {
+            continue;
+        };
+        if !named.contains(&idx) {
+            graph
+                .symbol_index
+                .entry(info.name.clone())
+                .or_default()
+                .push(idx);
+        }
+        if !kinded.contains(&idx) {
+            graph
+                .kind_index
+                .entry(info.kind.clone())
+                .or_default()
+                .push(idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::graph::node::SymbolKind;
+
+    #[test]
+    fn test_split_kind() {
+        assert_eq!(split_kind("type:*Props"), (Some("type"), "*Props"));
+        assert_eq!(split_kind("__*"), (None, "__*"));
+        assert_eq!(split_kind("Builder::*"), (None, "Builder::*"));
+    }
+
+    #[test]
+    fn test_exclusions_drop_and_restore_index_entries() {
+        let mut graph = CodeGraph::new();
+        let file = graph.add_file(PathBuf::from("/proj/src/app.tsx"), "typescript");
+        for (name, kind) in [
+            ("ButtonProps", SymbolKind::TypeAlias),
+            ("ButtonProps", SymbolKind::Variable),
+            ("__init", SymbolKind::Function),
+            ("render", SymbolKind::Function),
+        ] {
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: name.into(),
+                    kind,
+                    ..Default::default()
+                },
+            );
+        }
+
+        apply_symbol_exclusions(&mut graph, &["__*".into(), "type:*Props".into()]);
+        assert!(!graph.symbol_index.contains_key("__init"));
+        assert_eq!(
+            graph.symbol_index["ButtonProps"].len(),
+            1,
+            "the variable stays"
+        );
+        assert!(!graph.kind_index.contains_key(&SymbolKind::TypeAlias));
+        assert!(graph.symbol_index.contains_key("render"));
+        assert_eq!(graph.symbol_count(), 4, "the nodes stay in the graph");
+
+        apply_symbol_exclusions(&mut graph, &[]);
+        assert_eq!(graph.symbol_index["ButtonProps"].len(), 2);
+        assert_eq!(graph.symbol_index["__init"].len(), 1);
+        assert_eq!(graph.kind_index[&SymbolKind::Function].len(), 2);
+    }
+}
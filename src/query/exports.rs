@@ -0,0 +1,866 @@
+//! Public surface of a package (`code-graph exports <dir|package>`).
+//!
+//! Starts from the package's entry points — `index.{ts,tsx,js,jsx}` or the `package.json`
+//! `main`/`module`/`types` files, the crate root or `mod.rs`, `__init__.py`, every file of
+//! a Go package — and follows what they expose:
+//!
+//! - exported (`pub`) top-level symbols of each surface file;
+//! - `export * from` barrels (`BarrelReExportAll` edges);
+//! - named re-exports (`export { X } from './x'`, `from .x import X` in `__init__.py`);
+//! - Rust `pub use` items and globs, and the files of `pub mod` children.
+//!
+//! Each exported item is counted against the files outside the package that use it: a
+//! named binding, a call into it or its members, a JSX render, or a file-level import
+//! (namespace, Rust `use`) of any file that exposes it.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+use crate::parser::imports::ExportKind;
+use crate::query::api_diff::{declaration_header, is_public};
+use crate::query::find::kind_to_str;
+use crate::query::util::{dedup_impl_method_twins, find_containing_file_idx};
+use crate::resolver::bindings::find_bound_symbol;
+use crate::resolver::rust_resolver::expand_use_tree;
+
+/// Entry file names looked up in a package directory and its `src/`.
+const ENTRY_NAMES: &[&str] = &[
+    "index.ts",
+    "index.tsx",
+    "index.js",
+    "index.jsx",
+    "index.mjs",
+    "lib.rs",
+    "main.rs",
+    "mod.rs",
+    "__init__.py",
+];
+
+/// One item of the package's public surface.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExportedItem {
+    pub name: String,
+    pub kind: String,
+    /// Project-relative path of the file that declares the item.
+    pub file: String,
+    /// 1-based declaration line.
+    pub line: usize,
+    /// Entry or barrel file that re-exports the item, when it is declared elsewhere.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+    /// Project-relative paths of the files outside the package that use the item.
+    pub consumers: Vec<String>,
+}
+
+/// Result of `exports`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExportsReport {
+    pub target: String,
+    /// Project-relative entry files the surface was collected from.
+    pub entry_files: Vec<String>,
+    pub exports: Vec<ExportedItem>,
+}
+
+/// One item of a package's consumable API, with its declaration header.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ApiItem {
+    /// Symbol name, qualified by its parent for members (`Server.listen`).
+    pub name: String,
+    pub kind: String,
+    /// Declaration header with whitespace collapsed, e.g. `pub fn parse(src: &str) -> Ast`.
+    pub signature: String,
+    /// Project-relative path of the file that declares the item.
+    pub file: String,
+    /// 1-based declaration line.
+    pub line: usize,
+    /// Entry or barrel file that re-exports the item, when it is declared elsewhere.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+}
+
+/// Result of `api`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ApiReport {
+    pub target: String,
+    /// Project-relative entry files the surface was collected from.
+    pub entry_files: Vec<String>,
+    /// Items sorted by file and line, members after their parent.
+    pub items: Vec<ApiItem>,
+}
+
+/// Exposed symbol -> (files exposing it, first re-exporting file it was reached through).
+type Exposed = HashMap<NodeIndex, (HashSet<NodeIndex>, Option<NodeIndex>)>;
+
+/// The files of the target package and where its surface starts.
+struct Package {
+    files: HashSet<NodeIndex>,
+    entries: Vec<NodeIndex>,
+}
+
+fn rel_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Indexed source files under `dir`.
+fn files_under(graph: &CodeGraph, dir: &Path) -> HashSet<NodeIndex> {
+    graph
+        .file_index
+        .iter()
+        .filter(|(path, _)| path.starts_with(dir))
+        .filter(|&(_, &idx)| matches!(&graph.graph[idx], GraphNode::File(fi) if fi.kind == FileKind::Source))
+        .map(|(_, &idx)| idx)
+        .collect()
+}
+
+/// Entry files of a package directory: the `package.json` entry fields, the conventional
+/// entry names in the directory and its `src/`, or every non-test file of a Go package.
+fn entry_files(graph: &CodeGraph, dir: &Path) -> Vec<NodeIndex> {
+    let mut entries: Vec<NodeIndex> = Vec::new();
+    let push = |path: PathBuf, entries: &mut Vec<NodeIndex>| {
+        if let Some(&idx) = graph.file_index.get(&path)
+            && !entries.contains(&idx)
+        {
+            entries.push(idx);
+        }
+    };
+
+    if let Ok(content) = std::fs::read_to_string(dir.join("package.json"))
+        && let Ok(json) = serde_json::from_str::<serde_json::Value>(&content)
+    {
+        for field in ["main", "module", "types"] {
+            if let Some(entry) = json[field].as_str() {
+                push(dir.join(entry.trim_start_matches("./")), &mut entries);
+            }
+        }
+    }
+    for base in [dir.to_path_buf(), dir.join("src")] {
+        for name in ENTRY_NAMES {
+            push(base.join(name), &mut entries);
+        }
+    }
+    if entries.is_empty() {
+        let mut go_files: Vec<&PathBuf> = graph
+            .file_index
+            .keys()
+            .filter(|p| p.parent() == Some(dir))
+            .filter(|p| p.extension().is_some_and(|e| e == "go"))
+            .filter(|p| !p.to_string_lossy().ends_with("_test.go"))
+            .collect();
+        go_files.sort();
+        for path in go_files {
+            push(path.clone(), &mut entries);
+        }
+    }
+    entries
+}
+
+/// Resolve `target` as a workspace package name, a Cargo crate name, or a directory
+/// relative to `root`.
+fn resolve_package(graph: &CodeGraph, root: &Path, target: &str) -> Result<Package> {
+    let workspace = crate::resolver::workspace::discover_workspace_packages(root);
+    if let Some(src_dir) = workspace.get(target) {
+        let dir = if src_dir.ends_with("src") {
+            src_dir.parent().unwrap_or(src_dir)
+        } else {
+            src_dir.as_path()
+        };
+        return Ok(Package {
+            files: files_under(graph, dir),
+            entries: entry_files(graph, dir),
+        });
+    }
+
+    let crate_name = target.replace('-', "_");
+    let crate_files: HashSet<NodeIndex> = graph
+        .file_index
+        .values()
+        .copied()
+        .filter(|&idx| {
+            matches!(&graph.graph[idx], GraphNode::File(fi)
+                if fi.crate_name.as_deref() == Some(crate_name.as_str()))
+        })
+        .collect();
+    if !crate_files.is_empty() {
+        // The crate root is the lib.rs (else main.rs) next to the crate's Cargo.toml.
+        let mut roots: Vec<(bool, NodeIndex)> = crate_files
+            .iter()
+            .filter_map(|&idx| {
+                let GraphNode::File(fi) = &graph.graph[idx] else {
+                    return None;
+                };
+                let name = fi.path.file_name()?.to_str()?;
+                let src = fi.path.parent()?;
+                let has_manifest = src.parent()?.join("Cargo.toml").exists();
+                match name {
+                    "lib.rs" if has_manifest => Some((false, idx)),
+                    "main.rs" if has_manifest => Some((true, idx)),
+                    _ => None,
+                }
+            })
+            .collect();
+        roots.sort();
+        return Ok(Package {
+            files: crate_files,
+            entries: roots.into_iter().take(1).map(|(_, idx)| idx).collect(),
+        });
+    }
+
+    let dir = root.join(target);
+    if dir.is_dir() {
+        let dir = dir.canonicalize().unwrap_or(dir);
+        return Ok(Package {
+            files: files_under(graph, &dir),
+            entries: entry_files(graph, &dir),
+        });
+    }
+    bail!(
+        "'{}' is neither a workspace package, a crate, nor a directory",
+        target
+    )
+}
+
+/// Public top-level symbols of `file_idx`.
+fn public_symbols(graph: &CodeGraph, file_idx: NodeIndex) -> Vec<NodeIndex> {
+    let GraphNode::File(fi) = &graph.graph[file_idx] else {
+        return Vec::new();
+    };
+    graph
+        .graph
+        .edges_directed(file_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .map(|e| e.target())
+        .filter(|&idx| {
+            !graph
+                .graph
+                .edges_directed(idx, Direction::Outgoing)
+                .any(|e| matches!(e.weight(), EdgeKind::ChildOf))
+        })
+        .filter(
+            |&idx| matches!(&graph.graph[idx], GraphNode::Symbol(s) if is_public(s, &fi.language)),
+        )
+        .collect()
+}
+
+/// Extensions tried, in order, when resolving a relative re-export source.
+const RELATIVE_SUFFIXES: &[&str] = &[
+    "",
+    ".ts",
+    ".tsx",
+    ".js",
+    ".jsx",
+    ".mjs",
+    "/index.ts",
+    "/index.tsx",
+    "/index.js",
+    "/index.jsx",
+];
+
+/// Files `file_idx` imports with `specifier`. A relative specifier without an import edge
+/// (a named re-export the importer side bypasses) is looked up on disk; any other
+/// specifier falls back to every file `file_idx` imports.
+fn import_targets(graph: &CodeGraph, file_idx: NodeIndex, specifier: &str) -> Vec<NodeIndex> {
+    let mut exact = Vec::new();
+    let mut all = Vec::new();
+    for edge in graph.graph.edges_directed(file_idx, Direction::Outgoing) {
+        if let EdgeKind::ResolvedImport { specifier: s, .. } = edge.weight()
+            && edge.target() != file_idx
+            && matches!(graph.graph[edge.target()], GraphNode::File(_))
+        {
+            if s == specifier {
+                exact.push(edge.target());
+            }
+            all.push(edge.target());
+        }
+    }
+    if !exact.is_empty() {
+        return exact;
+    }
+    if specifier.starts_with("./") || specifier.starts_with("../") {
+        let GraphNode::File(fi) = &graph.graph[file_idx] else {
+            return all;
+        };
+        let Some(dir) = fi.path.parent() else {
+            return all;
+        };
+        let base = dir.join(specifier);
+        let base = base.to_string_lossy();
+        return RELATIVE_SUFFIXES
+            .iter()
+            .filter_map(|suffix| {
+                let candidate = PathBuf::from(format!("{base}{suffix}"));
+                let candidate = candidate.canonicalize().unwrap_or(candidate);
+                graph.file_index.get(&candidate).copied()
+            })
+            .take(1)
+            .collect();
+    }
+    all
+}
+
+/// Files of the `pub mod name;` children declared in the Rust file `path`.
+fn pub_mod_files(graph: &CodeGraph, path: &Path, source: &str) -> Vec<NodeIndex> {
+    let Some(parent) = path.parent() else {
+        return Vec::new();
+    };
+    let dir = match path.file_name().and_then(|n| n.to_str()) {
+        Some("main.rs" | "lib.rs" | "mod.rs") => parent.to_path_buf(),
+        _ => match path.file_stem() {
+            Some(stem) => parent.join(stem),
+            None => return Vec::new(),
+        },
+    };
+    source
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix("pub mod ")?
+                .strip_suffix(';')
+                .map(str::trim)
+        })
+        .filter_map(|name| {
+            [
+                dir.join(format!("{name}.rs")),
+                dir.join(name).join("mod.rs"),
+            ]
+            .iter()
+            .find_map(|p| graph.file_index.get(p).copied())
+        })
+        .collect()
+}
+
+/// Symbols and files a surface file re-exports from elsewhere: named re-exports, Python
+/// `__init__.py` imports, Rust `pub use` items, and files whose whole surface it exposes
+/// (`pub use x::*`, `pub mod x`, `export *`).
+fn reexports(graph: &CodeGraph, file_idx: NodeIndex) -> (Vec<NodeIndex>, Vec<NodeIndex>) {
+    let mut symbols = Vec::new();
+    let mut files: Vec<NodeIndex> = graph
+        .graph
+        .edges_directed(file_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::BarrelReExportAll))
+        .map(|e| e.target())
+        .collect();
+
+    let GraphNode::File(fi) = &graph.graph[file_idx] else {
+        return (symbols, files);
+    };
+    let Ok(bytes) = std::fs::read(&fi.path) else {
+        return (symbols, files);
+    };
+    let Ok(parsed) = crate::parser::parse_file(&fi.path, &bytes) else {
+        return (symbols, files);
+    };
+
+    for export in parsed
+        .exports
+        .iter()
+        .filter(|e| e.kind == ExportKind::ReExport)
+    {
+        let Some(source) = &export.source else {
+            continue;
+        };
+        let targets = import_targets(graph, file_idx, source);
+        symbols.extend(
+            export
+                .names
+                .iter()
+                .filter_map(|name| find_bound_symbol(graph, &targets, name, false)),
+        );
+    }
+
+    let is_init = fi.path.file_name().is_some_and(|n| n == "__init__.py");
+    for import in parsed.imports.iter().filter(|_| is_init) {
+        let targets = import_targets(graph, file_idx, &import.module_path);
+        symbols.extend(
+            import
+                .specifiers
+                .iter()
+                .filter(|s| !s.is_namespace)
+                .filter_map(|s| find_bound_symbol(graph, &targets, &s.name, false)),
+        );
+    }
+
+    for leaf in parsed
+        .rust_uses
+        .iter()
+        .filter(|u| u.is_pub_use)
+        .flat_map(|u| expand_use_tree(&u.path))
+    {
+        let targets = import_targets(graph, file_idx, &leaf);
+        match leaf.strip_suffix("::*") {
+            Some(_) => {
+                files.extend(targets.first().copied());
+            }
+            None => {
+                let name = leaf.rsplit("::").next().unwrap_or(&leaf);
+                symbols.extend(find_bound_symbol(graph, &targets, name, false));
+            }
+        }
+    }
+    if fi.language == "rust" {
+        let source = String::from_utf8_lossy(&bytes);
+        files.extend(pub_mod_files(graph, &fi.path, &source));
+    }
+    (symbols, files)
+}
+
+/// Files outside the package that use `sym`, which files in `exposing` expose.
+fn consumers(
+    graph: &CodeGraph,
+    package: &Package,
+    sym: NodeIndex,
+    exposing: &HashSet<NodeIndex>,
+    bound_files: &HashSet<NodeIndex>,
+) -> BTreeSet<NodeIndex> {
+    let mut found = BTreeSet::new();
+    let members: Vec<NodeIndex> = std::iter::once(sym)
+        .chain(
+            graph
+                .graph
+                .edges_directed(sym, Direction::Incoming)
+                .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+                .map(|e| e.source()),
+        )
+        .collect();
+    for &member in &members {
+        for edge in graph.graph.edges_directed(member, Direction::Incoming) {
+            let source = match edge.weight() {
+                EdgeKind::ImportsSymbol { .. } => Some(edge.source()),
+                EdgeKind::Calls { .. } | EdgeKind::Renders => {
+                    find_containing_file_idx(graph, edge.source())
+                        .or(Some(edge.source()))
+                        .filter(|&f| matches!(graph.graph[f], GraphNode::File(_)))
+                }
+                _ => None,
+            };
+            found.extend(source.filter(|f| !package.files.contains(f)));
+        }
+    }
+    // File-level imports (namespace imports, Rust `use`) count toward every item the
+    // imported file exposes, unless the importer binds names from the package.
+    for &file in exposing {
+        for edge in graph.graph.edges_directed(file, Direction::Incoming) {
+            if matches!(edge.weight(), EdgeKind::ResolvedImport { .. })
+                && !package.files.contains(&edge.source())
+                && !bound_files.contains(&edge.source())
+            {
+                found.insert(edge.source());
+            }
+        }
+    }
+    found
+}
+
+/// Symbols exposed by the entry points of `package`, in discovery order, each with the
+/// files that expose it and the first re-exporting file it was reached through.
+fn surface(graph: &CodeGraph, package: &Package) -> (Vec<NodeIndex>, Exposed) {
+    // Breadth-first over surface files.
+    let mut exposed: Exposed = HashMap::new();
+    let mut order: Vec<NodeIndex> = Vec::new();
+    let mut queue: Vec<(NodeIndex, Option<NodeIndex>)> =
+        package.entries.iter().map(|&e| (e, None)).collect();
+    let mut visited: HashSet<NodeIndex> = HashSet::new();
+    let mut i = 0;
+    while i < queue.len() {
+        let (file, via) = queue[i];
+        i += 1;
+        if !visited.insert(file) {
+            continue;
+        }
+        let mut record = |sym: NodeIndex, via: Option<NodeIndex>| {
+            let defining = find_containing_file_idx(graph, sym);
+            let entry = exposed.entry(sym).or_insert_with(|| {
+                order.push(sym);
+                (HashSet::new(), via.filter(|&v| Some(v) != defining))
+            });
+            entry.0.insert(file);
+            entry.0.extend(defining);
+            entry.0.extend(via);
+        };
+        for sym in public_symbols(graph, file) {
+            record(sym, via);
+        }
+        let (symbols, files) = reexports(graph, file);
+        for sym in symbols {
+            record(sym, Some(via.unwrap_or(file)));
+        }
+        queue.extend(files.into_iter().map(|f| (f, Some(via.unwrap_or(file)))));
+    }
+    (order, exposed)
+}
+
+/// List everything exported from the entry points of `target` (a workspace package name,
+/// crate name, or directory relative to `root`), with the files outside the package that
+/// use each item.
+pub fn list_exports(graph: &CodeGraph, root: &Path, target: &str) -> Result<ExportsReport> {
+    let package = resolve_package(graph, root, target)?;
+    if package.entries.is_empty() {
+        bail!("no entry file found for '{}'", target);
+    }
+    let (order, exposed) = surface(graph, &package);
+
+    // Importers that bind names from the package only use what they bind.
+    let bound_files: HashSet<NodeIndex> = package
+        .files
+        .iter()
+        .flat_map(|&f| graph.graph.edges_directed(f, Direction::Outgoing))
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .flat_map(|e| graph.graph.edges_directed(e.target(), Direction::Incoming))
+        .filter(|e| matches!(e.weight(), EdgeKind::ImportsSymbol { .. }))
+        .map(|e| e.source())
+        .collect();
+
+    let path_of = |idx: NodeIndex| match &graph.graph[idx] {
+        GraphNode::File(fi) => rel_path(&fi.path, root),
+        _ => String::new(),
+    };
+    let mut exports: Vec<ExportedItem> = order
+        .into_iter()
+        .filter_map(|sym| {
+            let GraphNode::Symbol(info) = &graph.graph[sym] else {
+                return None;
+            };
+            let (exposing, via) = &exposed[&sym];
+            let file = find_containing_file_idx(graph, sym)?;
+            Some(ExportedItem {
+                name: info.name.clone(),
+                kind: kind_to_str(&info.kind).to_string(),
+                file: path_of(file),
+                line: info.line,
+                via: via.map(path_of),
+                consumers: consumers(graph, &package, sym, exposing, &bound_files)
+                    .into_iter()
+                    .map(path_of)
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect(),
+            })
+        })
+        .collect();
+    exports.sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+    dedup_impl_method_twins(&mut exports, |e| {
+        ((e.file.clone(), e.line), e.name.as_str())
+    });
+
+    let mut entry_files: Vec<String> = package.entries.iter().map(|&e| path_of(e)).collect();
+    entry_files.sort();
+    Ok(ExportsReport {
+        target: target.to_string(),
+        entry_files,
+        exports,
+    })
+}
+
+/// List the consumable API of `target` (a workspace package name, crate name, or
+/// directory relative to `root`): every item exposed from its entry points, and the public
+/// members of exposed types, with their declaration headers.
+pub fn package_api(graph: &CodeGraph, root: &Path, target: &str) -> Result<ApiReport> {
+    let package = resolve_package(graph, root, target)?;
+    if package.entries.is_empty() {
+        bail!("no entry file found for '{}'", target);
+    }
+    let (order, exposed) = surface(graph, &package);
+
+    let path_of = |idx: NodeIndex| match &graph.graph[idx] {
+        GraphNode::File(fi) => rel_path(&fi.path, root),
+        _ => String::new(),
+    };
+    let mut sources: HashMap<NodeIndex, String> = HashMap::new();
+    let mut items: Vec<ApiItem> = Vec::new();
+    for sym in order {
+        let (GraphNode::Symbol(info), Some(file)) =
+            (&graph.graph[sym], find_containing_file_idx(graph, sym))
+        else {
+            continue;
+        };
+        let GraphNode::File(fi) = &graph.graph[file] else {
+            continue;
+        };
+        let source = sources
+            .entry(file)
+            .or_insert_with(|| std::fs::read_to_string(&fi.path).unwrap_or_default());
+        let lines: Vec<&str> = source.lines().collect();
+        let via = exposed[&sym].1.map(path_of);
+        let mut push = |name: String, member: &crate::graph::node::SymbolInfo| {
+            items.push(ApiItem {
+                name,
+                kind: kind_to_str(&member.kind).to_string(),
+                signature: declaration_header(&lines, member.line, member.line_end),
+                file: path_of(file),
+                line: member.line,
+                via: via.clone(),
+            });
+        };
+        push(info.name.clone(), info);
+        // Members are part of the API when the language makes them public: `pub` in Rust,
+        // every non-`#private` member elsewhere.
+        let mut members: Vec<&crate::graph::node::SymbolInfo> = graph
+            .graph
+            .edges_directed(sym, Direction::Incoming)
+            .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+            .filter_map(|e| match &graph.graph[e.source()] {
+                GraphNode::Symbol(m)
+                    if (fi.language != "rust" || is_public(m, &fi.language))
+                        && !m.name.starts_with('#') =>
+                {
+                    Some(m)
+                }
+                _ => None,
+            })
+            .collect();
+        members.sort_by_key(|m| (m.line, m.col));
+        for member in members {
+            let short = member.name.rsplit("::").next().unwrap_or(&member.name);
+            push(format!("{}.{}", info.name, short), member);
+        }
+    }
+    items.sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+    dedup_impl_method_twins(&mut items, |item| {
+        ((item.file.clone(), item.line), item.name.as_str())
+    });
+
+    let mut entry_files: Vec<String> = package.entries.iter().map(|&e| path_of(e)).collect();
+    entry_files.sort();
+    Ok(ApiReport {
+        target: target.to_string(),
+        entry_files,
+        items,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind, SymbolVisibility};
+    use std::fs;
+
+    fn symbol(name: &str, line: usize, is_exported: bool) -> SymbolInfo {
+        SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line,
+            is_exported,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_barrels_and_named_reexports_with_consumers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let lib = root.join("lib");
+        fs::create_dir_all(&lib).unwrap();
+        fs::write(
+            lib.join("index.ts"),
+            "export * from './a';\nexport { b } from './b';\nexport const version = 1;\n",
+        )
+        .unwrap();
+        fs::write(lib.join("a.ts"), "export function a() {}\n").unwrap();
+        fs::write(
+            lib.join("b.ts"),
+            "export function b() {}\nexport function hidden() {}\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let index = graph.add_file(lib.join("index.ts"), "typescript");
+        graph.add_symbol(index, symbol("version", 3, true));
+        let a_file = graph.add_file(lib.join("a.ts"), "typescript");
+        let a = graph.add_symbol(a_file, symbol("a", 1, true));
+        let b_file = graph.add_file(lib.join("b.ts"), "typescript");
+        graph.add_symbol(b_file, symbol("b", 1, true));
+        graph.add_symbol(b_file, symbol("hidden", 2, true));
+        graph.add_resolved_import(index, a_file, "./a");
+        graph.add_barrel_reexport_all(index, a_file);
+        let app = graph.add_file(root.join("app.ts"), "typescript");
+        graph.add_resolved_import(app, index, "./lib");
+        graph.add_imports_symbol(app, a, 0, 0);
+
+        let report = list_exports(&graph, &root, "lib").unwrap();
+        assert_eq!(report.entry_files, vec!["lib/index.ts"]);
+        let items: Vec<String> = report
+            .exports
+            .iter()
+            .map(|e| {
+                format!(
+                    "{} {} via={:?} used={:?}",
+                    e.name, e.file, e.via, e.consumers
+                )
+            })
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                r#"a lib/a.ts via=Some("lib/index.ts") used=["app.ts"]"#,
+                r#"b lib/b.ts via=Some("lib/index.ts") used=[]"#,
+                r#"version lib/index.ts via=None used=[]"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_package_api_lists_signatures_and_members() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let lib = root.join("lib");
+        fs::create_dir_all(&lib).unwrap();
+        fs::write(lib.join("index.ts"), "export * from './server';\n").unwrap();
+        fs::write(
+            lib.join("server.ts"),
+            "export class Server {\n  listen(port: number): void {}\n  #secret() {}\n}\nexport function start(port = 80): Server { return new Server(); }\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let index = graph.add_file(lib.join("index.ts"), "typescript");
+        let server_file = graph.add_file(lib.join("server.ts"), "typescript");
+        let server = graph.add_symbol(
+            server_file,
+            SymbolInfo {
+                kind: SymbolKind::Class,
+                line_end: 4,
+                ..symbol("Server", 1, true)
+            },
+        );
+        for (name, line) in [("listen", 2), ("#secret", 3)] {
+            graph.add_child_symbol(
+                server,
+                SymbolInfo {
+                    kind: SymbolKind::Method,
+                    line_end: line,
+                    ..symbol(name, line, false)
+                },
+            );
+        }
+        graph.add_symbol(
+            server_file,
+            SymbolInfo {
+                line_end: 5,
+                ..symbol("start", 5, true)
+            },
+        );
+        graph.add_resolved_import(index, server_file, "./server");
+        graph.add_barrel_reexport_all(index, server_file);
+
+        let report = package_api(&graph, &root, "lib").unwrap();
+        let items: Vec<(&str, &str)> = report
+            .items
+            .iter()
+            .map(|i| (i.name.as_str(), i.signature.as_str()))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("Server", "export class Server"),
+                ("Server.listen", "listen(port: number): void"),
+                ("start", "export function start(port = 80): Server"),
+            ]
+        );
+        assert_eq!(report.items[0].via.as_deref(), Some("lib/index.ts"));
+    }
+
+    #[test]
+    fn test_rust_pub_use_and_pub_mod() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let dir = root.join("src/api");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("mod.rs"),
+            "pub mod model;\nmod inner;\npub use inner::run;\n",
+        )
+        .unwrap();
+        fs::write(dir.join("model.rs"), "pub struct Model;\nfn private() {}\n").unwrap();
+        fs::write(dir.join("inner.rs"), "pub fn run() {}\npub fn other() {}\n").unwrap();
+
+        let pub_fn = |name: &str, line: usize| SymbolInfo {
+            visibility: SymbolVisibility::Pub,
+            ..symbol(name, line, false)
+        };
+        let mut graph = CodeGraph::new();
+        let module = graph.add_file(dir.join("mod.rs"), "rust");
+        let model = graph.add_file(dir.join("model.rs"), "rust");
+        graph.add_symbol(
+            model,
+            SymbolInfo {
+                kind: SymbolKind::Struct,
+                ..pub_fn("Model", 1)
+            },
+        );
+        graph.add_symbol(model, symbol("private", 2, false));
+        let inner = graph.add_file(dir.join("inner.rs"), "rust");
+        let run = graph.add_symbol(inner, pub_fn("run", 1));
+        graph.add_symbol(inner, pub_fn("other", 2));
+        graph.add_resolved_import(module, inner, "inner::run");
+        let main = graph.add_file(root.join("src/main.rs"), "rust");
+        let main_fn = graph.add_symbol(main, symbol("main", 1, false));
+        graph.add_call_site(main_fn, run, 2);
+
+        let report = list_exports(&graph, &root, "src/api").unwrap();
+        let items: Vec<(&str, &str, Vec<String>)> = report
+            .exports
+            .iter()
+            .map(|e| (e.name.as_str(), e.file.as_str(), e.consumers.clone()))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("run", "src/api/inner.rs", vec!["src/main.rs".to_string()]),
+                ("Model", "src/api/model.rs", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rust_impl_method_listed_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), "pub mod shapes;\n").unwrap();
+        fs::write(
+            src.join("shapes.rs"),
+            "pub struct Circle;\n\nimpl Circle {\n    pub fn new() -> Self {\n        Circle\n    }\n}\n",
+        )
+        .unwrap();
+
+        let public = |name: &str, kind: SymbolKind, line: usize| SymbolInfo {
+            kind,
+            visibility: SymbolVisibility::Pub,
+            ..symbol(name, line, false)
+        };
+        let mut graph = CodeGraph::new();
+        let lib = graph.add_file(src.join("lib.rs"), "rust");
+        let shapes = graph.add_file(src.join("shapes.rs"), "rust");
+        graph.add_resolved_import(lib, shapes, "shapes");
+        graph.add_symbol(shapes, public("Circle", SymbolKind::Struct, 1));
+        // The parser reports the method both qualified and as a bare function.
+        graph.add_symbol(shapes, public("new", SymbolKind::Function, 4));
+        graph.add_symbol(shapes, public("Circle::new", SymbolKind::ImplMethod, 4));
+
+        let report = list_exports(&graph, &root, "src").unwrap();
+        let names: Vec<(&str, usize)> = report
+            .exports
+            .iter()
+            .map(|e| (e.name.as_str(), e.line))
+            .collect();
+        assert_eq!(names, vec![("Circle", 1), ("Circle::new", 4)]);
+    }
+
+    #[test]
+    fn test_unknown_target_errors() {
+        let graph = CodeGraph::new();
+        let err = list_exports(&graph, Path::new("/nowhere"), "missing").unwrap_err();
+        assert!(err.to_string().contains("neither a workspace package"));
+    }
+}
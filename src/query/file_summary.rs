@@ -41,6 +41,18 @@ pub struct ExportedSymbol {
     pub kind: String, // "fn", "struct", etc.
 }
 
+/// A top-level symbol and the number of lines it spans.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SymbolSize {
+    pub name: String,
+    pub kind: String,
+    pub line: usize,
+    pub lines: usize,
+}
+
+/// How many symbols `largest_symbols` keeps.
+const LARGEST_SYMBOLS: usize = 5;
+
 /// Summary information for a single file.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct FileSummary {
@@ -51,6 +63,8 @@ pub struct FileSummary {
     /// Breakdown of all symbols by kind string (e.g. "fn" -> 3, "struct" -> 1).
     pub symbol_kinds: HashMap<String, usize>,
     pub exports: Vec<ExportedSymbol>,
+    /// The top-level symbols spanning the most lines, largest first.
+    pub largest_symbols: Vec<SymbolSize>,
     pub import_count: usize,   // outgoing import edges
     pub importer_count: usize, // incoming import edges
     pub graph_label: Option<GraphLabel>,
@@ -287,8 +301,24 @@ pub fn file_summary(
     // Role detection
     let role = detect_role(&file_info, root, reexport_count, &all_symbols);
 
-    // Line count
-    let line_count = count_lines(&abs_path);
+    // Line count: recorded at parse time, read from disk for older caches.
+    let line_count = if file_info.line_count > 0 {
+        file_info.line_count
+    } else {
+        count_lines(&abs_path)
+    };
+
+    let mut largest_symbols: Vec<SymbolSize> = all_symbols
+        .iter()
+        .map(|sym| SymbolSize {
+            name: sym.name.clone(),
+            kind: kind_to_str(&sym.kind).to_string(),
+            line: sym.line,
+            lines: sym.line_count(),
+        })
+        .collect();
+    largest_symbols.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.line.cmp(&b.line)));
+    largest_symbols.truncate(LARGEST_SYMBOLS);
 
     // Compute relative path for display
     let relative_path = abs_path
@@ -303,6 +333,7 @@ pub fn file_summary(
         symbol_count,
         symbol_kinds,
         exports,
+        largest_symbols,
         import_count,
         importer_count,
         graph_label,
@@ -478,6 +509,10 @@ mod tests {
                 hub_idx,
                 EdgeKind::ResolvedImport {
                     specifier: "./central".into(),
+                    is_type_only: false,
+                    count: 1,
+                    line: 0,
+                    col: 0,
                 },
             );
         }
@@ -524,6 +559,10 @@ mod tests {
                 bridge_idx,
                 EdgeKind::ResolvedImport {
                     specifier: "./bridge".into(),
+                    is_type_only: false,
+                    count: 1,
+                    line: 0,
+                    col: 0,
                 },
             );
         }
@@ -537,6 +576,10 @@ mod tests {
                 dep_idx,
                 EdgeKind::ResolvedImport {
                     specifier: format!("./dep{}", i),
+                    is_type_only: false,
+                    count: 1,
+                    line: 0,
+                    col: 0,
                 },
             );
         }
@@ -599,4 +642,34 @@ mod tests {
             "Should count 10 lines in the temp file"
         );
     }
+
+    #[test]
+    fn test_recorded_line_count_and_largest_symbols() {
+        let mut graph = CodeGraph::new();
+        let root = PathBuf::from("/tmp/test_project");
+        let file_path = root.join("src/app.ts");
+        let file_idx = graph.add_file(file_path.clone(), "typescript");
+        graph.set_file_source(file_idx, 120, None);
+        for (name, line, line_end) in [("small", 1, 3), ("big", 10, 60), ("mid", 70, 90)] {
+            graph.add_symbol(
+                file_idx,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Function,
+                    line,
+                    line_end,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let summary = file_summary(&graph, &root, &file_path).unwrap();
+        assert_eq!(summary.line_count, 120, "recorded count wins over the disk");
+        let largest: Vec<(&str, usize)> = summary
+            .largest_symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.lines))
+            .collect();
+        assert_eq!(largest, vec![("big", 51), ("mid", 21), ("small", 3)]);
+    }
 }
@@ -10,7 +10,7 @@ use regex::RegexBuilder;
 use crate::graph::{
     CodeGraph,
     edge::EdgeKind,
-    node::{DecoratorInfo, GraphNode, SymbolKind, SymbolVisibility},
+    node::{DecoratorInfo, GraphNode, SignatureInfo, SymbolKind, SymbolVisibility},
 };
 
 /// Indicates how a search result was matched. Used in BM25/hybrid search (plan 20-01).
@@ -42,13 +42,60 @@ pub struct FindResult {
     pub line: usize,
     pub line_end: usize,
     pub col: usize,
+    pub col_end: usize,
+    /// Byte range of the declaration; `byte_end` is `0` when unknown.
+    pub byte_start: usize,
+    pub byte_end: usize,
     pub is_exported: bool,
     pub is_default: bool,
     pub visibility: SymbolVisibility,
     #[allow(dead_code)]
     pub decorators: Vec<DecoratorInfo>,
+    /// Parameters and return type, for functions and methods.
+    pub signature: Option<SignatureInfo>,
 }
 
+/// Parse a `find --visibility` value: `pub` (or `public`), `pub(crate)` (or `crate`),
+/// `protected`, or `private`.
+pub fn parse_visibility(value: &str) -> Result<SymbolVisibility> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "pub" | "public" => Ok(SymbolVisibility::Pub),
+        "pub(crate)" | "pub-crate" | "crate" => Ok(SymbolVisibility::PubCrate),
+        "protected" => Ok(SymbolVisibility::Protected),
+        "private" => Ok(SymbolVisibility::Private),
+        other => anyhow::bail!(
+            "unknown visibility '{}' (expected pub, pub(crate), protected, or private)",
+            other
+        ),
+    }
+}
+
+/// Every name [`kind_to_str`] returns, in declaration order.
+pub const SYMBOL_KIND_NAMES: &[&str] = &[
+    "function",
+    "class",
+    "interface",
+    "type",
+    "namespace",
+    "enum",
+    "variable",
+    "component",
+    "method",
+    "property",
+    "struct",
+    "trait",
+    "impl_method",
+    "const",
+    "static",
+    "macro",
+    "message",
+    "service",
+    "rpc",
+    "table",
+    "view",
+    "endpoint",
+];
+
 /// Convert a `SymbolKind` to its lowercase string representation used in output and filtering.
 pub fn kind_to_str(kind: &SymbolKind) -> &'static str {
     match kind {
@@ -56,6 +103,7 @@ pub fn kind_to_str(kind: &SymbolKind) -> &'static str {
         SymbolKind::Class => "class",
         SymbolKind::Interface => "interface",
         SymbolKind::TypeAlias => "type",
+        SymbolKind::Namespace => "namespace",
         SymbolKind::Enum => "enum",
         SymbolKind::Variable => "variable",
         SymbolKind::Component => "component",
@@ -68,6 +116,14 @@ pub fn kind_to_str(kind: &SymbolKind) -> &'static str {
         SymbolKind::Const => "const",
         SymbolKind::Static => "static",
         SymbolKind::Macro => "macro",
+        // Protobuf kinds
+        SymbolKind::Message => "message",
+        SymbolKind::Service => "service",
+        SymbolKind::Rpc => "rpc",
+        // SQL kinds
+        SymbolKind::Table => "table",
+        SymbolKind::View => "view",
+        SymbolKind::Endpoint => "endpoint",
     }
 }
 
@@ -115,7 +171,11 @@ fn find_containing_file_of_child(
         })
 }
 
-/// Find symbols in `graph` matching the given regex `pattern`.
+/// Pattern that matches every symbol name without compiling a regex. With a kind filter,
+/// candidates come straight from the graph's kind index.
+pub const MATCH_ALL: &str = "*";
+
+/// Find symbols in `graph` matching the given regex `pattern` ([`MATCH_ALL`] for any name).
 ///
 /// - `case_insensitive`: enable case-insensitive regex matching
 /// - `kind_filter`: if non-empty, only include symbols whose kind string is in this list
@@ -135,74 +195,97 @@ pub fn find_symbol(
     project_root: &Path,
     language_filter: Option<&str>,
 ) -> Result<Vec<FindResult>> {
-    let re = RegexBuilder::new(pattern)
-        .case_insensitive(case_insensitive)
-        .build()
-        .map_err(|e| anyhow::anyhow!("invalid symbol pattern '{}': {}", pattern, e))?;
+    let candidates: Vec<NodeIndex> = if pattern != MATCH_ALL {
+        let re = RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|e| anyhow::anyhow!("invalid symbol pattern '{}': {}", pattern, e))?;
+        // Iterate symbol_index keys — O(symbols). Regex compiled ONCE above. Export
+        // aliases match too and resolve to the symbols they name.
+        let mut candidates: Vec<NodeIndex> = graph
+            .symbol_index
+            .iter()
+            .chain(&graph.alias_index)
+            .filter(|(name, _)| re.is_match(name))
+            .flat_map(|(_, indices)| indices.iter().copied())
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    } else if kind_filter.is_empty() {
+        graph.symbol_index.values().flatten().copied().collect()
+    } else {
+        graph
+            .kind_index
+            .iter()
+            .filter(|(kind, _)| kind_filter.iter().any(|k| k == kind_to_str(kind)))
+            .flat_map(|(_, indices)| indices.iter().copied())
+            .collect()
+    };
 
     let mut results: Vec<FindResult> = Vec::new();
 
-    // Iterate symbol_index keys — O(symbols). Regex compiled ONCE above.
-    for (name, node_indices) in &graph.symbol_index {
-        if !re.is_match(name) {
-            continue;
-        }
-
-        for &sym_idx in node_indices {
-            let sym_info = match &graph.graph[sym_idx] {
-                GraphNode::Symbol(info) => info.clone(),
-                _ => continue,
-            };
-
-            // Kind filter (if any).
-            if !kind_filter.is_empty() {
-                let kind_str = kind_to_str(&sym_info.kind);
-                if !kind_filter.iter().any(|k| k.as_str() == kind_str) {
-                    continue;
-                }
-            }
-
-            // Find parent file via Contains edge (not just any incoming file neighbor).
-            // Falls back to ChildOf -> Contains for child symbols.
-            let file_info = find_containing_file(graph, sym_idx)
-                .or_else(|| find_containing_file_of_child(graph, sym_idx));
-
-            let file_info = match file_info {
-                Some(fi) => fi,
-                None => continue, // Cannot locate file — skip.
-            };
+    for sym_idx in candidates {
+        let sym_info = match &graph.graph[sym_idx] {
+            GraphNode::Symbol(info) => info.clone(),
+            _ => continue,
+        };
 
-            // File filter: match relative path prefix.
-            if let Some(filter) = file_filter {
-                let rel_path = file_info
-                    .path
-                    .strip_prefix(project_root)
-                    .unwrap_or(&file_info.path);
-                if !rel_path.starts_with(filter) {
-                    continue;
-                }
+        // Kind filter (if any).
+        if !kind_filter.is_empty() {
+            let kind_str = kind_to_str(&sym_info.kind);
+            if !kind_filter.iter().any(|k| k.as_str() == kind_str) {
+                continue;
             }
+        }
 
-            // Language filter: skip symbols from files whose language doesn't match.
-            if let Some(lang) = language_filter
-                && file_info.language.as_str() != lang
-            {
+        // Find parent file via Contains edge (not just any incoming file neighbor).
+        // Falls back to ChildOf -> Contains for child symbols.
+        let file_info = find_containing_file(graph, sym_idx)
+            .or_else(|| find_containing_file_of_child(graph, sym_idx));
+
+        let file_info = match file_info {
+            Some(fi) => fi,
+            None => continue, // Cannot locate file — skip.
+        };
+
+        // File filter: match relative path prefix.
+        if let Some(filter) = file_filter {
+            let rel_path = file_info
+                .path
+                .strip_prefix(project_root)
+                .unwrap_or(&file_info.path);
+            if !rel_path.starts_with(filter) {
                 continue;
             }
+        }
 
-            results.push(FindResult {
-                symbol_name: sym_info.name.clone(),
-                kind: sym_info.kind.clone(),
-                file_path: file_info.path.clone(),
-                line: sym_info.line,
-                line_end: sym_info.line_end,
-                col: sym_info.col,
-                is_exported: sym_info.is_exported,
-                is_default: sym_info.is_default,
-                visibility: sym_info.visibility.clone(),
-                decorators: sym_info.decorators.clone(),
-            });
+        // Language filter: skip symbols from files whose language doesn't match.
+        // Notebooks carry their kernel's language, and also match "jupyter".
+        if let Some(lang) = language_filter
+            && file_info.language.as_str() != lang
+            && !(lang == "jupyter"
+                && file_info.path.extension().and_then(|e| e.to_str()) == Some("ipynb"))
+        {
+            continue;
         }
+
+        results.push(FindResult {
+            symbol_name: sym_info.name.clone(),
+            kind: sym_info.kind.clone(),
+            file_path: file_info.path.clone(),
+            line: sym_info.line,
+            line_end: sym_info.line_end,
+            col: sym_info.col,
+            col_end: sym_info.col_end,
+            byte_start: sym_info.byte_start,
+            byte_end: sym_info.byte_end,
+            is_exported: sym_info.is_exported,
+            is_default: sym_info.is_default,
+            visibility: sym_info.visibility.clone(),
+            decorators: sym_info.decorators.clone(),
+            signature: sym_info.signature.clone(),
+        });
     }
 
     // Sort by file path then line number for deterministic output.
@@ -213,7 +296,8 @@ pub fn find_symbol(
 
 /// Compile `pattern` as a regex and collect all matching symbol names with their node indices.
 ///
-/// Returns a vec of `(name, indices)` pairs — one entry per unique symbol name that matches.
+/// Returns a vec of `(name, indices)` pairs — one entry per unique symbol name that matches,
+/// plus one per matching export alias naming symbols not already listed.
 /// The caller decides whether an empty result is an error.
 ///
 /// `case_insensitive`: enable case-insensitive matching.
@@ -227,13 +311,29 @@ pub fn match_symbols(
         .build()
         .map_err(|e| anyhow::anyhow!("invalid symbol pattern '{}': {}", pattern, e))?;
 
-    let matches: Vec<(String, Vec<NodeIndex>)> = graph
+    let mut matches: Vec<(String, Vec<NodeIndex>)> = graph
         .symbol_index
         .iter()
         .filter(|(name, _)| re.is_match(name))
         .map(|(name, indices)| (name.clone(), indices.clone()))
         .collect();
 
+    // Export aliases resolve to the symbols they name, unless those already matched.
+    let matched: HashSet<NodeIndex> = matches.iter().flat_map(|(_, i)| i).copied().collect();
+    for (alias, indices) in &graph.alias_index {
+        if !re.is_match(alias) {
+            continue;
+        }
+        let indices: Vec<NodeIndex> = indices
+            .iter()
+            .copied()
+            .filter(|i| !matched.contains(i))
+            .collect();
+        if !indices.is_empty() {
+            matches.push((alias.clone(), indices));
+        }
+    }
+
     Ok(matches)
 }
 
@@ -243,6 +343,7 @@ pub fn match_symbols(
 
 /// Compute character-level trigrams from a string (lowercased).
 /// Returns an empty set for strings shorter than 3 characters. Used in plan 20-01.
+#[cfg_attr(not(feature = "web"), allow(dead_code))]
 pub(crate) fn trigrams(s: &str) -> HashSet<[char; 3]> {
     let chars: Vec<char> = s.to_lowercase().chars().collect();
     if chars.len() < 3 {
@@ -253,6 +354,7 @@ pub(crate) fn trigrams(s: &str) -> HashSet<[char; 3]> {
 
 /// Jaccard similarity between two trigram sets: |A ∩ B| / |A ∪ B|.
 /// Returns 0.0 if both sets are empty (no useful comparison possible). Used in plan 20-01.
+#[cfg_attr(not(feature = "web"), allow(dead_code))]
 pub(crate) fn jaccard_similarity(a: &HashSet<[char; 3]>, b: &HashSet<[char; 3]>) -> f32 {
     let intersection = a.intersection(b).count();
     let union = a.union(b).count();
@@ -269,6 +371,7 @@ pub(crate) fn jaccard_similarity(a: &HashSet<[char; 3]>, b: &HashSet<[char; 3]>)
 /// Find symbols using trigram similarity. Returns `FindResult` items for all
 /// symbols whose Jaccard similarity with `query` is >= 0.3.
 /// Results are sorted by score descending and limited to `limit`. Used in plan 20-01.
+#[cfg_attr(not(feature = "web"), allow(dead_code))]
 pub fn find_symbol_trigram(graph: &CodeGraph, query: &str, limit: usize) -> Vec<FindResult> {
     let query_trigrams = trigrams(query);
     if query_trigrams.is_empty() {
@@ -304,10 +407,14 @@ pub fn find_symbol_trigram(graph: &CodeGraph, query: &str, limit: usize) -> Vec<
                         line: sym_info.line,
                         line_end: sym_info.line_end,
                         col: sym_info.col,
+                        col_end: sym_info.col_end,
+                        byte_start: sym_info.byte_start,
+                        byte_end: sym_info.byte_end,
                         is_exported: sym_info.is_exported,
                         is_default: sym_info.is_default,
                         visibility: sym_info.visibility.clone(),
                         decorators: sym_info.decorators.clone(),
+                        signature: sym_info.signature.clone(),
                     },
                     score,
                 ));
@@ -328,6 +435,7 @@ pub fn find_symbol_trigram(graph: &CodeGraph, query: &str, limit: usize) -> Vec<
 
 /// Search for symbols using the BM25 full-text index.
 /// Returns an empty vec if the BM25 index is not built yet (`bm25_index` is None). Used in plan 20-01.
+#[cfg_attr(not(feature = "web"), allow(dead_code))]
 pub fn bm25_search(graph: &CodeGraph, query: &str, limit: usize) -> Vec<FindResult> {
     let engine = match &graph.bm25_index {
         Some(e) => e,
@@ -351,10 +459,14 @@ pub fn bm25_search(graph: &CodeGraph, query: &str, limit: usize) -> Vec<FindResu
                     line: sym.line,
                     line_end: sym.line_end,
                     col: sym.col,
+                    col_end: sym.col_end,
+                    byte_start: sym.byte_start,
+                    byte_end: sym.byte_end,
                     is_exported: sym.is_exported,
                     is_default: sym.is_default,
                     visibility: sym.visibility.clone(),
                     decorators: sym.decorators.clone(),
+                    signature: sym.signature.clone(),
                 });
             }
         }
@@ -365,6 +477,7 @@ pub fn bm25_search(graph: &CodeGraph, query: &str, limit: usize) -> Vec<FindResu
 
 /// Merge two ranked result lists using Reciprocal Rank Fusion (k=60).
 /// Returns a unified list sorted by combined RRF score, highest first. Used in plan 20-01.
+#[cfg_attr(not(feature = "web"), allow(dead_code))]
 pub fn reciprocal_rank_fusion(list_a: &[FindResult], list_b: &[FindResult]) -> Vec<FindResult> {
     let k = 60.0_f32;
     let mut scores: HashMap<String, (f32, FindResult)> = HashMap::new();
@@ -482,6 +595,23 @@ mod tests {
         assert_eq!(results[0].kind, SymbolKind::Function);
     }
 
+    #[test]
+    fn test_match_all_lists_kind_from_index() {
+        let (mut graph, root) = make_graph_with_symbols();
+        let classes = vec!["class".to_string()];
+        let results = find_symbol(&graph, MATCH_ALL, false, &classes, None, &root, None).unwrap();
+        let names: Vec<&str> = results.iter().map(|r| r.symbol_name.as_str()).collect();
+        assert_eq!(names, vec!["AuthService", "UserService"]);
+
+        let all = find_symbol(&graph, MATCH_ALL, false, &[], None, &root, None).unwrap();
+        assert_eq!(all.len(), 3);
+
+        // Removing a file drops its symbols from the kind index too.
+        graph.remove_file_from_graph(&root.join("src/auth.ts"));
+        assert_eq!(graph.kind_index[&SymbolKind::Class].len(), 1);
+        assert!(!graph.kind_index.contains_key(&SymbolKind::Function));
+    }
+
     #[test]
     fn test_no_match_returns_empty() {
         let (graph, root) = make_graph_with_symbols();
@@ -517,7 +647,7 @@ mod tests {
 
         // Simulate a Calls edge from main.ts (file) to greet (symbol), as the resolver does.
         let f2 = graph.add_file(root.join("src/main.ts"), "typescript");
-        graph.add_calls_edge(f2, greet_sym);
+        graph.add_call_site(f2, greet_sym, 1);
 
         let results = find_symbol(&graph, "greet", false, &[], None, &root, None).unwrap();
         assert_eq!(results.len(), 1, "should find exactly one definition");
@@ -634,10 +764,14 @@ mod tests {
             line,
             line_end: line,
             col: 0,
+            col_end: 0,
+            byte_start: 0,
+            byte_end: 0,
             is_exported: false,
             is_default: false,
             visibility: crate::graph::node::SymbolVisibility::Private,
             decorators: vec![],
+            signature: None,
         }
     }
 
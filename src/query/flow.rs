@@ -142,7 +142,7 @@ fn dfs_trace(
 
         if !matches!(
             edge_ref.weight(),
-            EdgeKind::Calls | EdgeKind::ResolvedImport { .. }
+            EdgeKind::Calls { .. } | EdgeKind::ResolvedImport { .. }
         ) {
             continue;
         }
@@ -228,7 +228,7 @@ fn reachable_set(
         for edge_ref in graph.graph.edges_directed(current, Direction::Outgoing) {
             if !matches!(
                 edge_ref.weight(),
-                EdgeKind::Calls | EdgeKind::ResolvedImport { .. }
+                EdgeKind::Calls { .. } | EdgeKind::ResolvedImport { .. }
             ) {
                 continue;
             }
@@ -306,8 +306,8 @@ mod tests {
             },
         );
 
-        g.add_calls_edge(a, b);
-        g.add_calls_edge(b, c);
+        g.add_call_site(a, b, 1);
+        g.add_call_site(b, c, 1);
 
         (g, r)
     }
@@ -367,10 +367,10 @@ mod tests {
         );
 
         // Two distinct paths: A->B->C and A->D->C
-        g.add_calls_edge(a, b);
-        g.add_calls_edge(b, c);
-        g.add_calls_edge(a, d);
-        g.add_calls_edge(d, c);
+        g.add_call_site(a, b, 1);
+        g.add_call_site(b, c, 1);
+        g.add_call_site(a, d, 1);
+        g.add_call_site(d, c, 1);
 
         let result = trace_flow(&g, "A", "C", 3, 20);
         assert_eq!(result.paths.len(), 2, "expected 2 paths (A->B->C, A->D->C)");
@@ -412,8 +412,8 @@ mod tests {
                     ..Default::default()
                 },
             );
-            g.add_calls_edge(a, b);
-            g.add_calls_edge(b, c);
+            g.add_call_site(a, b, 1);
+            g.add_call_site(b, c, 1);
         }
 
         let result = trace_flow(&g, "A", "C", 3, 20);
@@ -459,9 +459,9 @@ mod tests {
             },
         );
 
-        g.add_calls_edge(a, b);
-        g.add_calls_edge(b, c);
-        g.add_calls_edge(c, a); // cycle back
+        g.add_call_site(a, b, 1);
+        g.add_call_site(b, c, 1);
+        g.add_call_site(c, a, 1); // cycle back
 
         // Must not hang — cycle safety via per-path visited set.
         let result = trace_flow(&g, "A", "C", 3, 20);
@@ -501,7 +501,7 @@ mod tests {
                     ..Default::default()
                 },
             );
-            g.add_calls_edge(prev, next);
+            g.add_call_site(prev, next, 1);
             prev = next;
         }
 
@@ -554,7 +554,7 @@ mod tests {
             },
         );
 
-        g.add_calls_edge(a, b);
+        g.add_call_site(a, b, 1);
         g.add_resolved_import(fa, fb, "./b");
 
         // At minimum, the Calls path funcA -> funcB should be traced.
@@ -0,0 +1,236 @@
+//! Hotspot ranking (`code-graph hotspots`).
+//!
+//! Ranks source files, or symbols, by how many other files depend on them: the files that
+//! import a file, or the files that call, bind, render, extend, or otherwise reference a
+//! symbol. With git churn, the score is `dependents * commits`, so a widely used module
+//! that also changes often — the riskiest kind to touch — rises to the top, while a
+//! stable one drops.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::Result;
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+use crate::query::util::find_containing_file_idx;
+
+/// Whether `hotspots` ranks files or symbols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotspotLevel {
+    File,
+    Symbol,
+}
+
+/// One ranked file or symbol.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Hotspot {
+    /// Project-relative path for a file, or the symbol name.
+    pub name: String,
+    /// `file` or the symbol kind.
+    pub kind: String,
+    /// Project-relative path of the (defining) file.
+    pub file: String,
+    /// 1-based definition line of a symbol; `None` for a file.
+    pub line: Option<usize>,
+    /// Distinct other files that depend on it.
+    pub dependents: usize,
+    /// Commits touching the (defining) file among the ones read; `None` without churn.
+    pub churn: Option<usize>,
+    /// `dependents`, multiplied by `churn` when churn is read.
+    pub score: usize,
+}
+
+/// Result of `hotspots`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HotspotReport {
+    /// Commits read for churn; `None` without churn.
+    pub commits_analyzed: Option<usize>,
+    /// Highest score first.
+    pub hotspots: Vec<Hotspot>,
+}
+
+/// Rank the project's files or symbols by dependents, weighted by the churn of the last
+/// `churn_commits` commits when given, keeping the top `limit`.
+pub fn find_hotspots(
+    graph: &CodeGraph,
+    root: &Path,
+    level: HotspotLevel,
+    churn_commits: Option<usize>,
+    limit: usize,
+) -> Result<HotspotReport> {
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+
+    let churn: Option<(usize, HashMap<String, usize>)> = match churn_commits {
+        Some(commits) => {
+            let sets = crate::query::coupling::commit_file_sets(root, commits)?;
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for file in sets.iter().flatten() {
+                *counts.entry(file.clone()).or_default() += 1;
+            }
+            Some((sets.len(), counts))
+        }
+        None => None,
+    };
+
+    let mut hotspots: Vec<Hotspot> = Vec::new();
+    for idx in graph.graph.node_indices() {
+        let (name, kind, file, line, dependents) = match &graph.graph[idx] {
+            GraphNode::File(fi) if level == HotspotLevel::File => {
+                if fi.kind != FileKind::Source {
+                    continue;
+                }
+                let file = relative(&fi.path);
+                (
+                    file.clone(),
+                    "file".to_string(),
+                    file,
+                    None,
+                    file_dependents(graph, idx),
+                )
+            }
+            GraphNode::Symbol(info) if level == HotspotLevel::Symbol => {
+                let Some(file_idx) = find_containing_file_idx(graph, idx) else {
+                    continue;
+                };
+                let GraphNode::File(fi) = &graph.graph[file_idx] else {
+                    continue;
+                };
+                (
+                    info.name.clone(),
+                    crate::query::find::kind_to_str(&info.kind).to_string(),
+                    relative(&fi.path),
+                    Some(info.line),
+                    symbol_dependents(graph, idx, file_idx),
+                )
+            }
+            _ => continue,
+        };
+        if dependents == 0 {
+            continue;
+        }
+        let churn = churn
+            .as_ref()
+            .map(|(_, counts)| counts.get(&file).copied().unwrap_or(0));
+        hotspots.push(Hotspot {
+            name,
+            kind,
+            file,
+            line,
+            dependents,
+            churn,
+            score: dependents * churn.unwrap_or(1),
+        });
+    }
+
+    hotspots.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| b.dependents.cmp(&a.dependents))
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    hotspots.truncate(limit);
+
+    Ok(HotspotReport {
+        commits_analyzed: churn.map(|(commits, _)| commits),
+        hotspots,
+    })
+}
+
+/// Distinct files importing `file_idx`.
+fn file_dependents(graph: &CodeGraph, file_idx: NodeIndex) -> usize {
+    graph
+        .graph
+        .edges_directed(file_idx, Direction::Incoming)
+        .filter(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+        .map(|e| e.source())
+        .filter(|&src| src != file_idx && matches!(graph.graph[src], GraphNode::File(_)))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Distinct files, other than its own, that reference `sym_idx` through a call, named
+/// import, render, inheritance, override, or table reference.
+fn symbol_dependents(graph: &CodeGraph, sym_idx: NodeIndex, own_file: NodeIndex) -> usize {
+    graph
+        .graph
+        .edges_directed(sym_idx, Direction::Incoming)
+        .filter(|e| {
+            matches!(
+                e.weight(),
+                EdgeKind::Calls { .. }
+                    | EdgeKind::ImportsSymbol { .. }
+                    | EdgeKind::Renders
+                    | EdgeKind::Extends
+                    | EdgeKind::Implements
+                    | EdgeKind::Overrides
+                    | EdgeKind::ReferencesTable
+            )
+        })
+        .filter_map(|e| match graph.graph[e.source()] {
+            GraphNode::File(_) => Some(e.source()),
+            _ => find_containing_file_idx(graph, e.source()),
+        })
+        .filter(|&file| file != own_file)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+
+    #[test]
+    fn test_hotspots_rank_by_distinct_dependents() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let db = graph.add_file(root.join("db.ts"), "typescript");
+        let log = graph.add_file(root.join("log.ts"), "typescript");
+        let a = graph.add_file(root.join("a.ts"), "typescript");
+        let b = graph.add_file(root.join("b.ts"), "typescript");
+        let query = graph.add_symbol(
+            db,
+            SymbolInfo {
+                name: "query".into(),
+                kind: SymbolKind::Function,
+                line: 3,
+                ..Default::default()
+            },
+        );
+        graph.add_resolved_import(a, db, "./db");
+        graph.add_resolved_import(b, db, "./db");
+        graph.add_resolved_import(a, log, "./log");
+        graph.add_call_site(a, query, 1);
+        graph.add_call_site(a, query, 2);
+        graph.add_call_site(db, query, 9);
+
+        let report = find_hotspots(&graph, &root, HotspotLevel::File, None, 10).unwrap();
+        let ranked: Vec<(&str, usize)> = report
+            .hotspots
+            .iter()
+            .map(|h| (h.name.as_str(), h.score))
+            .collect();
+        assert_eq!(ranked, vec![("db.ts", 2), ("log.ts", 1)]);
+        assert!(report.commits_analyzed.is_none());
+
+        let report = find_hotspots(&graph, &root, HotspotLevel::Symbol, None, 1).unwrap();
+        assert_eq!(report.hotspots.len(), 1);
+        assert_eq!(report.hotspots[0].name, "query");
+        assert_eq!(report.hotspots[0].line, Some(3));
+        // Two calls from a.ts count once; db.ts calling its own function does not count.
+        assert_eq!(report.hotspots[0].dependents, 1);
+    }
+}
@@ -0,0 +1,433 @@
+//! Hover payload for editor plugins: everything a popup shows for the identifier under
+//! the cursor, in one query against the cached graph.
+//!
+//! The identifier at `file:line:col` is resolved in order to a symbol declared on that
+//! line, a callee of the enclosing symbol, a symbol of the same file, a symbol the file
+//! imports by name, and finally any symbol of that name in the project (exported first).
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow, bail};
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::{CodeGraph, edge::EdgeKind, node::GraphNode};
+use crate::query::api_diff::declaration_header;
+use crate::query::find::kind_to_str;
+use crate::query::util::find_containing_file_idx;
+
+/// A symbol calling the hovered symbol, with the number of call sites.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HoverCaller {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub line: usize,
+    pub count: usize,
+}
+
+/// The hover bundle for one symbol.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HoverInfo {
+    pub name: String,
+    pub kind: String,
+    /// Declaration header read from the source (`pub fn run(cli: Cli) -> Result<()>`).
+    pub signature: Option<String>,
+    /// Doc comment above the declaration, or a Python docstring, with comment markers
+    /// stripped.
+    pub doc: Option<String>,
+    /// Project-relative path of the defining file.
+    pub file: String,
+    /// 1-based line and column of the definition.
+    pub line: usize,
+    pub col: usize,
+    /// Number of import and call references (as counted by `refs`).
+    pub references: usize,
+    /// Callers with the most call sites first.
+    pub callers: Vec<HoverCaller>,
+}
+
+/// Parse `file:line:col` (1-based line and column).
+pub fn parse_position(position: &str) -> Result<(PathBuf, usize, usize)> {
+    let mut parts = position.rsplitn(3, ':');
+    let (Some(col), Some(line), Some(file)) = (parts.next(), parts.next(), parts.next()) else {
+        bail!("expected <file>:<line>:<col>");
+    };
+    let line: usize = line
+        .parse()
+        .map_err(|_| anyhow!("invalid line '{}'", line))?;
+    let col: usize = col
+        .parse()
+        .map_err(|_| anyhow!("invalid column '{}'", col))?;
+    if file.is_empty() || line == 0 || col == 0 {
+        bail!("expected <file>:<line>:<col> with 1-based line and column");
+    }
+    Ok((PathBuf::from(file), line, col))
+}
+
+/// The identifier covering 1-based `col` of `text`, if any.
+fn identifier_at(text: &str, col: usize) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let at = col.checked_sub(1)?;
+    if !chars.get(at).is_some_and(|&c| is_ident(c)) {
+        return None;
+    }
+    let start = (0..at)
+        .rev()
+        .take_while(|&i| is_ident(chars[i]))
+        .last()
+        .unwrap_or(at);
+    let end = (at..chars.len())
+        .take_while(|&i| is_ident(chars[i]))
+        .last()
+        .unwrap_or(at);
+    Some(chars[start..=end].iter().collect())
+}
+
+/// Every symbol of `file_idx`, including children, with its declaration line span.
+fn file_symbols(graph: &CodeGraph, file_idx: NodeIndex) -> Vec<(NodeIndex, usize, usize)> {
+    let mut symbols = Vec::new();
+    let mut stack: Vec<NodeIndex> = graph
+        .graph
+        .edges(file_idx)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .map(|e| e.target())
+        .collect();
+    while let Some(idx) = stack.pop() {
+        let GraphNode::Symbol(sym) = &graph.graph[idx] else {
+            continue;
+        };
+        symbols.push((idx, sym.line, sym.line_end.max(sym.line)));
+        stack.extend(
+            graph
+                .graph
+                .edges_directed(idx, Direction::Incoming)
+                .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+                .map(|e| e.source()),
+        );
+    }
+    symbols.sort_by_key(|&(idx, line, _)| (line, idx));
+    symbols
+}
+
+fn symbol_name(graph: &CodeGraph, idx: NodeIndex) -> Option<&str> {
+    match &graph.graph[idx] {
+        GraphNode::Symbol(sym) => Some(sym.name.as_str()),
+        _ => None,
+    }
+}
+
+/// Resolve `name`, written on 1-based `line` of `file_idx`, to a symbol node.
+pub fn resolve_symbol(
+    graph: &CodeGraph,
+    file_idx: NodeIndex,
+    line: usize,
+    name: &str,
+) -> Option<NodeIndex> {
+    let symbols = file_symbols(graph, file_idx);
+    let named = |idx: &NodeIndex| symbol_name(graph, *idx) == Some(name);
+
+    // The declaration itself.
+    if let Some(&(idx, _, _)) = symbols
+        .iter()
+        .find(|(idx, start, _)| *start == line && named(idx))
+    {
+        return Some(idx);
+    }
+
+    // A call made by an enclosing symbol, innermost first.
+    let mut enclosing: Vec<&(NodeIndex, usize, usize)> = symbols
+        .iter()
+        .filter(|(_, start, end)| (*start..=*end).contains(&line))
+        .collect();
+    enclosing.sort_by_key(|(_, start, end)| end - start);
+    for &&(caller, _, _) in &enclosing {
+        let callee = graph
+            .graph
+            .edges(caller)
+            .filter(|e| matches!(e.weight(), EdgeKind::Calls { .. }))
+            .map(|e| e.target())
+            .find(named);
+        if callee.is_some() {
+            return callee;
+        }
+    }
+
+    // A symbol of the same file, then one the file imports by name.
+    if let Some(&(idx, _, _)) = symbols.iter().find(|(idx, _, _)| named(idx)) {
+        return Some(idx);
+    }
+    let imported = graph
+        .graph
+        .edges(file_idx)
+        .filter(|e| matches!(e.weight(), EdgeKind::ImportsSymbol { .. }))
+        .map(|e| e.target())
+        .find(named);
+    if imported.is_some() {
+        return imported;
+    }
+
+    // Anywhere in the project, exported symbols first.
+    let mut candidates: Vec<(bool, PathBuf, usize, NodeIndex)> = graph
+        .symbol_index
+        .get(name)?
+        .iter()
+        .filter_map(|&idx| {
+            let GraphNode::Symbol(sym) = &graph.graph[idx] else {
+                return None;
+            };
+            let file = find_containing_file_idx(graph, idx)?;
+            let GraphNode::File(fi) = &graph.graph[file] else {
+                return None;
+            };
+            Some((!sym.is_exported, fi.path.clone(), sym.line, idx))
+        })
+        .collect();
+    candidates.sort();
+    candidates.first().map(|c| c.3)
+}
+
+/// The doc comment directly above 1-based `line`, skipping attributes and decorators.
+pub(crate) fn leading_doc(lines: &[&str], line: usize) -> Option<String> {
+    let mut doc: Vec<String> = Vec::new();
+    for text in lines.get(..line.saturating_sub(1))?.iter().rev() {
+        let text = text.trim();
+        if text.starts_with("#[") || text.starts_with("#![") || text.starts_with('@') {
+            continue;
+        }
+        let stripped = ["/**", "///", "//", "/*", "*/", "*", "#", "--"]
+            .iter()
+            .find_map(|marker| text.strip_prefix(marker));
+        match stripped {
+            Some(rest) if !text.starts_with("//!") => {
+                let rest = rest.trim_end_matches("*/").trim();
+                doc.push(rest.to_string());
+            }
+            _ => break,
+        }
+    }
+    doc.reverse();
+    tidy_doc(doc)
+}
+
+/// The Python docstring opening the body of the `def`/`class` on 1-based `line`.
+pub(crate) fn docstring(lines: &[&str], line: usize) -> Option<String> {
+    let mut body = lines.get(line..)?.iter().map(|l| l.trim());
+    let first = body.find(|l| !l.is_empty())?;
+    let quote = ["\"\"\"", "'''"]
+        .into_iter()
+        .find(|q| first.starts_with(q))?;
+    let first = &first[quote.len()..];
+    if let Some(end) = first.find(quote) {
+        return tidy_doc(vec![first[..end].to_string()]);
+    }
+    let mut doc = vec![first.to_string()];
+    for text in body {
+        if let Some(end) = text.find(quote) {
+            doc.push(text[..end].to_string());
+            break;
+        }
+        doc.push(text.to_string());
+    }
+    tidy_doc(doc)
+}
+
+/// Join doc lines, dropping blank lines at either end.
+fn tidy_doc(lines: Vec<String>) -> Option<String> {
+    let text = lines.join("\n");
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Build the hover bundle for the identifier at 1-based `line`:`col` of `file`.
+///
+/// `file` may be absolute or relative to `root`. At most `max_callers` callers are listed.
+pub fn hover(
+    graph: &CodeGraph,
+    root: &Path,
+    file: &Path,
+    line: usize,
+    col: usize,
+    max_callers: usize,
+) -> Result<HoverInfo> {
+    let abs_path = if file.is_absolute() {
+        file.to_path_buf()
+    } else {
+        root.join(file)
+    };
+    let file_idx = *graph
+        .file_index
+        .get(&abs_path)
+        .ok_or_else(|| anyhow!("file not indexed: {}", file.display()))?;
+    let source = std::fs::read_to_string(&abs_path)?;
+    let name = source
+        .lines()
+        .nth(line - 1)
+        .and_then(|text| identifier_at(text, col))
+        .ok_or_else(|| anyhow!("no identifier at {}:{}:{}", file.display(), line, col))?;
+    let sym_idx = resolve_symbol(graph, file_idx, line, &name)
+        .ok_or_else(|| anyhow!("no symbol named '{}' found", name))?;
+    let GraphNode::Symbol(sym) = &graph.graph[sym_idx] else {
+        bail!("no symbol named '{}' found", name);
+    };
+
+    let def_path = find_containing_file_idx(graph, sym_idx)
+        .and_then(|idx| match &graph.graph[idx] {
+            GraphNode::File(fi) => Some(fi.path.clone()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let def_source = if def_path == abs_path {
+        Some(source)
+    } else {
+        std::fs::read_to_string(&def_path).ok()
+    };
+    let (signature, doc) = match &def_source {
+        Some(text) => {
+            let lines: Vec<&str> = text.lines().collect();
+            let signature = declaration_header(&lines, sym.line, sym.line_end);
+            let doc = leading_doc(&lines, sym.line).or_else(|| {
+                if def_path.extension().is_some_and(|e| e == "py") {
+                    docstring(&lines, sym.line)
+                } else {
+                    None
+                }
+            });
+            ((!signature.is_empty()).then_some(signature), doc)
+        }
+        None => (None, None),
+    };
+
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let mut callers: Vec<HoverCaller> = graph
+        .graph
+        .edges_directed(sym_idx, Direction::Incoming)
+        .filter(|e| matches!(e.weight(), EdgeKind::Calls { .. }))
+        .filter_map(|e| {
+            let GraphNode::Symbol(caller) = &graph.graph[e.source()] else {
+                return None;
+            };
+            let file = find_containing_file_idx(graph, e.source())?;
+            let GraphNode::File(fi) = &graph.graph[file] else {
+                return None;
+            };
+            Some(HoverCaller {
+                name: caller.name.clone(),
+                kind: kind_to_str(&caller.kind).to_string(),
+                file: relative(&fi.path),
+                line: caller.line,
+                count: e.weight().weight(),
+            })
+        })
+        .collect();
+    callers.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.file.cmp(&b.file))
+            .then(a.line.cmp(&b.line))
+    });
+    callers.truncate(max_callers);
+
+    Ok(HoverInfo {
+        name: sym.name.clone(),
+        kind: kind_to_str(&sym.kind).to_string(),
+        signature,
+        doc,
+        file: relative(&def_path),
+        line: sym.line,
+        col: sym.col + 1,
+        references: crate::query::refs::find_refs(graph, &sym.name, &[sym_idx], root).len(),
+        callers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+
+    #[test]
+    fn test_parse_position() {
+        let (file, line, col) = parse_position("src/main.rs:12:5").unwrap();
+        assert_eq!((file, line, col), (PathBuf::from("src/main.rs"), 12, 5));
+        assert!(parse_position("src/main.rs:12").is_err());
+        assert!(parse_position("src/main.rs:0:1").is_err());
+        assert!(parse_position("src/main.rs:x:1").is_err());
+    }
+
+    #[test]
+    fn test_identifier_at() {
+        let text = "    let graph = build_graph(&root);";
+        assert_eq!(identifier_at(text, 17).as_deref(), Some("build_graph"));
+        assert_eq!(identifier_at(text, 27).as_deref(), Some("build_graph"));
+        assert_eq!(identifier_at(text, 28), None);
+        assert_eq!(identifier_at(text, 99), None);
+    }
+
+    #[test]
+    fn test_leading_doc_and_docstring() {
+        let rust = [
+            "/// Parse the input.",
+            "///",
+            "/// Returns the tree.",
+            "#[inline]",
+            "pub fn parse() {}",
+        ];
+        assert_eq!(
+            leading_doc(&rust, 5).as_deref(),
+            Some("Parse the input.\n\nReturns the tree.")
+        );
+        let ts = [
+            "/**",
+            " * Render a button.",
+            " */",
+            "export function Button() {}",
+        ];
+        assert_eq!(leading_doc(&ts, 4).as_deref(), Some("Render a button."));
+        assert_eq!(leading_doc(&["", "fn bare() {}"], 2), None);
+
+        let py = [
+            "def load(path):",
+            "    \"\"\"Load a config",
+            "    file.\"\"\"",
+            "    pass",
+        ];
+        assert_eq!(docstring(&py, 1).as_deref(), Some("Load a config\nfile."));
+    }
+
+    #[test]
+    fn test_resolve_symbol() {
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(PathBuf::from("/proj/src/app.ts"), "typescript");
+        let utils = graph.add_file(PathBuf::from("/proj/src/utils.ts"), "typescript");
+        let symbol = |name: &str, line: usize, line_end: usize| SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line,
+            line_end,
+            is_exported: true,
+            ..Default::default()
+        };
+        let main = graph.add_symbol(app, symbol("main", 1, 10));
+        let local_format = graph.add_symbol(app, symbol("format", 12, 14));
+        let format = graph.add_symbol(utils, symbol("format", 1, 3));
+        graph.add_call_site(main, format, 4);
+
+        // Declaration line, callee of the enclosing symbol, same-file fallback.
+        assert_eq!(resolve_symbol(&graph, app, 1, "main"), Some(main));
+        assert_eq!(resolve_symbol(&graph, app, 4, "format"), Some(format));
+        assert_eq!(
+            resolve_symbol(&graph, app, 13, "format"),
+            Some(local_format)
+        );
+        assert_eq!(resolve_symbol(&graph, utils, 2, "main"), Some(main));
+        assert_eq!(resolve_symbol(&graph, app, 4, "missing"), None);
+    }
+}
@@ -0,0 +1,147 @@
+//! Byte-identical source files (vendored copies).
+//!
+//! Files are grouped by the content hash recorded when they were parsed. In each group
+//! the copy with the shortest path is canonical; the others are reported as copies and
+//! skipped by dead-code and clone detection, so a vendored library checked in twice is
+//! analysed once.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use petgraph::stable_graph::NodeIndex;
+
+use crate::graph::CodeGraph;
+use crate::graph::node::{FileInfo, GraphNode};
+
+/// Files shorter than this are never grouped: empty `__init__.py` files and one-line
+/// re-export stubs are identical by nature, not vendored.
+const MIN_LINES: usize = 5;
+
+/// A set of byte-identical files.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct IdenticalFiles {
+    /// The copy that is analysed (shortest path, then lexicographically first).
+    pub canonical: PathBuf,
+    /// The other copies, relative to the project root, sorted.
+    pub copies: Vec<PathBuf>,
+    /// Lines in each copy.
+    pub lines: usize,
+}
+
+/// Byte-identical files, each group sorted canonical first.
+fn groups(graph: &CodeGraph) -> Vec<Vec<&FileInfo>> {
+    let mut by_hash: HashMap<u64, Vec<&FileInfo>> = HashMap::new();
+    for idx in graph.file_index.values() {
+        if let GraphNode::File(fi) = &graph.graph[*idx]
+            && let Some(hash) = fi.content_hash
+            && fi.line_count >= MIN_LINES
+        {
+            by_hash.entry(hash).or_default().push(fi);
+        }
+    }
+    by_hash
+        .into_values()
+        .filter(|files| files.len() > 1)
+        .map(|mut files| {
+            files.sort_by(|a, b| {
+                a.path
+                    .components()
+                    .count()
+                    .cmp(&b.path.components().count())
+                    .then_with(|| a.path.cmp(&b.path))
+            });
+            files
+        })
+        .collect()
+}
+
+/// Group the graph's source files by content. Paths are relative to `root`; groups are
+/// sorted by canonical path.
+pub fn identical_file_groups(graph: &CodeGraph, root: &Path) -> Vec<IdenticalFiles> {
+    let relative = |p: &Path| p.strip_prefix(root).unwrap_or(p).to_path_buf();
+    let mut groups: Vec<IdenticalFiles> = groups(graph)
+        .into_iter()
+        .map(|files| {
+            let mut copies: Vec<PathBuf> = files[1..].iter().map(|f| relative(&f.path)).collect();
+            copies.sort();
+            IdenticalFiles {
+                canonical: relative(&files[0].path),
+                copies,
+                lines: files[0].line_count,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| a.canonical.cmp(&b.canonical));
+    groups
+}
+
+/// Absolute paths of every non-canonical copy, for analyses that should skip them.
+pub fn identical_copies(graph: &CodeGraph) -> HashSet<PathBuf> {
+    groups(graph)
+        .into_iter()
+        .flat_map(|files| files.into_iter().skip(1).map(|f| f.path.clone()))
+        .collect()
+}
+
+/// File node of each canonical file mapped to the file nodes of its copies. Analyses
+/// that skip the copies use it to count usage of a copy towards its canonical file.
+pub fn copies_by_canonical(graph: &CodeGraph) -> HashMap<NodeIndex, Vec<NodeIndex>> {
+    groups(graph)
+        .into_iter()
+        .map(|files| {
+            let node = |f: &FileInfo| graph.file_index[&f.path];
+            (node(files[0]), files[1..].iter().map(|f| node(f)).collect())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(graph: &mut CodeGraph, path: &str, lines: usize, hash: u64) {
+        let idx = graph.add_file(PathBuf::from(path), "typescript");
+        graph.set_file_source(idx, lines, Some(hash));
+    }
+
+    #[test]
+    fn test_groups_identical_files_with_shortest_path_canonical() {
+        let mut graph = CodeGraph::new();
+        add(&mut graph, "/p/vendor/a/lib/x.ts", 20, 7);
+        add(&mut graph, "/p/lib/x.ts", 20, 7);
+        add(&mut graph, "/p/vendor/b/lib/x.ts", 20, 7);
+        add(&mut graph, "/p/src/other.ts", 20, 8);
+
+        let groups = identical_file_groups(&graph, Path::new("/p"));
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].canonical, PathBuf::from("lib/x.ts"));
+        assert_eq!(
+            groups[0].copies,
+            vec![
+                PathBuf::from("vendor/a/lib/x.ts"),
+                PathBuf::from("vendor/b/lib/x.ts")
+            ]
+        );
+        assert_eq!(groups[0].lines, 20);
+
+        let copies = identical_copies(&graph);
+        assert_eq!(copies.len(), 2);
+        assert!(copies.contains(Path::new("/p/vendor/a/lib/x.ts")));
+        assert!(!copies.contains(Path::new("/p/lib/x.ts")));
+
+        let nodes = copies_by_canonical(&graph);
+        let canonical = graph.file_index[Path::new("/p/lib/x.ts")];
+        assert_eq!(nodes[&canonical].len(), 2);
+    }
+
+    #[test]
+    fn test_short_and_unhashed_files_are_not_grouped() {
+        let mut graph = CodeGraph::new();
+        add(&mut graph, "/p/a/__init__.py", 1, 3);
+        add(&mut graph, "/p/b/__init__.py", 1, 3);
+        graph.add_file(PathBuf::from("/p/c.ts"), "typescript");
+        graph.add_file(PathBuf::from("/p/d.ts"), "typescript");
+
+        assert!(identical_file_groups(&graph, Path::new("/p")).is_empty());
+    }
+}
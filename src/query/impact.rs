@@ -1,9 +1,10 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::PoisonError;
 
 use petgraph::Direction;
 use petgraph::stable_graph::NodeIndex;
-use petgraph::visit::EdgeRef;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 
 use crate::graph::{CodeGraph, edge::EdgeKind, node::GraphNode};
 
@@ -107,16 +108,278 @@ pub fn classify_risk(
     }
 }
 
+/// Affected files rolled up to one directory, for `impact --by-dir`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DirImpact {
+    /// Directory relative to the project root (`.` for the root).
+    pub dir: String,
+    /// Number of affected files directly in the directory.
+    pub files: usize,
+    /// Smallest BFS depth among those files.
+    pub depth: usize,
+}
+
+/// Roll impact results up to their parent directories, nearest directories first.
+pub fn group_by_dir(results: &[ImpactResult], project_root: &Path) -> Vec<DirImpact> {
+    let mut dirs: HashMap<String, DirImpact> = HashMap::new();
+    for r in results {
+        let dir = crate::query::util::relative_dir(&r.file_path, project_root);
+        let entry = dirs.entry(dir.clone()).or_insert(DirImpact {
+            dir,
+            files: 0,
+            depth: r.depth,
+        });
+        entry.files += 1;
+        entry.depth = entry.depth.min(r.depth);
+    }
+    let mut dirs: Vec<DirImpact> = dirs.into_values().collect();
+    dirs.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.dir.cmp(&b.dir)));
+    dirs
+}
+
+/// Commits of history read for the churn part of `impact --estimate`.
+const ESTIMATE_COMMITS: usize = 500;
+
+/// Effort weights for `impact --estimate`, in rough "units" of work: every file costs
+/// a base unit to open and review, plus its size, symbol count, and recent churn (capped,
+/// so one hot file does not dominate). Untested source files cost half again as much,
+/// since their changes must be verified by hand.
+const EFFORT_PER_FILE: f64 = 1.0;
+const EFFORT_PER_100_LOC: f64 = 1.0;
+const EFFORT_PER_SYMBOL: f64 = 0.2;
+const EFFORT_PER_COMMIT: f64 = 0.1;
+const EFFORT_MAX_COMMITS: usize = 20;
+const EFFORT_UNTESTED_FACTOR: f64 = 1.5;
+
+/// Refactor-effort estimate for one affected file, for `impact --estimate`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileEffort {
+    /// File path relative to the project root.
+    pub file: String,
+    /// BFS depth of the file in the blast radius.
+    pub depth: usize,
+    /// Symbols defined in the file.
+    pub symbols: usize,
+    /// Lines in the file.
+    pub loc: usize,
+    /// Commits touching the file in recent history; `None` outside a git repository.
+    pub churn: Option<usize>,
+    /// Whether a test file tests the file or one of its symbols; `None` for test files.
+    pub tested: Option<bool>,
+    /// Weighted effort score.
+    pub effort: f64,
+}
+
+/// Refactor-effort estimate for a whole blast radius.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EffortEstimate {
+    /// Affected files, most effort first.
+    pub files: Vec<FileEffort>,
+    /// Sum of the per-file scores.
+    pub total: f64,
+}
+
+/// Score each affected file by symbol count, size, churn, and test coverage into a rough
+/// refactor-effort number. See the `EFFORT_*` weights.
+pub fn estimate_effort(
+    graph: &CodeGraph,
+    results: &[ImpactResult],
+    project_root: &Path,
+) -> EffortEstimate {
+    let churn: Option<HashMap<String, usize>> =
+        crate::query::coupling::commit_file_sets(project_root, ESTIMATE_COMMITS)
+            .ok()
+            .map(|commits| {
+                let mut counts = HashMap::new();
+                for file in commits.into_iter().flatten() {
+                    *counts.entry(file).or_default() += 1;
+                }
+                counts
+            });
+
+    let mut files: Vec<FileEffort> = results
+        .iter()
+        .map(|r| {
+            let file = r
+                .file_path
+                .strip_prefix(project_root)
+                .unwrap_or(&r.file_path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let file_idx = graph.file_index.get(&r.file_path).copied();
+            let symbols: Vec<NodeIndex> = file_idx
+                .map(|idx| {
+                    graph
+                        .graph
+                        .edges_directed(idx, Direction::Outgoing)
+                        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+                        .map(|e| e.target())
+                        .filter(|&t| matches!(graph.graph[t], GraphNode::Symbol(_)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let is_test = file_idx
+                .is_some_and(|idx| matches!(&graph.graph[idx], GraphNode::File(fi) if fi.is_test));
+            let tested = (!is_test).then(|| {
+                file_idx
+                    .into_iter()
+                    .chain(symbols.iter().copied())
+                    .any(|idx| {
+                        graph
+                            .graph
+                            .edges_directed(idx, Direction::Incoming)
+                            .any(|e| matches!(e.weight(), EdgeKind::Tests))
+                    })
+            });
+            let loc = std::fs::read_to_string(&r.file_path)
+                .map(|s| s.lines().count())
+                .unwrap_or(0);
+            let churn = churn
+                .as_ref()
+                .map(|counts| counts.get(&file).copied().unwrap_or(0));
+
+            let mut effort = EFFORT_PER_FILE
+                + EFFORT_PER_100_LOC * loc as f64 / 100.0
+                + EFFORT_PER_SYMBOL * symbols.len() as f64
+                + EFFORT_PER_COMMIT * churn.unwrap_or(0).min(EFFORT_MAX_COMMITS) as f64;
+            if tested == Some(false) {
+                effort *= EFFORT_UNTESTED_FACTOR;
+            }
+            FileEffort {
+                file,
+                depth: r.depth,
+                symbols: symbols.len(),
+                loc,
+                churn,
+                tested,
+                effort: (effort * 10.0).round() / 10.0,
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| {
+        b.effort
+            .total_cmp(&a.effort)
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    let total = (files.iter().map(|f| f.effort).sum::<f64>() * 10.0).round() / 10.0;
+    EffortEstimate { files, total }
+}
+
+/// Narrow blast-radius `results` to test files, for `impact --tests`.
+///
+/// Tests with a `Tests` edge to a queried symbol (or its enclosing top-level symbol) or to
+/// a file defining one are marked HIGH confidence as direct tests.
+pub fn affected_tests(
+    graph: &CodeGraph,
+    symbol_indices: &[NodeIndex],
+    results: Vec<ImpactResult>,
+) -> Vec<ImpactResult> {
+    let subjects: HashSet<NodeIndex> = symbol_indices
+        .iter()
+        .flat_map(|&sym_idx| {
+            [
+                Some(sym_idx),
+                Some(top_level_symbol(graph, sym_idx)),
+                find_containing_file_idx(graph, sym_idx),
+            ]
+        })
+        .flatten()
+        .collect();
+
+    results
+        .into_iter()
+        .filter_map(|mut r| {
+            let &file_idx = graph.file_index.get(&r.file_path)?;
+            if !matches!(&graph.graph[file_idx], GraphNode::File(fi) if fi.is_test) {
+                return None;
+            }
+            let direct = graph
+                .graph
+                .edges_directed(file_idx, Direction::Outgoing)
+                .any(|e| matches!(e.weight(), EdgeKind::Tests) && subjects.contains(&e.target()));
+            if direct {
+                r.confidence = ConfidenceTier::High;
+                r.basis = "test imports it directly".to_string();
+            }
+            Some(r)
+        })
+        .collect()
+}
+
+/// Which dependencies carry impact from a file to its dependents, for `impact --edges`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImpactEdges {
+    /// Resolved imports (`ResolvedImport`).
+    pub imports: bool,
+    /// Calls into the file's symbols, whether or not the caller imports the file.
+    pub calls: bool,
+    /// Re-exports: `export * from` barrels and aliased re-exports (`ExportsAs`).
+    pub reexports: bool,
+}
+
+impl Default for ImpactEdges {
+    fn default() -> Self {
+        ImpactEdges {
+            imports: true,
+            calls: false,
+            reexports: false,
+        }
+    }
+}
+
+/// Tuning for [`blast_radius_with`]. The default is the plain [`blast_radius`]: the full
+/// transitive closure over imports.
+#[derive(Debug, Clone, Default)]
+pub struct ImpactOptions {
+    /// Stop the walk at this depth (1 = direct dependents only).
+    pub max_depth: Option<usize>,
+    pub edges: ImpactEdges,
+    /// Skip type-only imports (`import type`), which are erased at compile time.
+    pub runtime_only: bool,
+    /// Leave test files out of the result.
+    pub exclude_tests: bool,
+}
+
 /// Compute the blast radius of changing the given symbols.
 ///
-/// Performs a custom BFS on INCOMING `ResolvedImport` edges only (not Calls, Contains, etc.),
-/// starting from the file(s) that define the queried symbols.
+/// Shorthand for [`blast_radius_with`] following every import, with `runtime_only` as given.
+pub fn blast_radius(
+    graph: &CodeGraph,
+    symbol_indices: &[NodeIndex],
+    project_root: &Path,
+    runtime_only: bool,
+) -> Vec<ImpactResult> {
+    blast_radius_with(
+        graph,
+        symbol_indices,
+        project_root,
+        &ImpactOptions {
+            runtime_only,
+            ..Default::default()
+        },
+    )
+}
+
+/// Compute the blast radius of changing the given symbols, tuned by `options`.
+///
+/// Performs a custom BFS on INCOMING dependency edges of the kinds in `options.edges`
+/// (by default `ResolvedImport` only), starting from the file(s) that define the queried
+/// symbols. Direct importers whose named
+/// imports from a defining file (`ImportsSymbol` edges) all bind other symbols are not
+/// affected and are not followed. Files with a `ReferencesTable`
+/// edge to a queried SQL table/view, and files whose components render a queried
+/// component (`Renders`), are seeded at depth 1. Markdown docs that mention the
+/// symbols (`DocumentedBy`) are flagged at depth 1 as possibly stale documentation.
+/// With `runtime_only`, type-only imports (`import type`) are not followed. Calls and
+/// re-exports out of a defining file only count when they reach a queried symbol (a whole
+/// `export *` always does).
 ///
 /// Returns all transitively dependent files sorted by depth (ascending) then by path.
-pub fn blast_radius(
+pub fn blast_radius_with(
     graph: &CodeGraph,
     symbol_indices: &[NodeIndex],
     project_root: &Path,
+    options: &ImpactOptions,
 ) -> Vec<ImpactResult> {
     let _ = project_root; // kept for API consistency with find_refs
 
@@ -135,35 +398,51 @@ pub fn blast_radius(
         return Vec::new();
     }
 
-    // Step 2: Custom BFS following only incoming ResolvedImport edges (reverse import graph).
-    let mut queue: VecDeque<NodeIndex> = VecDeque::new();
-    let mut visited: HashSet<NodeIndex> = HashSet::new();
-    let mut depths: HashMap<NodeIndex, usize> = HashMap::new();
+    // Named imports bind top-level symbols, so a queried method counts through its class.
+    let bound_symbols: HashSet<NodeIndex> = symbol_indices
+        .iter()
+        .map(|&sym_idx| top_level_symbol(graph, sym_idx))
+        .collect();
 
+    // Step 2: Custom BFS following only incoming ResolvedImport edges (reverse import graph).
     // Seed with starting files at depth 0.
-    for &start_idx in &starting_files {
-        queue.push_back(start_idx);
-        visited.insert(start_idx);
-        depths.insert(start_idx, 0);
-    }
+    let mut seeds: Vec<(NodeIndex, usize)> = starting_files.iter().map(|&f| (f, 0)).collect();
 
-    while let Some(current) = queue.pop_front() {
-        let current_depth = depths[&current];
-
-        // Walk INCOMING edges to find files that import this file.
-        for edge_ref in graph.graph.edges_directed(current, Direction::Incoming) {
-            if matches!(edge_ref.weight(), EdgeKind::ResolvedImport { .. }) {
-                let source = edge_ref.source();
-                // Only follow File nodes — skip Symbol, ExternalPackage, UnresolvedImport.
-                if !visited.contains(&source) && matches!(graph.graph[source], GraphNode::File(_)) {
-                    visited.insert(source);
-                    depths.insert(source, current_depth + 1);
-                    queue.push_back(source);
-                }
+    // Files that query a SQL table/view directly depend on it without importing its file.
+    for &sym_idx in symbol_indices {
+        for edge_ref in graph.graph.edges_directed(sym_idx, Direction::Incoming) {
+            if matches!(edge_ref.weight(), EdgeKind::ReferencesTable) {
+                seeds.push((edge_ref.source(), 1));
             }
         }
     }
 
+    // Files that render a queried component as JSX use it directly, whichever barrel
+    // they import it through.
+    let rendering_files: HashSet<NodeIndex> = symbol_indices
+        .iter()
+        .flat_map(|&sym_idx| graph.graph.edges_directed(sym_idx, Direction::Incoming))
+        .filter(|e| matches!(e.weight(), EdgeKind::Renders))
+        .filter_map(|e| match graph.graph[e.source()] {
+            GraphNode::File(_) => Some(e.source()),
+            _ => find_containing_file_idx(graph, e.source()),
+        })
+        .collect();
+    seeds.extend(rendering_files.iter().map(|&f| (f, 1)));
+
+    let (depths, direct_callers) =
+        walk_dependents(graph, &seeds, options, |current, source, symbol| {
+            !starting_files.contains(&current)
+                || match symbol {
+                    None => imports_any_of(graph, source, current, &bound_symbols),
+                    Some(symbol) => {
+                        symbol_set.contains(&symbol)
+                            || bound_symbols.contains(&top_level_symbol(graph, symbol))
+                    }
+                }
+        });
+    let visited: HashSet<NodeIndex> = depths.keys().copied().collect();
+
     // Step 3: Collect results, excluding the starting files themselves.
     let mut results: Vec<ImpactResult> = visited
         .iter()
@@ -172,14 +451,18 @@ pub fn blast_radius(
             if let GraphNode::File(ref fi) = graph.graph[idx] {
                 let depth = depths[&idx];
 
-                // Check if this file node has an outgoing CALLS edge directly to any of the queried symbols.
-                let has_direct_call =
-                    graph
+                // Check if this file node has an outgoing CALLS (or table reference) edge
+                // directly to any of the queried symbols, or renders one of them.
+                let has_direct_call = rendering_files.contains(&idx)
+                    || direct_callers.contains(&idx)
+                    || graph
                         .graph
                         .edges_directed(idx, Direction::Outgoing)
                         .any(|e| {
-                            matches!(e.weight(), EdgeKind::Calls)
-                                && symbol_set.contains(&e.target())
+                            matches!(
+                                e.weight(),
+                                EdgeKind::Calls { .. } | EdgeKind::ReferencesTable
+                            ) && symbol_set.contains(&e.target())
                         });
 
                 let (confidence, basis) = score_confidence(depth, has_direct_call);
@@ -196,12 +479,331 @@ pub fn blast_radius(
         })
         .collect();
 
+    // Step 4: Markdown docs that mention the queried symbols (or link to their files) may
+    // go stale. They are reported at depth 1 with LOW confidence.
+    let mut docs: HashSet<NodeIndex> = HashSet::new();
+    for &idx in symbol_indices.iter().chain(starting_files.iter()) {
+        for edge_ref in graph.graph.edges_directed(idx, Direction::Outgoing) {
+            if matches!(edge_ref.weight(), EdgeKind::DocumentedBy)
+                && !visited.contains(&edge_ref.target())
+            {
+                docs.insert(edge_ref.target());
+            }
+        }
+    }
+    for doc_idx in docs {
+        if let GraphNode::File(ref fi) = graph.graph[doc_idx] {
+            results.push(ImpactResult {
+                file_path: fi.path.clone(),
+                depth: 1,
+                confidence: ConfidenceTier::Low,
+                basis: "documentation mentions it, may be stale".to_string(),
+            });
+        }
+    }
+
+    if let Some(max_depth) = options.max_depth {
+        results.retain(|r| r.depth <= max_depth);
+    }
+    if options.exclude_tests {
+        results.retain(|r| {
+            !graph
+                .file_index
+                .get(&r.file_path)
+                .is_some_and(|&idx| matches!(&graph.graph[idx], GraphNode::File(fi) if fi.is_test))
+        });
+    }
+
     // Sort by depth ascending, then by file path for deterministic output.
     results.sort_by(|a, b| a.depth.cmp(&b.depth).then(a.file_path.cmp(&b.file_path)));
 
     results
 }
 
+/// Breadth-first walk from `seeds` to dependent files along the edge kinds enabled in
+/// `options`, up to `options.max_depth`.
+///
+/// `follow(current, next, symbol)` can veto a hop; `symbol` is the symbol of `current`
+/// that `next` calls or re-exports under an alias, `None` for an import or `export *`.
+/// Returns the depth of every reached file (seeds included) and the files reached by
+/// calling a symbol of a seed directly.
+fn walk_dependents(
+    graph: &CodeGraph,
+    seeds: &[(NodeIndex, usize)],
+    options: &ImpactOptions,
+    mut follow: impl FnMut(NodeIndex, NodeIndex, Option<NodeIndex>) -> bool,
+) -> (HashMap<NodeIndex, usize>, HashSet<NodeIndex>) {
+    // Calls and aliases point at symbols; index them once by the file defining the target.
+    // Target file -> (dependent file, target symbol, is a call).
+    let mut symbol_dependents: HashMap<NodeIndex, Vec<(NodeIndex, NodeIndex, bool)>> =
+        HashMap::new();
+    if options.edges.calls || options.edges.reexports {
+        for edge_ref in graph.graph.edge_references() {
+            let is_call = match edge_ref.weight() {
+                EdgeKind::Calls { .. } if options.edges.calls => true,
+                EdgeKind::ExportsAs { .. } if options.edges.reexports => false,
+                _ => continue,
+            };
+            let source = match graph.graph[edge_ref.source()] {
+                GraphNode::File(_) => Some(edge_ref.source()),
+                _ => find_containing_file_idx(graph, edge_ref.source()),
+            };
+            if let (Some(source), Some(target_file)) =
+                (source, find_containing_file_idx(graph, edge_ref.target()))
+                && source != target_file
+            {
+                symbol_dependents.entry(target_file).or_default().push((
+                    source,
+                    edge_ref.target(),
+                    is_call,
+                ));
+            }
+        }
+    }
+
+    let mut queue: VecDeque<NodeIndex> = VecDeque::new();
+    let mut depths: HashMap<NodeIndex, usize> = HashMap::new();
+    let mut direct_callers: HashSet<NodeIndex> = HashSet::new();
+    for &(idx, depth) in seeds {
+        if let std::collections::hash_map::Entry::Vacant(entry) = depths.entry(idx) {
+            entry.insert(depth);
+            queue.push_back(idx);
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let current_depth = depths[&current];
+        if options.max_depth.is_some_and(|max| current_depth >= max) {
+            continue;
+        }
+        let mut next_files: Vec<(NodeIndex, Option<NodeIndex>, bool)> = Vec::new();
+        for edge_ref in graph.graph.edges_directed(current, Direction::Incoming) {
+            let followed = match edge_ref.weight() {
+                EdgeKind::ResolvedImport { is_type_only, .. } => {
+                    options.edges.imports && !(options.runtime_only && *is_type_only)
+                }
+                EdgeKind::BarrelReExportAll => options.edges.reexports,
+                _ => false,
+            };
+            if followed {
+                next_files.push((edge_ref.source(), None, false));
+            }
+        }
+        for &(source, symbol, is_call) in symbol_dependents.get(&current).into_iter().flatten() {
+            next_files.push((source, Some(symbol), is_call));
+        }
+
+        for (next, symbol, is_call) in next_files {
+            // Only follow File nodes — skip Symbol, ExternalPackage, UnresolvedImport.
+            if depths.contains_key(&next)
+                || !matches!(graph.graph[next], GraphNode::File(_))
+                || !follow(current, next, symbol)
+            {
+                continue;
+            }
+            if is_call && current_depth == 0 {
+                direct_callers.insert(next);
+            }
+            depths.insert(next, current_depth + 1);
+            queue.push_back(next);
+        }
+    }
+    (depths, direct_callers)
+}
+
+/// Breadth-first walk of the file import graph along `ResolvedImport` edges, starting from
+/// `seeds` (file, depth) pairs: `Incoming` finds importers (dependents), `Outgoing` the
+/// imported files (dependencies). Only file nodes are followed, and only up to
+/// `max_depth` when given. `follow(current, next)` can veto an individual hop. With
+/// `runtime_only`, type-only imports (`import type`) are skipped.
+///
+/// Returns the depth of every reached file, seeds included. A seed listed twice keeps its
+/// first depth.
+pub(crate) fn walk_imports(
+    graph: &CodeGraph,
+    seeds: &[(NodeIndex, usize)],
+    direction: Direction,
+    max_depth: Option<usize>,
+    runtime_only: bool,
+    mut follow: impl FnMut(NodeIndex, NodeIndex) -> bool,
+) -> HashMap<NodeIndex, usize> {
+    let mut queue: VecDeque<NodeIndex> = VecDeque::new();
+    let mut depths: HashMap<NodeIndex, usize> = HashMap::new();
+    for &(idx, depth) in seeds {
+        if let std::collections::hash_map::Entry::Vacant(entry) = depths.entry(idx) {
+            entry.insert(depth);
+            queue.push_back(idx);
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let current_depth = depths[&current];
+        if max_depth.is_some_and(|max| current_depth >= max) {
+            continue;
+        }
+        for edge_ref in graph.graph.edges_directed(current, direction) {
+            let EdgeKind::ResolvedImport { is_type_only, .. } = edge_ref.weight() else {
+                continue;
+            };
+            if runtime_only && *is_type_only {
+                continue;
+            }
+            let next = match direction {
+                Direction::Incoming => edge_ref.source(),
+                Direction::Outgoing => edge_ref.target(),
+            };
+            // Only follow File nodes — skip Symbol, ExternalPackage, UnresolvedImport.
+            if !depths.contains_key(&next)
+                && matches!(graph.graph[next], GraphNode::File(_))
+                && follow(current, next)
+            {
+                depths.insert(next, current_depth + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    depths
+}
+
+/// Upper bound on cached root sets; the cache is simply reset when it fills up.
+const MAX_CACHED_ROOTS: usize = 256;
+
+/// Transient cache of blast-radius results for long-running sessions (daemon, watch).
+///
+/// Entries are keyed by the sorted root symbol indices and remember every file their
+/// result depends on (defining files plus impacted files). The cache is tied to
+/// `CodeGraph::generation`: a lookup against a different generation drops every entry,
+/// unless the watcher already patched the cache forward with
+/// [`ImpactCache::apply_file_change`].
+#[derive(Default)]
+pub struct ImpactCache {
+    generation: u64,
+    entries: HashMap<Vec<NodeIndex>, CachedImpact>,
+}
+
+struct CachedImpact {
+    results: Vec<ImpactResult>,
+    files: HashSet<PathBuf>,
+}
+
+impl ImpactCache {
+    /// Patch the cache after a single-file update moved the graph from `previous` to
+    /// `generation`.
+    ///
+    /// `dependencies` are the files the updated file now imports or whose tables it
+    /// queries (see [`file_dependency_paths`]). An entry survives when the changed file is
+    /// neither one of its files nor now depends on one of them — the rest of the reverse
+    /// import graph is untouched by a single-file update. If the cache was not at
+    /// `previous`, it missed an update and is cleared instead.
+    pub fn apply_file_change(
+        &mut self,
+        path: &Path,
+        dependencies: &HashSet<PathBuf>,
+        previous: u64,
+        generation: u64,
+    ) {
+        if self.generation == previous {
+            self.entries.retain(|_, entry| {
+                !entry.files.contains(path) && entry.files.is_disjoint(dependencies)
+            });
+        } else {
+            self.entries.clear();
+        }
+        self.generation = generation;
+    }
+}
+
+/// Files that `path` depends on in the current graph: its resolved imports, the files
+/// defining the SQL tables/views it references and, for a doc, the files it documents.
+pub fn file_dependency_paths(graph: &CodeGraph, path: &Path) -> HashSet<PathBuf> {
+    let mut paths = HashSet::new();
+    let Some(&file_idx) = graph.file_index.get(path) else {
+        return paths;
+    };
+    for edge_ref in graph.graph.edges_directed(file_idx, Direction::Incoming) {
+        if matches!(edge_ref.weight(), EdgeKind::DocumentedBy) {
+            let subject = edge_ref.source();
+            let documented = match graph.graph[subject] {
+                GraphNode::File(_) => Some(subject),
+                _ => find_containing_file_idx(graph, subject),
+            };
+            if let Some(documented) = documented
+                && let GraphNode::File(ref fi) = graph.graph[documented]
+            {
+                paths.insert(fi.path.clone());
+            }
+        }
+    }
+    for edge_ref in graph.graph.edges_directed(file_idx, Direction::Outgoing) {
+        let target = match edge_ref.weight() {
+            EdgeKind::ResolvedImport { .. } => Some(edge_ref.target()),
+            EdgeKind::ReferencesTable => find_containing_file_idx(graph, edge_ref.target()),
+            _ => None,
+        };
+        if let Some(target) = target
+            && let GraphNode::File(ref fi) = graph.graph[target]
+        {
+            paths.insert(fi.path.clone());
+        }
+    }
+    paths
+}
+
+/// [`blast_radius`] backed by the graph's transient [`ImpactCache`].
+///
+/// Repeated queries for the same root symbols within one graph generation reuse the
+/// previous BFS instead of recomputing it.
+pub fn cached_blast_radius(
+    graph: &CodeGraph,
+    symbol_indices: &[NodeIndex],
+    project_root: &Path,
+) -> Vec<ImpactResult> {
+    let mut key = symbol_indices.to_vec();
+    key.sort();
+    key.dedup();
+
+    {
+        let mut cache = graph
+            .impact_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if cache.generation != graph.generation {
+            cache.entries.clear();
+            cache.generation = graph.generation;
+        }
+        if let Some(entry) = cache.entries.get(&key) {
+            return entry.results.clone();
+        }
+    }
+
+    let results = blast_radius(graph, &key, project_root, false);
+
+    let mut files: HashSet<PathBuf> = results.iter().map(|r| r.file_path.clone()).collect();
+    for &sym_idx in &key {
+        if let Some(file_idx) = find_containing_file_idx(graph, sym_idx)
+            && let GraphNode::File(ref fi) = graph.graph[file_idx]
+        {
+            files.insert(fi.path.clone());
+        }
+    }
+
+    let mut cache = graph
+        .impact_cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if cache.entries.len() >= MAX_CACHED_ROOTS {
+        cache.entries.clear();
+    }
+    cache.entries.insert(
+        key,
+        CachedImpact {
+            results: results.clone(),
+            files,
+        },
+    );
+    results
+}
+
 fn risk_ord(r: &RiskTier) -> u8 {
     match r {
         RiskTier::High => 0,
@@ -249,7 +851,7 @@ pub fn diff_impact(
             symbol_indices
         };
 
-        let affected = blast_radius(graph, &seeds, project_root);
+        let affected = blast_radius(graph, &seeds, project_root, false);
         let risk = classify_risk(affected.len(), high_threshold, medium_threshold);
 
         results.push(DiffImpactResult {
@@ -269,7 +871,7 @@ pub fn diff_impact(
 }
 
 // Re-export the shared utility for backward compatibility within this module.
-use super::util::find_containing_file_idx;
+use super::util::{find_containing_file_idx, imports_any_of, top_level_symbol};
 
 // ---------------------------------------------------------------------------
 // Unit tests
@@ -322,7 +924,7 @@ mod tests {
     #[test]
     fn test_direct_importer_in_blast_radius() {
         let (graph, root, foo_sym, _, _, _) = three_file_chain();
-        let results = blast_radius(&graph, &[foo_sym], &root);
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
 
         let has_b = results.iter().any(|r| r.file_path.ends_with("b.ts"));
         assert!(
@@ -334,7 +936,7 @@ mod tests {
     #[test]
     fn test_transitive_importer_in_blast_radius() {
         let (graph, root, foo_sym, _, _, _) = three_file_chain();
-        let results = blast_radius(&graph, &[foo_sym], &root);
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
 
         let has_c = results.iter().any(|r| r.file_path.ends_with("c.ts"));
         assert!(
@@ -343,10 +945,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_runtime_only_skips_type_only_importers() {
+        let (mut graph, root, foo_sym, a_file, _, _) = three_file_chain();
+        let d_file = graph.add_file(root.join("d.ts"), "typescript");
+        graph.add_import_at(d_file, a_file, "./a", true, 0, 0);
+
+        let all = blast_radius(&graph, &[foo_sym], &root, false);
+        assert!(all.iter().any(|r| r.file_path.ends_with("d.ts")));
+        let runtime = blast_radius(&graph, &[foo_sym], &root, true);
+        assert!(!runtime.iter().any(|r| r.file_path.ends_with("d.ts")));
+        assert!(runtime.iter().any(|r| r.file_path.ends_with("c.ts")));
+    }
+
     #[test]
     fn test_defining_file_excluded_from_blast_radius() {
         let (graph, root, foo_sym, _, _, _) = three_file_chain();
-        let results = blast_radius(&graph, &[foo_sym], &root);
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
 
         let has_a = results.iter().any(|r| r.file_path.ends_with("a.ts"));
         assert!(
@@ -355,6 +970,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_import_binding_to_other_symbol_excluded() {
+        let (mut graph, root, foo_sym, a_file, b_file, _) = three_file_chain();
+        let bar_sym = graph.add_symbol(
+            a_file,
+            SymbolInfo {
+                name: "bar".into(),
+                kind: SymbolKind::Function,
+                line: 5,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        // b.ts only binds `bar`, so neither it nor its importer c.ts depends on foo.
+        graph.add_imports_symbol(b_file, bar_sym, 0, 0);
+
+        assert!(blast_radius(&graph, &[foo_sym], &root, false).is_empty());
+        let bar_impact = blast_radius(&graph, &[bar_sym], &root, false);
+        assert_eq!(bar_impact.len(), 2, "b.ts and c.ts depend on bar");
+    }
+
     #[test]
     fn test_non_importing_file_excluded() {
         let root = PathBuf::from("/proj");
@@ -375,7 +1011,7 @@ mod tests {
         // unrelated.ts has no edge to a.ts.
         let _unrelated = graph.add_file(root.join("unrelated.ts"), "typescript");
 
-        let results = blast_radius(&graph, &[foo_sym], &root);
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
         let has_unrelated = results
             .iter()
             .any(|r| r.file_path.ends_with("unrelated.ts"));
@@ -388,7 +1024,7 @@ mod tests {
     #[test]
     fn test_depth_tracking() {
         let (graph, root, foo_sym, _, _, _) = three_file_chain();
-        let results = blast_radius(&graph, &[foo_sym], &root);
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
 
         // b.ts is at depth 1 (directly imports a.ts), c.ts is at depth 2.
         let b_result = results
@@ -424,9 +1060,9 @@ mod tests {
 
         // caller.ts has a Calls edge to foo but NOT a ResolvedImport edge to a.ts.
         let caller_file = graph.add_file(root.join("caller.ts"), "typescript");
-        graph.add_calls_edge(caller_file, foo_sym);
+        graph.add_call_site(caller_file, foo_sym, 1);
 
-        let results = blast_radius(&graph, &[foo_sym], &root);
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
         let has_caller = results.iter().any(|r| r.file_path.ends_with("caller.ts"));
         assert!(
             !has_caller,
@@ -434,6 +1070,159 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_blast_radius_with_edges_depth_and_tests() {
+        let (mut graph, root, foo_sym, a_file, _b_file, c_file) = three_file_chain();
+        // index.ts re-exports a.ts; caller.ts calls foo without importing a.ts.
+        let index = graph.add_file(root.join("index.ts"), "typescript");
+        graph.add_barrel_reexport_all(index, a_file);
+        let caller = graph.add_file(root.join("caller.ts"), "typescript");
+        graph.add_call_site(caller, foo_sym, 1);
+        let spec = graph.add_file(root.join("c.spec.ts"), "typescript");
+        graph.add_resolved_import(spec, c_file, "./c");
+        crate::resolver::tests_resolver::link_tests(&mut graph, &root);
+
+        let names = |options: &ImpactOptions| -> Vec<String> {
+            blast_radius_with(&graph, &[foo_sym], &root, options)
+                .iter()
+                .map(|r| r.file_path.file_name().unwrap().to_string_lossy().into())
+                .collect()
+        };
+        assert_eq!(
+            names(&ImpactOptions::default()),
+            vec!["b.ts", "c.ts", "c.spec.ts"]
+        );
+        assert_eq!(
+            names(&ImpactOptions {
+                max_depth: Some(2),
+                exclude_tests: true,
+                ..Default::default()
+            }),
+            vec!["b.ts", "c.ts"]
+        );
+        assert_eq!(
+            names(&ImpactOptions {
+                edges: ImpactEdges {
+                    imports: false,
+                    calls: true,
+                    reexports: true,
+                },
+                ..Default::default()
+            }),
+            vec!["caller.ts", "index.ts"]
+        );
+    }
+
+    #[test]
+    fn test_table_references_seed_blast_radius() {
+        // repo.ts queries the `users` table (no import of schema.sql); api.ts imports repo.ts.
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+
+        let schema_file = graph.add_file(root.join("schema.sql"), "sql");
+        let users_table = graph.add_symbol(
+            schema_file,
+            SymbolInfo {
+                name: "users".into(),
+                kind: SymbolKind::Table,
+                line: 1,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        let repo_file = graph.add_file(root.join("repo.ts"), "typescript");
+        graph.add_table_reference(repo_file, users_table);
+        let api_file = graph.add_file(root.join("api.ts"), "typescript");
+        graph.add_resolved_import(api_file, repo_file, "./repo");
+
+        let results = blast_radius(&graph, &[users_table], &root, false);
+        let depths: Vec<(String, usize, ConfidenceTier)> = results
+            .iter()
+            .map(|r| {
+                (
+                    r.file_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned(),
+                    r.depth,
+                    r.confidence.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            depths,
+            vec![
+                ("repo.ts".to_string(), 1, ConfidenceTier::High),
+                ("api.ts".to_string(), 2, ConfidenceTier::Medium),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_documenting_docs_flagged_as_possibly_stale() {
+        let (mut graph, root, foo_sym, _, _, _) = three_file_chain();
+        let doc =
+            graph.add_non_parsed_file(root.join("docs/foo.md"), crate::graph::node::FileKind::Doc);
+        graph.add_documented_by(foo_sym, doc);
+
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
+        let doc_result = results
+            .iter()
+            .find(|r| r.file_path.ends_with("docs/foo.md"))
+            .expect("doc mentioning foo must be flagged");
+        assert_eq!(doc_result.depth, 1);
+        assert_eq!(doc_result.confidence, ConfidenceTier::Low);
+        assert_eq!(results.len(), 3, "b.ts, c.ts and the doc");
+    }
+
+    #[test]
+    fn test_cached_blast_radius_patched_per_file_change() {
+        let (mut graph, root, foo_sym, _, _, c_file) = three_file_chain();
+        // Unrelated component: d.ts defines `bar`, e.ts imports d.ts.
+        let d_file = graph.add_file(root.join("d.ts"), "typescript");
+        let bar_sym = graph.add_symbol(
+            d_file,
+            SymbolInfo {
+                name: "bar".into(),
+                kind: SymbolKind::Function,
+                line: 1,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        let e_file = graph.add_file(root.join("e.ts"), "typescript");
+        graph.add_resolved_import(e_file, d_file, "./d");
+
+        assert_eq!(cached_blast_radius(&graph, &[foo_sym], &root).len(), 2);
+        assert_eq!(cached_blast_radius(&graph, &[bar_sym], &root).len(), 1);
+
+        // A new importer of c.ts: within the same generation the cached result is reused.
+        let x_path = root.join("x.ts");
+        let x_file = graph.add_file(x_path.clone(), "typescript");
+        graph.add_resolved_import(x_file, c_file, "./c");
+        assert_eq!(cached_blast_radius(&graph, &[foo_sym], &root).len(), 2);
+
+        // The watcher reports x.ts: only the entry whose files x.ts now depends on is evicted.
+        let dependencies = file_dependency_paths(&graph, &x_path);
+        assert_eq!(dependencies, HashSet::from([root.join("c.ts")]));
+        graph.generation += 1;
+        graph.impact_cache.get_mut().unwrap().apply_file_change(
+            &x_path,
+            &dependencies,
+            0,
+            graph.generation,
+        );
+        assert_eq!(graph.impact_cache.get_mut().unwrap().entries.len(), 1);
+        assert_eq!(cached_blast_radius(&graph, &[foo_sym], &root).len(), 3);
+        assert_eq!(cached_blast_radius(&graph, &[bar_sym], &root).len(), 1);
+
+        // An update the cache did not see (generation moved on) drops every entry.
+        graph.generation += 1;
+        assert_eq!(cached_blast_radius(&graph, &[bar_sym], &root).len(), 1);
+        assert_eq!(graph.impact_cache.get_mut().unwrap().entries.len(), 1);
+    }
+
     // ---------------------------------------------------------------------------
     // Confidence tier tests
     // ---------------------------------------------------------------------------
@@ -505,7 +1294,7 @@ mod tests {
     fn test_confidence_in_blast_radius_depth_1() {
         // b.ts is at depth 1 -> should be HIGH
         let (graph, root, foo_sym, _, _, _) = three_file_chain();
-        let results = blast_radius(&graph, &[foo_sym], &root);
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
         let b_result = results
             .iter()
             .find(|r| r.file_path.ends_with("b.ts"))
@@ -521,7 +1310,7 @@ mod tests {
     fn test_confidence_in_blast_radius_depth_2() {
         // c.ts is at depth 2 -> should be MEDIUM
         let (graph, root, foo_sym, _, _, _) = three_file_chain();
-        let results = blast_radius(&graph, &[foo_sym], &root);
+        let results = blast_radius(&graph, &[foo_sym], &root, false);
         let c_result = results
             .iter()
             .find(|r| r.file_path.ends_with("c.ts"))
@@ -676,4 +1465,139 @@ mod tests {
             "2 affected files with threshold 1 should be HIGH risk"
         );
     }
+
+    #[test]
+    fn test_group_by_dir() {
+        let root = PathBuf::from("/proj");
+        let result = |path: &str, depth: usize| ImpactResult {
+            file_path: root.join(path),
+            depth,
+            confidence: ConfidenceTier::High,
+            basis: String::new(),
+        };
+        let results = vec![
+            result("src/ui/a.ts", 2),
+            result("src/ui/b.ts", 1),
+            result("src/api/c.ts", 3),
+            result("index.ts", 2),
+        ];
+        let dirs: Vec<(String, usize, usize)> = group_by_dir(&results, &root)
+            .into_iter()
+            .map(|d| (d.dir, d.files, d.depth))
+            .collect();
+        assert_eq!(
+            dirs,
+            vec![
+                ("src/ui".to_string(), 2, 1),
+                (".".to_string(), 1, 2),
+                ("src/api".to_string(), 1, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_affected_tests() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let button = graph.add_file(root.join("src/button.ts"), "typescript");
+        let render = graph.add_symbol(
+            button,
+            SymbolInfo {
+                name: "render".into(),
+                kind: SymbolKind::Function,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        let form = graph.add_file(root.join("src/form.ts"), "typescript");
+        let button_spec = graph.add_file(root.join("src/button.spec.ts"), "typescript");
+        let form_spec = graph.add_file(root.join("src/form.spec.ts"), "typescript");
+        graph.add_resolved_import(form, button, "./button");
+        graph.add_resolved_import(button_spec, button, "./button");
+        graph.add_resolved_import(form_spec, form, "./form");
+        crate::resolver::tests_resolver::link_tests(&mut graph, &root);
+
+        let results = blast_radius(&graph, &[render], &root, false);
+        assert_eq!(results.len(), 3);
+        let tests = affected_tests(&graph, &[render], results);
+        let summary: Vec<(&Path, usize, &str)> = tests
+            .iter()
+            .map(|r| {
+                (
+                    r.file_path.strip_prefix(&root).unwrap(),
+                    r.depth,
+                    r.basis.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    Path::new("src/button.spec.ts"),
+                    1,
+                    "test imports it directly"
+                ),
+                (
+                    Path::new("src/form.spec.ts"),
+                    2,
+                    "transitive dependency at depth 2"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_estimate_effort_weights_coverage_and_symbols() {
+        let root = PathBuf::from("/proj/missing");
+        let mut graph = CodeGraph::new();
+        let button = graph.add_file(root.join("src/button.ts"), "typescript");
+        let render = graph.add_symbol(
+            button,
+            SymbolInfo {
+                name: "render".into(),
+                kind: SymbolKind::Function,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        let form = graph.add_file(root.join("src/form.ts"), "typescript");
+        let page = graph.add_file(root.join("src/page.ts"), "typescript");
+        graph.add_symbol(
+            page,
+            SymbolInfo {
+                name: "Page".into(),
+                kind: SymbolKind::Function,
+                ..Default::default()
+            },
+        );
+        let form_spec = graph.add_file(root.join("src/form.spec.ts"), "typescript");
+        graph.add_resolved_import(form, button, "./button");
+        graph.add_resolved_import(page, button, "./button");
+        graph.add_resolved_import(form_spec, form, "./form");
+        crate::resolver::tests_resolver::link_tests(&mut graph, &root);
+
+        let results = blast_radius(&graph, &[render], &root, false);
+        let estimate = estimate_effort(&graph, &results, &root);
+        let summary: Vec<(&str, usize, Option<bool>, f64)> = estimate
+            .files
+            .iter()
+            .map(|f| (f.file.as_str(), f.symbols, f.tested, f.effort))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/page.ts", 1, Some(false), 1.8),
+                ("src/form.spec.ts", 0, None, 1.0),
+                ("src/form.ts", 0, Some(true), 1.0),
+            ]
+        );
+        assert!(
+            estimate
+                .files
+                .iter()
+                .all(|f| f.churn.is_none() && f.loc == 0)
+        );
+        assert_eq!(estimate.total, 3.8);
+    }
 }
@@ -1,5 +1,7 @@
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 
 use crate::graph::{CodeGraph, edge::EdgeKind, node::GraphNode};
@@ -17,12 +19,24 @@ pub enum ImportCategory {
     Builtin,   // std/core/alloc or node builtins
 }
 
+/// How an external Rust dependency relates to its crate's `Cargo.toml`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub enum DependencyStatus {
+    Declared,   // imported and declared in Cargo.toml
+    Undeclared, // imported but not declared (a transitive or missing dependency)
+    Unused,     // declared but imported by no file of the crate
+}
+
 /// A single import entry.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ImportEntry {
     pub specifier: String,
     pub category: ImportCategory,
     pub is_reexport: bool,
+    /// For files of a crate whose `Cargo.toml` declares dependencies: whether an external
+    /// import is declared, plus, on the crate root, the declared dependencies nothing imports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependency: Option<DependencyStatus>,
 }
 
 // ---------------------------------------------------------------------------
@@ -52,6 +66,70 @@ fn classify_rust_import(path: &str, source_crate: Option<&str>) -> ImportCategor
     }
 }
 
+/// Dependencies of one crate, compared against its `Cargo.toml`.
+struct CrateDependencies {
+    /// External packages the crate root declares (`DeclaresDependency` targets).
+    declared: HashSet<NodeIndex>,
+    /// External packages some file of the crate imports with `use`.
+    used: HashSet<NodeIndex>,
+    /// Paths of the crate's files.
+    files: Vec<PathBuf>,
+}
+
+/// Collect the dependencies of the crate `crate_name`. `None` when the crate declares
+/// nothing, so there is no manifest to compare against.
+fn crate_dependencies(graph: &CodeGraph, crate_name: &str) -> Option<CrateDependencies> {
+    let mut deps = CrateDependencies {
+        declared: HashSet::new(),
+        used: HashSet::new(),
+        files: Vec::new(),
+    };
+    for &file_idx in graph.file_index.values() {
+        match &graph.graph[file_idx] {
+            GraphNode::File(fi) if fi.crate_name.as_deref() == Some(crate_name) => {
+                deps.files.push(fi.path.clone());
+            }
+            _ => continue,
+        }
+        for edge_ref in graph.graph.edges(file_idx) {
+            let target = edge_ref.target();
+            match edge_ref.weight() {
+                EdgeKind::DeclaresDependency { .. } => {
+                    deps.declared.insert(target);
+                }
+                EdgeKind::ResolvedImport { .. }
+                    if matches!(graph.graph[target], GraphNode::ExternalPackage(_)) =>
+                {
+                    deps.used.insert(target);
+                }
+                _ => {}
+            }
+        }
+    }
+    (!deps.declared.is_empty()).then_some(deps)
+}
+
+/// Drop from `names` the crates some file in `files` names by path (`serde_json::to_value`,
+/// `#[derive(serde::Serialize)]`) without a `use` declaration.
+pub(crate) fn retain_unreferenced(names: &mut Vec<String>, files: &[PathBuf]) {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    for path in files {
+        if names.is_empty() {
+            return;
+        }
+        let Ok(source) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        names.retain(|name| {
+            let needle = format!("{}::", name);
+            !source.match_indices(&needle).any(|(at, _)| {
+                !source[..at].chars().next_back().is_some_and(is_ident)
+                    && !source[..at].ends_with("::")
+            })
+        });
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Main query function
 // ---------------------------------------------------------------------------
@@ -84,11 +162,16 @@ pub fn file_imports(
         _ => None,
     };
 
+    let dependencies = source_crate
+        .as_deref()
+        .and_then(|name| crate_dependencies(graph, name));
+
     let mut entries: Vec<ImportEntry> = Vec::new();
+    let mut unused: Vec<String> = Vec::new();
 
     for edge_ref in graph.graph.edges(file_idx) {
         match edge_ref.weight() {
-            EdgeKind::ResolvedImport { specifier } => {
+            EdgeKind::ResolvedImport { specifier, .. } => {
                 let target_idx = edge_ref.target();
                 let category = match &graph.graph[target_idx] {
                     GraphNode::File(fi) => {
@@ -114,18 +197,47 @@ pub fn file_imports(
                     _ => continue,
                 };
 
+                let dependency = match (&dependencies, &graph.graph[target_idx]) {
+                    (Some(deps), _) if deps.declared.contains(&target_idx) => {
+                        Some(DependencyStatus::Declared)
+                    }
+                    // A binary's `mod` paths (`use cli::Cli` in main.rs) can resolve as
+                    // external; those are not dependencies.
+                    (Some(_), GraphNode::ExternalPackage(pkg))
+                        if !graph.module_index.contains_key(&format!(
+                            "{}::{}",
+                            source_crate.as_deref().unwrap_or_default(),
+                            pkg.name
+                        )) =>
+                    {
+                        Some(DependencyStatus::Undeclared)
+                    }
+                    _ => None,
+                };
+
                 entries.push(ImportEntry {
                     specifier: specifier.clone(),
                     category,
                     is_reexport: false,
+                    dependency,
                 });
             }
 
+            EdgeKind::DeclaresDependency { .. } => {
+                if let (Some(deps), GraphNode::ExternalPackage(pkg)) =
+                    (&dependencies, &graph.graph[edge_ref.target()])
+                    && !deps.used.contains(&edge_ref.target())
+                {
+                    unused.push(pkg.name.clone());
+                }
+            }
+
             EdgeKind::ReExport { path } => {
                 entries.push(ImportEntry {
                     specifier: path.clone(),
                     category: ImportCategory::Internal,
                     is_reexport: true,
+                    dependency: None,
                 });
             }
 
@@ -145,6 +257,7 @@ pub fn file_imports(
                     specifier,
                     category: ImportCategory::Internal,
                     is_reexport: true,
+                    dependency: None,
                 });
             }
 
@@ -154,6 +267,7 @@ pub fn file_imports(
                     specifier: path.clone(),
                     category,
                     is_reexport: false,
+                    dependency: None,
                 });
             }
 
@@ -162,6 +276,19 @@ pub fn file_imports(
         }
     }
 
+    // A dependency may be declared in several tables; list it once.
+    unused.sort();
+    unused.dedup();
+    if let Some(deps) = &dependencies {
+        retain_unreferenced(&mut unused, &deps.files);
+    }
+    entries.extend(unused.into_iter().map(|name| ImportEntry {
+        specifier: name,
+        category: ImportCategory::External,
+        is_reexport: false,
+        dependency: Some(DependencyStatus::Unused),
+    }));
+
     Ok(entries)
 }
 
@@ -187,6 +314,10 @@ mod tests {
             language: "rust".into(),
             crate_name: crate_name.map(|s| s.to_string()),
             kind: FileKind::Source,
+            category: None,
+            is_test: false,
+            line_count: 0,
+            content_hash: None,
         }
     }
 
@@ -225,6 +356,10 @@ mod tests {
             tgt_idx,
             EdgeKind::ResolvedImport {
                 specifier: "./b".into(),
+                is_type_only: false,
+                count: 1,
+                line: 0,
+                col: 0,
             },
         );
 
@@ -407,4 +542,39 @@ mod tests {
             "Error message should mention 'File not found'"
         );
     }
+
+    #[test]
+    fn test_declared_undeclared_and_unused_dependencies() {
+        use crate::graph::edge::DependencySection;
+
+        let root = PathBuf::from("/tmp/test_project");
+        let (mut graph, lib_path) = make_graph_with_source(&root, "src/lib.rs", Some("app"));
+        let lib = graph.file_index[&lib_path];
+        let declare = |graph: &mut CodeGraph, name: &str| {
+            graph.add_declared_dependency(
+                lib,
+                name,
+                Some("1"),
+                EdgeKind::DeclaresDependency {
+                    section: DependencySection::Normal,
+                    optional: false,
+                    features: Vec::new(),
+                },
+            );
+        };
+        declare(&mut graph, "serde");
+        declare(&mut graph, "regex");
+        graph.add_external_package(lib, "serde", "serde::Serialize");
+        graph.add_external_package(lib, "itertools", "itertools::Itertools");
+
+        let entries = file_imports(&graph, &root, &lib_path).unwrap();
+        let statuses: Vec<(&str, Option<DependencyStatus>)> = entries
+            .iter()
+            .map(|e| (e.specifier.as_str(), e.dependency.clone()))
+            .collect();
+        assert!(statuses.contains(&("serde::Serialize", Some(DependencyStatus::Declared))));
+        assert!(statuses.contains(&("itertools::Itertools", Some(DependencyStatus::Undeclared))));
+        assert!(statuses.contains(&("regex", Some(DependencyStatus::Unused))));
+        assert_eq!(entries.len(), 3);
+    }
 }
@@ -0,0 +1,429 @@
+//! Import grouping lint (`code-graph lint-imports`).
+//!
+//! Each file's static imports are re-read in source order and classified into
+//! [`ImportGroup`]s with the resolver's own results — the `ResolvedImport` edge of each
+//! specifier, the Python and Go stdlib lists, workspace package and crate names — then
+//! checked against the `[lint_imports]` rules of `code-graph.toml`:
+//!
+//! - **order**: groups appear in the configured order (default builtin, external,
+//!   workspace, internal, relative);
+//! - **deep-relative**: a relative import climbs at most `max_parent_depth` directories
+//!   (`../../../x`, `super::super::super::x`, `from .... import x`).
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::config::{ImportGroup, LintImportsConfig};
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+use crate::parser::imports::{ImportInfo, ImportKind};
+
+/// Which rule an import breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportRule {
+    Order,
+    DeepRelative,
+}
+
+/// One import that breaks a rule.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ImportViolation {
+    /// Project-relative path of the importing file.
+    pub file: String,
+    /// 1-based line of the import.
+    pub line: usize,
+    pub rule: ImportRule,
+    pub specifier: String,
+    pub group: ImportGroup,
+    pub message: String,
+}
+
+/// Result of `lint-imports`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LintImportsReport {
+    /// Number of files whose imports were checked.
+    pub files_checked: usize,
+    pub violations: Vec<ImportViolation>,
+}
+
+/// A static import of one file, in source order.
+#[derive(Debug, Clone, PartialEq)]
+struct LintedImport {
+    line: usize,
+    specifier: String,
+    group: ImportGroup,
+    /// Directories a relative import climbs.
+    parent_depth: usize,
+}
+
+/// Names a file can import from another package or crate of the workspace.
+struct WorkspaceNames {
+    /// npm/yarn/pnpm workspace package names.
+    packages: HashSet<String>,
+    /// Crate names (`-` as `_`) of every indexed crate.
+    crates: HashSet<String>,
+}
+
+/// Whether `name` is a file-backed child module of the Rust file `file`, which `use` can
+/// name without a prefix (`mod model; use model::Params;`).
+fn is_child_module(graph: &CodeGraph, file: &Path, name: &str) -> bool {
+    let Some(parent) = file.parent() else {
+        return false;
+    };
+    let dir = match file.file_name().and_then(|n| n.to_str()) {
+        Some("main.rs" | "lib.rs" | "mod.rs") => parent.to_path_buf(),
+        _ => match file.file_stem() {
+            Some(stem) => parent.join(stem),
+            None => return false,
+        },
+    };
+    graph
+        .file_index
+        .contains_key(&dir.join(format!("{name}.rs")))
+        || graph
+            .file_index
+            .contains_key(&dir.join(name).join("mod.rs"))
+}
+
+/// Group of a Rust `use` path written in `file`, of crate `own_crate`.
+fn rust_group(
+    graph: &CodeGraph,
+    file: &Path,
+    path: &str,
+    own_crate: Option<&str>,
+    names: &WorkspaceNames,
+) -> ImportGroup {
+    let first = path
+        .trim_start_matches("::")
+        .split("::")
+        .next()
+        .unwrap_or("")
+        .trim();
+    match first {
+        "std" | "core" | "alloc" => ImportGroup::Builtin,
+        "crate" => ImportGroup::Internal,
+        "self" | "super" => ImportGroup::Relative,
+        seg if Some(seg) == own_crate => ImportGroup::Internal,
+        seg if is_child_module(graph, file, seg) => ImportGroup::Relative,
+        seg if names.crates.contains(seg) => ImportGroup::Workspace,
+        // A binary's `mod` paths (`use cli::Cli` in main.rs).
+        seg if own_crate
+            .is_some_and(|krate| graph.module_index.contains_key(&format!("{krate}::{seg}"))) =>
+        {
+            ImportGroup::Internal
+        }
+        _ => ImportGroup::External,
+    }
+}
+
+/// Group of a TS/JS, Python, or Go import from `file_idx`, using the resolver's edge for
+/// its specifier when there is one.
+fn import_group(
+    graph: &CodeGraph,
+    file_idx: NodeIndex,
+    language: &str,
+    import: &ImportInfo,
+    names: &WorkspaceNames,
+) -> ImportGroup {
+    let spec = import.module_path.as_str();
+    if matches!(import.kind, ImportKind::PythonRelative { .. }) || spec.starts_with('.') {
+        return ImportGroup::Relative;
+    }
+    match language {
+        "python" if crate::resolver::python_resolver::is_stdlib(spec) => {
+            return ImportGroup::Builtin;
+        }
+        "go" if crate::resolver::go_resolver::is_go_stdlib(spec) => return ImportGroup::Builtin,
+        _ => {}
+    }
+    if spec.starts_with("node:") {
+        return ImportGroup::Builtin;
+    }
+    if names.packages.iter().any(|pkg| {
+        spec == pkg
+            || spec
+                .strip_prefix(pkg.as_str())
+                .is_some_and(|r| r.starts_with('/'))
+    }) {
+        return ImportGroup::Workspace;
+    }
+    let target = graph.graph.edges(file_idx).find_map(|e| match e.weight() {
+        EdgeKind::ResolvedImport { specifier, .. } if specifier == spec => Some(e.target()),
+        _ => None,
+    });
+    match target.map(|t| &graph.graph[t]) {
+        Some(GraphNode::File(_)) => ImportGroup::Internal,
+        Some(GraphNode::Builtin { .. }) => ImportGroup::Builtin,
+        Some(GraphNode::UnresolvedImport { reason, .. }) if reason == "builtin" => {
+            ImportGroup::Builtin
+        }
+        _ => ImportGroup::External,
+    }
+}
+
+/// Directories a relative TS/JS or Python import climbs.
+fn parent_depth(import: &ImportInfo) -> usize {
+    match import.kind {
+        ImportKind::PythonRelative { level } | ImportKind::PythonConditionalRelative { level } => {
+            level.saturating_sub(1)
+        }
+        _ => import
+            .module_path
+            .split('/')
+            .take_while(|seg| *seg == ".." || *seg == ".")
+            .filter(|seg| *seg == "..")
+            .count(),
+    }
+}
+
+/// The static imports of one file, in source order.
+fn file_imports(
+    graph: &CodeGraph,
+    file_idx: NodeIndex,
+    names: &WorkspaceNames,
+) -> Option<Vec<LintedImport>> {
+    let GraphNode::File(fi) = &graph.graph[file_idx] else {
+        return None;
+    };
+    let source = std::fs::read(&fi.path).ok()?;
+    let parsed = crate::parser::parse_file(&fi.path, &source).ok()?;
+
+    let mut imports: Vec<LintedImport> = parsed
+        .rust_uses
+        .iter()
+        .filter(|u| !u.is_pub_use)
+        .map(|u| LintedImport {
+            line: u.line,
+            specifier: u.path.split_whitespace().collect::<Vec<_>>().join(" "),
+            group: rust_group(graph, &fi.path, &u.path, fi.crate_name.as_deref(), names),
+            parent_depth: u
+                .path
+                .split("::")
+                .take_while(|seg| seg.trim() == "super")
+                .count(),
+        })
+        .collect();
+    imports.extend(
+        parsed
+            .imports
+            .iter()
+            .filter(|i| {
+                i.line > 0
+                    && matches!(
+                        i.kind,
+                        ImportKind::Esm
+                            | ImportKind::PythonAbsolute
+                            | ImportKind::PythonRelative { .. }
+                            | ImportKind::GoAbsolute
+                            | ImportKind::GoBlank
+                            | ImportKind::GoDot
+                    )
+            })
+            .map(|i| LintedImport {
+                line: i.line,
+                specifier: i.module_path.clone(),
+                group: import_group(graph, file_idx, &fi.language, i, names),
+                parent_depth: parent_depth(i),
+            }),
+    );
+    imports.sort_by_key(|i| i.line);
+    Some(imports)
+}
+
+/// Check one file's imports against `config`.
+fn check_imports(
+    file: &str,
+    imports: &[LintedImport],
+    config: &LintImportsConfig,
+) -> Vec<ImportViolation> {
+    let rank: HashMap<ImportGroup, usize> = config
+        .order
+        .iter()
+        .enumerate()
+        .map(|(i, &g)| (g, i))
+        .collect();
+    let mut violations = Vec::new();
+    // The latest-ranked import seen so far.
+    let mut highest: Option<(usize, &LintedImport)> = None;
+    for import in imports {
+        if import.group == ImportGroup::Relative && import.parent_depth > config.max_parent_depth {
+            violations.push(ImportViolation {
+                file: file.to_string(),
+                line: import.line,
+                rule: ImportRule::DeepRelative,
+                specifier: import.specifier.clone(),
+                group: import.group,
+                message: format!(
+                    "climbs {} directories (max {})",
+                    import.parent_depth, config.max_parent_depth
+                ),
+            });
+        }
+        let Some(&r) = rank.get(&import.group) else {
+            continue;
+        };
+        match highest {
+            Some((top, prev)) if r < top => violations.push(ImportViolation {
+                file: file.to_string(),
+                line: import.line,
+                rule: ImportRule::Order,
+                specifier: import.specifier.clone(),
+                group: import.group,
+                message: format!(
+                    "{} import after {} import `{}` (line {})",
+                    import.group.as_str(),
+                    prev.group.as_str(),
+                    prev.specifier,
+                    prev.line
+                ),
+            }),
+            Some((top, _)) if r == top => {}
+            _ => highest = Some((r, import)),
+        }
+    }
+    violations
+}
+
+/// Lint the imports of every source file in the graph.
+pub fn lint_imports(
+    graph: &CodeGraph,
+    root: &Path,
+    config: &LintImportsConfig,
+) -> LintImportsReport {
+    let names = WorkspaceNames {
+        packages: crate::resolver::workspace::discover_workspace_packages(root)
+            .into_keys()
+            .collect(),
+        crates: graph
+            .graph
+            .node_weights()
+            .filter_map(|n| match n {
+                GraphNode::File(fi) => fi.crate_name.as_ref().map(|c| c.replace('-', "_")),
+                _ => None,
+            })
+            .collect(),
+    };
+
+    let mut files: Vec<(&Path, NodeIndex)> = graph
+        .file_index
+        .iter()
+        .filter(|(_, idx)| {
+            matches!(&graph.graph[**idx], GraphNode::File(fi) if fi.kind == FileKind::Source)
+        })
+        .map(|(path, &idx)| (path.as_path(), idx))
+        .collect();
+    files.sort();
+
+    let mut report = LintImportsReport {
+        files_checked: 0,
+        violations: Vec::new(),
+    };
+    for (path, idx) in files {
+        let Some(imports) = file_imports(graph, idx, &names) else {
+            continue;
+        };
+        if imports.is_empty() {
+            continue;
+        }
+        report.files_checked += 1;
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        report
+            .violations
+            .extend(check_imports(&rel, &imports, config));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn import(line: usize, specifier: &str, group: ImportGroup, depth: usize) -> LintedImport {
+        LintedImport {
+            line,
+            specifier: specifier.into(),
+            group,
+            parent_depth: depth,
+        }
+    }
+
+    #[test]
+    fn test_check_order_and_depth() {
+        let imports = vec![
+            import(1, "fs", ImportGroup::Builtin, 0),
+            import(2, "./x", ImportGroup::Relative, 0),
+            import(3, "react", ImportGroup::External, 0),
+            import(4, "../../../y", ImportGroup::Relative, 3),
+            import(5, "path", ImportGroup::Builtin, 0),
+        ];
+        let violations = check_imports("a.ts", &imports, &LintImportsConfig::default());
+        let summary: Vec<(usize, ImportRule, &str)> = violations
+            .iter()
+            .map(|v| (v.line, v.rule, v.message.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    3,
+                    ImportRule::Order,
+                    "external import after relative import `./x` (line 2)"
+                ),
+                (4, ImportRule::DeepRelative, "climbs 3 directories (max 2)"),
+                (
+                    5,
+                    ImportRule::Order,
+                    "builtin import after relative import `./x` (line 2)"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_groups_missing_from_order_are_unchecked() {
+        let config = LintImportsConfig {
+            order: vec![ImportGroup::Builtin, ImportGroup::External],
+            max_parent_depth: 5,
+        };
+        let imports = vec![
+            import(1, "./x", ImportGroup::Relative, 0),
+            import(2, "react", ImportGroup::External, 0),
+            import(3, "crate::a", ImportGroup::Internal, 0),
+            import(4, "std::fs", ImportGroup::Builtin, 0),
+        ];
+        let violations = check_imports("a.rs", &imports, &config);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, 4);
+    }
+
+    #[test]
+    fn test_rust_groups() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        graph.add_file(root.join("src/export/mod.rs"), "rust");
+        graph.add_file(root.join("src/export/model.rs"), "rust");
+        let names = WorkspaceNames {
+            packages: HashSet::new(),
+            crates: ["app", "shared"].iter().map(|s| s.to_string()).collect(),
+        };
+        let file = root.join("src/export/mod.rs");
+        let group = |path: &str| rust_group(&graph, &file, path, Some("app"), &names);
+        assert_eq!(group("std::fs"), ImportGroup::Builtin);
+        assert_eq!(group("serde::Serialize"), ImportGroup::External);
+        assert_eq!(group("shared::Thing"), ImportGroup::Workspace);
+        assert_eq!(group("crate::graph"), ImportGroup::Internal);
+        assert_eq!(group("app::graph"), ImportGroup::Internal);
+        assert_eq!(group("super::x"), ImportGroup::Relative);
+        assert_eq!(group("model::Params"), ImportGroup::Relative);
+    }
+}
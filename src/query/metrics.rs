@@ -0,0 +1,91 @@
+//! Package coupling metrics (`code-graph metrics`).
+//!
+//! For each package of the package graph (see [`crate::query::pkgql`]), afferent coupling
+//! (Ca) counts the other packages importing from it, efferent coupling (Ce) the packages
+//! it imports from, and instability is `Ce / (Ca + Ce)`: 0 for a package everything
+//! depends on and that depends on nothing, 1 for one nothing depends on.
+
+use std::path::Path;
+
+use crate::graph::CodeGraph;
+use crate::query::pkgql::PackageGraph;
+
+/// Coupling metrics of one package.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PackageMetrics {
+    pub package: String,
+    /// Source files in the package.
+    pub files: usize,
+    /// Packages importing from this one (fan-in).
+    pub afferent: usize,
+    /// Packages this one imports from (fan-out).
+    pub efferent: usize,
+    /// `efferent / (afferent + efferent)`; `None` for a package with no coupling at all.
+    pub instability: Option<f64>,
+}
+
+/// Coupling metrics of every package, sorted by name.
+pub fn package_metrics(graph: &CodeGraph, project_root: &Path) -> Vec<PackageMetrics> {
+    let packages = PackageGraph::build(graph, project_root);
+    packages
+        .names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let afferent = packages.rdeps[i].len();
+            let efferent = packages.deps[i].len();
+            let total = afferent + efferent;
+            PackageMetrics {
+                package: name.clone(),
+                files: packages.files[i],
+                afferent,
+                efferent,
+                instability: (total > 0).then(|| efferent as f64 / total as f64),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_package_metrics_count_fan_in_and_fan_out() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(root.join("app/main.ts"), "typescript");
+        let api = graph.add_file(root.join("api/index.ts"), "typescript");
+        let db = graph.add_file(root.join("db/index.ts"), "typescript");
+        let db_pool = graph.add_file(root.join("db/pool.ts"), "typescript");
+        graph.add_file(root.join("docs/notes.ts"), "typescript");
+        graph.add_resolved_import(app, api, "../api");
+        graph.add_resolved_import(app, db, "../db");
+        graph.add_resolved_import(api, db, "../db");
+        graph.add_resolved_import(db, db_pool, "./pool");
+
+        let metrics = package_metrics(&graph, &root);
+        let rows: Vec<(&str, usize, usize, usize, Option<f64>)> = metrics
+            .iter()
+            .map(|m| {
+                (
+                    m.package.as_str(),
+                    m.files,
+                    m.afferent,
+                    m.efferent,
+                    m.instability,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("api", 1, 1, 1, Some(0.5)),
+                ("app", 1, 0, 2, Some(1.0)),
+                ("db", 2, 2, 0, Some(0.0)),
+                ("docs", 1, 0, 0, None),
+            ]
+        );
+    }
+}
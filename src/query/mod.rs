@@ -1,18 +1,47 @@
+pub mod api_diff;
+pub mod barrels;
+pub mod callgraph;
+pub mod can_delete;
+pub mod categories;
 pub mod circular;
 pub mod clones;
 pub mod clusters;
+pub mod complexity;
 pub mod context;
+pub mod coupling;
+pub mod cut_points;
 pub mod dead_code;
 pub mod decorators;
+pub mod dep_path;
+pub mod deps;
 pub mod diff;
+pub mod dsl;
+pub mod duplicates;
+pub mod entrypoints;
+pub mod exclusions;
+pub mod exports;
 pub mod file_summary;
 pub mod find;
 pub mod flow;
+pub mod hotspots;
+pub mod hover;
+pub mod identical_files;
 pub mod impact;
 pub mod imports;
+pub mod lint_imports;
+pub mod metrics;
 pub mod output;
+pub mod owners;
+pub mod pkgql;
 pub mod refs;
 pub mod rename;
+pub mod report;
+pub mod rules;
+pub mod select_tests;
+pub mod similar;
 pub mod stats;
 pub mod structure;
+pub mod touched;
+pub mod unused_deps;
+pub mod unused_exports;
 pub(crate) mod util;
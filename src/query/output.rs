@@ -1,12 +1,13 @@
-use std::io::IsTerminal;
-use std::path::Path;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use crate::query::structure::StructureNode;
 
-use crate::cli::OutputFormat;
+use crate::cli::{GroupBy, OutputFormat, SortBy};
 use crate::graph::node::SymbolVisibility;
 use crate::query::circular::CircularDep;
-use crate::query::context::SymbolContext;
+use crate::query::context::{SourceSnippet, SymbolContext};
 use crate::query::find::FindResult;
 use crate::query::find::kind_to_str;
 use crate::query::impact::ImpactResult;
@@ -21,6 +22,14 @@ fn language_of_file(path: &Path) -> &'static str {
         "rs" => "Rust",
         "py" => "Python",
         "go" => "Go",
+        "proto" => "Protobuf",
+        "sql" => "SQL",
+        "astro" => "Astro",
+        "zig" => "Zig",
+        "sh" | "bash" => "Shell",
+        "ipynb" => "Jupyter",
+        "css" => "CSS",
+        "scss" => "SCSS",
         _ => "Unknown",
     }
 }
@@ -54,9 +63,222 @@ fn visibility_str(vis: &SymbolVisibility) -> &'static str {
         SymbolVisibility::Pub => "pub",
         SymbolVisibility::PubCrate => "pub(crate)",
         SymbolVisibility::Private => "private",
+        SymbolVisibility::Protected => "protected",
     }
 }
 
+/// Find results sorted by language first (for grouping), then file path, then line.
+///
+/// Only sorts when `mixed` is true — single-language results keep their original order.
+fn group_by_language(results: &[FindResult], mixed: bool) -> Vec<&FindResult> {
+    let mut v: Vec<&FindResult> = results.iter().collect();
+    if mixed {
+        v.sort_by(|a, b| {
+            let la = language_of_file(&a.file_path);
+            let lb = language_of_file(&b.file_path);
+            language_sort_key(la)
+                .cmp(&language_sort_key(lb))
+                .then(a.file_path.cmp(&b.file_path))
+                .then(a.line.cmp(&b.line))
+        });
+    }
+    v
+}
+
+// ---------------------------------------------------------------------------
+// Grouping and sorting (`--group-by`, `--sort`)
+// ---------------------------------------------------------------------------
+
+/// How `find`, `refs`, and `impact` results are grouped and ordered (`--group-by`,
+/// `--sort`). The default keeps each command's own ordering.
+#[derive(Debug, Clone, Default)]
+pub struct Arrangement {
+    pub group_by: Option<GroupBy>,
+    pub sort: Option<SortBy>,
+    /// Package of each file, for `--group-by package`.
+    pub packages: HashMap<PathBuf, String>,
+    /// Reference counts for `--sort refs`: per definition `(file, line)` for `find`, per
+    /// file (line 0) for `impact`. `refs` counts its own results per file.
+    pub refs: HashMap<(PathBuf, usize), usize>,
+}
+
+impl Arrangement {
+    pub fn is_default(&self) -> bool {
+        self.group_by.is_none() && self.sort.is_none()
+    }
+
+    /// Group of a result in `file`; `kind` is its symbol kind, reference kind, or
+    /// confidence tier.
+    fn group_key(&self, group_by: GroupBy, file: &Path, project_root: &Path, kind: &str) -> String {
+        match group_by {
+            GroupBy::File => file
+                .strip_prefix(project_root)
+                .unwrap_or(file)
+                .to_string_lossy()
+                .into_owned(),
+            GroupBy::Kind => kind.to_string(),
+            GroupBy::Package => self
+                .packages
+                .get(file)
+                .cloned()
+                .unwrap_or_else(|| "(no package)".to_string()),
+            GroupBy::Language => language_of_file(file).to_string(),
+        }
+    }
+}
+
+/// A `--- {group} ---` header and the results under it; one headerless group when the
+/// results are not grouped.
+type Groups<'a, T> = Vec<(Option<String>, Vec<&'a T>)>;
+
+/// `items` sorted with `cmp` (stable on their incoming order) and grouped by `group_key`,
+/// groups in key order.
+fn arrange<'a, T>(
+    items: &'a [T],
+    arrangement: &Arrangement,
+    group_key: impl Fn(&T, GroupBy) -> String,
+    cmp: impl Fn(&T, &T, SortBy) -> Ordering,
+) -> Groups<'a, T> {
+    let mut sorted: Vec<&T> = items.iter().collect();
+    if let Some(sort) = arrangement.sort {
+        sorted.sort_by(|a, b| cmp(a, b, sort));
+    }
+    let Some(group_by) = arrangement.group_by else {
+        return vec![(None, sorted)];
+    };
+    let mut groups: BTreeMap<String, Vec<&T>> = BTreeMap::new();
+    for item in sorted {
+        groups
+            .entry(group_key(item, group_by))
+            .or_default()
+            .push(item);
+    }
+    groups.into_iter().map(|(key, v)| (Some(key), v)).collect()
+}
+
+/// JSON array of arranged results, each tagged with its `"group"` when grouped.
+fn arranged_json<T>(
+    groups: &Groups<'_, T>,
+    to_json: impl Fn(&T) -> serde_json::Value,
+) -> serde_json::Value {
+    let mut entries = Vec::new();
+    for (group, items) in groups {
+        for item in items {
+            let mut entry = to_json(item);
+            if let Some(group) = group {
+                entry["group"] = serde_json::json!(group);
+            }
+            entries.push(entry);
+        }
+    }
+    serde_json::Value::Array(entries)
+}
+
+/// Find results in display groups: `arrangement` when one was requested, and otherwise
+/// one group per language when they span several.
+fn find_groups<'a>(
+    results: &'a [FindResult],
+    arrangement: &Arrangement,
+    project_root: &Path,
+) -> Groups<'a, FindResult> {
+    if arrangement.is_default() {
+        let mixed = is_mixed_language(results, |r: &FindResult| r.file_path.as_path());
+        let mut groups: Groups<'a, FindResult> = Vec::new();
+        for r in group_by_language(results, mixed) {
+            let lang = mixed.then(|| language_of_file(&r.file_path).to_string());
+            match groups.last_mut() {
+                Some((last, items)) if *last == lang => items.push(r),
+                _ => groups.push((lang, vec![r])),
+            }
+        }
+        return groups;
+    }
+    let refs = |r: &FindResult| {
+        arrangement
+            .refs
+            .get(&(r.file_path.clone(), r.line))
+            .copied()
+            .unwrap_or(0)
+    };
+    arrange(
+        results,
+        arrangement,
+        |r, group_by| {
+            arrangement.group_key(group_by, &r.file_path, project_root, kind_to_str(&r.kind))
+        },
+        |a, b, sort| {
+            let by_location = a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line));
+            match sort {
+                SortBy::Name => a.symbol_name.cmp(&b.symbol_name).then(by_location),
+                SortBy::Line => by_location,
+                SortBy::Refs => refs(b).cmp(&refs(a)).then(by_location),
+            }
+        },
+    )
+}
+
+/// Reference results in display groups for `arrangement`. `--sort refs` ranks files by
+/// their number of references.
+fn refs_groups<'a>(
+    results: &'a [RefResult],
+    arrangement: &Arrangement,
+    project_root: &Path,
+) -> Groups<'a, RefResult> {
+    let mut per_file: HashMap<&Path, usize> = HashMap::new();
+    for r in results {
+        *per_file.entry(r.file_path.as_path()).or_default() += 1;
+    }
+    arrange(
+        results,
+        arrangement,
+        |r, group_by| {
+            arrangement.group_key(group_by, &r.file_path, project_root, r.ref_kind.as_str())
+        },
+        |a, b, sort| {
+            let by_location = a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line));
+            match sort {
+                SortBy::Name => a.symbol_name.cmp(&b.symbol_name).then(by_location),
+                SortBy::Line => by_location,
+                SortBy::Refs => per_file[b.file_path.as_path()]
+                    .cmp(&per_file[a.file_path.as_path()])
+                    .then(by_location),
+            }
+        },
+    )
+}
+
+/// Impact results in display groups for `arrangement`. Results are files: `--sort name`
+/// and `--sort line` order them by path, and `--group-by kind` groups by confidence tier.
+fn impact_groups<'a>(
+    results: &'a [ImpactResult],
+    arrangement: &Arrangement,
+    project_root: &Path,
+) -> Groups<'a, ImpactResult> {
+    let refs = |r: &ImpactResult| {
+        arrangement
+            .refs
+            .get(&(r.file_path.clone(), 0))
+            .copied()
+            .unwrap_or(0)
+    };
+    arrange(
+        results,
+        arrangement,
+        |r, group_by| {
+            arrangement.group_key(
+                group_by,
+                &r.file_path,
+                project_root,
+                &r.confidence.to_string(),
+            )
+        },
+        |a, b, sort| match sort {
+            SortBy::Name | SortBy::Line => a.file_path.cmp(&b.file_path),
+            SortBy::Refs => refs(b).cmp(&refs(a)).then(a.file_path.cmp(&b.file_path)),
+        },
+    )
+}
+
 /// Returns true if any result has non-Private visibility.
 /// Used to suppress visibility column noise for pure TS/JS projects.
 fn any_non_private(results: &[FindResult]) -> bool {
@@ -65,72 +287,61 @@ fn any_non_private(results: &[FindResult]) -> bool {
         .any(|r| r.visibility != SymbolVisibility::Private)
 }
 
+/// `path` relative to `project_root` for a table column, shortened to `--max-path-width`.
+fn table_path(path: &Path, project_root: &Path) -> String {
+    crate::pager::fit_path(
+        &path
+            .strip_prefix(project_root)
+            .unwrap_or(path)
+            .to_string_lossy(),
+    )
+}
+
 /// Format and print find results to stdout according to the selected output format.
 ///
-/// In compact and table modes, if results span multiple languages, groups them under
-/// `--- {Language} ---` section headers. JSON mode adds a "language" field per result.
+/// In compact and table modes, results are printed in `arrangement`'s groups under
+/// `--- {group} ---` headers; without one, results spanning multiple languages are grouped
+/// under `--- {Language} ---` headers. JSON mode adds a "language" field per result, and a
+/// "group" field when grouped.
 pub fn format_find_results(
     results: &[FindResult],
     format: &OutputFormat,
     project_root: &Path,
     symbol_name: &str,
+    arrangement: &Arrangement,
 ) {
     let show_vis = any_non_private(results);
-    let mixed = is_mixed_language(results, |r: &FindResult| r.file_path.as_path());
-
-    // Sort results: by language first (for grouping), then file path, then line.
-    // Only clone when sorting is needed to avoid unnecessary allocation.
-    let sorted;
-    let results_ref = if mixed {
-        sorted = {
-            let mut v = results.to_vec();
-            v.sort_by(|a, b| {
-                let la = language_of_file(&a.file_path);
-                let lb = language_of_file(&b.file_path);
-                language_sort_key(la)
-                    .cmp(&language_sort_key(lb))
-                    .then(a.file_path.cmp(&b.file_path))
-                    .then(a.line.cmp(&b.line))
-            });
-            v
-        };
-        &sorted[..]
-    } else {
-        results
-    };
+    let groups = find_groups(results, arrangement, project_root);
 
     match format {
         OutputFormat::Compact => {
-            let mut last_lang: Option<&'static str> = None;
-            for r in results_ref {
-                if mixed {
-                    let lang = language_of_file(&r.file_path);
-                    if last_lang != Some(lang) {
-                        println!("--- {} ---", lang);
-                        last_lang = Some(lang);
-                    }
+            for (group, items) in &groups {
+                if let Some(group) = group {
+                    println!("--- {} ---", group);
                 }
-                let rel = r
-                    .file_path
-                    .strip_prefix(project_root)
-                    .unwrap_or(&r.file_path);
-                if show_vis {
-                    println!(
-                        "def {} {}:{} {} {}",
-                        r.symbol_name,
-                        rel.display(),
-                        r.line,
-                        kind_to_str(&r.kind),
-                        visibility_str(&r.visibility),
-                    );
-                } else {
-                    println!(
-                        "def {} {}:{} {}",
-                        r.symbol_name,
-                        rel.display(),
-                        r.line,
-                        kind_to_str(&r.kind)
-                    );
+                for r in items {
+                    let rel = r
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&r.file_path);
+                    if show_vis {
+                        println!(
+                            "def {} {}:{} {} {}",
+                            r.symbol_name,
+                            rel.display(),
+                            r.line,
+                            kind_to_str(&r.kind),
+                            visibility_str(&r.visibility),
+                        );
+                    } else {
+                        println!(
+                            "def {} {}:{} {}",
+                            r.symbol_name,
+                            rel.display(),
+                            r.line,
+                            kind_to_str(&r.kind)
+                        );
+                    }
                 }
             }
             println!("{} definitions found", results.len());
@@ -142,16 +353,11 @@ pub fn format_find_results(
         }
 
         OutputFormat::Table => {
-            let use_color = std::io::stdout().is_terminal();
+            let use_color = crate::pager::start();
 
             // Column widths: auto-sized to data (single pass).
-            let (name_w, file_w) = results_ref.iter().fold((6usize, 4usize), |(nw, fw), r| {
-                let file_len = r
-                    .file_path
-                    .strip_prefix(project_root)
-                    .unwrap_or(&r.file_path)
-                    .to_string_lossy()
-                    .len();
+            let (name_w, file_w) = results.iter().fold((6usize, 4usize), |(nw, fw), r| {
+                let file_len = table_path(&r.file_path, project_root).chars().count();
                 (nw.max(r.symbol_name.len()), fw.max(file_len))
             });
 
@@ -178,30 +384,6 @@ pub fn format_find_results(
                     );
                 }
                 println!("{}", "-".repeat(name_w + file_w + 26));
-                let mut last_lang: Option<&'static str> = None;
-                for r in results_ref {
-                    if mixed {
-                        let lang = language_of_file(&r.file_path);
-                        if last_lang != Some(lang) {
-                            println!("--- {} ---", lang);
-                            last_lang = Some(lang);
-                        }
-                    }
-                    let rel = r
-                        .file_path
-                        .strip_prefix(project_root)
-                        .unwrap_or(&r.file_path);
-                    println!(
-                        "{:<name_w$}  {:<file_w$}  {:>4}  {:<10}  {}",
-                        r.symbol_name,
-                        rel.display(),
-                        r.line,
-                        visibility_str(&r.visibility),
-                        kind_to_str(&r.kind),
-                        name_w = name_w,
-                        file_w = file_w,
-                    );
-                }
             } else {
                 if use_color {
                     println!(
@@ -223,57 +405,142 @@ pub fn format_find_results(
                     );
                 }
                 println!("{}", "-".repeat(name_w + file_w + 14));
-                let mut last_lang: Option<&'static str> = None;
-                for r in results_ref {
-                    if mixed {
-                        let lang = language_of_file(&r.file_path);
-                        if last_lang != Some(lang) {
-                            println!("--- {} ---", lang);
-                            last_lang = Some(lang);
-                        }
+            }
+            for (group, items) in &groups {
+                if let Some(group) = group {
+                    println!("--- {} ---", group);
+                }
+                for r in items {
+                    let rel = table_path(&r.file_path, project_root);
+                    if show_vis {
+                        println!(
+                            "{:<name_w$}  {:<file_w$}  {:>4}  {:<10}  {}",
+                            r.symbol_name,
+                            rel,
+                            r.line,
+                            visibility_str(&r.visibility),
+                            kind_to_str(&r.kind),
+                            name_w = name_w,
+                            file_w = file_w,
+                        );
+                    } else {
+                        println!(
+                            "{:<name_w$}  {:<file_w$}  {:>4}  {}",
+                            r.symbol_name,
+                            rel,
+                            r.line,
+                            kind_to_str(&r.kind),
+                            name_w = name_w,
+                            file_w = file_w,
+                        );
                     }
-                    let rel = r
-                        .file_path
-                        .strip_prefix(project_root)
-                        .unwrap_or(&r.file_path);
+                }
+            }
+        }
+
+        OutputFormat::Json if arrangement.is_default() => {
+            print_json(&find_results_to_json(results, project_root))
+        }
+        OutputFormat::Json => print_json(&arranged_json(&groups, |r| {
+            find_result_json(r, project_root)
+        })),
+    }
+}
+
+/// The four `stats --files` rankings: title, JSON key, and ranking metric.
+type FileMetric = fn(&crate::query::stats::FileStats) -> usize;
+const FILE_RANKINGS: [(&str, &str, FileMetric); 4] = [
+    ("Most symbols", "most_symbols", |f| f.symbols),
+    ("Most lines", "most_lines", |f| f.lines),
+    ("Highest fan-in (imported by)", "fan_in", |f| f.fan_in),
+    ("Highest fan-out (imports)", "fan_out", |f| f.fan_out),
+];
+
+/// Format and print the top `n` files by symbol count, lines, fan-in, and fan-out.
+pub fn format_file_rankings(
+    files: &[crate::query::stats::FileStats],
+    n: usize,
+    format: &OutputFormat,
+    project_root: &Path,
+) {
+    use crate::query::stats::top_files;
+
+    let rel = |p: &Path| {
+        p.strip_prefix(project_root)
+            .unwrap_or(p)
+            .to_string_lossy()
+            .to_string()
+    };
+
+    match format {
+        OutputFormat::Compact => {
+            for (i, (title, _, metric)) in FILE_RANKINGS.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                println!("{}:", title);
+                for f in top_files(files, metric, n) {
+                    println!("  {:>6} {}", metric(&f), rel(&f.path));
+                }
+            }
+        }
+
+        OutputFormat::Table => {
+            let use_color = crate::pager::start();
+            for (i, (title, _, metric)) in FILE_RANKINGS.iter().enumerate() {
+                let top = top_files(files, metric, n);
+                let file_w = top
+                    .iter()
+                    .map(|f| table_path(&f.path, project_root).chars().count())
+                    .max()
+                    .unwrap_or(4)
+                    .max(4);
+                if i > 0 {
+                    println!();
+                }
+                let header = format!(
+                    "{:<file_w$}  {:>7}  {:>6}  {:>6}  {:>7}",
+                    "FILE", "SYMBOLS", "LINES", "FAN-IN", "FAN-OUT"
+                );
+                if use_color {
+                    println!("\x1b[1m=== {} ===\x1b[0m", title);
+                    println!("\x1b[1m{}\x1b[0m", header);
+                } else {
+                    println!("=== {} ===", title);
+                    println!("{}", header);
+                }
+                println!("{}", "-".repeat(file_w + 34));
+                for f in &top {
                     println!(
-                        "{:<name_w$}  {:<file_w$}  {:>4}  {}",
-                        r.symbol_name,
-                        rel.display(),
-                        r.line,
-                        kind_to_str(&r.kind),
-                        name_w = name_w,
-                        file_w = file_w,
+                        "{:<file_w$}  {:>7}  {:>6}  {:>6}  {:>7}",
+                        table_path(&f.path, project_root),
+                        f.symbols,
+                        f.lines,
+                        f.fan_in,
+                        f.fan_out
                     );
                 }
             }
         }
 
         OutputFormat::Json => {
-            let json_results: Vec<serde_json::Value> = results_ref
-                .iter()
-                .map(|r| {
-                    let rel = r
-                        .file_path
-                        .strip_prefix(project_root)
-                        .unwrap_or(&r.file_path);
-                    serde_json::json!({
-                        "name": r.symbol_name,
-                        "kind": kind_to_str(&r.kind),
-                        "file": rel.to_string_lossy(),
-                        "language": language_of_file(&r.file_path),
-                        "line": r.line,
-                        "col": r.col,
-                        "exported": r.is_exported,
-                        "default": r.is_default,
-                        "visibility": visibility_str(&r.visibility),
+            let mut obj = serde_json::Map::new();
+            for (_, key, metric) in FILE_RANKINGS.iter() {
+                let entries: Vec<serde_json::Value> = top_files(files, metric, n)
+                    .iter()
+                    .map(|f| {
+                        serde_json::json!({
+                            "file": rel(&f.path),
+                            "symbols": f.symbols,
+                            "lines": f.lines,
+                            "fan_in": f.fan_in,
+                            "fan_out": f.fan_out,
+                        })
                     })
-                })
-                .collect();
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&json_results).unwrap_or_default()
-            );
+                    .collect();
+                obj.insert(key.to_string(), serde_json::Value::Array(entries));
+            }
+            print_json(&serde_json::Value::Object(obj));
         }
     }
 }
@@ -296,7 +563,7 @@ fn stats_has_rust(stats: &ProjectStats) -> bool {
 
 /// Determine if the stats have TypeScript/JavaScript symbols present.
 fn stats_has_ts_js(stats: &ProjectStats) -> bool {
-    // Total symbols minus Rust-specific, Python-specific, and Go-specific symbols indicates TS/JS presence.
+    // Total symbols minus Rust, Python, Go, protobuf, and SQL symbols indicates TS/JS presence.
     let rust_total = stats.rust_fns
         + stats.rust_structs
         + stats.rust_enums
@@ -306,14 +573,25 @@ fn stats_has_ts_js(stats: &ProjectStats) -> bool {
         + stats.rust_consts
         + stats.rust_statics
         + stats.rust_macros;
-    let non_rust_non_py_non_go = stats
-        .symbol_count
-        .saturating_sub(rust_total + stats.python_symbol_count + stats.go_symbol_count);
+    let non_rust_non_py_non_go = stats.symbol_count.saturating_sub(
+        rust_total
+            + stats.python_symbol_count
+            + stats.go_symbol_count
+            + stats.proto_symbol_count
+            + stats.sql_symbol_count
+            + stats.zig_symbol_count
+            + stats.shell_fns
+            + stats.css_symbol_count,
+    );
     non_rust_non_py_non_go > 0
         || stats.classes > stats.python_classes
         || stats.interfaces > stats.go_interfaces
-        || stats.variables > stats.python_variables + stats.go_variables
-        || stats.methods > stats.python_methods + stats.go_methods
+        || stats.variables
+            > stats.python_variables
+                + stats.go_variables
+                + stats.zig_variables
+                + stats.css_variables
+        || stats.methods > stats.python_methods + stats.go_methods + stats.zig_methods
         || stats.components > 0
 }
 
@@ -327,6 +605,41 @@ fn stats_has_go(stats: &ProjectStats) -> bool {
     stats.go_file_count > 0 || stats.go_symbol_count > 0
 }
 
+/// Determine if the stats have protobuf symbols or files present.
+fn stats_has_proto(stats: &ProjectStats) -> bool {
+    stats.proto_file_count > 0 || stats.proto_symbol_count > 0
+}
+
+/// Determine if the stats have SQL symbols or files present.
+fn stats_has_sql(stats: &ProjectStats) -> bool {
+    stats.sql_file_count > 0 || stats.sql_symbol_count > 0
+}
+
+/// Determine if the stats have Zig symbols or files present.
+fn stats_has_zig(stats: &ProjectStats) -> bool {
+    stats.zig_file_count > 0 || stats.zig_symbol_count > 0
+}
+
+/// Determine if the stats have shell scripts present.
+fn stats_has_shell(stats: &ProjectStats) -> bool {
+    stats.shell_file_count > 0 || stats.shell_fns > 0
+}
+
+/// Determine if the stats have CSS/SCSS stylesheets present.
+fn stats_has_css(stats: &ProjectStats) -> bool {
+    stats.css_file_count > 0 || stats.css_symbol_count > 0
+}
+
+/// Determine if the stats have Jupyter notebooks present.
+fn stats_has_jupyter(stats: &ProjectStats) -> bool {
+    stats.notebook_file_count > 0
+}
+
+/// Number of non-canonical copies across all identical-file groups.
+fn identical_copy_count(stats: &ProjectStats) -> usize {
+    stats.identical_files.iter().map(|g| g.copies.len()).sum()
+}
+
 /// Format and print project stats to stdout according to the selected output format.
 ///
 /// `language_filter`: if Some("rust"), show only Rust section; if Some("typescript"),
@@ -337,188 +650,29 @@ pub fn format_stats(stats: &ProjectStats, format: &OutputFormat, language_filter
         || language_filter == Some("typescript")
         || language_filter == Some("javascript");
     let show_python = language_filter.is_none() || language_filter == Some("python");
-    let show_go = language_filter.is_none() || language_filter == Some("go");
+    let show_proto = language_filter.is_none() || language_filter == Some("proto");
+    let show_sql = language_filter.is_none() || language_filter == Some("sql");
+    let show_zig = language_filter.is_none() || language_filter == Some("zig");
+    let show_shell = language_filter.is_none() || language_filter == Some("shell");
+    let show_jupyter = language_filter.is_none() || language_filter == Some("jupyter");
+    let show_css = language_filter.is_none() || language_filter == Some("css");
     let show_totals = language_filter.is_none();
 
     let has_rust = stats_has_rust(stats);
     let has_ts = stats_has_ts_js(stats);
     let has_python = stats_has_python(stats);
-    let has_go = stats_has_go(stats);
+    let has_proto = stats_has_proto(stats);
+    let has_sql = stats_has_sql(stats);
+    let has_zig = stats_has_zig(stats);
+    let has_shell = stats_has_shell(stats);
+    let has_jupyter = stats_has_jupyter(stats);
+    let has_css = stats_has_css(stats);
 
     match format {
-        OutputFormat::Compact => {
-            // File overview line
-            if stats.non_parsed_files > 0 {
-                println!(
-                    "{} files ({} source, {} non-parsed), {} symbols",
-                    stats.file_count,
-                    stats.source_files,
-                    stats.non_parsed_files,
-                    stats.symbol_count
-                );
-                println!(
-                    "non-parsed: doc {} config {} ci {} asset {} other {}",
-                    stats.doc_files,
-                    stats.config_files,
-                    stats.ci_files,
-                    stats.asset_files,
-                    stats.other_files,
-                );
-            }
-            // Per-language sections with per-language counts and combined totals.
-            if show_rust && has_rust {
-                let rust_symbol_total = stats.rust_fns
-                    + stats.rust_structs
-                    + stats.rust_enums
-                    + stats.rust_traits
-                    + stats.rust_impl_methods
-                    + stats.rust_type_aliases
-                    + stats.rust_consts
-                    + stats.rust_statics
-                    + stats.rust_macros;
-                println!(
-                    "Rust: {} symbols (fn: {} struct: {} enum: {} trait: {} impl_method: {} type: {} const: {} static: {} macro: {})",
-                    rust_symbol_total,
-                    stats.rust_fns,
-                    stats.rust_structs,
-                    stats.rust_enums,
-                    stats.rust_traits,
-                    stats.rust_impl_methods,
-                    stats.rust_type_aliases,
-                    stats.rust_consts,
-                    stats.rust_statics,
-                    stats.rust_macros,
-                );
-                println!(
-                    "rust_use {} rust_pub_use {}",
-                    stats.rust_imports, stats.rust_reexports,
-                );
-                // Dependencies section (Phase 9)
-                let has_deps = stats.external_packages > 0 || stats.builtin_count > 0;
-                if has_deps {
-                    println!(
-                        "dependencies external_crates {} (usages {}) builtins {} (usages {})",
-                        stats.external_packages,
-                        stats.external_usage_count,
-                        stats.builtin_count,
-                        stats.builtin_usage_count,
-                    );
-                }
-                // Per-crate breakdown (Phase 9, only for workspaces with multiple crates)
-                if !stats.rust_crate_stats.is_empty() {
-                    for cs in &stats.rust_crate_stats {
-                        println!(
-                            "crate {} files {} symbols {}",
-                            cs.crate_name, cs.file_count, cs.symbol_count
-                        );
-                    }
-                }
-            }
-            if show_ts && has_ts {
-                // Subtract Rust-specific, Python, and Go symbols to get TS/JS-only counts.
-                let ts_fns = stats
-                    .functions
-                    .saturating_sub(stats.rust_fns + stats.python_fns + stats.go_fns);
-                let ts_classes = stats.classes.saturating_sub(stats.python_classes);
-                let ts_enums = stats.enums.saturating_sub(stats.rust_enums);
-                let ts_type_aliases = stats.type_aliases.saturating_sub(
-                    stats.rust_type_aliases + stats.python_type_aliases + stats.go_type_aliases,
-                );
-                let ts_variables = stats
-                    .variables
-                    .saturating_sub(stats.python_variables + stats.go_variables);
-                let ts_methods = stats
-                    .methods
-                    .saturating_sub(stats.python_methods + stats.go_methods);
-                let rust_total = stats.rust_fns
-                    + stats.rust_structs
-                    + stats.rust_enums
-                    + stats.rust_traits
-                    + stats.rust_impl_methods
-                    + stats.rust_type_aliases
-                    + stats.rust_consts
-                    + stats.rust_statics
-                    + stats.rust_macros;
-                let ts_total = stats
-                    .symbol_count
-                    .saturating_sub(rust_total + stats.python_symbol_count + stats.go_symbol_count);
-                println!(
-                    "TypeScript: {} symbols (function: {} class: {} interface: {} type: {} enum: {} variable: {} component: {} method: {} property: {})",
-                    ts_total,
-                    ts_fns,
-                    ts_classes,
-                    stats.interfaces,
-                    ts_type_aliases,
-                    ts_enums,
-                    ts_variables,
-                    stats.components,
-                    ts_methods,
-                    stats.properties,
-                );
-                println!(
-                    "imports {} external {} unresolved {}",
-                    stats.import_edges, stats.external_packages, stats.unresolved_imports,
-                );
-            }
-            if show_python && has_python {
-                println!(
-                    "Python: {} files, {} symbols (fn: {} class: {} method: {} type: {} variable: {})",
-                    stats.python_file_count,
-                    stats.python_symbol_count,
-                    stats.python_fns,
-                    stats.python_classes,
-                    stats.python_methods,
-                    stats.python_type_aliases,
-                    stats.python_variables,
-                );
-            }
-            if show_go && has_go {
-                println!(
-                    "Go: {} files, {} symbols (fn: {} struct: {} interface: {} method: {} const: {} var: {} type: {})",
-                    stats.go_file_count,
-                    stats.go_symbol_count,
-                    stats.go_fns,
-                    stats.go_structs,
-                    stats.go_interfaces,
-                    stats.go_methods,
-                    stats.go_consts,
-                    stats.go_variables,
-                    stats.go_type_aliases,
-                );
-            }
-            if show_totals && (has_rust || has_ts || has_python || has_go) {
-                let language_count = [has_rust, has_ts, has_python, has_go]
-                    .iter()
-                    .filter(|&&x| x)
-                    .count();
-                if language_count > 1 {
-                    println!("---");
-                    println!(
-                        "Total: {} files, {} symbols",
-                        stats.file_count, stats.symbol_count
-                    );
-                } else {
-                    println!("files {}", stats.file_count);
-                    println!("symbols {}", stats.symbol_count);
-                }
-            } else if show_totals {
-                println!("files {}", stats.file_count);
-                println!("symbols {}", stats.symbol_count);
-            }
-            // Fallback: show full stats if no language-specific sections match
-            if !has_rust && !has_ts && !has_python && !has_go {
-                println!("files {}", stats.file_count);
-                println!("symbols {}", stats.symbol_count);
-                println!(
-                    "imports {} external {} unresolved {}",
-                    stats.import_edges, stats.external_packages, stats.unresolved_imports
-                );
-            }
-            println!("hint: use dead-code to find unreferenced symbols");
-        }
+        OutputFormat::Compact => print!("{}", format_stats_to_string(stats, language_filter)),
 
         OutputFormat::Table => {
-            let use_color = std::io::stdout().is_terminal();
+            let use_color = crate::pager::start();
             let header = |s: &str| {
                 if use_color {
                     format!("\x1b[1m{s}\x1b[0m")
@@ -534,6 +688,14 @@ pub fn format_stats(stats: &ProjectStats, format: &OutputFormat, language_filter
                     stats.file_count, stats.source_files, stats.non_parsed_files
                 );
                 println!("Symbols:  {}", stats.symbol_count);
+                println!("Lines:    {}", stats.total_lines);
+                if !stats.identical_files.is_empty() {
+                    println!(
+                        "Identical copies: {} in {} groups (skipped by dead-code and clones)",
+                        identical_copy_count(stats),
+                        stats.identical_files.len()
+                    );
+                }
                 if stats.non_parsed_files > 0 {
                     println!(
                         "  doc: {} config: {} ci: {} asset: {} other: {}",
@@ -549,16 +711,26 @@ pub fn format_stats(stats: &ProjectStats, format: &OutputFormat, language_filter
 
             if show_ts && has_ts {
                 // Subtract both Rust-specific and Python symbols to get TS/JS-only counts.
-                let ts_fns = stats
-                    .functions
-                    .saturating_sub(stats.rust_fns + stats.python_fns);
+                let ts_fns = stats.functions.saturating_sub(
+                    stats.rust_fns
+                        + stats.python_fns
+                        + stats.zig_fns
+                        + stats.shell_fns
+                        + stats.css_fns,
+                );
                 let ts_classes = stats.classes.saturating_sub(stats.python_classes);
-                let ts_enums = stats.enums.saturating_sub(stats.rust_enums);
+                let ts_enums = stats
+                    .enums
+                    .saturating_sub(stats.rust_enums + stats.proto_enums + stats.zig_enums);
                 let ts_type_aliases = stats
                     .type_aliases
                     .saturating_sub(stats.rust_type_aliases + stats.python_type_aliases);
-                let ts_variables = stats.variables.saturating_sub(stats.python_variables);
-                let ts_methods = stats.methods.saturating_sub(stats.python_methods);
+                let ts_variables = stats.variables.saturating_sub(
+                    stats.python_variables + stats.zig_variables + stats.css_variables,
+                );
+                let ts_methods = stats
+                    .methods
+                    .saturating_sub(stats.python_methods + stats.zig_methods);
                 println!("{}", header("--- TypeScript/JavaScript ---"));
                 println!("  Functions:    {}", ts_fns);
                 println!("  Classes:      {}", ts_classes);
@@ -605,6 +777,65 @@ pub fn format_stats(stats: &ProjectStats, format: &OutputFormat, language_filter
                 println!("  Variables:    {}", stats.python_variables);
             }
 
+            // Protobuf section — only when .proto files are present and filter allows
+            if show_proto && has_proto {
+                println!();
+                println!("{}", header("--- Protobuf ---"));
+                println!("  Files:        {}", stats.proto_file_count);
+                println!("  Symbols:      {}", stats.proto_symbol_count);
+                println!("  Messages:     {}", stats.proto_messages);
+                println!("  Enums:        {}", stats.proto_enums);
+                println!("  Services:     {}", stats.proto_services);
+                println!("  RPCs:         {}", stats.proto_rpcs);
+            }
+
+            // SQL section — only when .sql files are present and filter allows
+            if show_sql && has_sql {
+                println!();
+                println!("{}", header("--- SQL ---"));
+                println!("  Files:        {}", stats.sql_file_count);
+                println!("  Symbols:      {}", stats.sql_symbol_count);
+                println!("  Tables:       {}", stats.sql_tables);
+                println!("  Views:        {}", stats.sql_views);
+                println!("  Table refs:   {}", stats.sql_table_refs);
+            }
+
+            // Zig section — only when .zig files are present and filter allows
+            if show_zig && has_zig {
+                println!();
+                println!("{}", header("--- Zig ---"));
+                println!("  Files:        {}", stats.zig_file_count);
+                println!("  Symbols:      {}", stats.zig_symbol_count);
+                println!("  Functions:    {}", stats.zig_fns);
+                println!("  Methods:      {}", stats.zig_methods);
+                println!("  Structs:      {}", stats.zig_structs);
+                println!("  Enums:        {}", stats.zig_enums);
+            }
+
+            // Shell section — only when shell scripts are present and filter allows
+            if show_shell && has_shell {
+                println!();
+                println!("{}", header("--- Shell ---"));
+                println!("  Files:        {}", stats.shell_file_count);
+                println!("  Functions:    {}", stats.shell_fns);
+            }
+
+            // Jupyter section — symbols are listed under each notebook's kernel language
+            if show_jupyter && has_jupyter {
+                println!();
+                println!("{}", header("--- Jupyter ---"));
+                println!("  Notebooks:    {}", stats.notebook_file_count);
+            }
+
+            // CSS section — only when stylesheets are present and filter allows
+            if show_css && has_css {
+                println!();
+                println!("{}", header("--- CSS/SCSS ---"));
+                println!("  Files:        {}", stats.css_file_count);
+                println!("  Mixins/fns:   {}", stats.css_fns);
+                println!("  Variables:    {}", stats.css_variables);
+            }
+
             // Rust section — only when Rust symbols are present and filter allows
             if show_rust && has_rust {
                 println!();
@@ -614,12 +845,24 @@ pub fn format_stats(stats: &ProjectStats, format: &OutputFormat, language_filter
                 println!("  enum:        {}", stats.rust_enums);
                 println!("  trait:       {}", stats.rust_traits);
                 println!("  impl method: {}", stats.rust_impl_methods);
+                if stats.rust_derived_impl_methods + stats.rust_derive_traits > 0 {
+                    println!(
+                        "    derived:   {} impl methods, {} #[derive] traits",
+                        stats.rust_derived_impl_methods, stats.rust_derive_traits
+                    );
+                }
                 println!("  type:        {}", stats.rust_type_aliases);
                 println!("  const:       {}", stats.rust_consts);
                 println!("  static:      {}", stats.rust_statics);
                 println!("  macro:       {}", stats.rust_macros);
                 println!("  use (unresolved): {}", stats.rust_imports);
                 println!("  pub use (re-exports): {}", stats.rust_reexports);
+                if stats.rust_cfg_test + stats.rust_cfg_feature + stats.rust_cfg_other > 0 {
+                    println!(
+                        "  cfg-gated:   test {} feature {} other {}",
+                        stats.rust_cfg_test, stats.rust_cfg_feature, stats.rust_cfg_other
+                    );
+                }
 
                 // Dependencies section (Phase 9)
                 let has_deps = stats.external_packages > 0 || stats.builtin_count > 0;
@@ -659,84 +902,30 @@ pub fn format_stats(stats: &ProjectStats, format: &OutputFormat, language_filter
                     }
                 }
             }
-        }
 
-        OutputFormat::Json => {
-            // Build per-crate breakdown as JSON array
-            let crate_stats_json: Vec<serde_json::Value> = stats
-                .rust_crate_stats
-                .iter()
-                .map(|cs| {
-                    serde_json::json!({
-                        "crate_name": cs.crate_name,
-                        "file_count": cs.file_count,
-                        "symbol_count": cs.symbol_count,
-                        "fn_count": cs.fn_count,
-                        "struct_count": cs.struct_count,
-                        "enum_count": cs.enum_count,
-                        "trait_count": cs.trait_count,
-                        "impl_method_count": cs.impl_method_count,
-                        "type_alias_count": cs.type_alias_count,
-                        "const_count": cs.const_count,
-                        "static_count": cs.static_count,
-                        "macro_count": cs.macro_count,
-                    })
-                })
-                .collect();
-
-            let json = serde_json::json!({
-                "file_count": stats.file_count,
-                "source_files": stats.source_files,
-                "non_parsed_files": stats.non_parsed_files,
-                "doc_files": stats.doc_files,
-                "config_files": stats.config_files,
-                "ci_files": stats.ci_files,
-                "asset_files": stats.asset_files,
-                "other_files": stats.other_files,
-                "symbol_count": stats.symbol_count,
-                "functions": stats.functions,
-                "classes": stats.classes,
-                "interfaces": stats.interfaces,
-                "type_aliases": stats.type_aliases,
-                "enums": stats.enums,
-                "variables": stats.variables,
-                "components": stats.components,
-                "methods": stats.methods,
-                "properties": stats.properties,
-                "import_edges": stats.import_edges,
-                "external_packages": stats.external_packages,
-                "unresolved_imports": stats.unresolved_imports,
-                "rust_fns": stats.rust_fns,
-                "rust_structs": stats.rust_structs,
-                "rust_enums": stats.rust_enums,
-                "rust_traits": stats.rust_traits,
-                "rust_impl_methods": stats.rust_impl_methods,
-                "rust_type_aliases": stats.rust_type_aliases,
-                "rust_consts": stats.rust_consts,
-                "rust_statics": stats.rust_statics,
-                "rust_macros": stats.rust_macros,
-                "rust_imports": stats.rust_imports,
-                "rust_reexports": stats.rust_reexports,
-                "dependencies": {
-                    "external_crates": stats.external_packages,
-                    "external_usage_count": stats.external_usage_count,
-                    "builtin_crates": stats.builtin_count,
-                    "builtin_usage_count": stats.builtin_usage_count,
-                },
-                "crate_stats": crate_stats_json,
-                "python_file_count": stats.python_file_count,
-                "python_symbol_count": stats.python_symbol_count,
-                "python_fns": stats.python_fns,
-                "python_classes": stats.python_classes,
-                "python_methods": stats.python_methods,
-                "python_type_aliases": stats.python_type_aliases,
-                "python_variables": stats.python_variables,
-            });
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&json).unwrap_or_default()
-            );
+            if show_totals && !stats.largest_files.is_empty() {
+                println!();
+                println!("{}", header("--- Largest Files ---"));
+                for f in &stats.largest_files {
+                    println!("  {:>6}  {}", f.lines, f.file);
+                }
+            }
+            if show_totals && !stats.largest_functions.is_empty() {
+                println!();
+                println!("{}", header("--- Largest Functions ---"));
+                for f in &stats.largest_functions {
+                    println!(
+                        "  {:>6}  {} ({}:{})",
+                        f.lines,
+                        f.name.as_deref().unwrap_or_default(),
+                        f.file,
+                        f.line
+                    );
+                }
+            }
         }
+
+        OutputFormat::Json => print_json(&stats_to_json(stats)),
     }
 }
 
@@ -744,28 +933,52 @@ pub fn format_stats(stats: &ProjectStats, format: &OutputFormat, language_filter
 // Refs output
 // ---------------------------------------------------------------------------
 
-/// Format and print reference results to stdout.
+/// `rel` for an import reference, followed by `:line` and `:col` when the import statement's
+/// position is known.
+fn import_site(rel: &Path, r: &RefResult) -> String {
+    match (r.line, r.col) {
+        (Some(line), Some(col)) => format!("{}:{}:{}", rel.display(), line, col),
+        (Some(line), None) => format!("{}:{}", rel.display(), line),
+        _ => rel.display().to_string(),
+    }
+}
+
+/// Format and print reference results to stdout, in `arrangement`'s groups under
+/// `--- {group} ---` headers.
 pub fn format_refs_results(
     results: &[RefResult],
     format: &OutputFormat,
     project_root: &Path,
     symbol_name: &str,
+    arrangement: &Arrangement,
 ) {
+    let groups = refs_groups(results, arrangement, project_root);
     match format {
         OutputFormat::Compact => {
-            for r in results {
-                let rel = r
-                    .file_path
-                    .strip_prefix(project_root)
-                    .unwrap_or(&r.file_path);
-                match r.ref_kind {
-                    RefKind::Import => {
-                        println!("ref {} import", rel.display());
-                    }
-                    RefKind::Call => {
-                        let caller = r.symbol_name.as_deref().unwrap_or("?");
-                        let line = r.line.map_or_else(|| "?".to_string(), |l| l.to_string());
-                        println!("ref {}:{} call {}", rel.display(), line, caller);
+            for (group, items) in &groups {
+                if let Some(group) = group {
+                    println!("--- {} ---", group);
+                }
+                for r in items {
+                    let rel = r
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&r.file_path);
+                    match r.ref_kind {
+                        RefKind::Import => {
+                            println!("ref {} import", import_site(rel, r));
+                        }
+                        RefKind::Call | RefKind::Render | RefKind::Read | RefKind::Write => {
+                            let caller = r.symbol_name.as_deref().unwrap_or("?");
+                            let line = r.line.map_or_else(|| "?".to_string(), |l| l.to_string());
+                            println!(
+                                "ref {}:{} {} {}",
+                                rel.display(),
+                                line,
+                                r.ref_kind.as_str(),
+                                caller
+                            );
+                        }
                     }
                 }
             }
@@ -778,17 +991,11 @@ pub fn format_refs_results(
         }
 
         OutputFormat::Table => {
-            let use_color = std::io::stdout().is_terminal();
+            let use_color = crate::pager::start();
 
             let file_w = results
                 .iter()
-                .map(|r| {
-                    r.file_path
-                        .strip_prefix(project_root)
-                        .unwrap_or(&r.file_path)
-                        .to_string_lossy()
-                        .len()
-                })
+                .map(|r| table_path(&r.file_path, project_root).chars().count())
                 .max()
                 .unwrap_or(4)
                 .max(4);
@@ -822,54 +1029,31 @@ pub fn format_refs_results(
             }
             println!("{}", "-".repeat(file_w + caller_w + 20));
 
-            for r in results {
-                let rel = r
-                    .file_path
-                    .strip_prefix(project_root)
-                    .unwrap_or(&r.file_path);
-                let kind_str = match r.ref_kind {
-                    RefKind::Import => "import",
-                    RefKind::Call => "call",
-                };
-                let caller = r.symbol_name.as_deref().unwrap_or("");
-                let line_str = r.line.map_or_else(|| "-".to_string(), |l| l.to_string());
-                println!(
-                    "{:<file_w$}  {:<6}  {:<caller_w$}  {:>6}",
-                    rel.display(),
-                    kind_str,
-                    caller,
-                    line_str,
-                    file_w = file_w,
-                    caller_w = caller_w,
-                );
+            for (group, items) in &groups {
+                if let Some(group) = group {
+                    println!("--- {} ---", group);
+                }
+                for r in items {
+                    let rel = table_path(&r.file_path, project_root);
+                    let kind_str = r.ref_kind.as_str();
+                    let caller = r.symbol_name.as_deref().unwrap_or("");
+                    let line_str = r.line.map_or_else(|| "-".to_string(), |l| l.to_string());
+                    println!(
+                        "{:<file_w$}  {:<6}  {:<caller_w$}  {:>6}",
+                        rel,
+                        kind_str,
+                        caller,
+                        line_str,
+                        file_w = file_w,
+                        caller_w = caller_w,
+                    );
+                }
             }
         }
 
-        OutputFormat::Json => {
-            let json_results: Vec<serde_json::Value> = results
-                .iter()
-                .map(|r| {
-                    let rel = r
-                        .file_path
-                        .strip_prefix(project_root)
-                        .unwrap_or(&r.file_path);
-                    let kind_str = match r.ref_kind {
-                        RefKind::Import => "import",
-                        RefKind::Call => "call",
-                    };
-                    serde_json::json!({
-                        "file": rel.to_string_lossy(),
-                        "kind": kind_str,
-                        "caller": r.symbol_name,
-                        "line": r.line,
-                    })
-                })
-                .collect();
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&json_results).unwrap_or_default()
-            );
-        }
+        OutputFormat::Json => print_json(&arranged_json(&groups, |r| {
+            ref_result_json(r, project_root)
+        })),
     }
 }
 
@@ -877,7 +1061,8 @@ pub fn format_refs_results(
 // Impact output
 // ---------------------------------------------------------------------------
 
-/// Format and print impact (blast radius) results to stdout.
+/// Format and print impact (blast radius) results to stdout, in `arrangement`'s groups
+/// under `--- {group} ---` headers.
 ///
 /// `tree_mode`: when true, use 2-space indentation per depth level.
 pub fn format_impact_results(
@@ -886,16 +1071,25 @@ pub fn format_impact_results(
     project_root: &Path,
     tree_mode: bool,
     symbol_name: &str,
+    arrangement: &Arrangement,
 ) {
+    let groups = impact_groups(results, arrangement, project_root);
     match format {
         OutputFormat::Compact => {
-            if tree_mode {
-                for r in results {
+            for (group, items) in &groups {
+                if let Some(group) = group {
+                    println!("--- {} ---", group);
+                }
+                for r in items {
                     let rel = r
                         .file_path
                         .strip_prefix(project_root)
                         .unwrap_or(&r.file_path);
-                    let indent = "  ".repeat(r.depth.saturating_sub(1));
+                    let indent = if tree_mode {
+                        "  ".repeat(r.depth.saturating_sub(1))
+                    } else {
+                        String::new()
+                    };
                     println!(
                         "{}impact {} [{}: {}]",
                         indent,
@@ -904,14 +1098,6 @@ pub fn format_impact_results(
                         r.basis
                     );
                 }
-            } else {
-                for r in results {
-                    let rel = r
-                        .file_path
-                        .strip_prefix(project_root)
-                        .unwrap_or(&r.file_path);
-                    println!("impact {} [{}: {}]", rel.display(), r.confidence, r.basis);
-                }
             }
             println!("{} files affected", results.len());
             if results.is_empty() {
@@ -925,17 +1111,11 @@ pub fn format_impact_results(
         }
 
         OutputFormat::Table => {
-            let use_color = std::io::stdout().is_terminal();
+            let use_color = crate::pager::start();
 
             let file_w = results
                 .iter()
-                .map(|r| {
-                    r.file_path
-                        .strip_prefix(project_root)
-                        .unwrap_or(&r.file_path)
-                        .to_string_lossy()
-                        .len()
-                })
+                .map(|r| table_path(&r.file_path, project_root).chars().count())
                 .max()
                 .unwrap_or(4)
                 .max(4);
@@ -959,43 +1139,1285 @@ pub fn format_impact_results(
             }
             println!("{}", "-".repeat(file_w + 8 + 14 + 20));
 
-            for r in results {
-                let rel = r
-                    .file_path
-                    .strip_prefix(project_root)
-                    .unwrap_or(&r.file_path);
+            for (group, items) in &groups {
+                if let Some(group) = group {
+                    println!("--- {} ---", group);
+                }
+                for r in items {
+                    let rel = table_path(&r.file_path, project_root);
+                    println!(
+                        "{:>5}  {:<file_w$}  {:<10}  {}",
+                        r.depth,
+                        rel,
+                        r.confidence.to_string(),
+                        r.basis,
+                        file_w = file_w,
+                    );
+                }
+            }
+        }
+
+        OutputFormat::Json => print_json(&arranged_json(&groups, |r| {
+            impact_result_json(r, project_root)
+        })),
+    }
+}
+
+/// Format and print `impact --by-dir` results: affected files rolled up to directories.
+pub fn format_impact_dirs(
+    dirs: &[crate::query::impact::DirImpact],
+    format: &OutputFormat,
+    symbol_name: &str,
+) {
+    let file_count: usize = dirs.iter().map(|d| d.files).sum();
+    match format {
+        OutputFormat::Compact => {
+            for d in dirs {
+                println!("impact {}/ {} files [depth {}]", d.dir, d.files, d.depth);
+            }
+            println!("{} directories affected ({} files)", dirs.len(), file_count);
+            if dirs.is_empty() {
+                println!("hint: no results found -- try a broader pattern or check spelling");
+            } else {
+                println!("hint: drop --by-dir to list the files of {}", symbol_name);
+            }
+        }
+
+        OutputFormat::Table => {
+            let use_color = crate::pager::start();
+            let dir_w = dirs
+                .iter()
+                .map(|d| crate::pager::fit_path(&d.dir).chars().count())
+                .max()
+                .unwrap_or(3)
+                .max(3);
+            let header = format!("{:>5}  {:<dir_w$}  {:>5}", "DEPTH", "DIR", "FILES");
+            if use_color {
+                println!("\x1b[1m{}\x1b[0m", header);
+            } else {
+                println!("{}", header);
+            }
+            println!("{}", "-".repeat(dir_w + 14));
+            for d in dirs {
                 println!(
-                    "{:>5}  {:<file_w$}  {:<10}  {}",
-                    r.depth,
-                    rel.display(),
-                    r.confidence.to_string(),
-                    r.basis,
-                    file_w = file_w,
+                    "{:>5}  {:<dir_w$}  {:>5}",
+                    d.depth,
+                    crate::pager::fit_path(&d.dir),
+                    d.files
                 );
             }
         }
 
+        OutputFormat::Json => print_json(&serde_json::to_value(dirs).unwrap_or_default()),
+    }
+}
+
+/// Format and print `impact --estimate` results: per-file effort scores and the total.
+pub fn format_impact_estimate(
+    estimate: &crate::query::impact::EffortEstimate,
+    format: &OutputFormat,
+    symbol_name: &str,
+) {
+    let commits = |churn: Option<usize>| churn.map_or("-".to_string(), |c| c.to_string());
+    let tested = |tested: Option<bool>| match tested {
+        Some(true) => "tested",
+        Some(false) => "untested",
+        None => "test",
+    };
+    match format {
+        OutputFormat::Compact => {
+            for f in &estimate.files {
+                println!(
+                    "estimate {} effort {:.1} [{} symbols, {} loc, {} commits, {}]",
+                    f.file,
+                    f.effort,
+                    f.symbols,
+                    f.loc,
+                    commits(f.churn),
+                    tested(f.tested)
+                );
+            }
+            println!(
+                "{} files affected, estimated effort {:.1}",
+                estimate.files.len(),
+                estimate.total
+            );
+            if estimate.files.is_empty() {
+                println!("hint: no results found -- try a broader pattern or check spelling");
+            } else {
+                println!(
+                    "hint: use context {} for full dependency picture",
+                    symbol_name
+                );
+            }
+        }
+
+        OutputFormat::Table => {
+            let use_color = crate::pager::start();
+            let file_w = estimate
+                .files
+                .iter()
+                .map(|f| crate::pager::fit_path(&f.file).chars().count())
+                .max()
+                .unwrap_or(4)
+                .max(4);
+            let header = format!(
+                "{:>6}  {:<file_w$}  {:>7}  {:>6}  {:>7}  TESTS",
+                "EFFORT", "FILE", "SYMBOLS", "LOC", "COMMITS"
+            );
+            if use_color {
+                println!("\x1b[1m{}\x1b[0m", header);
+            } else {
+                println!("{}", header);
+            }
+            println!("{}", "-".repeat(file_w + 46));
+            for f in &estimate.files {
+                println!(
+                    "{:>6.1}  {:<file_w$}  {:>7}  {:>6}  {:>7}  {}",
+                    f.effort,
+                    crate::pager::fit_path(&f.file),
+                    f.symbols,
+                    f.loc,
+                    commits(f.churn),
+                    tested(f.tested)
+                );
+            }
+            println!("{:>6.1}  total", estimate.total);
+        }
+
+        OutputFormat::Json => print_json(&serde_json::to_value(estimate).unwrap_or_default()),
+    }
+}
+
+/// Format `query` results: one line per matching symbol (as in `find`) or file.
+pub fn format_dsl_query_results(
+    result: &crate::query::dsl::QueryResult,
+    format: &OutputFormat,
+    project_root: &Path,
+) {
+    use crate::query::dsl::QueryResult;
+
+    let rel = |p: &Path| {
+        p.strip_prefix(project_root)
+            .unwrap_or(p)
+            .to_string_lossy()
+            .to_string()
+    };
+    match (result, format) {
+        (QueryResult::Symbols(symbols), OutputFormat::Json) => {
+            print_json(&find_results_to_json(symbols, project_root))
+        }
+        (QueryResult::Files(files), OutputFormat::Json) => {
+            let entries: Vec<serde_json::Value> = files
+                .iter()
+                .map(|f| {
+                    serde_json::json!({
+                        "file": rel(&f.path),
+                        "symbols": f.symbols,
+                        "lines": f.lines,
+                        "importers": f.fan_in,
+                        "imports": f.fan_out,
+                    })
+                })
+                .collect();
+            print_json(&serde_json::Value::Array(entries));
+        }
+
+        (QueryResult::Symbols(symbols), OutputFormat::Compact) => {
+            for r in symbols {
+                println!(
+                    "def {} {}:{} {}",
+                    r.symbol_name,
+                    rel(&r.file_path),
+                    r.line,
+                    kind_to_str(&r.kind)
+                );
+            }
+            println!("{} symbols matched", symbols.len());
+        }
+        (QueryResult::Files(files), OutputFormat::Compact) => {
+            for f in files {
+                println!(
+                    "file {} [{} symbols, {} lines, {} importers, {} imports]",
+                    rel(&f.path),
+                    f.symbols,
+                    f.lines,
+                    f.fan_in,
+                    f.fan_out
+                );
+            }
+            println!("{} files matched", files.len());
+        }
+
+        (QueryResult::Symbols(symbols), OutputFormat::Table) => {
+            let use_color = crate::pager::start();
+            let (name_w, file_w) = symbols.iter().fold((6usize, 4usize), |(nw, fw), r| {
+                let file_len = table_path(&r.file_path, project_root).chars().count();
+                (nw.max(r.symbol_name.chars().count()), fw.max(file_len))
+            });
+            let header = format!(
+                "{:<name_w$}  {:<file_w$}  {:>5}  KIND",
+                "SYMBOL", "FILE", "LINE"
+            );
+            if use_color {
+                println!("\x1b[1m{}\x1b[0m", header);
+            } else {
+                println!("{}", header);
+            }
+            println!("{}", "-".repeat(name_w + file_w + 15));
+            for r in symbols {
+                println!(
+                    "{:<name_w$}  {:<file_w$}  {:>5}  {}",
+                    r.symbol_name,
+                    table_path(&r.file_path, project_root),
+                    r.line,
+                    kind_to_str(&r.kind)
+                );
+            }
+        }
+        (QueryResult::Files(files), OutputFormat::Table) => {
+            let use_color = crate::pager::start();
+            let file_w = files
+                .iter()
+                .map(|f| table_path(&f.path, project_root).chars().count())
+                .max()
+                .unwrap_or(4)
+                .max(4);
+            let header = format!(
+                "{:<file_w$}  {:>7}  {:>6}  {:>9}  {:>7}",
+                "FILE", "SYMBOLS", "LINES", "IMPORTERS", "IMPORTS"
+            );
+            if use_color {
+                println!("\x1b[1m{}\x1b[0m", header);
+            } else {
+                println!("{}", header);
+            }
+            println!("{}", "-".repeat(file_w + 38));
+            for f in files {
+                println!(
+                    "{:<file_w$}  {:>7}  {:>6}  {:>9}  {:>7}",
+                    table_path(&f.path, project_root),
+                    f.symbols,
+                    f.lines,
+                    f.fan_in,
+                    f.fan_out
+                );
+            }
+        }
+    }
+}
+
+/// Format `pkg-query` results: one line per package with its file count and the result
+/// packages it imports from.
+pub fn format_pkg_query_results(
+    results: &[crate::query::pkgql::PackageResult],
+    format: &OutputFormat,
+) {
+    match format {
+        OutputFormat::Compact => {
+            for p in results {
+                if p.deps.is_empty() {
+                    println!("pkg {} [{} files]", p.name, p.files);
+                } else {
+                    println!(
+                        "pkg {} [{} files] -> {}",
+                        p.name,
+                        p.files,
+                        p.deps.join(", ")
+                    );
+                }
+            }
+            println!("{} packages", results.len());
+        }
+
+        OutputFormat::Table => {
+            let use_color = crate::pager::start();
+            let name_w = results
+                .iter()
+                .map(|p| p.name.chars().count())
+                .max()
+                .unwrap_or(7)
+                .max(7);
+            let header = format!("{:<name_w$}  {:>5}  DEPS", "PACKAGE", "FILES");
+            if use_color {
+                println!("\x1b[1m{}\x1b[0m", header);
+            } else {
+                println!("{}", header);
+            }
+            println!("{}", "-".repeat(name_w + 20));
+            for p in results {
+                let row = format!("{:<name_w$}  {:>5}  {}", p.name, p.files, p.deps.join(", "));
+                println!("{}", row.trim_end());
+            }
+        }
+
+        OutputFormat::Json => print_json(&serde_json::to_value(results).unwrap_or_default()),
+    }
+}
+
+/// Format a `duplicates` report: one block per duplicated utility and per duplicated
+/// dependency, with the copies or declarations indented beneath.
+pub fn format_duplicates(
+    report: &crate::query::duplicates::DuplicateReport,
+    format: &OutputFormat,
+) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for utility in &report.utilities {
+        println!("utility {} [{}]", utility.name, utility.symbols.join(", "));
+        for copy in &utility.copies {
+            println!("  {}  {}", copy.package, crate::pager::fit_path(&copy.file));
+        }
+    }
+    for dep in &report.dependencies {
+        let mut notes: Vec<String> = Vec::new();
+        if !dep.aliases.is_empty() {
+            notes.push(format!("aliases: {}", dep.aliases.join(", ")));
+        }
+        if !dep.versions.is_empty() {
+            notes.push(format!("versions: {}", dep.versions.join(", ")));
+        }
+        println!("dependency {} ({})", dep.name, notes.join("; "));
+        for decl in &dep.declarations {
+            let version = decl.version.as_deref().unwrap_or("*");
+            if decl.alias == dep.name {
+                println!("  {}  {}", decl.package, version);
+            } else {
+                println!("  {}  {} -> {}", decl.package, decl.alias, version);
+            }
+        }
+    }
+    println!(
+        "{} utilities, {} dependencies",
+        report.utilities.len(),
+        report.dependencies.len()
+    );
+}
+
+/// Format `metrics`: one line per package with its fan-in (Ca), fan-out (Ce), and
+/// instability.
+pub fn format_package_metrics(
+    metrics: &[crate::query::metrics::PackageMetrics],
+    format: &OutputFormat,
+) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(metrics).unwrap_or_default());
+        return;
+    }
+    let instability = |m: &crate::query::metrics::PackageMetrics| {
+        m.instability
+            .map_or_else(|| "-".to_string(), |i| format!("{:.2}", i))
+    };
+    if let OutputFormat::Table = format {
+        let use_color = crate::pager::start();
+        let width = metrics
+            .iter()
+            .map(|m| m.package.len())
+            .max()
+            .unwrap_or(0)
+            .max("PACKAGE".len());
+        let header = format!(
+            "{:<width$}  {:>5}  {:>3}  {:>3}  {:>11}",
+            "PACKAGE", "FILES", "CA", "CE", "INSTABILITY"
+        );
+        if use_color {
+            println!("\x1b[1m{}\x1b[0m", header);
+        } else {
+            println!("{}", header);
+        }
+        for m in metrics {
+            println!(
+                "{:<width$}  {:>5}  {:>3}  {:>3}  {:>11}",
+                m.package,
+                m.files,
+                m.afferent,
+                m.efferent,
+                instability(m)
+            );
+        }
+        return;
+    }
+    for m in metrics {
+        println!(
+            "package {} files {} ca {} ce {} instability {}",
+            m.package,
+            m.files,
+            m.afferent,
+            m.efferent,
+            instability(m)
+        );
+    }
+    println!("{} packages", metrics.len());
+}
+
+/// Format `callgraph` trees, indented two spaces per hop.
+///
+/// Compact output:
+/// ```text
+/// callgraph run src/app.ts:5
+///   -> step src/app.ts:9
+///     -> run src/app.ts:5 (cycle)
+///   <- main src/app.ts:1
+/// ```
+///
+/// Table output adds each symbol's kind.
+pub fn format_callgraph(roots: &[crate::query::callgraph::CallGraphRoot], format: &OutputFormat) {
+    use crate::query::callgraph::CallNode;
+
+    fn print_nodes(nodes: &[CallNode], arrow: &str, depth: usize, with_kind: bool) {
+        for node in nodes {
+            let kind = if with_kind {
+                format!(" ({})", node.kind)
+            } else {
+                String::new()
+            };
+            let location = if node.line == 0 {
+                crate::pager::fit_path(&node.file)
+            } else {
+                format!("{}:{}", crate::pager::fit_path(&node.file), node.line)
+            };
+            let name = if node.kind == "file" {
+                "<top-level>"
+            } else {
+                &node.name
+            };
+            println!(
+                "{}{} {}{} {}{}",
+                "  ".repeat(depth),
+                arrow,
+                name,
+                kind,
+                location,
+                if node.cycle { " (cycle)" } else { "" }
+            );
+            print_nodes(&node.children, arrow, depth + 1, with_kind);
+        }
+    }
+
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(roots).unwrap_or_default());
+        return;
+    }
+    let with_kind = matches!(format, OutputFormat::Table);
+    if with_kind {
+        crate::pager::start();
+    }
+    for root in roots {
+        let kind = if with_kind {
+            format!(" ({})", root.symbol.kind)
+        } else {
+            String::new()
+        };
+        println!(
+            "callgraph {}{} {}:{}",
+            root.symbol.name,
+            kind,
+            crate::pager::fit_path(&root.symbol.file),
+            root.symbol.line
+        );
+        if let Some(callees) = &root.callees {
+            print_nodes(callees, "->", 1, with_kind);
+        }
+        if let Some(callers) = &root.callers {
+            print_nodes(callers, "<-", 1, with_kind);
+        }
+    }
+}
+
+/// Format `deps` / `rdeps` results (`label`): one line per reached file with its import
+/// depth, nearest first.
+pub fn format_file_deps(result: &crate::query::deps::FileDeps, label: &str, format: &OutputFormat) {
+    match format {
+        OutputFormat::Json => {
+            print_json(&serde_json::to_value(result).unwrap_or_default());
+        }
+        OutputFormat::Compact => {
+            for f in &result.files {
+                println!("{} {} [depth {}]", label, f.file, f.depth);
+            }
+            println!("{} files", result.files.len());
+        }
+        OutputFormat::Table => {
+            let use_color = crate::pager::start();
+            let header = format!("{:>5}  FILE", "DEPTH");
+            if use_color {
+                println!("\x1b[1m{}\x1b[0m", header);
+            } else {
+                println!("{}", header);
+            }
+            for f in &result.files {
+                println!("{:>5}  {}", f.depth, crate::pager::fit_path(&f.file));
+            }
+            println!();
+            println!("{} {}: {} files", label, result.file, result.files.len());
+        }
+    }
+}
+
+/// Format `path` results: one block per shortest path, one hop per line with the edge that
+/// leads to it.
+pub fn format_dependency_paths(
+    result: &crate::query::dep_path::DependencyPaths,
+    format: &OutputFormat,
+) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(result).unwrap_or_default());
+        return;
+    }
+    let with_kind = matches!(format, OutputFormat::Table);
+    if with_kind {
+        crate::pager::start();
+    }
+    for (i, hops) in result.paths.iter().enumerate() {
+        println!(
+            "path {}/{} ({} hops)",
+            i + 1,
+            result.paths.len(),
+            hops.len().saturating_sub(1)
+        );
+        for hop in hops {
+            let location = match hop.line {
+                Some(line) => format!(
+                    "{} {}:{}",
+                    hop.name,
+                    crate::pager::fit_path(&hop.file),
+                    line
+                ),
+                None => crate::pager::fit_path(&hop.file),
+            };
+            let kind = if with_kind && hop.line.is_some() {
+                format!(" ({})", hop.kind)
+            } else {
+                String::new()
+            };
+            match hop.via {
+                Some(via) => println!("  -> {} {}{}", via, location, kind),
+                None => println!("  {}{}", location, kind),
+            }
+        }
+    }
+}
+
+/// Format a `coupling` report: one line per hidden-coupling pair, strongest first.
+pub fn format_coupling(report: &crate::query::coupling::CouplingReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for pair in &report.pairs {
+        println!(
+            "coupling {} <-> {}  shared {} ({}/{} commits, degree {:.0}%)",
+            crate::pager::fit_path(&pair.file_a),
+            crate::pager::fit_path(&pair.file_b),
+            pair.shared,
+            pair.revisions_a,
+            pair.revisions_b,
+            pair.degree * 100.0
+        );
+    }
+    println!(
+        "{} hidden pairs ({} already linked) in {} commits",
+        report.pairs.len(),
+        report.linked_pairs,
+        report.commits_analyzed
+    );
+}
+
+/// Format `complexity`: one line per function, most complex first.
+pub fn format_complexity(
+    functions: &[crate::query::complexity::FunctionComplexity],
+    format: &OutputFormat,
+) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(functions).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        let use_color = crate::pager::start();
+        let header = format!("{:>10}  {:<10}  SYMBOL", "COMPLEXITY", "KIND");
+        if use_color {
+            println!("\x1b[1m{}\x1b[0m", header);
+        } else {
+            println!("{}", header);
+        }
+        for f in functions {
+            println!(
+                "{:>10}  {:<10}  {} {}:{}",
+                f.complexity,
+                f.kind,
+                f.name,
+                crate::pager::fit_path(&f.file),
+                f.line
+            );
+        }
+        return;
+    }
+    for f in functions {
+        println!(
+            "complexity {} {} {}:{}",
+            f.complexity,
+            f.name,
+            crate::pager::fit_path(&f.file),
+            f.line
+        );
+    }
+    println!("{} functions", functions.len());
+}
+
+/// Format a `hotspots` ranking: one line per file or symbol, highest score first.
+pub fn format_hotspots(report: &crate::query::hotspots::HotspotReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    let location = |h: &crate::query::hotspots::Hotspot| match h.line {
+        Some(line) => format!("{} {}:{}", h.name, crate::pager::fit_path(&h.file), line),
+        None => crate::pager::fit_path(&h.file),
+    };
+    if let OutputFormat::Table = format {
+        let use_color = crate::pager::start();
+        let header = format!(
+            "{:>6}  {:>10}  {:>5}  TARGET",
+            "SCORE", "DEPENDENTS", "CHURN"
+        );
+        if use_color {
+            println!("\x1b[1m{}\x1b[0m", header);
+        } else {
+            println!("{}", header);
+        }
+        for h in &report.hotspots {
+            let churn = h.churn.map_or_else(|| "-".to_string(), |c| c.to_string());
+            println!(
+                "{:>6}  {:>10}  {:>5}  {}",
+                h.score,
+                h.dependents,
+                churn,
+                location(h)
+            );
+        }
+        return;
+    }
+    for h in &report.hotspots {
+        let churn = h.churn.map(|c| format!(" churn {}", c)).unwrap_or_default();
+        println!(
+            "hotspot {} dependents {}{} score {}",
+            location(h),
+            h.dependents,
+            churn,
+            h.score
+        );
+    }
+    match report.commits_analyzed {
+        Some(commits) => println!(
+            "{} hotspots (churn from {} commits)",
+            report.hotspots.len(),
+            commits
+        ),
+        None => println!("{} hotspots", report.hotspots.len()),
+    }
+}
+
+/// Format a `barrels` report: one line per barrel with its symbols, importers, and chain
+/// depth, the chain itself below barrels that re-export through other barrels, then a
+/// summary line.
+pub fn format_barrels(report: &crate::query::barrels::BarrelsReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for b in &report.barrels {
+        println!(
+            "{} {} symbols, {} importers, chain {}{}",
+            crate::pager::fit_path(&b.file),
+            b.symbols,
+            b.importers,
+            b.chain.len() - 1,
+            if b.pure { " (pure)" } else { "" }
+        );
+        if b.chain.len() > 2 {
+            println!("  {}", b.chain.join(" -> "));
+        }
+    }
+    let pure = report.barrels.iter().filter(|b| b.pure).count();
+    let longest = report
+        .barrels
+        .first()
+        .map_or(0, |b| b.chain.len().saturating_sub(1));
+    println!(
+        "{} barrels ({} pure), longest chain {}",
+        report.barrels.len(),
+        pure,
+        longest
+    );
+}
+
+/// Format an `entrypoints` report: one `file (reasons) reaches N files` line per entry
+/// point, then a summary line.
+pub fn format_entrypoints(
+    report: &crate::query::entrypoints::EntrypointsReport,
+    format: &OutputFormat,
+) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for e in &report.entrypoints {
+        println!(
+            "{} ({}) reaches {} files",
+            crate::pager::fit_path(&e.file),
+            e.reasons.join(", "),
+            e.reaches
+        );
+    }
+    println!("{} entry points", report.entrypoints.len());
+}
+
+/// Format a `cut-points` report: one line per chokepoint with the files it cuts off, the
+/// parts its removal leaves, and the files it dominates.
+pub fn format_cut_points(report: &crate::query::cut_points::CutPointReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for c in &report.cut_points {
+        let parts = if c.parts.is_empty() {
+            String::new()
+        } else {
+            let sizes: Vec<String> = c.parts.iter().map(|p| p.to_string()).collect();
+            format!(" into parts of {}", sizes.join(" + "))
+        };
+        println!(
+            "{} cuts off {} files{}, dominates {} [fan-in {}, fan-out {}]",
+            crate::pager::fit_path(&c.file),
+            c.disconnects,
+            parts,
+            c.dominates,
+            c.fan_in,
+            c.fan_out
+        );
+    }
+    println!(
+        "{} cut points among {} files",
+        report.cut_points.len(),
+        report.files
+    );
+}
+
+/// Format an `exports` listing: one line per exported item with its outside consumer
+/// count, flagging items nothing outside the package uses.
+pub fn format_exports(report: &crate::query::exports::ExportsReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    println!(
+        "exports {} (entry: {})",
+        report.target,
+        report.entry_files.join(", ")
+    );
+    for item in &report.exports {
+        let via = item
+            .via
+            .as_deref()
+            .map(|v| format!(" via {}", crate::pager::fit_path(v)))
+            .unwrap_or_default();
+        let usage = if item.consumers.is_empty() {
+            "unused".to_string()
+        } else {
+            format!("{} refs", item.consumers.len())
+        };
+        println!(
+            "  {:<8} {} {} {}:{}{}",
+            usage,
+            item.kind,
+            item.name,
+            crate::pager::fit_path(&item.file),
+            item.line,
+            via
+        );
+    }
+    let unused = report
+        .exports
+        .iter()
+        .filter(|e| e.consumers.is_empty())
+        .count();
+    println!(
+        "{} exports, {} unused outside the package",
+        report.exports.len(),
+        unused
+    );
+}
+
+/// Format an `api` report: a header with the entry files, then one `signature file:line`
+/// line per item, members indented under their type.
+pub fn format_api(report: &crate::query::exports::ApiReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    println!(
+        "api {} (entry: {})",
+        report.target,
+        report.entry_files.join(", ")
+    );
+    for item in &report.items {
+        let is_member = item.name.contains('.') || item.name.contains("::");
+        let indent = if is_member { "    " } else { "  " };
+        let signature = if item.signature.is_empty() {
+            format!("{} {}", item.kind, item.name)
+        } else {
+            item.signature.clone()
+        };
+        println!(
+            "{}{} {}:{}",
+            indent,
+            signature,
+            crate::pager::fit_path(&item.file),
+            item.line
+        );
+    }
+    println!("{} items", report.items.len());
+}
+
+/// Format `lint-imports` violations as `file:line rule specifier — message`, then a
+/// summary line.
+pub fn format_lint_imports(
+    report: &crate::query::lint_imports::LintImportsReport,
+    format: &OutputFormat,
+) {
+    use crate::query::lint_imports::ImportRule;
+
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for v in &report.violations {
+        let rule = match v.rule {
+            ImportRule::Order => "order",
+            ImportRule::DeepRelative => "deep-relative",
+        };
+        println!(
+            "{}:{} {} `{}`: {}",
+            crate::pager::fit_path(&v.file),
+            v.line,
+            rule,
+            v.specifier,
+            v.message
+        );
+    }
+    println!(
+        "{} violations in {} files checked",
+        report.violations.len(),
+        report.files_checked
+    );
+}
+
+/// Format `check` results: one line per import breaking a layering rule, then a summary.
+pub fn format_rules_check(report: &crate::query::rules::RulesReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for v in &report.violations {
+        println!(
+            "{} -> {} breaks {} `{}`",
+            crate::pager::fit_path(&v.from),
+            v.to,
+            v.rule,
+            v.specifier
+        );
+    }
+    println!(
+        "{} violations of {} rules",
+        report.violations.len(),
+        report.rules_checked
+    );
+}
+
+/// Format an `unused-exports` report: one `file:line name kind` line per unused export,
+/// then a summary line.
+pub fn format_unused_exports(
+    report: &crate::query::unused_exports::UnusedExportsReport,
+    format: &OutputFormat,
+) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for u in &report.unused {
+        println!(
+            "{}:{} {} {}",
+            crate::pager::fit_path(&u.file),
+            u.line,
+            u.name,
+            u.kind
+        );
+    }
+    println!(
+        "{} unused of {} exports",
+        report.unused.len(),
+        report.checked
+    );
+}
+
+/// Format a `similar` report: a `name similarity` header per group with one
+/// `file:line kind (N lines)` line per member, then a summary line.
+pub fn format_similar(report: &crate::query::similar::SimilarReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for group in &report.groups {
+        println!("{} {:.0}% similar", group.name, group.similarity * 100.0);
+        for m in &group.members {
+            println!(
+                "  {}:{} {} ({} lines)",
+                crate::pager::fit_path(&m.file),
+                m.line,
+                m.kind,
+                m.lines
+            );
+        }
+    }
+    println!(
+        "{} groups of similar symbols ({} symbols compared)",
+        report.groups.len(),
+        report.symbols_compared
+    );
+}
+
+/// Format a `touched` report: one `change kind name file:line (N refs)` line per symbol,
+/// then a summary line.
+pub fn format_touched(report: &crate::query::touched::TouchedReport, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    println!("touched {}..{}", report.from, report.to);
+    for s in &report.symbols {
+        println!(
+            "  {:<8} {} {} {}:{} ({} refs)",
+            s.change,
+            s.kind,
+            s.name,
+            crate::pager::fit_path(&s.file),
+            s.line,
+            s.refs
+        );
+    }
+    let count = |change: &str| report.symbols.iter().filter(|s| s.change == change).count();
+    println!(
+        "{} symbols touched in {} files ({} added, {} removed, {} modified)",
+        report.symbols.len(),
+        report.files,
+        count("added"),
+        count("removed"),
+        count("modified")
+    );
+}
+
+/// Format a `select-tests` selection. Compact output is bare test paths (or `cargo test`
+/// arguments), one per line, so it can be piped straight into a test runner; changed files
+/// missing from the graph are noted on stderr.
+pub fn format_select_tests(
+    selection: &crate::query::select_tests::TestSelection,
+    cargo: bool,
+    format: &OutputFormat,
+) {
+    match format {
         OutputFormat::Json => {
-            let json_results: Vec<serde_json::Value> = results
+            print_json(&serde_json::to_value(selection).unwrap_or_default());
+        }
+        OutputFormat::Table => {
+            crate::pager::start();
+            for t in &selection.tests {
+                println!(
+                    "{:<10} {} (via {}, depth {})",
+                    t.reason,
+                    crate::pager::fit_path(&t.file),
+                    crate::pager::fit_path(&t.via),
+                    t.depth
+                );
+            }
+            println!(
+                "{} tests selected for {} changed files since {}",
+                selection.tests.len(),
+                selection.changed.len(),
+                selection.base
+            );
+        }
+        OutputFormat::Compact => {
+            if cargo {
+                for args in &selection.cargo {
+                    println!("{}", args);
+                }
+            } else {
+                for t in &selection.tests {
+                    println!("{}", t.file);
+                }
+            }
+        }
+    }
+    if !selection.unmapped.is_empty() && !matches!(format, OutputFormat::Json) {
+        eprintln!(
+            "note: {} changed files are not in the graph: {}",
+            selection.unmapped.len(),
+            selection.unmapped.join(", ")
+        );
+    }
+}
+
+/// Format an `unused-deps` report: one `manifest name section` line per unused
+/// dependency, then a summary line.
+pub fn format_unused_deps(
+    report: &crate::query::unused_deps::UnusedDepsReport,
+    format: &OutputFormat,
+) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    for u in &report.unused {
+        println!(
+            "{} {} ({})",
+            crate::pager::fit_path(&u.manifest),
+            u.name,
+            u.section
+        );
+    }
+    println!(
+        "{} unused of {} dependencies in {} packages",
+        report.unused.len(),
+        report.declared,
+        report.packages
+    );
+}
+
+/// Format a `can-delete` verdict: the verdict line, then each blocking reference and each
+/// dynamic-usage reason.
+pub fn format_can_delete(
+    report: &crate::query::can_delete::CanDeleteReport,
+    format: &OutputFormat,
+) {
+    use crate::query::can_delete::Verdict;
+
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(report).unwrap_or_default());
+        return;
+    }
+    if let OutputFormat::Table = format {
+        crate::pager::start();
+    }
+    let verdict = match report.verdict {
+        Verdict::Safe => "safe".to_string(),
+        Verdict::Blocked => format!("blocked ({} references)", report.references.len()),
+        Verdict::Unknown => format!("unknown ({} dynamic signals)", report.dynamic.len()),
+    };
+    println!(
+        "can-delete {} {}: {}",
+        report.target_kind, report.target, verdict
+    );
+    for r in &report.references {
+        let location = match r.line {
+            Some(line) => format!("{}:{}", crate::pager::fit_path(&r.file), line),
+            None => crate::pager::fit_path(&r.file),
+        };
+        match &r.symbol {
+            Some(symbol) => println!("  {:<10} {} {}", r.kind, location, symbol),
+            None => println!("  {:<10} {}", r.kind, location),
+        }
+    }
+    for reason in &report.dynamic {
+        println!("  {:<10} {}", "dynamic", reason);
+    }
+}
+
+/// Format a `hover` bundle: the definition line, signature, doc, reference count, and
+/// top callers. Table output matches compact output.
+pub fn format_hover(info: &crate::query::hover::HoverInfo, format: &OutputFormat) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(info).unwrap_or_default());
+        return;
+    }
+    println!(
+        "{} {} {}:{}:{}",
+        info.kind, info.name, info.file, info.line, info.col
+    );
+    if let Some(signature) = &info.signature {
+        println!("  {}", signature);
+    }
+    if let Some(doc) = &info.doc {
+        for line in doc.lines() {
+            println!("{}", format!("  | {}", line).trim_end());
+        }
+    }
+    println!("refs {}", info.references);
+    for caller in &info.callers {
+        println!(
+            "caller {} {}:{} (x{})",
+            caller.name, caller.file, caller.line, caller.count
+        );
+    }
+}
+
+/// Format one ready-to-post message per CODEOWNERS owner for `impact --notify-format`.
+///
+/// `changed_files` are the files defining the changed symbol. Slack messages use mrkdwn;
+/// GitHub messages use markdown with the owner as an `@` mention heading. With
+/// `OutputFormat::Json`, each group is emitted with its files and rendered message.
+pub fn format_impact_notifications(
+    groups: &[crate::query::owners::OwnerGroup],
+    notify: &crate::cli::NotifyFormat,
+    format: &OutputFormat,
+    project_root: &Path,
+    symbol_name: &str,
+    changed_files: &[PathBuf],
+) -> String {
+    use crate::cli::NotifyFormat;
+    use std::fmt::Write;
+
+    let rel = |p: &Path| -> String {
+        p.strip_prefix(project_root)
+            .unwrap_or(p)
+            .to_string_lossy()
+            .to_string()
+    };
+    let defined_in = match changed_files {
+        [] => String::new(),
+        files => format!(
+            " ({})",
+            files
                 .iter()
-                .map(|r| {
-                    let rel = r
-                        .file_path
-                        .strip_prefix(project_root)
-                        .unwrap_or(&r.file_path);
+                .map(|f| format!("`{}`", rel(f)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    let messages: Vec<String> = groups
+        .iter()
+        .map(|group| {
+            let mut msg = String::new();
+            match (notify, &group.owner) {
+                (NotifyFormat::Slack, Some(owner)) => writeln!(
+                    msg,
+                    "*{}*: your area is affected by a change to `{}`{} via these files:",
+                    owner, symbol_name, defined_in
+                ),
+                (NotifyFormat::Slack, None) => writeln!(
+                    msg,
+                    "*Unowned files* affected by a change to `{}`{}:",
+                    symbol_name, defined_in
+                ),
+                (NotifyFormat::Github, owner) => writeln!(
+                    msg,
+                    "### {}\n\n{} affected by a change to `{}`{}{}:\n",
+                    owner.as_deref().unwrap_or("Unowned files"),
+                    if owner.is_some() {
+                        "Your area is"
+                    } else {
+                        "These files are"
+                    },
+                    symbol_name,
+                    defined_in,
+                    if owner.is_some() {
+                        " via these files"
+                    } else {
+                        ""
+                    }
+                ),
+            }
+            .unwrap();
+            let bullet = match notify {
+                NotifyFormat::Slack => "•",
+                NotifyFormat::Github => "-",
+            };
+            for r in &group.files {
+                writeln!(
+                    msg,
+                    "{} `{}` ({}: {})",
+                    bullet,
+                    rel(&r.file_path),
+                    r.confidence,
+                    r.basis
+                )
+                .unwrap();
+            }
+            msg
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Json => {
+            let json: Vec<serde_json::Value> = groups
+                .iter()
+                .zip(&messages)
+                .map(|(group, message)| {
+                    let files: Vec<serde_json::Value> = group
+                        .files
+                        .iter()
+                        .map(|r| {
+                            serde_json::json!({
+                                "file": rel(&r.file_path),
+                                "confidence": r.confidence.to_string(),
+                                "basis": r.basis,
+                            })
+                        })
+                        .collect();
                     serde_json::json!({
-                        "file": rel.to_string_lossy(),
-                        "depth": r.depth,
-                        "confidence": r.confidence.to_string(),
-                        "basis": r.basis,
+                        "owner": group.owner,
+                        "files": files,
+                        "message": message,
                     })
                 })
                 .collect();
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&json_results).unwrap_or_default()
-            );
+            format!(
+                "{}\n",
+                serde_json::to_string_pretty(&json).unwrap_or_default()
+            )
         }
+        _ => messages.join("\n"),
     }
 }
 
@@ -1042,12 +2464,18 @@ pub fn format_context_results(
                         .unwrap_or(&r.file_path);
                     match r.ref_kind {
                         RefKind::Import => {
-                            println!("ref {} import", rel.display());
+                            println!("ref {} import", import_site(rel, r));
                         }
-                        RefKind::Call => {
+                        RefKind::Call | RefKind::Render | RefKind::Read | RefKind::Write => {
                             let caller = r.symbol_name.as_deref().unwrap_or("?");
                             let line = r.line.map_or_else(|| "?".to_string(), |l| l.to_string());
-                            println!("ref {}:{} call {}", rel.display(), line, caller);
+                            println!(
+                                "ref {}:{} {} {}",
+                                rel.display(),
+                                line,
+                                r.ref_kind.as_str(),
+                                caller
+                            );
                         }
                     }
                 }
@@ -1125,6 +2553,55 @@ pub fn format_context_results(
                     );
                 }
 
+                for over in &ctx.overrides {
+                    let rel = over
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&over.file_path);
+                    println!(
+                        "overrides {} {}:{}",
+                        over.symbol_name,
+                        rel.display(),
+                        over.line
+                    );
+                }
+
+                for over_by in &ctx.overridden_by {
+                    let rel = over_by
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&over_by.file_path);
+                    println!(
+                        "overridden-by {} {}:{}",
+                        over_by.symbol_name,
+                        rel.display(),
+                        over_by.line
+                    );
+                }
+
+                for doc in &ctx.documented_by {
+                    let rel = doc.strip_prefix(project_root).unwrap_or(doc);
+                    println!("doc {}", rel.display());
+                }
+
+                for route in &ctx.routes {
+                    let rel = route
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&route.file_path);
+                    println!(
+                        "route {} {}:{}",
+                        route.symbol_name,
+                        rel.display(),
+                        route.line
+                    );
+                }
+
+                for snippet in &ctx.source {
+                    println!("source {}", snippet_header(snippet, project_root));
+                    print!("{}", snippet_body(snippet, ""));
+                }
+
                 // Summary line.
                 println!(
                     "{} refs, {} callers, {} callees",
@@ -1144,7 +2621,7 @@ pub fn format_context_results(
         }
 
         OutputFormat::Table => {
-            let use_color = std::io::stdout().is_terminal();
+            let use_color = crate::pager::start();
             let bold = |s: &str| -> String {
                 if use_color {
                     format!("\x1b[1m{s}\x1b[0m")
@@ -1195,13 +2672,19 @@ pub fn format_context_results(
                             .unwrap_or(&r.file_path);
                         match r.ref_kind {
                             RefKind::Import => {
-                                println!("  {}  import", rel.display());
+                                println!("  {}  import", import_site(rel, r));
                             }
-                            RefKind::Call => {
+                            RefKind::Call | RefKind::Render | RefKind::Read | RefKind::Write => {
                                 let caller = r.symbol_name.as_deref().unwrap_or("?");
                                 let line =
                                     r.line.map_or_else(|| "?".to_string(), |l| l.to_string());
-                                println!("  {}:{}  call  {}", rel.display(), line, caller);
+                                println!(
+                                    "  {}:{}  {}  {}",
+                                    rel.display(),
+                                    line,
+                                    r.ref_kind.as_str(),
+                                    caller
+                                );
                             }
                         }
                     }
@@ -1314,184 +2797,999 @@ pub fn format_context_results(
                     }
                     println!();
                 }
+
+                // Overrides section.
+                if !ctx.overrides.is_empty() {
+                    println!("{}", bold(&format!("Overrides ({}):", ctx.overrides.len())));
+                    for over in &ctx.overrides {
+                        let rel = over
+                            .file_path
+                            .strip_prefix(project_root)
+                            .unwrap_or(&over.file_path);
+                        println!("  {}  {}:{}", over.symbol_name, rel.display(), over.line);
+                    }
+                    println!();
+                }
+
+                // Overridden By section.
+                if !ctx.overridden_by.is_empty() {
+                    println!(
+                        "{}",
+                        bold(&format!("Overridden By ({}):", ctx.overridden_by.len()))
+                    );
+                    for over_by in &ctx.overridden_by {
+                        let rel = over_by
+                            .file_path
+                            .strip_prefix(project_root)
+                            .unwrap_or(&over_by.file_path);
+                        println!(
+                            "  {}  {}:{}",
+                            over_by.symbol_name,
+                            rel.display(),
+                            over_by.line
+                        );
+                    }
+                    println!();
+                }
+
+                // Documented By section.
+                if !ctx.documented_by.is_empty() {
+                    println!(
+                        "{}",
+                        bold(&format!("Documented By ({}):", ctx.documented_by.len()))
+                    );
+                    for doc in &ctx.documented_by {
+                        let rel = doc.strip_prefix(project_root).unwrap_or(doc);
+                        println!("  {}", rel.display());
+                    }
+                    println!();
+                }
+
+                // Routes section.
+                if !ctx.routes.is_empty() {
+                    println!("{}", bold(&format!("Routes ({}):", ctx.routes.len())));
+                    for route in &ctx.routes {
+                        let rel = route
+                            .file_path
+                            .strip_prefix(project_root)
+                            .unwrap_or(&route.file_path);
+                        println!("  {}  {}:{}", route.symbol_name, rel.display(), route.line);
+                    }
+                    println!();
+                }
+
+                // Source section.
+                if !ctx.source.is_empty() {
+                    println!("{}", bold(&format!("Source ({}):", ctx.source.len())));
+                    for snippet in &ctx.source {
+                        println!("  {}", snippet_header(snippet, project_root));
+                        print!("{}", snippet_body(snippet, "    "));
+                    }
+                    println!();
+                }
             }
         }
 
-        OutputFormat::Json => {
-            let json_results: Vec<serde_json::Value> = contexts
+        OutputFormat::Json => print_json(&context_results_to_json(contexts, project_root)),
+    }
+}
+
+/// `def src/a.ts:3-12` or `call src/b.ts:40-46 handleRequest`.
+fn snippet_header(snippet: &SourceSnippet, project_root: &Path) -> String {
+    let rel = snippet
+        .file_path
+        .strip_prefix(project_root)
+        .unwrap_or(&snippet.file_path);
+    let line_end = snippet.line_start + snippet.lines.len().saturating_sub(1);
+    let mut header = format!(
+        "{} {}:{}-{}",
+        snippet.kind.as_str(),
+        rel.display(),
+        snippet.line_start,
+        line_end
+    );
+    if let Some(caller) = &snippet.caller {
+        header.push(' ');
+        header.push_str(caller);
+    }
+    header
+}
+
+/// The snippet's lines, each prefixed with `indent` and its right-aligned line number.
+fn snippet_body(snippet: &SourceSnippet, indent: &str) -> String {
+    let width = (snippet.line_start + snippet.lines.len()).to_string().len();
+    snippet
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("{indent}{:>width$}| {line}\n", snippet.line_start + i))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// JSON serializers (shared by CLI `--format json` and daemon `format: "json"`)
+// ---------------------------------------------------------------------------
+
+/// Pretty-print a JSON value to stdout.
+fn print_json(value: &serde_json::Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    );
+}
+
+/// `[byte_start, byte_end]` of a definition, or `null` when its parser does not track
+/// byte offsets.
+fn byte_range_json(r: &FindResult) -> serde_json::Value {
+    if r.byte_end == 0 {
+        serde_json::Value::Null
+    } else {
+        serde_json::json!([r.byte_start, r.byte_end])
+    }
+}
+
+/// Serialize find results. Mixed-language results are grouped by language, and every
+/// entry carries a "language" field.
+pub fn find_results_to_json(results: &[FindResult], project_root: &Path) -> serde_json::Value {
+    let mixed = is_mixed_language(results, |r: &FindResult| r.file_path.as_path());
+    let json_results: Vec<serde_json::Value> = group_by_language(results, mixed)
+        .into_iter()
+        .map(|r| find_result_json(r, project_root))
+        .collect();
+    serde_json::Value::Array(json_results)
+}
+
+fn find_result_json(r: &FindResult, project_root: &Path) -> serde_json::Value {
+    let rel = r
+        .file_path
+        .strip_prefix(project_root)
+        .unwrap_or(&r.file_path);
+    serde_json::json!({
+        "name": r.symbol_name,
+        "kind": kind_to_str(&r.kind),
+        "file": rel.to_string_lossy(),
+        "language": language_of_file(&r.file_path),
+        "line": r.line,
+        "col": r.col,
+        "line_end": r.line_end,
+        "col_end": r.col_end,
+        "bytes": byte_range_json(r),
+        "exported": r.is_exported,
+        "default": r.is_default,
+        "visibility": visibility_str(&r.visibility),
+        "signature": r.signature,
+    })
+}
+
+/// Serialize reference results.
+pub fn refs_results_to_json(results: &[RefResult], project_root: &Path) -> serde_json::Value {
+    let json_results: Vec<serde_json::Value> = results
+        .iter()
+        .map(|r| ref_result_json(r, project_root))
+        .collect();
+    serde_json::Value::Array(json_results)
+}
+
+fn ref_result_json(r: &RefResult, project_root: &Path) -> serde_json::Value {
+    let rel = r
+        .file_path
+        .strip_prefix(project_root)
+        .unwrap_or(&r.file_path);
+    serde_json::json!({
+        "file": rel.to_string_lossy(),
+        "kind": r.ref_kind.as_str(),
+        "caller": r.symbol_name,
+        "line": r.line,
+        "col": r.col,
+    })
+}
+
+/// Serialize impact (blast radius) results.
+pub fn impact_results_to_json(results: &[ImpactResult], project_root: &Path) -> serde_json::Value {
+    let json_results: Vec<serde_json::Value> = results
+        .iter()
+        .map(|r| impact_result_json(r, project_root))
+        .collect();
+    serde_json::Value::Array(json_results)
+}
+
+fn impact_result_json(r: &ImpactResult, project_root: &Path) -> serde_json::Value {
+    let rel = r
+        .file_path
+        .strip_prefix(project_root)
+        .unwrap_or(&r.file_path);
+    serde_json::json!({
+        "file": rel.to_string_lossy(),
+        "depth": r.depth,
+        "confidence": r.confidence.to_string(),
+        "basis": r.basis,
+    })
+}
+
+/// Serialize circular dependency groups (SCC files, size and representative cycles).
+pub fn circular_results_to_json(cycles: &[CircularDep], project_root: &Path) -> serde_json::Value {
+    let rel = |p: &std::path::PathBuf| {
+        p.strip_prefix(project_root)
+            .unwrap_or(p)
+            .to_string_lossy()
+            .to_string()
+    };
+    let json_results: Vec<serde_json::Value> = cycles
+        .iter()
+        .map(|group| {
+            let files: Vec<String> = group.files.iter().map(rel).collect();
+            let representative: Vec<Vec<String>> = group
+                .cycles
                 .iter()
-                .map(|ctx| {
-                    let definitions: Vec<serde_json::Value> = ctx
-                        .definitions
-                        .iter()
-                        .map(|d| {
-                            let rel = d
-                                .file_path
-                                .strip_prefix(project_root)
-                                .unwrap_or(&d.file_path);
-                            serde_json::json!({
-                                "file": rel.to_string_lossy(),
-                                "line": d.line,
-                                "kind": kind_to_str(&d.kind),
-                                "exported": d.is_exported,
-                            })
-                        })
-                        .collect();
+                .map(|cycle| cycle.iter().map(rel).collect())
+                .collect();
+            serde_json::json!({
+                "files": files,
+                "scc_size": group.files.len(),
+                "cycles": representative,
+            })
+        })
+        .collect();
+    serde_json::Value::Array(json_results)
+}
 
-                    let references: Vec<serde_json::Value> = ctx
-                        .references
-                        .iter()
-                        .map(|r| {
-                            let rel = r
-                                .file_path
-                                .strip_prefix(project_root)
-                                .unwrap_or(&r.file_path);
-                            let kind_str = match r.ref_kind {
-                                RefKind::Import => "import",
-                                RefKind::Call => "call",
-                            };
-                            serde_json::json!({
-                                "file": rel.to_string_lossy(),
-                                "kind": kind_str,
-                                "caller": r.symbol_name,
-                                "line": r.line,
-                            })
-                        })
-                        .collect();
+/// Render each representative cycle of a circular group as `a -> b -> a`.
+fn circular_chains(group: &CircularDep, project_root: &Path) -> Vec<String> {
+    group
+        .cycles
+        .iter()
+        .map(|cycle| {
+            cycle
+                .iter()
+                .map(|p| {
+                    p.strip_prefix(project_root)
+                        .unwrap_or(p)
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        })
+        .collect()
+}
 
-                    let callees: Vec<serde_json::Value> = ctx
-                        .callees
-                        .iter()
-                        .map(|c| {
-                            let rel = c
-                                .file_path
-                                .strip_prefix(project_root)
-                                .unwrap_or(&c.file_path);
-                            serde_json::json!({
-                                "name": c.symbol_name,
-                                "kind": kind_to_str(&c.kind),
-                                "file": rel.to_string_lossy(),
-                                "line": c.line,
-                            })
-                        })
-                        .collect();
+/// Size note for a circular group whose shown cycles do not cover the whole SCC.
+fn circular_group_note(group: &CircularDep) -> Option<String> {
+    let single_cycle_covers_scc =
+        group.cycles.len() == 1 && group.cycles[0].len().saturating_sub(1) == group.files.len();
+    if single_cycle_covers_scc {
+        None
+    } else {
+        Some(format!(
+            "scc size {}; representative cycles shown: {}",
+            group.files.len(),
+            group.cycles.len()
+        ))
+    }
+}
 
-                    let callers: Vec<serde_json::Value> = ctx
-                        .callers
-                        .iter()
-                        .map(|c| {
-                            let rel = c
-                                .file_path
-                                .strip_prefix(project_root)
-                                .unwrap_or(&c.file_path);
-                            serde_json::json!({
-                                "name": c.symbol_name,
-                                "kind": kind_to_str(&c.kind),
-                                "file": rel.to_string_lossy(),
-                                "line": c.line,
-                            })
-                        })
-                        .collect();
+/// Serialize symbol context results.
+pub fn context_results_to_json(
+    contexts: &[SymbolContext],
+    project_root: &Path,
+) -> serde_json::Value {
+    let json_results: Vec<serde_json::Value> = contexts
+        .iter()
+        .map(|ctx| {
+            let definitions: Vec<serde_json::Value> = ctx
+                .definitions
+                .iter()
+                .map(|d| {
+                    let rel = d
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&d.file_path);
+                    serde_json::json!({
+                        "file": rel.to_string_lossy(),
+                        "line": d.line,
+                        "line_end": d.line_end,
+                        "bytes": byte_range_json(d),
+                        "kind": kind_to_str(&d.kind),
+                        "exported": d.is_exported,
+                        "signature": d.signature,
+                    })
+                })
+                .collect();
 
-                    let extends: Vec<serde_json::Value> = ctx
-                        .extends
-                        .iter()
-                        .map(|e| {
-                            let rel = e
-                                .file_path
-                                .strip_prefix(project_root)
-                                .unwrap_or(&e.file_path);
-                            serde_json::json!({
-                                "name": e.symbol_name,
-                                "kind": kind_to_str(&e.kind),
-                                "file": rel.to_string_lossy(),
-                                "line": e.line,
-                            })
-                        })
-                        .collect();
+            let references: Vec<serde_json::Value> = ctx
+                .references
+                .iter()
+                .map(|r| {
+                    let rel = r
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&r.file_path);
+                    let kind_str = r.ref_kind.as_str();
+                    serde_json::json!({
+                        "file": rel.to_string_lossy(),
+                        "kind": kind_str,
+                        "caller": r.symbol_name,
+                        "line": r.line,
+                        "col": r.col,
+                    })
+                })
+                .collect();
 
-                    let implements: Vec<serde_json::Value> = ctx
-                        .implements
-                        .iter()
-                        .map(|i| {
-                            let rel = i
-                                .file_path
-                                .strip_prefix(project_root)
-                                .unwrap_or(&i.file_path);
-                            serde_json::json!({
-                                "name": i.symbol_name,
-                                "kind": kind_to_str(&i.kind),
-                                "file": rel.to_string_lossy(),
-                                "line": i.line,
-                            })
-                        })
-                        .collect();
+            let callees: Vec<serde_json::Value> = ctx
+                .callees
+                .iter()
+                .map(|c| {
+                    let rel = c
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&c.file_path);
+                    serde_json::json!({
+                        "name": c.symbol_name,
+                        "kind": kind_to_str(&c.kind),
+                        "file": rel.to_string_lossy(),
+                        "line": c.line,
+                    })
+                })
+                .collect();
 
-                    let extended_by: Vec<serde_json::Value> = ctx
-                        .extended_by
-                        .iter()
-                        .map(|e| {
-                            let rel = e
-                                .file_path
-                                .strip_prefix(project_root)
-                                .unwrap_or(&e.file_path);
-                            serde_json::json!({
-                                "name": e.symbol_name,
-                                "kind": kind_to_str(&e.kind),
-                                "file": rel.to_string_lossy(),
-                                "line": e.line,
-                            })
-                        })
-                        .collect();
+            let callers: Vec<serde_json::Value> = ctx
+                .callers
+                .iter()
+                .map(|c| {
+                    let rel = c
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&c.file_path);
+                    serde_json::json!({
+                        "name": c.symbol_name,
+                        "kind": kind_to_str(&c.kind),
+                        "file": rel.to_string_lossy(),
+                        "line": c.line,
+                    })
+                })
+                .collect();
 
-                    let implemented_by: Vec<serde_json::Value> = ctx
-                        .implemented_by
-                        .iter()
-                        .map(|i| {
-                            let rel = i
-                                .file_path
-                                .strip_prefix(project_root)
-                                .unwrap_or(&i.file_path);
-                            serde_json::json!({
-                                "name": i.symbol_name,
-                                "kind": kind_to_str(&i.kind),
-                                "file": rel.to_string_lossy(),
-                                "line": i.line,
-                            })
-                        })
-                        .collect();
+            let extends: Vec<serde_json::Value> = ctx
+                .extends
+                .iter()
+                .map(|e| {
+                    let rel = e
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&e.file_path);
+                    serde_json::json!({
+                        "name": e.symbol_name,
+                        "kind": kind_to_str(&e.kind),
+                        "file": rel.to_string_lossy(),
+                        "line": e.line,
+                    })
+                })
+                .collect();
+
+            let implements: Vec<serde_json::Value> = ctx
+                .implements
+                .iter()
+                .map(|i| {
+                    let rel = i
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&i.file_path);
+                    serde_json::json!({
+                        "name": i.symbol_name,
+                        "kind": kind_to_str(&i.kind),
+                        "file": rel.to_string_lossy(),
+                        "line": i.line,
+                    })
+                })
+                .collect();
+
+            let extended_by: Vec<serde_json::Value> = ctx
+                .extended_by
+                .iter()
+                .map(|e| {
+                    let rel = e
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&e.file_path);
+                    serde_json::json!({
+                        "name": e.symbol_name,
+                        "kind": kind_to_str(&e.kind),
+                        "file": rel.to_string_lossy(),
+                        "line": e.line,
+                    })
+                })
+                .collect();
+
+            let implemented_by: Vec<serde_json::Value> = ctx
+                .implemented_by
+                .iter()
+                .map(|i| {
+                    let rel = i
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&i.file_path);
+                    serde_json::json!({
+                        "name": i.symbol_name,
+                        "kind": kind_to_str(&i.kind),
+                        "file": rel.to_string_lossy(),
+                        "line": i.line,
+                    })
+                })
+                .collect();
 
+            let call_info_json = |c: &crate::query::context::CallInfo| {
+                let rel = c
+                    .file_path
+                    .strip_prefix(project_root)
+                    .unwrap_or(&c.file_path);
+                serde_json::json!({
+                    "name": c.symbol_name,
+                    "kind": kind_to_str(&c.kind),
+                    "file": rel.to_string_lossy(),
+                    "line": c.line,
+                })
+            };
+            let overrides: Vec<serde_json::Value> =
+                ctx.overrides.iter().map(call_info_json).collect();
+            let overridden_by: Vec<serde_json::Value> =
+                ctx.overridden_by.iter().map(call_info_json).collect();
+
+            let documented_by: Vec<String> = ctx
+                .documented_by
+                .iter()
+                .map(|p| {
+                    p.strip_prefix(project_root)
+                        .unwrap_or(p)
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect();
+
+            let routes: Vec<serde_json::Value> = ctx
+                .routes
+                .iter()
+                .map(|r| {
+                    let rel = r
+                        .file_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&r.file_path);
                     serde_json::json!({
-                        "symbol": ctx.symbol_name,
-                        "definitions": definitions,
-                        "references": references,
-                        "callees": callees,
-                        "callers": callers,
-                        "extends": extends,
-                        "implements": implements,
-                        "extended_by": extended_by,
-                        "implemented_by": implemented_by,
+                        "name": r.symbol_name,
+                        "kind": kind_to_str(&r.kind),
+                        "file": rel.to_string_lossy(),
+                        "line": r.line,
                     })
                 })
                 .collect();
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&json_results).unwrap_or_default()
-            );
-        }
-    }
+
+            let mut json = serde_json::json!({
+                "symbol": ctx.symbol_name,
+                "definitions": definitions,
+                "references": references,
+                "callees": callees,
+                "callers": callers,
+                "extends": extends,
+                "implements": implements,
+                "extended_by": extended_by,
+                "implemented_by": implemented_by,
+                "overrides": overrides,
+                "overridden_by": overridden_by,
+                "documented_by": documented_by,
+                "routes": routes,
+            });
+            if !ctx.source.is_empty() {
+                json["source"] = ctx
+                    .source
+                    .iter()
+                    .map(|snippet| {
+                        let rel = snippet
+                            .file_path
+                            .strip_prefix(project_root)
+                            .unwrap_or(&snippet.file_path);
+                        serde_json::json!({
+                            "kind": snippet.kind.as_str(),
+                            "file": rel.to_string_lossy(),
+                            "caller": snippet.caller,
+                            "line_start": snippet.line_start,
+                            "line_end": snippet.line_start + snippet.lines.len().saturating_sub(1),
+                            "code": snippet.lines.join("\n"),
+                        })
+                    })
+                    .collect();
+            }
+            json
+        })
+        .collect();
+    serde_json::Value::Array(json_results)
+}
+
+/// Serialize project stats.
+pub fn stats_to_json(stats: &ProjectStats) -> serde_json::Value {
+    // Build per-crate breakdown as JSON array
+    let crate_stats_json: Vec<serde_json::Value> = stats
+        .rust_crate_stats
+        .iter()
+        .map(|cs| {
+            serde_json::json!({
+                "crate_name": cs.crate_name,
+                "file_count": cs.file_count,
+                "symbol_count": cs.symbol_count,
+                "fn_count": cs.fn_count,
+                "struct_count": cs.struct_count,
+                "enum_count": cs.enum_count,
+                "trait_count": cs.trait_count,
+                "impl_method_count": cs.impl_method_count,
+                "type_alias_count": cs.type_alias_count,
+                "const_count": cs.const_count,
+                "static_count": cs.static_count,
+                "macro_count": cs.macro_count,
+            })
+        })
+        .collect();
+
+    let mut json = serde_json::json!({
+        "file_count": stats.file_count,
+        "source_files": stats.source_files,
+        "non_parsed_files": stats.non_parsed_files,
+        "doc_files": stats.doc_files,
+        "config_files": stats.config_files,
+        "ci_files": stats.ci_files,
+        "asset_files": stats.asset_files,
+        "other_files": stats.other_files,
+        "symbol_count": stats.symbol_count,
+        "functions": stats.functions,
+        "classes": stats.classes,
+        "interfaces": stats.interfaces,
+        "type_aliases": stats.type_aliases,
+        "enums": stats.enums,
+        "variables": stats.variables,
+        "components": stats.components,
+        "methods": stats.methods,
+        "properties": stats.properties,
+        "import_edges": stats.import_edges,
+        "external_packages": stats.external_packages,
+        "unresolved_imports": stats.unresolved_imports,
+        "rust_fns": stats.rust_fns,
+        "rust_structs": stats.rust_structs,
+        "rust_enums": stats.rust_enums,
+        "rust_traits": stats.rust_traits,
+        "rust_impl_methods": stats.rust_impl_methods,
+        "rust_type_aliases": stats.rust_type_aliases,
+        "rust_consts": stats.rust_consts,
+        "rust_statics": stats.rust_statics,
+        "rust_macros": stats.rust_macros,
+        "rust_imports": stats.rust_imports,
+        "rust_reexports": stats.rust_reexports,
+        "dependencies": {
+            "external_crates": stats.external_packages,
+            "external_usage_count": stats.external_usage_count,
+            "builtin_crates": stats.builtin_count,
+            "builtin_usage_count": stats.builtin_usage_count,
+        },
+        "crate_stats": crate_stats_json,
+        "python_file_count": stats.python_file_count,
+        "python_symbol_count": stats.python_symbol_count,
+        "python_fns": stats.python_fns,
+        "python_classes": stats.python_classes,
+        "python_methods": stats.python_methods,
+        "python_type_aliases": stats.python_type_aliases,
+        "python_variables": stats.python_variables,
+    });
+    // Added outside the macro above, which is at serde_json's recursion limit.
+    json["rust_cfg"] = serde_json::json!({
+        "test": stats.rust_cfg_test,
+        "feature": stats.rust_cfg_feature,
+        "other": stats.rust_cfg_other,
+    });
+    json["rust_derived"] = serde_json::json!({
+        "impl_methods": stats.rust_derived_impl_methods,
+        "derive_traits": stats.rust_derive_traits,
+    });
+    json["proto"] = serde_json::json!({
+        "file_count": stats.proto_file_count,
+        "symbol_count": stats.proto_symbol_count,
+        "messages": stats.proto_messages,
+        "enums": stats.proto_enums,
+        "services": stats.proto_services,
+        "rpcs": stats.proto_rpcs,
+    });
+    json["sql"] = serde_json::json!({
+        "file_count": stats.sql_file_count,
+        "symbol_count": stats.sql_symbol_count,
+        "tables": stats.sql_tables,
+        "views": stats.sql_views,
+        "table_refs": stats.sql_table_refs,
+    });
+    json["zig"] = serde_json::json!({
+        "file_count": stats.zig_file_count,
+        "symbol_count": stats.zig_symbol_count,
+        "fns": stats.zig_fns,
+        "methods": stats.zig_methods,
+        "structs": stats.zig_structs,
+        "enums": stats.zig_enums,
+        "variables": stats.zig_variables,
+    });
+    json["shell"] = serde_json::json!({
+        "file_count": stats.shell_file_count,
+        "fns": stats.shell_fns,
+    });
+    json["jupyter"] = serde_json::json!({
+        "notebook_count": stats.notebook_file_count,
+    });
+    json["css"] = serde_json::json!({
+        "file_count": stats.css_file_count,
+        "symbol_count": stats.css_symbol_count,
+        "fns": stats.css_fns,
+        "variables": stats.css_variables,
+    });
+    json["total_lines"] = stats.total_lines.into();
+    json["largest_files"] = serde_json::json!(stats.largest_files);
+    json["largest_functions"] = serde_json::json!(stats.largest_functions);
+    json["identical_files"] = serde_json::json!(stats.identical_files);
+    json
 }
 
 // ---------------------------------------------------------------------------
 // String-returning formatters (siblings of the println!-based CLI formatters)
 // ---------------------------------------------------------------------------
 
+/// Format project stats to a String in the compact format used by `stats --format compact`.
+pub fn format_stats_to_string(stats: &ProjectStats, language_filter: Option<&str>) -> String {
+    use std::fmt::Write;
+    let show_rust = language_filter.is_none() || language_filter == Some("rust");
+    let show_ts = language_filter.is_none()
+        || language_filter == Some("typescript")
+        || language_filter == Some("javascript");
+    let show_python = language_filter.is_none() || language_filter == Some("python");
+    let show_go = language_filter.is_none() || language_filter == Some("go");
+    let show_proto = language_filter.is_none() || language_filter == Some("proto");
+    let show_sql = language_filter.is_none() || language_filter == Some("sql");
+    let show_zig = language_filter.is_none() || language_filter == Some("zig");
+    let show_shell = language_filter.is_none() || language_filter == Some("shell");
+    let show_jupyter = language_filter.is_none() || language_filter == Some("jupyter");
+    let show_css = language_filter.is_none() || language_filter == Some("css");
+    let show_totals = language_filter.is_none();
+
+    let has_rust = stats_has_rust(stats);
+    let has_ts = stats_has_ts_js(stats);
+    let has_python = stats_has_python(stats);
+    let has_go = stats_has_go(stats);
+    let has_proto = stats_has_proto(stats);
+    let has_sql = stats_has_sql(stats);
+    let has_zig = stats_has_zig(stats);
+    let has_shell = stats_has_shell(stats);
+    let has_jupyter = stats_has_jupyter(stats);
+    let has_css = stats_has_css(stats);
+
+    let mut buf = String::new();
+    // File overview line
+    if stats.non_parsed_files > 0 {
+        writeln!(
+            buf,
+            "{} files ({} source, {} non-parsed), {} symbols",
+            stats.file_count, stats.source_files, stats.non_parsed_files, stats.symbol_count
+        )
+        .unwrap();
+        writeln!(
+            buf,
+            "non-parsed: doc {} config {} ci {} asset {} other {}",
+            stats.doc_files,
+            stats.config_files,
+            stats.ci_files,
+            stats.asset_files,
+            stats.other_files,
+        )
+        .unwrap();
+    }
+    // Per-language sections with per-language counts and combined totals.
+    if show_rust && has_rust {
+        let rust_symbol_total = stats.rust_fns
+            + stats.rust_structs
+            + stats.rust_enums
+            + stats.rust_traits
+            + stats.rust_impl_methods
+            + stats.rust_type_aliases
+            + stats.rust_consts
+            + stats.rust_statics
+            + stats.rust_macros;
+        writeln!(
+    buf,
+            "Rust: {} symbols (fn: {} struct: {} enum: {} trait: {} impl_method: {} type: {} const: {} static: {} macro: {})",
+            rust_symbol_total,
+            stats.rust_fns,
+            stats.rust_structs,
+            stats.rust_enums,
+            stats.rust_traits,
+            stats.rust_impl_methods,
+            stats.rust_type_aliases,
+            stats.rust_consts,
+            stats.rust_statics,
+            stats.rust_macros,
+        ).unwrap();
+        writeln!(
+            buf,
+            "rust_use {} rust_pub_use {}",
+            stats.rust_imports, stats.rust_reexports,
+        )
+        .unwrap();
+        if stats.rust_cfg_test + stats.rust_cfg_feature + stats.rust_cfg_other > 0 {
+            writeln!(
+                buf,
+                "rust_cfg test {} feature {} other {}",
+                stats.rust_cfg_test, stats.rust_cfg_feature, stats.rust_cfg_other,
+            )
+            .unwrap();
+        }
+        if stats.rust_derived_impl_methods + stats.rust_derive_traits > 0 {
+            writeln!(
+                buf,
+                "rust_derived impl_methods {} derive_traits {}",
+                stats.rust_derived_impl_methods, stats.rust_derive_traits,
+            )
+            .unwrap();
+        }
+        // Dependencies section (Phase 9)
+        let has_deps = stats.external_packages > 0 || stats.builtin_count > 0;
+        if has_deps {
+            writeln!(
+                buf,
+                "dependencies external_crates {} (usages {}) builtins {} (usages {})",
+                stats.external_packages,
+                stats.external_usage_count,
+                stats.builtin_count,
+                stats.builtin_usage_count,
+            )
+            .unwrap();
+        }
+        // Per-crate breakdown (Phase 9, only for workspaces with multiple crates)
+        if !stats.rust_crate_stats.is_empty() {
+            for cs in &stats.rust_crate_stats {
+                writeln!(
+                    buf,
+                    "crate {} files {} symbols {}",
+                    cs.crate_name, cs.file_count, cs.symbol_count
+                )
+                .unwrap();
+            }
+        }
+    }
+    if show_ts && has_ts {
+        // Subtract Rust-specific, Python, and Go symbols to get TS/JS-only counts.
+        let ts_fns = stats.functions.saturating_sub(
+            stats.rust_fns
+                + stats.python_fns
+                + stats.go_fns
+                + stats.zig_fns
+                + stats.shell_fns
+                + stats.css_fns,
+        );
+        let ts_classes = stats.classes.saturating_sub(stats.python_classes);
+        let ts_enums = stats
+            .enums
+            .saturating_sub(stats.rust_enums + stats.proto_enums + stats.zig_enums);
+        let ts_type_aliases = stats.type_aliases.saturating_sub(
+            stats.rust_type_aliases + stats.python_type_aliases + stats.go_type_aliases,
+        );
+        let ts_variables = stats.variables.saturating_sub(
+            stats.python_variables + stats.go_variables + stats.zig_variables + stats.css_variables,
+        );
+        let ts_methods = stats
+            .methods
+            .saturating_sub(stats.python_methods + stats.go_methods + stats.zig_methods);
+        let rust_total = stats.rust_fns
+            + stats.rust_structs
+            + stats.rust_enums
+            + stats.rust_traits
+            + stats.rust_impl_methods
+            + stats.rust_type_aliases
+            + stats.rust_consts
+            + stats.rust_statics
+            + stats.rust_macros;
+        let ts_total = stats.symbol_count.saturating_sub(
+            rust_total
+                + stats.python_symbol_count
+                + stats.go_symbol_count
+                + stats.proto_symbol_count
+                + stats.sql_symbol_count
+                + stats.zig_symbol_count
+                + stats.shell_fns
+                + stats.css_symbol_count,
+        );
+        writeln!(
+    buf,
+            "TypeScript: {} symbols (function: {} class: {} interface: {} type: {} enum: {} variable: {} component: {} method: {} property: {})",
+            ts_total,
+            ts_fns,
+            ts_classes,
+            stats.interfaces,
+            ts_type_aliases,
+            ts_enums,
+            ts_variables,
+            stats.components,
+            ts_methods,
+            stats.properties,
+        ).unwrap();
+        writeln!(
+            buf,
+            "imports {} external {} unresolved {}",
+            stats.import_edges, stats.external_packages, stats.unresolved_imports,
+        )
+        .unwrap();
+    }
+    if show_python && has_python {
+        writeln!(
+            buf,
+            "Python: {} files, {} symbols (fn: {} class: {} method: {} type: {} variable: {})",
+            stats.python_file_count,
+            stats.python_symbol_count,
+            stats.python_fns,
+            stats.python_classes,
+            stats.python_methods,
+            stats.python_type_aliases,
+            stats.python_variables,
+        )
+        .unwrap();
+    }
+    if show_go && has_go {
+        writeln!(
+    buf,
+            "Go: {} files, {} symbols (fn: {} struct: {} interface: {} method: {} const: {} var: {} type: {})",
+            stats.go_file_count,
+            stats.go_symbol_count,
+            stats.go_fns,
+            stats.go_structs,
+            stats.go_interfaces,
+            stats.go_methods,
+            stats.go_consts,
+            stats.go_variables,
+            stats.go_type_aliases,
+        ).unwrap();
+    }
+    if show_proto && has_proto {
+        writeln!(
+            buf,
+            "Protobuf: {} files, {} symbols (message: {} enum: {} service: {} rpc: {})",
+            stats.proto_file_count,
+            stats.proto_symbol_count,
+            stats.proto_messages,
+            stats.proto_enums,
+            stats.proto_services,
+            stats.proto_rpcs,
+        )
+        .unwrap();
+    }
+    if show_sql && has_sql {
+        writeln!(
+            buf,
+            "SQL: {} files, {} symbols (table: {} view: {}) table_refs {}",
+            stats.sql_file_count,
+            stats.sql_symbol_count,
+            stats.sql_tables,
+            stats.sql_views,
+            stats.sql_table_refs,
+        )
+        .unwrap();
+    }
+    if show_zig && has_zig {
+        writeln!(
+            buf,
+            "Zig: {} files, {} symbols (fn: {} method: {} struct: {} enum: {})",
+            stats.zig_file_count,
+            stats.zig_symbol_count,
+            stats.zig_fns,
+            stats.zig_methods,
+            stats.zig_structs,
+            stats.zig_enums,
+        )
+        .unwrap();
+    }
+    if show_shell && has_shell {
+        writeln!(
+            buf,
+            "Shell: {} files, {} functions",
+            stats.shell_file_count, stats.shell_fns,
+        )
+        .unwrap();
+    }
+    if show_jupyter && has_jupyter {
+        writeln!(buf, "Jupyter: {} notebooks", stats.notebook_file_count).unwrap();
+    }
+    if show_css && has_css {
+        writeln!(
+            buf,
+            "CSS: {} stylesheets, {} symbols (mixin/function: {} variable: {})",
+            stats.css_file_count, stats.css_symbol_count, stats.css_fns, stats.css_variables,
+        )
+        .unwrap();
+    }
+    if show_totals
+        && (has_rust
+            || has_ts
+            || has_python
+            || has_go
+            || has_proto
+            || has_sql
+            || has_zig
+            || has_shell
+            || has_jupyter
+            || has_css)
+    {
+        let language_count = [
+            has_rust, has_ts, has_python, has_go, has_proto, has_sql, has_zig, has_shell, has_css,
+        ]
+        .iter()
+        .filter(|&&x| x)
+        .count();
+        if language_count > 1 {
+            writeln!(buf, "---").unwrap();
+            writeln!(
+                buf,
+                "Total: {} files, {} symbols",
+                stats.file_count, stats.symbol_count
+            )
+            .unwrap();
+        } else {
+            writeln!(buf, "files {}", stats.file_count).unwrap();
+            writeln!(buf, "symbols {}", stats.symbol_count).unwrap();
+        }
+    } else if show_totals {
+        writeln!(buf, "files {}", stats.file_count).unwrap();
+        writeln!(buf, "symbols {}", stats.symbol_count).unwrap();
+    }
+    // Fallback: show full stats if no language-specific sections match
+    if !has_rust
+        && !has_ts
+        && !has_python
+        && !has_go
+        && !has_proto
+        && !has_sql
+        && !has_zig
+        && !has_shell
+        && !has_jupyter
+        && !has_css
+    {
+        writeln!(buf, "files {}", stats.file_count).unwrap();
+        writeln!(buf, "symbols {}", stats.symbol_count).unwrap();
+        writeln!(
+            buf,
+            "imports {} external {} unresolved {}",
+            stats.import_edges, stats.external_packages, stats.unresolved_imports
+        )
+        .unwrap();
+    }
+    if show_totals && stats.total_lines > 0 {
+        writeln!(buf, "lines {}", stats.total_lines).unwrap();
+        for f in &stats.largest_files {
+            writeln!(buf, "largest_file {} {}", f.lines, f.file).unwrap();
+        }
+        for f in &stats.largest_functions {
+            let name = f.name.as_deref().unwrap_or_default();
+            writeln!(buf, "largest_fn {} {} {}:{}", f.lines, name, f.file, f.line).unwrap();
+        }
+        for group in &stats.identical_files {
+            let copies: Vec<String> = group
+                .copies
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            writeln!(
+                buf,
+                "identical {} = {}",
+                group.canonical.display(),
+                copies.join(" ")
+            )
+            .unwrap();
+        }
+    }
+    writeln!(buf, "hint: use dead-code to find unreferenced symbols").unwrap();
+    buf
+}
+
 /// Format find results to a String in compact prefix-free format for CLI output.
 ///
 /// No summary line. No "def " prefix. Line format: `{rel_path}:{line} {symbol_name} {kind}`
 /// (with optional visibility suffix for Rust). In mixed-language results, groups by language
 /// with `--- {Language} ---` section headers.
-#[cfg(test)]
 pub fn format_find_to_string(
     results: &[FindResult],
     project_root: &Path,
@@ -1501,25 +3799,7 @@ pub fn format_find_to_string(
     let show_vis = any_non_private(results);
     let mixed = is_mixed_language(results, |r: &FindResult| r.file_path.as_path());
 
-    // Only clone when sorting is needed to avoid unnecessary allocation.
-    let sorted;
-    let results_ref = if mixed {
-        sorted = {
-            let mut v = results.to_vec();
-            v.sort_by(|a, b| {
-                let la = language_of_file(&a.file_path);
-                let lb = language_of_file(&b.file_path);
-                language_sort_key(la)
-                    .cmp(&language_sort_key(lb))
-                    .then(a.file_path.cmp(&b.file_path))
-                    .then(a.line.cmp(&b.line))
-            });
-            v
-        };
-        &sorted[..]
-    } else {
-        results
-    };
+    let results_ref = &*group_by_language(results, mixed);
 
     let mut buf = String::new();
     let mut last_lang: Option<&'static str> = None;
@@ -1626,9 +3906,8 @@ pub fn format_decorator_to_string(
 /// Format reference results to a String in compact prefix-free format for CLI output.
 ///
 /// No summary line. No "ref " prefix. Line formats:
-/// - Import: `{rel_path} import`
+/// - Import: `{rel_path}[:{line}[:{col}]] import`
 /// - Call:   `{rel_path}:{line} call {caller_name}`
-#[cfg(test)]
 pub fn format_refs_to_string(
     results: &[RefResult],
     project_root: &Path,
@@ -1643,12 +3922,20 @@ pub fn format_refs_to_string(
             .unwrap_or(&r.file_path);
         match r.ref_kind {
             RefKind::Import => {
-                writeln!(buf, "{} import", rel.display()).unwrap();
+                writeln!(buf, "{} import", import_site(rel, r)).unwrap();
             }
-            RefKind::Call => {
+            RefKind::Call | RefKind::Render | RefKind::Read | RefKind::Write => {
                 let caller = r.symbol_name.as_deref().unwrap_or("?");
                 let line = r.line.map_or_else(|| "?".to_string(), |l| l.to_string());
-                writeln!(buf, "{}:{} call {}", rel.display(), line, caller).unwrap();
+                writeln!(
+                    buf,
+                    "{}:{} {} {}",
+                    rel.display(),
+                    line,
+                    r.ref_kind.as_str(),
+                    caller
+                )
+                .unwrap();
             }
         }
     }
@@ -1668,7 +3955,6 @@ pub fn format_refs_to_string(
 ///
 /// No summary line. No "impact " prefix. Line format: `{rel_path} (depth N) [TIER: basis]`.
 /// Uses flat (non-tree) format — flat format is more token-efficient.
-#[cfg(test)]
 pub fn format_impact_to_string(
     results: &[ImpactResult],
     project_root: &Path,
@@ -1710,23 +3996,19 @@ pub fn format_impact_to_string(
 
 /// Format circular dependency results to a String in compact prefix-free format for CLI output.
 ///
-/// No summary line. No "cycle " prefix. Line format: `{file1} -> {file2} -> {file3}`.
-#[cfg(test)]
+/// No summary line. No "cycle " prefix. Line format: `{file1} -> {file2} -> {file3}`,
+/// one line per representative cycle, followed by an indented size note for groups
+/// larger than the cycles shown.
 pub fn format_circular_to_string(cycles: &[CircularDep], project_root: &Path) -> String {
     use std::fmt::Write;
     let mut buf = String::new();
-    for cycle in cycles {
-        let parts: Vec<String> = cycle
-            .files
-            .iter()
-            .map(|p| {
-                p.strip_prefix(project_root)
-                    .unwrap_or(p)
-                    .to_string_lossy()
-                    .to_string()
-            })
-            .collect();
-        writeln!(buf, "{}", parts.join(" -> ")).unwrap();
+    for group in cycles {
+        for chain in circular_chains(group, project_root) {
+            writeln!(buf, "{}", chain).unwrap();
+        }
+        if let Some(note) = circular_group_note(group) {
+            writeln!(buf, "  ({})", note).unwrap();
+        }
     }
     if cycles.is_empty() {
         writeln!(
@@ -1759,11 +4041,11 @@ pub fn format_circular_to_string(cycles: &[CircularDep], project_root: &Path) ->
 ///
 /// - `None` input → `None` output (no filtering, all sections shown)
 /// - Characters map to section names: r=references, c=callers, e=callees,
-///   x=extends, i=implements, X=extended-by, I=implemented-by
+///   x=extends, i=implements, X=extended-by, I=implemented-by, o=overrides,
+///   O=overridden-by, d=documented-by, R=routes
 /// - Commas and whitespace are separators (silently ignored)
 /// - Unknown characters are silently ignored
 /// - Returns `Some(HashSet)` with the matched section names
-#[cfg(test)]
 pub fn parse_sections(sections: Option<&str>) -> Option<std::collections::HashSet<&'static str>> {
     let s = sections?;
     let mut set = std::collections::HashSet::new();
@@ -1790,6 +4072,18 @@ pub fn parse_sections(sections: Option<&str>) -> Option<std::collections::HashSe
             'I' => {
                 set.insert("implemented-by");
             }
+            'o' => {
+                set.insert("overrides");
+            }
+            'O' => {
+                set.insert("overridden-by");
+            }
+            'd' => {
+                set.insert("documented-by");
+            }
+            'R' => {
+                set.insert("routes");
+            }
             _ => {} // separators (comma, space) and unknown chars silently ignored
         }
     }
@@ -1809,13 +4103,15 @@ pub fn parse_sections(sections: Option<&str>) -> Option<std::collections::HashSe
 /// - References (call):      `{rel_path}:{line} call {caller}`
 /// - Callers:                `{caller_name} {rel_path}:{line}`
 /// - Callees:                `{callee_name} {rel_path}:{line}`
-/// - Extends/implements/extended-by/implemented-by: `{name} {rel_path}:{line}`
+/// - Extends/implements/extended-by/implemented-by/overrides/overridden-by:
+///   `{name} {rel_path}:{line}`
+/// - Documented-by:          `{rel_path} doc`
+/// - Routes:                 `{name} {rel_path}:{line}`
 ///
 /// Empty sections are silently omitted.
 ///
 /// `sections`: optional filter string (e.g. `"r,c"`). Definitions are always included.
 /// Non-empty sections that were filtered out are listed on an `omitted: ...` line.
-#[cfg(test)]
 pub fn format_context_to_string(
     contexts: &[SymbolContext],
     project_root: &Path,
@@ -1870,12 +4166,20 @@ pub fn format_context_to_string(
                     .unwrap_or(&r.file_path);
                 match r.ref_kind {
                     RefKind::Import => {
-                        writeln!(buf, "{} import", rel.display()).unwrap();
+                        writeln!(buf, "{} import", import_site(rel, r)).unwrap();
                     }
-                    RefKind::Call => {
+                    RefKind::Call | RefKind::Render | RefKind::Read | RefKind::Write => {
                         let caller = r.symbol_name.as_deref().unwrap_or("?");
                         let line = r.line.map_or_else(|| "?".to_string(), |l| l.to_string());
-                        writeln!(buf, "{}:{} call {}", rel.display(), line, caller).unwrap();
+                        writeln!(
+                            buf,
+                            "{}:{} {} {}",
+                            rel.display(),
+                            line,
+                            r.ref_kind.as_str(),
+                            caller
+                        )
+                        .unwrap();
                     }
                 }
             }
@@ -1989,6 +4293,69 @@ pub fn format_context_to_string(
             omitted.push("implemented-by");
         }
 
+        // Overrides
+        if active.as_ref().is_none_or(|s| s.contains("overrides")) {
+            for over in &ctx.overrides {
+                let rel = over
+                    .file_path
+                    .strip_prefix(project_root)
+                    .unwrap_or(&over.file_path);
+                writeln!(buf, "{} {}:{}", over.symbol_name, rel.display(), over.line).unwrap();
+            }
+        } else if !ctx.overrides.is_empty() {
+            omitted.push("overrides");
+        }
+
+        // Overridden-by
+        if active.as_ref().is_none_or(|s| s.contains("overridden-by")) {
+            for over_by in &ctx.overridden_by {
+                let rel = over_by
+                    .file_path
+                    .strip_prefix(project_root)
+                    .unwrap_or(&over_by.file_path);
+                writeln!(
+                    buf,
+                    "{} {}:{}",
+                    over_by.symbol_name,
+                    rel.display(),
+                    over_by.line
+                )
+                .unwrap();
+            }
+        } else if !ctx.overridden_by.is_empty() {
+            omitted.push("overridden-by");
+        }
+
+        // Documented-by
+        if active.as_ref().is_none_or(|s| s.contains("documented-by")) {
+            for doc in &ctx.documented_by {
+                let rel = doc.strip_prefix(project_root).unwrap_or(doc);
+                writeln!(buf, "{} doc", rel.display()).unwrap();
+            }
+        } else if !ctx.documented_by.is_empty() {
+            omitted.push("documented-by");
+        }
+
+        // Routes
+        if active.as_ref().is_none_or(|s| s.contains("routes")) {
+            for route in &ctx.routes {
+                let rel = route
+                    .file_path
+                    .strip_prefix(project_root)
+                    .unwrap_or(&route.file_path);
+                writeln!(
+                    buf,
+                    "{} {}:{}",
+                    route.symbol_name,
+                    rel.display(),
+                    route.line
+                )
+                .unwrap();
+            }
+        } else if !ctx.routes.is_empty() {
+            omitted.push("routes");
+        }
+
         // Emit omitted line only when sections were filtered AND some were non-empty.
         if !omitted.is_empty() {
             writeln!(buf, "omitted: {}", omitted.join(", ")).unwrap();
@@ -2015,18 +4382,13 @@ pub fn format_context_to_string(
 pub fn format_circular_results(cycles: &[CircularDep], format: &OutputFormat, project_root: &Path) {
     match format {
         OutputFormat::Compact => {
-            for cycle in cycles {
-                let parts: Vec<String> = cycle
-                    .files
-                    .iter()
-                    .map(|p| {
-                        p.strip_prefix(project_root)
-                            .unwrap_or(p)
-                            .to_string_lossy()
-                            .to_string()
-                    })
-                    .collect();
-                println!("cycle {}", parts.join(" -> "));
+            for group in cycles {
+                for chain in circular_chains(group, project_root) {
+                    println!("cycle {}", chain);
+                }
+                if let Some(note) = circular_group_note(group) {
+                    println!("  ({})", note);
+                }
             }
             println!("{} cycles found", cycles.len());
             if cycles.is_empty() {
@@ -2051,7 +4413,7 @@ pub fn format_circular_results(cycles: &[CircularDep], format: &OutputFormat, pr
         }
 
         OutputFormat::Table => {
-            let use_color = std::io::stdout().is_terminal();
+            let use_color = crate::pager::start();
             let header = |s: &str| {
                 if use_color {
                     format!("\x1b[1m{s}\x1b[0m")
@@ -2060,42 +4422,76 @@ pub fn format_circular_results(cycles: &[CircularDep], format: &OutputFormat, pr
                 }
             };
 
-            for (i, cycle) in cycles.iter().enumerate() {
-                println!("{}", header(&format!("=== Cycle {} ===", i + 1)));
-                // Show all but the last entry (which is the repeated first file).
-                let unique_files = &cycle.files[..cycle.files.len().saturating_sub(1)];
-                for path in unique_files {
-                    let rel = path.strip_prefix(project_root).unwrap_or(path);
-                    println!("  {}", rel.display());
+            for (i, group) in cycles.iter().enumerate() {
+                println!(
+                    "{}",
+                    header(&format!(
+                        "=== Cycle {} ({} files) ===",
+                        i + 1,
+                        group.files.len()
+                    ))
+                );
+                for chain in circular_chains(group, project_root) {
+                    println!("  {}", chain);
+                }
+                if let Some(note) = circular_group_note(group) {
+                    println!("  ({})", note);
                 }
                 println!();
             }
             println!("{} cycles found", cycles.len());
         }
 
-        OutputFormat::Json => {
-            let json_results: Vec<serde_json::Value> = cycles
-                .iter()
-                .map(|cycle| {
-                    let files: Vec<String> = cycle
-                        .files
-                        .iter()
-                        .map(|p| {
-                            p.strip_prefix(project_root)
-                                .unwrap_or(p)
-                                .to_string_lossy()
-                                .to_string()
-                        })
-                        .collect();
-                    serde_json::json!({ "files": files })
-                })
-                .collect();
+        OutputFormat::Json => print_json(&circular_results_to_json(cycles, project_root)),
+    }
+}
+
+/// Format symbol-level cycles (`circular --granularity symbol`).
+pub fn format_symbol_circular_results(
+    groups: &[crate::query::circular::SymbolCircularDep],
+    format: &OutputFormat,
+) {
+    if let OutputFormat::Json = format {
+        print_json(&serde_json::to_value(groups).unwrap_or_default());
+        return;
+    }
+    if groups.is_empty() {
+        println!("no circular dependencies found");
+        return;
+    }
+    let table = matches!(format, OutputFormat::Table);
+    if table {
+        crate::pager::start();
+    }
+    let chain = |cycle: &[crate::query::circular::CycleSymbol]| -> String {
+        cycle
+            .iter()
+            .map(|s| format!("{} ({}:{})", s.name, s.file, s.line))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    };
+    for (i, group) in groups.iter().enumerate() {
+        if table {
+            println!("=== Cycle {} ({} symbols) ===", i + 1, group.symbols.len());
+        }
+        for cycle in &group.cycles {
+            if table {
+                println!("  {}", chain(cycle));
+            } else {
+                println!("cycle {}", chain(cycle));
+            }
+        }
+        let covered = group.cycles.len() == 1
+            && group.cycles[0].len().saturating_sub(1) == group.symbols.len();
+        if !covered {
             println!(
-                "{}",
-                serde_json::to_string_pretty(&json_results).unwrap_or_default()
+                "  (scc size {}; representative cycles shown: {})",
+                group.symbols.len(),
+                group.cycles.len()
             );
         }
     }
+    println!("{} cycles found", groups.len());
 }
 
 // ---------------------------------------------------------------------------
@@ -2153,9 +4549,29 @@ fn format_nodes(nodes: &[StructureNode], depth: usize, lines: &mut Vec<String>)
                         sym_indent, prefix, sym.name, sym.kind
                     ));
                 }
-            }
-            StructureNode::NonParsedFile { name, kind_tag } => {
-                lines.push(format!("{}{} [{}]", indent, name, kind_tag));
+            }
+            StructureNode::NonParsedFile { name, kind_tag } => {
+                lines.push(format!("{}{} [{}]", indent, name, kind_tag));
+            }
+            StructureNode::Module {
+                name,
+                symbols,
+                children,
+            } => {
+                lines.push(format!("{}{}::", indent, name));
+                let sym_indent = "  ".repeat(depth + 1);
+                for sym in symbols {
+                    let prefix = match sym.visibility.as_str() {
+                        "pub" => "pub ",
+                        "pub(crate)" => "pub(crate) ",
+                        _ => "",
+                    };
+                    lines.push(format!(
+                        "{}{}{} ({})",
+                        sym_indent, prefix, sym.name, sym.kind
+                    ));
+                }
+                format_nodes(children, depth + 1, lines);
             }
             StructureNode::Truncated { count } => {
                 lines.push(format!("{}... ({} more items)", indent, count));
@@ -2237,6 +4653,17 @@ pub fn format_file_summary_to_string(summary: &crate::query::file_summary::FileS
         lines.push(format!("exports: {}", export_list));
     }
 
+    // largest:
+    if !summary.largest_symbols.is_empty() {
+        let largest: String = summary
+            .largest_symbols
+            .iter()
+            .map(|s| format!("{} ({}, {} lines)", s.name, s.kind, s.lines))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(format!("largest: {}", largest));
+    }
+
     // imports: / importers:
     lines.push(format!("imports: {}", summary.import_count));
     lines.push(format!("importers: {}", summary.importer_count));
@@ -2278,7 +4705,7 @@ pub fn format_imports_to_string(
     entries: &[crate::query::imports::ImportEntry],
     file_path: &str,
 ) -> String {
-    use crate::query::imports::ImportCategory;
+    use crate::query::imports::{DependencyStatus, ImportCategory};
 
     if entries.is_empty() {
         return format!("{} imports: none", file_path);
@@ -2294,6 +4721,12 @@ pub fn format_imports_to_string(
             ImportCategory::External => "external",
             ImportCategory::Builtin => "builtin",
         };
+        let category_str = match entry.dependency {
+            Some(DependencyStatus::Declared) => format!("{}, declared", category_str),
+            Some(DependencyStatus::Undeclared) => format!("{}, undeclared", category_str),
+            Some(DependencyStatus::Unused) => format!("{}, declared, unused", category_str),
+            None => category_str.to_string(),
+        };
         if entry.is_reexport {
             lines.push(format!(
                 "{} [re-export] ({})",
@@ -2307,65 +4740,160 @@ pub fn format_imports_to_string(
     lines.join("\n")
 }
 
-/// Format dead code analysis results to a compact string.
+/// Format dead code analysis results to a compact string, one section per severity tier.
 ///
 /// Output format:
 /// ```text
-/// unreachable files (N):
+/// [private] unreferenced symbols (N in M files):
+/// src/utils/helpers.rs:
+///   fn unused_helper :10
+///   fn old_function :25 [cfg(feature = "legacy")]
+///
+/// [file] unreachable files (N):
 ///   src/unused_module.rs
 ///   src/old_helper.ts
 ///
-/// unreferenced symbols (N in M files):
-/// src/utils/helpers.rs:
-///   fn unused_helper :10
-///   fn old_function :25
+/// [exported] unused exports (N in M files):
 /// src/lib/parser.ts:
-///   function deadFunc :42
+///   function parseLegacy :42
 /// ```
 ///
-/// Paths are relative to `root`.
+/// When `tier` is set only that tier's section is printed. Paths are relative to `root`.
 pub fn format_dead_code_to_string(
     result: &crate::query::dead_code::DeadCodeResult,
     root: &Path,
+    tier: Option<crate::query::dead_code::DeadTier>,
 ) -> String {
-    let mut lines: Vec<String> = Vec::new();
+    use crate::query::dead_code::DeadTier;
 
-    // --- Unreachable files section ---
-    let file_count = result.unreachable_files.len();
-    lines.push(format!("unreachable files ({}):", file_count));
-    if file_count == 0 {
-        lines.push("  none".to_string());
-    } else {
-        for file_path in &result.unreachable_files {
-            let rel = file_path.strip_prefix(root).unwrap_or(file_path);
-            lines.push(format!("  {}", rel.display()));
-        }
+    let shown = |t: DeadTier| tier.is_none_or(|only| only == t);
+    let mut sections: Vec<Vec<String>> = Vec::new();
+
+    if shown(DeadTier::Private) {
+        sections.push(dead_symbol_section(
+            "[private] unreferenced symbols",
+            &result.unreferenced_symbols,
+            root,
+        ));
     }
 
-    lines.push(String::new()); // blank line between sections
+    if shown(DeadTier::File) {
+        let mut lines = Vec::new();
+        let file_count = result.unreachable_files.len();
+        lines.push(format!("[file] unreachable files ({}):", file_count));
+        if file_count == 0 {
+            lines.push("  none".to_string());
+        } else {
+            for file_path in &result.unreachable_files {
+                let rel = file_path.strip_prefix(root).unwrap_or(file_path);
+                lines.push(format!("  {}", rel.display()));
+            }
+        }
+        sections.push(lines);
+    }
 
-    // --- Unreferenced symbols section ---
-    let total_symbols: usize = result
-        .unreferenced_symbols
-        .iter()
-        .map(|(_, syms)| syms.len())
-        .sum();
-    let file_groups = result.unreferenced_symbols.len();
+    if shown(DeadTier::Exported) {
+        sections.push(dead_symbol_section(
+            "[exported] unused exports",
+            &result.unused_exports,
+            root,
+        ));
+    }
 
-    lines.push(format!(
-        "unreferenced symbols ({} in {} files):",
-        total_symbols, file_groups
-    ));
+    sections
+        .into_iter()
+        .map(|lines| lines.join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
 
+/// One dead-symbol section of `format_dead_code_to_string`: a header with counts, then
+/// the symbols grouped under their file.
+fn dead_symbol_section(
+    title: &str,
+    groups: &[(PathBuf, Vec<crate::query::dead_code::DeadSymbol>)],
+    root: &Path,
+) -> Vec<String> {
+    let total_symbols: usize = groups.iter().map(|(_, syms)| syms.len()).sum();
+    let mut lines = vec![format!(
+        "{} ({} in {} files):",
+        title,
+        total_symbols,
+        groups.len()
+    )];
     if total_symbols == 0 {
         lines.push("  none".to_string());
     } else {
-        for (file_path, syms) in &result.unreferenced_symbols {
+        for (file_path, syms) in groups {
             let rel = file_path.strip_prefix(root).unwrap_or(file_path);
             lines.push(format!("{}:", rel.display()));
             for sym in syms {
-                lines.push(format!("  {} {} :{}", sym.kind, sym.name, sym.line));
+                match &sym.cfg {
+                    Some(cfg) => lines.push(format!(
+                        "  {} {} :{} [cfg({})]",
+                        sym.kind, sym.name, sym.line, cfg
+                    )),
+                    None => lines.push(format!("  {} {} :{}", sym.kind, sym.name, sym.line)),
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// Format dead code findings as a fix list for a deletion script.
+///
+/// Each line is `path:start-end`: delete that inclusive line range. Ranges nested inside
+/// another listed range are dropped. Within a file, ranges are listed bottom-up so deleting
+/// them in order never shifts a later entry's lines.
+///
+/// Unreachable files are never listed whole: a file nothing imports may still be compiled
+/// or loaded some other way, so only the symbols found dead inside it are. Only functions
+/// and methods are listed. The graph does not see every use of them (a function passed by
+/// value, a serde default named in a string), so callers first drop the findings still
+/// named elsewhere with
+/// [`DeadCodeResult::retain_unnamed_elsewhere`](crate::query::dead_code::DeadCodeResult::retain_unnamed_elsewhere).
+///
+/// Unused exports may still be consumed from outside the project, so they are only listed
+/// when `tier` asks for [`DeadTier::Exported`](crate::query::dead_code::DeadTier) itself.
+///
+/// Paths are relative to `root`.
+pub fn format_dead_code_fix_list(
+    result: &crate::query::dead_code::DeadCodeResult,
+    root: &Path,
+    tier: Option<crate::query::dead_code::DeadTier>,
+) -> String {
+    let exported = tier == Some(crate::query::dead_code::DeadTier::Exported);
+
+    let mut ranges: std::collections::BTreeMap<&PathBuf, Vec<(usize, usize)>> =
+        std::collections::BTreeMap::new();
+    for (file_path, syms) in result
+        .unreferenced_symbols
+        .iter()
+        .chain(result.unused_exports.iter().filter(|_| exported))
+    {
+        let entry = ranges.entry(file_path).or_default();
+        entry.extend(
+            syms.iter()
+                .filter(|s| matches!(s.kind.as_str(), "function" | "method" | "impl_method"))
+                .map(|s| (s.line, s.line_end)),
+        );
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for (file_path, mut spans) in ranges {
+        // Outermost first: by start, then widest end, so nested spans follow their parent.
+        spans.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+        let mut kept: Vec<(usize, usize)> = Vec::new();
+        for span in spans {
+            if kept.last().is_some_and(|last| span.1 <= last.1) {
+                continue;
             }
+            kept.push(span);
+        }
+        let rel = file_path.strip_prefix(root).unwrap_or(file_path);
+        for (start, end) in kept.into_iter().rev() {
+            lines.push(format!("{}:{}-{}", rel.display(), start, end));
         }
     }
 
@@ -2439,7 +4967,7 @@ pub fn format_clones_to_string(
 pub fn format_clones_table(result: &crate::query::clones::CloneGroupResult, root: &Path) -> String {
     let mut lines: Vec<String> = Vec::new();
 
-    let use_color = std::io::IsTerminal::is_terminal(&std::io::stdout());
+    let use_color = crate::pager::use_color();
 
     if use_color {
         lines.push(format!(
@@ -2479,12 +5007,7 @@ pub fn format_clones_table(result: &crate::query::clones::CloneGroupResult, root
 
             // Compute column widths
             let (name_w, file_w) = group.members.iter().fold((4usize, 4usize), |(nw, fw), m| {
-                let file_len = m
-                    .file
-                    .strip_prefix(root)
-                    .unwrap_or(&m.file)
-                    .as_os_str()
-                    .len();
+                let file_len = table_path(&m.file, root).chars().count();
                 (nw.max(m.name.len()), fw.max(file_len))
             });
 
@@ -2518,12 +5041,12 @@ pub fn format_clones_table(result: &crate::query::clones::CloneGroupResult, root
             ));
 
             for m in &group.members {
-                let rel = m.file.strip_prefix(root).unwrap_or(&m.file);
+                let rel = table_path(&m.file, root);
                 lines.push(format!(
                     "  {:<12}  {:<name_w$}  {:<file_w$}  {:>4}  {:>4}",
                     m.kind,
                     m.name,
-                    rel.display(),
+                    rel,
                     m.line,
                     m.body_size,
                     name_w = name_w,
@@ -2540,13 +5063,201 @@ pub fn format_clones_table(result: &crate::query::clones::CloneGroupResult, root
 // Diff output
 // ---------------------------------------------------------------------------
 
+/// Format an API diff as a markdown changelog section: one `###` heading per package with
+/// Added / Removed / Changed lists.
+pub fn format_api_diff_markdown(diff: &crate::query::api_diff::ApiDiff) -> String {
+    use std::fmt::Write;
+    let mut buf = String::new();
+    writeln!(buf, "## API changes: {}...{}", diff.from, diff.to).unwrap();
+    if diff.packages.is_empty() {
+        writeln!(buf, "\nNo public API changes (semver: patch).").unwrap();
+        return buf;
+    }
+    let reason = match diff.level {
+        crate::query::api_diff::SemverLevel::Major => "breaking changes",
+        crate::query::api_diff::SemverLevel::Minor => "additions only",
+        crate::query::api_diff::SemverLevel::Patch => "no API changes",
+    };
+    writeln!(buf, "\nSemver: **{}** ({})", diff.level.as_str(), reason).unwrap();
+    for package in &diff.packages {
+        writeln!(
+            buf,
+            "\n### {} ({})",
+            package.package,
+            package.level.as_str()
+        )
+        .unwrap();
+        let sections = [("Added", &package.added), ("Removed", &package.removed)];
+        for (title, symbols) in sections {
+            if symbols.is_empty() {
+                continue;
+            }
+            writeln!(buf, "\n#### {}\n", title).unwrap();
+            for sym in symbols {
+                // Members' headers do not name their parent; lead with the qualified name.
+                if sym.signature.contains(&sym.name) {
+                    writeln!(buf, "- `{}` ({})", sym.signature, sym.file).unwrap();
+                } else {
+                    writeln!(buf, "- `{}`: `{}` ({})", sym.name, sym.signature, sym.file).unwrap();
+                }
+            }
+        }
+        if !package.changed.is_empty() {
+            writeln!(buf, "\n#### Changed\n").unwrap();
+            for change in &package.changed {
+                writeln!(
+                    buf,
+                    "- `{}`: `{}` → `{}` ({})",
+                    change.name, change.from_signature, change.to_signature, change.file
+                )
+                .unwrap();
+            }
+        }
+    }
+    buf
+}
+
+/// Print an onboarding report: markdown, or JSON with `--format json`.
+pub fn format_onboarding_report(
+    report: &crate::query::report::OnboardingReport,
+    format: &OutputFormat,
+) {
+    match format {
+        OutputFormat::Json => print_json(&serde_json::to_value(report).unwrap_or_default()),
+        OutputFormat::Compact | OutputFormat::Table => {
+            print!("{}", format_onboarding_markdown(report))
+        }
+    }
+}
+
+/// Format an onboarding report as a markdown document, one `##` section per query.
+pub fn format_onboarding_markdown(report: &crate::query::report::OnboardingReport) -> String {
+    use std::fmt::Write;
+    let mut buf = String::new();
+    writeln!(buf, "# {}: architecture overview", report.project).unwrap();
+
+    writeln!(buf, "\n## At a glance\n").unwrap();
+    writeln!(
+        buf,
+        "- {} source files, {} symbols, {} imports between files, {} external packages",
+        report.source_files, report.symbols, report.import_edges, report.external_packages
+    )
+    .unwrap();
+    if !report.languages.is_empty() {
+        let languages: Vec<String> = report
+            .languages
+            .iter()
+            .map(|l| format!("{} ({})", l.language, l.files))
+            .collect();
+        writeln!(buf, "- Languages: {}", languages.join(", ")).unwrap();
+    }
+
+    if !report.structure.is_empty() {
+        writeln!(buf, "\n## Structure\n").unwrap();
+        for dir in &report.structure {
+            writeln!(
+                buf,
+                "{}- `{}/`: {} files, {} symbols",
+                "  ".repeat(dir.depth - 1),
+                dir.path,
+                dir.files,
+                dir.symbols
+            )
+            .unwrap();
+        }
+    }
+
+    if !report.packages.is_empty() {
+        writeln!(buf, "\n## Packages\n").unwrap();
+        writeln!(buf, "| Package | Files | Depends on | Used by |").unwrap();
+        writeln!(buf, "|---|---:|---|---:|").unwrap();
+        for package in &report.packages {
+            let depends_on = if package.depends_on.is_empty() {
+                "-".to_string()
+            } else {
+                package.depends_on.join(", ")
+            };
+            writeln!(
+                buf,
+                "| `{}` | {} | {} | {} |",
+                package.name, package.files, depends_on, package.used_by
+            )
+            .unwrap();
+        }
+    }
+
+    if !report.central_files.is_empty() {
+        writeln!(buf, "\n## Most central files\n").unwrap();
+        writeln!(buf, "| File | Imported by | Imports | Symbols |").unwrap();
+        writeln!(buf, "|---|---:|---:|---:|").unwrap();
+        for file in &report.central_files {
+            writeln!(
+                buf,
+                "| `{}` | {} | {} | {} |",
+                file.file, file.fan_in, file.fan_out, file.symbols
+            )
+            .unwrap();
+        }
+    }
+
+    if !report.entrypoints.is_empty() {
+        writeln!(buf, "\n## Entry points\n").unwrap();
+        writeln!(buf, "| File | Why | Files reached |").unwrap();
+        writeln!(buf, "|---|---|---:|").unwrap();
+        for entry in &report.entrypoints {
+            writeln!(
+                buf,
+                "| `{}` | {} | {} |",
+                entry.file,
+                entry.reasons.join(", "),
+                entry.reaches
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(buf, "\n## Circular dependencies\n").unwrap();
+    if report.cycle_groups == 0 {
+        writeln!(buf, "None.").unwrap();
+    } else {
+        writeln!(
+            buf,
+            "Groups of files importing each other in a cycle: {}\n",
+            report.cycle_groups
+        )
+        .unwrap();
+        for cycle in &report.cycles {
+            writeln!(buf, "- `{}`", cycle.join(" → ")).unwrap();
+        }
+    }
+
+    writeln!(buf, "\n## Going further\n").unwrap();
+    writeln!(
+        buf,
+        "- `code-graph context <symbol>`: definition, references, callers, and callees"
+    )
+    .unwrap();
+    writeln!(
+        buf,
+        "- `code-graph impact <symbol>`: what depends on a symbol before you change it"
+    )
+    .unwrap();
+    writeln!(
+        buf,
+        "- `code-graph structure <dir>`: the symbols of each file in a directory"
+    )
+    .unwrap();
+    buf
+}
+
 /// Format a GraphDiff as a compact string for CLI output.
 ///
 /// Example:
 /// ```text
-/// files: +2 -1
+/// files: +2 -1 R1
 /// +  src/new_module.rs
 /// -  src/removed.rs
+/// R  src/parse.rs → src/parser/mod.rs
 ///
 /// symbols: +3 -2 ~1
 /// +  src/new_module.rs :: new_function
@@ -2557,17 +5268,24 @@ pub fn format_diff_to_string(diff: &crate::query::diff::GraphDiff) -> String {
     let mut lines: Vec<String> = Vec::new();
 
     // Files header
-    lines.push(format!(
+    let mut header = format!(
         "files: +{} -{}",
         diff.added_files.len(),
         diff.removed_files.len()
-    ));
+    );
+    if !diff.renamed_files.is_empty() {
+        header.push_str(&format!(" R{}", diff.renamed_files.len()));
+    }
+    lines.push(header);
     for f in &diff.added_files {
         lines.push(format!("+  {}", f));
     }
     for f in &diff.removed_files {
         lines.push(format!("-  {}", f));
     }
+    for r in &diff.renamed_files {
+        lines.push(format!("R  {} → {}", r.from, r.to));
+    }
 
     lines.push(String::new()); // blank separator
 
@@ -2594,6 +5312,34 @@ pub fn format_diff_to_string(diff: &crate::query::diff::GraphDiff) -> String {
 
     lines.join("\n")
 }
+
+/// Format the symbol changes of a single-file diff (see `diff::file_snapshot`), one per
+/// line, for watch-mode announcements. The file itself is named by the caller.
+///
+/// Example:
+/// ```text
+/// +  new_function
+/// -  old_function
+/// ~  parse_input (line 10 → 15)
+/// ```
+pub fn format_file_diff_to_string(diff: &crate::query::diff::GraphDiff) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for (_, sym) in &diff.added_symbols {
+        lines.push(format!("+  {}", sym));
+    }
+    for (_, sym) in &diff.removed_symbols {
+        lines.push(format!("-  {}", sym));
+    }
+    for change in &diff.modified_symbols {
+        lines.push(format!(
+            "~  {} ({})",
+            change.name,
+            change.changes.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
 // ---------------------------------------------------------------------------
 // Unit tests for compact formatters
 // ---------------------------------------------------------------------------
@@ -2603,7 +5349,7 @@ mod tests {
     use std::path::PathBuf;
 
     use super::*;
-    use crate::graph::node::{SymbolKind, SymbolVisibility};
+    use crate::graph::node::{ParamInfo, SignatureInfo, SymbolKind, SymbolVisibility};
     use crate::query::circular::CircularDep;
     use crate::query::context::{CallInfo, SymbolContext};
     use crate::query::find::FindResult;
@@ -2618,10 +5364,14 @@ mod tests {
             line,
             line_end: 0,
             col: 0,
+            col_end: 0,
+            byte_start: 0,
+            byte_end: 0,
             is_exported: false,
             is_default: false,
             visibility: SymbolVisibility::Private,
             decorators: Vec::new(),
+            signature: None,
         }
     }
 
@@ -2653,6 +5403,96 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_json_includes_signature() {
+        let root = PathBuf::from("/project");
+        let mut result = make_find_result("load", "/project/src/foo.ts", 3, SymbolKind::Function);
+        result.signature = Some(SignatureInfo {
+            params: vec![ParamInfo {
+                name: "id".into(),
+                type_annotation: Some("string".into()),
+            }],
+            return_type: Some("Promise<User>".into()),
+        });
+        let plain = make_find_result("Config", "/project/src/foo.ts", 9, SymbolKind::Interface);
+
+        let json = find_results_to_json(&[result, plain], &root);
+        assert_eq!(
+            json[0]["signature"],
+            serde_json::json!({
+                "params": [{"name": "id", "type": "string"}],
+                "return_type": "Promise<User>",
+            })
+        );
+        assert!(json[1]["signature"].is_null());
+        assert!(json[1]["bytes"].is_null());
+    }
+
+    #[test]
+    fn test_find_and_refs_groups_follow_arrangement() {
+        let root = PathBuf::from("/project");
+        let results = vec![
+            make_find_result("zeta", "/project/src/a.ts", 5, SymbolKind::Function),
+            make_find_result("Alpha", "/project/src/b.ts", 1, SymbolKind::Class),
+            make_find_result("beta", "/project/src/a.ts", 2, SymbolKind::Function),
+        ];
+        let mut arrangement = Arrangement {
+            group_by: Some(GroupBy::Kind),
+            sort: Some(SortBy::Name),
+            ..Default::default()
+        };
+        let summary = |groups: Groups<'_, FindResult>| -> Vec<(Option<String>, Vec<String>)> {
+            groups
+                .into_iter()
+                .map(|(g, items)| (g, items.iter().map(|r| r.symbol_name.clone()).collect()))
+                .collect()
+        };
+        assert_eq!(
+            summary(find_groups(&results, &arrangement, &root)),
+            vec![
+                (Some("class".into()), vec!["Alpha".into()]),
+                (Some("function".into()), vec!["beta".into(), "zeta".into()]),
+            ]
+        );
+
+        arrangement.group_by = None;
+        arrangement.sort = Some(SortBy::Refs);
+        arrangement
+            .refs
+            .insert((PathBuf::from("/project/src/b.ts"), 1), 7);
+        arrangement
+            .refs
+            .insert((PathBuf::from("/project/src/a.ts"), 5), 2);
+        assert_eq!(
+            summary(find_groups(&results, &arrangement, &root)),
+            vec![(None, vec!["Alpha".into(), "zeta".into(), "beta".into()])]
+        );
+
+        let refs = vec![
+            make_ref_result("/project/src/x.ts", RefKind::Import),
+            make_ref_result("/project/src/y.ts", RefKind::Import),
+            make_ref_result("/project/src/y.ts", RefKind::Call),
+        ];
+        let arrangement = Arrangement {
+            group_by: Some(GroupBy::File),
+            sort: Some(SortBy::Refs),
+            ..Default::default()
+        };
+        let groups = refs_groups(&refs, &arrangement, &root);
+        let keys: Vec<(Option<String>, usize)> = groups
+            .into_iter()
+            .map(|(g, items)| (g, items.len()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![(Some("src/x.ts".into()), 1), (Some("src/y.ts".into()), 2)]
+        );
+        let json = arranged_json(&refs_groups(&refs, &arrangement, &root), |r| {
+            ref_result_json(r, &root)
+        });
+        assert_eq!(json[0]["group"], "src/x.ts");
+    }
+
     #[test]
     fn test_refs_compact_format_no_prefix() {
         let root = PathBuf::from("/project");
@@ -2662,12 +5502,16 @@ mod tests {
                 ref_kind: RefKind::Import,
                 symbol_name: None,
                 line: None,
+                col: None,
+                derived: false,
             },
             RefResult {
                 file_path: PathBuf::from("/project/src/baz.ts"),
                 ref_kind: RefKind::Call,
                 symbol_name: Some("callerFn".to_string()),
                 line: Some(42),
+                col: None,
+                derived: false,
             },
         ];
         let output = format_refs_to_string(&results, &root, "MySymbol");
@@ -2755,6 +5599,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_impact_notifications() {
+        use crate::cli::NotifyFormat;
+        use crate::query::impact::ConfidenceTier;
+        use crate::query::owners::{CodeOwners, group_by_owner};
+
+        let root = PathBuf::from("/project");
+        let results = vec![
+            ImpactResult {
+                file_path: PathBuf::from("/project/src/billing/invoice.ts"),
+                depth: 1,
+                confidence: ConfidenceTier::High,
+                basis: "direct caller at depth 1".to_string(),
+            },
+            ImpactResult {
+                file_path: PathBuf::from("/project/scripts/seed.ts"),
+                depth: 2,
+                confidence: ConfidenceTier::Medium,
+                basis: "indirect caller at depth 2".to_string(),
+            },
+        ];
+        let owners = CodeOwners::parse(&root, "src/billing/ @org/billing\n");
+        let groups = group_by_owner(&results, Some(&owners));
+        let changed = [PathBuf::from("/project/src/money.ts")];
+
+        let slack = format_impact_notifications(
+            &groups,
+            &NotifyFormat::Slack,
+            &OutputFormat::Compact,
+            &root,
+            "Money",
+            &changed,
+        );
+        assert!(
+            slack.contains(
+                "*@org/billing*: your area is affected by a change to `Money` (`src/money.ts`) via these files:\n\
+                 • `src/billing/invoice.ts` (HIGH: direct caller at depth 1)\n"
+            ),
+            "got: {slack}"
+        );
+        assert!(slack.contains("*Unowned files*"), "got: {slack}");
+
+        let github = format_impact_notifications(
+            &groups,
+            &NotifyFormat::Github,
+            &OutputFormat::Compact,
+            &root,
+            "Money",
+            &changed,
+        );
+        assert!(github.starts_with("### @org/billing\n\nYour area is affected"));
+        assert!(github.contains("- `scripts/seed.ts` (MEDIUM: indirect caller at depth 2)"));
+
+        let json: serde_json::Value = serde_json::from_str(&format_impact_notifications(
+            &groups,
+            &NotifyFormat::Github,
+            &OutputFormat::Json,
+            &root,
+            "Money",
+            &changed,
+        ))
+        .unwrap();
+        assert_eq!(json[0]["owner"], "@org/billing");
+        assert_eq!(json[0]["files"][0]["file"], "src/billing/invoice.ts");
+        assert!(json[1]["owner"].is_null());
+    }
+
     #[test]
     fn test_circular_compact_format_no_prefix() {
         let root = PathBuf::from("/project");
@@ -2762,8 +5673,12 @@ mod tests {
             files: vec![
                 PathBuf::from("/project/src/a.ts"),
                 PathBuf::from("/project/src/b.ts"),
-                PathBuf::from("/project/src/a.ts"),
             ],
+            cycles: vec![vec![
+                PathBuf::from("/project/src/a.ts"),
+                PathBuf::from("/project/src/b.ts"),
+                PathBuf::from("/project/src/a.ts"),
+            ]],
         }];
         let output = format_circular_to_string(&cycles, &root);
 
@@ -2782,6 +5697,101 @@ mod tests {
             output.contains("src/a.ts -> src/b.ts -> src/a.ts"),
             "output should contain arrow-chain format, got: {output}"
         );
+        assert!(
+            !output.contains("scc size"),
+            "a cycle covering its whole group needs no size note, got: {output}"
+        );
+    }
+
+    #[test]
+    fn test_circular_compact_reports_scc_size() {
+        let root = PathBuf::from("/project");
+        let cycles = vec![CircularDep {
+            files: ["a", "b", "c", "d"]
+                .iter()
+                .map(|n| PathBuf::from(format!("/project/{n}.ts")))
+                .collect(),
+            cycles: vec![
+                ["a", "b", "a"]
+                    .iter()
+                    .map(|n| PathBuf::from(format!("/project/{n}.ts")))
+                    .collect(),
+                ["c", "d", "c"]
+                    .iter()
+                    .map(|n| PathBuf::from(format!("/project/{n}.ts")))
+                    .collect(),
+            ],
+        }];
+        let output = format_circular_to_string(&cycles, &root);
+        assert!(output.contains("a.ts -> b.ts -> a.ts\nc.ts -> d.ts -> c.ts\n"));
+        assert!(
+            output.contains("(scc size 4; representative cycles shown: 2)"),
+            "got: {output}"
+        );
+
+        let json = circular_results_to_json(&cycles, &root);
+        assert_eq!(json[0]["scc_size"], 4);
+        assert_eq!(json[0]["cycles"][1][0], "c.ts");
+    }
+
+    #[test]
+    fn test_dead_code_fix_list_ranges() {
+        use crate::query::dead_code::{DeadCodeResult, DeadSymbol};
+        let root = PathBuf::from("/project");
+        let sym = |name: &str, line: usize, line_end: usize| DeadSymbol {
+            name: name.to_string(),
+            kind: "function".to_string(),
+            line,
+            line_end,
+            bytes: 0,
+            cfg: None,
+        };
+        let result = DeadCodeResult {
+            unreachable_files: vec![PathBuf::from("/project/src/old.ts")],
+            unreferenced_symbols: vec![
+                (
+                    PathBuf::from("/project/src/a.rs"),
+                    // `helper` is a method nested inside the dead `Legacy` impl range.
+                    vec![
+                        sym("Legacy", 3, 20),
+                        sym("helper", 5, 8),
+                        sym("tail", 30, 31),
+                    ],
+                ),
+                (
+                    PathBuf::from("/project/src/old.ts"),
+                    vec![sym("inner", 1, 2)],
+                ),
+            ],
+            unused_exports: vec![(PathBuf::from("/project/src/b.ts"), vec![sym("api", 7, 9)])],
+        };
+
+        let mut result = result;
+        result.unreferenced_symbols[0].1.push(DeadSymbol {
+            kind: "const".to_string(),
+            ..sym("LIMIT", 40, 40)
+        });
+        let fix = format_dead_code_fix_list(&result, &root, None);
+        // Constants are not listed, and the unreachable file is not listed whole: only
+        // the dead symbol inside it is.
+        assert_eq!(fix, "src/a.rs:30-31\nsrc/a.rs:3-20\nsrc/old.ts:1-2");
+        // Unused exports are only listed when the exported tier is asked for.
+        let mut exported = result.clone();
+        exported.retain_tier(crate::query::dead_code::DeadTier::Exported);
+        let fix = format_dead_code_fix_list(
+            &exported,
+            &root,
+            Some(crate::query::dead_code::DeadTier::Exported),
+        );
+        assert_eq!(fix, "src/b.ts:7-9");
+
+        let compact = format_dead_code_to_string(
+            &result,
+            &root,
+            Some(crate::query::dead_code::DeadTier::Exported),
+        );
+        assert!(compact.starts_with("[exported] unused exports (1 in 1 files):"));
+        assert!(!compact.contains("[private]"));
     }
 
     #[test]
@@ -2804,6 +5814,11 @@ mod tests {
             implements: vec![],
             extended_by: vec![],
             implemented_by: vec![],
+            overrides: vec![],
+            overridden_by: vec![],
+            documented_by: vec![],
+            routes: vec![],
+            source: vec![],
         };
         let output = format_context_to_string(&[ctx], &root, None);
 
@@ -2924,6 +5939,8 @@ mod tests {
             ref_kind: kind,
             symbol_name: None,
             line: None,
+            col: None,
+            derived: false,
         }
     }
 
@@ -2948,6 +5965,11 @@ mod tests {
             implements: vec![],
             extended_by: vec![],
             implemented_by: vec![],
+            overrides: vec![],
+            overridden_by: vec![],
+            documented_by: vec![],
+            routes: vec![],
+            source: vec![],
         };
         let output = format_context_to_string(&[ctx], &root, Some("r"));
 
@@ -2992,6 +6014,11 @@ mod tests {
             implements: vec![],
             extended_by: vec![],
             implemented_by: vec![],
+            overrides: vec![],
+            overridden_by: vec![],
+            documented_by: vec![],
+            routes: vec![],
+            source: vec![],
         };
         // Request only callers — but definitions should still be rendered
         let output = format_context_to_string(&[ctx], &root, Some("c"));
@@ -3023,6 +6050,11 @@ mod tests {
             implements: vec![],
             extended_by: vec![],
             implemented_by: vec![],
+            overrides: vec![],
+            overridden_by: vec![],
+            documented_by: vec![],
+            routes: vec![],
+            source: vec![],
         };
         // Request only references — callers is empty so should NOT appear in omitted
         let output = format_context_to_string(&[ctx], &root, Some("r"));
@@ -3055,6 +6087,11 @@ mod tests {
             implements: vec![],
             extended_by: vec![],
             implemented_by: vec![],
+            overrides: vec![],
+            overridden_by: vec![],
+            documented_by: vec![],
+            routes: vec![],
+            source: vec![],
         };
         // sections=None means all sections
         let output = format_context_to_string(&[ctx], &root, None);
@@ -3153,6 +6190,61 @@ pub fn format_flow_to_string(result: &FlowResult, entry: &str, target: &str) ->
     lines.join("\n")
 }
 
+/// Format `packages` reports as a human-readable string for CLI output.
+///
+/// Output format:
+/// ```text
+/// package app [features: cli, default]
+///   dep anyhow
+///   dep clap (optional)
+///   dep core_lib (workspace)
+///   off axum, tracing
+///   mod app::server [feature server] off
+/// ```
+pub fn format_packages_to_string(
+    reports: &[crate::resolver::cargo_features::PackageReport],
+) -> String {
+    let mut out = String::new();
+    for report in reports {
+        let features = if report.features.is_empty() {
+            "none".to_string()
+        } else {
+            report.features.join(", ")
+        };
+        out.push_str(&format!(
+            "package {} [features: {}]\n",
+            report.name, features
+        ));
+        let mut off: Vec<&str> = Vec::new();
+        for dep in &report.dependencies {
+            if !dep.enabled {
+                off.push(&dep.name);
+                continue;
+            }
+            let note = match (dep.workspace, dep.optional) {
+                (true, true) => " (workspace, optional)",
+                (true, false) => " (workspace)",
+                (false, true) => " (optional)",
+                (false, false) => "",
+            };
+            out.push_str(&format!("  dep {}{}\n", dep.name, note));
+        }
+        if !off.is_empty() {
+            out.push_str(&format!("  off {}\n", off.join(", ")));
+        }
+        for module in &report.gated_modules {
+            out.push_str(&format!(
+                "  mod {} [feature {}] {}\n",
+                module.module,
+                module.feature,
+                if module.enabled { "on" } else { "off" }
+            ));
+        }
+    }
+    out.push_str(&format!("{} packages\n", reports.len()));
+    out
+}
+
 /// Format rename plan items as a human-readable string for CLI output.
 ///
 /// Output format:
@@ -3204,6 +6296,45 @@ pub fn format_rename_to_string(items: &[RenameItem], root: &Path) -> String {
     lines.join("\n")
 }
 
+/// Format a rename plan grouped by file, for `rename --dry-run`: a header, then each file
+/// with its sites (line and role) indented below it.
+pub fn format_rename_preview_to_string(items: &[RenameItem], root: &Path) -> String {
+    if items.is_empty() {
+        return "Rename Preview: no sites found — symbol not in graph.".to_string();
+    }
+    let files = crate::query::rename::group_by_file(items);
+    let mut lines: Vec<String> = vec![format!(
+        "Rename Preview: {} -> {} ({} sites in {} files)",
+        items[0].old_text,
+        items[0].new_text,
+        items.len(),
+        files.len()
+    )];
+    for file in &files {
+        let rel = file.file_path.strip_prefix(root).unwrap_or(&file.file_path);
+        lines.push(rel.display().to_string());
+        for site in &file.sites {
+            let line_str = if site.line == 0 {
+                "?".to_string()
+            } else {
+                site.line.to_string()
+            };
+            let note_str = site
+                .note
+                .as_deref()
+                .map(|n| format!("  [{}]", n))
+                .unwrap_or_default();
+            lines.push(format!(
+                "  {:>5}  {}{}",
+                line_str,
+                site.kind.as_str(),
+                note_str
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
 /// Format diff-impact results as a human-readable string.
 ///
 /// Used by the diff-impact CLI subcommand.
@@ -3363,6 +6494,7 @@ mod formatter_tests {
         let items = vec![
             RenameItem {
                 file_path: root.join("src/foo.rs"),
+                kind: crate::query::rename::RenameSiteKind::Definition,
                 line: 10,
                 old_text: "Foo".to_string(),
                 new_text: "Bar".to_string(),
@@ -3370,6 +6502,7 @@ mod formatter_tests {
             },
             RenameItem {
                 file_path: root.join("src/importer.rs"),
+                kind: crate::query::rename::RenameSiteKind::Import,
                 line: 0,
                 old_text: "Foo".to_string(),
                 new_text: "Bar".to_string(),
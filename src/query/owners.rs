@@ -0,0 +1,178 @@
+//! CODEOWNERS lookup and owner grouping for impact notifications.
+//!
+//! Follows GitHub's rules: the file is read from `.github/CODEOWNERS`, `CODEOWNERS`, or
+//! `docs/CODEOWNERS` (first found), patterns use gitignore syntax, and the last matching
+//! line wins. A matching line with no owners leaves the path unowned.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::query::impact::ImpactResult;
+
+/// Locations GitHub reads a CODEOWNERS file from, in priority order.
+const CODEOWNERS_LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// One `pattern owner...` line.
+struct OwnerRule {
+    matcher: Gitignore,
+    owners: Vec<String>,
+}
+
+/// Parsed CODEOWNERS rules for a project.
+pub struct CodeOwners {
+    root: PathBuf,
+    rules: Vec<OwnerRule>,
+}
+
+impl CodeOwners {
+    /// Load the project's CODEOWNERS file. Returns `None` when there is none.
+    pub fn load(project_root: &Path) -> Option<Self> {
+        CODEOWNERS_LOCATIONS.iter().find_map(|rel| {
+            let text = std::fs::read_to_string(project_root.join(rel)).ok()?;
+            Some(Self::parse(project_root, &text))
+        })
+    }
+
+    /// Parse CODEOWNERS text. Lines with invalid patterns are skipped.
+    pub fn parse(project_root: &Path, text: &str) -> Self {
+        let rules = text
+            .lines()
+            .filter_map(|line| {
+                let line = line.split(" #").next().unwrap_or("").trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?;
+                let mut builder = GitignoreBuilder::new(project_root);
+                builder.add_line(None, pattern).ok()?;
+                Some(OwnerRule {
+                    matcher: builder.build().ok()?,
+                    owners: parts.map(str::to_string).collect(),
+                })
+            })
+            .collect();
+        CodeOwners {
+            root: project_root.to_path_buf(),
+            rules,
+        }
+    }
+
+    /// Owners of `path` (absolute or relative to the project root). Empty when unowned.
+    pub fn owners_of(&self, path: &Path) -> &[String] {
+        let rel = path.strip_prefix(&self.root).unwrap_or(path);
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                rule.matcher
+                    .matched_path_or_any_parents(rel, false)
+                    .is_ignore()
+            })
+            .map_or(&[], |rule| rule.owners.as_slice())
+    }
+}
+
+/// Affected files of one owner (or of nobody, when `owner` is `None`).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OwnerGroup {
+    pub owner: Option<String>,
+    pub files: Vec<ImpactResult>,
+}
+
+/// Group impact results by CODEOWNERS owner. A file with several owners appears in each
+/// of their groups. Owners are sorted by name, with unowned files last.
+pub fn group_by_owner(results: &[ImpactResult], owners: Option<&CodeOwners>) -> Vec<OwnerGroup> {
+    let mut owned: BTreeMap<String, Vec<ImpactResult>> = BTreeMap::new();
+    let mut unowned: Vec<ImpactResult> = Vec::new();
+    for result in results {
+        let file_owners = owners.map_or(&[][..], |o| o.owners_of(&result.file_path));
+        if file_owners.is_empty() {
+            unowned.push(result.clone());
+        }
+        for owner in file_owners {
+            owned.entry(owner.clone()).or_default().push(result.clone());
+        }
+    }
+
+    let mut groups: Vec<OwnerGroup> = owned
+        .into_iter()
+        .map(|(owner, files)| OwnerGroup {
+            owner: Some(owner),
+            files,
+        })
+        .collect();
+    if !unowned.is_empty() {
+        groups.push(OwnerGroup {
+            owner: None,
+            files: unowned,
+        });
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::impact::ConfidenceTier;
+
+    fn result(root: &Path, rel: &str) -> ImpactResult {
+        ImpactResult {
+            file_path: root.join(rel),
+            depth: 1,
+            confidence: ConfidenceTier::High,
+            basis: "direct importer".into(),
+        }
+    }
+
+    #[test]
+    fn test_owners_of_last_match_wins() {
+        let root = Path::new("/repo");
+        let owners = CodeOwners::parse(
+            root,
+            "# Default\n\
+             *       @org/core\n\
+             *.md    @org/docs\n\
+             /src/payments/ @org/payments @alice # inline comment\n\
+             src/payments/legacy/\n\
+             docs/**/api.md @org/api\n",
+        );
+        assert_eq!(owners.owners_of(&root.join("src/main.ts")), ["@org/core"]);
+        assert_eq!(owners.owners_of(Path::new("README.md")), ["@org/docs"]);
+        assert_eq!(
+            owners.owners_of(&root.join("src/payments/charge.ts")),
+            ["@org/payments", "@alice"]
+        );
+        // A later line without owners clears ownership.
+        assert!(
+            owners
+                .owners_of(&root.join("src/payments/legacy/old.ts"))
+                .is_empty()
+        );
+        assert_eq!(owners.owners_of(&root.join("docs/v1/api.md")), ["@org/api"]);
+    }
+
+    #[test]
+    fn test_group_by_owner() {
+        let root = Path::new("/repo");
+        let owners = CodeOwners::parse(root, "src/a/ @team-a\nsrc/shared/ @team-a @team-b\n");
+        let results = vec![
+            result(root, "src/a/x.ts"),
+            result(root, "src/shared/y.ts"),
+            result(root, "src/z.ts"),
+        ];
+        let groups = group_by_owner(&results, Some(&owners));
+        let summary: Vec<(Option<&str>, usize)> = groups
+            .iter()
+            .map(|g| (g.owner.as_deref(), g.files.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(Some("@team-a"), 2), (Some("@team-b"), 1), (None, 1)]
+        );
+        // Without a CODEOWNERS file everything is unowned.
+        assert_eq!(group_by_owner(&results, None).len(), 1);
+    }
+}
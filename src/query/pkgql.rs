@@ -0,0 +1,517 @@
+//! Bazel-style query operators over the package graph (`code-graph pkg-query`).
+//!
+//! Packages are npm/yarn/pnpm workspace packages, Cargo workspace crates when there are
+//! several, and otherwise the same groups as `export --granularity package` (top-level Rust
+//! modules or top-level directories). Package `a` depends on `b` when a file of `a` imports
+//! a file of `b`.
+//!
+//! Grammar:
+//!
+//! ```text
+//! expr  := term (op term)*          op: `+` / `union`, `-` / `except`, `^` / `intersect`
+//! term  := deps(expr [, depth]) | rdeps(expr [, depth])
+//!        | somepath(expr, expr) | allpaths(expr, expr)
+//!        | ( expr ) | name
+//! ```
+//!
+//! Names may be quoted and may contain glob wildcards (`@app/*`). Operators are
+//! left-associative with equal precedence and must be separated by spaces, since `-` is
+//! common in package names. `deps` and `rdeps` include their arguments, as in Bazel.
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow, bail};
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+
+/// Packages and the import dependencies between them.
+#[derive(Debug, Default)]
+pub struct PackageGraph {
+    /// Package names, sorted.
+    pub names: Vec<String>,
+    /// Number of source files per package.
+    pub files: Vec<usize>,
+    /// Packages each package imports from.
+    pub deps: Vec<BTreeSet<usize>>,
+    /// Packages importing from each package.
+    pub rdeps: Vec<BTreeSet<usize>>,
+}
+
+/// The package of every source file: its npm/yarn/pnpm workspace package, its Cargo crate
+/// when the project has several, and otherwise its `export --granularity package` group.
+pub fn file_packages(graph: &CodeGraph, project_root: &Path) -> HashMap<NodeIndex, String> {
+    // Workspace package roots, deepest first so nested packages win.
+    let mut workspace: Vec<(String, PathBuf)> =
+        crate::resolver::workspace::discover_workspace_packages(project_root)
+            .into_iter()
+            .map(|(name, dir)| {
+                let root = match dir.file_name() {
+                    Some(last) if last == "src" => dir.parent().map(Path::to_path_buf),
+                    _ => None,
+                };
+                (name, root.unwrap_or(dir))
+            })
+            .collect();
+    workspace.sort_by_key(|(_, dir)| std::cmp::Reverse(dir.components().count()));
+
+    let crates: HashSet<&str> = graph
+        .graph
+        .node_weights()
+        .filter_map(|n| match n {
+            GraphNode::File(fi) => fi.crate_name.as_deref(),
+            _ => None,
+        })
+        .collect();
+    let by_crate = crates.len() > 1;
+
+    let mut file_package: HashMap<NodeIndex, String> = HashMap::new();
+    for &idx in graph.file_index.values() {
+        let GraphNode::File(fi) = &graph.graph[idx] else {
+            continue;
+        };
+        if fi.kind != FileKind::Source {
+            continue;
+        }
+        let package = workspace
+            .iter()
+            .find(|(_, dir)| fi.path.starts_with(dir))
+            .map(|(name, _)| name.clone())
+            .or_else(|| fi.crate_name.clone().filter(|_| by_crate))
+            .or_else(|| crate::export::dot::package_of_file(graph, idx, project_root));
+        if let Some(package) = package {
+            file_package.insert(idx, package);
+        }
+    }
+    file_package
+}
+
+impl PackageGraph {
+    pub fn build(graph: &CodeGraph, project_root: &Path) -> Self {
+        let file_package = file_packages(graph, project_root);
+        let names: Vec<String> = file_package
+            .values()
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let position: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect();
+        let mut files = vec![0; names.len()];
+        for package in file_package.values() {
+            files[position[package.as_str()]] += 1;
+        }
+
+        let mut deps = vec![BTreeSet::new(); names.len()];
+        let mut rdeps = vec![BTreeSet::new(); names.len()];
+        for edge in graph.graph.edge_references() {
+            if !matches!(edge.weight(), EdgeKind::ResolvedImport { .. }) {
+                continue;
+            }
+            let (Some(src), Some(tgt)) = (
+                file_package.get(&edge.source()),
+                file_package.get(&edge.target()),
+            ) else {
+                continue;
+            };
+            let (src, tgt) = (position[src.as_str()], position[tgt.as_str()]);
+            if src != tgt {
+                deps[src].insert(tgt);
+                rdeps[tgt].insert(src);
+            }
+        }
+
+        Self {
+            names,
+            files,
+            deps,
+            rdeps,
+        }
+    }
+
+    /// Packages named `name`, or matching it as a glob pattern.
+    fn lookup(&self, name: &str) -> Result<Vec<usize>> {
+        if let Ok(i) = self.names.binary_search_by(|n| n.as_str().cmp(name)) {
+            return Ok(vec![i]);
+        }
+        if name.contains(['*', '?', '[']) {
+            let pattern = glob::Pattern::new(name)
+                .map_err(|e| anyhow!("invalid package pattern '{}': {}", name, e))?;
+            return Ok((0..self.names.len())
+                .filter(|&i| pattern.matches(&self.names[i]))
+                .collect());
+        }
+        bail!("unknown package '{}'", name)
+    }
+}
+
+/// One package of a query result.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PackageResult {
+    pub name: String,
+    pub files: usize,
+    /// Packages of the result that this package imports from.
+    pub deps: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    _ => Token::Comma,
+                });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut word = String::new();
+                let mut closed = false;
+                for ch in chars.by_ref() {
+                    if ch == c {
+                        closed = true;
+                        break;
+                    }
+                    word.push(ch);
+                }
+                if !closed {
+                    bail!("unterminated quote in query");
+                }
+                tokens.push(Token::Word(word));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || matches!(ch, '(' | ')' | ',') {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parsed query expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Name(String),
+    Deps(Box<Expr>, Option<usize>),
+    Rdeps(Box<Expr>, Option<usize>),
+    SomePath(Box<Expr>, Box<Expr>),
+    AllPaths(Box<Expr>, Box<Expr>),
+    Union(Box<Expr>, Box<Expr>),
+    Except(Box<Expr>, Box<Expr>),
+    Intersect(Box<Expr>, Box<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => bail!("expected {}", what),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut left = self.term()?;
+        while let Some(Token::Word(word)) = self.peek() {
+            let op = word.clone();
+            let combine: fn(Box<Expr>, Box<Expr>) -> Expr = match op.as_str() {
+                "+" | "union" => Expr::Union,
+                "-" | "except" => Expr::Except,
+                "^" | "intersect" => Expr::Intersect,
+                _ => bail!("unexpected '{}': expected an operator", op),
+            };
+            self.pos += 1;
+            let right = self.term()?;
+            left = combine(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Open) => {
+                let inner = self.expr()?;
+                self.expect(Token::Close, "')'")?;
+                Ok(inner)
+            }
+            Some(Token::Word(word)) if self.peek() == Some(&Token::Open) => {
+                self.pos += 1;
+                let expr = match word.as_str() {
+                    "deps" | "rdeps" => {
+                        let arg = Box::new(self.expr()?);
+                        let depth = self.depth()?;
+                        if word == "deps" {
+                            Expr::Deps(arg, depth)
+                        } else {
+                            Expr::Rdeps(arg, depth)
+                        }
+                    }
+                    "somepath" | "allpaths" => {
+                        let from = Box::new(self.expr()?);
+                        self.expect(Token::Comma, "',' between the path endpoints")?;
+                        let to = Box::new(self.expr()?);
+                        if word == "somepath" {
+                            Expr::SomePath(from, to)
+                        } else {
+                            Expr::AllPaths(from, to)
+                        }
+                    }
+                    _ => bail!(
+                        "unknown operator '{}' (expected deps, rdeps, somepath or allpaths)",
+                        word
+                    ),
+                };
+                self.expect(Token::Close, "')'")?;
+                Ok(expr)
+            }
+            Some(Token::Word(word)) => Ok(Expr::Name(word)),
+            Some(_) => bail!("expected a package name or operator"),
+            None => bail!("unexpected end of query"),
+        }
+    }
+
+    /// Optional `, N` depth argument.
+    fn depth(&mut self) -> Result<Option<usize>> {
+        if self.peek() != Some(&Token::Comma) {
+            return Ok(None);
+        }
+        self.pos += 1;
+        match self.next() {
+            Some(Token::Word(n)) => n
+                .parse()
+                .map(Some)
+                .map_err(|_| anyhow!("invalid depth '{}'", n)),
+            _ => bail!("expected a depth"),
+        }
+    }
+}
+
+fn parse(query: &str) -> Result<Expr> {
+    let mut parser = Parser {
+        tokens: tokenize(query)?,
+        pos: 0,
+    };
+    let expr = parser.expr()?;
+    if parser.pos < parser.tokens.len() {
+        bail!("unexpected trailing input in query");
+    }
+    Ok(expr)
+}
+
+/// Packages reachable from `start` along `edges` within `depth` steps, `start` included.
+fn reachable(start: &[usize], edges: &[BTreeSet<usize>], depth: Option<usize>) -> Vec<usize> {
+    let mut seen: BTreeSet<usize> = start.iter().copied().collect();
+    let mut queue: VecDeque<(usize, usize)> = start.iter().map(|&i| (i, 0)).collect();
+    while let Some((current, d)) = queue.pop_front() {
+        if depth.is_some_and(|max| d >= max) {
+            continue;
+        }
+        for &next in &edges[current] {
+            if seen.insert(next) {
+                queue.push_back((next, d + 1));
+            }
+        }
+    }
+    seen.into_iter().collect()
+}
+
+fn eval(expr: &Expr, graph: &PackageGraph) -> Result<Vec<usize>> {
+    let sorted = |set: BTreeSet<usize>| set.into_iter().collect::<Vec<_>>();
+    Ok(match expr {
+        Expr::Name(name) => graph.lookup(name)?,
+        Expr::Deps(arg, depth) => reachable(&eval(arg, graph)?, &graph.deps, *depth),
+        Expr::Rdeps(arg, depth) => reachable(&eval(arg, graph)?, &graph.rdeps, *depth),
+        Expr::SomePath(from, to) => {
+            let targets: HashSet<usize> = eval(to, graph)?.into_iter().collect();
+            let mut parent: HashMap<usize, Option<usize>> = HashMap::new();
+            let mut queue: VecDeque<usize> = VecDeque::new();
+            for start in eval(from, graph)? {
+                parent.insert(start, None);
+                queue.push_back(start);
+            }
+            let mut path = Vec::new();
+            while let Some(current) = queue.pop_front() {
+                if targets.contains(&current) {
+                    let mut node = Some(current);
+                    while let Some(n) = node {
+                        path.push(n);
+                        node = parent[&n];
+                    }
+                    path.reverse();
+                    break;
+                }
+                for &next in &graph.deps[current] {
+                    if let std::collections::hash_map::Entry::Vacant(e) = parent.entry(next) {
+                        e.insert(Some(current));
+                        queue.push_back(next);
+                    }
+                }
+            }
+            path
+        }
+        Expr::AllPaths(from, to) => {
+            let forward: BTreeSet<usize> = reachable(&eval(from, graph)?, &graph.deps, None)
+                .into_iter()
+                .collect();
+            let backward: BTreeSet<usize> = reachable(&eval(to, graph)?, &graph.rdeps, None)
+                .into_iter()
+                .collect();
+            sorted(&forward & &backward)
+        }
+        Expr::Union(a, b) => {
+            let set: BTreeSet<usize> = eval(a, graph)?.into_iter().chain(eval(b, graph)?).collect();
+            sorted(set)
+        }
+        Expr::Except(a, b) => {
+            let remove: HashSet<usize> = eval(b, graph)?.into_iter().collect();
+            eval(a, graph)?
+                .into_iter()
+                .filter(|i| !remove.contains(i))
+                .collect()
+        }
+        Expr::Intersect(a, b) => {
+            let keep: HashSet<usize> = eval(b, graph)?.into_iter().collect();
+            eval(a, graph)?
+                .into_iter()
+                .filter(|i| keep.contains(i))
+                .collect()
+        }
+    })
+}
+
+/// Run `query` against the package graph. `somepath` results are in path order; all other
+/// results are sorted by package name.
+pub fn run_query(graph: &PackageGraph, query: &str) -> Result<Vec<PackageResult>> {
+    let expr = parse(query)?;
+    let result = eval(&expr, graph)?;
+    let members: HashSet<usize> = result.iter().copied().collect();
+    Ok(result
+        .iter()
+        .map(|&i| PackageResult {
+            name: graph.names[i].clone(),
+            files: graph.files[i],
+            deps: graph.deps[i]
+                .iter()
+                .filter(|d| members.contains(d))
+                .map(|&d| graph.names[d].clone())
+                .collect(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `@app -> @ui -> @core`, `@app -> @api -> @core`, `@tools` on its own.
+    fn sample() -> PackageGraph {
+        let names = ["@api", "@app", "@core", "@tools", "@ui"];
+        let edges = [(1, 4), (4, 2), (1, 0), (0, 2)];
+        let mut graph = PackageGraph {
+            names: names.iter().map(|n| n.to_string()).collect(),
+            files: vec![1; names.len()],
+            deps: vec![BTreeSet::new(); names.len()],
+            rdeps: vec![BTreeSet::new(); names.len()],
+        };
+        for (a, b) in edges {
+            graph.deps[a].insert(b);
+            graph.rdeps[b].insert(a);
+        }
+        graph
+    }
+
+    fn names(graph: &PackageGraph, query: &str) -> Vec<String> {
+        run_query(graph, query)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_deps_and_rdeps() {
+        let graph = sample();
+        assert_eq!(names(&graph, "deps(@app, 1)"), ["@api", "@app", "@ui"]);
+        assert_eq!(
+            names(&graph, "deps(@app)"),
+            ["@api", "@app", "@core", "@ui"]
+        );
+        assert_eq!(names(&graph, "rdeps(@core, 1)"), ["@api", "@core", "@ui"]);
+        assert_eq!(names(&graph, "deps('@app') - @app ^ @ui"), ["@ui"]);
+        assert_eq!(names(&graph, "@t* union @ui"), ["@tools", "@ui"]);
+    }
+
+    #[test]
+    fn test_paths() {
+        let graph = sample();
+        assert_eq!(
+            names(&graph, "somepath(@app, @core)"),
+            ["@app", "@api", "@core"]
+        );
+        assert_eq!(
+            names(&graph, "allpaths(@app, @core)"),
+            ["@api", "@app", "@core", "@ui"]
+        );
+        assert!(names(&graph, "somepath(@core, @app)").is_empty());
+        let result = run_query(&graph, "allpaths(@ui, @core)").unwrap();
+        assert_eq!(result[1].deps, vec!["@core"]);
+    }
+
+    #[test]
+    fn test_query_errors() {
+        let graph = sample();
+        for (query, error) in [
+            ("deps(@nope)", "unknown package '@nope'"),
+            ("depz(@app)", "unknown operator 'depz'"),
+            ("deps(@app, x)", "invalid depth 'x'"),
+            ("@app @ui", "unexpected '@ui'"),
+            ("somepath(@app)", "expected ','"),
+        ] {
+            let err = run_query(&graph, query).unwrap_err().to_string();
+            assert!(err.contains(error), "{query}: {err}");
+        }
+    }
+}
@@ -1,19 +1,45 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use petgraph::Direction;
 use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 
-use crate::graph::{CodeGraph, edge::EdgeKind, node::GraphNode};
+use crate::graph::{
+    CodeGraph,
+    edge::EdgeKind,
+    node::{GraphNode, SymbolKind},
+};
+use crate::query::find::FindResult;
+use crate::query::util::{imports_any_of, innermost_symbol_at, top_level_symbol};
 
 /// The kind of reference a file or symbol has to the queried symbol.
 #[derive(Debug, Clone)]
 pub enum RefKind {
-    /// A file imports the file that defines the symbol (via a ResolvedImport edge).
+    /// A file imports the file that defines the symbol (via a ResolvedImport edge), and
+    /// either binds the symbol by name or has no named import from that file.
     Import,
     /// A symbol calls the queried symbol (via a Calls edge).
     Call,
+    /// A component renders the queried component as a JSX element (via a Renders edge).
+    Render,
+    /// A variable, const, or static is read at this line.
+    Read,
+    /// A variable, const, or static (or a field or element of it) is assigned or updated
+    /// at this line.
+    Write,
+}
+
+impl RefKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RefKind::Import => "import",
+            RefKind::Call => "call",
+            RefKind::Render => "render",
+            RefKind::Read => "read",
+            RefKind::Write => "write",
+        }
+    }
 }
 
 /// A single reference result to a queried symbol.
@@ -21,19 +47,38 @@ pub enum RefKind {
 pub struct RefResult {
     /// Absolute path of the file that contains the reference.
     pub file_path: PathBuf,
-    /// Whether the reference is an import or a call.
+    /// Whether the reference is an import, a call, a render, a read, or a write.
     pub ref_kind: RefKind,
-    /// Caller symbol name (only for `RefKind::Call` references).
+    /// Caller symbol name (for all but `RefKind::Import` references; the enclosing symbol
+    /// for reads, writes, and calls of a variable).
     pub symbol_name: Option<String>,
-    /// 1-based line of the caller symbol (only for `RefKind::Call` references).
+    /// 1-based line of the import statement (for `RefKind::Import` references, when the
+    /// parser recorded it), of the caller symbol (for `RefKind::Call` and `RefKind::Render`
+    /// references from a symbol), of the call itself (for file-level calls), or of the
+    /// access itself for reads, writes, and calls of a variable.
     pub line: Option<usize>,
+    /// 1-based column of the import statement, for `RefKind::Import` references whose
+    /// parser recorded it.
+    pub col: Option<usize>,
+    /// The caller is a method a derive macro generated (see [`SymbolInfo::derived`]).
+    ///
+    /// [`SymbolInfo::derived`]: crate::graph::node::SymbolInfo::derived
+    pub derived: bool,
 }
 
 /// Find all files and symbols that reference any of the given symbol node indices.
 ///
-/// Produces two classes of results:
+/// Produces four classes of results:
 /// - **Import refs**: files that have a `ResolvedImport` edge to the file containing the symbol.
-/// - **Call refs**: symbol nodes that have a `Calls` edge to the queried symbol.
+///   Files whose named imports from that file (`ImportsSymbol` edges) all bind other symbols
+///   are left out.
+/// - **Call refs**: symbol nodes that have a `Calls` edge to the queried symbol, and one ref
+///   per recorded call line of a file-level `Calls` edge.
+/// - **Render refs**: components that render the queried component (`Renders` edges).
+/// - **Access refs**: for variables, consts, and statics, every use in the defining and
+///   importing files, classified as a read, a write (assignment, compound assignment,
+///   increment, or assignment to a field or element), or a call. Uses are matched by
+///   name in the re-parsed source, so a local that shadows the name counts too.
 ///
 /// Results are sorted by file path for deterministic output.
 pub fn find_refs(
@@ -52,6 +97,12 @@ pub fn find_refs(
         }
     }
 
+    // Named imports bind top-level symbols, so a queried method counts through its class.
+    let bound_symbols: HashSet<NodeIndex> = symbol_indices
+        .iter()
+        .map(|&sym_idx| top_level_symbol(graph, sym_idx))
+        .collect();
+
     let mut results: Vec<RefResult> = Vec::new();
     let mut import_ref_files_seen: HashSet<NodeIndex> = HashSet::new();
 
@@ -62,57 +113,151 @@ pub fn find_refs(
             continue;
         }
 
-        let mut found_import = false;
+        // The earliest known import statement site among the matching edges, (0, 0) if none.
+        // A statement binding the symbol by name is preferred over the file import, which
+        // only keeps the first of several statements with the same specifier.
+        let earliest = |best: Option<(usize, usize)>, site: (usize, usize)| {
+            Some(best.map_or(site, |best| {
+                std::cmp::min_by_key(best, site, |s| (s.0 == 0, *s))
+            }))
+        };
+        let mut found_import: Option<(usize, usize)> = None;
+        let mut binding_site: Option<(usize, usize)> = None;
         for edge_ref in graph.graph.edges_directed(file_idx, Direction::Outgoing) {
-            if matches!(edge_ref.weight(), EdgeKind::ResolvedImport { .. }) {
-                let target = edge_ref.target();
-                if defining_files.contains(&target) {
-                    found_import = true;
-                    break;
+            match edge_ref.weight() {
+                EdgeKind::ResolvedImport { line, col, .. } => {
+                    let target = edge_ref.target();
+                    if defining_files.contains(&target)
+                        && imports_any_of(graph, file_idx, target, &bound_symbols)
+                    {
+                        found_import = earliest(found_import, (*line, *col));
+                    }
+                }
+                EdgeKind::ImportsSymbol { line, col }
+                    if *line > 0 && bound_symbols.contains(&edge_ref.target()) =>
+                {
+                    binding_site = earliest(binding_site, (*line, *col));
                 }
+                _ => {}
             }
         }
 
-        if found_import && !import_ref_files_seen.contains(&file_idx) {
-            import_ref_files_seen.insert(file_idx);
-            if let GraphNode::File(ref fi) = graph.graph[file_idx] {
-                results.push(RefResult {
-                    file_path: fi.path.clone(),
-                    ref_kind: RefKind::Import,
-                    symbol_name: None,
-                    line: None,
-                });
-            }
+        if let Some((line, col)) = found_import.map(|site| binding_site.unwrap_or(site))
+            && import_ref_files_seen.insert(file_idx)
+            && let GraphNode::File(ref fi) = graph.graph[file_idx]
+        {
+            results.push(RefResult {
+                file_path: fi.path.clone(),
+                ref_kind: RefKind::Import,
+                symbol_name: None,
+                line: (line > 0).then_some(line),
+                col: (col > 0).then_some(col),
+                derived: false,
+            });
         }
     }
 
-    // Step 3: Call references — symbols with a Calls edge pointing to the queried symbols.
+    // Step 3: Call and render references — symbols with a Calls or Renders edge pointing
+    // to the queried symbols.
     for &sym_idx in symbol_indices {
         for edge_ref in graph.graph.edges_directed(sym_idx, Direction::Incoming) {
-            if matches!(edge_ref.weight(), EdgeKind::Calls) {
-                let caller_idx = edge_ref.source();
-                // The caller can be a Symbol node or a File node (for file-level calls).
-                let (caller_name, caller_line, file_path) = match &graph.graph[caller_idx] {
-                    GraphNode::Symbol(info) => {
-                        // Find the file containing the caller symbol.
-                        let fp = find_file_path_of_node(graph, caller_idx);
-                        (Some(info.name.clone()), Some(info.line), fp)
+            let ref_kind = match edge_ref.weight() {
+                EdgeKind::Calls { .. } => RefKind::Call,
+                EdgeKind::Renders => RefKind::Render,
+                _ => continue,
+            };
+            let caller_idx = edge_ref.source();
+            // The caller can be a Symbol node or a File node (for file-level calls).
+            match &graph.graph[caller_idx] {
+                GraphNode::Symbol(info) => {
+                    // Find the file containing the caller symbol.
+                    if let Some(fp) = find_file_path_of_node(graph, caller_idx) {
+                        results.push(RefResult {
+                            file_path: fp,
+                            ref_kind,
+                            symbol_name: Some(info.name.clone()),
+                            line: Some(info.line),
+                            col: None,
+                            derived: info.derived.is_some(),
+                        });
+                    }
+                }
+                GraphNode::File(fi) => {
+                    // A file-level Calls edge (the resolver adds these for unscoped calls):
+                    // one reference per recorded call line, attributed to the innermost
+                    // symbol spanning it.
+                    let lines = match edge_ref.weight() {
+                        EdgeKind::Calls { lines, .. } => lines.as_slice(),
+                        _ => &[],
+                    };
+                    if lines.is_empty() {
+                        results.push(RefResult {
+                            file_path: fi.path.clone(),
+                            ref_kind: ref_kind.clone(),
+                            symbol_name: None,
+                            line: None,
+                            col: None,
+                            derived: false,
+                        });
                     }
-                    GraphNode::File(fi) => {
-                        // A file-level Calls edge (resolver adds these for unscoped calls).
-                        (None, None, Some(fi.path.clone()))
+                    for &line in lines {
+                        let enclosing = innermost_symbol_at(graph, caller_idx, line).and_then(
+                            |idx| match &graph.graph[idx] {
+                                GraphNode::Symbol(s) => Some(s),
+                                _ => None,
+                            },
+                        );
+                        results.push(RefResult {
+                            file_path: fi.path.clone(),
+                            ref_kind: ref_kind.clone(),
+                            symbol_name: enclosing.map(|s| s.name.clone()),
+                            line: Some(line),
+                            col: None,
+                            derived: enclosing.is_some_and(|s| s.derived.is_some()),
+                        });
                     }
-                    _ => continue,
-                };
-
-                if let Some(fp) = file_path {
-                    results.push(RefResult {
-                        file_path: fp,
-                        ref_kind: RefKind::Call,
-                        symbol_name: caller_name,
-                        line: caller_line,
-                    });
                 }
+                _ => continue,
+            }
+        }
+    }
+
+    // Step 4: Access references — reads, writes, and calls of variable-like symbols.
+    for &sym_idx in symbol_indices {
+        let GraphNode::Symbol(info) = &graph.graph[sym_idx] else {
+            continue;
+        };
+        if !matches!(
+            info.kind,
+            SymbolKind::Variable | SymbolKind::Const | SymbolKind::Static
+        ) {
+            continue;
+        }
+        let Some(defining) = find_containing_file_idx(graph, sym_idx) else {
+            continue;
+        };
+        for file_idx in std::iter::once(defining).chain(import_ref_files_seen.iter().copied()) {
+            let GraphNode::File(fi) = &graph.graph[file_idx] else {
+                continue;
+            };
+            for (line, ref_kind) in access_sites(&fi.path, &info.name) {
+                // The declaration itself is not a use.
+                if file_idx == defining && line == info.line {
+                    continue;
+                }
+                results.push(RefResult {
+                    file_path: fi.path.clone(),
+                    ref_kind,
+                    symbol_name: innermost_symbol_at(graph, file_idx, line).and_then(|idx| {
+                        match &graph.graph[idx] {
+                            GraphNode::Symbol(s) => Some(s.name.clone()),
+                            _ => None,
+                        }
+                    }),
+                    line: Some(line),
+                    col: None,
+                    derived: false,
+                });
             }
         }
     }
@@ -127,6 +272,126 @@ pub fn find_refs(
 // Private helpers
 // ---------------------------------------------------------------------------
 
+/// Assignment nodes across the supported grammars; the target is the first child.
+const ASSIGNMENT_KINDS: &[&str] = &[
+    "assignment_expression",
+    "augmented_assignment_expression",
+    "compound_assignment_expr",
+    "assignment",
+    "augmented_assignment",
+    "assignment_statement",
+];
+
+/// Increment and decrement nodes: `x++`, `x--` (TS/JS, Go).
+const UPDATE_KINDS: &[&str] = &["update_expression", "inc_statement", "dec_statement"];
+
+/// Field and element access nodes; the accessed value is the first named child.
+const ACCESS_KINDS: &[&str] = &[
+    "member_expression",
+    "subscript_expression",
+    "field_expression",
+    "index_expression",
+    "attribute",
+    "subscript",
+    "selector_expression",
+];
+
+/// Statements whose identifiers name imports, not uses.
+const IMPORT_KINDS: &[&str] = &[
+    "import_statement",
+    "import_from_statement",
+    "import_declaration",
+    "use_declaration",
+    "export_specifier",
+];
+
+/// Classify one identifier occurrence as a read, a write, or a call. `None` when the
+/// identifier is a field name or part of an import.
+fn classify_access(node: tree_sitter::Node) -> Option<RefKind> {
+    let mut current = node;
+    let mut through_access = false;
+    let parent = loop {
+        let parent = current.parent()?;
+        let kind = parent.kind();
+        if ACCESS_KINDS.contains(&kind) {
+            // `obj.name`: only the accessed value is a use of the name.
+            if parent.named_child(0) != Some(current) {
+                return None;
+            }
+            through_access = true;
+        } else if !matches!(
+            kind,
+            "scoped_identifier" | "parenthesized_expression" | "expression_list" | "pattern_list"
+        ) {
+            break parent;
+        }
+        current = parent;
+    };
+
+    let mut ancestor = Some(parent);
+    while let Some(a) = ancestor {
+        if IMPORT_KINDS.contains(&a.kind()) {
+            return None;
+        }
+        ancestor = a.parent();
+    }
+
+    let kind = parent.kind();
+    if ASSIGNMENT_KINDS.contains(&kind) && parent.named_child(0) == Some(current) {
+        return Some(RefKind::Write);
+    }
+    if UPDATE_KINDS.contains(&kind) {
+        return Some(RefKind::Write);
+    }
+    if matches!(kind, "call_expression" | "call")
+        && !through_access
+        && parent.child_by_field_name("function") == Some(current)
+    {
+        return Some(RefKind::Call);
+    }
+    Some(RefKind::Read)
+}
+
+/// Read, write, and call sites of the identifier `name` in the file at `path`, as
+/// `(line, kind)` pairs. Empty for files that cannot be read or languages without a
+/// tree-sitter grammar here.
+fn access_sites(path: &Path, name: &str) -> Vec<(usize, RefKind)> {
+    let Some(language) = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(crate::parser::languages::language_for_extension)
+    else {
+        return Vec::new();
+    };
+    let Ok(source) = std::fs::read(path) else {
+        return Vec::new();
+    };
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&language).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(&source, None) else {
+        return Vec::new();
+    };
+
+    let mut sites = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "identifier" | "shorthand_property_identifier")
+            && node.utf8_text(&source).is_ok_and(|t| t == name)
+        {
+            if let Some(kind) = classify_access(node) {
+                sites.push((node.start_position().row + 1, kind));
+            }
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    sites.sort_by_key(|(line, _)| *line);
+    sites
+}
+
 /// Return the NodeIndex of the File node that contains `sym_idx` via a Contains edge.
 fn find_containing_file_idx(graph: &CodeGraph, sym_idx: NodeIndex) -> Option<NodeIndex> {
     // Direct Contains edge: File -> Symbol.
@@ -152,6 +417,32 @@ fn find_containing_file_idx(graph: &CodeGraph, sym_idx: NodeIndex) -> Option<Nod
     None
 }
 
+/// Number of references ([`find_refs`]) to each definition in `results`, keyed by its
+/// file and line, for `find --sort refs`.
+pub fn reference_counts(
+    graph: &CodeGraph,
+    results: &[FindResult],
+    project_root: &Path,
+) -> HashMap<(PathBuf, usize), usize> {
+    let mut counts = HashMap::new();
+    for r in results {
+        let indices: Vec<NodeIndex> = graph
+            .symbol_index
+            .get(&r.symbol_name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&idx| {
+                matches!(&graph.graph[idx], GraphNode::Symbol(info) if info.line == r.line)
+                    && find_file_path_of_node(graph, idx).as_ref() == Some(&r.file_path)
+            })
+            .collect();
+        let count = find_refs(graph, &r.symbol_name, &indices, project_root).len();
+        counts.insert((r.file_path.clone(), r.line), count);
+    }
+    counts
+}
+
 /// Return the file path of a node (Symbol or File) by walking Contains edges.
 fn find_file_path_of_node(graph: &CodeGraph, node_idx: NodeIndex) -> Option<PathBuf> {
     match &graph.graph[node_idx] {
@@ -247,6 +538,70 @@ mod tests {
         assert!(!has_unrelated, "unrelated.ts should NOT appear in refs");
     }
 
+    #[test]
+    fn test_import_binding_narrows_import_refs() {
+        let (mut graph, root, foo_sym) = graph_with_import_ref();
+        let defining = graph.file_index[&root.join("defining.ts")];
+        let bar_sym = graph.add_symbol(
+            defining,
+            SymbolInfo {
+                name: "bar".into(),
+                kind: SymbolKind::Function,
+                line: 5,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        // importer.ts only binds `bar` (`import { bar } from './defining'`).
+        let importer = graph.file_index[&root.join("importer.ts")];
+        graph.add_imports_symbol(importer, bar_sym, 0, 0);
+
+        let foo_refs = find_refs(&graph, "foo", &[foo_sym], &root);
+        assert!(
+            foo_refs
+                .iter()
+                .all(|r| !r.file_path.ends_with("importer.ts")),
+            "importer.ts does not import foo"
+        );
+        let bar_refs = find_refs(&graph, "bar", &[bar_sym], &root);
+        assert!(
+            bar_refs
+                .iter()
+                .any(|r| r.file_path.ends_with("importer.ts"))
+        );
+    }
+
+    #[test]
+    fn test_import_ref_reports_the_binding_statement() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let other = graph.add_file(root.join("other.ts"), "typescript");
+        let symbol = |name: &str, line: usize| SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line,
+            is_exported: true,
+            ..Default::default()
+        };
+        let a_sym = graph.add_symbol(other, symbol("a", 1));
+        let other_sym = graph.add_symbol(other, symbol("other", 2));
+        // `import { a } from './other';` then `import { other } from './other';`: both
+        // statements collapse into one file import edge at the first.
+        let index = graph.add_file(root.join("index.ts"), "typescript");
+        graph.add_import_at(index, other, "./other", false, 1, 1);
+        graph.add_import_at(index, other, "./other", false, 2, 1);
+        graph.add_imports_symbol(index, a_sym, 1, 1);
+        graph.add_imports_symbol(index, other_sym, 2, 1);
+
+        let line_of = |sym| {
+            let refs = find_refs(&graph, "", &[sym], &root);
+            assert_eq!(refs.len(), 1);
+            (refs[0].line, refs[0].col)
+        };
+        assert_eq!(line_of(a_sym), (Some(1), Some(1)));
+        assert_eq!(line_of(other_sym), (Some(2), Some(1)));
+    }
+
     #[test]
     fn test_call_edge_produces_call_ref() {
         let root = PathBuf::from("/proj");
@@ -276,7 +631,7 @@ mod tests {
                 ..Default::default()
             },
         );
-        graph.add_calls_edge(bar_sym, foo_sym);
+        graph.add_call_site(bar_sym, foo_sym, 1);
 
         let results = find_refs(&graph, "foo", &[foo_sym], &root);
         let call_refs: Vec<_> = results
@@ -290,6 +645,44 @@ mod tests {
         assert!(call_refs[0].file_path.ends_with("caller.ts"));
     }
 
+    #[test]
+    fn test_file_level_calls_produce_one_ref_per_line() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let defining = graph.add_file(root.join("src/graph.rs"), "rust");
+        let build = graph.add_symbol(
+            defining,
+            SymbolInfo {
+                name: "build_graph".into(),
+                kind: SymbolKind::Function,
+                line: 1,
+                ..Default::default()
+            },
+        );
+        let caller_file = graph.add_file(root.join("src/main.rs"), "rust");
+        graph.add_symbol(
+            caller_file,
+            SymbolInfo {
+                name: "run".into(),
+                kind: SymbolKind::Function,
+                line: 10,
+                line_end: 20,
+                ..Default::default()
+            },
+        );
+        // The Rust resolver records calls from the file, at each call line.
+        graph.add_call_site(caller_file, build, 12);
+        graph.add_call_site(caller_file, build, 30);
+
+        let results = find_refs(&graph, "build_graph", &[build], &root);
+        let calls: Vec<(Option<&str>, Option<usize>)> = results
+            .iter()
+            .filter(|r| matches!(r.ref_kind, RefKind::Call))
+            .map(|r| (r.symbol_name.as_deref(), r.line))
+            .collect();
+        assert_eq!(calls, vec![(Some("run"), Some(12)), (None, Some(30))]);
+    }
+
     #[test]
     fn test_defining_file_excluded_from_import_refs() {
         let (graph, root, foo_sym) = graph_with_import_ref();
@@ -346,4 +739,114 @@ mod tests {
             "multiple edges to same file => deduplicated to one import ref"
         );
     }
+
+    #[test]
+    fn test_import_ref_reports_earliest_import_site() {
+        let (mut graph, root, foo_sym) = graph_with_import_ref();
+        let importer = graph.file_index[&root.join("importer.ts")];
+        let defining = graph.file_index[&root.join("defining.ts")];
+        graph.add_import_at(importer, defining, "./defining", false, 9, 1);
+        graph.add_import_at(importer, defining, "./defining", false, 4, 3);
+        graph.add_import_at(importer, defining, "./defining.ts", true, 7, 1);
+
+        let results = find_refs(&graph, "foo", &[foo_sym], &root);
+        let import_ref = results
+            .iter()
+            .find(|r| matches!(r.ref_kind, RefKind::Import))
+            .unwrap();
+        assert_eq!((import_ref.line, import_ref.col), (Some(4), Some(3)));
+    }
+
+    #[test]
+    fn test_variable_access_sites_classified() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::write(
+            root.join("config.ts"),
+            "export let config = { debug: false };\n\
+             export function reset() {\n  config = { debug: false };\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("app.ts"),
+            "import { config } from './config';\n\
+             function boot() {\n  config.debug = true;\n  return config.debug;\n}\n\
+             config.hooks();\nconfig();\nother.config = 1;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("counter.py"),
+            "count = 0\ncount += 1\nprint(count)\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let config_file = graph.add_file(root.join("config.ts"), "typescript");
+        let config = graph.add_symbol(
+            config_file,
+            SymbolInfo {
+                name: "config".into(),
+                kind: SymbolKind::Variable,
+                line: 1,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        graph.add_symbol(
+            config_file,
+            SymbolInfo {
+                name: "reset".into(),
+                kind: SymbolKind::Function,
+                line: 2,
+                line_end: 4,
+                ..Default::default()
+            },
+        );
+        let app = graph.add_file(root.join("app.ts"), "typescript");
+        graph.add_resolved_import(app, config_file, "./config");
+        graph.add_symbol(
+            app,
+            SymbolInfo {
+                name: "boot".into(),
+                kind: SymbolKind::Function,
+                line: 2,
+                line_end: 5,
+                ..Default::default()
+            },
+        );
+
+        let results = find_refs(&graph, "config", &[config], &root);
+        let accesses: Vec<(String, usize, &str, Option<&str>)> = results
+            .iter()
+            .filter(|r| !matches!(r.ref_kind, RefKind::Import))
+            .map(|r| {
+                (
+                    r.file_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned(),
+                    r.line.unwrap(),
+                    r.ref_kind.as_str(),
+                    r.symbol_name.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            accesses,
+            vec![
+                ("app.ts".to_string(), 3, "write", Some("boot")),
+                ("app.ts".to_string(), 4, "read", Some("boot")),
+                ("app.ts".to_string(), 6, "read", None),
+                ("app.ts".to_string(), 7, "call", None),
+                ("config.ts".to_string(), 3, "write", Some("reset")),
+            ]
+        );
+
+        let py: Vec<(usize, &str)> = access_sites(&root.join("counter.py"), "count")
+            .into_iter()
+            .map(|(line, kind)| (line, kind.as_str()))
+            .collect();
+        assert_eq!(py, vec![(1, "write"), (2, "write"), (3, "read")]);
+    }
 }
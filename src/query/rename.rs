@@ -7,15 +7,42 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use petgraph::Direction;
 use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
 
-use crate::graph::{CodeGraph, node::GraphNode};
-use crate::query::refs::find_refs;
+use crate::graph::{CodeGraph, edge::EdgeKind, node::GraphNode};
+use crate::query::refs::{RefKind, find_refs};
 
 // ---------------------------------------------------------------------------
 // Data structures
 // ---------------------------------------------------------------------------
 
+/// The role of a rename site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RenameSiteKind {
+    /// Where the symbol is declared.
+    Definition,
+    /// A call, render, read, or write of the symbol.
+    Reference,
+    /// An import (or `use`) naming the symbol or its file.
+    Import,
+    /// A re-export: `export { Foo } from`, `export { Foo as Bar }`, `pub use x::Foo`.
+    ReExport,
+}
+
+impl RenameSiteKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RenameSiteKind::Definition => "definition",
+            RenameSiteKind::Reference => "reference",
+            RenameSiteKind::Import => "import",
+            RenameSiteKind::ReExport => "re-export",
+        }
+    }
+}
+
 /// A single site that must be updated during a rename operation.
 ///
 /// `plan_rename` returns one item per definition site and one per reference site.
@@ -24,6 +51,8 @@ use crate::query::refs::find_refs;
 pub struct RenameItem {
     /// Absolute path of the file containing this occurrence.
     pub file_path: PathBuf,
+    /// What the site is: definition, reference, import, or re-export.
+    pub kind: RenameSiteKind,
     /// 1-based line number of the occurrence. 0 means the exact line is unknown (import site).
     pub line: usize,
     /// The current text to be replaced (the old symbol name).
@@ -34,6 +63,14 @@ pub struct RenameItem {
     pub note: Option<String>,
 }
 
+/// The rename sites of one file, for `rename --dry-run`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RenameFile {
+    pub file_path: PathBuf,
+    /// Sites in line order.
+    pub sites: Vec<RenameItem>,
+}
+
 // ---------------------------------------------------------------------------
 // Entry point
 // ---------------------------------------------------------------------------
@@ -42,7 +79,9 @@ pub struct RenameItem {
 ///
 /// Returns one `RenameItem` per:
 /// - Definition site (from symbol_index)
-/// - Reference site: call sites (with line) and import sites (line=0, note added)
+/// - Call site: every line a `Calls` edge records
+/// - Other reference site: import sites (line=0 and a note when the file cannot be read),
+///   renders, reads, and writes
 ///
 /// Items are deduplicated by (file_path, line) and sorted by file_path then line.
 pub fn plan_rename(
@@ -76,6 +115,7 @@ pub fn plan_rename(
         if seen.insert(key) {
             items.push(RenameItem {
                 file_path,
+                kind: RenameSiteKind::Definition,
                 line: info.line,
                 old_text: symbol.to_string(),
                 new_text: new_name.to_string(),
@@ -84,10 +124,89 @@ pub fn plan_rename(
         }
     }
 
-    // Step 2: Reference sites (calls + import refs) via find_refs.
+    // Step 2: Call sites, one per line a `Calls` edge records. An edge without lines
+    // falls back to the code lines naming the symbol in the caller (its span, or the
+    // whole file for a file-level caller); lines where the name only appears in a comment
+    // or a string are skipped.
+    let mut sites: Vec<(PathBuf, usize)> = Vec::new();
+    for &sym_idx in &indices {
+        for edge in graph.graph.edges_directed(sym_idx, Direction::Incoming) {
+            let EdgeKind::Calls { lines, .. } = edge.weight() else {
+                continue;
+            };
+            let caller = edge.source();
+            let Some(file_path) =
+                find_containing_file_path(graph, caller).or_else(|| match &graph.graph[caller] {
+                    GraphNode::File(fi) => Some(fi.path.clone()),
+                    _ => None,
+                })
+            else {
+                continue;
+            };
+            let code = code_word_lines(&file_path, symbol);
+            if lines.is_empty() {
+                let span = match &graph.graph[caller] {
+                    GraphNode::Symbol(info) => info.line..=info.line_end.max(info.line),
+                    _ => 1..=usize::MAX,
+                };
+                for (line, text) in code.into_iter().flatten() {
+                    if span.contains(&line) && !is_import_statement(&text) {
+                        sites.push((file_path.clone(), line));
+                    }
+                }
+            } else {
+                for &line in lines {
+                    // An unreadable file cannot be checked, so its recorded lines stand.
+                    let names_symbol = code
+                        .as_ref()
+                        .is_none_or(|code| code.iter().any(|(l, _)| *l == line));
+                    if names_symbol {
+                        sites.push((file_path.clone(), line));
+                    }
+                }
+            }
+        }
+    }
+    for (file_path, line) in sites {
+        if seen.insert((file_path.clone(), line)) {
+            items.push(RenameItem {
+                file_path,
+                kind: RenameSiteKind::Reference,
+                line,
+                old_text: symbol.to_string(),
+                new_text: new_name.to_string(),
+                note: None,
+            });
+        }
+    }
+
+    // Step 3: Other reference sites (imports, renders, accesses) via find_refs. Import
+    // refs carry no line; it is read from the file, where an `export` or `pub use`
+    // statement makes an import site a re-export. An importing file that never names the
+    // symbol has nothing to rename.
     let refs = find_refs(graph, symbol, &indices, root);
     for r in refs {
-        let line = r.line.unwrap_or(0);
+        let (kind, line) = match (&r.ref_kind, r.line) {
+            (RefKind::Call, _) => continue,
+            (RefKind::Import, _) => match code_word_lines(&r.file_path, symbol) {
+                Some(lines) => match lines.into_iter().next() {
+                    Some((line, text)) => (statement_kind(&text), line),
+                    None => continue,
+                },
+                None => (RenameSiteKind::Import, 0),
+            },
+            (_, Some(line)) => (RenameSiteKind::Reference, line),
+            (_, None) => {
+                let line = code_word_lines(&r.file_path, symbol)
+                    .and_then(|lines| {
+                        lines
+                            .into_iter()
+                            .find(|(_, text)| !is_import_statement(text))
+                    })
+                    .map_or(0, |(line, _)| line);
+                (RenameSiteKind::Reference, line)
+            }
+        };
         let key = (r.file_path.clone(), line);
         if seen.insert(key) {
             let note = if line == 0 {
@@ -97,6 +216,7 @@ pub fn plan_rename(
             };
             items.push(RenameItem {
                 file_path: r.file_path,
+                kind,
                 line,
                 old_text: symbol.to_string(),
                 new_text: new_name.to_string(),
@@ -105,16 +225,153 @@ pub fn plan_rename(
         }
     }
 
-    // Step 3: Sort by file_path then line.
+    // Step 4: Re-exports under an alias (`ExportsAs`) and Rust `pub use` paths naming the
+    // symbol.
+    let mut reexporters: Vec<PathBuf> = indices
+        .iter()
+        .flat_map(|&idx| graph.graph.edges_directed(idx, Direction::Incoming))
+        .filter(|e| matches!(e.weight(), EdgeKind::ExportsAs { .. }))
+        .filter_map(|e| match &graph.graph[e.source()] {
+            GraphNode::File(fi) => Some(fi.path.clone()),
+            _ => None,
+        })
+        .collect();
+    for (path, &file_idx) in &graph.file_index {
+        let names_symbol = graph
+            .graph
+            .edges_directed(file_idx, Direction::Outgoing)
+            .any(|e| matches!(e.weight(), EdgeKind::ReExport { path } if has_word(path, symbol)));
+        if names_symbol {
+            reexporters.push(path.clone());
+        }
+    }
+    for file_path in reexporters {
+        let Some((line, _)) =
+            code_word_lines(&file_path, symbol).and_then(|l| l.into_iter().next())
+        else {
+            continue;
+        };
+        if seen.insert((file_path.clone(), line)) {
+            items.push(RenameItem {
+                file_path,
+                kind: RenameSiteKind::ReExport,
+                line,
+                old_text: symbol.to_string(),
+                new_text: new_name.to_string(),
+                note: None,
+            });
+        }
+    }
+
+    // Step 5: Sort by file_path then line.
     items.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
 
     items
 }
 
+/// Group a rename plan by file, for `rename --dry-run`. Files keep the plan's path order.
+pub fn group_by_file(items: &[RenameItem]) -> Vec<RenameFile> {
+    let mut files: Vec<RenameFile> = Vec::new();
+    for item in items {
+        match files.last_mut() {
+            Some(file) if file.file_path == item.file_path => file.sites.push(item.clone()),
+            _ => files.push(RenameFile {
+                file_path: item.file_path.clone(),
+                sites: vec![item.clone()],
+            }),
+        }
+    }
+    files
+}
+
 // ---------------------------------------------------------------------------
 // Private helpers
 // ---------------------------------------------------------------------------
 
+/// `true` when `name` occurs in `text` as a whole identifier.
+fn has_word(text: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    text.match_indices(name).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+/// The 1-based lines of the file at `path` naming `name` as a whole identifier outside
+/// comments and string literals, with their text. `None` when the file cannot be read.
+fn code_word_lines(path: &Path, name: &str) -> Option<Vec<(usize, String)>> {
+    let source = std::fs::read_to_string(path).ok()?;
+    Some(
+        source
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| has_word(&code_text(line), name))
+            .map(|(i, line)| (i + 1, line.to_string()))
+            .collect(),
+    )
+}
+
+/// The code of one source line, with `//` and `#` comments, comment continuation lines
+/// (`/*`, ` *`), and the contents of `"..."` and `` `...` `` string literals removed.
+fn code_text(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("/*")
+        || trimmed.starts_with('*')
+        || (trimmed.starts_with('#') && !trimmed.starts_with("#["))
+    {
+        return String::new();
+    }
+    let mut code = String::with_capacity(line.len());
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    chars.next();
+                } else if c == q {
+                    quote = None;
+                    code.push(c);
+                }
+            }
+            None if c == '"' || c == '`' => {
+                quote = Some(c);
+                code.push(c);
+            }
+            None if c == '/' && chars.peek() == Some(&'/') => break,
+            None => code.push(c),
+        }
+    }
+    code
+}
+
+/// `true` when `line` starts an import, `use`, or re-export statement.
+fn is_import_statement(line: &str) -> bool {
+    let line = line.trim_start();
+    [
+        "import ",
+        "from ",
+        "use ",
+        "export {",
+        "export *",
+        "pub use ",
+        "pub(crate) use ",
+    ]
+    .iter()
+    .any(|prefix| line.starts_with(prefix))
+}
+
+/// Whether the statement on `line` re-exports (`export ... from`, `pub use`) or imports.
+fn statement_kind(line: &str) -> RenameSiteKind {
+    let line = line.trim_start();
+    if line.starts_with("export ") || line.starts_with("pub use ") || line.starts_with("pub(") {
+        RenameSiteKind::ReExport
+    } else {
+        RenameSiteKind::Import
+    }
+}
+
 /// Find the file path containing a symbol node, using the shared utility.
 fn find_containing_file_path(graph: &CodeGraph, sym_idx: NodeIndex) -> Option<PathBuf> {
     let file_idx = super::util::find_containing_file_idx(graph, sym_idx)?;
@@ -168,7 +425,7 @@ mod tests {
                 ..Default::default()
             },
         );
-        g.add_calls_edge(bar_sym, foo_sym);
+        g.add_call_site(bar_sym, foo_sym, 1);
 
         let caller2 = g.add_file(r.join("src/baz.rs"), "rust");
         let baz_sym = g.add_symbol(
@@ -180,7 +437,7 @@ mod tests {
                 ..Default::default()
             },
         );
-        g.add_calls_edge(baz_sym, foo_sym);
+        g.add_call_site(baz_sym, foo_sym, 1);
 
         let items = plan_rename(&g, "Foo", "Bar", &r);
 
@@ -248,6 +505,124 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_plan_rename_classifies_imports_and_reexports() {
+        let tmp = TempDir::new().unwrap();
+        let r = tmp.path().to_path_buf();
+        std::fs::create_dir(r.join("src")).unwrap();
+        let write = |name: &str, source: &str| {
+            let path = r.join("src").join(name);
+            std::fs::write(&path, source).unwrap();
+            path
+        };
+        let foo = write("foo.ts", "export class Foo {}\n");
+        let bar = write(
+            "bar.ts",
+            "import { Foo } from './foo';\n\nexport function bar() {\n  return new Foo();\n}\n",
+        );
+        let index = write("index.ts", "export { Foo } from './foo';\n");
+        let alias = write(
+            "alias.ts",
+            "// public name\nexport { Foo as Widget } from './foo';\n",
+        );
+
+        let mut g = crate::graph::CodeGraph::new();
+        let def_file = g.add_file(foo, "typescript");
+        let foo_sym = g.add_symbol(
+            def_file,
+            SymbolInfo {
+                name: "Foo".into(),
+                kind: SymbolKind::Class,
+                line: 1,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        let bar_file = g.add_file(bar, "typescript");
+        g.add_resolved_import(bar_file, def_file, "./foo");
+        let bar_sym = g.add_symbol(
+            bar_file,
+            SymbolInfo {
+                name: "bar".into(),
+                kind: SymbolKind::Function,
+                line: 3,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        g.add_call_site(bar_sym, foo_sym, 4);
+        let index_file = g.add_file(index, "typescript");
+        g.add_resolved_import(index_file, def_file, "./foo");
+        let alias_file = g.add_file(alias, "typescript");
+        g.graph.add_edge(
+            alias_file,
+            foo_sym,
+            EdgeKind::ExportsAs {
+                alias: "Widget".into(),
+            },
+        );
+
+        let items = plan_rename(&g, "Foo", "Gadget", &r);
+        let sites: Vec<(String, usize, &str)> = items
+            .iter()
+            .map(|i| {
+                let rel = i.file_path.strip_prefix(&r).unwrap();
+                (rel.display().to_string(), i.line, i.kind.as_str())
+            })
+            .collect();
+        assert_eq!(
+            sites,
+            vec![
+                ("src/alias.ts".to_string(), 2, "re-export"),
+                ("src/bar.ts".to_string(), 1, "import"),
+                ("src/bar.ts".to_string(), 4, "reference"),
+                ("src/foo.ts".to_string(), 1, "definition"),
+                ("src/index.ts".to_string(), 1, "re-export"),
+            ]
+        );
+
+        let files = group_by_file(&items);
+        assert_eq!(files.len(), 4);
+        assert_eq!(files[1].sites.len(), 2);
+    }
+
+    #[test]
+    fn test_plan_rename_uses_call_site_lines_and_skips_comments() {
+        let tmp = TempDir::new().unwrap();
+        let r = tmp.path().to_path_buf();
+        std::fs::create_dir(r.join("src")).unwrap();
+        let lib = r.join("src/lib.rs");
+        std::fs::write(
+            &lib,
+            "// NOTE: make_graph re-parses every file.\npub fn make_graph() {}\n\n\
+             /// Calls make_graph twice.\npub fn twice() {\n    make_graph();\n    \
+             let label = \"make_graph\";\n    make_graph();\n}\n",
+        )
+        .unwrap();
+
+        let mut g = crate::graph::CodeGraph::new();
+        let file = g.add_file(lib, "rust");
+        let make_graph = g.add_symbol(
+            file,
+            SymbolInfo {
+                name: "make_graph".into(),
+                kind: SymbolKind::Function,
+                line: 2,
+                ..Default::default()
+            },
+        );
+        // The Rust resolver records calls from the file, at each call line.
+        g.add_call_site(file, make_graph, 6);
+        g.add_call_site(file, make_graph, 8);
+
+        let items = plan_rename(&g, "make_graph", "build", &r);
+        let sites: Vec<(usize, &str)> = items.iter().map(|i| (i.line, i.kind.as_str())).collect();
+        assert_eq!(
+            sites,
+            vec![(2, "definition"), (6, "reference"), (8, "reference")]
+        );
+    }
+
     #[test]
     fn test_plan_rename_import_refs() {
         let r = root();
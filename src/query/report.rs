@@ -0,0 +1,248 @@
+//! Onboarding report (`code-graph report --audience onboarding`).
+//!
+//! Composes the other queries into one architecture primer: the size and languages of the
+//! project (`stats`), its directory layout (`structure`), its packages and their
+//! dependencies (`pkg-query`), the files most of the code imports (`stats --files`), where
+//! execution starts (`entrypoints`), and its import cycles (`circular`). Every list is cut
+//! to the `top` most significant entries so the report stays readable on large projects.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::graph::CodeGraph;
+use crate::graph::node::{FileKind, GraphNode};
+use crate::query::entrypoints::EntryPoint;
+use crate::query::pkgql::PackageGraph;
+
+/// Directory levels the structure section descends.
+const STRUCTURE_DEPTH: usize = 2;
+
+/// Source files of one language.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LanguageCount {
+    pub language: String,
+    pub files: usize,
+}
+
+/// One directory of the structure section.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DirSummary {
+    /// Project-relative path.
+    pub path: String,
+    /// Directory levels below the project root, from 1.
+    pub depth: usize,
+    /// Source files under the directory, recursively.
+    pub files: usize,
+    /// Symbols in those files.
+    pub symbols: usize,
+}
+
+/// One package and its place in the package graph.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PackageSummary {
+    pub name: String,
+    pub files: usize,
+    /// Packages it imports from, sorted.
+    pub depends_on: Vec<String>,
+    /// Number of packages importing from it.
+    pub used_by: usize,
+}
+
+/// A file much of the project imports.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CentralFile {
+    /// Project-relative path.
+    pub file: String,
+    /// Distinct files importing it.
+    pub fan_in: usize,
+    /// Distinct files it imports.
+    pub fan_out: usize,
+    pub symbols: usize,
+}
+
+/// Result of [`onboarding_report`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OnboardingReport {
+    /// Name of the project root directory.
+    pub project: String,
+    pub source_files: usize,
+    pub symbols: usize,
+    pub import_edges: usize,
+    pub external_packages: usize,
+    /// Languages by source files, most first.
+    pub languages: Vec<LanguageCount>,
+    /// Directories down to two levels, sorted by path.
+    pub structure: Vec<DirSummary>,
+    /// The largest packages; empty when the project is a single package.
+    pub packages: Vec<PackageSummary>,
+    /// Files by importers, most first.
+    pub central_files: Vec<CentralFile>,
+    /// Entry points by the files they reach, most first.
+    pub entrypoints: Vec<EntryPoint>,
+    /// Groups of files importing each other in a cycle, in total.
+    pub cycle_groups: usize,
+    /// One representative cycle per group, closed by repeating its first file.
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// Build the onboarding report for the project at `root`, keeping the `top` entries of
+/// each ranked section.
+pub fn onboarding_report(graph: &CodeGraph, root: &Path, top: usize) -> OnboardingReport {
+    let rel = |path: &Path| -> String {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let stats = crate::query::stats::project_stats(graph, root);
+    let file_stats = crate::query::stats::file_stats(graph);
+
+    let mut by_language: HashMap<&str, usize> = HashMap::new();
+    for idx in graph.file_index.values() {
+        if let GraphNode::File(fi) = &graph.graph[*idx]
+            && fi.kind == FileKind::Source
+        {
+            *by_language.entry(fi.language.as_str()).or_default() += 1;
+        }
+    }
+    let mut languages: Vec<LanguageCount> = by_language
+        .into_iter()
+        .map(|(language, files)| LanguageCount {
+            language: language.to_string(),
+            files,
+        })
+        .collect();
+    languages.sort_by(|a, b| {
+        b.files
+            .cmp(&a.files)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+
+    let mut dirs: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for file in &file_stats {
+        let path = rel(&file.path);
+        let parts: Vec<&str> = path.split('/').collect();
+        for depth in 1..parts.len().min(STRUCTURE_DEPTH + 1) {
+            let entry = dirs.entry(parts[..depth].join("/")).or_default();
+            entry.0 += 1;
+            entry.1 += file.symbols;
+        }
+    }
+    let structure = dirs
+        .into_iter()
+        .map(|(path, (files, symbols))| DirSummary {
+            depth: path.split('/').count(),
+            path,
+            files,
+            symbols,
+        })
+        .collect();
+
+    let package_graph = PackageGraph::build(graph, root);
+    let mut packages: Vec<PackageSummary> = if package_graph.names.len() > 1 {
+        (0..package_graph.names.len())
+            .map(|i| PackageSummary {
+                name: package_graph.names[i].clone(),
+                files: package_graph.files[i],
+                depends_on: package_graph.deps[i]
+                    .iter()
+                    .map(|&d| package_graph.names[d].clone())
+                    .collect(),
+                used_by: package_graph.rdeps[i].len(),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    packages.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.name.cmp(&b.name)));
+    packages.truncate(top);
+
+    let central_files = crate::query::stats::top_files(&file_stats, |f| f.fan_in, top)
+        .into_iter()
+        .map(|f| CentralFile {
+            file: rel(&f.path),
+            fan_in: f.fan_in,
+            fan_out: f.fan_out,
+            symbols: f.symbols,
+        })
+        .collect();
+
+    let mut entrypoints = crate::query::entrypoints::find_entrypoints(graph, root).entrypoints;
+    entrypoints.sort_by(|a, b| b.reaches.cmp(&a.reaches).then_with(|| a.file.cmp(&b.file)));
+    entrypoints.truncate(top);
+
+    let circular = crate::query::circular::find_circular(graph, root, 1, false);
+    let cycles = circular
+        .iter()
+        .filter_map(|group| group.cycles.first())
+        .take(top)
+        .map(|cycle| cycle.iter().map(|p| rel(p)).collect())
+        .collect();
+
+    OnboardingReport {
+        project: root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| root.display().to_string()),
+        source_files: file_stats.len(),
+        symbols: stats.symbol_count,
+        import_edges: stats.import_edges,
+        external_packages: stats.external_packages,
+        languages,
+        structure,
+        packages,
+        central_files,
+        entrypoints,
+        cycle_groups: circular.len(),
+        cycles,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_onboarding_report_sections() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let main = graph.add_file(root.join("src/main.ts"), "typescript");
+        let util = graph.add_file(root.join("src/lib/util.ts"), "typescript");
+        let log = graph.add_file(root.join("src/lib/log.ts"), "typescript");
+        graph.add_symbol(
+            util,
+            SymbolInfo {
+                name: "clamp".into(),
+                kind: SymbolKind::Function,
+                ..Default::default()
+            },
+        );
+        graph.add_resolved_import(main, util, "./lib/util");
+        graph.add_resolved_import(util, log, "./log");
+        graph.add_resolved_import(log, util, "./util");
+
+        let report = onboarding_report(&graph, &root, 10);
+        assert_eq!(report.project, "proj");
+        assert_eq!(report.source_files, 3);
+        let structure: Vec<(&str, usize, usize)> = report
+            .structure
+            .iter()
+            .map(|d| (d.path.as_str(), d.files, d.symbols))
+            .collect();
+        assert_eq!(structure, vec![("src", 3, 1), ("src/lib", 2, 1)]);
+        assert_eq!(report.central_files[0].file, "src/lib/util.ts");
+        assert_eq!(report.central_files[0].fan_in, 2);
+        assert_eq!(
+            report
+                .entrypoints
+                .iter()
+                .map(|e| e.file.as_str())
+                .collect::<Vec<_>>(),
+            vec!["src/main.ts"]
+        );
+        assert_eq!(report.cycle_groups, 1);
+        assert_eq!(report.cycles[0].len(), 3);
+    }
+}
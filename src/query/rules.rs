@@ -0,0 +1,200 @@
+//! Architecture layering rules (`code-graph check`).
+//!
+//! Each `[[rules]]` table of `code-graph.toml` forbids the files matching `from` to import
+//! anything matching `forbid`: another indexed file by its project-relative path, or an
+//! external package by name. Every resolved import edge is checked against every rule.
+
+use std::path::Path;
+
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::config::RuleConfig;
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+
+/// One import that breaks a rule.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RuleViolation {
+    /// Name of the broken rule.
+    pub rule: String,
+    /// Project-relative path of the importing file.
+    pub from: String,
+    /// Project-relative path of the imported file, or the external package name.
+    pub to: String,
+    /// The import as written in source.
+    pub specifier: String,
+}
+
+/// Result of `check`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RulesReport {
+    /// Number of rules evaluated.
+    pub rules_checked: usize,
+    pub violations: Vec<RuleViolation>,
+}
+
+/// A rule pattern: a glob, which also matches below it as a directory when it has no
+/// glob characters (`src/domain` matches `src/domain/user.ts`).
+struct PathPattern {
+    glob: glob::Pattern,
+    dir: Option<String>,
+}
+
+impl PathPattern {
+    fn matches(&self, target: &str) -> bool {
+        self.glob.matches(target)
+            || self
+                .dir
+                .as_ref()
+                .is_some_and(|dir| target.starts_with(&format!("{dir}/")))
+    }
+}
+
+/// A `[[rules]]` table with its patterns compiled.
+struct Rule {
+    name: String,
+    from: Vec<PathPattern>,
+    forbid: Vec<PathPattern>,
+}
+
+/// Compile the configured rules. Invalid globs are reported on stderr and skipped.
+fn compile_rules(configs: &[RuleConfig]) -> Vec<Rule> {
+    let compile = |name: &str, patterns: &[String]| -> Vec<PathPattern> {
+        patterns
+            .iter()
+            .filter_map(|p| {
+                let p = p.trim_end_matches('/');
+                match glob::Pattern::new(p) {
+                    Ok(glob) => Some(PathPattern {
+                        glob,
+                        dir: (!p.contains(['*', '?', '['])).then(|| p.to_string()),
+                    }),
+                    Err(err) => {
+                        eprintln!("warning: rule '{name}': invalid pattern '{p}': {err}");
+                        None
+                    }
+                }
+            })
+            .collect()
+    };
+    configs
+        .iter()
+        .map(|c| Rule {
+            name: c.name.clone(),
+            from: compile(&c.name, &c.from),
+            forbid: compile(&c.name, &c.forbid),
+        })
+        .collect()
+}
+
+/// Project-relative path of a file node, or the name of an external package node.
+fn target_name(node: &GraphNode, root: &Path) -> Option<String> {
+    match node {
+        GraphNode::File(fi) => Some(
+            fi.path
+                .strip_prefix(root)
+                .unwrap_or(&fi.path)
+                .to_string_lossy()
+                .replace('\\', "/"),
+        ),
+        GraphNode::ExternalPackage(pkg) => Some(pkg.name.clone()),
+        _ => None,
+    }
+}
+
+/// Evaluate `rules` against every resolved import of the graph.
+pub fn check_rules(graph: &CodeGraph, root: &Path, rules: &[RuleConfig]) -> RulesReport {
+    let compiled = compile_rules(rules);
+    let mut violations = Vec::new();
+    for edge in graph.graph.edge_references() {
+        let EdgeKind::ResolvedImport { specifier, .. } = edge.weight() else {
+            continue;
+        };
+        let source = &graph.graph[edge.source()];
+        if !matches!(source, GraphNode::File(_)) || edge.source() == edge.target() {
+            continue;
+        }
+        let (Some(from), Some(to)) = (
+            target_name(source, root),
+            target_name(&graph.graph[edge.target()], root),
+        ) else {
+            continue;
+        };
+        for rule in &compiled {
+            if rule.from.iter().any(|p| p.matches(&from))
+                && rule.forbid.iter().any(|p| p.matches(&to))
+            {
+                violations.push(RuleViolation {
+                    rule: rule.name.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                    specifier: specifier.clone(),
+                });
+            }
+        }
+    }
+    violations.sort_by(|a, b| (&a.from, &a.to, &a.rule).cmp(&(&b.from, &b.to, &b.rule)));
+    RulesReport {
+        rules_checked: compiled.len(),
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn rule(name: &str, from: &[&str], forbid: &[&str]) -> RuleConfig {
+        RuleConfig {
+            name: name.into(),
+            from: from.iter().map(|s| s.to_string()).collect(),
+            forbid: forbid.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_check_rules_reports_forbidden_imports() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let user = graph.add_file(root.join("src/domain/user.ts"), "typescript");
+        let order = graph.add_file(root.join("src/domain/order.ts"), "typescript");
+        let db = graph.add_file(root.join("src/infrastructure/db.ts"), "typescript");
+        let api = graph.add_file(root.join("src/api/routes.ts"), "typescript");
+        graph.add_resolved_import(user, db, "../infrastructure/db");
+        graph.add_resolved_import(user, order, "./order");
+        graph.add_resolved_import(api, db, "../infrastructure/db");
+        graph.add_external_package(order, "axios", "axios");
+
+        let rules = [
+            rule(
+                "domain-is-pure",
+                &["src/domain"],
+                &["src/infrastructure/**"],
+            ),
+            rule("no-http-in-domain", &["src/domain/*.ts"], &["axios"]),
+        ];
+        let report = check_rules(&graph, &root, &rules);
+        assert_eq!(report.rules_checked, 2);
+        let found: Vec<(&str, &str, &str)> = report
+            .violations
+            .iter()
+            .map(|v| (v.rule.as_str(), v.from.as_str(), v.to.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("no-http-in-domain", "src/domain/order.ts", "axios"),
+                (
+                    "domain-is-pure",
+                    "src/domain/user.ts",
+                    "src/infrastructure/db.ts"
+                ),
+            ]
+        );
+        assert_eq!(report.violations[1].specifier, "../infrastructure/db");
+
+        assert!(check_rules(&graph, &root, &[]).violations.is_empty());
+    }
+}
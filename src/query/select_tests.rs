@@ -0,0 +1,272 @@
+//! Impact-aware test selection for CI (`code-graph select-tests --base <rev>`).
+//!
+//! Files changed since the merge base of `--base` and `HEAD` (working-tree changes
+//! included) are followed through their importers to the test files depending on them: a
+//! changed test file is selected as is, a test with a `Tests` edge to a changed file is
+//! `direct`, one reaching it through other imports `transitive`.
+//!
+//! Inline `#[cfg(test)]` modules do not make their file a test file, so for `cargo test`
+//! every changed Rust file also selects the tests of its own module.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+use crate::query::impact::walk_imports;
+use crate::query::util::find_containing_file_idx;
+
+/// One test file worth re-running.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SelectedTest {
+    /// Project-relative path.
+    pub file: String,
+    /// `changed`, `direct`, or `transitive`.
+    pub reason: &'static str,
+    /// Project-relative path of the changed file that selects it (the closest one).
+    pub via: String,
+    /// Import depth from `via`; 0 for a changed test file.
+    pub depth: usize,
+}
+
+/// Result of [`select_tests`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct TestSelection {
+    pub base: String,
+    /// Changed files, project-relative.
+    pub changed: Vec<String>,
+    /// Changed files not in the graph (deleted, or not indexed). A conservative pipeline
+    /// runs the whole suite when this is non-empty.
+    pub unmapped: Vec<String>,
+    /// Selected test files sorted by path.
+    pub tests: Vec<SelectedTest>,
+    /// `cargo test` arguments, one invocation each: `--test <name>` for integration tests,
+    /// a `module::path::` filter for a module's unit tests, `--lib` / `--bins` for a crate
+    /// root.
+    pub cargo: Vec<String>,
+}
+
+/// Files changed between the merge base of `base` and `HEAD` and the working tree, as
+/// absolute paths under `root`.
+pub fn changed_files(root: &Path, base: &str) -> Result<Vec<PathBuf>> {
+    let git = |args: &[&str]| -> Result<String> {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .context("failed to run git. Ensure git is in PATH.")?;
+        if !output.status.success() {
+            bail!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let merge_base = git(&["merge-base", base, "HEAD"])?;
+    let toplevel = git(&["rev-parse", "--show-toplevel"])?;
+    let toplevel = Path::new(toplevel.trim());
+    Ok(
+        git(&["diff", "--name-only", "--no-renames", merge_base.trim()])?
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| toplevel.join(l))
+            .filter(|p| p.starts_with(root))
+            .collect(),
+    )
+}
+
+/// `cargo test` arguments selecting the tests of a Rust file: the integration test target
+/// for a file under a `tests/` directory, otherwise the unit tests of its module.
+fn cargo_filter(graph: &CodeGraph, file_idx: NodeIndex, rel: &Path) -> Option<String> {
+    let GraphNode::File(fi) = &graph.graph[file_idx] else {
+        return None;
+    };
+    if fi.language != "rust" {
+        return None;
+    }
+    let components: Vec<&str> = rel
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect();
+    if let Some(pos) = components.iter().position(|&c| c == "tests") {
+        let target = components.get(pos + 1)?;
+        return Some(format!("--test {}", target.trim_end_matches(".rs")));
+    }
+    let module = graph.module_of_file(file_idx)?;
+    match module.path.split_once("::") {
+        Some((_, inner)) => Some(format!("{}::", inner)),
+        None if rel.file_name().is_some_and(|n| n == "lib.rs") => Some("--lib".to_string()),
+        None => Some("--bins".to_string()),
+    }
+}
+
+/// Select the test files affected by `changed` (absolute paths) in `graph`.
+pub fn select_tests(
+    graph: &CodeGraph,
+    root: &Path,
+    base: &str,
+    changed: &[PathBuf],
+) -> TestSelection {
+    let rel = |path: &Path| -> String {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    };
+    let mut selection = TestSelection {
+        base: base.to_string(),
+        ..Default::default()
+    };
+    // Test path -> (reason rank, depth, via); the lowest (rank, depth) wins.
+    let mut tests: BTreeMap<String, (u8, usize, String)> = BTreeMap::new();
+    let mut cargo: BTreeSet<String> = BTreeSet::new();
+    let mut select = |file: String, rank: u8, depth: usize, via: &str| {
+        let entry = tests.entry(file).or_insert((rank, depth, via.to_string()));
+        if (rank, depth) < (entry.0, entry.1) {
+            *entry = (rank, depth, via.to_string());
+        }
+    };
+
+    for path in changed {
+        let via = rel(path);
+        selection.changed.push(via.clone());
+        let Some(&file_idx) = graph.file_index.get(path) else {
+            selection.unmapped.push(via);
+            continue;
+        };
+        cargo.extend(cargo_filter(graph, file_idx, Path::new(&via)));
+
+        let depths = walk_imports(
+            graph,
+            &[(file_idx, 0)],
+            Direction::Incoming,
+            None,
+            false,
+            |_, _| true,
+        );
+        for (idx, depth) in depths {
+            let GraphNode::File(fi) = &graph.graph[idx] else {
+                continue;
+            };
+            if !fi.is_test {
+                continue;
+            }
+            let direct = graph
+                .graph
+                .edges_directed(idx, Direction::Outgoing)
+                .any(|e| {
+                    matches!(e.weight(), EdgeKind::Tests)
+                        && (e.target() == file_idx
+                            || find_containing_file_idx(graph, e.target()) == Some(file_idx))
+                });
+            let rank = match depth {
+                0 => 0,
+                _ if direct => 1,
+                _ => 2,
+            };
+            let file = rel(&fi.path);
+            cargo.extend(cargo_filter(graph, idx, Path::new(&file)));
+            select(file, rank, depth, &via);
+        }
+    }
+
+    selection.tests = tests
+        .into_iter()
+        .map(|(file, (rank, depth, via))| SelectedTest {
+            file,
+            reason: match rank {
+                0 => "changed",
+                1 => "direct",
+                _ => "transitive",
+            },
+            via,
+            depth,
+        })
+        .collect();
+    selection.cargo = cargo.into_iter().collect();
+    selection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_tests_follows_imports_to_tests() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let button = graph.add_file(root.join("src/button.ts"), "typescript");
+        let form = graph.add_file(root.join("src/form.ts"), "typescript");
+        let button_spec = graph.add_file(root.join("src/button.spec.ts"), "typescript");
+        let form_spec = graph.add_file(root.join("src/form.spec.ts"), "typescript");
+        let other_spec = graph.add_file(root.join("src/other.spec.ts"), "typescript");
+        let other = graph.add_file(root.join("src/other.ts"), "typescript");
+        graph.add_resolved_import(form, button, "./button");
+        graph.add_resolved_import(button_spec, button, "./button");
+        graph.add_resolved_import(form_spec, form, "./form");
+        graph.add_resolved_import(other_spec, other, "./other");
+        crate::resolver::tests_resolver::link_tests(&mut graph, &root);
+
+        let selection = select_tests(
+            &graph,
+            &root,
+            "main",
+            &[
+                root.join("src/button.ts"),
+                root.join("src/other.spec.ts"),
+                root.join("README.md"),
+            ],
+        );
+        let summary: Vec<(&str, &str, &str, usize)> = selection
+            .tests
+            .iter()
+            .map(|t| (t.file.as_str(), t.reason, t.via.as_str(), t.depth))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/button.spec.ts", "direct", "src/button.ts", 1),
+                ("src/form.spec.ts", "transitive", "src/button.ts", 2),
+                ("src/other.spec.ts", "changed", "src/other.spec.ts", 0),
+            ]
+        );
+        assert_eq!(selection.unmapped, vec!["README.md"]);
+        assert!(selection.cargo.is_empty());
+    }
+
+    #[test]
+    fn test_cargo_filters_for_rust_files() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let lib = graph.add_file(root.join("src/lib.rs"), "rust");
+        let parser = graph.add_file(root.join("src/parser/imports.rs"), "rust");
+        let it = graph.add_file(root.join("tests/parsing.rs"), "rust");
+        let crate_root = graph.add_module("demo", "demo");
+        graph.set_file_module(lib, crate_root);
+        let module = graph.add_module("demo::parser::imports", "demo");
+        graph.set_file_module(parser, module);
+        graph.add_resolved_import(it, parser, "demo::parser::imports");
+        crate::resolver::tests_resolver::link_tests(&mut graph, &root);
+
+        let selection = select_tests(
+            &graph,
+            &root,
+            "HEAD~1",
+            &[root.join("src/lib.rs"), root.join("src/parser/imports.rs")],
+        );
+        assert_eq!(selection.tests.len(), 1);
+        assert_eq!(selection.tests[0].file, "tests/parsing.rs");
+        assert_eq!(
+            selection.cargo,
+            vec!["--lib", "--test parsing", "parser::imports::"]
+        );
+    }
+}
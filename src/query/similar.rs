@@ -0,0 +1,338 @@
+//! Copy-pasted symbols (`code-graph similar`).
+//!
+//! Symbols sharing a name in different files are compared by the token shingles of their
+//! bodies (every run of [`SHINGLE`] consecutive tokens, whitespace ignored). Pairs whose
+//! Jaccard similarity reaches the threshold are merged into groups, surfacing utilities
+//! that were copied and lightly edited rather than shared.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use petgraph::stable_graph::NodeIndex;
+
+use crate::graph::CodeGraph;
+use crate::graph::node::{GraphNode, SymbolInfo};
+use crate::query::find::kind_to_str;
+use crate::query::util::find_containing_file_idx;
+
+/// Tokens per shingle.
+const SHINGLE: usize = 5;
+
+/// One copy in a [`SimilarGroup`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SimilarMember {
+    pub kind: String,
+    /// Project-relative path.
+    pub file: String,
+    pub line: usize,
+    pub lines: usize,
+}
+
+/// Same-named symbols with near-identical bodies.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SimilarGroup {
+    pub name: String,
+    /// Lowest similarity (0-1) of the pairs that joined the group.
+    pub similarity: f64,
+    /// Members sorted by file.
+    pub members: Vec<SimilarMember>,
+}
+
+/// Result of [`find_similar`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SimilarReport {
+    /// Symbols compared (those sharing a name with a symbol in another file).
+    pub symbols_compared: usize,
+    /// Groups, most similar first, then by name.
+    pub groups: Vec<SimilarGroup>,
+}
+
+/// A symbol body ready for comparison.
+struct Body {
+    node: NodeIndex,
+    file: NodeIndex,
+    shingles: HashSet<u64>,
+}
+
+/// Find same-named symbols in different files whose bodies are at least `threshold`
+/// similar (0-1). Symbols shorter than `min_lines`, or outside `scope` (relative to
+/// `root`) when given, are skipped.
+pub fn find_similar(
+    graph: &CodeGraph,
+    root: &Path,
+    scope: Option<&Path>,
+    threshold: f64,
+    min_lines: usize,
+) -> SimilarReport {
+    let abs_scope: Option<PathBuf> = scope.map(|s| {
+        if s.is_absolute() {
+            s.to_path_buf()
+        } else {
+            root.join(s)
+        }
+    });
+    // Vendored copies are one file, not copy-paste.
+    let copies = crate::query::identical_files::identical_copies(graph);
+
+    let mut sources: HashMap<NodeIndex, Option<Vec<u8>>> = HashMap::new();
+    let mut symbols_compared = 0;
+    let mut groups: Vec<SimilarGroup> = Vec::new();
+
+    let mut names: Vec<&String> = graph.symbol_index.keys().collect();
+    names.sort();
+    for name in names {
+        let candidates: Vec<(NodeIndex, NodeIndex)> = graph.symbol_index[name]
+            .iter()
+            .filter_map(|&node| {
+                let GraphNode::Symbol(sym) = &graph.graph[node] else {
+                    return None;
+                };
+                let file = find_containing_file_idx(graph, node)?;
+                let GraphNode::File(fi) = &graph.graph[file] else {
+                    return None;
+                };
+                let keep = sym.line_count() >= min_lines
+                    && sym.byte_end > sym.byte_start
+                    && !copies.contains(&fi.path)
+                    && abs_scope.as_ref().is_none_or(|s| fi.path.starts_with(s));
+                keep.then_some((node, file))
+            })
+            .collect();
+        let files: HashSet<NodeIndex> = candidates.iter().map(|&(_, f)| f).collect();
+        if files.len() < 2 {
+            continue;
+        }
+
+        let bodies: Vec<Body> = candidates
+            .into_iter()
+            .filter_map(|(node, file)| {
+                let source = sources
+                    .entry(file)
+                    .or_insert_with(|| match &graph.graph[file] {
+                        GraphNode::File(fi) => std::fs::read(&fi.path).ok(),
+                        _ => None,
+                    })
+                    .as_deref()?;
+                let GraphNode::Symbol(sym) = &graph.graph[node] else {
+                    return None;
+                };
+                let body = source.get(sym.byte_start..sym.byte_end)?;
+                Some(Body {
+                    node,
+                    file,
+                    shingles: shingles(&String::from_utf8_lossy(body)),
+                })
+            })
+            .filter(|b| !b.shingles.is_empty())
+            .collect();
+        symbols_compared += bodies.len();
+
+        // Union-find over the pairs that reach the threshold.
+        let mut parent: Vec<usize> = (0..bodies.len()).collect();
+        let mut weakest: HashMap<usize, f64> = HashMap::new();
+        fn find(parent: &mut [usize], i: usize) -> usize {
+            let mut r = i;
+            while parent[r] != r {
+                r = parent[r];
+            }
+            parent[i] = r;
+            r
+        }
+        let mut joined: Vec<(usize, usize, f64)> = Vec::new();
+        for i in 0..bodies.len() {
+            for j in i + 1..bodies.len() {
+                if bodies[i].file == bodies[j].file {
+                    continue;
+                }
+                let sim = jaccard(&bodies[i].shingles, &bodies[j].shingles);
+                if sim >= threshold {
+                    joined.push((i, j, sim));
+                }
+            }
+        }
+        for &(i, j, _) in &joined {
+            let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+            if ri != rj {
+                parent[ri] = rj;
+            }
+        }
+        for &(i, _, sim) in &joined {
+            let r = find(&mut parent, i);
+            let w = weakest.entry(r).or_insert(1.0);
+            *w = w.min(sim);
+        }
+
+        let mut members_of: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in 0..bodies.len() {
+            let r = find(&mut parent, i);
+            if weakest.contains_key(&r) {
+                members_of.entry(r).or_default().push(i);
+            }
+        }
+        for (r, members) in members_of {
+            let mut members: Vec<SimilarMember> = members
+                .into_iter()
+                .filter_map(|i| member(graph, root, bodies[i].node, bodies[i].file))
+                .collect();
+            members.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+            groups.push(SimilarGroup {
+                name: name.clone(),
+                similarity: weakest[&r],
+                members,
+            });
+        }
+    }
+
+    groups.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.members[0].file.cmp(&b.members[0].file))
+    });
+    SimilarReport {
+        symbols_compared,
+        groups,
+    }
+}
+
+fn member(
+    graph: &CodeGraph,
+    root: &Path,
+    node: NodeIndex,
+    file: NodeIndex,
+) -> Option<SimilarMember> {
+    let (GraphNode::Symbol(sym), GraphNode::File(fi)) = (&graph.graph[node], &graph.graph[file])
+    else {
+        return None;
+    };
+    Some(SimilarMember {
+        kind: kind_to_str(&sym.kind).to_string(),
+        file: fi
+            .path
+            .strip_prefix(root)
+            .unwrap_or(&fi.path)
+            .to_string_lossy()
+            .into_owned(),
+        line: sym.line,
+        lines: SymbolInfo::line_count(sym),
+    })
+}
+
+/// Split source into identifier/number words and single punctuation characters.
+fn tokens(source: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, c) in source.char_indices() {
+        let word = c.is_alphanumeric() || c == '_';
+        match (word, start) {
+            (true, None) => start = Some(i),
+            (true, Some(_)) => {}
+            (false, Some(s)) => {
+                out.push(&source[s..i]);
+                start = None;
+            }
+            (false, None) => {}
+        }
+        if !word && !c.is_whitespace() {
+            out.push(&source[i..i + c.len_utf8()]);
+        }
+    }
+    if let Some(s) = start {
+        out.push(&source[s..]);
+    }
+    out
+}
+
+/// Hashes of every [`SHINGLE`]-token window of `source`; the whole token list when it is
+/// shorter.
+fn shingles(source: &str) -> HashSet<u64> {
+    let tokens = tokens(source);
+    let hash = |window: &[&str]| {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        window.hash(&mut hasher);
+        hasher.finish()
+    };
+    if tokens.len() < SHINGLE {
+        return if tokens.is_empty() {
+            HashSet::new()
+        } else {
+            HashSet::from([hash(&tokens)])
+        };
+    }
+    tokens.windows(SHINGLE).map(hash).collect()
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let shared = a.intersection(b).count();
+    let total = a.len() + b.len() - shared;
+    if total == 0 {
+        0.0
+    } else {
+        shared as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::SymbolKind;
+
+    #[test]
+    fn test_tokens_ignore_whitespace() {
+        assert_eq!(
+            tokens("fn  a(x)\n{ x+1 }"),
+            vec!["fn", "a", "(", "x", ")", "{", "x", "+", "1", "}"]
+        );
+        assert_eq!(shingles("a b c d e"), shingles("a  b\nc d\te"));
+    }
+
+    #[test]
+    fn test_groups_same_named_near_identical_bodies() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        let original = "export function formatDate(d: Date) {\n  const y = d.getFullYear();\n  const m = d.getMonth() + 1;\n  const day = d.getDate();\n  return `${y}-${m}-${day}`;\n}\n";
+        let edited = original.replace("const day", "let day");
+        let other = "export function formatDate(d: Date) {\n  return d.toISOString();\n}\n";
+        let mut graph = CodeGraph::new();
+        for (path, source) in [
+            ("web/utils.ts", original.to_string()),
+            ("api/utils.ts", edited),
+            ("cli/utils.ts", other.to_string()),
+        ] {
+            let full = root.join(path);
+            std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+            std::fs::write(&full, &source).unwrap();
+            let file = graph.add_file(full, "typescript");
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: "formatDate".into(),
+                    kind: SymbolKind::Function,
+                    line: 1,
+                    line_end: source.lines().count() - 1,
+                    byte_start: 0,
+                    byte_end: source.len(),
+                    is_exported: true,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let report = find_similar(&graph, root, None, 0.7, 2);
+        assert_eq!(report.symbols_compared, 3);
+        assert_eq!(report.groups.len(), 1, "{:?}", report.groups);
+        let group = &report.groups[0];
+        assert_eq!(group.name, "formatDate");
+        let files: Vec<&str> = group.members.iter().map(|m| m.file.as_str()).collect();
+        assert_eq!(files, vec!["api/utils.ts", "web/utils.ts"]);
+        assert!(group.similarity >= 0.7 && group.similarity < 1.0);
+
+        assert!(find_similar(&graph, root, None, 0.99, 2).groups.is_empty());
+        assert!(
+            find_similar(&graph, root, Some(Path::new("web")), 0.7, 2)
+                .groups
+                .is_empty()
+        );
+    }
+}
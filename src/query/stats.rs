@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use petgraph::Direction;
 use petgraph::visit::EdgeRef;
 
@@ -6,6 +8,8 @@ use crate::graph::{
     edge::EdgeKind,
     node::{GraphNode, SymbolKind},
 };
+use crate::query::identical_files::{IdenticalFiles, identical_file_groups};
+use crate::query::util::find_containing_file_idx;
 
 /// Per-crate symbol breakdown (for workspace projects with multiple crates).
 #[derive(Debug)]
@@ -57,6 +61,17 @@ pub struct ProjectStats {
     pub rust_macros: usize,
     pub rust_imports: usize,
     pub rust_reexports: usize,
+    /// Rust symbols compiled only under `#[cfg(test)]` (including everything in `mod tests`).
+    pub rust_cfg_test: usize,
+    /// Rust symbols gated on a Cargo feature (`#[cfg(feature = "...")]`).
+    pub rust_cfg_feature: usize,
+    /// Rust symbols behind any other `#[cfg(...)]` predicate (platform, `not(...)`, ...).
+    pub rust_cfg_other: usize,
+    /// Rust impl methods a derive macro generated (`#[automatically_derived]` impls); the
+    /// rest of `rust_impl_methods` is hand-written.
+    pub rust_derived_impl_methods: usize,
+    /// Traits named in `#[derive(...)]` attributes of Rust types, one per type and trait.
+    pub rust_derive_traits: usize,
     // Phase 9 additions: per-crate breakdowns and dependency counts
     /// Per-crate symbol breakdowns (non-empty only for workspace projects).
     pub rust_crate_stats: Vec<CrateStats>,
@@ -100,6 +115,62 @@ pub struct ProjectStats {
     pub go_variables: usize,
     /// Go type alias/definition count.
     pub go_type_aliases: usize,
+    // Protobuf-specific counts
+    /// Number of `.proto` files in the graph.
+    pub proto_file_count: usize,
+    /// Total protobuf symbols (messages + nested types + enums + services + rpcs).
+    pub proto_symbol_count: usize,
+    /// Protobuf message count (including nested messages).
+    pub proto_messages: usize,
+    /// Protobuf enum count (including nested enums).
+    pub proto_enums: usize,
+    /// Protobuf service count.
+    pub proto_services: usize,
+    /// Protobuf rpc method count.
+    pub proto_rpcs: usize,
+    // SQL-specific counts
+    /// Number of `.sql` files in the graph.
+    pub sql_file_count: usize,
+    /// Total SQL symbols (tables + views).
+    pub sql_symbol_count: usize,
+    /// SQL table count.
+    pub sql_tables: usize,
+    /// SQL view count.
+    pub sql_views: usize,
+    /// `ReferencesTable` edges from SQL statements and SQL string literals to tables/views.
+    pub sql_table_refs: usize,
+    // Zig-specific counts
+    /// Number of `.zig` files in the graph.
+    pub zig_file_count: usize,
+    /// Total Zig symbols (including container members).
+    pub zig_symbol_count: usize,
+    /// Zig free function count.
+    pub zig_fns: usize,
+    /// Zig container method count.
+    pub zig_methods: usize,
+    /// Zig struct/union/opaque container count.
+    pub zig_structs: usize,
+    /// Zig enum count.
+    pub zig_enums: usize,
+    /// Zig `var` declaration count.
+    pub zig_variables: usize,
+    // Shell-specific counts
+    /// Number of `.sh` / `.bash` files in the graph.
+    pub shell_file_count: usize,
+    /// Shell function count (the only shell symbol kind).
+    pub shell_fns: usize,
+    /// Number of `.ipynb` notebooks in the graph. Their symbols are counted under the
+    /// language of their kernel.
+    pub notebook_file_count: usize,
+    // Stylesheet-specific counts
+    /// Number of `.css` / `.scss` files in the graph.
+    pub css_file_count: usize,
+    /// Total stylesheet symbols.
+    pub css_symbol_count: usize,
+    /// SCSS `@mixin` and `@function` count.
+    pub css_fns: usize,
+    /// SCSS top-level `$variable` count.
+    pub css_variables: usize,
     // Phase 12: Non-parsed file counts
     /// Total number of non-parsed (non-source) files in the graph.
     pub non_parsed_files: usize,
@@ -115,10 +186,36 @@ pub struct ProjectStats {
     pub other_files: usize,
     /// Count of source files (FileKind::Source) -- for clarity in output.
     pub source_files: usize,
+    // Size
+    /// Lines across all source files.
+    pub total_lines: usize,
+    /// The source files with the most lines, largest first.
+    pub largest_files: Vec<SizedItem>,
+    /// The functions and methods spanning the most lines, largest first.
+    pub largest_functions: Vec<SizedItem>,
+    /// Groups of byte-identical source files (vendored copies).
+    pub identical_files: Vec<IdenticalFiles>,
 }
 
-/// Compute project statistics from a built `CodeGraph`.
-pub fn project_stats(graph: &CodeGraph) -> ProjectStats {
+/// How many entries `largest_files` and `largest_functions` keep.
+const LARGEST_COUNT: usize = 5;
+
+/// A file or function and the number of lines it spans.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SizedItem {
+    /// Function name, or `None` for a whole file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Project-relative path of the file.
+    pub file: String,
+    /// Declaration line of a function, or 1 for a file.
+    pub line: usize,
+    pub lines: usize,
+}
+
+/// Compute project statistics from a built `CodeGraph`. Paths in the size rankings are
+/// relative to `root`.
+pub fn project_stats(graph: &CodeGraph, root: &Path) -> ProjectStats {
     let breakdown = graph.symbols_by_kind();
 
     let import_edges = graph
@@ -176,6 +273,11 @@ pub fn project_stats(graph: &CodeGraph) -> ProjectStats {
     let mut rust_consts = 0usize;
     let mut rust_statics = 0usize;
     let mut rust_macros = 0usize;
+    let mut rust_cfg_test = 0usize;
+    let mut rust_cfg_feature = 0usize;
+    let mut rust_cfg_other = 0usize;
+    let mut rust_derived_impl_methods = 0usize;
+    let mut rust_derive_traits = 0usize;
 
     for idx in graph.graph.node_indices() {
         if let GraphNode::Symbol(ref s) = graph.graph[idx] {
@@ -232,6 +334,17 @@ pub fn project_stats(graph: &CodeGraph) -> ProjectStats {
                 SymbolKind::Macro => rust_macros += 1,
                 _ => {}
             }
+            if s.is_cfg_test() {
+                rust_cfg_test += 1;
+            } else if s.is_cfg_feature() {
+                rust_cfg_feature += 1;
+            } else if s.cfg.is_some() {
+                rust_cfg_other += 1;
+            }
+            if s.derived.is_some() {
+                rust_derived_impl_methods += 1;
+            }
+            rust_derive_traits += s.derive_traits().len();
         }
     }
 
@@ -355,6 +468,170 @@ pub fn project_stats(graph: &CodeGraph) -> ProjectStats {
         }
     }
 
+    // ---------------------------------------------------------------------------
+    // Protobuf symbol counts. Nested messages/enums and rpcs are child symbols
+    // (ChildOf edges only), so they are counted through their parent.
+    // ---------------------------------------------------------------------------
+    let mut proto_file_count = 0usize;
+    let mut proto_symbol_count = 0usize;
+    let mut proto_messages = 0usize;
+    let mut proto_enums = 0usize;
+    let mut proto_services = 0usize;
+    let mut proto_rpcs = 0usize;
+
+    for file_idx in graph.graph.node_indices() {
+        match graph.graph[file_idx] {
+            GraphNode::File(ref fi) if fi.language == "proto" => proto_file_count += 1,
+            _ => continue,
+        }
+        for edge in graph.graph.edges(file_idx) {
+            if let EdgeKind::Contains = edge.weight() {
+                let children = graph
+                    .graph
+                    .edges_directed(edge.target(), Direction::Incoming)
+                    .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+                    .map(|e| e.source());
+                for sym_idx in std::iter::once(edge.target()).chain(children) {
+                    if let GraphNode::Symbol(ref s) = graph.graph[sym_idx] {
+                        proto_symbol_count += 1;
+                        match s.kind {
+                            SymbolKind::Message => proto_messages += 1,
+                            SymbolKind::Enum => proto_enums += 1,
+                            SymbolKind::Service => proto_services += 1,
+                            SymbolKind::Rpc => proto_rpcs += 1,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // SQL symbol counts and table reference edges.
+    // ---------------------------------------------------------------------------
+    let mut sql_file_count = 0usize;
+    let mut sql_symbol_count = 0usize;
+    let mut sql_tables = 0usize;
+    let mut sql_views = 0usize;
+
+    for file_idx in graph.graph.node_indices() {
+        match graph.graph[file_idx] {
+            GraphNode::File(ref fi) if fi.language == "sql" => sql_file_count += 1,
+            _ => continue,
+        }
+        for edge in graph.graph.edges(file_idx) {
+            if let EdgeKind::Contains = edge.weight()
+                && let GraphNode::Symbol(ref s) = graph.graph[edge.target()]
+            {
+                sql_symbol_count += 1;
+                match s.kind {
+                    SymbolKind::Table => sql_tables += 1,
+                    SymbolKind::View => sql_views += 1,
+                    _ => {}
+                }
+            }
+        }
+    }
+    let sql_table_refs = graph
+        .graph
+        .edge_weights()
+        .filter(|e| matches!(e, EdgeKind::ReferencesTable))
+        .count();
+
+    // ---------------------------------------------------------------------------
+    // Zig symbol counts. Container members are child symbols, counted through their parent.
+    // ---------------------------------------------------------------------------
+    let mut zig_file_count = 0usize;
+    let mut zig_symbol_count = 0usize;
+    let mut zig_fns = 0usize;
+    let mut zig_methods = 0usize;
+    let mut zig_structs = 0usize;
+    let mut zig_enums = 0usize;
+    let mut zig_variables = 0usize;
+
+    for file_idx in graph.graph.node_indices() {
+        match graph.graph[file_idx] {
+            GraphNode::File(ref fi) if fi.language == "zig" => zig_file_count += 1,
+            _ => continue,
+        }
+        for edge in graph.graph.edges(file_idx) {
+            if let EdgeKind::Contains = edge.weight() {
+                let children = graph
+                    .graph
+                    .edges_directed(edge.target(), Direction::Incoming)
+                    .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+                    .map(|e| e.source());
+                for sym_idx in std::iter::once(edge.target()).chain(children) {
+                    if let GraphNode::Symbol(ref s) = graph.graph[sym_idx] {
+                        zig_symbol_count += 1;
+                        match s.kind {
+                            SymbolKind::Function => zig_fns += 1,
+                            SymbolKind::Method => zig_methods += 1,
+                            SymbolKind::Struct => zig_structs += 1,
+                            SymbolKind::Enum => zig_enums += 1,
+                            SymbolKind::Variable => zig_variables += 1,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // Shell function counts.
+    // ---------------------------------------------------------------------------
+    let mut shell_file_count = 0usize;
+    let mut shell_fns = 0usize;
+
+    for file_idx in graph.graph.node_indices() {
+        match graph.graph[file_idx] {
+            GraphNode::File(ref fi) if fi.language == "shell" => shell_file_count += 1,
+            _ => continue,
+        }
+        shell_fns += graph
+            .graph
+            .edges(file_idx)
+            .filter(|e| {
+                matches!(e.weight(), EdgeKind::Contains)
+                    && matches!(graph.graph[e.target()], GraphNode::Symbol(_))
+            })
+            .count();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Stylesheet counts.
+    // ---------------------------------------------------------------------------
+    let mut css_file_count = 0usize;
+    let mut css_fns = 0usize;
+    let mut css_variables = 0usize;
+
+    for file_idx in graph.graph.node_indices() {
+        match graph.graph[file_idx] {
+            GraphNode::File(ref fi) if fi.language == "css" => css_file_count += 1,
+            _ => continue,
+        }
+        for edge in graph.graph.edges(file_idx) {
+            if !matches!(edge.weight(), EdgeKind::Contains) {
+                continue;
+            }
+            if let GraphNode::Symbol(ref s) = graph.graph[edge.target()] {
+                match s.kind {
+                    SymbolKind::Function => css_fns += 1,
+                    SymbolKind::Variable => css_variables += 1,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let notebook_file_count = graph
+        .file_index
+        .keys()
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("ipynb"))
+        .count();
+
     // ---------------------------------------------------------------------------
     // Per-crate breakdown (Phase 9).
     //
@@ -384,6 +661,17 @@ pub fn project_stats(graph: &CodeGraph) -> ProjectStats {
     }
     let non_parsed_files = doc_files + config_files + ci_files + asset_files + other_files;
 
+    let files = file_stats(graph);
+    let largest_files = top_files(&files, |f| f.lines, LARGEST_COUNT)
+        .into_iter()
+        .map(|f| SizedItem {
+            name: None,
+            file: relative_path(&f.path, root),
+            line: 1,
+            lines: f.lines,
+        })
+        .collect();
+
     ProjectStats {
         file_count: graph.file_index.len(),
         symbol_count: graph.symbol_count(),
@@ -410,6 +698,11 @@ pub fn project_stats(graph: &CodeGraph) -> ProjectStats {
         rust_macros,
         rust_imports,
         rust_reexports,
+        rust_cfg_test,
+        rust_cfg_feature,
+        rust_cfg_other,
+        rust_derived_impl_methods,
+        rust_derive_traits,
         rust_crate_stats,
         builtin_count,
         builtin_usage_count,
@@ -432,6 +725,36 @@ pub fn project_stats(graph: &CodeGraph) -> ProjectStats {
         go_consts,
         go_variables,
         go_type_aliases,
+        // Protobuf counts
+        proto_file_count,
+        proto_symbol_count,
+        proto_messages,
+        proto_enums,
+        proto_services,
+        proto_rpcs,
+        // SQL counts
+        sql_file_count,
+        sql_symbol_count,
+        sql_tables,
+        sql_views,
+        sql_table_refs,
+        // Zig counts
+        zig_file_count,
+        zig_symbol_count,
+        zig_fns,
+        zig_methods,
+        zig_structs,
+        zig_enums,
+        zig_variables,
+        // Shell counts
+        shell_file_count,
+        shell_fns,
+        notebook_file_count,
+        // Stylesheet counts
+        css_file_count,
+        css_symbol_count: css_fns + css_variables,
+        css_fns,
+        css_variables,
         // Phase 12: Non-parsed file counts
         non_parsed_files,
         doc_files,
@@ -440,13 +763,199 @@ pub fn project_stats(graph: &CodeGraph) -> ProjectStats {
         asset_files,
         other_files,
         source_files,
+        total_lines: files.iter().map(|f| f.lines).sum(),
+        largest_files,
+        largest_functions: largest_functions(graph, root, LARGEST_COUNT),
+        identical_files: identical_file_groups(graph, root),
+    }
+}
+
+/// Compute project statistics over only the files `keep` accepts, for per-package or
+/// per-language overviews. Symbols of the other files, and the external packages,
+/// builtins and unresolved imports only they used, are left out of every count.
+pub fn project_stats_for_files(
+    graph: &CodeGraph,
+    root: &Path,
+    keep: impl Fn(&Path) -> bool,
+) -> ProjectStats {
+    let dropped: Vec<std::path::PathBuf> = graph
+        .file_index
+        .keys()
+        .filter(|path| !keep(path))
+        .cloned()
+        .collect();
+    if dropped.is_empty() {
+        return project_stats(graph, root);
+    }
+
+    let mut scoped = graph.clone();
+    for path in &dropped {
+        scoped.remove_file_from_graph(path);
+    }
+    let orphans: Vec<_> = scoped
+        .graph
+        .node_indices()
+        .filter(|&idx| {
+            matches!(
+                scoped.graph[idx],
+                GraphNode::ExternalPackage(_)
+                    | GraphNode::Builtin { .. }
+                    | GraphNode::UnresolvedImport { .. }
+            ) && scoped
+                .graph
+                .edges_directed(idx, Direction::Incoming)
+                .next()
+                .is_none()
+        })
+        .collect();
+    for idx in orphans {
+        scoped.graph.remove_node(idx);
     }
+    project_stats(&scoped, root)
+}
+
+/// The `n` functions and methods spanning the most lines, ties broken by file and line.
+fn largest_functions(graph: &CodeGraph, root: &Path, n: usize) -> Vec<SizedItem> {
+    let mut functions: Vec<SizedItem> = graph
+        .graph
+        .node_indices()
+        .filter_map(|idx| {
+            let GraphNode::Symbol(info) = &graph.graph[idx] else {
+                return None;
+            };
+            if !matches!(
+                info.kind,
+                SymbolKind::Function
+                    | SymbolKind::Method
+                    | SymbolKind::ImplMethod
+                    | SymbolKind::Component
+            ) {
+                return None;
+            }
+            let GraphNode::File(fi) = &graph.graph[find_containing_file_idx(graph, idx)?] else {
+                return None;
+            };
+            Some(SizedItem {
+                name: Some(info.name.clone()),
+                file: relative_path(&fi.path, root),
+                line: info.line,
+                lines: info.line_count(),
+            })
+        })
+        .collect();
+    functions.sort_by(|a, b| {
+        b.lines
+            .cmp(&a.lines)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    functions.truncate(n);
+    functions
+}
+
+fn relative_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
 }
 
 // ---------------------------------------------------------------------------
 // Per-crate breakdown computation
 // ---------------------------------------------------------------------------
 
+/// Size and coupling of one source file, for `stats --files`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileStats {
+    pub path: std::path::PathBuf,
+    /// Symbols defined in the file, including child symbols (methods, properties).
+    pub symbols: usize,
+    /// Lines in the file, as recorded at parse time or else read from disk (0 when it
+    /// cannot be read).
+    pub lines: usize,
+    /// Distinct files importing this file.
+    pub fan_in: usize,
+    /// Distinct files this file imports.
+    pub fan_out: usize,
+}
+
+/// Number of lines in the file at `path`, or 0 when it cannot be read.
+fn count_lines(path: &std::path::Path) -> usize {
+    match std::fs::read(path) {
+        Ok(bytes) if bytes.is_empty() => 0,
+        Ok(bytes) => {
+            bytes.iter().filter(|&&b| b == b'\n').count() + usize::from(!bytes.ends_with(b"\n"))
+        }
+        Err(_) => 0,
+    }
+}
+
+/// Collect [`FileStats`] for every source file in the graph.
+pub fn file_stats(graph: &CodeGraph) -> Vec<FileStats> {
+    use std::collections::HashSet;
+
+    graph
+        .graph
+        .node_indices()
+        .filter_map(|idx| match &graph.graph[idx] {
+            GraphNode::File(fi) if fi.kind == crate::graph::node::FileKind::Source => {
+                Some((idx, fi))
+            }
+            _ => None,
+        })
+        .map(|(idx, fi)| {
+            let mut symbols = 0;
+            for edge in graph.graph.edges(idx) {
+                if matches!(edge.weight(), EdgeKind::Contains)
+                    && matches!(graph.graph[edge.target()], GraphNode::Symbol(_))
+                {
+                    symbols += 1 + graph
+                        .graph
+                        .edges_directed(edge.target(), Direction::Incoming)
+                        .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+                        .count();
+                }
+            }
+            let import_neighbors = |direction: Direction| -> usize {
+                graph
+                    .graph
+                    .edges_directed(idx, direction)
+                    .filter(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+                    .map(|e| match direction {
+                        Direction::Incoming => e.source(),
+                        Direction::Outgoing => e.target(),
+                    })
+                    .filter(|&other| other != idx)
+                    .collect::<HashSet<_>>()
+                    .len()
+            };
+            FileStats {
+                path: fi.path.clone(),
+                symbols,
+                lines: if fi.line_count > 0 {
+                    fi.line_count
+                } else {
+                    count_lines(&fi.path)
+                },
+                fan_in: import_neighbors(Direction::Incoming),
+                fan_out: import_neighbors(Direction::Outgoing),
+            }
+        })
+        .collect()
+}
+
+/// The `n` files ranking highest by `metric`, ties broken by path. Files scoring 0 are
+/// left out.
+pub fn top_files(
+    files: &[FileStats],
+    metric: impl Fn(&FileStats) -> usize,
+    n: usize,
+) -> Vec<FileStats> {
+    let mut ranked: Vec<&FileStats> = files.iter().filter(|f| metric(f) > 0).collect();
+    ranked.sort_by(|a, b| metric(b).cmp(&metric(a)).then_with(|| a.path.cmp(&b.path)));
+    ranked.into_iter().take(n).cloned().collect()
+}
+
 /// Build per-crate symbol stats by grouping files by their `crate_name` field.
 ///
 /// Returns an empty `Vec` if there are no Rust files with `crate_name` set, or if all
@@ -570,7 +1079,7 @@ mod tests {
         graph.add_non_parsed_file(PathBuf::from("logo.png"), FileKind::Asset);
         graph.add_non_parsed_file(PathBuf::from("LICENSE"), FileKind::Other);
 
-        let stats = project_stats(&graph);
+        let stats = project_stats(&graph, Path::new(""));
 
         assert_eq!(stats.file_count, 7, "total file count includes all files");
         assert_eq!(stats.source_files, 2, "source files only");
@@ -587,9 +1096,155 @@ mod tests {
         let mut graph = CodeGraph::new();
         graph.add_file(PathBuf::from("src/main.rs"), "rust");
 
-        let stats = project_stats(&graph);
+        let stats = project_stats(&graph, Path::new(""));
 
         assert_eq!(stats.source_files, 1);
         assert_eq!(stats.non_parsed_files, 0);
     }
+
+    #[test]
+    fn test_file_stats_and_top_files() {
+        use crate::graph::node::SymbolInfo;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let a_path = tmp.path().join("a.ts");
+        std::fs::write(&a_path, "export class A {\n  run() {}\n}").unwrap();
+        let mut graph = CodeGraph::new();
+        let a = graph.add_file(a_path.clone(), "typescript");
+        let b = graph.add_file(tmp.path().join("b.ts"), "typescript");
+        let c = graph.add_file(tmp.path().join("c.ts"), "typescript");
+        graph.add_non_parsed_file(tmp.path().join("README.md"), FileKind::Doc);
+        let class = graph.add_symbol(
+            a,
+            SymbolInfo {
+                name: "A".into(),
+                kind: SymbolKind::Class,
+                ..Default::default()
+            },
+        );
+        graph.add_child_symbol(
+            class,
+            SymbolInfo {
+                name: "run".into(),
+                kind: SymbolKind::Method,
+                ..Default::default()
+            },
+        );
+        graph.add_resolved_import(b, a, "./a");
+        graph.add_resolved_import(c, a, "./a");
+        graph.add_resolved_import(c, b, "./b");
+
+        let files = file_stats(&graph);
+        assert_eq!(files.len(), 3, "non-source files are skipped");
+        let a_stats = files.iter().find(|f| f.path == a_path).unwrap();
+        assert_eq!(
+            (
+                a_stats.symbols,
+                a_stats.lines,
+                a_stats.fan_in,
+                a_stats.fan_out
+            ),
+            (2, 3, 2, 0)
+        );
+
+        let by_fan_out = top_files(&files, |f| f.fan_out, 5);
+        let names: Vec<_> = by_fan_out
+            .iter()
+            .map(|f| f.path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["c.ts", "b.ts"], "zero scores are left out");
+        assert_eq!(top_files(&files, |f| f.fan_in, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_project_stats_for_files_drops_other_files() {
+        use crate::graph::node::SymbolInfo;
+
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let api = graph.add_file(root.join("api/server.ts"), "typescript");
+        let web = graph.add_file(root.join("web/app.ts"), "typescript");
+        graph.add_file(root.join("api/lib.rs"), "rust");
+        for (file, name) in [(api, "serve"), (web, "render")] {
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Function,
+                    ..Default::default()
+                },
+            );
+        }
+        graph.add_external_package(web, "react", "react");
+
+        let stats = project_stats_for_files(&graph, &root, |p| p.starts_with(root.join("api")));
+        assert_eq!((stats.file_count, stats.symbol_count), (2, 1));
+        assert_eq!(stats.external_packages, 0, "only web/ used react");
+
+        let stats =
+            project_stats_for_files(&graph, &root, |p| p.extension().is_some_and(|e| e == "ts"));
+        assert_eq!((stats.file_count, stats.functions), (2, 2));
+        assert_eq!(stats.external_packages, 1);
+    }
+
+    #[test]
+    fn test_project_stats_lines_and_largest() {
+        use crate::graph::node::SymbolInfo;
+
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(root.join("src/app.ts"), "typescript");
+        let db = graph.add_file(root.join("src/db.ts"), "typescript");
+        graph.set_file_source(app, 200, None);
+        graph.set_file_source(db, 40, None);
+        let service = graph.add_symbol(
+            app,
+            SymbolInfo {
+                name: "Service".into(),
+                kind: SymbolKind::Class,
+                line: 1,
+                line_end: 150,
+                ..Default::default()
+            },
+        );
+        graph.add_child_symbol(
+            service,
+            SymbolInfo {
+                name: "run".into(),
+                kind: SymbolKind::Method,
+                line: 10,
+                line_end: 89,
+                ..Default::default()
+            },
+        );
+        graph.add_symbol(
+            db,
+            SymbolInfo {
+                name: "connect".into(),
+                kind: SymbolKind::Function,
+                line: 5,
+                line_end: 14,
+                ..Default::default()
+            },
+        );
+
+        let stats = project_stats(&graph, &root);
+        assert_eq!(stats.total_lines, 240);
+        let files: Vec<(&str, usize)> = stats
+            .largest_files
+            .iter()
+            .map(|f| (f.file.as_str(), f.lines))
+            .collect();
+        assert_eq!(files, vec![("src/app.ts", 200), ("src/db.ts", 40)]);
+        let functions: Vec<(&str, &str, usize)> = stats
+            .largest_functions
+            .iter()
+            .map(|f| (f.name.as_deref().unwrap(), f.file.as_str(), f.lines))
+            .collect();
+        assert_eq!(
+            functions,
+            vec![("run", "src/app.ts", 80), ("connect", "src/db.ts", 10)],
+            "classes are not functions"
+        );
+    }
 }
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 
 use crate::graph::{
@@ -32,6 +34,12 @@ pub enum StructureNode {
         name: String,
         kind_tag: String, // "doc", "config", "ci", "asset", "other"
     },
+    /// A Rust module with the top-level symbols of its file and its submodules.
+    Module {
+        name: String,
+        symbols: Vec<StructureSymbol>,
+        children: Vec<StructureNode>,
+    },
     /// Truncation marker when depth limit is hit.
     Truncated {
         count: usize, // number of items not shown
@@ -43,7 +51,7 @@ pub enum StructureNode {
 pub struct StructureSymbol {
     pub name: String,
     pub kind: String,       // "fn", "struct", "trait", etc.
-    pub visibility: String, // "pub", "pub(crate)", "private"
+    pub visibility: String, // "pub", "pub(crate)", "private", "protected"
 }
 
 // ---------------------------------------------------------------------------
@@ -66,6 +74,7 @@ fn visibility_label(vis: &SymbolVisibility) -> &'static str {
         SymbolVisibility::Pub => "pub",
         SymbolVisibility::PubCrate => "pub(crate)",
         SymbolVisibility::Private => "private",
+        SymbolVisibility::Protected => "protected",
     }
 }
 
@@ -241,6 +250,81 @@ pub fn file_structure(
     build_tree(graph, &paths, depth)
 }
 
+/// Build one `Module` entry for `module_idx`, named `name`, recursing into submodules.
+fn build_module(
+    graph: &CodeGraph,
+    module_idx: NodeIndex,
+    name: String,
+    depth: usize,
+) -> StructureNode {
+    let mut symbols = Vec::new();
+    let mut submodules: Vec<(String, NodeIndex)> = Vec::new();
+    for edge in graph
+        .graph
+        .edges_directed(module_idx, Direction::Incoming)
+        .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+    {
+        match &graph.graph[edge.source()] {
+            GraphNode::File(_) => symbols.extend(collect_symbols(graph, edge.source())),
+            GraphNode::Module(info) => submodules.push((info.name().to_string(), edge.source())),
+            _ => {}
+        }
+    }
+    symbols.sort_by(|a, b| a.name.cmp(&b.name));
+    submodules.sort();
+
+    let children = if depth == 0 {
+        if submodules.is_empty() {
+            Vec::new()
+        } else {
+            vec![StructureNode::Truncated {
+                count: submodules.len(),
+            }]
+        }
+    } else {
+        submodules
+            .into_iter()
+            .map(|(name, idx)| build_module(graph, idx, name, depth - 1))
+            .collect()
+    };
+    StructureNode::Module {
+        name,
+        symbols,
+        children,
+    }
+}
+
+/// Build the Rust module tree (`crate::module::submodule`) from the graph's `Module` nodes.
+///
+/// - `module`: optional crate-qualified module path to scope to (e.g. `my_crate::parser`);
+///   if `None`, every crate root is listed.
+/// - `depth`: maximum submodule levels to recurse below the listed modules.
+pub fn module_structure(
+    graph: &CodeGraph,
+    module: Option<&str>,
+    depth: usize,
+) -> Vec<StructureNode> {
+    let mut roots: Vec<(String, NodeIndex)> = match module {
+        Some(path) => graph
+            .module_index
+            .get(path)
+            .map(|&idx| (path.to_string(), idx))
+            .into_iter()
+            .collect(),
+        None => graph
+            .module_index
+            .iter()
+            .filter(|(path, _)| !path.contains("::"))
+            .map(|(path, &idx)| (path.clone(), idx))
+            .collect(),
+    };
+    roots.sort();
+    roots
+        .into_iter()
+        .map(|(name, idx)| build_module(graph, idx, name, depth))
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -474,4 +558,42 @@ mod tests {
             "Should contain Cargo.toml with kind tag"
         );
     }
+
+    #[test]
+    fn test_module_structure() {
+        let mut graph = CodeGraph::new();
+        let root = PathBuf::from("/tmp/test_project");
+        let lib = graph.add_file(root.join("src/lib.rs"), "rust");
+        let imports = graph.add_file(root.join("src/parser/imports.rs"), "rust");
+        graph.add_symbol(
+            lib,
+            make_symbol("run", SymbolKind::Function, SymbolVisibility::Pub),
+        );
+        graph.add_symbol(
+            imports,
+            make_symbol("ImportInfo", SymbolKind::Struct, SymbolVisibility::Pub),
+        );
+        let crate_idx = graph.add_module("my_crate", "my_crate");
+        let imports_mod = graph.add_module("my_crate::parser::imports", "my_crate");
+        graph.set_file_module(lib, crate_idx);
+        graph.set_file_module(imports, imports_mod);
+
+        let tree = module_structure(&graph, None, 3);
+        let output = format_structure_to_string(&tree, &root);
+        assert_eq!(
+            output,
+            "my_crate::\n  pub run (function)\n  parser::\n    imports::\n      pub ImportInfo (struct)"
+        );
+
+        // Depth 1 stops below `parser`; scoping starts at the given module.
+        let shallow = format_structure_to_string(&module_structure(&graph, None, 1), &root);
+        assert!(
+            shallow.ends_with("parser::\n    ... (1 more items)"),
+            "{shallow}"
+        );
+        let scoped = module_structure(&graph, Some("my_crate::parser::imports"), 3);
+        assert!(
+            matches!(&scoped[0], StructureNode::Module { name, .. } if name == "my_crate::parser::imports")
+        );
+    }
 }
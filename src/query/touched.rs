@@ -0,0 +1,441 @@
+//! Symbol-level view of a commit or range (`code-graph touched <rev>`).
+//!
+//! The hunks of `git diff --unified=0` are mapped onto the symbol ranges of both sides:
+//! old-side hunks onto the graph of the base revision, new-side hunks onto the graph of
+//! the target revision, each indexed in a temporary worktree. A touched symbol present on
+//! both sides (same file, name, and kind; renamed files are followed) is modified, one only
+//! on the new side added, one only on the old side removed. When a change falls inside a
+//! method, the method is reported, not its class.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+use crate::query::diff::{git_rev_parse, with_revision_graph};
+use crate::query::find::kind_to_str;
+use crate::query::refs::find_refs;
+use crate::query::util::dedup_impl_method_twins;
+
+/// One symbol a commit touched.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TouchedSymbol {
+    pub name: String,
+    pub kind: String,
+    /// `added`, `removed`, or `modified`.
+    pub change: &'static str,
+    /// Project-relative path, on the new side unless the symbol was removed.
+    pub file: String,
+    /// 1-based declaration line on the same side as `file`.
+    pub line: usize,
+    /// References to the symbol: after the change, or before it for removed symbols.
+    pub refs: usize,
+}
+
+/// Result of [`touched_symbols`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TouchedReport {
+    pub from: String,
+    pub to: String,
+    /// Files the diff changes.
+    pub files: usize,
+    /// Touched symbols sorted by file and line.
+    pub symbols: Vec<TouchedSymbol>,
+}
+
+/// Changed line ranges of one file, from `git diff --unified=0`.
+#[derive(Debug, Default, PartialEq)]
+struct FileDiff {
+    /// Path on the old side; `None` for an added file.
+    old_path: Option<String>,
+    /// Path on the new side; `None` for a deleted file.
+    new_path: Option<String>,
+    /// Inclusive 1-based line ranges removed or replaced on the old side.
+    old_ranges: Vec<(usize, usize)>,
+    /// Inclusive 1-based line ranges added or replaced on the new side.
+    new_ranges: Vec<(usize, usize)>,
+}
+
+/// (new-side file, name, kind): how a symbol is matched across the two sides.
+type SymbolKey = (String, String, String);
+
+/// A symbol found on one side of the diff.
+struct SideSymbol {
+    key: SymbolKey,
+    file: String,
+    line: usize,
+    refs: usize,
+}
+
+/// Git's empty tree, the base of a root commit.
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Split `rev` into base and target revisions: `a..b` and `a...b` (from the merge base)
+/// as given, a single commit against its first parent, or against [`EMPTY_TREE`] when it
+/// has none.
+fn split_range(root: &Path, rev: &str) -> Result<(String, String)> {
+    if let Some((a, b)) = rev.split_once("...") {
+        let b = if b.is_empty() { "HEAD" } else { b };
+        let output = std::process::Command::new("git")
+            .args(["merge-base", a, b])
+            .current_dir(root)
+            .output()
+            .context("failed to run git. Ensure git is in PATH.")?;
+        if !output.status.success() {
+            bail!("no merge base between '{}' and '{}'", a, b);
+        }
+        let base = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return Ok((base, b.to_string()));
+    }
+    if let Some((a, b)) = rev.split_once("..") {
+        let b = if b.is_empty() { "HEAD" } else { b };
+        return Ok((a.to_string(), b.to_string()));
+    }
+    let parent = format!("{}^", rev);
+    if git_rev_parse(root, &parent).is_none() && git_rev_parse(root, rev).is_some() {
+        return Ok((EMPTY_TREE.to_string(), rev.to_string()));
+    }
+    Ok((parent, rev.to_string()))
+}
+
+/// `start,count` from a hunk header side; a missing count is 1.
+fn parse_range(spec: &str) -> Option<(usize, usize)> {
+    let (start, count) = match spec.split_once(',') {
+        Some((s, c)) => (s.parse().ok()?, c.parse().ok()?),
+        None => (spec.parse().ok()?, 1),
+    };
+    (count > 0).then(|| (start, start + count - 1))
+}
+
+/// Parse `git diff --unified=0` output into per-file changed ranges.
+fn parse_diff(text: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    for line in text.lines() {
+        if line.starts_with("diff --git ") {
+            files.push(FileDiff::default());
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if let Some(path) = line.strip_prefix("--- ") {
+            file.old_path = path.strip_prefix("a/").map(str::to_string);
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            file.new_path = path.strip_prefix("b/").map(str::to_string);
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            file.old_path = Some(path.to_string());
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            file.new_path = Some(path.to_string());
+        } else if let Some(header) = line.strip_prefix("@@ ") {
+            let mut parts = header.split_whitespace();
+            let old = parts.next().and_then(|p| p.strip_prefix('-'));
+            let new = parts.next().and_then(|p| p.strip_prefix('+'));
+            file.old_ranges.extend(old.and_then(parse_range));
+            file.new_ranges.extend(new.and_then(parse_range));
+        }
+    }
+    files
+}
+
+/// Symbols of `path` (relative to `tree_root`) that overlap `ranges`, innermost only, and
+/// the keys of every symbol of the file. `key_file` names the file in the keys.
+fn side_symbols(
+    graph: &CodeGraph,
+    tree_root: &Path,
+    path: &str,
+    key_file: &str,
+    ranges: &[(usize, usize)],
+    also: &HashSet<SymbolKey>,
+) -> (Vec<SideSymbol>, HashSet<SymbolKey>) {
+    let Some(&file_idx) = graph.file_index.get(&tree_root.join(path)) else {
+        return (Vec::new(), HashSet::new());
+    };
+    let symbols: Vec<NodeIndex> = graph
+        .graph
+        .edges_directed(file_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .map(|e| e.target())
+        .collect();
+    let key_of = |idx: NodeIndex| match &graph.graph[idx] {
+        GraphNode::Symbol(s) => Some((
+            key_file.to_string(),
+            s.name.clone(),
+            kind_to_str(&s.kind).to_string(),
+        )),
+        _ => None,
+    };
+    let keys: HashSet<SymbolKey> = symbols.iter().filter_map(|&s| key_of(s)).collect();
+
+    let hit: HashSet<NodeIndex> = symbols
+        .iter()
+        .copied()
+        .filter(|&idx| {
+            let GraphNode::Symbol(s) = &graph.graph[idx] else {
+                return false;
+            };
+            let end = s.line_end.max(s.line);
+            ranges.iter().any(|&(a, b)| a <= end && s.line <= b)
+                || key_of(idx).is_some_and(|k| also.contains(&k))
+        })
+        .collect();
+    // A class is not reported when the change is inside one of its touched members.
+    let has_touched_child = |idx: NodeIndex| {
+        graph
+            .graph
+            .edges_directed(idx, Direction::Incoming)
+            .any(|e| matches!(e.weight(), EdgeKind::ChildOf) && hit.contains(&e.source()))
+    };
+    let found = hit
+        .iter()
+        .filter(|&&idx| !has_touched_child(idx))
+        .filter_map(|&idx| {
+            let GraphNode::Symbol(s) = &graph.graph[idx] else {
+                return None;
+            };
+            Some(SideSymbol {
+                key: key_of(idx)?,
+                file: path.to_string(),
+                line: s.line,
+                refs: find_refs(graph, &s.name, &[idx], tree_root).len(),
+            })
+        })
+        .collect();
+    (found, keys)
+}
+
+/// List the symbols added, removed, and modified by the commit `rev` (against its first
+/// parent) or the range `a..b` / `a...b`, with their reference counts.
+pub fn touched_symbols(root: &Path, rev: &str, verbose: bool) -> Result<TouchedReport> {
+    let (from, to) = split_range(root, rev)?;
+    let output = std::process::Command::new("git")
+        .args([
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "-M",
+            "--relative",
+            &from,
+            &to,
+        ])
+        .current_dir(root)
+        .output()
+        .context("failed to run git. Ensure git is in PATH.")?;
+    if !output.status.success() {
+        bail!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let diffs = parse_diff(&String::from_utf8_lossy(&output.stdout));
+
+    // Keys are named by the new-side path so renamed files match.
+    let key_file = |d: &FileDiff| {
+        d.new_path
+            .clone()
+            .or(d.old_path.clone())
+            .unwrap_or_default()
+    };
+    // The empty tree has no files to index: a root commit only adds.
+    let (old_touched, old_keys) = if from == EMPTY_TREE {
+        (Vec::new(), HashSet::new())
+    } else {
+        with_revision_graph(root, &from, verbose, |graph, tree_root| {
+            let mut touched = Vec::new();
+            let mut keys = HashSet::new();
+            for d in &diffs {
+                let Some(path) = &d.old_path else {
+                    continue;
+                };
+                // Every symbol of a deleted file is touched.
+                let ranges = if d.new_path.is_none() {
+                    vec![(1, usize::MAX)]
+                } else {
+                    d.old_ranges.clone()
+                };
+                let (found, all) = side_symbols(
+                    graph,
+                    tree_root,
+                    path,
+                    &key_file(d),
+                    &ranges,
+                    &HashSet::new(),
+                );
+                touched.extend(found);
+                keys.extend(all);
+            }
+            (touched, keys)
+        })?
+    };
+
+    let old_touched_keys: HashSet<SymbolKey> = old_touched.iter().map(|s| s.key.clone()).collect();
+    let (new_touched, new_keys) = with_revision_graph(root, &to, verbose, |graph, tree_root| {
+        let mut touched = Vec::new();
+        let mut keys = HashSet::new();
+        for d in &diffs {
+            let Some(path) = &d.new_path else {
+                continue;
+            };
+            let ranges = if d.old_path.is_none() {
+                vec![(1, usize::MAX)]
+            } else {
+                d.new_ranges.clone()
+            };
+            // Symbols whose old body changed (a pure deletion) are touched on this side too.
+            let (found, all) = side_symbols(
+                graph,
+                tree_root,
+                path,
+                &key_file(d),
+                &ranges,
+                &old_touched_keys,
+            );
+            touched.extend(found);
+            keys.extend(all);
+        }
+        (touched, keys)
+    })?;
+
+    let mut symbols: Vec<TouchedSymbol> = Vec::new();
+    let mut push = |s: SideSymbol, change: &'static str| {
+        let (_, name, kind) = s.key;
+        symbols.push(TouchedSymbol {
+            name,
+            kind,
+            change,
+            file: s.file,
+            line: s.line,
+            refs: s.refs,
+        });
+    };
+    for s in new_touched {
+        let change = if old_keys.contains(&s.key) {
+            "modified"
+        } else {
+            "added"
+        };
+        push(s, change);
+    }
+    for s in old_touched {
+        if !new_keys.contains(&s.key) {
+            push(s, "removed");
+        }
+    }
+
+    symbols.sort_by(|a, b| {
+        (&a.file, a.line, a.change, &a.name).cmp(&(&b.file, b.line, b.change, &b.name))
+    });
+    dedup_impl_method_twins(&mut symbols, |s| {
+        ((s.file.clone(), s.line, s.change), s.name.as_str())
+    });
+    Ok(TouchedReport {
+        from,
+        to,
+        files: diffs.len(),
+        symbols,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diff_ranges_and_renames() {
+        let text = "\
+diff --git a/src/a.ts b/src/a.ts
+index 1..2 100644
+--- a/src/a.ts
++++ b/src/a.ts
+@@ -3 +3,2 @@ export function a() {
+@@ -10,2 +11,0 @@
+diff --git a/old.ts b/new.ts
+similarity index 90%
+rename from old.ts
+rename to new.ts
+--- a/old.ts
++++ b/new.ts
+@@ -1,0 +2 @@
+diff --git a/gone.ts b/gone.ts
+deleted file mode 100644
+--- a/gone.ts
++++ /dev/null
+@@ -1,4 +0,0 @@
+";
+        let diffs = parse_diff(text);
+        assert_eq!(diffs.len(), 3);
+        assert_eq!(
+            diffs[0],
+            FileDiff {
+                old_path: Some("src/a.ts".into()),
+                new_path: Some("src/a.ts".into()),
+                old_ranges: vec![(3, 3), (10, 11)],
+                new_ranges: vec![(3, 4)],
+            }
+        );
+        assert_eq!(diffs[1].old_path.as_deref(), Some("old.ts"));
+        assert_eq!(diffs[1].new_path.as_deref(), Some("new.ts"));
+        assert_eq!(diffs[1].old_ranges, vec![]);
+        assert_eq!(diffs[1].new_ranges, vec![(2, 2)]);
+        assert_eq!(diffs[2].new_path, None);
+        assert_eq!(diffs[2].old_ranges, vec![(1, 4)]);
+    }
+
+    #[test]
+    fn test_touched_symbols_of_a_commit() {
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let root = tmp.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(root)
+                .output()
+                .expect("git should run");
+            assert!(status.status.success(), "git {:?} failed", args);
+        };
+        std::fs::write(
+            root.join("a.ts"),
+            "export function keep() {\n  return 1;\n}\n\nexport function edit() {\n  return 2;\n}\n\nexport function drop() {\n  return 3;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("b.ts"), "import { edit } from './a';\nedit();\n").unwrap();
+        git(&["init", "-q"]);
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "first"]);
+        std::fs::write(
+            root.join("a.ts"),
+            "export function keep() {\n  return 1;\n}\n\nexport function edit() {\n  return 20;\n}\n\nexport function fresh() {\n  return 4;\n}\n",
+        )
+        .unwrap();
+        git(&["commit", "-q", "-am", "second"]);
+
+        // The root commit has no parent: it is diffed against the empty tree.
+        let report = touched_symbols(root, "HEAD^", false).unwrap();
+        assert_eq!(report.files, 2);
+        assert!(report.symbols.iter().all(|s| s.change == "added"));
+        assert_eq!(report.symbols.len(), 3);
+
+        let report = touched_symbols(root, "HEAD", false).unwrap();
+        assert_eq!(report.files, 1);
+        let symbols: Vec<String> = report
+            .symbols
+            .iter()
+            .map(|s| format!("{} {} {}:{} {}", s.change, s.name, s.file, s.line, s.refs))
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                "modified edit a.ts:5 2",
+                "added fresh a.ts:9 0",
+                "removed drop a.ts:9 0",
+            ]
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Declared dependencies nothing imports (`code-graph unused-deps`).
+//!
+//! Every workspace package is checked on its own: each npm/yarn/pnpm package (and the root
+//! `package.json`) against the `ExternalPackage` imports of the source files under its
+//! directory, and each Cargo crate against those of its files. A dependency also counts as
+//! used when:
+//!
+//! - npm: a `scripts` entry of its `package.json` names it (CLI tools such as `eslint`);
+//! - Cargo: a file of the crate (or `build.rs`, for build dependencies) names it by path
+//!   without a `use` (`serde_json::to_value`, `#[derive(serde::Serialize)]`).
+//!
+//! `@types/*` packages are never reported: the compiler loads them, nothing imports them.
+//! Workspace-internal dependencies (`workspace:`, `file:`, `link:`, other crates of the
+//! workspace) are skipped.
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+use crate::query::duplicates::NPM_DEPENDENCY_TABLES;
+use crate::query::imports::retain_unreferenced;
+use crate::resolver::cargo_features::CrateManifest;
+use crate::resolver::cargo_workspace::discover_workspace_manifests;
+use crate::resolver::workspace::discover_workspace_package_jsons;
+
+/// A declared dependency no file of its package uses.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+pub struct UnusedDependency {
+    /// Declaring package (`package.json` or `[package]` name).
+    pub package: String,
+    /// Project-relative path of the manifest.
+    pub manifest: String,
+    /// Dependency name as declared.
+    pub name: String,
+    /// Manifest table: `dependencies`, `devDependencies`, `dev-dependencies`, ...
+    pub section: String,
+}
+
+/// Result of [`find_unused_dependencies`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct UnusedDepsReport {
+    /// Manifests checked.
+    pub packages: usize,
+    /// External dependencies declared across them.
+    pub declared: usize,
+    /// Unused ones, sorted by manifest, then name.
+    pub unused: Vec<UnusedDependency>,
+}
+
+/// Compare every workspace manifest under `project_root` with the external packages its
+/// files import.
+pub fn find_unused_dependencies(graph: &CodeGraph, project_root: &Path) -> UnusedDepsReport {
+    let mut report = UnusedDepsReport::default();
+    npm_unused(graph, project_root, &mut report);
+    cargo_unused(graph, project_root, &mut report);
+    report.unused.sort_by(|a, b| {
+        (&a.manifest, &a.name, &a.section).cmp(&(&b.manifest, &b.name, &b.section))
+    });
+    report
+}
+
+fn relative(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Names of the external packages `files` import.
+fn imported_packages(graph: &CodeGraph, files: &[NodeIndex]) -> HashSet<String> {
+    files
+        .iter()
+        .flat_map(|&f| graph.graph.edges(f))
+        .filter(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+        .filter_map(|e| match &graph.graph[e.target()] {
+            GraphNode::ExternalPackage(pkg) => Some(pkg.name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn npm_unused(graph: &CodeGraph, project_root: &Path, report: &mut UnusedDepsReport) {
+    let mut manifests = discover_workspace_package_jsons(project_root);
+    manifests.push(project_root.join("package.json"));
+    let mut manifests: Vec<(PathBuf, serde_json::Value)> = manifests
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|path| {
+            let json = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())?;
+            Some((path, json))
+        })
+        .collect();
+    // Deepest first, so a file belongs to the nearest package.
+    manifests.sort_by_key(|(path, _)| std::cmp::Reverse(path.components().count()));
+    let dirs: Vec<&Path> = manifests
+        .iter()
+        .map(|(path, _)| path.parent().unwrap_or(project_root))
+        .collect();
+
+    let mut files: Vec<Vec<NodeIndex>> = vec![Vec::new(); manifests.len()];
+    for &idx in graph.file_index.values() {
+        if let GraphNode::File(fi) = &graph.graph[idx]
+            && fi.kind == FileKind::Source
+            && let Some(owner) = dirs.iter().position(|d| fi.path.starts_with(d))
+        {
+            files[owner].push(idx);
+        }
+    }
+
+    for ((path, json), files) in manifests.iter().zip(&files) {
+        let imported = imported_packages(graph, files);
+        let scripts: Vec<&str> = json["scripts"]
+            .as_object()
+            .map(|s| s.values().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        let in_scripts = |name: &str| {
+            scripts.iter().any(|script| {
+                script
+                    .split(|c: char| c.is_whitespace() || matches!(c, '&' | '|' | ';' | '('))
+                    .any(|word| word == name)
+            })
+        };
+        let package = json["name"].as_str().unwrap_or("(root)");
+        let mut declared_any = false;
+        for table in NPM_DEPENDENCY_TABLES {
+            let Some(deps) = json[*table].as_object() else {
+                continue;
+            };
+            for (name, spec) in deps {
+                let spec = spec.as_str().unwrap_or_default();
+                if ["workspace:", "file:", "link:"]
+                    .iter()
+                    .any(|p| spec.starts_with(p))
+                {
+                    continue;
+                }
+                declared_any = true;
+                report.declared += 1;
+                if name.starts_with("@types/") || imported.contains(name) || in_scripts(name) {
+                    continue;
+                }
+                report.unused.push(UnusedDependency {
+                    package: package.to_string(),
+                    manifest: relative(path, project_root),
+                    name: name.clone(),
+                    section: table.to_string(),
+                });
+            }
+        }
+        if declared_any {
+            report.packages += 1;
+        }
+    }
+}
+
+fn cargo_unused(graph: &CodeGraph, project_root: &Path, report: &mut UnusedDepsReport) {
+    let crates: Vec<CrateManifest> = discover_workspace_manifests(project_root)
+        .iter()
+        .filter_map(|path| CrateManifest::load(path))
+        .collect();
+    let members: HashSet<&str> = crates.iter().map(|c| c.name.as_str()).collect();
+
+    for krate in &crates {
+        let (files, paths): (Vec<NodeIndex>, Vec<PathBuf>) = graph
+            .file_index
+            .values()
+            .filter_map(|&idx| match &graph.graph[idx] {
+                GraphNode::File(fi) if fi.crate_name.as_deref() == Some(krate.name.as_str()) => {
+                    Some((idx, fi.path.clone()))
+                }
+                _ => None,
+            })
+            .unzip();
+        let imported = imported_packages(graph, &files);
+        let build_script = krate.manifest_path.with_file_name("build.rs");
+
+        let sections = [
+            ("dependencies", &krate.dependencies),
+            ("dev-dependencies", &krate.dev_dependencies),
+            ("build-dependencies", &krate.build_dependencies),
+        ];
+        let mut declared_any = false;
+        for (section, dependencies) in sections {
+            // Collected per section, then filtered by path references in one pass.
+            let mut unused: Vec<String> = Vec::new();
+            for dep in dependencies {
+                let name = dep.name.replace('-', "_");
+                if members.contains(name.as_str()) {
+                    continue;
+                }
+                declared_any = true;
+                report.declared += 1;
+                if !imported.contains(&name) {
+                    unused.push(name);
+                }
+            }
+            let mut scanned = paths.clone();
+            if section == "build-dependencies" {
+                scanned.push(build_script.clone());
+            }
+            retain_unreferenced(&mut unused, &scanned);
+            for name in unused {
+                let declared = dependencies
+                    .iter()
+                    .find(|d| d.name.replace('-', "_") == name)
+                    .map_or(name.clone(), |d| d.name.clone());
+                report.unused.push(UnusedDependency {
+                    package: krate.name.clone(),
+                    manifest: relative(&krate.manifest_path, project_root),
+                    name: declared,
+                    section: section.to_string(),
+                });
+            }
+        }
+        if declared_any {
+            report.packages += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_npm_packages_checked_against_their_own_imports() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{"name": "root", "workspaces": ["packages/*"],
+                "devDependencies": {"eslint": "^9", "prettier": "^3"},
+                "scripts": {"lint": "eslint ."}}"#,
+        )
+        .unwrap();
+        let web = root.join("packages/web");
+        fs::create_dir_all(&web).unwrap();
+        fs::write(
+            web.join("package.json"),
+            r#"{"name": "web",
+                "dependencies": {"react": "^18", "lodash": "^4", "core": "workspace:*"},
+                "devDependencies": {"@types/react": "^18"}}"#,
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(web.join("src/app.tsx"), "tsx");
+        graph.add_external_package(app, "react", "react");
+
+        let report = find_unused_dependencies(&graph, root);
+        let unused: Vec<(&str, &str, &str)> = report
+            .unused
+            .iter()
+            .map(|u| (u.manifest.as_str(), u.name.as_str(), u.section.as_str()))
+            .collect();
+        assert_eq!(
+            unused,
+            vec![
+                ("package.json", "prettier", "devDependencies"),
+                ("packages/web/package.json", "lodash", "dependencies"),
+            ]
+        );
+        assert_eq!(report.packages, 2);
+        assert_eq!(report.declared, 5);
+    }
+
+    #[test]
+    fn test_cargo_dependencies_used_by_import_or_path() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1\"\nserde-json = \"1\"\nregex = \"1\"\n\n[build-dependencies]\ncc = \"1\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/main.rs"),
+            "use serde::Serialize;\nfn main() { serde_json::json!({}); }\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let main = graph.add_file(root.join("src/main.rs"), "rust");
+        if let GraphNode::File(fi) = &mut graph.graph[main] {
+            fi.crate_name = Some("app".into());
+        }
+        graph.add_external_package(main, "serde", "serde::Serialize");
+
+        let report = find_unused_dependencies(&graph, root);
+        let unused: Vec<(&str, &str)> = report
+            .unused
+            .iter()
+            .map(|u| (u.name.as_str(), u.section.as_str()))
+            .collect();
+        assert_eq!(
+            unused,
+            vec![("cc", "build-dependencies"), ("regex", "dependencies")]
+        );
+    }
+}
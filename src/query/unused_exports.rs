@@ -0,0 +1,311 @@
+//! Exports nothing else in the project uses (`code-graph unused-exports`).
+//!
+//! Checks exported TS/JS symbols and `pub` Rust items. An item is used when another file
+//! binds it by name (`ImportsSymbol`), calls or renders it or one of its members,
+//! extends, implements or embeds it, or re-exports it under an alias. A TS/JS import of
+//! the file that binds no names (`import * as ns`) uses every export of the file.
+//!
+//! Rust type references and qualified paths (`config::Config`) are not edges in the
+//! graph, so a Rust item also counts as used when its name appears as an identifier in
+//! another Rust file.
+//!
+//! Unlike the exported tier of `dead-code`, every kind of item is checked, not just
+//! callables, and references from inside the declaring file do not count.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+use crate::query::api_diff::is_public;
+use crate::query::dead_code::{is_entry_point_file, is_entry_point_symbol};
+use crate::query::find::kind_to_str;
+use crate::query::util::{dedup_impl_method_twins, find_containing_file_idx};
+
+/// An exported item with no users outside its own file.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UnusedExport {
+    pub name: String,
+    pub kind: String,
+    /// Project-relative path of the declaring file.
+    pub file: String,
+    /// 1-based declaration line.
+    pub line: usize,
+}
+
+/// Result of [`find_unused_exports`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UnusedExportsReport {
+    /// Exported items checked.
+    pub checked: usize,
+    /// Items without users, sorted by file and line.
+    pub unused: Vec<UnusedExport>,
+}
+
+/// List the exported TS/JS symbols and `pub` Rust items under `scope` (relative to
+/// `root`; the whole project when `None`) that no other file uses.
+///
+/// Entry-point files (`index.ts`, `lib.rs`, `main.rs`, tests) are skipped: their exports
+/// are the package's surface, used from outside the project.
+pub fn find_unused_exports(
+    graph: &CodeGraph,
+    root: &Path,
+    scope: Option<&Path>,
+) -> UnusedExportsReport {
+    let abs_scope: Option<PathBuf> = scope.map(|s| {
+        if s.is_absolute() {
+            s.to_path_buf()
+        } else {
+            root.join(s)
+        }
+    });
+    let copies = crate::query::identical_files::identical_copies(graph);
+
+    // (file, symbol) of every exported item in scope.
+    let mut candidates: Vec<(NodeIndex, NodeIndex)> = Vec::new();
+    for &file_idx in graph.file_index.values() {
+        let GraphNode::File(fi) = &graph.graph[file_idx] else {
+            continue;
+        };
+        if !is_checked_language(&fi.language)
+            || is_entry_point_file(fi)
+            || copies.contains(&fi.path)
+            || abs_scope.as_ref().is_some_and(|s| !fi.path.starts_with(s))
+        {
+            continue;
+        }
+        for edge in graph.graph.edges_directed(file_idx, Direction::Outgoing) {
+            if matches!(edge.weight(), EdgeKind::Contains)
+                && let GraphNode::Symbol(sym) = &graph.graph[edge.target()]
+                && is_public(sym, &fi.language)
+                && !is_entry_point_symbol(sym, fi)
+            {
+                candidates.push((file_idx, edge.target()));
+            }
+        }
+    }
+
+    let rust_names: HashSet<&str> = candidates
+        .iter()
+        .filter_map(
+            |&(file, sym)| match (&graph.graph[file], &graph.graph[sym]) {
+                (GraphNode::File(fi), GraphNode::Symbol(s)) if fi.language == "rust" => {
+                    Some(identifier(&s.name))
+                }
+                _ => None,
+            },
+        )
+        .collect();
+    let rust_mentions = rust_mentions(graph, &rust_names);
+
+    let mut unused: Vec<UnusedExport> = Vec::new();
+    for &(file_idx, sym_idx) in &candidates {
+        let (GraphNode::File(fi), GraphNode::Symbol(sym)) =
+            (&graph.graph[file_idx], &graph.graph[sym_idx])
+        else {
+            continue;
+        };
+        let used = used_outside(graph, sym_idx, file_idx)
+            || if fi.language == "rust" {
+                rust_mentions
+                    .get(identifier(&sym.name))
+                    .is_some_and(|files| files.iter().any(|&f| f != file_idx))
+            } else {
+                is_namespace_imported(graph, file_idx)
+            };
+        if !used {
+            unused.push(UnusedExport {
+                name: sym.name.clone(),
+                kind: kind_to_str(&sym.kind).to_string(),
+                file: fi
+                    .path
+                    .strip_prefix(root)
+                    .unwrap_or(&fi.path)
+                    .to_string_lossy()
+                    .into_owned(),
+                line: sym.line,
+            });
+        }
+    }
+
+    unused.sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+    dedup_impl_method_twins(&mut unused, |u| ((u.file.clone(), u.line), u.name.as_str()));
+    UnusedExportsReport {
+        checked: candidates.len(),
+        unused,
+    }
+}
+
+fn is_checked_language(language: &str) -> bool {
+    matches!(language, "typescript" | "tsx" | "javascript" | "rust")
+}
+
+/// Whether `sym` or one of its members is referenced from a file other than `own_file`.
+fn used_outside(graph: &CodeGraph, sym: NodeIndex, own_file: NodeIndex) -> bool {
+    let members = std::iter::once(sym).chain(
+        graph
+            .graph
+            .edges_directed(sym, Direction::Incoming)
+            .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+            .map(|e| e.source()),
+    );
+    members
+        .flat_map(|m| graph.graph.edges_directed(m, Direction::Incoming))
+        .filter(|e| {
+            matches!(
+                e.weight(),
+                EdgeKind::ImportsSymbol { .. }
+                    | EdgeKind::Calls { .. }
+                    | EdgeKind::Renders
+                    | EdgeKind::Extends
+                    | EdgeKind::Implements
+                    | EdgeKind::Embeds
+                    | EdgeKind::ExportsAs { .. }
+            )
+        })
+        .filter_map(|e| match graph.graph[e.source()] {
+            GraphNode::File(_) => Some(e.source()),
+            _ => find_containing_file_idx(graph, e.source()),
+        })
+        .any(|f| f != own_file)
+}
+
+/// Whether another file imports `file` without binding any of its names: a namespace
+/// import, through which every export is reachable.
+fn is_namespace_imported(graph: &CodeGraph, file: NodeIndex) -> bool {
+    let binders: HashSet<NodeIndex> = graph
+        .graph
+        .edges_directed(file, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .flat_map(|e| graph.graph.edges_directed(e.target(), Direction::Incoming))
+        .filter(|e| matches!(e.weight(), EdgeKind::ImportsSymbol { .. }))
+        .map(|e| e.source())
+        .collect();
+    graph
+        .graph
+        .edges_directed(file, Direction::Incoming)
+        .any(|e| {
+            matches!(e.weight(), EdgeKind::ResolvedImport { .. })
+                && e.source() != file
+                && !binders.contains(&e.source())
+        })
+}
+
+/// The identifier an item is referred to by: `method` for `Type::method`.
+fn identifier(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Rust files in which each of `names` appears as an identifier.
+fn rust_mentions<'a>(
+    graph: &CodeGraph,
+    names: &HashSet<&'a str>,
+) -> HashMap<&'a str, HashSet<NodeIndex>> {
+    let mut mentions: HashMap<&str, HashSet<NodeIndex>> = HashMap::new();
+    if names.is_empty() {
+        return mentions;
+    }
+    for &file_idx in graph.file_index.values() {
+        let GraphNode::File(fi) = &graph.graph[file_idx] else {
+            continue;
+        };
+        if fi.language != "rust" {
+            continue;
+        }
+        let Ok(source) = std::fs::read_to_string(&fi.path) else {
+            continue;
+        };
+        for word in source.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+            if let Some(&name) = names.get(word) {
+                mentions.entry(name).or_default().insert(file_idx);
+            }
+        }
+    }
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind, SymbolVisibility};
+
+    fn exported(name: &str, line: usize) -> SymbolInfo {
+        SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line,
+            is_exported: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reports_exports_without_outside_users() {
+        let root = PathBuf::from("/p");
+        let mut graph = CodeGraph::new();
+        let util = graph.add_file(root.join("src/util.ts"), "typescript");
+        let app = graph.add_file(root.join("src/app.ts"), "typescript");
+        let used = graph.add_symbol(util, exported("used", 1));
+        let internal_only = graph.add_symbol(util, exported("internalOnly", 5));
+        let caller = graph.add_symbol(util, exported("caller", 9));
+        graph.add_symbol(util, exported("unused", 13));
+        graph.add_imports_symbol(app, used, 0, 0);
+        // A call from inside the declaring file does not count.
+        graph.graph.add_edge(
+            caller,
+            internal_only,
+            EdgeKind::Calls {
+                count: 1,
+                lines: vec![10],
+            },
+        );
+
+        let report = find_unused_exports(&graph, &root, None);
+        let names: Vec<&str> = report.unused.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["internalOnly", "caller", "unused"]);
+        assert_eq!(report.unused[0].file, "src/util.ts");
+        assert_eq!(report.checked, 4);
+
+        let scoped = find_unused_exports(&graph, &root, Some(Path::new("lib")));
+        assert_eq!(scoped.checked, 0);
+    }
+
+    #[test]
+    fn test_rust_pub_items_used_when_named_in_another_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("src/config.rs"),
+            "pub struct Config;\npub struct Unused;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/app.rs"),
+            "fn load() -> crate::config::Config { crate::config::Config }\n",
+        )
+        .unwrap();
+        let mut graph = CodeGraph::new();
+        let config = graph.add_file(root.join("src/config.rs"), "rust");
+        graph.add_file(root.join("src/app.rs"), "rust");
+        let public = |name: &str, line| SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Struct,
+            line,
+            visibility: SymbolVisibility::Pub,
+            ..Default::default()
+        };
+        graph.add_symbol(config, public("Config", 1));
+        graph.add_symbol(config, public("Unused", 2));
+
+        let report = find_unused_exports(&graph, root, None);
+        let names: Vec<&str> = report.unused.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["Unused"]);
+        assert_eq!(report.checked, 2);
+    }
+}
@@ -1,3 +1,8 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::path::Path;
+
 use petgraph::Direction;
 use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::EdgeRef;
@@ -30,3 +35,116 @@ pub(crate) fn find_containing_file_idx(graph: &CodeGraph, sym_idx: NodeIndex) ->
 
     None
 }
+
+/// The innermost symbol of `file_idx` whose lines span the 1-based `line`, searching
+/// top-level symbols and their children (class methods).
+pub(crate) fn innermost_symbol_at(
+    graph: &CodeGraph,
+    file_idx: NodeIndex,
+    line: usize,
+) -> Option<NodeIndex> {
+    let spans = |idx: NodeIndex| {
+        matches!(&graph.graph[idx],
+            GraphNode::Symbol(info) if info.line <= line && line <= info.line_end)
+    };
+    let mut found = graph
+        .graph
+        .edges_directed(file_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .map(|e| e.target())
+        .filter(|&idx| spans(idx))
+        .min_by_key(|&idx| match &graph.graph[idx] {
+            GraphNode::Symbol(info) => info.line_end - info.line,
+            _ => usize::MAX,
+        })?;
+    while let Some(child) = graph
+        .graph
+        .edges_directed(found, Direction::Incoming)
+        .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+        .map(|e| e.source())
+        .find(|&idx| spans(idx))
+    {
+        found = child;
+    }
+    Some(found)
+}
+
+/// Directory of `path` relative to `project_root` with `/` separators, or `.` for files
+/// at the root.
+pub(crate) fn relative_dir(path: &Path, project_root: &Path) -> String {
+    let rel = path.strip_prefix(project_root).unwrap_or(path);
+    match rel.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_string_lossy().replace('\\', "/"),
+        _ => ".".to_string(),
+    }
+}
+
+/// The top-level symbol a symbol belongs to: `sym_idx` itself, or the outermost parent
+/// reached through `ChildOf` edges.
+pub(crate) fn top_level_symbol(graph: &CodeGraph, sym_idx: NodeIndex) -> NodeIndex {
+    graph
+        .graph
+        .edges_directed(sym_idx, Direction::Outgoing)
+        .find(|e| matches!(e.weight(), EdgeKind::ChildOf))
+        .map_or(sym_idx, |e| top_level_symbol(graph, e.target()))
+}
+
+/// Whether `importer` may use any of `symbols` (top-level symbols of `target_file`).
+///
+/// When `importer` binds named imports to symbols of `target_file` (`ImportsSymbol` edges),
+/// only those bindings count. An importer without any binding into it (namespace,
+/// side-effect, or unbound imports) is assumed to use everything the file defines.
+pub(crate) fn imports_any_of(
+    graph: &CodeGraph,
+    importer: NodeIndex,
+    target_file: NodeIndex,
+    symbols: &HashSet<NodeIndex>,
+) -> bool {
+    let mut has_binding = false;
+    for edge_ref in graph.graph.edges_directed(importer, Direction::Outgoing) {
+        if matches!(edge_ref.weight(), EdgeKind::ImportsSymbol { .. })
+            && find_containing_file_idx(graph, edge_ref.target()) == Some(target_file)
+        {
+            if symbols.contains(&edge_ref.target()) {
+                return true;
+            }
+            has_binding = true;
+        }
+    }
+    !has_binding
+}
+
+/// Keep one entry per declaration among `items`, whose `key` is a declaration's position
+/// (file and line, plus whatever else the caller groups by) and its name.
+///
+/// The Rust parser reports impl methods both as `Type::method` and as a bare function at
+/// the same position. Entries at the same position whose names end in the same segment
+/// are twins; the qualified one (`Type::method`, `Type.method`) is kept. The order of the
+/// kept entries is unchanged.
+pub(crate) fn dedup_impl_method_twins<T, K: Eq + Hash>(
+    items: &mut Vec<T>,
+    key: impl Fn(&T) -> (K, &str),
+) {
+    let qualified = |name: &str| name.contains("::") || name.contains('.');
+    let mut kept: HashMap<(K, String), usize> = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        let (position, name) = key(item);
+        let bare = name.rsplit([':', '.']).next().unwrap_or(name).to_string();
+        match kept.entry((position, bare)) {
+            Entry::Vacant(slot) => {
+                slot.insert(i);
+            }
+            Entry::Occupied(mut slot) => {
+                if qualified(name) && !qualified(key(&items[*slot.get()]).1) {
+                    slot.insert(i);
+                }
+            }
+        }
+    }
+    let kept: HashSet<usize> = kept.into_values().collect();
+    let mut i = 0;
+    items.retain(|_| {
+        i += 1;
+        kept.contains(&(i - 1))
+    });
+}
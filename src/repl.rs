@@ -0,0 +1,758 @@
+//! Interactive session over one loaded graph (`code-graph repl`).
+//!
+//! The graph is loaded once and every command runs against it in memory, so exploratory
+//! `find` / `refs` / `impact` / `path` / `query` sequences skip the per-invocation cache
+//! load. `reload` picks up files changed since.
+//!
+//! On a terminal, input is read in raw mode with a small line editor: Tab completes
+//! command names and then symbol names (and file paths for `path`), Up/Down walk the
+//! session history, Ctrl-U clears the line, Ctrl-C discards it, and Ctrl-D on an empty
+//! line leaves. Piped input is read line by line, so a script of commands works too.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+#[cfg(unix)]
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use crate::cli::OutputFormat;
+use crate::graph::CodeGraph;
+use crate::graph::node::GraphNode;
+use crate::query;
+
+/// Completion candidates listed at most when Tab is ambiguous.
+const MAX_LISTED_CANDIDATES: usize = 40;
+
+/// Commands run against the graph loaded by `code-graph repl`.
+#[derive(Parser, Debug)]
+#[command(
+    name = "code-graph>",
+    no_binary_name = true,
+    override_usage = "<COMMAND> [ARGS]",
+    disable_version_flag = true
+)]
+struct ReplLine {
+    #[command(subcommand)]
+    command: ReplCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum ReplCommand {
+    /// Find symbol definitions by name or regex.
+    Find {
+        pattern: String,
+        #[arg(short = 'i', long)]
+        case_insensitive: bool,
+        /// Filter by symbol kind (comma-separated).
+        #[arg(long, value_delimiter = ',')]
+        kind: Vec<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Find all references to a symbol.
+    Refs {
+        symbol: String,
+        #[arg(short = 'i', long)]
+        case_insensitive: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Show the files affected by changing a symbol.
+    Impact {
+        symbol: String,
+        #[arg(short = 'i', long)]
+        case_insensitive: bool,
+        /// Show the dependency chain as a tree.
+        #[arg(long)]
+        tree: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Show the shortest dependency paths between two files or symbols.
+    Path {
+        from: String,
+        to: String,
+        #[arg(long, default_value_t = 3)]
+        max_paths: usize,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Filter symbols or files with a query expression.
+    Query {
+        query: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Compact)]
+        format: OutputFormat,
+    },
+
+    /// Refresh the graph with the files changed since it was loaded.
+    Reload,
+
+    /// Leave the session.
+    #[command(alias = "quit")]
+    Exit,
+}
+
+/// Command names completed in the first word.
+const COMMANDS: &[&str] = &[
+    "exit", "find", "help", "impact", "path", "query", "quit", "refs", "reload",
+];
+
+/// Split a line into words: whitespace-separated, with single or double quotes grouping
+/// a word that contains spaces.
+fn split_words(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            chars.next();
+            if c == '\'' || c == '"' {
+                let mut closed = false;
+                for ch in chars.by_ref() {
+                    if ch == c {
+                        closed = true;
+                        break;
+                    }
+                    word.push(ch);
+                }
+                if !closed {
+                    anyhow::bail!("unterminated quote");
+                }
+            } else {
+                word.push(c);
+            }
+        }
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Names Tab completes against, sorted and deduplicated.
+struct Completions {
+    symbols: Vec<String>,
+    files: Vec<String>,
+}
+
+impl Completions {
+    fn new(graph: &CodeGraph, project_root: &Path) -> Self {
+        let mut symbols: Vec<String> = graph
+            .symbol_index
+            .keys()
+            .chain(graph.alias_index.keys())
+            .cloned()
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        let mut files: Vec<String> = graph
+            .file_index
+            .iter()
+            .filter(|(_, idx)| matches!(graph.graph[**idx], GraphNode::File(_)))
+            .map(|(path, _)| {
+                path.strip_prefix(project_root)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        files.sort();
+        Self { symbols, files }
+    }
+
+    /// Candidates for the last word of `line`: command names for the first word, and
+    /// otherwise symbol names, plus file paths after `path`.
+    fn candidates(&self, line: &str) -> Vec<&str> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let at_new_word = line.is_empty() || line.ends_with(char::is_whitespace);
+        let prefix = if at_new_word {
+            ""
+        } else {
+            words.last().copied().unwrap_or("")
+        };
+        let first_word = words.is_empty() || (words.len() == 1 && !at_new_word);
+        if first_word {
+            return COMMANDS
+                .iter()
+                .copied()
+                .filter(|c| c.starts_with(prefix))
+                .collect();
+        }
+        if prefix.starts_with('-') {
+            return Vec::new();
+        }
+        let mut candidates = with_prefix(&self.symbols, prefix);
+        if words.first() == Some(&"path") {
+            candidates.extend(with_prefix(&self.files, prefix));
+        }
+        candidates
+    }
+}
+
+/// The sorted `names` starting with `prefix`.
+fn with_prefix<'a>(names: &'a [String], prefix: &str) -> Vec<&'a str> {
+    let start = names.partition_point(|n| n.as_str() < prefix);
+    names[start..]
+        .iter()
+        .take_while(|n| n.starts_with(prefix))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Longest prefix shared by all `names`.
+fn common_prefix<'a>(names: &[&'a str]) -> &'a str {
+    let Some(first) = names.first() else {
+        return "";
+    };
+    let mut len = first.len();
+    for name in &names[1..] {
+        len = first
+            .char_indices()
+            .zip(name.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(len);
+    }
+    &first[..len]
+}
+
+/// Run the session until `exit` or end of input.
+pub fn run(project_root: &Path, graph: CodeGraph) -> Result<()> {
+    let mut graph = graph;
+    let mut completions = Completions::new(&graph, project_root);
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    if interactive {
+        println!(
+            "code-graph repl: {} files, {} symbols. Type `help` for commands, Tab to complete.",
+            graph.file_index.len(),
+            graph.symbol_count()
+        );
+    }
+    let mut history: Vec<String> = Vec::new();
+    // Piped input only: the line editor takes the stdin lock itself for each line.
+    let mut lines = (!interactive).then(|| io::stdin().lock().lines());
+
+    loop {
+        let line = if interactive {
+            match read_line_raw("code-graph> ", &history, &completions)? {
+                Some(line) => line,
+                None => break,
+            }
+        } else {
+            match lines.as_mut().and_then(Iterator::next) {
+                Some(line) => line?,
+                None => break,
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if history.last().map(String::as_str) != Some(line) {
+            history.push(line.to_string());
+        }
+
+        let words = match split_words(line) {
+            Ok(words) => words,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                continue;
+            }
+        };
+        let command = match ReplLine::try_parse_from(&words) {
+            Ok(parsed) => parsed.command,
+            Err(err) => {
+                let _ = err.print();
+                continue;
+            }
+        };
+        match command {
+            ReplCommand::Exit => break,
+            // A failed reload keeps the session on the graph it had.
+            ReplCommand::Reload => match crate::cache::load_or_build(project_root, false) {
+                Ok(reloaded) => {
+                    graph = reloaded;
+                    completions = Completions::new(&graph, project_root);
+                    println!(
+                        "reloaded: {} files, {} symbols",
+                        graph.file_index.len(),
+                        graph.symbol_count()
+                    );
+                }
+                Err(err) => eprintln!("error: reload failed: {:#}", err),
+            },
+            command => {
+                if let Err(err) = execute(&graph, project_root, command) {
+                    eprintln!("error: {}", err);
+                }
+                crate::pager::finish();
+            }
+        }
+        let _ = io::stdout().flush();
+    }
+    Ok(())
+}
+
+/// Run one query command against the loaded graph.
+fn execute(graph: &CodeGraph, project_root: &Path, command: ReplCommand) -> Result<()> {
+    let arrangement = query::output::Arrangement::default();
+    match command {
+        ReplCommand::Find {
+            pattern,
+            case_insensitive,
+            kind,
+            format,
+        } => {
+            let results = query::find::find_symbol(
+                graph,
+                &pattern,
+                case_insensitive,
+                &kind,
+                None,
+                project_root,
+                None,
+            )?;
+            if results.is_empty() {
+                eprintln!("no symbols matching '{}' found", pattern);
+            } else {
+                query::output::format_find_results(
+                    &results,
+                    &format,
+                    project_root,
+                    &pattern,
+                    &arrangement,
+                );
+            }
+        }
+        ReplCommand::Refs {
+            symbol,
+            case_insensitive,
+            format,
+        } => {
+            let indices = matched_indices(graph, &symbol, case_insensitive)?;
+            let results = query::refs::find_refs(graph, &symbol, &indices, project_root);
+            if results.is_empty() {
+                eprintln!("no references to '{}' found", symbol);
+            } else {
+                query::output::format_refs_results(
+                    &results,
+                    &format,
+                    project_root,
+                    &symbol,
+                    &arrangement,
+                );
+            }
+        }
+        ReplCommand::Impact {
+            symbol,
+            case_insensitive,
+            tree,
+            format,
+        } => {
+            let indices = matched_indices(graph, &symbol, case_insensitive)?;
+            let results = query::impact::blast_radius_with(
+                graph,
+                &indices,
+                project_root,
+                &query::impact::ImpactOptions::default(),
+            );
+            query::output::format_impact_results(
+                &results,
+                &format,
+                project_root,
+                tree,
+                &symbol,
+                &arrangement,
+            );
+        }
+        ReplCommand::Path {
+            from,
+            to,
+            max_paths,
+            format,
+        } => {
+            let result =
+                query::dep_path::dependency_paths(graph, project_root, &from, &to, max_paths)?;
+            if result.paths.is_empty() && !matches!(format, OutputFormat::Json) {
+                eprintln!("no dependency path from '{}' to '{}'", from, to);
+            } else {
+                query::output::format_dependency_paths(&result, &format);
+            }
+        }
+        ReplCommand::Query { query, format } => {
+            let result = query::dsl::run_query(graph, project_root, &query)
+                .map_err(|e| anyhow::anyhow!("invalid query '{}': {}", query, e))?;
+            query::output::format_dsl_query_results(&result, &format, project_root);
+        }
+        ReplCommand::Reload | ReplCommand::Exit => {}
+    }
+    Ok(())
+}
+
+/// Symbols matching the `symbol` pattern, or an error when there are none.
+fn matched_indices(
+    graph: &CodeGraph,
+    symbol: &str,
+    case_insensitive: bool,
+) -> Result<Vec<petgraph::stable_graph::NodeIndex>> {
+    let matches = query::find::match_symbols(graph, symbol, case_insensitive)?;
+    if matches.is_empty() {
+        anyhow::bail!("no symbols matching '{}' found", symbol);
+    }
+    Ok(matches
+        .into_iter()
+        .flat_map(|(_, indices)| indices)
+        .collect())
+}
+
+/// Puts the terminal in raw mode for reading one line, and restores it when dropped.
+#[cfg(unix)]
+struct RawMode {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        // SAFETY: tcgetattr/tcsetattr on stdin with a termios this function owns.
+        unsafe {
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut raw = original;
+            // Byte-at-a-time input without echo; Ctrl-C and Ctrl-D arrive as bytes.
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+            raw.c_iflag &= !(libc::IXON | libc::ICRNL);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Self { original })
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: restores the attributes read in `enable`.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// How long to wait for the rest of an escape sequence before taking Escape as a key of
+/// its own.
+#[cfg(unix)]
+const ESCAPE_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Read one line from the terminal with completion and history. `None` at end of input
+/// (Ctrl-D on an empty line).
+#[cfg(unix)]
+fn read_line_raw(
+    prompt: &str,
+    history: &[String],
+    completions: &Completions,
+) -> Result<Option<String>> {
+    let _raw = RawMode::enable()?;
+    let mut out = io::stdout().lock();
+    Ok(edit_line(
+        prompt,
+        history,
+        completions,
+        &mut out,
+        read_byte,
+    )?)
+}
+
+/// Read one byte from the terminal, waiting at most `timeout` when one is given. `None`
+/// on timeout or at end of input.
+///
+/// Reads the descriptor directly: the buffered `Stdin` would pull in a whole escape
+/// sequence at once and hide the rest of it from `poll`.
+#[cfg(unix)]
+fn read_byte(timeout: Option<Duration>) -> io::Result<Option<u8>> {
+    loop {
+        if let Some(timeout) = timeout {
+            let mut fd = libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let ms = libc::c_int::try_from(timeout.as_millis()).unwrap_or(libc::c_int::MAX);
+            // SAFETY: polls stdin with a pollfd owned by this function.
+            match unsafe { libc::poll(&mut fd, 1, ms) } {
+                0 => return Ok(None),
+                ready if ready < 0 => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() == io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return Err(err);
+                }
+                _ => {}
+            }
+        }
+        let mut byte = 0u8;
+        // SAFETY: reads at most one byte into a local buffer.
+        let n = unsafe { libc::read(libc::STDIN_FILENO, (&raw mut byte).cast(), 1) };
+        return match n {
+            0 => Ok(None),
+            n if n > 0 => Ok(Some(byte)),
+            _ => {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                Err(err)
+            }
+        };
+    }
+}
+
+/// Line editor behind [`read_line_raw`], reading bytes through `read` so it can be driven
+/// without a terminal.
+///
+/// An Escape not followed by another byte within [`ESCAPE_TIMEOUT`] is a key of its own;
+/// a byte that follows it without starting a `CSI`/`SS3` sequence (Alt+key) is taken as
+/// typed.
+#[cfg(unix)]
+fn edit_line(
+    prompt: &str,
+    history: &[String],
+    completions: &Completions,
+    out: &mut impl Write,
+    mut read: impl FnMut(Option<Duration>) -> io::Result<Option<u8>>,
+) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    let mut position = history.len();
+    let redraw = |out: &mut dyn Write, line: &str| -> io::Result<()> {
+        write!(out, "\r\x1b[K{}{}", prompt, line)?;
+        out.flush()
+    };
+    redraw(out, &line)?;
+
+    let mut pending = None;
+    loop {
+        let byte = match pending.take() {
+            Some(byte) => byte,
+            None => match read(None)? {
+                Some(byte) => byte,
+                None => {
+                    writeln!(out)?;
+                    return Ok(None);
+                }
+            },
+        };
+        match byte {
+            b'\r' | b'\n' => {
+                writeln!(out)?;
+                return Ok(Some(line));
+            }
+            // Ctrl-D
+            4 if line.is_empty() => {
+                writeln!(out)?;
+                return Ok(None);
+            }
+            // Ctrl-C
+            3 => {
+                writeln!(out, "^C")?;
+                line.clear();
+                position = history.len();
+            }
+            // Ctrl-U
+            21 => line.clear(),
+            // Backspace / Delete
+            8 | 127 => {
+                line.pop();
+            }
+            b'\t' => {
+                let candidates = completions.candidates(&line);
+                let typed = if line.is_empty() || line.ends_with(char::is_whitespace) {
+                    ""
+                } else {
+                    line.split_whitespace().last().unwrap_or("")
+                };
+                let shared = common_prefix(&candidates);
+                if candidates.len() == 1 {
+                    line.push_str(&candidates[0][typed.len()..]);
+                    line.push(' ');
+                } else if shared.len() > typed.len() {
+                    line.push_str(&shared[typed.len()..]);
+                } else if candidates.len() > 1 {
+                    writeln!(out)?;
+                    for name in candidates.iter().take(MAX_LISTED_CANDIDATES) {
+                        write!(out, "{}  ", name)?;
+                    }
+                    if candidates.len() > MAX_LISTED_CANDIDATES {
+                        write!(
+                            out,
+                            "... ({} more)",
+                            candidates.len() - MAX_LISTED_CANDIDATES
+                        )?;
+                    }
+                    writeln!(out)?;
+                }
+            }
+            // Escape sequences: Up/Down walk the history; the rest are ignored.
+            0x1b => match read(Some(ESCAPE_TIMEOUT))? {
+                Some(b'[' | b'O') => {
+                    // Parameter bytes up to the final byte, which names the key.
+                    let mut key = None;
+                    while let Some(b) = read(Some(ESCAPE_TIMEOUT))? {
+                        if (0x40..=0x7e).contains(&b) {
+                            key = Some(b);
+                            break;
+                        }
+                    }
+                    match key {
+                        Some(b'A') if position > 0 => {
+                            position -= 1;
+                            line = history[position].clone();
+                        }
+                        Some(b'B') if position < history.len() => {
+                            position += 1;
+                            line = history.get(position).cloned().unwrap_or_default();
+                        }
+                        _ => {}
+                    }
+                }
+                Some(b) => {
+                    pending = Some(b);
+                    continue;
+                }
+                None => {}
+            },
+            c if c >= 0x20 => {
+                // Collect the rest of a multi-byte UTF-8 character.
+                let extra = match c {
+                    0xc0..=0xdf => 1,
+                    0xe0..=0xef => 2,
+                    0xf0..=0xf7 => 3,
+                    _ => 0,
+                };
+                let mut bytes = vec![c];
+                for _ in 0..extra {
+                    match read(None)? {
+                        Some(b) => bytes.push(b),
+                        None => break,
+                    }
+                }
+                line.push_str(&String::from_utf8_lossy(&bytes));
+            }
+            _ => {}
+        }
+        redraw(out, &line)?;
+    }
+}
+
+/// Without termios, read a plain line after printing the prompt.
+#[cfg(not(unix))]
+fn read_line_raw(
+    prompt: &str,
+    _history: &[String],
+    _completions: &Completions,
+) -> Result<Option<String>> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_split_words_and_parse() {
+        let words = split_words(r#"query "symbols where name~'use*'" --format json"#).unwrap();
+        assert_eq!(
+            words,
+            vec!["query", "symbols where name~'use*'", "--format", "json"]
+        );
+        match ReplLine::try_parse_from(&words).unwrap().command {
+            ReplCommand::Query { query, format } => {
+                assert_eq!(query, "symbols where name~'use*'");
+                assert!(matches!(format, OutputFormat::Json));
+            }
+            other => panic!("expected query, got {other:?}"),
+        }
+        assert!(matches!(
+            ReplLine::try_parse_from(["quit"]).unwrap().command,
+            ReplCommand::Exit
+        ));
+        assert!(split_words("find 'open").is_err());
+    }
+
+    #[test]
+    fn test_completion_candidates() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let file = graph.add_file(root.join("src/user.ts"), "typescript");
+        for name in ["UserService", "UserRepository", "render"] {
+            graph.add_symbol(
+                file,
+                SymbolInfo {
+                    name: name.into(),
+                    kind: SymbolKind::Class,
+                    ..Default::default()
+                },
+            );
+        }
+        let completions = Completions::new(&graph, &root);
+
+        assert_eq!(completions.candidates("re"), ["refs", "reload"]);
+        let candidates = completions.candidates("refs User");
+        assert_eq!(candidates, ["UserRepository", "UserService"]);
+        assert_eq!(common_prefix(&candidates), "User");
+        assert_eq!(completions.candidates("impact re"), ["render"]);
+        assert_eq!(completions.candidates("path src/"), ["src/user.ts"]);
+        assert!(completions.candidates("find --k").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_line_escape_keys() {
+        let completions = Completions::new(&CodeGraph::new(), Path::new("/proj"));
+        let history = vec!["refs foo".to_string()];
+        // `None` stands for a pause longer than the escape timeout.
+        let edit = |input: Vec<Option<u8>>| {
+            let mut input = input.into_iter();
+            let read = |_: Option<Duration>| Ok(input.next().flatten());
+            edit_line("> ", &history, &completions, &mut Vec::new(), read).unwrap()
+        };
+        let keys = |text: &str| text.bytes().map(Some).collect::<Vec<_>>();
+
+        // A lone Escape does not swallow the keys typed after it.
+        let mut input = keys("\x1b");
+        input.push(None);
+        input.extend(keys("ab\r"));
+        assert_eq!(edit(input).as_deref(), Some("ab"));
+        // Alt+x arrives as Escape and `x`.
+        assert_eq!(edit(keys("\x1bx\r")).as_deref(), Some("x"));
+        // Up recalls history, with or without parameters; Delete (`ESC [3~`) is ignored.
+        assert_eq!(edit(keys("\x1b[A\r")).as_deref(), Some("refs foo"));
+        assert_eq!(edit(keys("\x1bOA\x1b[3~!\r")).as_deref(), Some("refs foo!"));
+    }
+}
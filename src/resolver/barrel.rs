@@ -1,12 +1,14 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 
 use crate::graph::CodeGraph;
 use crate::graph::edge::EdgeKind;
 use crate::parser::ParseResult;
 use crate::parser::imports::ExportKind;
+use crate::resolver::bindings::find_bound_symbol;
 
 /// Resolve barrel re-export chains in the graph.
 ///
@@ -126,6 +128,10 @@ pub fn resolve_barrel_chains(
     }
 }
 
+/// One named re-export statement: its `(exported, original)` name pairs — different for
+/// `export { Foo as Bar }` — and the file it re-exports from.
+type NamedReExport = (Vec<(String, String)>, PathBuf);
+
 /// Resolve named re-export chains in the graph, adding direct `ResolvedImport` edges from
 /// importing files to the files that actually *define* the imported names.
 ///
@@ -159,9 +165,11 @@ pub fn resolve_named_reexport_chains(
 ) -> usize {
     // -------------------------------------------------------------------------
     // Step 1: Build named re-export map.
-    // barrel_reexports[barrel_path] = vec of (names_exported, resolved_source_path)
+    // barrel_reexports[barrel_path] = vec of ((exported, original) names, resolved_source_path)
     // -------------------------------------------------------------------------
-    let mut barrel_reexports: HashMap<PathBuf, Vec<(Vec<String>, PathBuf)>> = HashMap::new();
+    let mut barrel_reexports: HashMap<PathBuf, Vec<NamedReExport>> = HashMap::new();
+    // (barrel_path, name) pairs re-exported with `export type { .. } from`.
+    let mut type_only_reexports: HashSet<(PathBuf, String)> = HashSet::new();
 
     for (file_path, result) in parse_results {
         let barrel_dir = match file_path.parent() {
@@ -184,10 +192,23 @@ pub fn resolve_named_reexport_chains(
             if let Some(source_path) =
                 resolve_relative_specifier(barrel_dir, source_specifier, parse_results)
             {
+                if export.is_type_only {
+                    type_only_reexports.extend(
+                        export
+                            .names
+                            .iter()
+                            .map(|name| (file_path.clone(), export.exported_name(name).to_owned())),
+                    );
+                }
+                let names = export
+                    .names
+                    .iter()
+                    .map(|name| (export.exported_name(name).to_owned(), name.clone()))
+                    .collect();
                 barrel_reexports
                     .entry(file_path.clone())
                     .or_default()
-                    .push((export.names.clone(), source_path));
+                    .push((names, source_path));
             }
         }
     }
@@ -215,7 +236,7 @@ pub fn resolve_named_reexport_chains(
         .edge_indices()
         .filter_map(|edge_idx| {
             match &graph.graph[edge_idx] {
-                EdgeKind::ResolvedImport { specifier } => {
+                EdgeKind::ResolvedImport { specifier, .. } => {
                     let (src_node, tgt_node) = graph.graph.edge_endpoints(edge_idx)?;
                     let importer_path = idx_to_path.get(&src_node)?;
                     let barrel_path = idx_to_path.get(&tgt_node)?;
@@ -241,7 +262,9 @@ pub fn resolve_named_reexport_chains(
     // -------------------------------------------------------------------------
     // Step 3: For each candidate edge, match imported names against barrel re-exports.
     // -------------------------------------------------------------------------
-    let mut edges_to_add: Vec<(PathBuf, PathBuf, String)> = Vec::new(); // (importer, defining_file, specifier)
+    // (importer, defining_file, specifier, is_type_only, import statement (line, col))
+    #[allow(clippy::type_complexity)]
+    let mut edges_to_add: Vec<(PathBuf, PathBuf, String, bool, (usize, usize))> = Vec::new();
 
     for (importer_path, barrel_path, specifier) in &candidates {
         // Get the import info for this importer + specifier to know which names were imported.
@@ -279,6 +302,10 @@ pub fn resolve_named_reexport_chains(
         if wanted_names.is_empty() {
             continue;
         }
+        let import_is_type_only = import_info.as_ref().is_some_and(|info| info.is_type_only);
+        let site = import_info
+            .as_ref()
+            .map_or((0, 0), |info| (info.line, info.col));
 
         let barrel_exports = match barrel_reexports.get(barrel_path) {
             Some(e) => e,
@@ -296,7 +323,17 @@ pub fn resolve_named_reexport_chains(
             ) {
                 // Don't add a redundant edge if the defining file IS the barrel itself.
                 if &defining_file != barrel_path {
-                    edges_to_add.push((importer_path.clone(), defining_file, specifier.clone()));
+                    // The direct edge is type-only when the import or the re-export is.
+                    let is_type_only = import_is_type_only
+                        || type_only_reexports
+                            .contains(&(barrel_path.clone(), wanted_name.clone()));
+                    edges_to_add.push((
+                        importer_path.clone(),
+                        defining_file,
+                        specifier.clone(),
+                        is_type_only,
+                        site,
+                    ));
                 }
             }
         }
@@ -307,7 +344,10 @@ pub fn resolve_named_reexport_chains(
     // -------------------------------------------------------------------------
     let mut added = 0usize;
 
-    for (importer_path, defining_path, specifier) in edges_to_add {
+    // Runtime edges first, so a name imported both ways keeps the runtime edge.
+    edges_to_add.sort_by_key(|(_, _, _, is_type_only, _)| *is_type_only);
+
+    for (importer_path, defining_path, specifier, is_type_only, (line, col)) in edges_to_add {
         let importer_idx = match graph.file_index.get(&importer_path).copied() {
             Some(idx) => idx,
             None => continue,
@@ -323,7 +363,14 @@ pub fn resolve_named_reexport_chains(
         });
 
         if !already_exists {
-            graph.add_resolved_import(importer_idx, defining_idx, &specifier);
+            graph.add_import_at(
+                importer_idx,
+                defining_idx,
+                &specifier,
+                is_type_only,
+                line,
+                col,
+            );
             added += 1;
 
             if verbose {
@@ -347,8 +394,8 @@ pub fn resolve_named_reexport_chains(
 fn chase_named_reexport(
     name: &str,
     current_barrel: &Path,
-    current_exports: &[(Vec<String>, PathBuf)],
-    all_barrel_reexports: &HashMap<PathBuf, Vec<(Vec<String>, PathBuf)>>,
+    current_exports: &[NamedReExport],
+    all_barrel_reexports: &HashMap<PathBuf, Vec<NamedReExport>>,
     verbose: bool,
 ) -> Option<PathBuf> {
     let mut visited: HashSet<PathBuf> = HashSet::new();
@@ -365,16 +412,21 @@ fn chase_named_reexport(
 
 fn chase_named_reexport_inner(
     name: &str,
-    current_exports: &[(Vec<String>, PathBuf)],
-    all_barrel_reexports: &HashMap<PathBuf, Vec<(Vec<String>, PathBuf)>>,
+    current_exports: &[NamedReExport],
+    all_barrel_reexports: &HashMap<PathBuf, Vec<NamedReExport>>,
     visited: &mut HashSet<PathBuf>,
     verbose: bool,
 ) -> Option<PathBuf> {
     // Find the export entry in current_exports that includes `name`.
     for (exported_names, source_path) in current_exports {
-        if !exported_names.iter().any(|n| n == name) {
+        // The source exports the name under its original name (`export { Foo as Bar }`).
+        let Some(original) = exported_names
+            .iter()
+            .find(|(exported, _)| exported == name)
+            .map(|(_, original)| original.as_str())
+        else {
             continue;
-        }
+        };
 
         // Found a match. Check if the source_path also re-exports this name (another barrel).
         if visited.contains(source_path) {
@@ -393,14 +445,14 @@ fn chase_named_reexport_inner(
         match all_barrel_reexports.get(source_path) {
             Some(next_exports) => {
                 // The source is itself a barrel with named re-exports.
-                // Check if it re-exports `name` further.
+                // Check if it re-exports `original` further.
                 let re_exported_again = next_exports
                     .iter()
-                    .any(|(ns, _)| ns.iter().any(|n| n == name));
+                    .any(|(ns, _)| ns.iter().any(|(exported, _)| exported == original));
                 if re_exported_again {
                     // Chase deeper.
                     return chase_named_reexport_inner(
-                        name,
+                        original,
                         next_exports,
                         all_barrel_reexports,
                         visited,
@@ -425,18 +477,29 @@ fn chase_named_reexport_inner(
 /// Attempt to resolve a relative module specifier to an absolute path that exists in
 /// `parse_results`.
 ///
-/// We try common TypeScript/JavaScript extension patterns:
-/// - exact path with extensions (.ts, .tsx, .js, .jsx, .mts, .mjs)
-/// - directory with index file (index.ts, index.tsx, index.js)
-///
-/// If the specifier already contains an extension, try it first. Falls back to the
-/// parse_results keys for exact match.
+/// See [`resolve_relative_in`] for the candidates tried.
 ///
 /// Returns `None` if no matching file is found in parse_results.
 fn resolve_relative_specifier(
     from_dir: &Path,
     specifier: &str,
     parse_results: &HashMap<PathBuf, ParseResult>,
+) -> Option<PathBuf> {
+    resolve_relative_in(from_dir, specifier, |p| parse_results.contains_key(p))
+}
+
+/// Attempt to resolve a relative module specifier to an absolute path for which `exists`
+/// holds.
+///
+/// We try common TypeScript/JavaScript extension patterns:
+/// - exact path with extensions (.ts, .tsx, .js, .jsx, .mts, .mjs)
+/// - directory with index file (index.ts, index.tsx, index.js)
+///
+/// If the specifier already contains an extension, try it first.
+fn resolve_relative_in(
+    from_dir: &Path,
+    specifier: &str,
+    exists: impl Fn(&Path) -> bool,
 ) -> Option<PathBuf> {
     // Only handle relative specifiers (starting with ./ or ../).
     // Non-relative specifiers are external packages handled elsewhere.
@@ -447,7 +510,7 @@ fn resolve_relative_specifier(
     let base = from_dir.join(specifier);
 
     // Try the path directly first (if specifier has an extension).
-    if parse_results.contains_key(&base) {
+    if exists(&base) {
         return Some(base.clone());
     }
 
@@ -455,7 +518,7 @@ fn resolve_relative_specifier(
     let extensions = [".ts", ".tsx", ".js", ".jsx", ".mts", ".mjs"];
     for ext in &extensions {
         let candidate = PathBuf::from(format!("{}{}", base.display(), ext));
-        if parse_results.contains_key(&candidate) {
+        if exists(&candidate) {
             return Some(candidate);
         }
     }
@@ -464,7 +527,7 @@ fn resolve_relative_specifier(
     let index_files = ["index.ts", "index.tsx", "index.js", "index.jsx"];
     for idx_file in &index_files {
         let candidate = base.join(idx_file);
-        if parse_results.contains_key(&candidate) {
+        if exists(&candidate) {
             return Some(candidate);
         }
     }
@@ -472,6 +535,95 @@ fn resolve_relative_specifier(
     None
 }
 
+/// Find the symbol `file` exports as `name`: a symbol of the file (or one it re-exports
+/// through `export *` or an alias), or else the symbol behind a named re-export of `name`,
+/// chased through the parsed files' `export { .. } from` statements.
+fn exported_symbol(
+    graph: &CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+    file: &Path,
+    name: &str,
+    visited: &mut HashSet<PathBuf>,
+) -> Option<NodeIndex> {
+    if !visited.insert(file.to_path_buf()) {
+        return None;
+    }
+    let &file_idx = graph.file_index.get(file)?;
+    if let Some(symbol) = find_bound_symbol(graph, &[file_idx], name, false) {
+        return Some(symbol);
+    }
+    let dir = file.parent()?;
+    for export in &parse_results.get(file)?.exports {
+        let (ExportKind::ReExport, Some(specifier)) = (&export.kind, &export.source) else {
+            continue;
+        };
+        let Some(original) = export
+            .names
+            .iter()
+            .find(|n| export.exported_name(n) == name)
+        else {
+            continue;
+        };
+        let Some(source) =
+            resolve_relative_in(dir, specifier, |p| graph.file_index.contains_key(p))
+        else {
+            continue;
+        };
+        if let Some(symbol) = exported_symbol(graph, parse_results, &source, original, visited) {
+            return Some(symbol);
+        }
+    }
+    None
+}
+
+/// Record export aliases (`export { Foo as Bar } from './x'`, `export { Foo as Bar }`):
+/// an `ExportsAs` edge from the aliasing file to the symbol the original name resolves
+/// to, so lookups of `Bar` reach `Foo`.
+///
+/// Returns the number of aliases recorded.
+pub fn resolve_reexport_aliases(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+    verbose: bool,
+) -> usize {
+    let mut aliases: Vec<(NodeIndex, NodeIndex, String)> = Vec::new();
+    for (file_path, result) in parse_results {
+        let (Some(&file_idx), Some(dir)) = (graph.file_index.get(file_path), file_path.parent())
+        else {
+            continue;
+        };
+        for export in &result.exports {
+            for (original, alias) in &export.aliases {
+                let source = match &export.source {
+                    Some(specifier) => {
+                        resolve_relative_in(dir, specifier, |p| graph.file_index.contains_key(p))
+                    }
+                    None => Some(file_path.clone()),
+                };
+                let symbol = source.and_then(|source| {
+                    exported_symbol(graph, parse_results, &source, original, &mut HashSet::new())
+                });
+                match symbol {
+                    Some(symbol) => aliases.push((file_idx, symbol, alias.clone())),
+                    None if verbose => eprintln!(
+                        "barrel(alias): {} exports '{}' as '{}' -> no matching symbol",
+                        file_path.display(),
+                        original,
+                        alias
+                    ),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    let count = aliases.len();
+    for (file_idx, symbol, alias) in aliases {
+        graph.add_export_alias(file_idx, symbol, &alias);
+    }
+    count
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -494,6 +646,7 @@ mod tests {
             exports,
             relationships: vec![],
             rust_uses: vec![],
+            ..Default::default()
         }
     }
 
@@ -508,6 +661,7 @@ mod tests {
             exports,
             relationships: vec![],
             rust_uses: vec![],
+            ..Default::default()
         }
     }
 
@@ -526,6 +680,8 @@ mod tests {
                 })
                 .collect(),
             line: 0,
+            col: 0,
+            is_type_only: false,
         }
     }
 
@@ -541,7 +697,9 @@ mod tests {
         let barrel_export = ExportInfo {
             kind: ExportKind::ReExportAll,
             names: vec![],
+            aliases: vec![],
             source: Some("./utils".to_owned()),
+            is_type_only: false,
         };
 
         let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
@@ -581,7 +739,9 @@ mod tests {
         let named_reexport = ExportInfo {
             kind: ExportKind::ReExport,
             names: vec!["helper".to_owned()],
+            aliases: vec![],
             source: Some("./utils".to_owned()),
+            is_type_only: false,
         };
 
         let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
@@ -613,7 +773,9 @@ mod tests {
         let barrel_export = ExportInfo {
             kind: ExportKind::ReExportAll,
             names: vec![],
+            aliases: vec![],
             source: Some("./missing".to_owned()),
+            is_type_only: false,
         };
 
         let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
@@ -663,7 +825,9 @@ mod tests {
         let barrel_export = ExportInfo {
             kind: ExportKind::ReExport,
             names: vec!["Foo".to_owned()],
+            aliases: vec![],
             source: Some("./FooService".to_owned()),
+            is_type_only: false,
         };
 
         let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
@@ -697,6 +861,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_aliased_reexport_links_alias_to_original_symbol() {
+        use crate::graph::node::{SymbolInfo, SymbolKind};
+
+        let mut graph = CodeGraph::new();
+        let app_path = PathBuf::from("/project/app.ts");
+        let index_path = PathBuf::from("/project/lib/index.ts");
+        let foo_path = PathBuf::from("/project/lib/foo.ts");
+        let app_idx = graph.add_file(app_path.clone(), "typescript");
+        let index_idx = graph.add_file(index_path.clone(), "typescript");
+        let foo_idx = graph.add_file(foo_path.clone(), "typescript");
+        let foo_sym = graph.add_symbol(
+            foo_idx,
+            SymbolInfo {
+                name: "Foo".into(),
+                kind: SymbolKind::Function,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        graph.add_resolved_import(app_idx, index_idx, "./lib");
+
+        let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
+        parse_results.insert(
+            app_path.clone(),
+            make_parse_result_with_imports(vec![make_named_import("./lib", &["Bar"])], vec![]),
+        );
+        parse_results.insert(
+            index_path.clone(),
+            make_parse_result(vec![ExportInfo {
+                kind: ExportKind::ReExport,
+                names: vec!["Foo".to_owned()],
+                aliases: vec![("Foo".to_owned(), "Bar".to_owned())],
+                source: Some("./foo".to_owned()),
+                is_type_only: false,
+            }]),
+        );
+        parse_results.insert(foo_path.clone(), make_parse_result(vec![]));
+
+        // The chain is chased under the alias, and the alias names the original symbol.
+        assert_eq!(
+            resolve_named_reexport_chains(&mut graph, &parse_results, false),
+            1
+        );
+        assert!(graph.graph.contains_edge(app_idx, foo_idx));
+        assert_eq!(
+            resolve_reexport_aliases(&mut graph, &parse_results, false),
+            1
+        );
+        assert_eq!(graph.alias_index["Bar"], vec![foo_sym]);
+        assert_eq!(graph.symbols_named("Bar"), Some(&vec![foo_sym]));
+        assert_eq!(
+            find_bound_symbol(&graph, &[index_idx], "Bar", false),
+            Some(foo_sym)
+        );
+
+        graph.remove_file_from_graph(&index_path);
+        assert!(graph.alias_index.is_empty());
+    }
+
     /// Test 2: Multi-level named re-export chain.
     ///
     /// Setup:
@@ -726,12 +950,16 @@ mod tests {
         let outer_export = ExportInfo {
             kind: ExportKind::ReExport,
             names: vec!["Foo".to_owned()],
+            aliases: vec![],
             source: Some("./inner".to_owned()),
+            is_type_only: false,
         };
         let inner_export = ExportInfo {
             kind: ExportKind::ReExport,
             names: vec!["Foo".to_owned()],
+            aliases: vec![],
             source: Some("./defining".to_owned()),
+            is_type_only: false,
         };
 
         let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
@@ -781,12 +1009,16 @@ mod tests {
         let a_export = ExportInfo {
             kind: ExportKind::ReExport,
             names: vec!["Foo".to_owned()],
+            aliases: vec![],
             source: Some("../b".to_owned()),
+            is_type_only: false,
         };
         let b_export = ExportInfo {
             kind: ExportKind::ReExport,
             names: vec!["Foo".to_owned()],
+            aliases: vec![],
             source: Some("../a".to_owned()),
+            is_type_only: false,
         };
 
         let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
@@ -831,7 +1063,9 @@ mod tests {
         let barrel_export = ExportInfo {
             kind: ExportKind::ReExport,
             names: vec!["Foo".to_owned()], // exports Foo, not Bar
+            aliases: vec![],
             source: Some("./FooService".to_owned()),
+            is_type_only: false,
         };
 
         let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
@@ -0,0 +1,259 @@
+//! Symbol-level import bindings.
+//!
+//! Integrates into `resolve_all` as Step 15, after every language has resolved its imports
+//! to files: each named specifier of an import (`import { Foo } from './x'`,
+//! `from pkg.x import Foo`) is bound to the top-level symbol it names with an
+//! `ImportsSymbol` edge. Default imports bind to the file's default export.
+//!
+//! The symbol is looked up in every file the import resolved to, which includes the
+//! defining files added by the named re-export pass (Step 4b), and then through
+//! `export * from` barrels (`BarrelReExportAll` edges). Namespace imports and names that
+//! match no symbol (a Python submodule, a re-exported name whose source is not indexed)
+//! stay file-level.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+use crate::parser::ParseResult;
+
+/// Top-level symbol of `file_idx` named `name` (or its default export).
+fn find_top_level(
+    graph: &CodeGraph,
+    file_idx: NodeIndex,
+    name: &str,
+    is_default: bool,
+) -> Option<NodeIndex> {
+    graph
+        .graph
+        .edges_directed(file_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .map(|e| e.target())
+        .find(|&idx| match &graph.graph[idx] {
+            GraphNode::Symbol(info) if is_default => info.is_default,
+            GraphNode::Symbol(info) => info.name == name,
+            _ => false,
+        })
+}
+
+/// Find the symbol a specifier binds, starting from the files its import resolved to and
+/// following export aliases and `export * from` barrels.
+pub(crate) fn find_bound_symbol(
+    graph: &CodeGraph,
+    targets: &[NodeIndex],
+    name: &str,
+    is_default: bool,
+) -> Option<NodeIndex> {
+    let mut queue: Vec<NodeIndex> = targets.to_vec();
+    let mut visited: HashSet<NodeIndex> = HashSet::new();
+    let mut i = 0;
+    while i < queue.len() {
+        let file_idx = queue[i];
+        i += 1;
+        if !visited.insert(file_idx) {
+            continue;
+        }
+        if let Some(sym) = find_top_level(graph, file_idx, name, is_default) {
+            return Some(sym);
+        }
+        // `export { Foo as Bar }` binds `Bar` to `Foo`.
+        if !is_default
+            && let Some(sym) = graph
+                .graph
+                .edges_directed(file_idx, Direction::Outgoing)
+                .find(|e| matches!(e.weight(), EdgeKind::ExportsAs { alias } if alias == name))
+                .map(|e| e.target())
+        {
+            return Some(sym);
+        }
+        // `export *` never re-exports a default export.
+        if !is_default {
+            queue.extend(
+                graph
+                    .graph
+                    .edges_directed(file_idx, Direction::Outgoing)
+                    .filter(|e| matches!(e.weight(), EdgeKind::BarrelReExportAll))
+                    .map(|e| e.target()),
+            );
+        }
+    }
+    None
+}
+
+/// Step 15 of `resolve_all`: add an `ImportsSymbol` edge for every named import specifier
+/// of the parsed files that names a symbol of the imported file.
+///
+/// Returns the number of edges added.
+pub fn resolve_import_bindings(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+    verbose: bool,
+) -> usize {
+    // (importer, symbol) -> earliest known (line, col) of a statement binding it.
+    let mut bindings: HashMap<(NodeIndex, NodeIndex), (usize, usize)> = HashMap::new();
+
+    for (path, result) in parse_results {
+        let Some(&importer) = graph.file_index.get(path) else {
+            continue;
+        };
+        for import in &result.imports {
+            if import.specifiers.iter().all(|s| s.is_namespace) {
+                continue;
+            }
+            let targets: Vec<NodeIndex> = graph
+                .graph
+                .edges_directed(importer, Direction::Outgoing)
+                .filter(|e| {
+                    matches!(e.weight(), EdgeKind::ResolvedImport { specifier, .. }
+                        if *specifier == import.module_path)
+                })
+                .map(|e| e.target())
+                .filter(|&t| t != importer && matches!(graph.graph[t], GraphNode::File(_)))
+                .collect();
+            if targets.is_empty() {
+                continue;
+            }
+
+            for spec in import.specifiers.iter().filter(|s| !s.is_namespace) {
+                // `alias` holds the exported name for `import { original as local }`.
+                let exported = spec.alias.as_deref().unwrap_or(&spec.name);
+                match find_bound_symbol(graph, &targets, exported, spec.is_default) {
+                    Some(sym) => {
+                        let site = (import.line, import.col);
+                        bindings
+                            .entry((importer, sym))
+                            .and_modify(|best| {
+                                if site.0 > 0 && (best.0 == 0 || site < *best) {
+                                    *best = site;
+                                }
+                            })
+                            .or_insert(site);
+                    }
+                    None if verbose => eprintln!(
+                        "  bind: {} imports '{}' from '{}' -> no matching symbol",
+                        path.display(),
+                        exported,
+                        import.module_path
+                    ),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    let mut added = 0;
+    for ((importer, sym), (line, col)) in bindings {
+        let exists = graph
+            .graph
+            .edges_connecting(importer, sym)
+            .any(|e| matches!(e.weight(), EdgeKind::ImportsSymbol { .. }));
+        if !exists {
+            graph.add_imports_symbol(importer, sym, line, col);
+            added += 1;
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use crate::parser::imports::{ImportInfo, ImportKind, ImportSpecifier};
+
+    fn symbol(name: &str, is_default: bool) -> SymbolInfo {
+        SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line: 1,
+            is_exported: true,
+            is_default,
+            ..Default::default()
+        }
+    }
+
+    fn specifier(name: &str, alias: Option<&str>, is_default: bool) -> ImportSpecifier {
+        ImportSpecifier {
+            name: name.into(),
+            alias: alias.map(str::to_string),
+            is_default,
+            is_namespace: false,
+        }
+    }
+
+    fn bound_names(graph: &CodeGraph, file_idx: NodeIndex) -> Vec<String> {
+        let mut names: Vec<String> = graph
+            .graph
+            .edges_directed(file_idx, Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::ImportsSymbol { .. }))
+            .filter_map(|e| match &graph.graph[e.target()] {
+                GraphNode::Symbol(info) => Some(info.name.clone()),
+                _ => None,
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_binds_named_default_and_star_reexported_imports() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(root.join("app.ts"), "typescript");
+        let utils = graph.add_file(root.join("utils.ts"), "typescript");
+        let barrel = graph.add_file(root.join("lib/index.ts"), "typescript");
+        let math = graph.add_file(root.join("lib/math.ts"), "typescript");
+        graph.add_symbol(utils, symbol("format", false));
+        graph.add_symbol(utils, symbol("parse", false));
+        graph.add_symbol(utils, symbol("Logger", true));
+        graph.add_symbol(math, symbol("sum", false));
+        graph.add_resolved_import(app, utils, "./utils");
+        graph.add_resolved_import(app, barrel, "./lib");
+        graph.add_barrel_reexport_all(barrel, math);
+
+        let result = ParseResult {
+            imports: vec![
+                ImportInfo {
+                    kind: ImportKind::Esm,
+                    module_path: "./utils".into(),
+                    specifiers: vec![
+                        specifier("Log", None, true),
+                        specifier("fmt", Some("format"), false),
+                        specifier("missing", None, false),
+                    ],
+                    line: 1,
+                    col: 0,
+                    is_type_only: false,
+                },
+                ImportInfo {
+                    kind: ImportKind::Esm,
+                    module_path: "./lib".into(),
+                    specifiers: vec![specifier("sum", None, false)],
+                    line: 2,
+                    col: 0,
+                    is_type_only: false,
+                },
+            ],
+            ..Default::default()
+        };
+        let mut parse_results = HashMap::new();
+        parse_results.insert(root.join("app.ts"), result);
+
+        assert_eq!(
+            resolve_import_bindings(&mut graph, &parse_results, false),
+            3
+        );
+        assert_eq!(bound_names(&graph, app), vec!["Logger", "format", "sum"]);
+        // Re-running the pass does not duplicate edges.
+        assert_eq!(
+            resolve_import_bindings(&mut graph, &parse_results, false),
+            0
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! `Cargo.toml` dependency nodes.
+//!
+//! Every external dependency declared by a workspace crate — in `[dependencies]`,
+//! `[dev-dependencies]`, `[build-dependencies]`, or their `[target.*]` variants — becomes
+//! an `ExternalPackage` node, shared with the `use` imports that name it, linked from the
+//! crate's root file by a `DeclaresDependency` edge that records the table, `optional`,
+//! and the requested features. Dependencies on other workspace members are left out: their
+//! imports already resolve to the member's root file.
+//!
+//! `imports` compares these declarations with the crate's actual imports to tell declared,
+//! undeclared, and unused dependencies apart.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use petgraph::stable_graph::EdgeIndex;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::{DependencySection, EdgeKind};
+use crate::resolver::cargo_features::CrateManifest;
+use crate::resolver::cargo_workspace::discover_workspace_manifests;
+use crate::resolver::rust_mod_tree::find_crate_root;
+
+/// Rebuild the `DeclaresDependency` edges of every workspace crate. Returns the number of
+/// edges added.
+///
+/// Idempotent: existing declarations are dropped first, so a re-indexed crate root or an
+/// edited `Cargo.toml` is picked up by simply running it again.
+pub fn link_cargo_dependencies(graph: &mut CodeGraph, project_root: &Path) -> usize {
+    let stale: Vec<EdgeIndex> = graph
+        .graph
+        .edge_indices()
+        .filter(|&e| matches!(graph.graph[e], EdgeKind::DeclaresDependency { .. }))
+        .collect();
+    for edge in stale {
+        graph.graph.remove_edge(edge);
+    }
+
+    let manifests: Vec<CrateManifest> = discover_workspace_manifests(project_root)
+        .iter()
+        .filter_map(|path| CrateManifest::load(path))
+        .collect();
+    let members: HashSet<&str> = manifests.iter().map(|m| m.name.as_str()).collect();
+
+    let mut added = 0;
+    for manifest in &manifests {
+        let Some(root_idx) = find_crate_root(&manifest.manifest_path)
+            .and_then(|(_, root)| graph.file_index.get(&root).copied())
+        else {
+            continue;
+        };
+        let sections = [
+            (DependencySection::Normal, &manifest.dependencies),
+            (DependencySection::Dev, &manifest.dev_dependencies),
+            (DependencySection::Build, &manifest.build_dependencies),
+        ];
+        for (section, dependencies) in sections {
+            for dep in dependencies {
+                let name = dep.name.replace('-', "_");
+                if members.contains(name.as_str()) {
+                    continue;
+                }
+                graph.add_declared_dependency(
+                    root_idx,
+                    &name,
+                    dep.version.as_deref(),
+                    EdgeKind::DeclaresDependency {
+                        section,
+                        optional: dep.optional,
+                        features: dep.features.clone(),
+                    },
+                );
+                added += 1;
+            }
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::GraphNode;
+    use petgraph::visit::EdgeRef;
+    use std::fs;
+
+    #[test]
+    fn test_link_cargo_dependencies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        for (name, manifest) in [
+            (
+                "app",
+                r#"[package]
+name = "app"
+
+[dependencies]
+serde-json = "1"
+tokio = { version = "1", optional = true, features = ["rt"] }
+core = { path = "../core" }
+
+[dev-dependencies]
+tempfile = "3"
+"#,
+            ),
+            ("core", "[package]\nname = \"core\"\n"),
+        ] {
+            let dir = root.join("crates").join(name);
+            fs::create_dir_all(dir.join("src")).unwrap();
+            fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+            fs::write(dir.join("src/lib.rs"), "").unwrap();
+        }
+
+        let mut graph = CodeGraph::new();
+        let app = graph.add_file(root.join("crates/app/src/lib.rs"), "rust");
+        graph.add_file(root.join("crates/core/src/lib.rs"), "rust");
+
+        assert_eq!(link_cargo_dependencies(&mut graph, root), 3);
+        assert_eq!(link_cargo_dependencies(&mut graph, root), 3);
+
+        let mut declared: Vec<String> = graph
+            .graph
+            .edges(app)
+            .filter_map(|e| match (e.weight(), &graph.graph[e.target()]) {
+                (
+                    EdgeKind::DeclaresDependency {
+                        section,
+                        optional,
+                        features,
+                    },
+                    GraphNode::ExternalPackage(pkg),
+                ) => Some(format!(
+                    "{} {:?} {:?} optional={} {:?}",
+                    pkg.name, pkg.version, section, optional, features
+                )),
+                _ => None,
+            })
+            .collect();
+        declared.sort();
+        assert_eq!(
+            declared,
+            vec![
+                r#"serde_json Some("1") Normal optional=false []"#,
+                r#"tempfile Some("3") Dev optional=false []"#,
+                r#"tokio Some("1") Normal optional=true ["rt"]"#,
+            ]
+        );
+    }
+}
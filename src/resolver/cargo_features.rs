@@ -0,0 +1,485 @@
+//! Cargo feature resolution for `code-graph packages`.
+//!
+//! Reads `[dependencies]` and `[features]` from the `Cargo.toml` of every workspace crate and
+//! resolves a feature selection the way Cargo does: `default` unless disabled, features
+//! enabling other features, `dep:x` and the implicit feature of an optional dependency `x`
+//! enabling it, `x/feat` enabling `x` (but `x?/feat` not), and features requested of other
+//! workspace members through `member/feat` or a dependency's `features = [...]`.
+//!
+//! The result tells which optional dependencies are pulled in and which modules gated by
+//! `#[cfg(feature = "...")]` on their `mod` declaration are compiled.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::resolver::cargo_workspace::discover_workspace_manifests;
+use crate::resolver::rust_mod_tree::{build_mod_tree, find_crate_root, qualify_module_path};
+
+/// One entry of a crate's `[dependencies]` (or `[target.*.dependencies]`), or of its dev or
+/// build dependency tables.
+#[derive(Debug, Clone)]
+pub struct CargoDependency {
+    /// Dependency key as written in the manifest.
+    pub name: String,
+    /// Version requirement, when given inline (`"1"` or `{ version = "1" }`).
+    pub version: Option<String>,
+    /// Registry name of a renamed dependency (`package = "..."`).
+    pub package: Option<String>,
+    pub optional: bool,
+    /// Features requested with `features = [...]`.
+    pub features: Vec<String>,
+}
+
+/// The parts of a crate's `Cargo.toml` that feature resolution needs.
+#[derive(Debug, Clone)]
+pub struct CrateManifest {
+    /// Normalized crate name (hyphens → underscores).
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub dependencies: Vec<CargoDependency>,
+    /// `[dev-dependencies]`, which feature resolution ignores.
+    pub dev_dependencies: Vec<CargoDependency>,
+    /// `[build-dependencies]`, which feature resolution ignores.
+    pub build_dependencies: Vec<CargoDependency>,
+    pub features: BTreeMap<String, Vec<String>>,
+}
+
+impl CrateManifest {
+    /// Parse the manifest at `path`. Returns `None` when it is unreadable or has no
+    /// `[package]` name.
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let manifest: toml::Value = toml::from_str(&content).ok()?;
+        let name = manifest
+            .get("package")?
+            .get("name")?
+            .as_str()?
+            .replace('-', "_");
+
+        let dependencies = dependency_tables(&manifest, "dependencies");
+        let dev_dependencies = dependency_tables(&manifest, "dev-dependencies");
+        let build_dependencies = dependency_tables(&manifest, "build-dependencies");
+
+        let features = manifest
+            .get("features")
+            .and_then(|f| f.as_table())
+            .map(|table| {
+                table
+                    .iter()
+                    .map(|(name, list)| (name.clone(), string_list(Some(list))))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Self {
+            name,
+            manifest_path: path.to_path_buf(),
+            dependencies,
+            dev_dependencies,
+            build_dependencies,
+            features,
+        })
+    }
+
+    fn dependency(&self, name: &str) -> Option<&CargoDependency> {
+        self.dependencies.iter().find(|d| d.name == name)
+    }
+}
+
+/// Entries of the `table` dependency table and of its `[target.*.<table>]` variants, sorted
+/// and deduplicated by name.
+fn dependency_tables(manifest: &toml::Value, table: &str) -> Vec<CargoDependency> {
+    let mut tables: Vec<&toml::Value> = manifest.get(table).into_iter().collect();
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        tables.extend(targets.values().filter_map(|t| t.get(table)));
+    }
+    let mut dependencies: Vec<CargoDependency> = tables
+        .into_iter()
+        .filter_map(|t| t.as_table())
+        .flat_map(|t| t.iter())
+        .map(|(name, spec)| CargoDependency {
+            name: name.clone(),
+            version: spec
+                .as_str()
+                .or_else(|| spec.get("version").and_then(|v| v.as_str()))
+                .map(str::to_string),
+            package: spec
+                .get("package")
+                .and_then(|p| p.as_str())
+                .map(str::to_string),
+            optional: spec
+                .get("optional")
+                .and_then(|o| o.as_bool())
+                .unwrap_or(false),
+            features: string_list(spec.get("features")),
+        })
+        .collect();
+    dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+    dependencies.dedup_by(|a, b| a.name == b.name);
+    dependencies
+}
+
+fn string_list(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|i| i.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Features requested on the command line.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureSelection {
+    /// `feat` (every crate defining it) or `crate/feat`.
+    pub features: Vec<String>,
+    pub all_features: bool,
+    pub no_default_features: bool,
+}
+
+/// Features and optional dependencies enabled in one crate.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EnabledFeatures {
+    pub features: BTreeSet<String>,
+    pub optional_deps: BTreeSet<String>,
+}
+
+/// Enable `feature` in `krate`, following the feature's own list. Features requested of
+/// other crates (`dep/feat` on a dependency) are pushed to `requests` as `(dep, feat)`.
+fn enable(
+    krate: &CrateManifest,
+    feature: &str,
+    enabled: &mut EnabledFeatures,
+    requests: &mut Vec<(String, String)>,
+) {
+    if let Some(dep) = feature.strip_prefix("dep:") {
+        enabled.optional_deps.insert(dep.to_string());
+        return;
+    }
+    if let Some((dep, dep_feature)) = feature.split_once('/') {
+        let (dep, weak) = match dep.strip_suffix('?') {
+            Some(dep) => (dep, true),
+            None => (dep, false),
+        };
+        if !weak && krate.dependency(dep).is_some_and(|d| d.optional) {
+            enable(krate, dep, enabled, requests);
+        }
+        requests.push((dep.to_string(), dep_feature.to_string()));
+        return;
+    }
+    if !enabled.features.insert(feature.to_string()) {
+        return;
+    }
+    match krate.features.get(feature) {
+        Some(list) => {
+            for item in list {
+                enable(krate, item, enabled, requests);
+            }
+        }
+        // Implicit feature of an optional dependency.
+        None => {
+            if krate.dependency(feature).is_some_and(|d| d.optional) {
+                enabled.optional_deps.insert(feature.to_string());
+            }
+        }
+    }
+}
+
+/// Resolve `selection` across all `crates`, propagating features requested of workspace
+/// members until nothing changes. Returns enabled features per crate name.
+pub fn resolve_features(
+    crates: &[CrateManifest],
+    selection: &FeatureSelection,
+) -> HashMap<String, EnabledFeatures> {
+    let by_name: HashMap<&str, &CrateManifest> =
+        crates.iter().map(|c| (c.name.as_str(), c)).collect();
+    let mut resolved: HashMap<String, EnabledFeatures> = HashMap::new();
+    let mut pending: Vec<(String, String)> = Vec::new();
+
+    for krate in crates {
+        let mut wanted: Vec<&str> = Vec::new();
+        if !selection.no_default_features && krate.features.contains_key("default") {
+            wanted.push("default");
+        }
+        if selection.all_features {
+            wanted.extend(krate.features.keys().map(String::as_str));
+            wanted.extend(
+                krate
+                    .dependencies
+                    .iter()
+                    .filter(|d| d.optional)
+                    .map(|d| d.name.as_str()),
+            );
+        }
+        for requested in &selection.features {
+            match requested.split_once('/') {
+                Some((name, feature)) if name.replace('-', "_") == krate.name => {
+                    wanted.push(feature)
+                }
+                Some(_) => {}
+                None if krate.features.contains_key(requested)
+                    || krate.dependency(requested).is_some_and(|d| d.optional) =>
+                {
+                    wanted.push(requested)
+                }
+                None => {}
+            }
+        }
+        let enabled = resolved.entry(krate.name.clone()).or_default();
+        for feature in wanted {
+            enable(krate, feature, enabled, &mut pending);
+        }
+    }
+
+    // Requests on workspace members, from `dep/feat` and from active dependencies'
+    // `features = [...]` lists, until a fixed point.
+    loop {
+        for krate in crates {
+            let enabled = &resolved[&krate.name];
+            for dep in &krate.dependencies {
+                if !dep.optional || enabled.optional_deps.contains(&dep.name) {
+                    pending.extend(dep.features.iter().map(|f| (dep.name.clone(), f.clone())));
+                }
+            }
+        }
+        let mut changed = false;
+        for (dep, feature) in std::mem::take(&mut pending) {
+            let Some(member) = by_name.get(dep.replace('-', "_").as_str()) else {
+                continue;
+            };
+            let enabled = resolved.entry(member.name.clone()).or_default();
+            let before = enabled.clone();
+            let mut requests = Vec::new();
+            enable(member, &feature, enabled, &mut requests);
+            changed |= *enabled != before;
+            pending.extend(requests);
+        }
+        if !changed {
+            break;
+        }
+    }
+    resolved
+}
+
+/// A dependency of a crate under the selected features.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DependencyState {
+    pub name: String,
+    pub optional: bool,
+    pub enabled: bool,
+    /// The dependency is another crate of the workspace.
+    pub workspace: bool,
+}
+
+/// A module whose `mod` declaration is gated by `#[cfg(feature = "...")]`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GatedModule {
+    /// Crate-qualified module path (`my_crate::web`).
+    pub module: String,
+    pub feature: String,
+    /// The feature is enabled and no enclosing module is gated off.
+    pub enabled: bool,
+}
+
+/// One workspace crate under the selected features.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PackageReport {
+    pub name: String,
+    pub features: Vec<String>,
+    pub dependencies: Vec<DependencyState>,
+    pub gated_modules: Vec<GatedModule>,
+}
+
+/// Report every workspace crate of `project_root` under `selection`, sorted by name.
+pub fn package_reports(project_root: &Path, selection: &FeatureSelection) -> Vec<PackageReport> {
+    let mut crates: Vec<CrateManifest> = discover_workspace_manifests(project_root)
+        .iter()
+        .filter_map(|path| CrateManifest::load(path))
+        .collect();
+    crates.sort_by(|a, b| a.name.cmp(&b.name));
+    crates.dedup_by(|a, b| a.name == b.name);
+    let resolved = resolve_features(&crates, selection);
+    let members: BTreeSet<&str> = crates.iter().map(|c| c.name.as_str()).collect();
+
+    crates
+        .iter()
+        .map(|krate| {
+            let enabled = &resolved[&krate.name];
+            let dependencies = krate
+                .dependencies
+                .iter()
+                .map(|d| DependencyState {
+                    name: d.name.clone(),
+                    optional: d.optional,
+                    enabled: !d.optional || enabled.optional_deps.contains(&d.name),
+                    workspace: members.contains(d.name.replace('-', "_").as_str()),
+                })
+                .collect();
+            PackageReport {
+                name: krate.name.clone(),
+                features: enabled.features.iter().cloned().collect(),
+                dependencies,
+                gated_modules: gated_modules(krate, enabled),
+            }
+        })
+        .collect()
+}
+
+/// Feature-gated modules of `krate`, each enabled only when its own gate and the gates of
+/// all enclosing modules are.
+fn gated_modules(krate: &CrateManifest, enabled: &EnabledFeatures) -> Vec<GatedModule> {
+    let Some((_, root)) = find_crate_root(&krate.manifest_path) else {
+        return Vec::new();
+    };
+    let tree = build_mod_tree(&krate.name, &root);
+    let gate_open = |path: &str| {
+        tree.feature_gates
+            .get(path)
+            .is_none_or(|f| enabled.features.contains(f))
+    };
+
+    let mut modules: Vec<GatedModule> = tree
+        .feature_gates
+        .iter()
+        .map(|(path, feature)| {
+            let mut open = true;
+            let mut current = path.as_str();
+            loop {
+                open &= gate_open(current);
+                match current.rsplit_once("::") {
+                    Some((parent, _)) => current = parent,
+                    None => break,
+                }
+            }
+            GatedModule {
+                module: qualify_module_path(&krate.name, path),
+                feature: feature.clone(),
+                enabled: open,
+            }
+        })
+        .collect();
+    modules.sort_by(|a, b| a.module.cmp(&b.module));
+    modules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_crate(dir: &Path, manifest: &str, lib: &str) {
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+        fs::write(dir.join("src/lib.rs"), lib).unwrap();
+    }
+
+    fn make_workspace(root: &Path) {
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        write_crate(
+            &root.join("crates/app"),
+            r#"[package]
+name = "app"
+
+[features]
+default = ["cli"]
+cli = ["dep:clap"]
+server = ["axum", "core-lib/async"]
+metrics = ["tracing?/log"]
+
+[dependencies]
+anyhow = "1"
+clap = { version = "4", optional = true }
+axum = { version = "0.8", optional = true }
+tracing = { version = "0.1", optional = true }
+core-lib = { path = "../core-lib", features = ["std"] }
+"#,
+            "#[cfg(feature = \"server\")]\npub mod server;\n",
+        );
+        fs::write(root.join("crates/app/src/server.rs"), "").unwrap();
+        write_crate(
+            &root.join("crates/core-lib"),
+            r#"[package]
+name = "core-lib"
+
+[features]
+std = []
+async = ["dep:tokio"]
+
+[dependencies]
+tokio = { version = "1", optional = true }
+"#,
+            "#[cfg(feature = \"async\")]\nmod runtime;\n",
+        );
+        fs::write(root.join("crates/core-lib/src/runtime.rs"), "").unwrap();
+    }
+
+    fn enabled_deps(report: &PackageReport) -> Vec<&str> {
+        report
+            .dependencies
+            .iter()
+            .filter(|d| d.enabled)
+            .map(|d| d.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_features() {
+        let tmp = tempfile::tempdir().unwrap();
+        make_workspace(tmp.path());
+        let reports = package_reports(tmp.path(), &FeatureSelection::default());
+        assert_eq!(reports.len(), 2);
+        let (app, core) = (&reports[0], &reports[1]);
+
+        assert_eq!(app.features, vec!["cli", "default"]);
+        assert_eq!(enabled_deps(app), vec!["anyhow", "clap", "core-lib"]);
+        assert!(
+            app.dependencies
+                .iter()
+                .any(|d| d.name == "core-lib" && d.workspace)
+        );
+        assert!(!app.gated_modules[0].enabled);
+        // `features = ["std"]` on the dependency reaches the member.
+        assert_eq!(core.features, vec!["std"]);
+        assert!(enabled_deps(core).is_empty());
+    }
+
+    #[test]
+    fn test_selected_features_propagate_to_members() {
+        let tmp = tempfile::tempdir().unwrap();
+        make_workspace(tmp.path());
+        let selection = FeatureSelection {
+            features: vec!["server".into(), "metrics".into()],
+            no_default_features: true,
+            ..Default::default()
+        };
+        let reports = package_reports(tmp.path(), &selection);
+        let (app, core) = (&reports[0], &reports[1]);
+
+        // `tracing?/log` does not enable the optional `tracing`.
+        assert_eq!(enabled_deps(app), vec!["anyhow", "axum", "core-lib"]);
+        assert_eq!(app.gated_modules[0].module, "app::server");
+        assert!(app.gated_modules[0].enabled);
+        assert_eq!(core.features, vec!["async", "std"]);
+        assert_eq!(enabled_deps(core), vec!["tokio"]);
+        assert!(core.gated_modules[0].enabled);
+    }
+
+    #[test]
+    fn test_all_features() {
+        let tmp = tempfile::tempdir().unwrap();
+        make_workspace(tmp.path());
+        let selection = FeatureSelection {
+            all_features: true,
+            ..Default::default()
+        };
+        let reports = package_reports(tmp.path(), &selection);
+        assert!(reports[0].dependencies.iter().all(|d| d.enabled));
+    }
+}
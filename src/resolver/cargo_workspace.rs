@@ -3,81 +3,85 @@ use std::path::{Path, PathBuf};
 
 use crate::resolver::rust_mod_tree::find_crate_root;
 
-/// Discover all Rust crates in a project and return a map of normalized crate name → crate root file.
-///
-/// Handles three cases:
-/// 1. **Workspace project**: Reads `[workspace].members` globs from the root `Cargo.toml`,
-///    expands each pattern, finds each member's `Cargo.toml`, and calls `find_crate_root` on it.
-/// 2. **Virtual workspace with package**: If the workspace `Cargo.toml` also has a `[package]`
-///    section, includes that package as a member too.
-/// 3. **Single-crate project**: Falls back to calling `find_crate_root` on the root `Cargo.toml`.
-///
-/// Crate names are normalized: hyphens → underscores (Cargo convention).
+/// Find the `Cargo.toml` of every crate in a project.
 ///
-/// # Parameters
-/// - `project_root`: the root directory of the project (where the top-level `Cargo.toml` lives)
-///
-/// # Returns
-/// A map of `crate_name → crate_root_file_path`.
-pub fn discover_rust_workspace_members(project_root: &Path) -> HashMap<String, PathBuf> {
+/// For a workspace, these are the manifests matched by `[workspace].members` globs, plus the
+/// root manifest when it also has a `[package]` section. Otherwise the root manifest alone is
+/// returned when it exists.
+pub fn discover_workspace_manifests(project_root: &Path) -> Vec<PathBuf> {
     let workspace_toml = project_root.join("Cargo.toml");
 
     let content = match std::fs::read_to_string(&workspace_toml) {
         Ok(c) => c,
-        Err(_) => return HashMap::new(),
+        Err(_) => return Vec::new(),
     };
 
     let manifest: toml::Value = match toml::from_str(&content) {
         Ok(v) => v,
-        Err(_) => return HashMap::new(),
+        Err(_) => return Vec::new(),
     };
 
-    let mut result: HashMap<String, PathBuf> = HashMap::new();
-
     // Check if this is a workspace Cargo.toml.
     let workspace_members = manifest
         .get("workspace")
         .and_then(|ws| ws.get("members"))
         .and_then(|m| m.as_array());
 
-    if let Some(members) = workspace_members {
-        // Expand workspace member glob patterns.
-        for member_value in members {
-            let member_glob = match member_value.as_str() {
-                Some(s) => s,
-                None => continue,
-            };
-
-            // Build the full glob pattern: <project_root>/<member_pattern>/Cargo.toml
-            let pattern = format!("{}/{}/Cargo.toml", project_root.display(), member_glob);
-
-            let entries = match glob::glob(&pattern) {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            for entry in entries.flatten() {
-                if let Some((name, root)) = find_crate_root(&entry) {
-                    result.insert(name, root);
-                }
-            }
-        }
+    let Some(members) = workspace_members else {
+        // Single-crate project: just the root manifest.
+        return vec![workspace_toml];
+    };
 
-        // Handle combined workspace+package Cargo.toml (virtual workspace with root package).
-        if manifest.get("package").is_some()
-            && let Some((name, root)) = find_crate_root(&workspace_toml)
-        {
-            result.entry(name).or_insert(root);
-        }
+    let mut manifests = Vec::new();
+    // Expand workspace member glob patterns.
+    for member_value in members {
+        let member_glob = match member_value.as_str() {
+            Some(s) => s,
+            None => continue,
+        };
+
+        // Build the full glob pattern: <project_root>/<member_pattern>/Cargo.toml
+        let pattern = format!("{}/{}/Cargo.toml", project_root.display(), member_glob);
+
+        let entries = match glob::glob(&pattern) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        manifests.extend(entries.flatten());
+    }
 
-        result
-    } else {
-        // Single-crate project: just find the root crate.
-        if let Some((name, root)) = find_crate_root(&workspace_toml) {
-            result.insert(name, root);
+    // Handle combined workspace+package Cargo.toml (virtual workspace with root package).
+    if manifest.get("package").is_some() {
+        manifests.push(workspace_toml);
+    }
+    manifests
+}
+
+/// Discover all Rust crates in a project and return a map of normalized crate name → crate root file.
+///
+/// Handles three cases:
+/// 1. **Workspace project**: Reads `[workspace].members` globs from the root `Cargo.toml`,
+///    expands each pattern, finds each member's `Cargo.toml`, and calls `find_crate_root` on it.
+/// 2. **Virtual workspace with package**: If the workspace `Cargo.toml` also has a `[package]`
+///    section, includes that package as a member too.
+/// 3. **Single-crate project**: Falls back to calling `find_crate_root` on the root `Cargo.toml`.
+///
+/// Crate names are normalized: hyphens → underscores (Cargo convention).
+///
+/// # Parameters
+/// - `project_root`: the root directory of the project (where the top-level `Cargo.toml` lives)
+///
+/// # Returns
+/// A map of `crate_name → crate_root_file_path`.
+pub fn discover_rust_workspace_members(project_root: &Path) -> HashMap<String, PathBuf> {
+    let mut result: HashMap<String, PathBuf> = HashMap::new();
+    for manifest in discover_workspace_manifests(project_root) {
+        if let Some((name, root)) = find_crate_root(&manifest) {
+            // Members listed first win over the root package of the same name.
+            result.entry(name).or_insert(root);
         }
-        result
     }
+    result
 }
 
 // ---------------------------------------------------------------------------
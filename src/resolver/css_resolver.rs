@@ -0,0 +1,241 @@
+//! CSS/SCSS stylesheet resolver.
+//!
+//! Integrates into `resolve_all` as Step 13: each `@import`, `@use`, and `@forward` of a
+//! parsed stylesheet becomes a `ResolvedImport` edge to the target stylesheet.
+//!
+//! Targets follow Sass's lookup rules: an extensionless `foo/bar` matches `foo/bar.scss`,
+//! the partial `foo/_bar.scss`, `foo/bar.css`, and then `foo/bar/_index.scss` /
+//! `foo/bar/index.scss`. Paths are tried against the stylesheet's directory and then the
+//! project root (the usual Sass load path). `~pkg/...` targets and targets found under
+//! `node_modules` become external packages; Sass built-in modules (`sass:math`) and remote
+//! URLs are skipped.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::graph::CodeGraph;
+use crate::graph::node::GraphNode;
+use crate::parser::ParseResult;
+use crate::parser::imports::ImportKind;
+
+/// Statistics collected by the stylesheet resolver.
+#[derive(Debug, Default)]
+pub struct CssResolveStats {
+    /// Imports resolved to an indexed stylesheet.
+    pub resolved: usize,
+    /// Imports of npm packages (`~pkg/...` or found under `node_modules`).
+    pub external: usize,
+    /// Relative imports whose target is not indexed.
+    pub unresolved: usize,
+    /// Sass built-in modules and remote URLs.
+    pub skipped: usize,
+}
+
+/// Returns `true` for `.css` / `.scss` paths.
+pub fn is_css(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("css" | "scss")
+    )
+}
+
+/// Returns `true` if the graph contains at least one stylesheet.
+pub fn has_css_files(graph: &CodeGraph) -> bool {
+    graph
+        .graph
+        .node_weights()
+        .any(|node| matches!(node, GraphNode::File(f) if f.language == "css"))
+}
+
+/// Returns `true` for targets that never name a project file.
+fn is_skipped(target: &str) -> bool {
+    target.starts_with("sass:")
+        || target.starts_with("//")
+        || target.starts_with("data:")
+        || target.contains("://")
+}
+
+/// Files a target may name relative to `base`, in Sass lookup order.
+fn lookup_candidates(base: &Path) -> Vec<PathBuf> {
+    let Some(name) = base.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let partial = base.with_file_name(format!("_{name}"));
+    if matches!(
+        base.extension().and_then(|e| e.to_str()),
+        Some("css" | "scss")
+    ) {
+        return vec![base.to_path_buf(), partial];
+    }
+    vec![
+        base.with_file_name(format!("{name}.scss")),
+        base.with_file_name(format!("_{name}.scss")),
+        base.with_file_name(format!("{name}.css")),
+        base.join("_index.scss"),
+        base.join("index.scss"),
+        base.join("_index.css"),
+        base.join("index.css"),
+    ]
+}
+
+/// Candidate files for an import `target` of `stylesheet`, most likely first.
+fn target_candidates(stylesheet: &Path, project_root: &Path, target: &str) -> Vec<PathBuf> {
+    if let Some(root_relative) = target.strip_prefix('/') {
+        return lookup_candidates(&super::normalize_path(&project_root.join(root_relative)));
+    }
+    let mut candidates = Vec::new();
+    if let Some(dir) = stylesheet.parent() {
+        candidates.extend(lookup_candidates(&super::normalize_path(&dir.join(target))));
+    }
+    if !target.starts_with('.') {
+        candidates.extend(lookup_candidates(&super::normalize_path(
+            &project_root.join(target),
+        )));
+    }
+    candidates
+}
+
+/// Step 13 of `resolve_all`: resolve the imports of every parsed stylesheet.
+pub fn resolve_css(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+    project_root: &Path,
+    verbose: bool,
+) -> CssResolveStats {
+    let mut stats = CssResolveStats::default();
+
+    for (path, result) in parse_results {
+        if !is_css(path) {
+            continue;
+        }
+        let Some(from_idx) = graph.file_index.get(path).copied() else {
+            continue;
+        };
+        for import in &result.imports {
+            if import.kind != ImportKind::CssImport {
+                continue;
+            }
+            let target = import.module_path.as_str();
+            if is_skipped(target) {
+                stats.skipped += 1;
+                continue;
+            }
+            if let Some(package) = target.strip_prefix('~') {
+                graph.add_external_package(from_idx, super::extract_package_name(package), target);
+                stats.external += 1;
+                continue;
+            }
+
+            let found = target_candidates(path, project_root, target)
+                .iter()
+                .find_map(|c| graph.file_index.get(c).copied());
+            if let Some(target_idx) = found {
+                graph.add_import_at(from_idx, target_idx, target, false, import.line, import.col);
+                stats.resolved += 1;
+                continue;
+            }
+
+            let package = super::extract_package_name(target);
+            if !target.starts_with(['.', '/'])
+                && project_root.join("node_modules").join(package).exists()
+            {
+                graph.add_external_package(from_idx, package, target);
+                stats.external += 1;
+            } else {
+                graph.add_unresolved_import(from_idx, target, "stylesheet not found");
+                stats.unresolved += 1;
+                if verbose {
+                    eprintln!(
+                        "  resolve: {} imports '{}' -> unresolved: stylesheet not found",
+                        path.display(),
+                        target
+                    );
+                }
+            }
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::edge::EdgeKind;
+    use crate::parser::css::parse_css;
+    use petgraph::Direction;
+    use petgraph::visit::EdgeRef;
+
+    #[test]
+    fn test_lookup_candidates_follow_sass_rules() {
+        let names: Vec<String> = lookup_candidates(Path::new("/p/styles/tokens"))
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "/p/styles/tokens.scss",
+                "/p/styles/_tokens.scss",
+                "/p/styles/tokens.css",
+                "/p/styles/tokens/_index.scss",
+                "/p/styles/tokens/index.scss",
+                "/p/styles/tokens/_index.css",
+                "/p/styles/tokens/index.css",
+            ]
+        );
+        assert!(is_skipped("sass:math"));
+        assert!(is_skipped("https://fonts.example.com/inter.css"));
+    }
+
+    #[test]
+    fn test_resolve_css_imports() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path().to_path_buf();
+        std::fs::create_dir_all(root.join("node_modules/bootstrap")).unwrap();
+
+        let mut graph = CodeGraph::new();
+        let main_path = root.join("src/styles/main.scss");
+        let tokens_path = root.join("src/styles/_tokens.scss");
+        let buttons_path = root.join("src/styles/buttons/_index.scss");
+        let reset_path = root.join("src/reset.css");
+        let main_idx = graph.add_file(main_path.clone(), "css");
+        let tokens_idx = graph.add_file(tokens_path.clone(), "css");
+        let buttons_idx = graph.add_file(buttons_path.clone(), "css");
+        let reset_idx = graph.add_file(reset_path.clone(), "css");
+
+        let main_src = br#"@use "sass:math";
+@use "tokens";
+@forward "./buttons";
+@import "../reset.css", "missing";
+@import "~normalize.css/normalize";
+@import "bootstrap/scss/bootstrap";
+@import url("https://fonts.example.com/inter.css");
+"#;
+        let mut parse_results = HashMap::new();
+        parse_results.insert(main_path.clone(), parse_css(main_src, true));
+
+        let stats = resolve_css(&mut graph, &parse_results, &root, false);
+        assert_eq!(
+            (
+                stats.resolved,
+                stats.external,
+                stats.unresolved,
+                stats.skipped
+            ),
+            (3, 2, 1, 2)
+        );
+
+        let mut targets: Vec<_> = graph
+            .graph
+            .edges_directed(main_idx, Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+            .map(|e| e.target())
+            .filter(|&t| matches!(graph.graph[t], GraphNode::File(_)))
+            .collect();
+        targets.sort();
+        let mut expected = vec![tokens_idx, buttons_idx, reset_idx];
+        expected.sort();
+        assert_eq!(targets, expected);
+    }
+}
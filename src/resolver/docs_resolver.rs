@@ -0,0 +1,262 @@
+//! Markdown documentation cross-linker.
+//!
+//! Doc files are indexed as non-parsed `File` nodes. This pass reads every Markdown doc and
+//! adds `DocumentedBy` edges for what it mentions:
+//! - intra-repo links (`[guide](../src/auth.ts)`) → edge from the linked file to the doc
+//! - identifiers inside inline code spans and fenced code blocks that name an indexed
+//!   symbol → edge from the symbol to the doc
+//!
+//! `context` lists the docs of a symbol and `impact` flags them as possibly stale.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use petgraph::stable_graph::NodeIndex;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+
+/// Identifiers shorter than this are never linked (`id`, `db`, `i`).
+const MIN_IDENT_LEN: usize = 3;
+
+/// Names defined more often than this (`new`, `default`, `render`) are too ambiguous to
+/// attribute a mention to one symbol, so they are not linked.
+const MAX_AMBIGUOUS_DEFS: usize = 3;
+
+/// Links and code identifiers mentioned by one Markdown document.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct DocRefs {
+    /// Link targets that look like repo paths (no scheme, no pure `#anchor`), with any
+    /// `#fragment` or `?query` stripped.
+    pub links: Vec<String>,
+    /// Distinct identifiers found in inline code spans and fenced code blocks.
+    pub code_idents: Vec<String>,
+}
+
+/// Returns `true` for files this pass reads: Markdown docs.
+pub fn is_markdown(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("md" | "markdown")
+    )
+}
+
+/// Extract links and code identifiers from Markdown source.
+pub(crate) fn extract_doc_refs(text: &str) -> DocRefs {
+    let mut refs = DocRefs::default();
+    let mut seen_idents: HashSet<String> = HashSet::new();
+    let mut fence: Option<&str> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+
+        // Fenced code blocks: ``` or ~~~ open and close (closing fence must match).
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            match fence {
+                Some(open) if open == marker => fence = None,
+                Some(_) => push_idents(trimmed, &mut refs, &mut seen_idents),
+                None => fence = Some(marker),
+            }
+            continue;
+        }
+        if fence.is_some() {
+            push_idents(line, &mut refs, &mut seen_idents);
+            continue;
+        }
+
+        // Reference-style link definition: `[label]: target`.
+        if trimmed.starts_with('[')
+            && let Some(pos) = trimmed.find("]:")
+        {
+            push_link(trimmed[pos + 2..].trim(), &mut refs);
+            continue;
+        }
+
+        // Inline code spans: text between matching backtick runs.
+        let mut rest = line;
+        let mut prose = String::new();
+        while let Some(start) = rest.find('`') {
+            prose.push_str(&rest[..start]);
+            let ticks = rest[start..].chars().take_while(|&c| c == '`').count();
+            let after = &rest[start + ticks..];
+            let closing = "`".repeat(ticks);
+            match after.find(&closing) {
+                Some(end) => {
+                    push_idents(&after[..end], &mut refs, &mut seen_idents);
+                    rest = &after[end + ticks..];
+                }
+                None => {
+                    rest = after;
+                }
+            }
+        }
+        prose.push_str(rest);
+
+        // Inline links and images outside code spans: `[text](target "title")`.
+        let mut rest = prose.as_str();
+        while let Some(pos) = rest.find("](") {
+            let after = &rest[pos + 2..];
+            let end = after.find(')').unwrap_or(after.len());
+            let target = after[..end].split_whitespace().next().unwrap_or("");
+            push_link(target.trim_matches(|c| c == '<' || c == '>'), &mut refs);
+            rest = &after[end..];
+        }
+    }
+
+    refs
+}
+
+fn push_link(target: &str, refs: &mut DocRefs) {
+    let target = target.split(['#', '?']).next().unwrap_or("");
+    if target.is_empty() || target.contains("://") || target.starts_with("mailto:") {
+        return;
+    }
+    if !refs.links.iter().any(|l| l == target) {
+        refs.links.push(target.to_string());
+    }
+}
+
+fn push_idents(code: &str, refs: &mut DocRefs, seen: &mut HashSet<String>) {
+    let mut start: Option<usize> = None;
+    for (i, c) in code
+        .char_indices()
+        .chain(std::iter::once((code.len(), ' ')))
+    {
+        let is_ident = c == '_' || c.is_ascii_alphanumeric();
+        match (start, is_ident) {
+            (None, true) if !c.is_ascii_digit() => start = Some(i),
+            (Some(s), false) => {
+                let ident = &code[s..i];
+                if ident.len() >= MIN_IDENT_LEN && seen.insert(ident.to_string()) {
+                    refs.code_idents.push(ident.to_string());
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Link every Markdown doc in the graph. Returns the number of `DocumentedBy` edges added.
+pub fn link_docs(graph: &mut CodeGraph, project_root: &Path) -> usize {
+    let docs: Vec<NodeIndex> = graph
+        .file_index
+        .iter()
+        .filter(|(path, _)| is_markdown(path))
+        .map(|(_, &idx)| idx)
+        .collect();
+    docs.into_iter()
+        .map(|doc_idx| link_doc_file(graph, doc_idx, project_root))
+        .sum()
+}
+
+/// Read one Markdown doc and add its `DocumentedBy` edges.
+///
+/// Idempotent: mentions that already have an edge to this doc are skipped, so the watcher
+/// can re-run it after the files or symbols a doc mentions are re-indexed.
+pub fn link_doc_file(graph: &mut CodeGraph, doc_idx: NodeIndex, project_root: &Path) -> usize {
+    let doc_path = match &graph.graph[doc_idx] {
+        GraphNode::File(fi) if fi.kind == FileKind::Doc && is_markdown(&fi.path) => fi.path.clone(),
+        _ => return 0,
+    };
+    let Ok(text) = std::fs::read_to_string(&doc_path) else {
+        return 0;
+    };
+    let refs = extract_doc_refs(&text);
+
+    let mut subjects: Vec<NodeIndex> = Vec::new();
+    let doc_dir = doc_path.parent().unwrap_or(project_root);
+    for link in &refs.links {
+        let target = match link.strip_prefix('/') {
+            Some(rooted) => project_root.join(rooted),
+            None => doc_dir.join(link),
+        };
+        if let Some(&file_idx) = graph.file_index.get(&super::normalize_path(&target))
+            && file_idx != doc_idx
+        {
+            subjects.push(file_idx);
+        }
+    }
+    for ident in &refs.code_idents {
+        if let Some(indices) = graph.symbol_index.get(ident)
+            && indices.len() <= MAX_AMBIGUOUS_DEFS
+        {
+            subjects.extend(indices.iter().copied());
+        }
+    }
+
+    let mut added = 0;
+    for subject in subjects {
+        let already_linked = graph
+            .graph
+            .edges_connecting(subject, doc_idx)
+            .any(|e| matches!(e.weight(), EdgeKind::DocumentedBy));
+        if !already_linked {
+            graph.add_documented_by(subject, doc_idx);
+            added += 1;
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+
+    #[test]
+    fn test_extract_doc_refs_links_and_code() {
+        let md = "# Auth\n\
+                  See [the service](../src/auth.ts#L10) and [site](https://example.com).\n\
+                  Call `validateToken(t)` first, not `id`.\n\
+                  ```ts\n\
+                  const s = new AuthService();\n\
+                  ```\n\
+                  [ref]: ./api.md\n";
+        let refs = extract_doc_refs(md);
+        assert_eq!(refs.links, vec!["../src/auth.ts", "./api.md"]);
+        assert_eq!(
+            refs.code_idents,
+            vec!["validateToken", "const", "new", "AuthService"]
+        );
+    }
+
+    #[test]
+    fn test_link_doc_file_adds_documented_by_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::write(
+            root.join("docs/auth.md"),
+            "Uses [auth](../src/auth.ts) via `AuthService.login`.\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let src = graph.add_file(root.join("src/auth.ts"), "typescript");
+        let service = graph.add_symbol(
+            src,
+            SymbolInfo {
+                name: "AuthService".into(),
+                kind: SymbolKind::Class,
+                line: 1,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        let doc = graph.add_non_parsed_file(root.join("docs/auth.md"), FileKind::Doc);
+
+        assert_eq!(link_docs(&mut graph, root), 2);
+        for subject in [src, service] {
+            assert!(
+                graph
+                    .graph
+                    .edges_connecting(subject, doc)
+                    .any(|e| matches!(e.weight(), EdgeKind::DocumentedBy))
+            );
+        }
+        // Re-linking adds nothing new.
+        assert_eq!(link_doc_file(&mut graph, doc, root), 0);
+    }
+}
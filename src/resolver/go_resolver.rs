@@ -136,7 +136,7 @@ fn parse_gowork(content: &str) -> GoWork {
 ///
 /// Heuristic: if the first path segment contains no dot, it's stdlib.
 /// e.g. "fmt" -> true, "net/http" -> true, "github.com/pkg/errors" -> false.
-fn is_go_stdlib(import_path: &str) -> bool {
+pub(crate) fn is_go_stdlib(import_path: &str) -> bool {
     let first_segment = import_path.split('/').next().unwrap_or("");
     !first_segment.contains('.') && !first_segment.is_empty()
 }
@@ -571,7 +571,14 @@ pub fn resolve_go_imports(
                                     );
                                 }
                                 _ => {
-                                    graph.add_resolved_import(from_idx, target_idx, import_path);
+                                    graph.add_import_at(
+                                        from_idx,
+                                        target_idx,
+                                        import_path,
+                                        false,
+                                        import.line,
+                                        import.col,
+                                    );
                                 }
                             }
                         }
@@ -769,6 +776,8 @@ use ./module-c
             kind: ImportKind::GoAbsolute,
             specifiers: vec![],
             line: 3,
+            col: 0,
+            is_type_only: false,
         };
 
         let mut parse_results: HashMap<PathBuf, ParseResult> = HashMap::new();
@@ -780,6 +789,7 @@ use ./module-c
                 exports: vec![],
                 relationships: vec![],
                 rust_uses: vec![],
+                ..Default::default()
             },
         );
 
@@ -809,11 +819,21 @@ use ./module-c
             line: 1,
             col: 0,
             line_end: 5,
+            col_end: 1,
+            byte_start: 0,
+            byte_end: 0,
             is_exported: true,
             is_default: false,
             visibility: SymbolVisibility::Pub,
             trait_impl: receiver.map(|s| s.to_string()),
             decorators: vec![],
+            category: None,
+            signature: None,
+            cfg: None,
+            is_abstract: false,
+            is_override: false,
+            complexity: None,
+            derived: None,
         }
     }
 
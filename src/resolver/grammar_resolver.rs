@@ -0,0 +1,135 @@
+//! Import resolver for files parsed by external grammars.
+//!
+//! Integrates into `resolve_all` as Step 14. An external grammar's `@import` captures carry
+//! no language-specific resolution rules, so a target is matched against indexed files
+//! only as a path: relative to the importing file, then to the project root, with and
+//! without the importing file's extension (`./util` from `lib/a.ex` finds `lib/util.ex`).
+//! Other relative paths are unresolved, and anything else (`Phoenix.Controller`,
+//! `scala.collection`) becomes an external package.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::graph::CodeGraph;
+use crate::parser::ParseResult;
+use crate::parser::imports::ImportKind;
+
+/// Statistics collected by the external grammar resolver.
+#[derive(Debug, Default)]
+pub struct GrammarResolveStats {
+    /// Imports resolved to an indexed file.
+    pub resolved: usize,
+    /// Imports recorded as external packages.
+    pub external: usize,
+    /// Relative imports whose target is not indexed.
+    pub unresolved: usize,
+}
+
+/// Candidate files a `target` imported by `file` may name, most likely first.
+fn target_candidates(file: &Path, project_root: &Path, target: &str) -> Vec<PathBuf> {
+    let ext = file.extension().and_then(|e| e.to_str());
+    let bases = [
+        file.parent().map(|dir| dir.join(target)),
+        Some(project_root.join(target.trim_start_matches('/'))),
+    ];
+    let mut candidates = Vec::new();
+    for base in bases.into_iter().flatten() {
+        let base = super::normalize_path(&base);
+        if let Some(ext) = ext {
+            let mut with_ext = base.clone().into_os_string();
+            with_ext.push(".");
+            with_ext.push(ext);
+            candidates.push(PathBuf::from(with_ext));
+        }
+        candidates.push(base);
+    }
+    candidates
+}
+
+/// Step 14 of `resolve_all`: resolve the `@import` captures of externally parsed files.
+pub fn resolve_grammar_imports(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+    project_root: &Path,
+    verbose: bool,
+) -> GrammarResolveStats {
+    let mut stats = GrammarResolveStats::default();
+
+    for (path, result) in parse_results {
+        let Some(from_idx) = graph.file_index.get(path).copied() else {
+            continue;
+        };
+        for import in &result.imports {
+            if import.kind != ImportKind::GrammarImport {
+                continue;
+            }
+            let target = import.module_path.as_str();
+            let found = target_candidates(path, project_root, target)
+                .iter()
+                .find_map(|c| graph.file_index.get(c).copied())
+                .filter(|&idx| idx != from_idx);
+            if let Some(target_idx) = found {
+                graph.add_import_at(from_idx, target_idx, target, false, import.line, import.col);
+                stats.resolved += 1;
+            } else if target.starts_with(['.', '/']) {
+                graph.add_unresolved_import(from_idx, target, "file not found");
+                stats.unresolved += 1;
+                if verbose {
+                    eprintln!(
+                        "  resolve: {} imports '{}' -> unresolved: file not found",
+                        path.display(),
+                        target
+                    );
+                }
+            } else {
+                graph.add_external_package(from_idx, target, target);
+                stats.external += 1;
+            }
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::imports::ImportInfo;
+
+    #[test]
+    fn test_resolve_grammar_imports() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let app = root.join("lib/app.ex");
+        let app_idx = graph.add_file(app.clone(), "elixir");
+        let util_idx = graph.add_file(root.join("lib/util.ex"), "elixir");
+
+        let import = |target: &str| ImportInfo {
+            kind: ImportKind::GrammarImport,
+            module_path: target.into(),
+            specifiers: Vec::new(),
+            line: 1,
+            col: 0,
+            is_type_only: false,
+        };
+        let result = ParseResult {
+            imports: vec![
+                import("./util"),
+                import("lib/util.ex"),
+                import("./missing"),
+                import("Phoenix.Controller"),
+            ],
+            ..Default::default()
+        };
+        let mut parse_results = HashMap::new();
+        parse_results.insert(app, result);
+
+        let stats = resolve_grammar_imports(&mut graph, &parse_results, &root, false);
+        assert_eq!(
+            (stats.resolved, stats.external, stats.unresolved),
+            (2, 1, 1)
+        );
+        assert!(graph.graph.contains_edge(app_idx, util_idx));
+        assert!(graph.external_index.contains_key("Phoenix.Controller"));
+    }
+}
@@ -1,25 +1,43 @@
 pub mod barrel;
+pub mod bindings;
+pub mod cargo_deps;
+pub mod cargo_features;
 pub mod cargo_workspace;
+pub mod css_resolver;
+pub mod docs_resolver;
 pub mod file_resolver;
 pub mod go_resolver;
+pub mod grammar_resolver;
+pub mod openapi_resolver;
+pub mod overrides;
+pub mod proto_resolver;
 pub mod python_resolver;
+pub mod rust_calls;
 pub mod rust_mod_tree;
 pub mod rust_resolver;
+pub mod shell_resolver;
+pub mod sql_resolver;
+pub mod string_refs;
+pub mod tests_resolver;
 pub mod workspace;
+pub mod zig_resolver;
 
 pub use file_resolver::{
     ResolutionOutcome, build_resolver, resolve_import, workspace_map_to_aliases,
 };
 pub use workspace::discover_workspace_packages;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 
 use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{GraphNode, SymbolKind};
 use crate::parser::ParseResult;
-use crate::parser::relationships::RelationshipKind;
+use crate::parser::relationships::{RelationshipInfo, RelationshipKind};
 
 /// Statistics collected during the resolution pipeline.
 #[derive(Debug, Default)]
@@ -47,6 +65,8 @@ pub struct ResolveStats {
     pub rust_builtin: usize,
     /// Rust use paths that could not be resolved — `UnresolvedImport` nodes created.
     pub rust_unresolved: usize,
+    /// `Calls` edges from Rust files to functions and methods (Step 6b).
+    pub rust_calls: usize,
 
     // --- Go-specific (Step 8) ---
     /// Go imports resolved to local file nodes.
@@ -57,6 +77,44 @@ pub struct ResolveStats {
     pub go_external: usize,
     /// Go imports that could not be resolved.
     pub go_unresolved: usize,
+
+    // --- Protobuf-specific (Step 9) ---
+    /// Proto `import` statements resolved to an indexed proto file.
+    pub proto_resolved: usize,
+    /// Edges linking generated stubs (or their importers) to proto files.
+    pub proto_generated_links: usize,
+
+    // --- SQL-specific (Step 10) ---
+    /// `ReferencesTable` edges from SQL statements or SQL string literals to tables/views.
+    pub sql_table_refs: usize,
+
+    // --- Zig-specific (Step 11) ---
+    /// Zig `@import`s resolved to an indexed file (relative paths and build modules).
+    pub zig_resolved: usize,
+    /// Zig `@import`s of package dependencies.
+    pub zig_external: usize,
+
+    // --- Shell-specific (Step 12) ---
+    /// `source` includes resolved to an indexed script.
+    pub shell_resolved: usize,
+    /// `Calls` edges from shell command words to shell functions.
+    pub shell_calls: usize,
+
+    // --- Stylesheet-specific (Step 13) ---
+    /// `@import`/`@use`/`@forward` targets resolved to an indexed stylesheet.
+    pub css_resolved: usize,
+    /// Stylesheet imports of npm packages.
+    pub css_external: usize,
+
+    // --- External grammars (Step 14) ---
+    /// `@import` captures of externally parsed files resolved to an indexed file.
+    pub grammar_resolved: usize,
+    /// `@import` captures of externally parsed files recorded as external packages.
+    pub grammar_external: usize,
+
+    // --- Import bindings (Step 15) ---
+    /// `ImportsSymbol` edges from named import specifiers to the symbols they bind.
+    pub import_bindings: usize,
 }
 
 /// Run the full import resolution pipeline on the code graph.
@@ -126,6 +184,18 @@ pub fn resolve_all(
         };
 
         for import in imports {
+            // Proto, Zig, shell, stylesheet, and external grammar imports are resolved in
+            // Steps 9, 11, 12, 13, and 14, not by the TS resolver.
+            if matches!(
+                import.kind,
+                crate::parser::imports::ImportKind::ProtoImport
+                    | crate::parser::imports::ImportKind::ZigImport
+                    | crate::parser::imports::ImportKind::ShellSource
+                    | crate::parser::imports::ImportKind::CssImport
+                    | crate::parser::imports::ImportKind::GrammarImport
+            ) {
+                continue;
+            }
             let specifier = &import.module_path;
             let outcome = resolve_import(&resolver, file_path, specifier);
 
@@ -133,8 +203,28 @@ pub fn resolve_all(
                 ResolutionOutcome::Resolved(target_path) => {
                     // Check if the resolved target is in the graph (was indexed).
                     if let Some(&target_idx) = graph.file_index.get(&target_path) {
-                        graph.add_resolved_import(from_idx, target_idx, specifier);
+                        graph.add_import_at(
+                            from_idx,
+                            target_idx,
+                            specifier,
+                            import.is_type_only,
+                            import.line,
+                            import.col,
+                        );
                         stats.resolved += 1;
+                    } else if is_external_package(specifier)
+                        && target_path
+                            .components()
+                            .any(|c| c.as_os_str() == "node_modules")
+                    {
+                        // An installed package: record it like an uninstalled one, so
+                        // external usage does not depend on whether `node_modules` exists.
+                        graph.add_external_package(
+                            from_idx,
+                            extract_package_name(specifier),
+                            specifier,
+                        );
+                        stats.external += 1;
                     } else {
                         // Resolved to a path not in the graph (e.g. JSON, .node file, or
                         // a file outside the indexed project). Treat as unresolved.
@@ -207,6 +297,13 @@ pub fn resolve_all(
         eprintln!("  Named re-export edges added: {}", named_reexport_edges);
     }
 
+    // Step 4c: Export aliases (`export { Foo as Bar }`), so calls and bindings of `Bar`
+    // reach `Foo`.
+    let aliases = barrel::resolve_reexport_aliases(graph, parse_results, verbose);
+    if verbose {
+        eprintln!("  Export aliases recorded: {}", aliases);
+    }
+
     // -----------------------------------------------------------------------
     // Step 5: Symbol relationship pass.
     // -----------------------------------------------------------------------
@@ -218,10 +315,15 @@ pub fn resolve_all(
             .collect();
 
     for (_file_path, relationships) in &file_relationships {
+        // Shell command words only bind to shell functions (Step 12).
+        if shell_resolver::is_shell(_file_path) {
+            continue;
+        }
         let from_file_idx = match graph.file_index.get(_file_path).copied() {
             Some(idx) => idx,
             None => continue,
         };
+        let is_rust_file = _file_path.extension().is_some_and(|ext| ext == "rs");
 
         for rel in relationships {
             match rel.kind {
@@ -234,17 +336,33 @@ pub fn resolve_all(
                         None => continue,
                     };
 
-                    let from_candidates = graph
+                    let mut from_candidates = graph
                         .symbol_index
                         .get(from_name)
                         .cloned()
                         .unwrap_or_default();
-                    let to_candidates = graph
+                    let mut to_candidates = graph
                         .symbol_index
                         .get(&rel.to_name)
                         .cloned()
                         .unwrap_or_default();
 
+                    // Rust `impl Trait for Type` only links a type to a trait, never to a
+                    // same-named TS interface or a Rust type sharing the trait's name.
+                    if is_rust_file {
+                        let has_kind = |idx: &NodeIndex, kinds: &[SymbolKind]| {
+                            matches!(&graph.graph[*idx],
+                                GraphNode::Symbol(info) if kinds.contains(&info.kind))
+                        };
+                        from_candidates.retain(|idx| {
+                            has_kind(
+                                idx,
+                                &[SymbolKind::Struct, SymbolKind::Enum, SymbolKind::TypeAlias],
+                            )
+                        });
+                        to_candidates.retain(|idx| has_kind(idx, &[SymbolKind::Trait]));
+                    }
+
                     if from_candidates.is_empty() || to_candidates.is_empty() {
                         continue;
                     }
@@ -292,11 +410,14 @@ pub fn resolve_all(
                     }
                 }
 
+                // Rust calls are resolved against the caller's imports in Step 6b.
+                RelationshipKind::Calls | RelationshipKind::MethodCall if is_rust_file => {}
+
                 RelationshipKind::Calls
                 | RelationshipKind::MethodCall
                 | RelationshipKind::TypeReference => {
-                    // Look up the callee / type name in the symbol index.
-                    let to_candidates = match graph.symbol_index.get(&rel.to_name) {
+                    // Look up the callee / type name in the symbol index, then the aliases.
+                    let to_candidates = match graph.symbols_named(&rel.to_name) {
                         Some(c) if !c.is_empty() => c.clone(),
                         _ => continue,
                     };
@@ -305,11 +426,20 @@ pub fn resolve_all(
                     // Cross-file call ambiguity is a documented limitation per research.
                     if to_candidates.len() == 1 {
                         let callee_idx = to_candidates[0];
-                        graph.add_calls_edge(from_file_idx, callee_idx);
+                        graph.add_call_site(from_file_idx, callee_idx, rel.line);
                         stats.relationships_added += 1;
                     }
                     // If multiple candidates: skip (ambiguous cross-file call — documented limitation)
                 }
+
+                // Table references are matched case-insensitively against SQL tables in Step 10.
+                RelationshipKind::TableReference => {}
+
+                RelationshipKind::Renders => {
+                    if wire_render(graph, from_file_idx, rel) {
+                        stats.relationships_added += 1;
+                    }
+                }
             }
         }
     }
@@ -339,6 +469,13 @@ pub fn resolve_all(
                 rust_stats.resolved, rust_stats.external, rust_stats.builtin, rust_stats.unresolved
             );
         }
+
+        // Step 6b: Rust calls, now that `use` edges tell which files a caller imports.
+        stats.rust_calls = rust_calls::resolve_rust_calls(graph, parse_results);
+        stats.relationships_added += stats.rust_calls;
+        if verbose {
+            eprintln!("  Rust calls: {} edges", stats.rust_calls);
+        }
     }
 
     // -----------------------------------------------------------------------
@@ -389,6 +526,121 @@ pub fn resolve_all(
         }
     }
 
+    // -----------------------------------------------------------------------
+    // Step 9: Protobuf import resolution and generated-code linking.
+    // -----------------------------------------------------------------------
+    let has_proto_files = graph.graph.node_indices().any(|idx| {
+        if let crate::graph::node::GraphNode::File(ref f) = graph.graph[idx] {
+            f.language == "proto"
+        } else {
+            false
+        }
+    });
+    if has_proto_files {
+        let proto_stats =
+            proto_resolver::resolve_proto(graph, parse_results, project_root, verbose);
+        stats.proto_resolved = proto_stats.resolved;
+        stats.proto_generated_links = proto_stats.generated_links;
+        stats.unresolved += proto_stats.unresolved;
+        if verbose {
+            eprintln!(
+                "  Proto resolution: {} resolved, {} unresolved, {} generated-code links",
+                proto_stats.resolved, proto_stats.unresolved, proto_stats.generated_links
+            );
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Step 10: SQL table references.
+    // -----------------------------------------------------------------------
+    if sql_resolver::has_tables(graph) {
+        stats.sql_table_refs = sql_resolver::resolve_table_refs(graph, parse_results);
+        if verbose {
+            eprintln!(
+                "  SQL resolution: {} table references",
+                stats.sql_table_refs
+            );
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Step 11: Zig @import resolution (relative paths and build.zig modules).
+    // -----------------------------------------------------------------------
+    if zig_resolver::has_zig_files(graph) {
+        let zig_stats = zig_resolver::resolve_zig(graph, parse_results, verbose);
+        stats.zig_resolved = zig_stats.resolved;
+        stats.zig_external = zig_stats.external;
+        stats.unresolved += zig_stats.unresolved;
+        if verbose {
+            eprintln!(
+                "  Zig resolution: {} resolved, {} builtin, {} external, {} unresolved",
+                zig_stats.resolved, zig_stats.builtin, zig_stats.external, zig_stats.unresolved
+            );
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Step 12: Shell `source` includes and function calls.
+    // -----------------------------------------------------------------------
+    if shell_resolver::has_shell_files(graph) {
+        let shell_stats =
+            shell_resolver::resolve_shell(graph, parse_results, project_root, verbose);
+        stats.shell_resolved = shell_stats.resolved;
+        stats.shell_calls = shell_stats.calls;
+        stats.unresolved += shell_stats.unresolved;
+        if verbose {
+            eprintln!(
+                "  Shell resolution: {} resolved, {} unresolved, {} skipped, {} calls",
+                shell_stats.resolved,
+                shell_stats.unresolved,
+                shell_stats.skipped,
+                shell_stats.calls
+            );
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Step 13: CSS/SCSS `@import`, `@use`, and `@forward`.
+    // -----------------------------------------------------------------------
+    // TS/JS imports of stylesheets (`import styles from './x.module.css'`) were already
+    // linked in Step 3, since stylesheets are indexed files.
+    if css_resolver::has_css_files(graph) {
+        let css_stats = css_resolver::resolve_css(graph, parse_results, project_root, verbose);
+        stats.css_resolved = css_stats.resolved;
+        stats.css_external = css_stats.external;
+        stats.unresolved += css_stats.unresolved;
+        if verbose {
+            eprintln!(
+                "  CSS resolution: {} resolved, {} external, {} unresolved, {} skipped",
+                css_stats.resolved, css_stats.external, css_stats.unresolved, css_stats.skipped
+            );
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Step 14: External grammar imports (`[[grammar]]` in code-graph.toml).
+    // -----------------------------------------------------------------------
+    let grammar_stats =
+        grammar_resolver::resolve_grammar_imports(graph, parse_results, project_root, verbose);
+    stats.grammar_resolved = grammar_stats.resolved;
+    stats.grammar_external = grammar_stats.external;
+    stats.unresolved += grammar_stats.unresolved;
+    if verbose && grammar_stats.resolved + grammar_stats.external + grammar_stats.unresolved > 0 {
+        eprintln!(
+            "  External grammar resolution: {} resolved, {} external, {} unresolved",
+            grammar_stats.resolved, grammar_stats.external, grammar_stats.unresolved
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Step 15: Symbol-level import bindings.
+    // -----------------------------------------------------------------------
+    // Runs last so named imports of every language have been resolved to files.
+    stats.import_bindings = bindings::resolve_import_bindings(graph, parse_results, verbose);
+    if verbose {
+        eprintln!("  Import bindings added: {}", stats.import_bindings);
+    }
+
     stats
 }
 
@@ -396,6 +648,89 @@ pub fn resolve_all(
 // Helper functions
 // ---------------------------------------------------------------------------
 
+/// Lexically normalize `.` and `..` components (the target may not exist on disk).
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// Link a JSX render site of `file_idx` to the component it renders with a `Renders`
+/// edge from the enclosing top-level symbol (the file itself for module-level JSX).
+///
+/// The component is the top-level symbol named `rel.to_name` declared in the file, else
+/// in a file it imports (following `export *` barrels), else the only one in the project.
+/// Returns `true` when an edge was added.
+pub(crate) fn wire_render(
+    graph: &mut CodeGraph,
+    file_idx: NodeIndex,
+    rel: &RelationshipInfo,
+) -> bool {
+    let top_level = |graph: &CodeGraph, file: NodeIndex, name: &str| {
+        graph
+            .graph
+            .edges(file)
+            .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+            .map(|e| e.target())
+            .find(|&idx| matches!(&graph.graph[idx], GraphNode::Symbol(s) if s.name == name))
+    };
+
+    let mut component = top_level(graph, file_idx, &rel.to_name);
+    if component.is_none() {
+        let mut queue: Vec<NodeIndex> = graph
+            .graph
+            .edges(file_idx)
+            .filter(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+            .map(|e| e.target())
+            .collect();
+        let mut visited: HashSet<NodeIndex> = HashSet::new();
+        while let Some(file) = queue.pop() {
+            if !visited.insert(file) || file == file_idx {
+                continue;
+            }
+            component = top_level(graph, file, &rel.to_name);
+            if component.is_some() {
+                break;
+            }
+            queue.extend(
+                graph
+                    .graph
+                    .edges(file)
+                    .filter(|e| matches!(e.weight(), EdgeKind::BarrelReExportAll))
+                    .map(|e| e.target()),
+            );
+        }
+    }
+    if component.is_none()
+        && let Some(candidates) = graph.symbol_index.get(&rel.to_name)
+        && candidates.len() == 1
+    {
+        component = Some(candidates[0]);
+    }
+    let Some(component) = component else {
+        return false;
+    };
+
+    let from = rel
+        .from_name
+        .as_deref()
+        .and_then(|name| top_level(graph, file_idx, name))
+        .unwrap_or(file_idx);
+    if from == component {
+        return false;
+    }
+    graph.add_renders(from, component);
+    true
+}
+
 /// Returns `true` if the specifier looks like an external package reference.
 ///
 /// External packages:
@@ -455,4 +790,87 @@ mod tests {
         assert_eq!(extract_package_name("lodash/merge"), "lodash");
         assert_eq!(extract_package_name("lodash"), "lodash");
     }
+
+    #[test]
+    fn test_rust_trait_impls_link_across_files() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path().to_path_buf();
+        let files = [
+            ("Cargo.toml", "[package]\nname = \"shapes\"\n"),
+            ("src/lib.rs", "pub mod circle;\npub trait Shape {}\n"),
+            (
+                "src/circle.rs",
+                "use crate::Shape;\npub struct Circle;\nimpl Shape for Circle {}\nimpl std::fmt::Debug for Circle {}\n",
+            ),
+        ];
+        let mut graph = CodeGraph::new();
+        let mut parse_results = HashMap::new();
+        for (rel, src) in files {
+            let path = root.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, src).unwrap();
+            if rel.ends_with(".rs") {
+                let result = crate::parser::parse_file(&path, src.as_bytes()).unwrap();
+                let file_idx = graph.add_file(path.clone(), "rust");
+                for (symbol, _) in &result.symbols {
+                    graph.add_symbol(file_idx, symbol.clone());
+                }
+                parse_results.insert(path, result);
+            }
+        }
+
+        resolve_all(&mut graph, &root, &parse_results, false);
+
+        let circle = graph.symbol_index["Circle"][0];
+        let shape = graph.symbol_index["Shape"][0];
+        let implements: Vec<NodeIndex> = graph
+            .graph
+            .edges(circle)
+            .filter(|e| matches!(e.weight(), crate::graph::edge::EdgeKind::Implements))
+            .map(|e| e.target())
+            .collect();
+        assert_eq!(implements, vec![shape]);
+    }
+
+    #[test]
+    fn test_jsx_renders_link_enclosing_component_through_barrel() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let files = [
+            ("src/ui/index.ts", "export * from './Button';\n"),
+            (
+                "src/ui/Button.tsx",
+                "export function Button() {\n  return <button />;\n}\n",
+            ),
+            (
+                "src/App.tsx",
+                "import { Button } from './ui';\nexport function App() {\n  return <Button />;\n}\n",
+            ),
+        ];
+        let mut graph = CodeGraph::new();
+        let mut parse_results = HashMap::new();
+        for (rel, src) in files {
+            let path = root.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, src).unwrap();
+            let result = crate::parser::parse_file(&path, src.as_bytes()).unwrap();
+            let file_idx = graph.add_file(path.clone(), "typescript");
+            for (symbol, _) in &result.symbols {
+                graph.add_symbol(file_idx, symbol.clone());
+            }
+            parse_results.insert(path, result);
+        }
+
+        resolve_all(&mut graph, &root, &parse_results, false);
+
+        let app = graph.symbol_index["App"][0];
+        let button = graph.symbol_index["Button"][0];
+        let rendered: Vec<NodeIndex> = graph
+            .graph
+            .edges(app)
+            .filter(|e| matches!(e.weight(), EdgeKind::Renders))
+            .map(|e| e.target())
+            .collect();
+        assert_eq!(rendered, vec![button]);
+    }
 }
@@ -0,0 +1,727 @@
+//! OpenAPI spec linker.
+//!
+//! Spec files (`openapi.yaml`, `swagger.json`, `*.openapi.yml`, ...) are indexed as
+//! non-parsed `File` nodes. This pass reads each spec's `paths` and adds one
+//! `SymbolKind::Endpoint` symbol per operation, named `METHOD /path` (`GET /users/{id}`),
+//! then links every endpoint to the TS/JS functions that implement it with `HandledBy`
+//! edges. A handler matches when:
+//! - its name equals the operation's `operationId`, or
+//! - it is registered for the same method and path, either by NestJS-style decorators
+//!   (`@Controller('users')` + `@Get(':id')`) or by an Express-style call
+//!   (`router.get('/users/:id', getUser)`).
+//!
+//! Path parameters match regardless of spelling (`{id}` and `:userId` are the same segment).
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{GraphNode, SymbolInfo, SymbolKind};
+
+/// HTTP methods that name an operation under an OpenAPI path item.
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// `operationId`s defined more often than this are too ambiguous to link.
+const MAX_AMBIGUOUS_DEFS: usize = 3;
+
+/// Statistics collected while linking OpenAPI specs.
+#[derive(Debug, Default)]
+pub struct OpenApiLinkStats {
+    /// Endpoint symbols present on spec files after linking.
+    pub endpoints: usize,
+    /// `HandledBy` edges added.
+    pub handled_by: usize,
+}
+
+/// One operation declared by a spec.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SpecEndpoint {
+    /// Upper-case HTTP method.
+    pub method: String,
+    /// Path template as written in the spec (`/users/{id}`).
+    pub path: String,
+    pub operation_id: Option<String>,
+    /// 1-based line of the method key.
+    pub line: usize,
+}
+
+impl SpecEndpoint {
+    fn symbol_name(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+}
+
+/// Returns `true` for files this pass reads: YAML/JSON files whose name contains `openapi`
+/// or `swagger`.
+pub fn is_openapi_spec(path: &Path) -> bool {
+    let ext_ok = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yaml" | "yml" | "json")
+    );
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    ext_ok && (name.contains("openapi") || name.contains("swagger"))
+}
+
+/// Extract the operations of a spec. Returns nothing unless the document declares a
+/// top-level `openapi` or `swagger` version.
+pub(crate) fn extract_endpoints(text: &str, is_json: bool) -> Vec<SpecEndpoint> {
+    if is_json {
+        extract_json_endpoints(text)
+    } else {
+        extract_yaml_endpoints(text)
+    }
+}
+
+fn extract_json_endpoints(text: &str) -> Vec<SpecEndpoint> {
+    let Ok(doc) = serde_json::from_str::<serde_json::Value>(text) else {
+        return Vec::new();
+    };
+    if doc.get("openapi").is_none() && doc.get("swagger").is_none() {
+        return Vec::new();
+    }
+    let Some(paths) = doc.get("paths").and_then(|p| p.as_object()) else {
+        return Vec::new();
+    };
+
+    // serde_json does not keep positions: locate each path key in the text, then each
+    // method key after it.
+    let line_of = |offset: usize| text[..offset].matches('\n').count() + 1;
+    let mut endpoints = Vec::new();
+    for (path, item) in paths {
+        let Some(item) = item.as_object() else {
+            continue;
+        };
+        let path_offset = text.find(&format!("\"{}\"", path)).unwrap_or(0);
+        for (method, op) in item {
+            if !HTTP_METHODS.contains(&method.as_str()) {
+                continue;
+            }
+            let method_offset = text[path_offset..]
+                .find(&format!("\"{}\"", method))
+                .map_or(path_offset, |o| path_offset + o);
+            endpoints.push(SpecEndpoint {
+                method: method.to_ascii_uppercase(),
+                path: path.clone(),
+                operation_id: op
+                    .get("operationId")
+                    .and_then(|id| id.as_str())
+                    .map(str::to_string),
+                line: line_of(method_offset),
+            });
+        }
+    }
+    endpoints
+}
+
+/// Split a block-style YAML mapping line into `(key, value)`. Flow-style and list lines
+/// return `None`.
+fn yaml_key(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.starts_with('-') || line.starts_with('#') {
+        return None;
+    }
+    for quote in ['"', '\''] {
+        if let Some(rest) = line.strip_prefix(quote) {
+            let end = rest.find(quote)?;
+            let value = rest[end + 1..].trim_start().strip_prefix(':')?;
+            return Some((&rest[..end], value.trim()));
+        }
+    }
+    let colon = line
+        .char_indices()
+        .find(|&(i, c)| c == ':' && line[i + 1..].chars().next().is_none_or(|n| n == ' '))
+        .map(|(i, _)| i)?;
+    Some((line[..colon].trim(), line[colon + 1..].trim()))
+}
+
+fn yaml_scalar(value: &str) -> &str {
+    let value = match value.find(" #") {
+        Some(comment) => &value[..comment],
+        None => value,
+    };
+    value.trim().trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// Indentation scanner for the `paths` section of a block-style YAML spec: path keys at
+/// one level, method keys below them, and each operation's own `operationId` below those.
+fn extract_yaml_endpoints(text: &str) -> Vec<SpecEndpoint> {
+    let mut is_spec = false;
+    let mut in_paths = false;
+    let mut path_indent: Option<usize> = None;
+    let mut method_indent: Option<usize> = None;
+    let mut op_indent: Option<usize> = None;
+    let mut current_path: Option<String> = None;
+    let mut current_op: Option<usize> = None;
+    let mut endpoints: Vec<SpecEndpoint> = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        let key = yaml_key(line);
+
+        if indent == 0 {
+            let top = key.map(|(k, _)| k);
+            is_spec |= matches!(top, Some("openapi" | "swagger"));
+            in_paths = top == Some("paths");
+            (path_indent, current_path, current_op) = (None, None, None);
+            continue;
+        }
+        if !in_paths {
+            continue;
+        }
+        let Some((key, value)) = key else {
+            continue;
+        };
+
+        let path_level = *path_indent.get_or_insert(indent);
+        if indent <= path_level {
+            current_path = (indent == path_level).then(|| key.to_string());
+            (method_indent, current_op) = (None, None);
+            continue;
+        }
+        let Some(path) = &current_path else {
+            continue;
+        };
+        let method_level = *method_indent.get_or_insert(indent);
+        if indent <= method_level {
+            current_op = None;
+            if indent == method_level && HTTP_METHODS.contains(&key) {
+                endpoints.push(SpecEndpoint {
+                    method: key.to_ascii_uppercase(),
+                    path: path.clone(),
+                    operation_id: None,
+                    line: i + 1,
+                });
+                current_op = Some(endpoints.len() - 1);
+                op_indent = None;
+            }
+            continue;
+        }
+        if let Some(op) = current_op
+            && indent == *op_indent.get_or_insert(indent)
+            && key == "operationId"
+        {
+            endpoints[op].operation_id = Some(yaml_scalar(value).to_string());
+        }
+    }
+
+    if is_spec { endpoints } else { Vec::new() }
+}
+
+/// Canonical form of a route for matching: no empty segments, every parameter (`{id}`,
+/// `:id`, `:id(\d+)`) replaced by `{}`.
+fn normalize_route(path: &str) -> String {
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            if s.starts_with(':') || (s.starts_with('{') && s.ends_with('}')) {
+                "{}"
+            } else {
+                s
+            }
+        })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// First quoted string inside decorator or call arguments.
+fn first_string_literal(args: &str) -> Option<&str> {
+    let start = args.find(['\'', '"', '`'])?;
+    let quote = args[start..].chars().next()?;
+    let rest = &args[start + 1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+/// Express-style route registrations in TS/JS source: `<obj>.<method>('/path', ..., handler)`
+/// where the last argument names a function. Returns `(METHOD, path, handler name)`.
+pub(crate) fn route_registrations(source: &str) -> Vec<(String, String, String)> {
+    let mut routes = Vec::new();
+    let mut rest = source;
+    while let Some(dot) = rest.find('.') {
+        let after = &rest[dot + 1..];
+        rest = after;
+        let Some(open) = after.find('(') else {
+            break;
+        };
+        let method = &after[..open];
+        if !HTTP_METHODS.contains(&method) && method != "all" {
+            continue;
+        }
+        let args_start = &after[open + 1..];
+        if !args_start.trim_start().starts_with(['\'', '"', '`']) {
+            continue;
+        }
+        let Some(path) = first_string_literal(args_start).filter(|p| p.starts_with('/')) else {
+            continue;
+        };
+
+        // Split the argument list at top-level commas, up to the matching `)`.
+        let mut depth = 0usize;
+        let mut quote: Option<char> = None;
+        let mut args: Vec<&str> = Vec::new();
+        let mut arg_start = 0;
+        let mut end = None;
+        for (i, c) in args_start.char_indices() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"' | '`') => quote = Some(c),
+                (None, '(' | '[' | '{') => depth += 1,
+                (None, ')') if depth == 0 => {
+                    args.push(&args_start[arg_start..i]);
+                    end = Some(i);
+                    break;
+                }
+                (None, ')' | ']' | '}') => depth = depth.saturating_sub(1),
+                (None, ',') if depth == 0 => {
+                    args.push(&args_start[arg_start..i]);
+                    arg_start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            continue;
+        };
+        rest = &args_start[end..];
+
+        let handler = args
+            .iter()
+            .rev()
+            .map(|a| a.trim())
+            .find(|a| !a.is_empty())
+            .and_then(|a| a.rsplit('.').next())
+            .unwrap_or("");
+        let is_ident = !handler.is_empty()
+            && !handler.starts_with(|c: char| c.is_ascii_digit())
+            && handler
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+        if args.len() >= 2 && is_ident {
+            routes.push((
+                method.to_ascii_uppercase(),
+                path.to_string(),
+                handler.to_string(),
+            ));
+        }
+    }
+    routes
+}
+
+fn is_ts_or_js(graph: &CodeGraph, file_idx: NodeIndex) -> bool {
+    matches!(
+        &graph.graph[file_idx],
+        GraphNode::File(f) if matches!(f.language.as_str(), "typescript" | "tsx" | "javascript")
+    )
+}
+
+/// The file node declaring `sym`, following `ChildOf` for class members.
+fn owning_file(graph: &CodeGraph, sym: NodeIndex) -> Option<NodeIndex> {
+    let mut current = sym;
+    for _ in 0..4 {
+        let mut parent = None;
+        for edge in graph.graph.edges_directed(current, Direction::Incoming) {
+            if matches!(edge.weight(), EdgeKind::Contains) {
+                return Some(edge.source());
+            }
+        }
+        for edge in graph.graph.edges_directed(current, Direction::Outgoing) {
+            if matches!(edge.weight(), EdgeKind::ChildOf) {
+                parent = Some(edge.target());
+            }
+        }
+        current = parent?;
+    }
+    None
+}
+
+/// TS/JS functions and methods named `name`.
+fn handler_candidates(graph: &CodeGraph, name: &str) -> Vec<NodeIndex> {
+    let Some(indices) = graph.symbol_index.get(name) else {
+        return Vec::new();
+    };
+    indices
+        .iter()
+        .copied()
+        .filter(|&idx| {
+            matches!(
+                &graph.graph[idx],
+                GraphNode::Symbol(s) if matches!(
+                    s.kind,
+                    SymbolKind::Function | SymbolKind::Method | SymbolKind::Variable
+                )
+            )
+        })
+        .filter(|&idx| owning_file(graph, idx).is_some_and(|f| is_ts_or_js(graph, f)))
+        .collect()
+}
+
+/// Route handlers declared in TS/JS code, keyed by `(METHOD, normalized route)`.
+pub(crate) struct RouteIndex {
+    routes: HashMap<(String, String), Vec<NodeIndex>>,
+}
+
+impl RouteIndex {
+    /// Collect decorator routes from the graph and call-style routes from TS/JS sources.
+    pub(crate) fn build(graph: &CodeGraph) -> Self {
+        let mut routes: HashMap<(String, String), Vec<NodeIndex>> = HashMap::new();
+
+        // NestJS-style: `@Get(':id')` on a method of a `@Controller('users')` class.
+        for idx in graph.graph.node_indices() {
+            let GraphNode::Symbol(sym) = &graph.graph[idx] else {
+                continue;
+            };
+            if sym.kind != SymbolKind::Method
+                || !owning_file(graph, idx).is_some_and(|f| is_ts_or_js(graph, f))
+            {
+                continue;
+            }
+            let Some(decorator) = sym.decorators.iter().find(|d| {
+                HTTP_METHODS
+                    .iter()
+                    .chain(&["all"])
+                    .any(|m| d.name.eq_ignore_ascii_case(m))
+            }) else {
+                continue;
+            };
+            let sub_path = decorator
+                .args_raw
+                .as_deref()
+                .and_then(first_string_literal)
+                .unwrap_or("");
+            let prefix = graph
+                .graph
+                .edges_directed(idx, Direction::Outgoing)
+                .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+                .find_map(|e| match &graph.graph[e.target()] {
+                    GraphNode::Symbol(class) => class
+                        .decorators
+                        .iter()
+                        .find(|d| d.name == "Controller")
+                        .map(|d| d.args_raw.as_deref().and_then(first_string_literal)),
+                    _ => None,
+                })
+                .flatten()
+                .unwrap_or("");
+            let route = normalize_route(&format!("{}/{}", prefix, sub_path));
+            routes
+                .entry((decorator.name.to_ascii_uppercase(), route))
+                .or_default()
+                .push(idx);
+        }
+
+        // Express-style: `router.get('/users/:id', getUser)`.
+        let files: Vec<(NodeIndex, &Path)> = graph
+            .file_index
+            .iter()
+            .filter(|&(_, &idx)| is_ts_or_js(graph, idx))
+            .map(|(path, &idx)| (idx, path.as_path()))
+            .collect();
+        for (file_idx, path) in files {
+            let Ok(source) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            for (method, path, handler) in route_registrations(&source) {
+                let candidates = handler_candidates(graph, &handler);
+                let local: Vec<NodeIndex> = candidates
+                    .iter()
+                    .copied()
+                    .filter(|&c| owning_file(graph, c) == Some(file_idx))
+                    .collect();
+                let chosen = if !local.is_empty() {
+                    local
+                } else if candidates.len() == 1 {
+                    candidates
+                } else {
+                    continue;
+                };
+                routes
+                    .entry((method, normalize_route(&path)))
+                    .or_default()
+                    .extend(chosen);
+            }
+        }
+
+        RouteIndex { routes }
+    }
+
+    fn handlers(&self, method: &str, path: &str) -> &[NodeIndex] {
+        self.routes
+            .get(&(method.to_string(), normalize_route(path)))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Link every OpenAPI spec in the graph.
+pub fn link_openapi(graph: &mut CodeGraph) -> OpenApiLinkStats {
+    let specs: Vec<NodeIndex> = graph
+        .file_index
+        .iter()
+        .filter(|(path, _)| is_openapi_spec(path))
+        .map(|(_, &idx)| idx)
+        .collect();
+    let mut stats = OpenApiLinkStats::default();
+    if specs.is_empty() {
+        return stats;
+    }
+    let routes = RouteIndex::build(graph);
+    for spec_idx in specs {
+        let (endpoints, added) = link_spec_file(graph, spec_idx, &routes);
+        stats.endpoints += endpoints;
+        stats.handled_by += added;
+    }
+    stats
+}
+
+/// Read one spec, add its missing endpoint symbols, and add their `HandledBy` edges.
+/// Returns `(endpoint count, edges added)`.
+///
+/// Idempotent: endpoints already on the spec node are reused and existing edges are
+/// skipped, so the watcher can re-run it after handler files are re-indexed.
+pub(crate) fn link_spec_file(
+    graph: &mut CodeGraph,
+    spec_idx: NodeIndex,
+    routes: &RouteIndex,
+) -> (usize, usize) {
+    let spec_path = match &graph.graph[spec_idx] {
+        GraphNode::File(fi) if is_openapi_spec(&fi.path) => fi.path.clone(),
+        _ => return (0, 0),
+    };
+    let Ok(text) = std::fs::read_to_string(&spec_path) else {
+        return (0, 0);
+    };
+    let is_json = spec_path.extension().and_then(|e| e.to_str()) == Some("json");
+    let endpoints = extract_endpoints(&text, is_json);
+
+    let existing: HashMap<String, NodeIndex> = graph
+        .graph
+        .edges_directed(spec_idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+        .filter_map(|e| match &graph.graph[e.target()] {
+            GraphNode::Symbol(s) if s.kind == SymbolKind::Endpoint => {
+                Some((s.name.clone(), e.target()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut added = 0;
+    for endpoint in &endpoints {
+        let name = endpoint.symbol_name();
+        let endpoint_idx = match existing.get(&name) {
+            Some(&idx) => idx,
+            None => graph.add_symbol(
+                spec_idx,
+                SymbolInfo {
+                    name,
+                    kind: SymbolKind::Endpoint,
+                    line: endpoint.line,
+                    line_end: endpoint.line,
+                    is_exported: true,
+                    ..Default::default()
+                },
+            ),
+        };
+
+        let mut handlers: HashSet<NodeIndex> = routes
+            .handlers(&endpoint.method, &endpoint.path)
+            .iter()
+            .copied()
+            .collect();
+        if let Some(op_id) = &endpoint.operation_id {
+            let by_id = handler_candidates(graph, op_id);
+            if by_id.len() <= MAX_AMBIGUOUS_DEFS {
+                handlers.extend(by_id);
+            }
+        }
+
+        for handler in handlers {
+            let already_linked = graph
+                .graph
+                .edges_connecting(endpoint_idx, handler)
+                .any(|e| matches!(e.weight(), EdgeKind::HandledBy));
+            if !already_linked {
+                graph.add_handled_by(endpoint_idx, handler);
+                added += 1;
+            }
+        }
+    }
+    (endpoints.len(), added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{DecoratorInfo, FileKind};
+
+    const SPEC_YAML: &str = r#"openapi: 3.0.3
+info:
+  title: Users
+  version: '1'
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      responses:
+        '200':
+          links:
+            self:
+              operationId: notThisOne
+    post:
+      summary: Create
+  "/users/{id}":
+    parameters:
+      - name: id
+    delete:
+      operationId: 'removeUser' # trailing comment
+components:
+  schemas: {}
+"#;
+
+    #[test]
+    fn test_extract_yaml_endpoints() {
+        let endpoints = extract_endpoints(SPEC_YAML, false);
+        let summary: Vec<(String, Option<&str>, usize)> = endpoints
+            .iter()
+            .map(|e| (e.symbol_name(), e.operation_id.as_deref(), e.line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("GET /users".to_string(), Some("listUsers"), 7),
+                ("POST /users".to_string(), None, 14),
+                ("DELETE /users/{id}".to_string(), Some("removeUser"), 19),
+            ]
+        );
+        // Not a spec without a version key.
+        assert!(extract_endpoints("paths:\n  /x:\n    get: {}\n", false).is_empty());
+    }
+
+    #[test]
+    fn test_extract_json_endpoints() {
+        let json = r#"{
+  "swagger": "2.0",
+  "paths": {
+    "/pets/{petId}": {
+      "get": { "operationId": "showPetById" },
+      "parameters": []
+    }
+  }
+}"#;
+        let endpoints = extract_endpoints(json, true);
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].symbol_name(), "GET /pets/{petId}");
+        assert_eq!(endpoints[0].operation_id.as_deref(), Some("showPetById"));
+        assert_eq!(endpoints[0].line, 5);
+    }
+
+    #[test]
+    fn test_route_registrations_and_normalize() {
+        let src = "router.get('/users/:id', auth, ctrl.getUser);\n\
+                   app.post(\"/users\", (req, res) => res.send(1));\n\
+                   map.get(key);\n\
+                   app.delete(`/users/:id(\\\\d+)`, removeUser)\n";
+        assert_eq!(
+            route_registrations(src),
+            vec![
+                ("GET".into(), "/users/:id".into(), "getUser".into()),
+                (
+                    "DELETE".into(),
+                    "/users/:id(\\\\d+)".into(),
+                    "removeUser".into()
+                ),
+            ]
+        );
+        assert_eq!(normalize_route("/users/{id}/"), "/users/{}");
+        assert_eq!(normalize_route("users/:userId"), "/users/{}");
+    }
+
+    #[test]
+    fn test_link_openapi_by_operation_id_and_routes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("openapi.yaml"), SPEC_YAML).unwrap();
+        std::fs::write(
+            root.join("routes.ts"),
+            "router.post('/users', createUser);\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let routes_file = graph.add_file(root.join("routes.ts"), "typescript");
+        let function = |name: &str| SymbolInfo {
+            name: name.into(),
+            kind: SymbolKind::Function,
+            line: 1,
+            is_exported: true,
+            ..Default::default()
+        };
+        let list = graph.add_symbol(routes_file, function("listUsers"));
+        let create = graph.add_symbol(routes_file, function("createUser"));
+
+        let ctrl_file = graph.add_file(root.join("users.controller.ts"), "typescript");
+        let controller = graph.add_symbol(
+            ctrl_file,
+            SymbolInfo {
+                name: "UsersController".into(),
+                kind: SymbolKind::Class,
+                decorators: vec![DecoratorInfo {
+                    name: "Controller".into(),
+                    args_raw: Some("('users')".into()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        let remove = graph.add_child_symbol(
+            controller,
+            SymbolInfo {
+                name: "remove".into(),
+                kind: SymbolKind::Method,
+                decorators: vec![DecoratorInfo {
+                    name: "Delete".into(),
+                    args_raw: Some("(':id')".into()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        let spec = graph.add_non_parsed_file(root.join("openapi.yaml"), FileKind::Config);
+
+        let stats = link_openapi(&mut graph);
+        assert_eq!((stats.endpoints, stats.handled_by), (3, 3));
+
+        let handler_of = |name: &str| -> Vec<NodeIndex> {
+            let endpoint = graph.symbol_index[name][0];
+            graph
+                .graph
+                .edges_directed(endpoint, Direction::Outgoing)
+                .filter(|e| matches!(e.weight(), EdgeKind::HandledBy))
+                .map(|e| e.target())
+                .collect()
+        };
+        assert_eq!(handler_of("GET /users"), vec![list]);
+        assert_eq!(handler_of("POST /users"), vec![create]);
+        assert_eq!(handler_of("DELETE /users/{id}"), vec![remove]);
+
+        // Re-linking reuses the endpoints and adds nothing new.
+        let routes = RouteIndex::build(&graph);
+        assert_eq!(link_spec_file(&mut graph, spec, &routes), (3, 0));
+        assert_eq!(graph.symbol_index["GET /users"].len(), 1);
+    }
+}
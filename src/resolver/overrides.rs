@@ -0,0 +1,261 @@
+//! `Overrides` edges between class methods and the declarations they override.
+//!
+//! A TS/JS class method overrides the same-named method of the nearest ancestor reached
+//! through `Extends` and `Implements` edges: a concrete method in a subclass points at the
+//! abstract (or concrete) base method, and a class method points at the interface method
+//! signature it implements. Each ancestor chain stops at its first match, since that
+//! declaration links further up itself, so `context` on a base method lists the methods
+//! that directly override it.
+//!
+//! Rust impl methods link the same way, through the `trait_impl` name recorded on each
+//! method of an `impl Trait for Type` block: `Type::fmt` overrides `Display::fmt` when the
+//! project defines `Display`, so `context` jumps from a trait method to every
+//! implementation and back.
+
+use std::collections::HashSet;
+
+use petgraph::Direction;
+use petgraph::stable_graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{GraphNode, SymbolKind};
+use crate::query::util::find_containing_file_idx;
+
+/// Rebuild every `Overrides` edge from the current class hierarchy. Returns the number of
+/// edges added.
+///
+/// Idempotent: existing edges are dropped first, so a re-indexed subclass or base class
+/// is picked up by simply running it again.
+pub fn link_overrides(graph: &mut CodeGraph) -> usize {
+    let stale: Vec<EdgeIndex> = graph
+        .graph
+        .edge_indices()
+        .filter(|&e| matches!(graph.graph[e], EdgeKind::Overrides))
+        .collect();
+    for edge in stale {
+        graph.graph.remove_edge(edge);
+    }
+
+    let classes: Vec<NodeIndex> = graph
+        .graph
+        .node_indices()
+        .filter(|&idx| {
+            matches!(&graph.graph[idx], GraphNode::Symbol(s) if s.kind == SymbolKind::Class)
+                && in_ts_or_js_file(graph, idx)
+        })
+        .collect();
+
+    let mut links: Vec<(NodeIndex, NodeIndex)> = Vec::new();
+    for class in classes {
+        for (name, method) in methods(graph, class) {
+            let mut queue = parents(graph, class);
+            let mut visited: HashSet<NodeIndex> = HashSet::from([class]);
+            while let Some(ancestor) = queue.pop() {
+                if !visited.insert(ancestor) {
+                    continue;
+                }
+                match methods(graph, ancestor)
+                    .into_iter()
+                    .find(|(n, _)| *n == name)
+                {
+                    Some((_, base)) => links.push((method, base)),
+                    None => queue.extend(parents(graph, ancestor)),
+                }
+            }
+        }
+    }
+
+    links.extend(rust_trait_impl_links(graph));
+
+    links.sort();
+    links.dedup();
+    for &(method, base) in &links {
+        graph.add_overrides_edge(method, base);
+    }
+    links.len()
+}
+
+/// `(impl method, trait method)` pairs for the project's Rust trait impls.
+///
+/// When several traits share the impl's trait name, the ones the impl's type has an
+/// `Implements` edge to are preferred.
+fn rust_trait_impl_links(graph: &CodeGraph) -> Vec<(NodeIndex, NodeIndex)> {
+    let mut links = Vec::new();
+    for idx in graph.graph.node_indices() {
+        let GraphNode::Symbol(info) = &graph.graph[idx] else {
+            continue;
+        };
+        let Some(trait_path) = info.trait_impl.as_deref() else {
+            continue;
+        };
+        if info.kind != SymbolKind::ImplMethod || !in_language(graph, idx, &["rust"]) {
+            continue;
+        }
+        let Some((type_name, method)) = info.name.rsplit_once("::") else {
+            continue;
+        };
+        let trait_name = trait_path.rsplit("::").next().unwrap_or(trait_path);
+
+        let traits: Vec<NodeIndex> = graph
+            .symbols_named(trait_name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&t| {
+                matches!(&graph.graph[t], GraphNode::Symbol(s) if s.kind == SymbolKind::Trait)
+                    && in_language(graph, t, &["rust"])
+            })
+            .collect();
+        let implemented: Vec<NodeIndex> = graph
+            .symbols_named(type_name)
+            .into_iter()
+            .flatten()
+            .flat_map(|&ty| parents(graph, ty))
+            .filter(|t| traits.contains(t))
+            .collect();
+        let traits = if implemented.is_empty() {
+            traits
+        } else {
+            implemented
+        };
+
+        let qualified = format!("{}::{}", trait_name, method);
+        for t in traits {
+            links.extend(
+                graph
+                    .graph
+                    .edges_directed(t, Direction::Incoming)
+                    .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+                    .map(|e| e.source())
+                    .filter(
+                        |&m| matches!(&graph.graph[m], GraphNode::Symbol(s) if s.name == qualified),
+                    )
+                    .map(|m| (idx, m)),
+            );
+        }
+    }
+    links
+}
+
+fn in_ts_or_js_file(graph: &CodeGraph, idx: NodeIndex) -> bool {
+    in_language(graph, idx, &["typescript", "tsx", "javascript"])
+}
+
+fn in_language(graph: &CodeGraph, idx: NodeIndex, languages: &[&str]) -> bool {
+    find_containing_file_idx(graph, idx).is_some_and(|file_idx| {
+        matches!(&graph.graph[file_idx], GraphNode::File(fi)
+            if languages.contains(&fi.language.as_str()))
+    })
+}
+
+/// Classes and interfaces `idx` extends or implements.
+fn parents(graph: &CodeGraph, idx: NodeIndex) -> Vec<NodeIndex> {
+    graph
+        .graph
+        .edges_directed(idx, Direction::Outgoing)
+        .filter(|e| matches!(e.weight(), EdgeKind::Extends | EdgeKind::Implements))
+        .map(|e| e.target())
+        .collect()
+}
+
+/// Method children of a class or interface, by name.
+fn methods(graph: &CodeGraph, idx: NodeIndex) -> Vec<(String, NodeIndex)> {
+    graph
+        .graph
+        .edges_directed(idx, Direction::Incoming)
+        .filter(|e| matches!(e.weight(), EdgeKind::ChildOf))
+        .filter_map(|e| match &graph.graph[e.source()] {
+            GraphNode::Symbol(s) if s.kind == SymbolKind::Method => {
+                Some((s.name.clone(), e.source()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::graph::node::SymbolInfo;
+
+    fn symbol(name: &str, kind: SymbolKind) -> SymbolInfo {
+        SymbolInfo {
+            name: name.into(),
+            kind,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_overrides_link_nearest_ancestor_method() {
+        let mut graph = CodeGraph::new();
+        let file = graph.add_file(PathBuf::from("/proj/shapes.ts"), "typescript");
+        let drawable = graph.add_symbol(file, symbol("Drawable", SymbolKind::Interface));
+        let draw_sig = graph.add_child_symbol(drawable, symbol("draw", SymbolKind::Method));
+        let shape = graph.add_symbol(file, symbol("Shape", SymbolKind::Class));
+        let base_draw = graph.add_child_symbol(shape, symbol("draw", SymbolKind::Method));
+        let base_area = graph.add_child_symbol(shape, symbol("area", SymbolKind::Method));
+        let square = graph.add_symbol(file, symbol("Square", SymbolKind::Class));
+        let square_area = graph.add_child_symbol(square, symbol("area", SymbolKind::Method));
+        let square_draw = graph.add_child_symbol(square, symbol("draw", SymbolKind::Method));
+        graph.add_child_symbol(square, symbol("side", SymbolKind::Property));
+        graph.add_implements_edge(shape, drawable);
+        graph.add_extends_edge(square, shape);
+
+        fn targets(graph: &CodeGraph, m: NodeIndex) -> Vec<NodeIndex> {
+            graph
+                .graph
+                .edges_directed(m, Direction::Outgoing)
+                .filter(|e| matches!(e.weight(), EdgeKind::Overrides))
+                .map(|e| e.target())
+                .collect()
+        }
+
+        assert_eq!(link_overrides(&mut graph), 3);
+        assert_eq!(targets(&graph, base_draw), vec![draw_sig]);
+        // The subclass stops at Shape.draw instead of also linking to Drawable.draw.
+        assert_eq!(targets(&graph, square_draw), vec![base_draw]);
+        assert_eq!(targets(&graph, square_area), vec![base_area]);
+
+        // Running again replaces the edges instead of duplicating them.
+        assert_eq!(link_overrides(&mut graph), 3);
+        assert_eq!(targets(&graph, square_draw), vec![base_draw]);
+    }
+
+    #[test]
+    fn test_overrides_link_rust_impl_methods_to_trait_methods() {
+        let mut graph = CodeGraph::new();
+        let lib = graph.add_file(PathBuf::from("/proj/src/shape.rs"), "rust");
+        let imp = graph.add_file(PathBuf::from("/proj/src/square.rs"), "rust");
+        let shape = graph.add_symbol(lib, symbol("Shape", SymbolKind::Trait));
+        let area = graph.add_child_symbol(shape, symbol("Shape::area", SymbolKind::ImplMethod));
+        let square = graph.add_symbol(imp, symbol("Square", SymbolKind::Struct));
+        let impl_method = |name: &str, trait_impl: Option<&str>| SymbolInfo {
+            trait_impl: trait_impl.map(str::to_string),
+            ..symbol(name, SymbolKind::ImplMethod)
+        };
+        let square_area = graph.add_symbol(imp, impl_method("Square::area", Some("Shape")));
+        let square_new = graph.add_symbol(imp, impl_method("Square::new", None));
+        let square_fmt = graph.add_symbol(imp, impl_method("Square::fmt", Some("fmt::Display")));
+        graph.add_implements_edge(square, shape);
+
+        assert_eq!(link_overrides(&mut graph), 1);
+        let overrides: Vec<(NodeIndex, NodeIndex)> = graph
+            .graph
+            .edge_indices()
+            .filter(|&e| matches!(graph.graph[e], EdgeKind::Overrides))
+            .filter_map(|e| graph.graph.edge_endpoints(e))
+            .collect();
+        assert_eq!(overrides, vec![(square_area, area)]);
+        // Inherent methods and traits outside the project stay unlinked.
+        assert!(
+            !overrides
+                .iter()
+                .any(|&(m, _)| m == square_new || m == square_fmt)
+        );
+    }
+}
@@ -0,0 +1,304 @@
+//! Protobuf resolver.
+//!
+//! Integrates into `resolve_all` as Step 9. Two passes:
+//! - **Proto imports** (`import "common/page.proto";`): resolved relative to the importing
+//!   file, then the project root, then by path suffix against all indexed `.proto` files.
+//!   `google/protobuf/*` well-known types become an ExternalPackage node.
+//! - **Generated code**: files that are (or import) generated stubs — `user_pb2.py`,
+//!   `user_pb2_grpc`, `user_pb.js`, `user_grpc_pb`, `user_connect`, `user.pb.go`,
+//!   `user_grpc.pb.go` — get a `ResolvedImport` edge to `user.proto`, so `impact` on a
+//!   message or service reaches the clients and servers built on it.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+use crate::parser::ParseResult;
+use crate::parser::imports::ImportKind;
+
+/// Statistics collected by the protobuf resolver.
+#[derive(Debug, Default)]
+pub struct ProtoResolveStats {
+    /// `import "x.proto"` statements resolved to an indexed proto file.
+    pub resolved: usize,
+    /// `import "x.proto"` statements that could not be resolved.
+    pub unresolved: usize,
+    /// Edges added from generated stubs (or their importers) to proto files.
+    pub generated_links: usize,
+}
+
+/// Name suffixes protoc plugins append to the proto file stem, after the extension
+/// has been stripped. Longest first so `_grpc_pb` wins over `_pb`.
+const GENERATED_SUFFIXES: &[&str] = &[
+    "_grpc_web_pb",
+    "_pb2_grpc",
+    "_grpc_pb",
+    "_pb2",
+    "_pb",
+    "_connect",
+];
+
+/// Extensions stripped from generated file names / import specifiers before suffix matching.
+const GENERATED_EXTENSIONS: &[&str] = &[".d.ts", ".ts", ".js", ".mjs", ".cjs", ".py", ".go"];
+
+/// Map a generated file name or import specifier back to the stem of its `.proto` source.
+///
+/// - `./gen/user_pb` / `user_pb.d.ts` / `user_grpc_web_pb.js` → `user`
+/// - `gen.user_pb2` / `user_pb2_grpc.py` → `user`
+/// - `user.pb.go` / `user_grpc.pb.go` → `user`
+///
+/// Returns `None` when the name does not follow any protoc naming convention.
+pub fn generated_stem(name: &str) -> Option<&str> {
+    let seg = name.rsplit('/').next().unwrap_or(name);
+    let seg = GENERATED_EXTENSIONS
+        .iter()
+        .find_map(|ext| seg.strip_suffix(ext))
+        .unwrap_or(seg);
+
+    // Go: `user.pb.go` / `user_grpc.pb.go` (extension already stripped).
+    if let Some(base) = seg.strip_suffix(".pb") {
+        let base = base.strip_suffix("_grpc").unwrap_or(base);
+        return Some(base).filter(|s| !s.is_empty());
+    }
+
+    // Python dotted module paths: `gen.user_pb2` -> `user_pb2`.
+    let seg = seg.rsplit('.').next().unwrap_or(seg);
+    GENERATED_SUFFIXES
+        .iter()
+        .find_map(|suffix| seg.strip_suffix(suffix))
+        .filter(|s| !s.is_empty())
+}
+
+fn is_proto(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("proto")
+}
+
+/// Resolve a proto import path against the indexed proto files.
+fn resolve_proto_path(
+    graph: &CodeGraph,
+    proto_files: &[(PathBuf, NodeIndex)],
+    importer: &Path,
+    project_root: &Path,
+    specifier: &str,
+) -> Option<NodeIndex> {
+    if let Some(dir) = importer.parent()
+        && let Some(&idx) = graph.file_index.get(&dir.join(specifier))
+    {
+        return Some(idx);
+    }
+    if let Some(&idx) = graph.file_index.get(&project_root.join(specifier)) {
+        return Some(idx);
+    }
+    // Fall back to suffix matching (covers `-I proto/` include roots). `proto_files` is
+    // sorted, so the pick is deterministic when several files share the suffix.
+    proto_files
+        .iter()
+        .find(|(path, _)| path.ends_with(specifier))
+        .map(|(_, idx)| *idx)
+}
+
+/// Step 9 of `resolve_all`: resolve proto imports and link generated code to proto files.
+pub fn resolve_proto(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+    project_root: &Path,
+    verbose: bool,
+) -> ProtoResolveStats {
+    let mut stats = ProtoResolveStats::default();
+
+    let mut proto_files: Vec<(PathBuf, NodeIndex)> = graph
+        .file_index
+        .iter()
+        .filter(|(path, _)| is_proto(path))
+        .map(|(path, &idx)| (path.clone(), idx))
+        .collect();
+    proto_files.sort();
+
+    for (path, result) in parse_results {
+        if !is_proto(path) {
+            continue;
+        }
+        let from_idx = match graph.file_index.get(path).copied() {
+            Some(idx) => idx,
+            None => continue,
+        };
+        for import in &result.imports {
+            if import.kind != ImportKind::ProtoImport {
+                continue;
+            }
+            let specifier = &import.module_path;
+            match resolve_proto_path(graph, &proto_files, path, project_root, specifier) {
+                Some(target_idx) => {
+                    graph.add_import_at(
+                        from_idx,
+                        target_idx,
+                        specifier,
+                        false,
+                        import.line,
+                        import.col,
+                    );
+                    stats.resolved += 1;
+                }
+                None if specifier.starts_with("google/protobuf/") => {
+                    graph.add_external_package(from_idx, "google.protobuf", specifier);
+                }
+                None => {
+                    graph.add_unresolved_import(from_idx, specifier, "proto file not found");
+                    stats.unresolved += 1;
+                    if verbose {
+                        eprintln!(
+                            "  resolve: {} imports '{}' -> unresolved: proto file not found",
+                            path.display(),
+                            specifier
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    stats.generated_links = link_generated_code(graph, parse_results);
+    stats
+}
+
+/// Add `ResolvedImport` edges from generated stubs and their importers to the proto files
+/// they were generated from.
+///
+/// Matches on (a) the file's own name, (b) the specifiers of its existing outgoing
+/// `ResolvedImport` edges (covers stubs that are gitignored build outputs and therefore
+/// only exist as unresolved/external imports), and (c) the imported names in
+/// `parse_results` (covers `from gen import user_pb2`). Idempotent: an edge is only added
+/// when the file has no `ResolvedImport` edge to that proto yet.
+///
+/// Returns the number of edges added.
+pub fn link_generated_code(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+) -> usize {
+    let mut by_stem: HashMap<String, Vec<NodeIndex>> = HashMap::new();
+    for (path, &idx) in &graph.file_index {
+        if is_proto(path)
+            && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+        {
+            by_stem.entry(stem.to_owned()).or_default().push(idx);
+        }
+    }
+    if by_stem.is_empty() {
+        return 0;
+    }
+
+    let mut pending: Vec<(NodeIndex, NodeIndex, String)> = Vec::new();
+    for (path, &file_idx) in &graph.file_index {
+        if is_proto(path) {
+            continue;
+        }
+        let mut names: Vec<String> = Vec::new();
+        if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
+            names.push(file_name.to_owned());
+        }
+        for edge in graph.graph.edges(file_idx) {
+            if let EdgeKind::ResolvedImport { specifier, .. } = edge.weight() {
+                let target_is_proto = matches!(
+                    &graph.graph[edge.target()],
+                    GraphNode::File(f) if is_proto(&f.path)
+                );
+                if !target_is_proto {
+                    names.push(specifier.clone());
+                }
+            }
+        }
+        if let Some(result) = parse_results.get(path) {
+            for import in &result.imports {
+                names.push(import.module_path.clone());
+                names.extend(import.specifiers.iter().map(|s| s.name.clone()));
+            }
+        }
+
+        let mut seen: HashSet<NodeIndex> = HashSet::new();
+        for name in &names {
+            let Some(targets) = generated_stem(name).and_then(|stem| by_stem.get(stem)) else {
+                continue;
+            };
+            for &target in targets {
+                if seen.insert(target) {
+                    pending.push((file_idx, target, name.clone()));
+                }
+            }
+        }
+    }
+
+    let mut added = 0;
+    for (from, to, specifier) in pending {
+        let already_linked = graph
+            .graph
+            .edges_connecting(from, to)
+            .any(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }));
+        if !already_linked {
+            graph.add_resolved_import(from, to, &specifier);
+            added += 1;
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_stem() {
+        assert_eq!(generated_stem("./gen/user_pb"), Some("user"));
+        assert_eq!(generated_stem("user_pb.d.ts"), Some("user"));
+        assert_eq!(generated_stem("../gen/user_grpc_web_pb.js"), Some("user"));
+        assert_eq!(generated_stem("user_grpc_pb"), Some("user"));
+        assert_eq!(generated_stem("gen.user_pb2"), Some("user"));
+        assert_eq!(generated_stem("user_pb2_grpc.py"), Some("user"));
+        assert_eq!(generated_stem("user_connect"), Some("user"));
+        assert_eq!(generated_stem("user.pb.go"), Some("user"));
+        assert_eq!(generated_stem("user_grpc.pb.go"), Some("user"));
+        assert_eq!(generated_stem("./utils"), None);
+        assert_eq!(generated_stem("_pb"), None);
+    }
+
+    #[test]
+    fn test_resolve_proto_links_imports_and_generated_code() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let user_proto = root.join("proto/user.proto");
+        let page_proto = root.join("proto/common/page.proto");
+        let user_idx = graph.add_file(user_proto.clone(), "proto");
+        let page_idx = graph.add_file(page_proto.clone(), "proto");
+        let stub_idx = graph.add_file(root.join("gen/user_pb2.py"), "python");
+        let client_idx = graph.add_file(root.join("web/client.ts"), "typescript");
+        // The TS stub is a gitignored build output: the client's import stays unresolved.
+        graph.add_unresolved_import(client_idx, "../gen/user_pb", "not found");
+
+        let mut parse_results = HashMap::new();
+        parse_results.insert(
+            user_proto,
+            crate::parser::proto::parse_proto(b"import \"common/page.proto\";\nmessage User {}"),
+        );
+
+        let stats = resolve_proto(&mut graph, &parse_results, &root, false);
+        assert_eq!(stats.resolved, 1);
+        assert_eq!(stats.unresolved, 0);
+        assert_eq!(stats.generated_links, 2);
+
+        let has_edge = |g: &CodeGraph, from, to| {
+            g.graph
+                .edges_connecting(from, to)
+                .any(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+        };
+        assert!(has_edge(&graph, user_idx, page_idx));
+        assert!(has_edge(&graph, stub_idx, user_idx));
+        assert!(has_edge(&graph, client_idx, user_idx));
+
+        // Re-running (e.g. an incremental scoped resolve) adds no duplicate links.
+        assert_eq!(link_generated_code(&mut graph, &HashMap::new()), 0);
+    }
+}
@@ -122,7 +122,7 @@ static STDLIB_MODULES: &[&str] = &[
     "zlib",
 ];
 
-fn is_stdlib(module_name: &str) -> bool {
+pub(crate) fn is_stdlib(module_name: &str) -> bool {
     // Check the top-level component (e.g., "os.path" -> "os")
     let top = module_name.split('.').next().unwrap_or(module_name);
     STDLIB_MODULES.contains(&top)
@@ -303,7 +303,7 @@ fn expand_wildcard(
 ///
 /// Called from `resolve_all` as Step 7 -- after TypeScript/Rust resolution.
 ///
-/// For each file with a `.py` extension:
+/// For each file with a `.py` extension (and each Python-kernel `.ipynb` notebook):
 ///   - Absolute imports -> try project_root lookup -> ExternalPackage (stdlib) or UnresolvedImport
 ///   - Relative imports -> compute base dir from level -> try local path
 ///   - Wildcard imports -> expand via `__all__` or all-public fallback
@@ -318,7 +318,12 @@ pub fn resolve_python_imports(
     // Collect Python file imports to avoid borrow conflicts.
     let python_imports: Vec<(PathBuf, Vec<ImportInfo>)> = parse_results
         .iter()
-        .filter(|(path, _)| path.extension().and_then(|e| e.to_str()) == Some("py"))
+        .filter(|(path, _)| {
+            matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("py" | "ipynb")
+            )
+        })
         .map(|(path, result)| (path.clone(), result.imports.clone()))
         .collect();
 
@@ -407,11 +412,25 @@ fn resolve_absolute_import(
             let names = expand_wildcard(&target_path, parse_results);
             if names.is_empty() {
                 // Create a single edge for the whole wildcard import.
-                add_import_edge(graph, from_idx, target_idx, module_path, is_conditional);
+                add_import_edge(
+                    graph,
+                    from_idx,
+                    target_idx,
+                    module_path,
+                    import_info,
+                    is_conditional,
+                );
                 stats.resolved += 1;
             } else {
                 for name in &names {
-                    add_import_edge(graph, from_idx, target_idx, name, is_conditional);
+                    add_import_edge(
+                        graph,
+                        from_idx,
+                        target_idx,
+                        name,
+                        import_info,
+                        is_conditional,
+                    );
                 }
                 stats.resolved += names.len().max(1);
             }
@@ -430,7 +449,14 @@ fn resolve_absolute_import(
             if import_info.specifiers.is_empty() {
                 // `import pkg` with no named specifiers -- point at __init__.py.
                 if let Some(&target_idx) = graph.file_index.get(&target_path) {
-                    add_import_edge(graph, from_idx, target_idx, module_path, is_conditional);
+                    add_import_edge(
+                        graph,
+                        from_idx,
+                        target_idx,
+                        module_path,
+                        import_info,
+                        is_conditional,
+                    );
                     stats.resolved += 1;
                 }
             } else {
@@ -439,11 +465,25 @@ fn resolve_absolute_import(
                         follow_init_reexport(&target_path, &spec.name, parse_results, 10);
 
                     if let Some(&target_idx) = graph.file_index.get(&resolved_target) {
-                        add_import_edge(graph, from_idx, target_idx, &spec.name, is_conditional);
+                        add_import_edge(
+                            graph,
+                            from_idx,
+                            target_idx,
+                            &spec.name,
+                            import_info,
+                            is_conditional,
+                        );
                         stats.resolved += 1;
                     } else if let Some(&init_idx) = graph.file_index.get(&target_path) {
                         // Fallback: point at __init__.py if the resolved target isn't indexed.
-                        add_import_edge(graph, from_idx, init_idx, &spec.name, is_conditional);
+                        add_import_edge(
+                            graph,
+                            from_idx,
+                            init_idx,
+                            &spec.name,
+                            import_info,
+                            is_conditional,
+                        );
                         stats.resolved += 1;
                     } else {
                         graph.add_unresolved_import(
@@ -460,11 +500,25 @@ fn resolve_absolute_import(
             if let Some(&target_idx) = graph.file_index.get(&target_path) {
                 if import_info.specifiers.is_empty() {
                     // `import module` with no specifiers -- single edge.
-                    add_import_edge(graph, from_idx, target_idx, module_path, is_conditional);
+                    add_import_edge(
+                        graph,
+                        from_idx,
+                        target_idx,
+                        module_path,
+                        import_info,
+                        is_conditional,
+                    );
                     stats.resolved += 1;
                 } else {
                     for spec in &import_info.specifiers {
-                        add_import_edge(graph, from_idx, target_idx, &spec.name, is_conditional);
+                        add_import_edge(
+                            graph,
+                            from_idx,
+                            target_idx,
+                            &spec.name,
+                            import_info,
+                            is_conditional,
+                        );
                         stats.resolved += 1;
                     }
                 }
@@ -524,11 +578,25 @@ fn resolve_relative_import(
         {
             let names = expand_wildcard(&target_path, parse_results);
             if names.is_empty() {
-                add_import_edge(graph, from_idx, target_idx, module_path, is_conditional);
+                add_import_edge(
+                    graph,
+                    from_idx,
+                    target_idx,
+                    module_path,
+                    import_info,
+                    is_conditional,
+                );
                 stats.resolved += 1;
             } else {
                 for name in &names {
-                    add_import_edge(graph, from_idx, target_idx, name, is_conditional);
+                    add_import_edge(
+                        graph,
+                        from_idx,
+                        target_idx,
+                        name,
+                        import_info,
+                        is_conditional,
+                    );
                 }
                 stats.resolved += names.len().max(1);
             }
@@ -549,10 +617,24 @@ fn resolve_relative_import(
                 let resolved_target =
                     follow_init_reexport(&target_path, &spec.name, parse_results, 10);
                 if let Some(&target_idx) = graph.file_index.get(&resolved_target) {
-                    add_import_edge(graph, from_idx, target_idx, &spec.name, is_conditional);
+                    add_import_edge(
+                        graph,
+                        from_idx,
+                        target_idx,
+                        &spec.name,
+                        import_info,
+                        is_conditional,
+                    );
                     stats.resolved += 1;
                 } else if let Some(&init_idx) = graph.file_index.get(&target_path) {
-                    add_import_edge(graph, from_idx, init_idx, &spec.name, is_conditional);
+                    add_import_edge(
+                        graph,
+                        from_idx,
+                        init_idx,
+                        &spec.name,
+                        import_info,
+                        is_conditional,
+                    );
                     stats.resolved += 1;
                 } else {
                     graph.add_unresolved_import(from_idx, &spec.name, "Python module not found");
@@ -567,11 +649,25 @@ fn resolve_relative_import(
                 } else {
                     module_path.as_str()
                 };
-                add_import_edge(graph, from_idx, target_idx, label, is_conditional);
+                add_import_edge(
+                    graph,
+                    from_idx,
+                    target_idx,
+                    label,
+                    import_info,
+                    is_conditional,
+                );
                 stats.resolved += 1;
             } else {
                 for spec in &import_info.specifiers {
-                    add_import_edge(graph, from_idx, target_idx, &spec.name, is_conditional);
+                    add_import_edge(
+                        graph,
+                        from_idx,
+                        target_idx,
+                        &spec.name,
+                        import_info,
+                        is_conditional,
+                    );
                     stats.resolved += 1;
                 }
             }
@@ -597,6 +693,7 @@ fn add_import_edge(
     from_idx: petgraph::stable_graph::NodeIndex,
     to_idx: petgraph::stable_graph::NodeIndex,
     specifier: &str,
+    import_info: &ImportInfo,
     is_conditional: bool,
 ) {
     if is_conditional {
@@ -608,7 +705,14 @@ fn add_import_edge(
             },
         );
     } else {
-        graph.add_resolved_import(from_idx, to_idx, specifier);
+        graph.add_import_at(
+            from_idx,
+            to_idx,
+            specifier,
+            false,
+            import_info.line,
+            import_info.col,
+        );
     }
 }
 
@@ -633,6 +737,7 @@ mod tests {
             exports: Vec::new(),
             relationships: Vec::new(),
             rust_uses: Vec::new(),
+            ..Default::default()
         }
     }
 
@@ -644,6 +749,7 @@ mod tests {
             exports: Vec::new(),
             relationships: Vec::new(),
             rust_uses: Vec::new(),
+            ..Default::default()
         }
     }
 
@@ -662,6 +768,8 @@ mod tests {
                 })
                 .collect(),
             line: 1,
+            col: 0,
+            is_type_only: false,
         }
     }
 
@@ -684,6 +792,8 @@ mod tests {
                 })
                 .collect(),
             line: 1,
+            col: 0,
+            is_type_only: false,
         }
     }
 
@@ -699,6 +809,8 @@ mod tests {
                 is_namespace: true,
             }],
             line: 1,
+            col: 0,
+            is_type_only: false,
         }
     }
 
@@ -717,6 +829,8 @@ mod tests {
                 })
                 .collect(),
             line: 1,
+            col: 0,
+            is_type_only: false,
         }
     }
 
@@ -1031,6 +1145,7 @@ mod tests {
             exports: Vec::new(),
             relationships: Vec::new(),
             rust_uses: Vec::new(),
+            ..Default::default()
         };
 
         let mut parse_results = HashMap::new();
@@ -1087,6 +1202,7 @@ mod tests {
             exports: Vec::new(),
             relationships: Vec::new(),
             rust_uses: Vec::new(),
+            ..Default::default()
         };
 
         let mut parse_results = HashMap::new();
@@ -0,0 +1,187 @@
+//! Call resolution for Rust files.
+//!
+//! Integrates into `resolve_all` as Step 6b, after Rust `use` paths have been resolved to
+//! files. The parser already names the callee as precisely as it can: `Type::method` for
+//! associated calls and for method calls whose receiver type it inferred (from `self`,
+//! typed parameters, and `let` bindings), a bare name otherwise.
+//!
+//! A call binds to a `Function` or `ImplMethod` symbol of that name, or failing that, one
+//! re-exported under that name by `pub use .. as`. When several symbols share it,
+//! candidates defined in the calling file win, then those in files the caller imports;
+//! calls that stay ambiguous add no edge.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{GraphNode, SymbolKind};
+use crate::parser::ParseResult;
+use crate::parser::relationships::RelationshipKind;
+use crate::query::util::find_containing_file_idx;
+
+/// Narrow `candidates` to those whose defining file is in `files`, keeping the original
+/// list when none is.
+fn prefer_files(
+    graph: &CodeGraph,
+    candidates: Vec<NodeIndex>,
+    files: &HashSet<NodeIndex>,
+) -> Vec<NodeIndex> {
+    let narrowed: Vec<NodeIndex> = candidates
+        .iter()
+        .copied()
+        .filter(|&idx| find_containing_file_idx(graph, idx).is_some_and(|f| files.contains(&f)))
+        .collect();
+    if narrowed.is_empty() {
+        candidates
+    } else {
+        narrowed
+    }
+}
+
+/// Step 6b of `resolve_all`: add a `Calls` edge for every call in a Rust file that names
+/// exactly one function or method.
+///
+/// Returns the number of edges added.
+pub fn resolve_rust_calls(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+) -> usize {
+    let mut calls: HashMap<(NodeIndex, NodeIndex), Vec<usize>> = HashMap::new();
+
+    for (path, result) in parse_results {
+        if path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+        let Some(&caller) = graph.file_index.get(path) else {
+            continue;
+        };
+        let imported: HashSet<NodeIndex> = graph
+            .graph
+            .edges_directed(caller, Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+            .map(|e| e.target())
+            .collect();
+        let same_file: HashSet<NodeIndex> = [caller].into_iter().collect();
+
+        for rel in &result.relationships {
+            if !matches!(
+                rel.kind,
+                RelationshipKind::Calls | RelationshipKind::MethodCall
+            ) {
+                continue;
+            }
+            let candidates: Vec<NodeIndex> = graph
+                .symbols_named(&rel.to_name)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|&idx| {
+                    matches!(&graph.graph[idx], GraphNode::Symbol(info)
+                        if matches!(info.kind, SymbolKind::Function | SymbolKind::ImplMethod))
+                })
+                .collect();
+            let candidates = prefer_files(graph, candidates, &same_file);
+            let candidates = prefer_files(graph, candidates, &imported);
+            if let [callee] = candidates[..] {
+                calls.entry((caller, callee)).or_default().push(rel.line);
+            }
+        }
+    }
+
+    let mut added = 0;
+    for ((caller, callee), lines) in calls {
+        let exists = graph
+            .graph
+            .edges_connecting(caller, callee)
+            .any(|e| matches!(e.weight(), EdgeKind::Calls { .. }));
+        if !exists {
+            for line in lines {
+                graph.add_call_site(caller, callee, line);
+            }
+            added += 1;
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::SymbolInfo;
+    use crate::parser::relationships::RelationshipInfo;
+
+    fn symbol(name: &str, kind: SymbolKind) -> SymbolInfo {
+        SymbolInfo {
+            name: name.into(),
+            kind,
+            line: 1,
+            ..Default::default()
+        }
+    }
+
+    fn call(kind: RelationshipKind, to: &str) -> RelationshipInfo {
+        RelationshipInfo {
+            from_name: None,
+            to_name: to.into(),
+            kind,
+            line: 1,
+        }
+    }
+
+    fn callees(graph: &CodeGraph, file_idx: NodeIndex) -> Vec<String> {
+        let mut names: Vec<String> = graph
+            .graph
+            .edges_directed(file_idx, Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::Calls { .. }))
+            .filter_map(|e| match &graph.graph[e.target()] {
+                GraphNode::Symbol(info) => Some(info.name.clone()),
+                _ => None,
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_resolves_method_calls_by_receiver_type() {
+        let root = PathBuf::from("/proj/src");
+        let mut graph = CodeGraph::new();
+        let main = graph.add_file(root.join("main.rs"), "rust");
+        let foo = graph.add_file(root.join("foo.rs"), "rust");
+        let bar = graph.add_file(root.join("bar.rs"), "rust");
+        let helpers_a = graph.add_file(root.join("a.rs"), "rust");
+        let helpers_b = graph.add_file(root.join("b.rs"), "rust");
+        let helpers_c = graph.add_file(root.join("c.rs"), "rust");
+        graph.add_symbol(foo, symbol("Foo::run", SymbolKind::ImplMethod));
+        graph.add_symbol(bar, symbol("Bar::run", SymbolKind::ImplMethod));
+        graph.add_symbol(helpers_a, symbol("helper", SymbolKind::Function));
+        graph.add_symbol(helpers_b, symbol("helper", SymbolKind::Function));
+        graph.add_symbol(helpers_a, symbol("orphan", SymbolKind::Function));
+        graph.add_symbol(helpers_c, symbol("orphan", SymbolKind::Function));
+        graph.add_resolved_import(main, helpers_b, "crate::b::helper");
+
+        let result = ParseResult {
+            relationships: vec![
+                call(RelationshipKind::MethodCall, "Foo::run"),
+                // Imported from `b`, so not ambiguous with `a::helper`.
+                call(RelationshipKind::Calls, "helper"),
+                // Defined in two files, neither imported.
+                call(RelationshipKind::Calls, "orphan"),
+                call(RelationshipKind::Calls, "missing"),
+            ],
+            ..Default::default()
+        };
+        let mut parse_results = HashMap::new();
+        parse_results.insert(root.join("main.rs"), result);
+
+        assert_eq!(resolve_rust_calls(&mut graph, &parse_results), 2);
+        assert_eq!(callees(&graph, main), vec!["Foo::run", "helper"]);
+        // Re-running the pass does not duplicate edges.
+        assert_eq!(resolve_rust_calls(&mut graph, &parse_results), 0);
+    }
+}
@@ -1,8 +1,10 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use tree_sitter::Tree;
+use tree_sitter::{Node, Tree};
 
+use crate::graph::CodeGraph;
+use crate::graph::node::GraphNode;
 use crate::parser::PARSER_RS;
 
 /// A per-crate mapping of module paths to source file paths, and the reverse.
@@ -20,6 +22,10 @@ pub struct RustModTree {
     pub mod_map: HashMap<String, PathBuf>,
     /// File path → module path (reverse map for `super::` resolution).
     pub reverse_map: HashMap<PathBuf, String>,
+    /// Module path → Cargo feature whose `#[cfg(feature = "...")]` gates its `mod` declaration.
+    pub feature_gates: HashMap<String, String>,
+    /// Module paths whose `mod` declaration is gated by `#[cfg(test)]`.
+    pub test_modules: HashSet<String>,
 }
 
 impl RustModTree {
@@ -120,11 +126,47 @@ pub fn find_crate_root(cargo_toml_path: &Path) -> Option<(String, PathBuf)> {
 // Module declaration extraction
 // ---------------------------------------------------------------------------
 
-/// Extract all file-backed `mod foo;` declarations from a tree-sitter parse tree.
+/// A file-backed `mod foo;` declaration and the `#[cfg(...)]` gating it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModDeclaration {
+    pub name: String,
+    /// Feature of a `#[cfg(feature = "...")]` attribute.
+    pub feature: Option<String>,
+    /// Declared under `#[cfg(test)]`.
+    pub test: bool,
+}
+
+/// Read the `#[cfg(feature = "...")]` and `#[cfg(test)]` attributes directly above `item`.
+fn cfg_gates(item: Node, source: &[u8]) -> (Option<String>, bool) {
+    let mut feature = None;
+    let mut test = false;
+    let mut attr = item.prev_sibling();
+    while let Some(node) = attr.filter(|n| n.kind() == "attribute_item") {
+        let text: String = node
+            .utf8_text(source)
+            .unwrap_or("")
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        if text == "#[cfg(test)]" {
+            test = true;
+        } else if let Some(f) = text
+            .strip_prefix("#[cfg(feature=\"")
+            .and_then(|rest| rest.strip_suffix("\")]"))
+        {
+            feature.get_or_insert_with(|| f.to_string());
+        }
+        attr = node.prev_sibling();
+    }
+    (feature, test)
+}
+
+/// Extract all file-backed `mod foo;` declarations from a tree-sitter parse tree, with the
+/// `#[cfg(feature = "...")]` / `#[cfg(test)]` gating each one.
 ///
 /// Only processes top-level children of the root node.
 /// Skips inline `mod foo { ... }` blocks (those have a `body` child).
-pub fn extract_mod_declarations(tree: &Tree, source: &[u8]) -> Vec<String> {
+pub fn extract_mod_declarations(tree: &Tree, source: &[u8]) -> Vec<ModDeclaration> {
     let mut mods = Vec::new();
     let root = tree.root_node();
     let mut cursor = root.walk();
@@ -141,7 +183,12 @@ pub fn extract_mod_declarations(tree: &Tree, source: &[u8]) -> Vec<String> {
         if let Some(name_node) = child.child_by_field_name("name") {
             let name = name_node.utf8_text(source).unwrap_or("").to_owned();
             if !name.is_empty() {
-                mods.push(name);
+                let (feature, test) = cfg_gates(child, source);
+                mods.push(ModDeclaration {
+                    name,
+                    feature,
+                    test,
+                });
             }
         }
     }
@@ -159,6 +206,8 @@ pub fn extract_mod_declarations(tree: &Tree, source: &[u8]) -> Vec<String> {
 /// - `file`: the file path for the current module
 /// - `mod_map`: mutable map being populated
 /// - `reverse_map`: mutable reverse map being populated
+/// - `feature_gates`: mutable map of feature-gated module paths being populated
+/// - `test_modules`: mutable set of `#[cfg(test)]` module paths being populated
 /// - `visited`: cycle guard — canonicalized file paths already processed
 ///
 /// For each `mod foo;` declaration found in `file`:
@@ -169,6 +218,8 @@ pub fn walk_mod_tree(
     file: &Path,
     mod_map: &mut HashMap<String, PathBuf>,
     reverse_map: &mut HashMap<PathBuf, String>,
+    feature_gates: &mut HashMap<String, String>,
+    test_modules: &mut HashSet<String>,
     visited: &mut HashSet<PathBuf>,
 ) {
     // Cycle guard: use canonicalized path if available, otherwise raw path.
@@ -225,7 +276,12 @@ pub fn walk_mod_tree(
         parent_dir.join(stem)
     };
 
-    for mod_name in mod_names {
+    for ModDeclaration {
+        name: mod_name,
+        feature,
+        test,
+    } in mod_names
+    {
         // Edition 2018+ layout: probe foo.rs first, then foo/mod.rs.
         let candidate_file = sub_dir.join(format!("{mod_name}.rs"));
         let candidate_dir = sub_dir.join(&mod_name).join("mod.rs");
@@ -241,7 +297,21 @@ pub fn walk_mod_tree(
         };
 
         let child_path = format!("{current_path}::{mod_name}");
-        walk_mod_tree(&child_path, &child_file, mod_map, reverse_map, visited);
+        if let Some(feature) = feature {
+            feature_gates.insert(child_path.clone(), feature);
+        }
+        if test {
+            test_modules.insert(child_path.clone());
+        }
+        walk_mod_tree(
+            &child_path,
+            &child_file,
+            mod_map,
+            reverse_map,
+            feature_gates,
+            test_modules,
+            visited,
+        );
     }
 }
 
@@ -260,6 +330,8 @@ pub fn build_mod_tree(crate_name: &str, crate_root: &Path) -> RustModTree {
     let _ = crate_name; // crate name context is in the module paths ("crate::" prefix)
     let mut mod_map = HashMap::new();
     let mut reverse_map = HashMap::new();
+    let mut feature_gates = HashMap::new();
+    let mut test_modules = HashSet::new();
     let mut visited = HashSet::new();
 
     walk_mod_tree(
@@ -267,12 +339,54 @@ pub fn build_mod_tree(crate_name: &str, crate_root: &Path) -> RustModTree {
         crate_root,
         &mut mod_map,
         &mut reverse_map,
+        &mut feature_gates,
+        &mut test_modules,
         &mut visited,
     );
 
     RustModTree {
         mod_map,
         reverse_map,
+        feature_gates,
+        test_modules,
+    }
+}
+
+/// Crate-qualified form of a mod tree path: `crate::parser` → `my_crate::parser`.
+pub fn qualify_module_path(crate_name: &str, mod_path: &str) -> String {
+    match mod_path.strip_prefix("crate") {
+        Some(rest) => format!("{crate_name}{rest}"),
+        None => mod_path.to_string(),
+    }
+}
+
+/// `true` when `mod_path` or one of its enclosing modules is declared under `#[cfg(test)]`.
+fn in_test_module(tree: &RustModTree, mod_path: &str) -> bool {
+    let mut current = mod_path;
+    loop {
+        if tree.test_modules.contains(current) {
+            return true;
+        }
+        match current.rsplit_once("::") {
+            Some((parent, _)) => current = parent,
+            None => return false,
+        }
+    }
+}
+
+/// Add a `Module` node for every module of `tree` and place each of its files in its
+/// module with a `ChildOf` edge. Modules under `#[cfg(test)]` are marked `is_test`.
+pub fn add_module_nodes(graph: &mut CodeGraph, crate_name: &str, tree: &RustModTree) {
+    for (mod_path, file) in &tree.mod_map {
+        let module_idx = graph.add_module(&qualify_module_path(crate_name, mod_path), crate_name);
+        if in_test_module(tree, mod_path)
+            && let GraphNode::Module(module) = &mut graph.graph[module_idx]
+        {
+            module.is_test = true;
+        }
+        if let Some(&file_idx) = graph.file_index.get(file) {
+            graph.set_file_module(file_idx, module_idx);
+        }
     }
 }
 
@@ -415,4 +529,65 @@ mod tests {
             "file-backed mod must be in mod_map"
         );
     }
+
+    #[test]
+    fn test_add_module_nodes_places_files_in_modules() {
+        let tmp = tempfile::tempdir().unwrap();
+        make_simple_crate(tmp.path());
+        let tree = build_mod_tree("my_crate", &tmp.path().join("src/lib.rs"));
+        let mut graph = CodeGraph::new();
+        let imports = graph.add_file(tmp.path().join("src/parser/imports.rs"), "rust");
+        let lib = graph.add_file(tmp.path().join("src/lib.rs"), "rust");
+
+        add_module_nodes(&mut graph, "my_crate", &tree);
+
+        let mut modules: Vec<&str> = graph.module_index.keys().map(String::as_str).collect();
+        modules.sort();
+        assert_eq!(
+            modules,
+            vec![
+                "my_crate",
+                "my_crate::parser",
+                "my_crate::parser::imports",
+                "my_crate::utils"
+            ]
+        );
+        assert_eq!(
+            graph.module_of_file(imports).map(|m| m.path.as_str()),
+            Some("my_crate::parser::imports")
+        );
+        assert_eq!(
+            graph.module_of_file(lib).map(|m| m.path.as_str()),
+            Some("my_crate")
+        );
+    }
+
+    #[test]
+    fn test_feature_gated_mods_recorded() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path();
+        fs::create_dir_all(p.join("src")).unwrap();
+        fs::write(
+            p.join("src/lib.rs"),
+            "pub mod core;\n#[cfg(feature = \"web\")]\n#[doc(hidden)]\npub mod web;\n#[cfg(test)]\nmod tests;\n",
+        )
+        .unwrap();
+        for name in ["core", "web", "tests"] {
+            fs::write(p.join(format!("src/{name}.rs")), "").unwrap();
+        }
+
+        let tree = build_mod_tree("gated", &p.join("src/lib.rs"));
+        assert_eq!(tree.mod_map.len(), 4);
+        assert_eq!(
+            tree.feature_gates.get("crate::web").map(String::as_str),
+            Some("web")
+        );
+        assert_eq!(
+            tree.feature_gates.len(),
+            1,
+            "only feature cfgs are recorded"
+        );
+        let tests: Vec<&str> = tree.test_modules.iter().map(String::as_str).collect();
+        assert_eq!(tests, vec!["crate::tests"]);
+    }
 }
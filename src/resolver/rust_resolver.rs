@@ -5,7 +5,9 @@
 //!
 //! # Classification
 //! - **Builtin**: `std::`, `core::`, `alloc::` (or bare `std`, `core`, `alloc`) → `GraphNode::Builtin`
-//! - **IntraCrate**: `crate::`, `self::`, `super::` → resolved to a `FileInfo` node via `RustModTree`
+//! - **IntraCrate**: `crate::`, `self::`, `super::`, or a 2018 uniform path starting with a
+//!   child module of the current file (`use cli::Cli;` next to `mod cli;`) → resolved to a
+//!   `FileInfo` node via `RustModTree`
 //! - **CrossWorkspace**: first segment matches a workspace crate name → resolved to that crate's root file
 //! - **External**: everything else → `GraphNode::ExternalPackage`
 
@@ -16,6 +18,7 @@ use crate::graph::CodeGraph;
 use crate::graph::edge::EdgeKind;
 use crate::graph::node::GraphNode;
 use crate::parser::ParseResult;
+use crate::resolver::bindings::find_bound_symbol;
 use crate::resolver::cargo_workspace::discover_rust_workspace_members;
 use crate::resolver::rust_mod_tree::{RustModTree, build_mod_tree};
 
@@ -95,6 +98,20 @@ fn classify_use_path(
     UsePathKind::External
 }
 
+/// `true` when the first segment of `path` names a child module of `current_file`, making
+/// it a 2018 uniform path (`use cli::Cli;` next to `mod cli;`) rather than an external
+/// crate.
+fn starts_with_child_module(path: &str, current_file: &Path, mod_tree: &RustModTree) -> bool {
+    let first_segment = path.split("::").next().unwrap_or("");
+    mod_tree
+        .file_to_module_path(current_file)
+        .is_some_and(|module| {
+            mod_tree
+                .mod_map
+                .contains_key(&format!("{module}::{first_segment}"))
+        })
+}
+
 // ---------------------------------------------------------------------------
 // super:: and self:: path expansion
 // ---------------------------------------------------------------------------
@@ -161,6 +178,94 @@ fn resolve_self_path(path: &str, current_file: &Path, mod_tree: &RustModTree) ->
     Some(format!("{module_path}::{rest}"))
 }
 
+// ---------------------------------------------------------------------------
+// Use tree expansion
+// ---------------------------------------------------------------------------
+
+/// Split `s` at the commas that are not nested inside braces.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Expand the argument of a `use` declaration into one path per leaf.
+///
+/// `crate::{parser::imports, graph::{self, node::GraphNode as Node}}` expands to
+/// `crate::parser::imports`, `crate::graph`, and `crate::graph::node::GraphNode`.
+/// Aliases are dropped and globs (`::*`) are kept.
+pub(crate) fn expand_use_tree(path: &str) -> Vec<String> {
+    expand_use_tree_aliased(path)
+        .into_iter()
+        .map(|(leaf, _)| leaf)
+        .collect()
+}
+
+/// [`expand_use_tree`] keeping each leaf's alias: `GraphNode as Node` expands to
+/// `(crate::graph::node::GraphNode, Some("Node"))`.
+fn expand_use_tree_aliased(path: &str) -> Vec<(String, Option<String>)> {
+    let path = path.trim();
+    let Some(open) = path.find('{') else {
+        let mut parts = path.splitn(2, " as ");
+        let leaf = parts.next().unwrap_or(path).trim();
+        let alias = parts
+            .next()
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(str::to_string);
+        return if leaf.is_empty() {
+            Vec::new()
+        } else {
+            vec![(leaf.split_whitespace().collect(), alias)]
+        };
+    };
+    let Some(close) = path.rfind('}') else {
+        return Vec::new();
+    };
+    let prefix: String = path[..open].split_whitespace().collect();
+    let mut leaves = Vec::new();
+    for item in split_top_level(&path[open + 1..close]) {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        if item == "self" {
+            leaves.push((prefix.trim_end_matches("::").to_string(), None));
+        } else {
+            leaves.extend(expand_use_tree_aliased(&format!("{prefix}{item}")));
+        }
+    }
+    leaves
+}
+
+/// Record `pub use path as alias` in `from_idx`: an `ExportsAs` edge to the symbol the
+/// last segment of `path` names in `target_idx`, when there is one.
+fn add_use_alias(
+    graph: &mut CodeGraph,
+    from_idx: petgraph::stable_graph::NodeIndex,
+    target_idx: petgraph::stable_graph::NodeIndex,
+    path: &str,
+    alias: &str,
+) {
+    let name = path.rsplit("::").next().unwrap_or(path);
+    if let Some(symbol) = find_bound_symbol(graph, &[target_idx], name, false) {
+        graph.add_export_alias(from_idx, symbol, alias);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Main resolver
 // ---------------------------------------------------------------------------
@@ -173,13 +278,15 @@ fn resolve_self_path(path: &str, current_file: &Path, mod_tree: &RustModTree) ->
 /// 3. Build `file_to_crate` map (inverse of mod trees).
 /// 4. Collect all Phase 8 self-edges (`RustImport` / `ReExport` where source == target).
 /// 5. Remove those self-edges from the graph.
-/// 6. For each collected edge, classify the path and emit the appropriate resolved edge or node.
+/// 6. For each collected edge, expand its use tree into leaf paths, classify each path, and
+///    emit the appropriate resolved edge or node (once per distinct target and statement).
+///    Paths into another workspace crate resolve through that crate's mod tree.
 ///
 /// Returns a [`RustResolveStats`] summary.
 pub fn resolve_rust_uses(
     graph: &mut CodeGraph,
     project_root: &Path,
-    _parse_results: &HashMap<PathBuf, ParseResult>,
+    parse_results: &HashMap<PathBuf, ParseResult>,
     verbose: bool,
 ) -> RustResolveStats {
     let mut stats = RustResolveStats::default();
@@ -287,7 +394,7 @@ pub fn resolve_rust_uses(
     // -----------------------------------------------------------------------
     // Step 6: Classify and emit resolved edges.
     // -----------------------------------------------------------------------
-    for (_edge_idx, from_idx, path, is_reexport) in self_edges {
+    for (_edge_idx, from_idx, use_path, is_reexport) in self_edges {
         // Get the source file path for super:: / self:: resolution.
         let from_file_path: Option<PathBuf> = match &graph.graph[from_idx] {
             GraphNode::File(fi) => Some(fi.path.clone()),
@@ -305,143 +412,200 @@ pub fn resolve_rust_uses(
 
         // Determine which crate owns this file.
         let current_crate = file_to_crate.get(from_file).cloned().unwrap_or_default();
+        let use_line = parse_results
+            .get(from_file)
+            .and_then(|r| r.rust_uses.iter().find(|u| u.path == use_path))
+            .map_or(0, |u| u.line);
+
+        // Targets already linked by this statement: `use crate::ast::{Expr, Stmt}` yields one
+        // edge to `ast.rs`, and `use std::{fmt, io}` one edge to `std`, each specified by the
+        // whole statement path and counted once in the stats.
+        // A path into the file's own module (or an inline `mod`) links nothing.
+        let mut linked_files: HashSet<petgraph::stable_graph::NodeIndex> =
+            HashSet::from([from_idx]);
+        let mut linked_names: HashSet<String> = HashSet::new();
+        let mut unindexed: HashSet<PathBuf> = HashSet::new();
+
+        for (path, alias) in expand_use_tree_aliased(&use_path) {
+            let mut kind = classify_use_path(&path, &current_crate, &workspace_crate_names);
+            if kind == UsePathKind::External
+                && crate_mod_trees
+                    .get(&current_crate)
+                    .is_some_and(|t| starts_with_child_module(&path, from_file, t))
+            {
+                kind = UsePathKind::IntraCrate;
+            }
+            // `pub use a::Foo as Bar;` re-exports `Foo` as `Bar`.
+            let alias = alias.filter(|a| is_reexport && a != "_");
+
+            match kind {
+                UsePathKind::Builtin => {
+                    // Extract the root name: "std", "core", or "alloc".
+                    let root = path.split("::").next().unwrap_or("std");
+                    if linked_names.insert(root.to_string()) {
+                        graph.add_builtin_node(from_idx, root, &use_path);
+                        stats.builtin += 1;
+                    }
+                    if verbose {
+                        eprintln!("  [rust-resolver] builtin: {} → {root}", path);
+                    }
+                }
 
-        let kind = classify_use_path(&path, &current_crate, &workspace_crate_names);
+                UsePathKind::IntraCrate => {
+                    // Normalise to `crate::` absolute path.
+                    let resolved_path = if path.starts_with("super::") {
+                        let mod_tree = crate_mod_trees.get(&current_crate);
+                        mod_tree.and_then(|t| resolve_super_path(&path, from_file, t))
+                    } else if path.starts_with("crate::") {
+                        Some(path.clone())
+                    } else {
+                        // `self::…`, or a uniform path into a child module.
+                        let mod_tree = crate_mod_trees.get(&current_crate);
+                        mod_tree.and_then(|t| resolve_self_path(&path, from_file, t))
+                    };
+
+                    let resolved_path = match resolved_path {
+                        Some(p) => p,
+                        None => {
+                            graph.add_unresolved_import(
+                                from_idx,
+                                &path,
+                                "rust: super:: exceeds module depth",
+                            );
+                            stats.unresolved += 1;
+                            continue;
+                        }
+                    };
 
-        match kind {
-            UsePathKind::Builtin => {
-                // Extract the root name: "std", "core", or "alloc".
-                let root = path.split("::").next().unwrap_or("std");
-                graph.add_builtin_node(from_idx, root, &path);
-                stats.builtin += 1;
-                if verbose {
-                    eprintln!("  [rust-resolver] builtin: {} → {root}", path);
-                }
-            }
+                    // Handle glob imports: strip `::*` and resolve the module prefix.
+                    let lookup_path = if resolved_path.ends_with("::*") {
+                        resolved_path[..resolved_path.len() - 3].to_string()
+                    } else {
+                        resolved_path.clone()
+                    };
 
-            UsePathKind::IntraCrate => {
-                // Normalise to `crate::` absolute path.
-                let resolved_path = if path.starts_with("super::") {
+                    // Look up in the mod tree via progressive stripping.
                     let mod_tree = crate_mod_trees.get(&current_crate);
-                    mod_tree.and_then(|t| resolve_super_path(&path, from_file, t))
-                } else if path.starts_with("self::") {
-                    let mod_tree = crate_mod_trees.get(&current_crate);
-                    mod_tree.and_then(|t| resolve_self_path(&path, from_file, t))
-                } else {
-                    // Already `crate::...`
-                    Some(path.clone())
-                };
-
-                let resolved_path = match resolved_path {
-                    Some(p) => p,
-                    None => {
-                        graph.add_unresolved_import(
-                            from_idx,
-                            &path,
-                            "rust: super:: exceeds module depth",
-                        );
-                        stats.unresolved += 1;
-                        continue;
-                    }
-                };
-
-                // Handle glob imports: strip `::*` and resolve the module prefix.
-                let lookup_path = if resolved_path.ends_with("::*") {
-                    resolved_path[..resolved_path.len() - 3].to_string()
-                } else {
-                    resolved_path.clone()
-                };
-
-                // Look up in the mod tree via progressive stripping.
-                let mod_tree = crate_mod_trees.get(&current_crate);
-                let target_file = mod_tree.and_then(|t| t.resolve_module_path(&lookup_path));
-
-                match target_file {
-                    Some(target_path) => {
-                        // Check if this file is in the graph.
-                        if let Some(&target_idx) = graph.file_index.get(target_path) {
-                            graph.add_resolved_import(from_idx, target_idx, &path);
-                            stats.resolved += 1;
-                            if is_reexport {
-                                stats.reexport_resolved += 1;
-                            }
-                            if verbose {
-                                eprintln!(
-                                    "  [rust-resolver] intra: {} → {}",
-                                    path,
-                                    target_path.display()
-                                );
+                    let target_file = mod_tree.and_then(|t| t.resolve_module_path(&lookup_path));
+
+                    match target_file {
+                        Some(target_path) => {
+                            // Check if this file is in the graph.
+                            if let Some(&target_idx) = graph.file_index.get(target_path) {
+                                if linked_files.insert(target_idx) {
+                                    graph.add_import_at(
+                                        from_idx, target_idx, &use_path, false, use_line, 0,
+                                    );
+                                    stats.resolved += 1;
+                                    if is_reexport {
+                                        stats.reexport_resolved += 1;
+                                    }
+                                }
+                                if let Some(alias) = &alias {
+                                    add_use_alias(graph, from_idx, target_idx, &path, alias);
+                                }
+                                if verbose {
+                                    eprintln!(
+                                        "  [rust-resolver] intra: {} → {}",
+                                        path,
+                                        target_path.display()
+                                    );
+                                }
+                            } else {
+                                // File exists in mod tree but not in graph (e.g. excluded by config).
+                                // Still count as resolved but no edge.
+                                if unindexed.insert(target_path.clone()) {
+                                    stats.resolved += 1;
+                                }
+                                if verbose {
+                                    eprintln!(
+                                        "  [rust-resolver] intra (not indexed): {} → {}",
+                                        path,
+                                        target_path.display()
+                                    );
+                                }
                             }
-                        } else {
-                            // File exists in mod tree but not in graph (e.g. excluded by config).
-                            // Still count as resolved but no edge.
-                            stats.resolved += 1;
+                        }
+                        None => {
+                            graph.add_unresolved_import(
+                                from_idx,
+                                &path,
+                                "rust: could not resolve module path",
+                            );
+                            stats.unresolved += 1;
                             if verbose {
-                                eprintln!(
-                                    "  [rust-resolver] intra (not indexed): {} → {}",
-                                    path,
-                                    target_path.display()
-                                );
+                                eprintln!("  [rust-resolver] unresolved intra: {}", path);
                             }
                         }
                     }
-                    None => {
-                        graph.add_unresolved_import(
-                            from_idx,
-                            &path,
-                            "rust: could not resolve module path",
-                        );
-                        stats.unresolved += 1;
-                        if verbose {
-                            eprintln!("  [rust-resolver] unresolved intra: {}", path);
-                        }
-                    }
                 }
-            }
 
-            UsePathKind::CrossWorkspace => {
-                // Resolve to the crate root file of the target workspace crate.
-                let first_segment = path.split("::").next().unwrap_or("").replace('-', "_");
-                let crate_root = workspace_members.get(&first_segment);
-
-                match crate_root {
-                    Some(root_path) => {
-                        if let Some(&target_idx) = graph.file_index.get(root_path) {
-                            graph.add_resolved_import(from_idx, target_idx, &path);
-                            stats.resolved += 1;
-                            if is_reexport {
-                                stats.reexport_resolved += 1;
-                            }
-                            if verbose {
-                                eprintln!(
-                                    "  [rust-resolver] cross-workspace: {} → {}",
-                                    path,
-                                    root_path.display()
-                                );
-                            }
+                UsePathKind::CrossWorkspace => {
+                    // Resolve `other_crate::a::B` as `crate::a::B` in the other crate's mod tree,
+                    // falling back to its crate root file.
+                    let (first_segment, rest) = path.split_once("::").unwrap_or((&path, ""));
+                    let first_segment = first_segment.replace('-', "_");
+                    let rest = rest
+                        .strip_suffix("::*")
+                        .unwrap_or(rest)
+                        .trim_end_matches('*');
+                    let module_file = crate_mod_trees.get(&first_segment).and_then(|t| {
+                        if rest.is_empty() {
+                            None
                         } else {
-                            // Crate root not indexed — still count as resolved.
-                            stats.resolved += 1;
+                            t.resolve_module_path(&format!("crate::{rest}"))
+                        }
+                    });
+                    let crate_root = module_file.or(workspace_members.get(&first_segment));
+
+                    match crate_root {
+                        Some(root_path) => {
+                            if let Some(&target_idx) = graph.file_index.get(root_path) {
+                                if linked_files.insert(target_idx) {
+                                    graph.add_import_at(
+                                        from_idx, target_idx, &use_path, false, use_line, 0,
+                                    );
+                                    stats.resolved += 1;
+                                    if is_reexport {
+                                        stats.reexport_resolved += 1;
+                                    }
+                                }
+                                if let Some(alias) = &alias {
+                                    add_use_alias(graph, from_idx, target_idx, &path, alias);
+                                }
+                                if verbose {
+                                    eprintln!(
+                                        "  [rust-resolver] cross-workspace: {} → {}",
+                                        path,
+                                        root_path.display()
+                                    );
+                                }
+                            } else if unindexed.insert(root_path.clone()) {
+                                // Crate root not indexed — still count as resolved.
+                                stats.resolved += 1;
+                            }
+                        }
+                        None => {
+                            graph.add_unresolved_import(
+                                from_idx,
+                                &path,
+                                "rust: workspace crate root not found",
+                            );
+                            stats.unresolved += 1;
                         }
-                    }
-                    None => {
-                        graph.add_unresolved_import(
-                            from_idx,
-                            &path,
-                            "rust: workspace crate root not found",
-                        );
-                        stats.unresolved += 1;
                     }
                 }
-            }
 
-            UsePathKind::External => {
-                // Extract package name: first `::` segment, normalised.
-                let pkg_name = path.split("::").next().unwrap_or(&path).replace('-', "_");
-                graph.add_external_package(from_idx, &pkg_name, &path);
-                stats.external += 1;
-                if verbose {
-                    eprintln!("  [rust-resolver] external: {} → {pkg_name}", path);
+                UsePathKind::External => {
+                    // Extract package name: first `::` segment, normalised.
+                    let pkg_name = path.split("::").next().unwrap_or(&path).replace('-', "_");
+                    if linked_names.insert(pkg_name.clone()) {
+                        graph.add_external_package(from_idx, &pkg_name, &use_path);
+                        stats.external += 1;
+                    }
+                    if verbose {
+                        eprintln!("  [rust-resolver] external: {} → {pkg_name}", path);
+                    }
                 }
             }
         }
@@ -469,6 +633,21 @@ mod tests {
         names.iter().map(|s| s.to_string()).collect()
     }
 
+    #[test]
+    fn test_expand_use_tree_keeps_aliases() {
+        assert_eq!(
+            expand_use_tree_aliased("crate::engine::{start as launch, stop, Config as _}"),
+            vec![
+                (
+                    "crate::engine::start".to_string(),
+                    Some("launch".to_string())
+                ),
+                ("crate::engine::stop".to_string(), None),
+                ("crate::engine::Config".to_string(), Some("_".to_string())),
+            ]
+        );
+    }
+
     // --- classify_use_path tests ---
 
     #[test]
@@ -545,8 +724,201 @@ mod tests {
         );
     }
 
+    // --- expand_use_tree tests ---
+
+    #[test]
+    fn test_expand_use_tree() {
+        assert_eq!(
+            expand_use_tree("crate::parser::imports"),
+            vec!["crate::parser::imports"]
+        );
+        assert_eq!(
+            expand_use_tree(
+                "crate::{\n    parser::imports,\n    graph::{self, node::GraphNode as Node},\n    walker::*,\n}"
+            ),
+            vec![
+                "crate::parser::imports",
+                "crate::graph",
+                "crate::graph::node::GraphNode",
+                "crate::walker::*",
+            ]
+        );
+        assert_eq!(
+            expand_use_tree("std::{fmt, io::Write}"),
+            vec!["std::fmt", "std::io::Write"]
+        );
+    }
+
+    /// Write a two-crate workspace: `app` uses `core_lib` and has modules `ast`, `parser`.
+    fn write_workspace(p: &Path) {
+        std::fs::create_dir_all(p.join("app/src")).unwrap();
+        std::fs::create_dir_all(p.join("core_lib/src")).unwrap();
+        std::fs::write(
+            p.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"app\", \"core_lib\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            p.join("app/Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            p.join("core_lib/Cargo.toml"),
+            "[package]\nname = \"core-lib\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(p.join("app/src/lib.rs"), "pub mod ast;\npub mod parser;\n").unwrap();
+        std::fs::write(p.join("app/src/ast.rs"), "").unwrap();
+        std::fs::write(p.join("app/src/parser.rs"), "").unwrap();
+        std::fs::write(p.join("core_lib/src/lib.rs"), "pub mod span;\n").unwrap();
+        std::fs::write(p.join("core_lib/src/span.rs"), "").unwrap();
+    }
+
+    #[test]
+    fn test_resolve_use_trees_to_module_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path().canonicalize().unwrap();
+        write_workspace(&p);
+
+        let mut graph = CodeGraph::new();
+        let lib = graph.add_file(p.join("app/src/lib.rs"), "rust");
+        let ast = graph.add_file(p.join("app/src/ast.rs"), "rust");
+        let parser = graph.add_file(p.join("app/src/parser.rs"), "rust");
+        let span = graph.add_file(p.join("core_lib/src/span.rs"), "rust");
+        graph.add_file(p.join("core_lib/src/lib.rs"), "rust");
+        let uses = [
+            (
+                parser,
+                "crate::{ast::{Expr, Stmt}, ast::Item as AstItem, parser}",
+            ),
+            (parser, "core_lib::span::{Span, Spanned}"),
+            (parser, "std::{fmt, io}"),
+            (ast, "super::parser::Parser"),
+        ];
+        for (file, path) in uses {
+            graph.graph.add_edge(
+                file,
+                file,
+                EdgeKind::RustImport {
+                    path: path.to_string(),
+                },
+            );
+        }
+
+        let stats = resolve_rust_uses(&mut graph, &p, &HashMap::new(), false);
+        assert_eq!(stats.unresolved, 0);
+
+        let file_targets = |from| {
+            let mut targets: Vec<_> = graph
+                .graph
+                .edges(from)
+                .map(|e| petgraph::visit::EdgeRef::target(&e))
+                .filter(|&t| matches!(graph.graph[t], GraphNode::File(_)))
+                .collect();
+            targets.sort();
+            targets
+        };
+        // One edge per distinct module file; `crate::parser` names the file itself.
+        let mut expected = vec![ast, span];
+        expected.sort();
+        assert_eq!(file_targets(parser), expected);
+        // `super::` from `ast.rs` reaches the sibling module, closing the ast <-> parser cycle.
+        assert_eq!(file_targets(ast), vec![parser]);
+        assert!(file_targets(lib).is_empty());
+        let std_edges = graph
+            .graph
+            .edges(parser)
+            .filter(|e| {
+                matches!(
+                    graph.graph[petgraph::visit::EdgeRef::target(e)],
+                    GraphNode::Builtin { .. }
+                )
+            })
+            .count();
+        assert_eq!(std_edges, 1);
+    }
+
+    #[test]
+    fn test_grouped_use_keeps_statement_specifier() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path().canonicalize().unwrap();
+        write_workspace(&p);
+
+        let mut graph = CodeGraph::new();
+        graph.add_file(p.join("app/src/lib.rs"), "rust");
+        graph.add_file(p.join("app/src/ast.rs"), "rust");
+        let parser = graph.add_file(p.join("app/src/parser.rs"), "rust");
+        for path in [
+            "std::path::{Path, PathBuf}",
+            "crate::ast::{Expr, Stmt}",
+            "serde::{Deserialize, Serialize}",
+        ] {
+            graph.graph.add_edge(
+                parser,
+                parser,
+                EdgeKind::RustImport {
+                    path: path.to_string(),
+                },
+            );
+        }
+
+        let stats = resolve_rust_uses(&mut graph, &p, &HashMap::new(), false);
+        // One edge, and one count, per statement and target.
+        assert_eq!((stats.builtin, stats.resolved, stats.external), (1, 1, 1));
+        let mut specifiers: Vec<&str> = graph
+            .graph
+            .edges(parser)
+            .filter_map(|e| match e.weight() {
+                EdgeKind::ResolvedImport { specifier, .. } => Some(specifier.as_str()),
+                _ => None,
+            })
+            .collect();
+        specifiers.sort();
+        assert_eq!(
+            specifiers,
+            vec![
+                "crate::ast::{Expr, Stmt}",
+                "serde::{Deserialize, Serialize}",
+                "std::path::{Path, PathBuf}",
+            ]
+        );
+    }
+
     // --- resolve_super_path tests ---
 
+    #[test]
+    fn test_resolve_uniform_path_to_child_module() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path().canonicalize().unwrap();
+        write_workspace(&p);
+
+        let mut graph = CodeGraph::new();
+        let lib = graph.add_file(p.join("app/src/lib.rs"), "rust");
+        let ast = graph.add_file(p.join("app/src/ast.rs"), "rust");
+        graph.add_file(p.join("app/src/parser.rs"), "rust");
+        // `ast` is a child module of the crate root; `serde` is not, so it stays external.
+        for path in ["ast::Expr", "serde::Serialize"] {
+            graph.graph.add_edge(
+                lib,
+                lib,
+                EdgeKind::RustImport {
+                    path: path.to_string(),
+                },
+            );
+        }
+
+        let stats = resolve_rust_uses(&mut graph, &p, &HashMap::new(), false);
+        assert_eq!((stats.resolved, stats.external), (1, 1));
+        let files: Vec<_> = graph
+            .graph
+            .edges(lib)
+            .map(|e| petgraph::visit::EdgeRef::target(&e))
+            .filter(|&t| matches!(graph.graph[t], GraphNode::File(_)))
+            .collect();
+        assert_eq!(files, vec![ast]);
+    }
+
     #[test]
     fn test_resolve_super_one_level() {
         let tmp = tempfile::tempdir().unwrap();
@@ -0,0 +1,324 @@
+//! Shell script resolver.
+//!
+//! Integrates into `resolve_all` as Step 12:
+//! - **Includes** — each `source path` / `. path` becomes a `ResolvedImport` edge to the
+//!   sourced script. A leading expansion such as `$(dirname "$0")/`, `${BASH_SOURCE%/*}/`,
+//!   or `$SCRIPT_DIR/` is read as the script's own directory; plain relative paths are
+//!   tried against the script's directory and then the project root. Paths that are still
+//!   dynamic after that, and absolute paths outside the project, are skipped.
+//! - **Calls** — a command word naming a shell function becomes a `Calls` edge. Functions
+//!   in the same script win, then functions in scripts it sources (transitively), then a
+//!   single project-wide match. Command words never bind to symbols of other languages.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::GraphNode;
+use crate::parser::ParseResult;
+use crate::parser::imports::ImportKind;
+use crate::parser::relationships::RelationshipKind;
+
+/// Statistics collected by the shell resolver.
+#[derive(Debug, Default)]
+pub struct ShellResolveStats {
+    /// `source` includes resolved to an indexed script.
+    pub resolved: usize,
+    /// `source` includes of relative paths that are not indexed.
+    pub unresolved: usize,
+    /// `source` includes skipped because the path is dynamic or outside the project.
+    pub skipped: usize,
+    /// `Calls` edges added from command words to shell functions.
+    pub calls: usize,
+}
+
+/// Returns `true` for `.sh` / `.bash` paths.
+pub fn is_shell(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("sh" | "bash")
+    )
+}
+
+/// Returns `true` if the graph contains at least one shell script.
+pub fn has_shell_files(graph: &CodeGraph) -> bool {
+    graph
+        .graph
+        .node_weights()
+        .any(|node| matches!(node, GraphNode::File(f) if f.language == "shell"))
+}
+
+/// Strip a leading `$VAR/`, `${...}/`, or `$(...)/` expansion, which scripts almost always
+/// use to name their own directory. Paths without such a prefix are returned unchanged.
+fn strip_dir_expansion(path: &str) -> &str {
+    let Some(rest) = path.strip_prefix('$') else {
+        return path;
+    };
+    let end = match rest.chars().next() {
+        Some(open @ ('(' | '{')) => {
+            let close = if open == '(' { ')' } else { '}' };
+            let mut depth = 0usize;
+            let mut end = None;
+            for (i, c) in rest.char_indices() {
+                if c == open {
+                    depth += 1;
+                } else if c == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(i + 1);
+                        break;
+                    }
+                }
+            }
+            match end {
+                Some(end) => end,
+                None => return path,
+            }
+        }
+        _ => rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len()),
+    };
+    rest[end..].strip_prefix('/').unwrap_or(path)
+}
+
+/// Candidate files for a `source` argument, most likely first. Empty when the path is
+/// dynamic or points outside the project.
+fn source_candidates(script: &Path, project_root: &Path, arg: &str) -> Vec<PathBuf> {
+    let rel = strip_dir_expansion(arg);
+    if rel.is_empty() || rel.contains('$') || rel.contains('`') || rel.starts_with('~') {
+        return Vec::new();
+    }
+    if Path::new(rel).is_absolute() {
+        let path = super::normalize_path(Path::new(rel));
+        return if path.starts_with(project_root) {
+            vec![path]
+        } else {
+            Vec::new()
+        };
+    }
+    let mut candidates = Vec::new();
+    if let Some(dir) = script.parent() {
+        candidates.push(super::normalize_path(&dir.join(rel)));
+    }
+    candidates.push(super::normalize_path(&project_root.join(rel)));
+    candidates
+}
+
+/// Shell files reachable from `start` through resolved `source` edges, in BFS order
+/// (excluding `start` itself).
+fn sourced_closure(graph: &CodeGraph, start: NodeIndex) -> Vec<NodeIndex> {
+    let mut seen = HashSet::from([start]);
+    let mut order = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(idx) = queue.pop_front() {
+        for edge in graph.graph.edges_directed(idx, Direction::Outgoing) {
+            if !matches!(edge.weight(), EdgeKind::ResolvedImport { .. }) {
+                continue;
+            }
+            let target = edge.target();
+            if matches!(&graph.graph[target], GraphNode::File(f) if f.language == "shell")
+                && seen.insert(target)
+            {
+                order.push(target);
+                queue.push_back(target);
+            }
+        }
+    }
+    order
+}
+
+/// The file node containing symbol `sym`, if any.
+fn containing_file(graph: &CodeGraph, sym: NodeIndex) -> Option<NodeIndex> {
+    graph
+        .graph
+        .edges_directed(sym, Direction::Incoming)
+        .find(|e| matches!(e.weight(), EdgeKind::Contains))
+        .map(|e| e.source())
+}
+
+/// Step 12 of `resolve_all`: resolve `source` includes and function calls of every parsed
+/// shell script.
+pub fn resolve_shell(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+    project_root: &Path,
+    verbose: bool,
+) -> ShellResolveStats {
+    let mut stats = ShellResolveStats::default();
+
+    // Includes first, so call resolution can follow the sourced scripts.
+    for (path, result) in parse_results {
+        if !is_shell(path) {
+            continue;
+        }
+        let Some(from_idx) = graph.file_index.get(path).copied() else {
+            continue;
+        };
+        for import in &result.imports {
+            if import.kind != ImportKind::ShellSource {
+                continue;
+            }
+            let specifier = import.module_path.as_str();
+            let candidates = source_candidates(path, project_root, specifier);
+            if candidates.is_empty() {
+                stats.skipped += 1;
+                continue;
+            }
+            match candidates
+                .iter()
+                .find_map(|c| graph.file_index.get(c).copied())
+            {
+                Some(target_idx) => {
+                    graph.add_import_at(
+                        from_idx,
+                        target_idx,
+                        specifier,
+                        false,
+                        import.line,
+                        import.col,
+                    );
+                    stats.resolved += 1;
+                }
+                None => {
+                    graph.add_unresolved_import(from_idx, specifier, "sourced script not found");
+                    stats.unresolved += 1;
+                    if verbose {
+                        eprintln!(
+                            "  resolve: {} sources '{}' -> unresolved: sourced script not found",
+                            path.display(),
+                            specifier
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    for (path, result) in parse_results {
+        if !is_shell(path) {
+            continue;
+        }
+        let Some(from_idx) = graph.file_index.get(path).copied() else {
+            continue;
+        };
+        let search_order: Vec<NodeIndex> = std::iter::once(from_idx)
+            .chain(sourced_closure(graph, from_idx))
+            .collect();
+        let mut linked: HashSet<NodeIndex> = HashSet::new();
+
+        for rel in &result.relationships {
+            if rel.kind != RelationshipKind::Calls {
+                continue;
+            }
+            let Some(candidates) = graph.symbol_index.get(&rel.to_name) else {
+                continue;
+            };
+            let shell_candidates: Vec<(NodeIndex, NodeIndex)> = candidates
+                .iter()
+                .filter_map(|&sym| {
+                    let file = containing_file(graph, sym)?;
+                    matches!(&graph.graph[file], GraphNode::File(f) if f.language == "shell")
+                        .then_some((sym, file))
+                })
+                .collect();
+            let only_candidate = match shell_candidates.as_slice() {
+                [(sym, _)] => Some(*sym),
+                _ => None,
+            };
+            let callee = search_order
+                .iter()
+                .find_map(|file| {
+                    shell_candidates
+                        .iter()
+                        .find(|(_, f)| f == file)
+                        .map(|(sym, _)| *sym)
+                })
+                .or(only_candidate);
+            if let Some(callee) = callee {
+                graph.add_call_site(from_idx, callee, rel.line);
+                if linked.insert(callee) {
+                    stats.calls += 1;
+                }
+            }
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::shell::parse_shell;
+
+    #[test]
+    fn test_strip_dir_expansion() {
+        assert_eq!(strip_dir_expansion("$DIR/lib.sh"), "lib.sh");
+        assert_eq!(
+            strip_dir_expansion("$(dirname \"$0\")/lib/log.sh"),
+            "lib/log.sh"
+        );
+        assert_eq!(
+            strip_dir_expansion("${BASH_SOURCE%/*}/common.sh"),
+            "common.sh"
+        );
+        assert_eq!(strip_dir_expansion("./common.sh"), "./common.sh");
+        assert_eq!(strip_dir_expansion("$LIB"), "$LIB");
+    }
+
+    #[test]
+    fn test_resolve_shell_sources_and_calls() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path().to_path_buf();
+
+        let mut graph = CodeGraph::new();
+        let main_path = root.join("scripts/deploy.sh");
+        let lib_path = root.join("scripts/lib/log.sh");
+        let other_path = root.join("tools/other.sh");
+        let main_idx = graph.add_file(main_path.clone(), "shell");
+        let lib_idx = graph.add_file(lib_path.clone(), "shell");
+        let other_idx = graph.add_file(other_path.clone(), "shell");
+
+        let main_src = b"source \"$(dirname \"$0\")/lib/log.sh\"\n. ./missing.sh\nsource \"$PLUGIN\"\nrun() { log_info hi; setup; }\nrun\n";
+        let lib_src = b"log_info() { echo \"$@\"; }\nsetup() { :; }\n";
+        let other_src = b"setup() { :; }\n";
+
+        let mut parse_results = HashMap::new();
+        for (path, idx, src) in [
+            (&main_path, main_idx, &main_src[..]),
+            (&lib_path, lib_idx, &lib_src[..]),
+            (&other_path, other_idx, &other_src[..]),
+        ] {
+            let result = parse_shell(src);
+            for (symbol, _) in &result.symbols {
+                graph.add_symbol(idx, symbol.clone());
+            }
+            parse_results.insert(path.clone(), result);
+        }
+
+        let stats = resolve_shell(&mut graph, &parse_results, &root, false);
+        assert_eq!((stats.resolved, stats.unresolved, stats.skipped), (1, 1, 1));
+
+        let called = |name: &str| -> Vec<NodeIndex> {
+            graph
+                .graph
+                .edges_directed(main_idx, Direction::Outgoing)
+                .filter(|e| matches!(e.weight(), EdgeKind::Calls { .. }))
+                .map(|e| e.target())
+                .filter(|&t| matches!(&graph.graph[t], GraphNode::Symbol(s) if s.name == name))
+                .collect()
+        };
+        assert_eq!(called("run").len(), 1);
+        assert_eq!(called("log_info").len(), 1);
+        // `setup` is defined twice; the sourced library's definition wins.
+        let setup = called("setup");
+        assert_eq!(setup.len(), 1);
+        assert_eq!(containing_file(&graph, setup[0]), Some(lib_idx));
+    }
+}
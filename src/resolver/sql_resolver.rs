@@ -0,0 +1,179 @@
+//! SQL table reference resolver.
+//!
+//! Integrates into `resolve_all` as Step 10. Every `TableReference` relationship — a
+//! `FROM users` inside a `.sql` file, or a SQL string literal in TS/JS/Rust code — is
+//! matched case-insensitively against the indexed `Table`/`View` symbols and becomes a
+//! `ReferencesTable` edge from the referencing file to the table symbol. `impact` on a
+//! table then reaches the data-access code that queries it.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use petgraph::stable_graph::NodeIndex;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{GraphNode, SymbolKind};
+use crate::parser::ParseResult;
+use crate::parser::relationships::{RelationshipInfo, RelationshipKind};
+
+/// Index of all `Table`/`View` symbols keyed by lowercase name (SQL identifiers are
+/// case-insensitive).
+fn table_index(graph: &CodeGraph) -> HashMap<String, Vec<NodeIndex>> {
+    let mut index: HashMap<String, Vec<NodeIndex>> = HashMap::new();
+    for (name, indices) in &graph.symbol_index {
+        for &idx in indices {
+            if let GraphNode::Symbol(ref info) = graph.graph[idx]
+                && matches!(info.kind, SymbolKind::Table | SymbolKind::View)
+            {
+                index
+                    .entry(name.to_ascii_lowercase())
+                    .or_default()
+                    .push(idx);
+            }
+        }
+    }
+    index
+}
+
+/// Returns `true` if the graph contains at least one SQL table or view.
+pub fn has_tables(graph: &CodeGraph) -> bool {
+    graph.graph.node_weights().any(|node| {
+        matches!(
+            node,
+            GraphNode::Symbol(info) if matches!(info.kind, SymbolKind::Table | SymbolKind::View)
+        )
+    })
+}
+
+/// Step 10 of `resolve_all`: link table references in every parsed file.
+///
+/// Returns the number of `ReferencesTable` edges added.
+pub fn resolve_table_refs(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+) -> usize {
+    let index = table_index(graph);
+    let mut added = 0;
+    for (path, result) in parse_results {
+        if let Some(file_idx) = graph.file_index.get(path).copied() {
+            added += link_with_index(graph, &index, file_idx, &result.relationships);
+        }
+    }
+    added
+}
+
+/// Link the `TableReference` relationships of a single file to table symbols.
+///
+/// Used by the watcher after a file is re-parsed. Idempotent: references to a table
+/// the file already has a `ReferencesTable` edge to are skipped.
+pub fn link_table_refs(
+    graph: &mut CodeGraph,
+    file_idx: NodeIndex,
+    relationships: &[RelationshipInfo],
+) -> usize {
+    if !relationships
+        .iter()
+        .any(|r| r.kind == RelationshipKind::TableReference)
+    {
+        return 0;
+    }
+    let index = table_index(graph);
+    link_with_index(graph, &index, file_idx, relationships)
+}
+
+fn link_with_index(
+    graph: &mut CodeGraph,
+    index: &HashMap<String, Vec<NodeIndex>>,
+    file_idx: NodeIndex,
+    relationships: &[RelationshipInfo],
+) -> usize {
+    let mut added = 0;
+    for rel in relationships {
+        if rel.kind != RelationshipKind::TableReference {
+            continue;
+        }
+        let Some(targets) = index.get(&rel.to_name.to_ascii_lowercase()) else {
+            continue;
+        };
+        for &table_idx in targets {
+            // A table's own definition file is not a reference to it.
+            let defined_here = graph
+                .graph
+                .edges_connecting(file_idx, table_idx)
+                .any(|e| matches!(e.weight(), EdgeKind::Contains));
+            let already_linked = graph
+                .graph
+                .edges_connecting(file_idx, table_idx)
+                .any(|e| matches!(e.weight(), EdgeKind::ReferencesTable));
+            if !defined_here && !already_linked {
+                graph.add_table_reference(file_idx, table_idx);
+                added += 1;
+            }
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::sql::parse_sql;
+    use petgraph::visit::EdgeRef;
+
+    #[test]
+    fn test_resolve_table_refs_links_sql_and_code() {
+        let mut graph = CodeGraph::new();
+        let schema_path = PathBuf::from("/proj/db/schema.sql");
+        let repo_path = PathBuf::from("/proj/src/repo.ts");
+        let schema_idx = graph.add_file(schema_path.clone(), "sql");
+        let repo_idx = graph.add_file(repo_path.clone(), "typescript");
+
+        let schema = parse_sql(
+            b"CREATE TABLE users (id INT);\n\
+              CREATE TABLE orders (user_id INT REFERENCES users(id));",
+        );
+        let mut table_ids = HashMap::new();
+        for (info, _) in &schema.symbols {
+            table_ids.insert(
+                info.name.clone(),
+                graph.add_symbol(schema_idx, info.clone()),
+            );
+        }
+
+        let mut parse_results = HashMap::new();
+        parse_results.insert(schema_path, schema);
+        parse_results.insert(
+            repo_path,
+            ParseResult {
+                symbols: Vec::new(),
+                imports: Vec::new(),
+                exports: Vec::new(),
+                relationships: vec![RelationshipInfo {
+                    from_name: None,
+                    to_name: "USERS".into(),
+                    kind: RelationshipKind::TableReference,
+                    line: 3,
+                }],
+                rust_uses: Vec::new(),
+                ..Default::default()
+            },
+        );
+
+        // The schema file defines both tables, so only the repo -> users edge is added.
+        assert_eq!(resolve_table_refs(&mut graph, &parse_results), 1);
+        let referencing: Vec<NodeIndex> = graph
+            .graph
+            .edges_directed(table_ids["users"], petgraph::Direction::Incoming)
+            .filter(|e| matches!(e.weight(), EdgeKind::ReferencesTable))
+            .map(|e| e.source())
+            .collect();
+        assert_eq!(referencing, vec![repo_idx]);
+
+        // Re-linking a file adds no duplicate edges.
+        let repo_rels = parse_results[&PathBuf::from("/proj/src/repo.ts")]
+            .relationships
+            .clone();
+        assert_eq!(link_table_refs(&mut graph, repo_idx, &repo_rels), 0);
+    }
+}
@@ -0,0 +1,238 @@
+//! Heuristic string references (`[string_refs]` in `code-graph.toml`).
+//!
+//! Reflection and dependency-injection code reaches symbols by name
+//! (`container.get("UserService")`, `getattr(module, "handler")`), which leaves no import
+//! or call edge behind and gets the symbol reported as dead. When enabled, this pass reads
+//! every source file and adds a `StringRef` edge from the file to each symbol one of its
+//! string literals spells, so `dead-code` counts the symbol as used.
+//!
+//! A literal counts when it is a bare identifier at least `min_length` long that names an
+//! indexed symbol defined in another file, and, when `calls` is set, when it is an argument
+//! of a call matching one of those globs.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use petgraph::stable_graph::{EdgeIndex, NodeIndex};
+use rayon::prelude::*;
+use regex::Regex;
+
+use crate::config::StringRefsConfig;
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+use crate::query::util::find_containing_file_idx;
+
+/// Names defined more often than this are too ambiguous to attribute a literal to.
+const MAX_AMBIGUOUS_DEFS: usize = 3;
+
+/// A quoted identifier: `"UserService"`, `'handler'`, or a template literal without
+/// substitutions.
+static LITERAL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"["'`]([A-Za-z_$][A-Za-z0-9_$]*)["'`]"#).unwrap());
+
+/// The call a literal is an argument of, matched against the text before the literal on
+/// its line: a dotted callee (`container.get`, `Foo::resolve`, `$this->get`), optional
+/// generic arguments, the opening parenthesis, and any earlier arguments.
+static CALL_BEFORE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"([A-Za-z_$][\w$]*(?:\s*(?:\.|::|->)\s*[A-Za-z_$][\w$]*)*)\s*(?:<[^()]*>)?\s*\(\s*(?:[^()]*,\s*)?$",
+    )
+    .unwrap()
+});
+
+/// One identifier-like string literal.
+#[derive(Debug, PartialEq)]
+pub(crate) struct StringLiteral {
+    /// 1-based line.
+    pub line: usize,
+    /// The literal's text, without quotes.
+    pub name: String,
+    /// The call it is an argument of, whitespace removed (`container.get`), if any.
+    pub callee: Option<String>,
+}
+
+/// Identifier-like string literals in `source`.
+pub(crate) fn string_literals(source: &str) -> Vec<StringLiteral> {
+    let mut literals = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        for caps in LITERAL.captures_iter(line) {
+            let whole = caps.get(0).unwrap();
+            let callee = CALL_BEFORE
+                .captures(&line[..whole.start()])
+                .map(|c| c[1].split_whitespace().collect::<String>());
+            literals.push(StringLiteral {
+                line: i + 1,
+                name: caps[1].to_string(),
+                callee,
+            });
+        }
+    }
+    literals
+}
+
+/// Replace the graph's `StringRef` edges with those `config` yields. Returns the number
+/// of edges added; with the pass disabled, the old edges are only removed.
+pub fn link_string_refs(graph: &mut CodeGraph, config: &StringRefsConfig) -> usize {
+    let stale: Vec<EdgeIndex> = graph
+        .graph
+        .edge_indices()
+        .filter(|&e| matches!(graph.graph[e], EdgeKind::StringRef { .. }))
+        .collect();
+    for edge in stale {
+        graph.graph.remove_edge(edge);
+    }
+    if !config.enabled {
+        return 0;
+    }
+
+    let calls: Vec<glob::Pattern> = config
+        .calls
+        .iter()
+        .filter_map(|p| match glob::Pattern::new(p) {
+            Ok(pattern) => Some(pattern),
+            Err(err) => {
+                eprintln!("warning: [string_refs] calls: invalid pattern '{p}': {err}");
+                None
+            }
+        })
+        .collect();
+
+    let files: Vec<(NodeIndex, PathBuf)> = graph
+        .file_index
+        .iter()
+        .filter(|(_, idx)| {
+            matches!(&graph.graph[**idx], GraphNode::File(fi) if fi.kind == FileKind::Source)
+        })
+        .map(|(path, &idx)| (idx, path.clone()))
+        .collect();
+    let found: Vec<(NodeIndex, Vec<StringLiteral>)> = files
+        .par_iter()
+        .filter_map(|(idx, path)| {
+            let text = std::fs::read_to_string(path).ok()?;
+            Some((*idx, string_literals(&text)))
+        })
+        .collect();
+
+    // (file, symbol) -> first line.
+    let mut refs: HashMap<(NodeIndex, NodeIndex), usize> = HashMap::new();
+    for (file_idx, literals) in found {
+        for literal in literals {
+            if literal.name.len() < config.min_length {
+                continue;
+            }
+            if !calls.is_empty()
+                && !literal
+                    .callee
+                    .as_deref()
+                    .is_some_and(|callee| calls.iter().any(|p| p.matches(callee)))
+            {
+                continue;
+            }
+            let Some(symbols) = graph.symbol_index.get(&literal.name) else {
+                continue;
+            };
+            if symbols.len() > MAX_AMBIGUOUS_DEFS {
+                continue;
+            }
+            for &symbol in symbols {
+                if find_containing_file_idx(graph, symbol) != Some(file_idx) {
+                    refs.entry((file_idx, symbol))
+                        .and_modify(|line| *line = (*line).min(literal.line))
+                        .or_insert(literal.line);
+                }
+            }
+        }
+    }
+
+    let added = refs.len();
+    for ((file_idx, symbol), line) in refs {
+        graph.add_string_ref(file_idx, symbol, line);
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+
+    #[test]
+    fn test_string_literals_with_callee() {
+        let source = "const svc = container.get<UserService>(\"UserService\");\n\
+                      handler = getattr(module, 'on_event')\n\
+                      log(`done`, \"not an ident!\")\n";
+        let literals = string_literals(source);
+        let summary: Vec<(usize, &str, Option<&str>)> = literals
+            .iter()
+            .map(|l| (l.line, l.name.as_str(), l.callee.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "UserService", Some("container.get")),
+                (2, "on_event", Some("getattr")),
+                (3, "done", Some("log")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_link_string_refs_respects_config() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        let service = root.join("user_service.ts");
+        let app = root.join("app.ts");
+        std::fs::write(&service, "export class UserService {}\n").unwrap();
+        std::fs::write(
+            &app,
+            "const a = container.get(\"UserService\");\nlog(\"UserService\");\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let service_idx = graph.add_file(service, "typescript");
+        graph.add_file(app.clone(), "typescript");
+        let class = graph.add_symbol(
+            service_idx,
+            SymbolInfo {
+                name: "UserService".into(),
+                kind: SymbolKind::Class,
+                ..Default::default()
+            },
+        );
+        let string_refs = |graph: &CodeGraph| -> Vec<(NodeIndex, usize)> {
+            graph
+                .graph
+                .edge_indices()
+                .filter_map(|e| match graph.graph[e] {
+                    EdgeKind::StringRef { line } => {
+                        Some((graph.graph.edge_endpoints(e).unwrap().1, line))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let mut config = StringRefsConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert_eq!(link_string_refs(&mut graph, &config), 1);
+        assert_eq!(string_refs(&graph), vec![(class, 1)]);
+
+        config.calls = vec!["log".into()];
+        link_string_refs(&mut graph, &config);
+        assert_eq!(string_refs(&graph), vec![(class, 2)]);
+
+        config.min_length = 20;
+        link_string_refs(&mut graph, &config);
+        assert!(string_refs(&graph).is_empty());
+
+        config.enabled = false;
+        config.min_length = 4;
+        link_string_refs(&mut graph, &config);
+        assert!(string_refs(&graph).is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+//! Test-file classification and `Tests` edges.
+//!
+//! A source file is a test file when its name follows a test convention (`*.test.ts`,
+//! `*.spec.tsx`, `test_*.py`, `*_test.py`, `*_test.go`), it lives under a `tests/` or
+//! `__tests__/` directory, or it is a Rust module declared under `#[cfg(test)]`.
+//! This pass sets `FileInfo::is_test` and adds a `Tests` edge from every test file to the
+//! non-test files it imports and the symbols its named imports bind, so `impact --tests`
+//! can narrow a blast radius to the tests worth re-running.
+//!
+//! Inline `#[cfg(test)] mod tests { ... }` blocks do not make their file a test file.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use petgraph::Direction;
+use petgraph::stable_graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+use crate::graph::CodeGraph;
+use crate::graph::edge::EdgeKind;
+use crate::graph::node::{FileKind, GraphNode};
+use crate::query::util::find_containing_file_idx;
+
+/// Directories whose source files are tests.
+const TEST_DIRS: &[&str] = &["tests", "__tests__"];
+
+/// `true` when the project-relative `path` follows a test file naming convention or lives
+/// in a test directory.
+pub fn is_test_path(path: &Path) -> bool {
+    let in_test_dir = path
+        .parent()
+        .into_iter()
+        .flat_map(|dir| dir.components())
+        .any(|c| TEST_DIRS.contains(&c.as_os_str().to_str().unwrap_or("")));
+    if in_test_dir {
+        return true;
+    }
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if stem.ends_with(".test") || stem.ends_with(".spec") {
+        return true;
+    }
+    match ext {
+        "py" => stem.starts_with("test_") || stem.ends_with("_test"),
+        "go" => stem.ends_with("_test"),
+        _ => false,
+    }
+}
+
+fn is_test_file(graph: &CodeGraph, idx: NodeIndex) -> bool {
+    matches!(&graph.graph[idx], GraphNode::File(fi) if fi.is_test)
+}
+
+/// Classify every source file and rebuild the `Tests` edges. Returns the number of edges
+/// added.
+///
+/// Idempotent: existing `Tests` edges are dropped first, so the watcher can re-run it after
+/// any file is re-indexed.
+pub fn link_tests(graph: &mut CodeGraph, project_root: &Path) -> usize {
+    let stale: Vec<EdgeIndex> = graph
+        .graph
+        .edge_indices()
+        .filter(|&e| matches!(graph.graph[e], EdgeKind::Tests))
+        .collect();
+    for edge in stale {
+        graph.graph.remove_edge(edge);
+    }
+
+    let classified: Vec<(NodeIndex, bool)> = graph
+        .file_index
+        .values()
+        .filter_map(|&idx| match &graph.graph[idx] {
+            GraphNode::File(fi) => {
+                let rel = fi.path.strip_prefix(project_root).unwrap_or(&fi.path);
+                let in_test_module = graph.module_of_file(idx).is_some_and(|m| m.is_test);
+                let is_test = fi.kind == FileKind::Source && (is_test_path(rel) || in_test_module);
+                Some((idx, is_test))
+            }
+            _ => None,
+        })
+        .collect();
+    let mut test_files: Vec<NodeIndex> = Vec::new();
+    for (idx, is_test) in classified {
+        if let GraphNode::File(fi) = &mut graph.graph[idx] {
+            fi.is_test = is_test;
+        }
+        if is_test {
+            test_files.push(idx);
+        }
+    }
+
+    let mut added = 0;
+    for test_idx in test_files {
+        let mut subjects: HashSet<NodeIndex> = HashSet::new();
+        for edge_ref in graph.graph.edges_directed(test_idx, Direction::Outgoing) {
+            let target = edge_ref.target();
+            match edge_ref.weight() {
+                EdgeKind::ResolvedImport { .. }
+                    if target != test_idx
+                        && matches!(graph.graph[target], GraphNode::File(_))
+                        && !is_test_file(graph, target) =>
+                {
+                    subjects.insert(target);
+                }
+                EdgeKind::ImportsSymbol { .. }
+                    if find_containing_file_idx(graph, target)
+                        .is_some_and(|file| !is_test_file(graph, file)) =>
+                {
+                    subjects.insert(target);
+                }
+                _ => {}
+            }
+        }
+        let mut subjects: Vec<NodeIndex> = subjects.into_iter().collect();
+        subjects.sort();
+        for subject in subjects {
+            graph.add_tests(test_idx, subject);
+            added += 1;
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::node::{SymbolInfo, SymbolKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_is_test_path() {
+        for path in [
+            "src/button.test.ts",
+            "src/button.spec.tsx",
+            "src/__tests__/button.ts",
+            "tests/integration.rs",
+            "crates/core/tests/api.rs",
+            "pkg/test_models.py",
+            "pkg/models_test.py",
+            "server/handler_test.go",
+        ] {
+            assert!(is_test_path(Path::new(path)), "{path}");
+        }
+        for path in [
+            "src/testing.ts",
+            "src/contest.py",
+            "src/latest.rs",
+            "tests.rs",
+        ] {
+            assert!(!is_test_path(Path::new(path)), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_link_tests() {
+        let root = PathBuf::from("/proj");
+        let mut graph = CodeGraph::new();
+        let button = graph.add_file(root.join("src/button.ts"), "typescript");
+        let render = graph.add_symbol(
+            button,
+            SymbolInfo {
+                name: "render".into(),
+                kind: SymbolKind::Function,
+                is_exported: true,
+                ..Default::default()
+            },
+        );
+        let helper = graph.add_file(root.join("src/__tests__/helper.ts"), "typescript");
+        let spec = graph.add_file(root.join("src/button.spec.ts"), "typescript");
+        graph.add_resolved_import(spec, button, "./button");
+        graph.add_resolved_import(spec, helper, "./__tests__/helper");
+        graph.add_imports_symbol(spec, render, 0, 0);
+
+        assert_eq!(link_tests(&mut graph, &root), 2);
+        // Re-running replaces the edges instead of duplicating them.
+        assert_eq!(link_tests(&mut graph, &root), 2);
+
+        let is_test = |idx| matches!(&graph.graph[idx], GraphNode::File(fi) if fi.is_test);
+        assert!(is_test(spec) && is_test(helper) && !is_test(button));
+        let mut tested: Vec<NodeIndex> = graph
+            .graph
+            .edges_directed(spec, Direction::Outgoing)
+            .filter(|e| matches!(e.weight(), EdgeKind::Tests))
+            .map(|e| e.target())
+            .collect();
+        tested.sort();
+        assert_eq!(tested, vec![button, render]);
+    }
+}
@@ -8,29 +8,35 @@ use std::path::{Path, PathBuf};
 /// no workspace configuration is found.
 pub fn discover_workspace_packages(root: &Path) -> HashMap<String, PathBuf> {
     let mut result = HashMap::new();
-    let patterns = read_workspace_globs(root);
 
-    for pattern in patterns {
+    for pkg_json_path in discover_workspace_package_jsons(root) {
+        if let Some(pkg_dir) = pkg_json_path.parent()
+            && let Ok(content) = std::fs::read_to_string(&pkg_json_path)
+            && let Ok(json) = serde_json::from_str::<serde_json::Value>(&content)
+            && let Some(name) = json["name"].as_str()
+        {
+            let src = pkg_dir.join("src");
+            let target = if src.exists() {
+                src
+            } else {
+                pkg_dir.to_path_buf()
+            };
+            result.insert(name.to_owned(), target);
+        }
+    }
+
+    result
+}
+
+/// The `package.json` of every workspace package matched by the workspace globs.
+pub fn discover_workspace_package_jsons(root: &Path) -> Vec<PathBuf> {
+    let mut result = Vec::new();
+    for pattern in read_workspace_globs(root) {
         let full_pattern = format!("{}/{}/package.json", root.display(), pattern);
         if let Ok(paths) = glob::glob(&full_pattern) {
-            for pkg_json_path in paths.flatten() {
-                if let Some(pkg_dir) = pkg_json_path.parent()
-                    && let Ok(content) = std::fs::read_to_string(&pkg_json_path)
-                    && let Ok(json) = serde_json::from_str::<serde_json::Value>(&content)
-                    && let Some(name) = json["name"].as_str()
-                {
-                    let src = pkg_dir.join("src");
-                    let target = if src.exists() {
-                        src
-                    } else {
-                        pkg_dir.to_path_buf()
-                    };
-                    result.insert(name.to_owned(), target);
-                }
-            }
+            result.extend(paths.flatten());
         }
     }
-
     result
 }
 
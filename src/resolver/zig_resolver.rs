@@ -0,0 +1,196 @@
+//! Zig `@import` resolver.
+//!
+//! Integrates into `resolve_all` as Step 11. Each `@import("...")` in a `.zig` file is
+//! classified as:
+//! - **Builtin** — `std`, `builtin`, `root`: a `Builtin` node.
+//! - **File** — a `.zig`/`.zon` path, relative to the importing file: a `ResolvedImport`
+//!   edge when the target is indexed.
+//! - **Build module** — a name declared by a `build.zig` script (`b.addModule`,
+//!   `addImport`, `addAnonymousImport`): an edge to the module's root source file, resolved
+//!   relative to the `build.zig` that declares it.
+//! - **Package** — any other name (a `build.zig.zon` dependency): an `ExternalPackage` node.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::graph::CodeGraph;
+use crate::graph::node::GraphNode;
+use crate::parser::ParseResult;
+use crate::parser::imports::ImportKind;
+
+/// Statistics collected by the Zig resolver.
+#[derive(Debug, Default)]
+pub struct ZigResolveStats {
+    /// `@import`s resolved to an indexed file (relative paths and build modules).
+    pub resolved: usize,
+    /// `@import`s of `std`, `builtin`, or `root`.
+    pub builtin: usize,
+    /// `@import`s of package dependencies.
+    pub external: usize,
+    /// `@import`s of `.zig` paths that are not indexed.
+    pub unresolved: usize,
+}
+
+/// Import names the Zig compiler provides itself.
+const ZIG_BUILTIN_MODULES: &[&str] = &["std", "builtin", "root"];
+
+fn is_zig(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("zig")
+}
+
+/// Returns `true` if the graph contains at least one Zig file.
+pub fn has_zig_files(graph: &CodeGraph) -> bool {
+    graph
+        .graph
+        .node_weights()
+        .any(|node| matches!(node, GraphNode::File(f) if f.language == "zig"))
+}
+
+/// Module name -> root source file for every module declared by an indexed `build.zig`.
+///
+/// When several scripts declare the same name, the one closest to the project root wins
+/// (paths are visited in sorted order), so a nested example's `build.zig` does not shadow
+/// the top-level package.
+fn build_module_map(graph: &CodeGraph) -> HashMap<String, PathBuf> {
+    let mut scripts: Vec<&PathBuf> = graph
+        .file_index
+        .keys()
+        .filter(|p| p.file_name().and_then(|n| n.to_str()) == Some("build.zig"))
+        .collect();
+    scripts.sort_by_key(|p| (p.components().count(), (*p).clone()));
+
+    let mut modules = HashMap::new();
+    for script in scripts {
+        let (Ok(source), Some(dir)) = (std::fs::read(script), script.parent()) else {
+            continue;
+        };
+        for (name, rel) in crate::parser::zig::build_modules(&source) {
+            modules
+                .entry(name)
+                .or_insert_with(|| super::normalize_path(&dir.join(rel)));
+        }
+    }
+    modules
+}
+
+/// Step 11 of `resolve_all`: resolve the `@import`s of every parsed Zig file.
+pub fn resolve_zig(
+    graph: &mut CodeGraph,
+    parse_results: &HashMap<PathBuf, ParseResult>,
+    verbose: bool,
+) -> ZigResolveStats {
+    let mut stats = ZigResolveStats::default();
+    let modules = build_module_map(graph);
+
+    for (path, result) in parse_results {
+        if !is_zig(path) {
+            continue;
+        }
+        let Some(from_idx) = graph.file_index.get(path).copied() else {
+            continue;
+        };
+        for import in &result.imports {
+            if import.kind != ImportKind::ZigImport {
+                continue;
+            }
+            let specifier = import.module_path.as_str();
+
+            if ZIG_BUILTIN_MODULES.contains(&specifier) {
+                graph.add_builtin_node(from_idx, specifier, specifier);
+                stats.builtin += 1;
+                continue;
+            }
+
+            let is_path = specifier.ends_with(".zig") || specifier.ends_with(".zon");
+            let target = if is_path {
+                path.parent()
+                    .map(|dir| super::normalize_path(&dir.join(specifier)))
+            } else {
+                modules.get(specifier).cloned()
+            };
+            match target.and_then(|t| graph.file_index.get(&t).copied()) {
+                Some(target_idx) => {
+                    graph.add_import_at(
+                        from_idx,
+                        target_idx,
+                        specifier,
+                        false,
+                        import.line,
+                        import.col,
+                    );
+                    stats.resolved += 1;
+                }
+                None if is_path => {
+                    graph.add_unresolved_import(from_idx, specifier, "zig file not found");
+                    stats.unresolved += 1;
+                    if verbose {
+                        eprintln!(
+                            "  resolve: {} imports '{}' -> unresolved: zig file not found",
+                            path.display(),
+                            specifier
+                        );
+                    }
+                }
+                None => {
+                    graph.add_external_package(from_idx, specifier, specifier);
+                    stats.external += 1;
+                }
+            }
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::edge::EdgeKind;
+    use crate::parser::zig::parse_zig;
+
+    #[test]
+    fn test_resolve_zig_paths_build_modules_and_packages() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path().to_path_buf();
+        std::fs::write(
+            root.join("build.zig"),
+            "pub fn build(b: *std.Build) void {\n    _ = b.addModule(\"core\", .{ .root_source_file = b.path(\"src/core/root.zig\") });\n}\n",
+        )
+        .unwrap();
+
+        let mut graph = CodeGraph::new();
+        let main_path = root.join("src/main.zig");
+        let main_idx = graph.add_file(main_path.clone(), "zig");
+        let util_idx = graph.add_file(root.join("src/util.zig"), "zig");
+        let core_idx = graph.add_file(root.join("src/core/root.zig"), "zig");
+        graph.add_file(root.join("build.zig"), "zig");
+
+        let mut parse_results = HashMap::new();
+        parse_results.insert(
+            main_path,
+            parse_zig(
+                b"const std = @import(\"std\");\nconst util = @import(\"./util.zig\");\nconst core = @import(\"core\");\nconst zap = @import(\"zap\");\nconst gone = @import(\"gone.zig\");\n",
+            ),
+        );
+
+        let stats = resolve_zig(&mut graph, &parse_results, false);
+        assert_eq!(
+            (
+                stats.resolved,
+                stats.builtin,
+                stats.external,
+                stats.unresolved
+            ),
+            (2, 1, 1, 1)
+        );
+        let has_edge = |to| {
+            graph
+                .graph
+                .edges_connecting(main_idx, to)
+                .any(|e| matches!(e.weight(), EdgeKind::ResolvedImport { .. }))
+        };
+        assert!(has_edge(util_idx));
+        assert!(has_edge(core_idx));
+        assert!(graph.external_index.contains_key("zap"));
+        assert!(graph.builtin_index.contains_key("std"));
+    }
+}
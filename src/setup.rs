@@ -438,12 +438,16 @@ fn cleanup_mcp(base_dir: &Path) -> Result<Vec<String>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::Cli;
+    use crate::cli::{Cli, with_cli_stack};
     use clap::Parser;
 
+    fn parse(args: &[&str]) -> Cli {
+        with_cli_stack(|| Cli::parse_from(args))
+    }
+
     #[test]
     fn test_setup_parses() {
-        let cli = Cli::parse_from(["code-graph", "setup"]);
+        let cli = parse(&["code-graph", "setup"]);
         match cli.command {
             crate::cli::Commands::Setup { global, uninstall } => {
                 assert!(!global, "--global should default to false");
@@ -455,7 +459,7 @@ mod tests {
 
     #[test]
     fn test_setup_global_flag() {
-        let cli = Cli::parse_from(["code-graph", "setup", "--global"]);
+        let cli = parse(&["code-graph", "setup", "--global"]);
         match cli.command {
             crate::cli::Commands::Setup { global, uninstall } => {
                 assert!(global, "--global should be true");
@@ -467,7 +471,7 @@ mod tests {
 
     #[test]
     fn test_setup_uninstall_flag() {
-        let cli = Cli::parse_from(["code-graph", "setup", "--uninstall"]);
+        let cli = parse(&["code-graph", "setup", "--uninstall"]);
         match cli.command {
             crate::cli::Commands::Setup { global, uninstall } => {
                 assert!(!global, "--global should default to false");
@@ -0,0 +1,306 @@
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use clap::{CommandFactory, ValueEnum};
+
+use crate::cli::{Cli, OutputFormat};
+use crate::export::model::{ExportFormat, Granularity};
+
+/// Crate name on crates.io, from which `self-update` installs.
+const CRATE_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// Languages parsed by the built-in grammars and parsers.
+const BUILTIN_LANGUAGES: &[&str] = &[
+    "typescript",
+    "tsx",
+    "javascript",
+    "rust",
+    "python",
+    "go",
+    "proto",
+    "sql",
+    "astro",
+    "zig",
+    "shell",
+    "jupyter",
+    "css",
+];
+
+/// What this binary was built with.
+#[derive(Debug, serde::Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    /// Cache format version; a binary only reads caches written with the same one.
+    pub cache_version: u32,
+    /// `<arch>-<os>`.
+    pub target: String,
+    /// `release` or `debug`.
+    pub profile: &'static str,
+    /// Cargo features this binary was compiled with.
+    pub features: Vec<&'static str>,
+    pub languages: Vec<&'static str>,
+}
+
+pub fn build_info() -> BuildInfo {
+    let features = [
+        ("web", cfg!(feature = "web")),
+        ("rag", cfg!(feature = "rag")),
+        ("dynamic-grammars", cfg!(feature = "dynamic-grammars")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(name, _)| name)
+    .collect();
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        cache_version: crate::cache::envelope::CACHE_VERSION,
+        target: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+        profile: if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        },
+        features,
+        languages: BUILTIN_LANGUAGES.to_vec(),
+    }
+}
+
+/// Print the version; with `verbose`, the full [`BuildInfo`].
+pub fn print_version(verbose: bool, format: &OutputFormat) {
+    let info = build_info();
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&info).unwrap_or_default()
+        );
+        return;
+    }
+    println!("code-graph {}", info.version);
+    if !verbose {
+        return;
+    }
+    println!("cache format: {}", info.cache_version);
+    println!("target: {} ({})", info.target, info.profile);
+    let features = if info.features.is_empty() {
+        "none".to_string()
+    } else {
+        info.features.join(", ")
+    };
+    println!("features: {}", features);
+    println!("languages: {}", info.languages.join(", "));
+}
+
+/// A subcommand of the CLI.
+#[derive(Debug, serde::Serialize)]
+pub struct CommandInfo {
+    pub name: String,
+    /// First line of its help.
+    pub about: String,
+}
+
+/// Everything a wrapper or editor extension needs to adapt to this binary.
+#[derive(Debug, serde::Serialize)]
+pub struct Capabilities {
+    #[serde(flatten)]
+    pub build: BuildInfo,
+    /// Top-level commands, in help order.
+    pub commands: Vec<CommandInfo>,
+    /// `--format` values of the query commands.
+    pub output_formats: Vec<String>,
+    /// `export --format` values.
+    pub export_formats: Vec<String>,
+    /// `export --granularity` values.
+    pub export_granularities: Vec<String>,
+    /// Dotted `code-graph.toml` keys.
+    pub config_keys: Vec<String>,
+}
+
+/// Names of a `clap::ValueEnum`'s variants as accepted on the command line.
+fn value_names<T: ValueEnum>() -> Vec<String> {
+    T::value_variants()
+        .iter()
+        .filter_map(|v| v.to_possible_value())
+        .map(|v| v.get_name().to_string())
+        .collect()
+}
+
+pub fn capabilities() -> Capabilities {
+    let commands = Cli::command()
+        .get_subcommands()
+        .filter(|c| !c.is_hide_set())
+        .map(|c| CommandInfo {
+            name: c.get_name().to_string(),
+            about: c
+                .get_about()
+                .map(|a| a.to_string())
+                .unwrap_or_default()
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        })
+        .collect();
+    Capabilities {
+        build: build_info(),
+        commands,
+        output_formats: value_names::<OutputFormat>(),
+        export_formats: value_names::<ExportFormat>(),
+        export_granularities: value_names::<Granularity>(),
+        config_keys: crate::config::known_keys(),
+    }
+}
+
+/// Print [`capabilities`]: JSON for tools, one `section: values` line each otherwise.
+pub fn print_capabilities(format: &OutputFormat) {
+    let caps = capabilities();
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&caps).unwrap_or_default()
+        );
+        return;
+    }
+    let names: Vec<&str> = caps.commands.iter().map(|c| c.name.as_str()).collect();
+    println!("version: {}", caps.build.version);
+    println!("cache format: {}", caps.build.cache_version);
+    println!("languages: {}", caps.build.languages.join(", "));
+    let features = if caps.build.features.is_empty() {
+        "none".to_string()
+    } else {
+        caps.build.features.join(", ")
+    };
+    println!("features: {}", features);
+    println!("commands: {}", names.join(", "));
+    println!("output formats: {}", caps.output_formats.join(", "));
+    println!("export formats: {}", caps.export_formats.join(", "));
+    println!(
+        "export granularities: {}",
+        caps.export_granularities.join(", ")
+    );
+    println!("config keys: {}", caps.config_keys.join(", "));
+}
+
+/// Install the latest published release with `cargo install`, keeping this binary's
+/// features. With `check`, only report whether a newer release exists.
+pub fn self_update(check: bool) -> Result<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let latest = latest_version()?;
+    if !is_newer(&latest, current) {
+        println!("code-graph {} is up to date", current);
+        return Ok(());
+    }
+    println!(
+        "code-graph {} is available (installed: {})",
+        latest, current
+    );
+    if check {
+        return Ok(());
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["install", CRATE_NAME, "--locked", "--force"]);
+    let features = build_info().features;
+    if !features.is_empty() {
+        cmd.args(["--features", &features.join(",")]);
+    }
+    let status = cmd
+        .status()
+        .context("failed to run `cargo install`; is cargo on PATH?")?;
+    if !status.success() {
+        bail!("`cargo install {}` failed ({})", CRATE_NAME, status);
+    }
+    // Caches written by the old binary are rebuilt on first use if the format changed.
+    println!("updated code-graph {} -> {}", current, latest);
+    Ok(())
+}
+
+/// Latest version of the crate on crates.io, via `cargo search`.
+fn latest_version() -> Result<String> {
+    let output = Command::new("cargo")
+        .args(["search", CRATE_NAME, "--limit", "1"])
+        .output()
+        .context("failed to run `cargo search`; is cargo on PATH?")?;
+    if !output.status.success() {
+        bail!(
+            "`cargo search` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_search_output(&String::from_utf8_lossy(&output.stdout))
+        .with_context(|| format!("{} not found on crates.io", CRATE_NAME))
+}
+
+/// Extract the version from a `cargo search` line: `code-graph-cli = "3.0.1"    # ...`.
+fn parse_search_output(stdout: &str) -> Option<String> {
+    stdout.lines().find_map(|line| {
+        let (name, rest) = line.split_once('=')?;
+        if name.trim() != CRATE_NAME {
+            return None;
+        }
+        let version = rest.trim().strip_prefix('"')?;
+        Some(version[..version.find('"')?].to_string())
+    })
+}
+
+/// Whether `latest` is a higher `major.minor.patch` than `current`. Pre-release and build
+/// suffixes are ignored.
+fn is_newer(latest: &str, current: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.split(['-', '+'])
+            .next()
+            .unwrap_or(v)
+            .split('.')
+            .map(|n| n.parse().unwrap_or(0))
+            .collect()
+    };
+    parse(latest) > parse(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_output() {
+        let stdout = "code-graph-cli = \"3.2.0\"    # Code intelligence engine\n\
+                      ... and 2 crates more (use --limit N to see more)\n";
+        assert_eq!(parse_search_output(stdout), Some("3.2.0".to_string()));
+        assert_eq!(
+            parse_search_output("code-graph = \"0.1.0\"    # other\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("3.0.2", "3.0.1"));
+        assert!(is_newer("3.10.0", "3.9.9"));
+        assert!(!is_newer("3.0.1", "3.0.1"));
+        assert!(!is_newer("2.9.0", "3.0.1"));
+        assert!(!is_newer("3.0.1-rc.1", "3.0.1"));
+    }
+
+    #[test]
+    fn test_capabilities_list_commands_formats_and_config_keys() {
+        let caps = crate::cli::with_cli_stack(capabilities);
+        let names: Vec<&str> = caps.commands.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.contains(&"find"), "{names:?}");
+        assert!(names.contains(&"capabilities"), "{names:?}");
+        assert!(names.contains(&"self-update"), "{names:?}");
+        assert_eq!(caps.output_formats, vec!["compact", "table", "json"]);
+        assert_eq!(caps.export_formats, vec!["dot", "mermaid"]);
+        assert!(
+            caps.config_keys
+                .contains(&"impact.high_threshold".to_string())
+        );
+        assert!(caps.config_keys.contains(&"exclude".to_string()));
+    }
+
+    #[test]
+    fn test_build_info_lists_cache_version_and_languages() {
+        let info = build_info();
+        assert_eq!(info.cache_version, crate::cache::envelope::CACHE_VERSION);
+        assert!(info.languages.contains(&"rust"));
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    }
+}
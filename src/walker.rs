@@ -6,7 +6,10 @@ use crate::language::LanguageKind;
 
 /// Source file extensions that code-graph discovers.
 /// .rs files are discovered and counted but not parsed until Phase 8.
-const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "rs", "py", "go"];
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "js", "jsx", "rs", "py", "go", "proto", "sql", "astro", "zig", "sh", "bash",
+    "ipynb", "css", "scss",
+];
 
 /// Walk a project directory and collect source files.
 ///
@@ -82,7 +85,9 @@ pub fn walk_non_parsed_files(
 
         // INVERT the source extension filter: collect files that are NOT source files
         let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        if SOURCE_EXTENSIONS.contains(&ext) {
+        if SOURCE_EXTENSIONS.contains(&ext)
+            || crate::parser::dynamic::language_for_path(path).is_some()
+        {
             continue; // skip source files -- they are handled by walk_project
         }
 
@@ -92,6 +97,170 @@ pub fn walk_non_parsed_files(
     Ok(files)
 }
 
+/// One path seen by [`explain_walk`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WalkEntry {
+    pub path: PathBuf,
+    /// A skipped directory: nothing below it is walked.
+    pub is_dir: bool,
+    /// Why the path is not indexed; `None` for a file `walk_project` returns.
+    pub skipped: Option<String>,
+}
+
+/// Explain the outcome of [`walk_project`] for every path under `root`.
+///
+/// Indexed files are exactly the ones `walk_project` returns. Each skipped path carries
+/// the filter that excluded it: `node_modules`, a hidden name, the `.gitignore`/`.ignore`
+/// pattern matching it, a `config.exclude` pattern, its extension, or the language
+/// filter. Directories pruned by the first three are reported once, not descended into.
+pub fn explain_walk(
+    root: &Path,
+    config: &CodeGraphConfig,
+    allowed_languages: Option<&HashSet<LanguageKind>>,
+) -> anyhow::Result<Vec<WalkEntry>> {
+    let indexed: HashSet<PathBuf> = walk_project(root, config, false, allowed_languages)?
+        .into_iter()
+        .collect();
+    let compiled_excludes = compile_exclude_patterns(config);
+
+    let mut ignores: Vec<ignore::gitignore::Gitignore> = Vec::new();
+    let info_exclude = root.join(".git/info/exclude");
+    if info_exclude.is_file() {
+        ignores.push(ignore::gitignore::Gitignore::new(&info_exclude).0);
+    }
+    let mut out = Vec::new();
+    explain_dir(
+        root,
+        root,
+        &indexed,
+        &compiled_excludes,
+        allowed_languages,
+        &mut ignores,
+        &mut out,
+    );
+    Ok(out)
+}
+
+/// The ignore-file pattern deciding `path`, deepest ignore file first; `None` when no
+/// pattern matches or the deciding one is a `!` whitelist.
+fn ignore_rule(
+    ignores: &[ignore::gitignore::Gitignore],
+    root: &Path,
+    path: &Path,
+    is_dir: bool,
+) -> Option<String> {
+    for gitignore in ignores.iter().rev() {
+        match gitignore.matched(path, is_dir) {
+            ignore::Match::Ignore(glob) => {
+                let from = glob
+                    .from()
+                    .map(|f| f.strip_prefix(root).unwrap_or(f).display().to_string())
+                    .unwrap_or_default();
+                return Some(format!("ignored by `{}` ({})", glob.original(), from));
+            }
+            ignore::Match::Whitelist(_) => return None,
+            ignore::Match::None => {}
+        }
+    }
+    None
+}
+
+#[allow(clippy::too_many_arguments)]
+fn explain_dir(
+    root: &Path,
+    dir: &Path,
+    indexed: &HashSet<PathBuf>,
+    compiled_excludes: &[glob::Pattern],
+    allowed_languages: Option<&HashSet<LanguageKind>>,
+    ignores: &mut Vec<ignore::gitignore::Gitignore>,
+    out: &mut Vec<WalkEntry>,
+) {
+    let pushed = [".gitignore", ".ignore"]
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|file| file.is_file())
+        .map(|file| ignores.push(ignore::gitignore::Gitignore::new(file).0))
+        .count();
+
+    let mut entries: Vec<(PathBuf, bool)> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| {
+            (
+                e.path(),
+                e.file_type().map(|ft| ft.is_dir()).unwrap_or(false),
+            )
+        })
+        .collect();
+    entries.sort();
+
+    for (path, is_dir) in entries {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name == ".git" {
+            continue;
+        }
+        let pruned = if name == "node_modules" {
+            Some("node_modules is always excluded".to_string())
+        } else if name.starts_with('.') {
+            Some("hidden".to_string())
+        } else {
+            ignore_rule(ignores, root, &path, is_dir)
+        };
+
+        if is_dir {
+            match pruned {
+                Some(reason) => out.push(WalkEntry {
+                    path,
+                    is_dir: true,
+                    skipped: Some(reason),
+                }),
+                None => explain_dir(
+                    root,
+                    &path,
+                    indexed,
+                    compiled_excludes,
+                    allowed_languages,
+                    ignores,
+                    out,
+                ),
+            }
+            continue;
+        }
+
+        let skipped = if indexed.contains(&path) {
+            None
+        } else if let Some(reason) = pruned {
+            Some(reason)
+        } else if let Some(pattern) = matching_exclude_pattern(&path, compiled_excludes) {
+            Some(format!("excluded by config pattern `{}`", pattern.as_str()))
+        } else {
+            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            if !SOURCE_EXTENSIONS.contains(&ext)
+                && crate::parser::dynamic::language_for_path(&path).is_none()
+            {
+                Some(if ext.is_empty() {
+                    "no source extension".to_string()
+                } else {
+                    format!("unsupported extension `.{}`", ext)
+                })
+            } else if allowed_languages.is_some() {
+                Some("filtered out by --language".to_string())
+            } else {
+                // A global gitignore or one above the project root.
+                Some("ignored by an ignore file outside the project".to_string())
+            }
+        };
+        out.push(WalkEntry {
+            path,
+            is_dir: false,
+            skipped,
+        });
+    }
+
+    ignores.truncate(ignores.len() - pushed);
+}
+
 /// Collect source files from a single directory tree using the `ignore` crate.
 fn collect_files(
     root: &Path,
@@ -139,7 +308,9 @@ fn collect_files(
 
         // Filter by source extension.
         let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        if !SOURCE_EXTENSIONS.contains(&ext) {
+        if !SOURCE_EXTENSIONS.contains(&ext)
+            && crate::parser::dynamic::language_for_path(path).is_none()
+        {
             continue;
         }
 
@@ -183,27 +354,30 @@ fn compile_exclude_patterns(config: &CodeGraphConfig) -> Vec<glob::Pattern> {
 
 /// Returns true if `path` matches any pre-compiled exclusion pattern.
 fn is_excluded_by_patterns(path: &Path, compiled: &[glob::Pattern]) -> bool {
+    matching_exclude_pattern(path, compiled).is_some()
+}
+
+/// The first pre-compiled exclusion pattern matching `path`.
+fn matching_exclude_pattern<'a>(
+    path: &Path,
+    compiled: &'a [glob::Pattern],
+) -> Option<&'a glob::Pattern> {
     if compiled.is_empty() {
-        return false;
+        return None;
     }
 
     let path_str = path.to_string_lossy();
 
-    for pattern in compiled {
-        if pattern.matches(&path_str) {
-            return true;
-        }
+    compiled.iter().find(|pattern| {
         // Also check if any component matches the pattern directly.
-        for component in path.components() {
-            if let Some(s) = component.as_os_str().to_str()
-                && pattern.matches(s)
-            {
-                return true;
-            }
-        }
-    }
-
-    false
+        pattern.matches(&path_str)
+            || path.components().any(|component| {
+                component
+                    .as_os_str()
+                    .to_str()
+                    .is_some_and(|s| pattern.matches(s))
+            })
+    })
 }
 
 #[cfg(test)]
@@ -267,6 +441,13 @@ mod tests {
         let config = CodeGraphConfig {
             exclude: Some(vec!["*.toml".to_string()]),
             impact: Default::default(),
+            snapshot: Default::default(),
+            grammar: Vec::new(),
+            category: Vec::new(),
+            lint_imports: Default::default(),
+            rules: Vec::new(),
+            symbols: Default::default(),
+            string_refs: Default::default(),
         };
 
         let files = walk_non_parsed_files(dir.path(), &config).unwrap();
@@ -331,4 +512,72 @@ mod tests {
             "should NOT find non-source files"
         );
     }
+
+    #[test]
+    fn test_explain_walk_reports_skip_reasons() {
+        let dir = tmp();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n*.gen.ts\n").unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("src/api.gen.ts"), "export {}").unwrap();
+        fs::write(root.join("src/fixture.ts"), "export {}").unwrap();
+        fs::write(root.join("README.md"), "# Hello").unwrap();
+        fs::write(root.join("target/debug/out.rs"), "").unwrap();
+        fs::write(root.join("node_modules/pkg/index.js"), "").unwrap();
+
+        let config = CodeGraphConfig {
+            exclude: Some(vec!["fixture.*".to_string()]),
+            ..Default::default()
+        };
+        let entries = explain_walk(root, &config, None).unwrap();
+        let summary: Vec<(String, bool, Option<&str>)> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.path
+                        .strip_prefix(root)
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    e.is_dir,
+                    e.skipped.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (".gitignore".to_string(), false, Some("hidden")),
+                (
+                    "README.md".to_string(),
+                    false,
+                    Some("unsupported extension `.md`")
+                ),
+                (
+                    "node_modules".to_string(),
+                    true,
+                    Some("node_modules is always excluded")
+                ),
+                (
+                    "src/api.gen.ts".to_string(),
+                    false,
+                    Some("ignored by `*.gen.ts` (.gitignore)")
+                ),
+                (
+                    "src/fixture.ts".to_string(),
+                    false,
+                    Some("excluded by config pattern `fixture.*`")
+                ),
+                ("src/main.rs".to_string(), false, None),
+                (
+                    "target".to_string(),
+                    true,
+                    Some("ignored by `target/` (.gitignore)")
+                ),
+            ]
+        );
+    }
 }
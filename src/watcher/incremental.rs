@@ -32,10 +32,12 @@ pub fn handle_file_event(graph: &mut CodeGraph, event: &WatchEvent, project_root
     match event {
         WatchEvent::Modified(path) => {
             handle_modified(graph, path, project_root);
+            advance_generation(graph, path);
             true
         }
         WatchEvent::Deleted(path) => {
             handle_deleted(graph, path);
+            advance_generation(graph, path);
             true
         }
         WatchEvent::ConfigChanged => {
@@ -49,9 +51,58 @@ pub fn handle_file_event(graph: &mut CodeGraph, event: &WatchEvent, project_root
     }
 }
 
+/// Bump the graph generation after an incremental update of `path`, carrying forward
+/// the cached impact results the change cannot have affected.
+fn advance_generation(graph: &mut CodeGraph, path: &Path) {
+    let dependencies = crate::query::impact::file_dependency_paths(graph, path);
+    let previous = graph.generation;
+    graph.generation += 1;
+    let generation = graph.generation;
+    graph
+        .impact_cache
+        .get_mut()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .apply_file_change(path, &dependencies, previous, generation);
+}
+
 /// Handle a modified or newly created file.
 fn handle_modified(graph: &mut CodeGraph, path: &Path, project_root: &Path) {
-    // 1. Remove old entry if it exists
+    // Markdown docs are not parsed: re-add the doc node and re-link its mentions.
+    if crate::resolver::docs_resolver::is_markdown(path) {
+        graph.remove_file_from_graph(path);
+        if path.exists() {
+            let kind = crate::graph::node::classify_file_kind(path);
+            let doc_idx = graph.add_non_parsed_file(path.to_path_buf(), kind);
+            crate::resolver::docs_resolver::link_doc_file(graph, doc_idx, project_root);
+        }
+        return;
+    }
+
+    // OpenAPI specs are not parsed either: re-add the spec node with fresh endpoints.
+    if crate::resolver::openapi_resolver::is_openapi_spec(path) {
+        graph.remove_file_from_graph(path);
+        if path.exists() {
+            let kind = crate::graph::node::classify_file_kind(path);
+            let spec_idx = graph.add_non_parsed_file(path.to_path_buf(), kind);
+            let routes = crate::resolver::openapi_resolver::RouteIndex::build(graph);
+            crate::resolver::openapi_resolver::link_spec_file(graph, spec_idx, &routes);
+        }
+        return;
+    }
+
+    // 1. Remove old entry if it exists. Files referencing this file's SQL tables lose
+    // their ReferencesTable edges with the old symbols, so remember them for step 6c.
+    // Docs mentioning the file or its symbols lose their DocumentedBy edges (step 6d).
+    // A Rust file keeps its crate and module (step 3).
+    let table_referrers = table_referrers_of(graph, path);
+    let documenting_docs = docs_documenting(graph, path);
+    let rust_placement = graph.file_index.get(path).and_then(|&idx| {
+        let GraphNode::File(fi) = &graph.graph[idx] else {
+            return None;
+        };
+        let module = graph.module_of_file(idx).map(|m| m.path.clone());
+        Some((fi.crate_name.clone(), module))
+    });
     graph.remove_file_from_graph(path);
 
     // 2. Read and parse the file
@@ -67,16 +118,37 @@ fn handle_modified(graph: &mut CodeGraph, path: &Path, project_root: &Path) {
         "rs" => "rust",
         "py" => "python",
         "go" => "go",
-        _ => return,
+        "proto" => "proto",
+        "sql" => "sql",
+        "astro" => "astro",
+        "zig" => "zig",
+        "sh" | "bash" => "shell",
+        "css" | "scss" => "css",
+        "ipynb" => crate::parser::notebook::kernel_language(&source),
+        _ => match crate::parser::dynamic::language_for_path(path) {
+            Some(language) => language,
+            None => return,
+        },
     };
 
-    let result = match parser::parse_file(path, &source) {
+    let mut result = match parser::parse_file(path, &source) {
         Ok(r) => r,
         Err(_) => return, // parse error — skip
     };
+    result.line_count = parser::count_lines(&source);
+    result.content_hash = Some(parser::content_hash(&source));
 
     // 3. Add file and symbols to graph
     let file_idx = graph.add_file(path.to_path_buf(), language_str);
+    graph.set_file_source(file_idx, result.line_count, result.content_hash);
+    if let Some((crate_name, module)) = rust_placement {
+        if let GraphNode::File(fi) = &mut graph.graph[file_idx] {
+            fi.crate_name = crate_name;
+        }
+        if let Some(&module_idx) = module.and_then(|m| graph.module_index.get(&m)) {
+            graph.set_file_module(file_idx, module_idx);
+        }
+    }
     for (symbol, children) in &result.symbols {
         let sym_idx = graph.add_symbol(file_idx, symbol.clone());
         for child in children {
@@ -124,6 +196,37 @@ fn handle_modified(graph: &mut CodeGraph, path: &Path, project_root: &Path) {
         let mut parse_results = HashMap::new();
         parse_results.insert(path.to_path_buf(), result);
         crate::resolver::resolve_all(graph, project_root, &parse_results, false);
+    } else if language_str == "proto" {
+        // 4d. Proto path: resolve_all Step 9 resolves this file's proto imports and
+        // re-links generated stubs and their importers (their edges died with the old node).
+        let mut parse_results = HashMap::new();
+        parse_results.insert(path.to_path_buf(), result);
+        crate::resolver::resolve_all(graph, project_root, &parse_results, false);
+    } else if language_str == "sql" {
+        // 4e. SQL path: resolve_all Step 10 links this file's table references.
+        let mut parse_results = HashMap::new();
+        parse_results.insert(path.to_path_buf(), result);
+        crate::resolver::resolve_all(graph, project_root, &parse_results, false);
+    } else if language_str == "zig" {
+        // 4f. Zig path: resolve_all Step 11 resolves this file's @imports.
+        let mut parse_results = HashMap::new();
+        parse_results.insert(path.to_path_buf(), result);
+        crate::resolver::resolve_all(graph, project_root, &parse_results, false);
+    } else if language_str == "shell" {
+        // 4g. Shell path: resolve_all Step 12 resolves this file's sources and calls.
+        let mut parse_results = HashMap::new();
+        parse_results.insert(path.to_path_buf(), result);
+        crate::resolver::resolve_all(graph, project_root, &parse_results, false);
+    } else if language_str == "css" {
+        // 4h. Stylesheet path: resolve_all Step 13 resolves this file's @import/@use.
+        let mut parse_results = HashMap::new();
+        parse_results.insert(path.to_path_buf(), result);
+        crate::resolver::resolve_all(graph, project_root, &parse_results, false);
+    } else if crate::parser::dynamic::language_for_path(path).is_some() {
+        // 4i. External grammar path: resolve_all Step 14 resolves this file's imports.
+        let mut parse_results = HashMap::new();
+        parse_results.insert(path.to_path_buf(), result);
+        crate::resolver::resolve_all(graph, project_root, &parse_results, false);
     } else {
         // 4b. TS/JS path: resolve imports using TS resolver, wire relationships.
         let workspace_map = discover_workspace_packages(project_root);
@@ -137,7 +240,14 @@ fn handle_modified(graph: &mut CodeGraph, path: &Path, project_root: &Path) {
             match outcome {
                 ResolutionOutcome::Resolved(target_path) => {
                     if let Some(&target_idx) = graph.file_index.get(&target_path) {
-                        graph.add_resolved_import(file_idx, target_idx, specifier);
+                        graph.add_import_at(
+                            file_idx,
+                            target_idx,
+                            specifier,
+                            import.is_type_only,
+                            import.line,
+                            import.col,
+                        );
                     }
                 }
                 ResolutionOutcome::BuiltinModule(_) => {
@@ -157,18 +267,129 @@ fn handle_modified(graph: &mut CodeGraph, path: &Path, project_root: &Path) {
         // 5. Wire symbol relationships for this file only
         wire_relationships_for_file(graph, &result.relationships, file_idx);
 
+        // 5b. Link SQL string literals to the tables they query.
+        crate::resolver::sql_resolver::link_table_refs(graph, file_idx, &result.relationships);
+
         // 6. Check if existing unresolved imports now resolve to this file
         fix_unresolved_pointing_to(graph, path, project_root);
+
+        // 6b. Re-link this file to any proto it imports generated stubs for.
+        let mut parse_results = HashMap::new();
+        parse_results.insert(path.to_path_buf(), result);
+        crate::resolver::proto_resolver::link_generated_code(graph, &parse_results);
+
+        // Re-record this file's export aliases, then bind its named imports to the symbols
+        // they name.
+        crate::resolver::barrel::resolve_reexport_aliases(graph, &parse_results, false);
+        crate::resolver::bindings::resolve_import_bindings(graph, &parse_results, false);
+    }
+
+    // 6c. Re-link files that referenced this file's tables to the re-added symbols.
+    for (referrer_idx, relationships) in table_referrers {
+        crate::resolver::sql_resolver::link_table_refs(graph, referrer_idx, &relationships);
+    }
+
+    // 6d. Re-link docs that mentioned this file or its symbols.
+    for doc_idx in documenting_docs {
+        crate::resolver::docs_resolver::link_doc_file(graph, doc_idx, project_root);
+    }
+
+    // 6e. Re-link OpenAPI endpoints: this file's handlers came back as fresh symbol nodes,
+    // and it may register new routes.
+    if matches!(language_str, "typescript" | "tsx" | "javascript") {
+        crate::resolver::openapi_resolver::link_openapi(graph);
     }
 
     // 7. Enrich decorator frameworks and add HasDecorator self-edges for re-parsed file
     crate::query::decorators::enrich_decorator_frameworks(graph);
     crate::query::decorators::add_has_decorator_edges(graph);
 
-    // 8. Rebuild BM25 index so new/changed symbols are searchable
+    // 7a. Re-link overrides: the re-parsed file's classes and methods are fresh nodes.
+    crate::resolver::overrides::link_overrides(graph);
+
+    // 7b. Re-classify test files: the re-parsed file lost its `Tests` edges and flag.
+    crate::resolver::tests_resolver::link_tests(graph, project_root);
+
+    // 7c. Re-link Cargo declarations: re-parsing a crate root drops its outgoing edges.
+    crate::resolver::cargo_deps::link_cargo_dependencies(graph, project_root);
+
+    // 8. Tag the re-parsed file and its symbols with the project's `[[category]]` rules,
+    //    and unindex the symbols `[symbols] exclude` filters out.
+    let config = crate::config::CodeGraphConfig::check(project_root).config;
+    crate::query::categories::apply_categories(graph, project_root, &config.category);
+    crate::query::exclusions::apply_symbol_exclusions(graph, &config.symbols.exclude);
+
+    // 9. Rebuild BM25 index so new/changed symbols are searchable
     graph.rebuild_bm25_index();
 }
 
+/// Collect the files with `ReferencesTable` edges into the tables/views defined in `path`,
+/// as `TableReference` relationships that can be re-linked after the file is re-added.
+fn table_referrers_of(
+    graph: &CodeGraph,
+    path: &Path,
+) -> Vec<(
+    petgraph::stable_graph::NodeIndex,
+    Vec<crate::parser::relationships::RelationshipInfo>,
+)> {
+    use crate::parser::relationships::{RelationshipInfo, RelationshipKind};
+
+    let Some(&file_idx) = graph.file_index.get(path) else {
+        return Vec::new();
+    };
+    let mut referrers: HashMap<petgraph::stable_graph::NodeIndex, Vec<RelationshipInfo>> =
+        HashMap::new();
+    for sym_edge in graph.graph.edges(file_idx) {
+        if !matches!(sym_edge.weight(), EdgeKind::Contains) {
+            continue;
+        }
+        let GraphNode::Symbol(ref info) = graph.graph[sym_edge.target()] else {
+            continue;
+        };
+        for e in graph
+            .graph
+            .edges_directed(sym_edge.target(), petgraph::Direction::Incoming)
+        {
+            if matches!(e.weight(), EdgeKind::ReferencesTable) {
+                referrers
+                    .entry(e.source())
+                    .or_default()
+                    .push(RelationshipInfo {
+                        from_name: None,
+                        to_name: info.name.clone(),
+                        kind: RelationshipKind::TableReference,
+                        line: info.line,
+                    });
+            }
+        }
+    }
+    referrers.into_iter().collect()
+}
+
+/// Collect the Markdown docs with `DocumentedBy` edges from `path` or its symbols.
+fn docs_documenting(graph: &CodeGraph, path: &Path) -> Vec<petgraph::stable_graph::NodeIndex> {
+    let Some(&file_idx) = graph.file_index.get(path) else {
+        return Vec::new();
+    };
+    let mut subjects = vec![file_idx];
+    subjects.extend(
+        graph
+            .graph
+            .edges(file_idx)
+            .filter(|e| matches!(e.weight(), EdgeKind::Contains))
+            .map(|e| e.target()),
+    );
+    let mut docs: Vec<petgraph::stable_graph::NodeIndex> = subjects
+        .into_iter()
+        .flat_map(|s| graph.graph.edges(s))
+        .filter(|e| matches!(e.weight(), EdgeKind::DocumentedBy))
+        .map(|e| e.target())
+        .collect();
+    docs.sort();
+    docs.dedup();
+    docs
+}
+
 /// Handle a deleted file.
 fn handle_deleted(graph: &mut CodeGraph, path: &Path) {
     // Find files that had ResolvedImport edges pointing to this file
@@ -183,7 +404,7 @@ fn handle_deleted(graph: &mut CodeGraph, path: &Path) {
         .graph
         .edges_directed(file_idx, petgraph::Direction::Incoming)
         .filter_map(|e| {
-            if let EdgeKind::ResolvedImport { specifier } = e.weight() {
+            if let EdgeKind::ResolvedImport { specifier, .. } = e.weight() {
                 Some((e.source(), specifier.clone()))
             } else {
                 None
@@ -203,7 +424,7 @@ fn handle_deleted(graph: &mut CodeGraph, path: &Path) {
     graph.rebuild_bm25_index();
 }
 
-/// Wire symbol relationships (Extends, Implements, Calls) for symbols in a single file.
+/// Wire symbol relationships (Extends, Implements, Calls, Renders) for symbols in a single file.
 /// Adapted from resolver::resolve_all Step 5 but scoped to one file.
 fn wire_relationships_for_file(
     graph: &mut CodeGraph,
@@ -271,15 +492,22 @@ fn wire_relationships_for_file(
             RelationshipKind::Calls
             | RelationshipKind::MethodCall
             | RelationshipKind::TypeReference => {
-                let to_candidates = match graph.symbol_index.get(&rel.to_name) {
+                let to_candidates = match graph.symbols_named(&rel.to_name) {
                     Some(c) if !c.is_empty() => c.clone(),
                     _ => continue,
                 };
 
                 if to_candidates.len() == 1 {
-                    graph.add_calls_edge(file_idx, to_candidates[0]);
+                    graph.add_call_site(file_idx, to_candidates[0], rel.line);
                 }
             }
+
+            // Linked by sql_resolver::link_table_refs in handle_modified.
+            RelationshipKind::TableReference => {}
+
+            RelationshipKind::Renders => {
+                crate::resolver::wire_render(graph, file_idx, rel);
+            }
         }
     }
 }
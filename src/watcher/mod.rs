@@ -1,9 +1,10 @@
 pub mod event;
 pub mod incremental;
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc as std_mpsc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use notify::RecursiveMode;
@@ -20,7 +21,11 @@ pub struct WatcherHandle {
 }
 
 /// File extensions we care about for incremental re-index.
-const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "rs", "py", "go"];
+/// Markdown docs are not parsed but are re-linked to the symbols they mention.
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "js", "jsx", "rs", "py", "go", "proto", "sql", "astro", "zig", "sh", "bash",
+    "ipynb", "css", "scss", "md", "markdown",
+];
 
 /// File basenames that trigger a full re-index.
 /// TypeScript/JS config files and Rust crate root files are all treated as full re-index triggers.
@@ -37,6 +42,7 @@ const FULL_REINDEX_FILES: &[&str] = &[
     "__init__.py",
     "go.mod",
     "go.work",
+    "build.zig",
 ];
 
 /// Rust crate root / module-tree files (subset of FULL_REINDEX_FILES).
@@ -92,12 +98,16 @@ pub fn start_watcher(
     let root = watch_root.to_path_buf();
     let bridge_thread = std::thread::spawn(move || {
         let mut consecutive_errors: usize = 0;
+        let mut seen: HashMap<PathBuf, (SystemTime, u64)> = HashMap::new();
         while let Ok(result) = notify_rx.recv() {
             match result {
                 Ok(events) => {
                     consecutive_errors = 0;
                     for debounced_event in events {
                         let path = debounced_event.path;
+                        if content_unchanged(&mut seen, &path) {
+                            continue;
+                        }
                         if let Some(watch_event) = classify_event(&path, &root, &gitignore)
                             && event_tx.send(watch_event).is_err()
                         {
@@ -128,6 +138,23 @@ pub fn start_watcher(
     ))
 }
 
+/// Returns true if `path` still has the modification time and size it had when last seen.
+///
+/// The debouncer reports read-only access (open/close) like any other event, so re-parsing
+/// a file — or `git add` reading it — would otherwise re-trigger its own update forever.
+/// Paths without metadata (deleted files) are never considered unchanged.
+fn content_unchanged(seen: &mut HashMap<PathBuf, (SystemTime, u64)>, path: &Path) -> bool {
+    let Ok(meta) = std::fs::metadata(path) else {
+        seen.remove(path);
+        return false;
+    };
+    let Ok(mtime) = meta.modified() else {
+        return false;
+    };
+    let stamp = (mtime, meta.len());
+    seen.insert(path.to_path_buf(), stamp) == Some(stamp)
+}
+
 /// Classify a filesystem event path into a WatchEvent, or None if it should be ignored.
 ///
 /// Filtering order:
@@ -166,9 +193,12 @@ fn classify_event(path: &Path, _project_root: &Path, gitignore: &Gitignore) -> O
         }
     }
 
-    // Check if it's a source file we care about
+    // Check if it's a source file we care about (or an OpenAPI spec to re-link)
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-    if !SOURCE_EXTENSIONS.contains(&ext) {
+    if !SOURCE_EXTENSIONS.contains(&ext)
+        && crate::parser::dynamic::language_for_path(path).is_none()
+        && !crate::resolver::openapi_resolver::is_openapi_spec(path)
+    {
         return None;
     }
 
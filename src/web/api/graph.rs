@@ -138,8 +138,15 @@ fn node_color(kind: &str) -> &'static str {
         "const" => "#96a8b8",       // slightly brighter slate
         "static" => "#7d8fa0",      // darker slate
         "macro" => "#a07ab0",       // muted mauve
+        "message" => "#5ba3f5",     // lighter blue (same as struct)
+        "service" => "#26a87e",     // darker teal (same as trait)
+        "rpc" => "#9b7fe8",         // soft purple (same as impl_method)
+        "table" => "#5ba3f5",       // lighter blue (same as struct)
+        "view" => "#7a9fd4",        // slate blue (same as type)
+        "endpoint" => "#2dba8c",    // teal (same as interface)
         "folder" => "#6366f1",      // indigo — folder hierarchy
         "module" => "#5e8bc0",      // module blue
+        "namespace" => "#5e8bc0",   // module blue (same as module)
         "file" => "#6b6090",        // purple-tinted grey
         _ => "#6b6090",             // fallback same as file
     }
@@ -172,13 +179,20 @@ fn language_color(lang: &str) -> &'static str {
 
 fn edge_color(edge_type: &str) -> &'static str {
     match edge_type {
-        "Imports" | "ResolvedImport" => "#1d4ed8", // muted blue
-        "Calls" => "#7c3aed",                      // muted violet
-        "Extends" => "#c2410c",                    // muted orange
-        "Contains" => "#2d5a3d",                   // muted green
-        "Implements" => "#be185d",                 // muted pink
-        "HasDecorator" => "#b45309",               // muted amber
-        _ => "#4a4060",                            // muted purple-grey
+        "Imports" | "ResolvedImport" | "ImportsSymbol" => "#1d4ed8", // muted blue
+        "Calls" => "#7c3aed",                                        // muted violet
+        "Extends" => "#c2410c",                                      // muted orange
+        "Contains" => "#2d5a3d",                                     // muted green
+        "Implements" => "#be185d",                                   // muted pink
+        "Overrides" => "#9d174d",                                    // muted magenta
+        "HasDecorator" => "#b45309",                                 // muted amber
+        "ReferencesTable" => "#0e7490",                              // muted cyan
+        "DocumentedBy" => "#6b7280",                                 // muted grey
+        "StringRef" => "#78716c",                                    // muted stone
+        "HandledBy" => "#15803d",                                    // muted green
+        "Tests" => "#a16207",                                        // muted yellow
+        "DeclaresDependency" => "#9f1239",                           // muted rose
+        _ => "#4a4060",                                              // muted purple-grey
     }
 }
 
@@ -202,7 +216,7 @@ fn edge_type_str(edge: &EdgeKind) -> &'static str {
         EdgeKind::SideEffectImport { .. } => "SideEffectImport",
         EdgeKind::DotImport { .. } => "DotImport",
         EdgeKind::Contains => "Contains",
-        EdgeKind::Calls => "Calls",
+        EdgeKind::Calls { .. } => "Calls",
         EdgeKind::Extends => "Extends",
         EdgeKind::Implements => "Implements",
         EdgeKind::ChildOf => "ChildOf",
@@ -211,6 +225,16 @@ fn edge_type_str(edge: &EdgeKind) -> &'static str {
         EdgeKind::ReExport { .. } => "ReExport",
         EdgeKind::RustImport { .. } => "RustImport",
         EdgeKind::Embeds => "Embeds",
+        EdgeKind::ReferencesTable => "ReferencesTable",
+        EdgeKind::DocumentedBy => "DocumentedBy",
+        EdgeKind::HandledBy => "HandledBy",
+        EdgeKind::ImportsSymbol { .. } => "ImportsSymbol",
+        EdgeKind::Tests => "Tests",
+        EdgeKind::Renders => "Renders",
+        EdgeKind::ExportsAs { .. } => "ExportsAs",
+        EdgeKind::DeclaresDependency { .. } => "DeclaresDependency",
+        EdgeKind::Overrides => "Overrides",
+        EdgeKind::StringRef { .. } => "StringRef",
     }
 }
 
@@ -225,14 +249,11 @@ pub async fn handler(
     let graph = state.graph.read().await;
 
     // Pre-compute which files are in circular dependency cycles.
-    let cycles = circular::find_circular(&graph, &state.project_root);
+    // Only SCC membership is needed here, so enumerate a single cycle per group.
+    let cycles = circular::find_circular(&graph, &state.project_root, 1, false);
     let mut circular_files: HashSet<PathBuf> = HashSet::new();
-    for cycle in &cycles {
-        // The cycle closes by repeating the first file -- skip the last duplicate.
-        let unique_len = cycle.files.len().saturating_sub(1);
-        for file in &cycle.files[..unique_len] {
-            circular_files.insert(file.clone());
-        }
+    for group in &cycles {
+        circular_files.extend(group.files.iter().cloned());
     }
 
     let granularity = params.granularity.as_str();
@@ -766,11 +787,13 @@ fn build_symbol_graph(
         let include = matches!(
             edge_ref.weight(),
             EdgeKind::Contains
-                | EdgeKind::Calls
+                | EdgeKind::Calls { .. }
                 | EdgeKind::Extends
                 | EdgeKind::Implements
+                | EdgeKind::Overrides
                 | EdgeKind::ChildOf
                 | EdgeKind::HasDecorator { .. }
+                | EdgeKind::ReferencesTable
         );
         if !include {
             continue;
@@ -1164,6 +1187,12 @@ mod tests {
             "const",
             "static",
             "macro",
+            "message",
+            "service",
+            "rpc",
+            "table",
+            "view",
+            "endpoint",
             "module",
             "file",
         ];
@@ -1228,13 +1257,10 @@ mod tests {
         graph.add_resolved_import(b_idx, a_idx, "./a");
 
         // Pre-compute circular files
-        let cycles = circular::find_circular(&graph, &root);
+        let cycles = circular::find_circular(&graph, &root, 1, false);
         let mut circular_files: HashSet<PathBuf> = HashSet::new();
-        for cycle in &cycles {
-            let unique_len = cycle.files.len().saturating_sub(1);
-            for file in &cycle.files[..unique_len] {
-                circular_files.insert(file.clone());
-            }
+        for group in &cycles {
+            circular_files.extend(group.files.iter().cloned());
         }
 
         assert!(
@@ -59,7 +59,7 @@ pub async fn handler(
     State(state): State<AppState>,
 ) -> Result<Json<StatsResponse>, (StatusCode, String)> {
     let graph = state.graph.read().await;
-    let stats = project_stats(&graph);
+    let stats = project_stats(&graph, &state.project_root);
 
     // Build per-language breakdown from file nodes.
     let mut lang_files: HashMap<String, usize> = HashMap::new();
@@ -157,7 +157,7 @@ mod tests {
             },
         );
 
-        let stats = project_stats(&graph);
+        let stats = project_stats(&graph, std::path::Path::new("/tmp"));
         assert_eq!(stats.file_count, 1, "one file expected");
         assert_eq!(stats.symbol_count, 2, "two symbols expected");
     }
@@ -57,6 +57,26 @@ struct WebAssets;
 
 const GRAPH_UPDATED_MSG: &str = r#"{"type":"graph_updated"}"#;
 
+/// Build the `graph_updated` push message for an incremental update of a single file,
+/// carrying the symbols the edit added, removed, or modified.
+fn graph_updated_message(file: &str, changes: &crate::query::diff::GraphDiff) -> String {
+    let names = |syms: &[(String, String)]| -> Vec<String> {
+        syms.iter().map(|(_, name)| name.clone()).collect()
+    };
+    serde_json::json!({
+        "type": "graph_updated",
+        "file": file,
+        "added": names(&changes.added_symbols),
+        "removed": names(&changes.removed_symbols),
+        "modified": changes
+            .modified_symbols
+            .iter()
+            .map(|c| serde_json::json!({"name": c.name, "changes": c.changes}))
+            .collect::<Vec<_>>(),
+    })
+    .to_string()
+}
+
 /// Middleware to validate the auth token on API routes.
 async fn auth_middleware(
     axum::extract::State(state): axum::extract::State<AppState>,
@@ -239,7 +259,9 @@ async fn serve_asset(uri: axum::http::Uri) -> impl IntoResponse {
 ///    - Creates session store (capacity 100).
 ///    - Resolves auth state (Claude or Ollama based on `ollama` flag).
 /// 4. Spawns a background watcher task that receives file events, updates the graph,
-///    and broadcasts `{"type":"graph_updated"}` to connected WebSocket clients.
+///    and broadcasts `{"type":"graph_updated"}` to connected WebSocket clients. For a
+///    modified file the message also names the file and the symbols that were added,
+///    removed, or modified.
 /// 5. When compiled with `rag` feature: after graph updates, re-embeds changed file's symbols.
 /// 6. Serves on `127.0.0.1:{port}` (localhost only).
 ///
@@ -382,14 +404,36 @@ pub async fn serve(root: PathBuf, port: u16, ollama: bool) -> anyhow::Result<()>
                         _ => None,
                     };
 
-                    {
+                    let message = {
                         let mut graph = watcher_graph.write().await;
-                        crate::watcher::incremental::handle_file_event(
-                            &mut graph,
-                            &event,
-                            &watcher_root,
-                        );
-                    }
+                        match &event {
+                            crate::watcher::event::WatchEvent::Modified(p)
+                            | crate::watcher::event::WatchEvent::Deleted(p) => {
+                                let before =
+                                    crate::query::diff::file_snapshot(&graph, &watcher_root, p);
+                                crate::watcher::incremental::handle_file_event(
+                                    &mut graph,
+                                    &event,
+                                    &watcher_root,
+                                );
+                                let after =
+                                    crate::query::diff::file_snapshot(&graph, &watcher_root, p);
+                                let rel = p.strip_prefix(&watcher_root).unwrap_or(p);
+                                graph_updated_message(
+                                    &rel.to_string_lossy(),
+                                    &crate::query::diff::diff_snapshots(&before, &after),
+                                )
+                            }
+                            _ => {
+                                crate::watcher::incremental::handle_file_event(
+                                    &mut graph,
+                                    &event,
+                                    &watcher_root,
+                                );
+                                GRAPH_UPDATED_MSG.to_string()
+                            }
+                        }
+                    };
 
                     // Re-embed changed file's symbols after graph update.
                     #[cfg(feature = "rag")]
@@ -418,7 +462,7 @@ pub async fn serve(root: PathBuf, port: u16, ollama: bool) -> anyhow::Result<()>
                     }
 
                     // Ignore send errors — no clients connected is fine.
-                    let _ = watcher_tx.send(GRAPH_UPDATED_MSG.to_string());
+                    let _ = watcher_tx.send(message);
                 }
             });
         }
@@ -596,6 +640,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_graph_updated_message_carries_symbol_changes() {
+        let changes = crate::query::diff::GraphDiff {
+            added_files: vec![],
+            removed_files: vec![],
+            renamed_files: vec![],
+            added_symbols: vec![("src/a.ts".into(), "greet".into())],
+            removed_symbols: vec![("src/a.ts".into(), "hello".into())],
+            modified_symbols: vec![crate::query::diff::SymbolChange {
+                file: "src/a.ts".into(),
+                name: "main".into(),
+                changes: vec!["line 1 → 4".into()],
+            }],
+        };
+        let msg: serde_json::Value =
+            serde_json::from_str(&graph_updated_message("src/a.ts", &changes)).unwrap();
+        assert_eq!(msg["type"], "graph_updated");
+        assert_eq!(msg["file"], "src/a.ts");
+        assert_eq!(msg["added"], serde_json::json!(["greet"]));
+        assert_eq!(msg["removed"], serde_json::json!(["hello"]));
+        assert_eq!(msg["modified"][0]["name"], "main");
+        assert_eq!(msg["modified"][0]["changes"][0], "line 1 → 4");
+    }
+
     /// Ensure none of the web module source files (outside tests) contain MCP references.
     /// This is a static assertion guarding against accidental MCP re-introduction.
     /// Forbidden terms are constructed at runtime to avoid false positives from this
@@ -154,6 +154,27 @@ fn test_find_regex_pattern() {
     );
 }
 
+/// test_find_kind_without_pattern — `--kind` alone lists every symbol of that kind.
+#[test]
+fn test_find_kind_without_pattern() {
+    let root = project_root();
+    let path = root.to_str().unwrap();
+    let stdout = run_success(&["find", "*", path, "--kind", "struct"]);
+    assert!(
+        stdout.contains("def CodeGraph "),
+        "expected the CodeGraph struct\nstdout: {}",
+        stdout
+    );
+    assert!(
+        stdout
+            .lines()
+            .filter(|l| l.starts_with("def"))
+            .all(|l| l.contains(" struct")),
+        "only structs expected\nstdout: {}",
+        stdout
+    );
+}
+
 /// test_find_nonexistent_symbol — finding a symbol that doesn't exist exits non-zero.
 #[test]
 fn test_find_nonexistent_symbol() {
@@ -167,6 +188,107 @@ fn test_find_nonexistent_symbol() {
     );
 }
 
+#[test]
+fn test_refs_reports_jsx_render_sites() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src/ui")).unwrap();
+    fs::write(root.join("src/ui/index.ts"), "export * from './Button';\n").unwrap();
+    fs::write(
+        root.join("src/ui/Button.tsx"),
+        "export function Button() {\n  return <button />;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/App.tsx"),
+        "import { Button } from './ui';\n\nexport function App() {\n  return <Button />;\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["refs", "Button", path]);
+    assert!(stdout.contains("ref src/App.tsx:3 render App"), "{stdout}");
+
+    let stdout = run_success(&["impact", "Button", path]);
+    assert!(stdout.contains("src/App.tsx [HIGH"), "{stdout}");
+}
+
+#[test]
+fn test_find_and_refs_follow_export_aliases() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src/lib")).unwrap();
+    fs::write(root.join("src/lib/foo.ts"), "export function Foo() {}\n").unwrap();
+    fs::write(
+        root.join("src/lib/index.ts"),
+        "export { Foo as Bar } from './foo';\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "import { Bar } from './lib';\nBar();\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/other.ts"),
+        "import { Foo } from './lib/foo';\nFoo();\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["find", "Bar", path]);
+    assert!(stdout.contains("def Foo src/lib/foo.ts:1"), "{stdout}");
+
+    for name in ["Foo", "Bar"] {
+        let stdout = run_success(&["refs", name, path]);
+        assert!(stdout.contains("ref src/app.ts:1:1 import"), "{stdout}");
+        assert!(stdout.contains("ref src/other.ts:1:1 import"), "{stdout}");
+    }
+}
+
+/// test_refs_classify_variable_reads_and_writes — refs on a variable lists each access
+/// site as a read, write, or call, and `--ref-kind` narrows to one kind.
+#[test]
+fn test_refs_classify_variable_reads_and_writes() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/config.ts"),
+        "export let settings = { debug: false };\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "import { settings } from './config';\n\
+         export function enableDebug() {\n  settings.debug = true;\n}\n\
+         export function isDebug() {\n  return settings.debug;\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["refs", "settings", path]);
+    assert!(stdout.contains("ref src/app.ts:1:1 import"), "{stdout}");
+    assert!(
+        stdout.contains("ref src/app.ts:3 write enableDebug"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("ref src/app.ts:6 read isDebug"), "{stdout}");
+
+    let stdout = run_success(&["refs", "settings", path, "--ref-kind", "write"]);
+    assert!(
+        stdout.contains("ref src/app.ts:3 write enableDebug"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("1 references found"), "{stdout}");
+}
+
 /// test_refs_rust_symbol — refs on a known symbol produces non-empty output.
 #[test]
 fn test_refs_rust_symbol() {
@@ -391,6 +513,129 @@ edition = "2021"
     );
 }
 
+#[test]
+fn test_category_filters() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let tmp_path = tmp.path();
+
+    fs::write(tmp_path.join("tsconfig.json"), "{}").unwrap();
+    fs::write(
+        tmp_path.join("code-graph.toml"),
+        r#"[[category]]
+name = "pages"
+paths = ["src/pages/**"]
+
+[[category]]
+name = "hooks"
+symbols = ["use*"]
+"#,
+    )
+    .unwrap();
+    fs::create_dir_all(tmp_path.join("src/pages")).unwrap();
+    fs::write(
+        tmp_path.join("src/hooks.ts"),
+        "export function useAuth() {}\nexport function formatName() {}\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp_path.join("src/pages/Home.ts"),
+        "import { useAuth } from '../hooks';\nexport function HomePage() { useAuth(); }\n",
+    )
+    .unwrap();
+    let path = tmp_path.to_str().unwrap();
+
+    let find_stdout = run_success(&["find", ".*", path, "--category", "hooks"]);
+    assert!(find_stdout.contains("useAuth"), "stdout: {}", find_stdout);
+    assert!(
+        !find_stdout.contains("formatName"),
+        "stdout: {}",
+        find_stdout
+    );
+    assert!(!find_stdout.contains("HomePage"), "stdout: {}", find_stdout);
+
+    let impact_stdout = run_success(&["impact", "useAuth", path, "--category", "pages"]);
+    assert!(
+        impact_stdout.contains("Home.ts"),
+        "stdout: {}",
+        impact_stdout
+    );
+
+    let dot = run_success(&["export", path, "--stdout", "--category", "pages"]);
+    assert!(dot.contains("src/pages/Home.ts"), "stdout: {}", dot);
+    assert!(!dot.contains("src/hooks.ts"), "stdout: {}", dot);
+    assert!(dot.contains("subgraph cluster_legend"), "stdout: {}", dot);
+    assert!(dot.contains("legend_0 [label=\"pages\""), "stdout: {}", dot);
+
+    let mermaid = run_success(&[
+        "export",
+        path,
+        "--stdout",
+        "--format",
+        "mermaid",
+        "--granularity",
+        "symbol",
+        "--tag",
+        "pages,hooks",
+    ]);
+    assert!(
+        mermaid.contains("legend_0[\"hooks\"]:::cat0"),
+        "stdout: {}",
+        mermaid
+    );
+    assert!(
+        mermaid.contains("legend_1[\"pages\"]:::cat1"),
+        "stdout: {}",
+        mermaid
+    );
+    assert!(
+        mermaid.contains("classDef cat0 fill:"),
+        "stdout: {}",
+        mermaid
+    );
+    assert!(!mermaid.contains("formatName"), "stdout: {}", mermaid);
+}
+
+/// test_runtime_only_ignores_type_imports — `import type` edges are kept in the graph but
+/// dropped from `circular` and `impact` with `--runtime-only`.
+#[test]
+fn test_runtime_only_ignores_type_imports() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let tmp_path = tmp.path();
+
+    fs::write(tmp_path.join("tsconfig.json"), "{}").unwrap();
+    fs::write(
+        tmp_path.join("user.ts"),
+        "import { save } from './store';\nexport interface User { id: string }\nexport function createUser() { save(); }\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp_path.join("store.ts"),
+        "import type { User } from './user';\nexport function save(user?: User) {}\n",
+    )
+    .unwrap();
+    let path = tmp_path.to_str().unwrap();
+
+    let cycles = run_success(&["circular", path]);
+    assert!(cycles.contains("store.ts"), "stdout: {}", cycles);
+    let runtime_cycles = run_success(&["circular", path, "--runtime-only"]);
+    assert!(
+        runtime_cycles.contains("no circular dependencies found"),
+        "stdout: {}",
+        runtime_cycles
+    );
+
+    let impact = run_success(&["impact", "User", path]);
+    assert!(impact.contains("store.ts"), "stdout: {}", impact);
+    let runtime_impact = run_success(&["impact", "User", path, "--runtime-only"]);
+    assert!(
+        !runtime_impact.contains("store.ts"),
+        "stdout: {}",
+        runtime_impact
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Task 2: MCP parity — JSON output format test (closest to MCP output format)
 // ---------------------------------------------------------------------------
@@ -532,6 +777,31 @@ fn test_export_dot_package_clusters() {
         cluster_count,
         &stdout[..stdout.len().min(800)]
     );
+
+    // Rust files are clustered by top-level module, not by directory.
+    assert!(
+        stdout.contains("label=\"code_graph_cli::parser\""),
+        "package granularity should cluster Rust files by module"
+    );
+}
+
+/// `structure --modules` lists the Rust module tree with each module's symbols.
+#[test]
+fn test_structure_modules() {
+    let root = project_root();
+    let path = root.to_str().unwrap();
+    let stdout = run_success(&[
+        "structure",
+        path,
+        "--modules",
+        "--path",
+        "code_graph_cli::graph",
+        "--depth",
+        "1",
+    ]);
+    assert!(stdout.contains("code_graph_cli::graph::"), "{stdout}");
+    assert!(stdout.contains("pub CodeGraph (struct)"), "{stdout}");
+    assert!(stdout.contains("  node::"), "{stdout}");
 }
 
 /// test_export_mermaid_edge_limit_warning — EXPORT-05: scale guard warning behavior.
@@ -668,3 +938,1229 @@ fn test_find_json_output() {
         first
     );
 }
+
+/// `--max-path-width` shortens table paths with a middle ellipsis, keeping columns aligned.
+#[test]
+fn test_table_max_path_width() {
+    let root = project_root();
+    let path = root.to_str().unwrap();
+    let stdout = run_success(&[
+        "find",
+        "build_mod_tree",
+        path,
+        "--format",
+        "table",
+        "--max-path-width",
+        "12",
+    ]);
+    assert!(stdout.contains("src/r…ree.rs"), "{stdout}");
+    assert!(
+        !stdout.contains("src/resolver/rust_mod_tree.rs"),
+        "{stdout}"
+    );
+}
+
+/// `export --granularity dir` renders one node per directory; `impact --by-dir` rolls
+/// affected files up to their directories.
+#[test]
+fn test_dir_granularity() {
+    let (stdout, _) = run_export(&["--granularity", "dir", "--stdout"]);
+    assert!(
+        stdout.contains("[label=\"src/query/\" shape=folder"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("find.rs"), "{stdout}");
+
+    let root = project_root();
+    let stdout = run_success(&["impact", "CodeGraph", root.to_str().unwrap(), "--by-dir"]);
+    assert!(stdout.contains("impact src/query/ "), "{stdout}");
+    assert!(stdout.contains("directories affected"), "{stdout}");
+}
+
+/// `stats --files N` ranks files by symbols, lines, fan-in, and fan-out.
+#[test]
+fn test_stats_files() {
+    let root = project_root();
+    let stdout = run_success(&[
+        "stats",
+        root.to_str().unwrap(),
+        "--files",
+        "3",
+        "--format",
+        "json",
+    ]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    for key in ["most_symbols", "most_lines", "fan_in", "fan_out"] {
+        let entries = json[key].as_array().expect(key);
+        assert_eq!(entries.len(), 3, "{key}");
+    }
+    let fan_in = json["fan_in"].as_array().unwrap();
+    assert!(fan_in[0]["fan_in"].as_u64() >= fan_in[1]["fan_in"].as_u64());
+    assert!(
+        fan_in.iter().any(|f| f["file"] == "src/graph/mod.rs"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_stats_lines_and_largest_functions() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "export function small() {\n  return 1;\n}\n\nexport function big() {\n  const a = 1;\n  const b = 2;\n  return a + b;\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["stats", path]);
+    assert!(stdout.contains("lines 9\n"), "{stdout}");
+    assert!(stdout.contains("largest_file 9 src/app.ts"), "{stdout}");
+    let big = stdout.find("largest_fn 5 big src/app.ts:5").expect(&stdout);
+    let small = stdout
+        .find("largest_fn 3 small src/app.ts:1")
+        .expect(&stdout);
+    assert!(big < small, "{stdout}");
+
+    let stdout = run_success(&["file-summary", "src/app.ts", path]);
+    assert!(stdout.contains("lines: 9"), "{stdout}");
+    assert!(
+        stdout.contains("largest: big (function, 5 lines), small (function, 3 lines)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_identical_vendored_files_flagged_and_analysed_once() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    let lib = "export function parse(input: string) {\n  const trimmed = input.trim();\n  if (trimmed.length === 0) {\n    return null;\n  }\n  return trimmed.split(',');\n}\n";
+    for dir in ["lib", "vendor/a/lib", "vendor/b/lib"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+        fs::write(root.join(dir).join("parse.ts"), lib).unwrap();
+    }
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["stats", path]);
+    assert!(
+        stdout.contains("identical lib/parse.ts = vendor/a/lib/parse.ts vendor/b/lib/parse.ts"),
+        "{stdout}"
+    );
+
+    let stdout = run_success(&["clones", path]);
+    assert!(!stdout.contains("vendor/"), "{stdout}");
+}
+
+#[test]
+fn test_stats_scope_and_language_filter_the_counts() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("api")).unwrap();
+    fs::create_dir_all(root.join("web")).unwrap();
+    fs::write(root.join("api/server.ts"), "export function serve() {}\n").unwrap();
+    fs::write(
+        root.join("web/app.js"),
+        "export function render() {}\nexport function mount() {}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["stats", path, "--scope", "api", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["source_files"], 1, "{stdout}");
+    assert_eq!(json["symbol_count"], 1, "{stdout}");
+
+    let stdout = run_success(&["stats", path, "--language", "js", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["source_files"], 1, "{stdout}");
+    assert_eq!(json["symbol_count"], 2, "{stdout}");
+}
+
+#[test]
+fn test_packages_feature_selection() {
+    let root = project_root();
+    let root = root.to_str().unwrap();
+    let stdout = run_success(&["packages", root]);
+    assert!(stdout.contains("package code_graph_cli"), "{stdout}");
+    assert!(!stdout.contains("dep axum"), "{stdout}");
+    assert!(
+        stdout.contains("mod code_graph_cli::web [feature web] off"),
+        "{stdout}"
+    );
+
+    let stdout = run_success(&["packages", root, "--features", "web"]);
+    assert!(stdout.contains("dep axum (optional)"), "{stdout}");
+    assert!(
+        stdout.contains("mod code_graph_cli::web [feature web] on"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_impact_tests_only() {
+    let root = project_root();
+    let stdout = run_success(&["impact", "CodeGraph", root.to_str().unwrap(), "--tests"]);
+    // `#[cfg(test)] mod tests;` in src/daemon/mod.rs makes tests.rs a test file.
+    assert!(stdout.contains("impact src/daemon/tests.rs"), "{stdout}");
+    assert!(!stdout.contains("impact src/main.rs"), "{stdout}");
+}
+
+#[test]
+fn test_pkg_query() {
+    let root = project_root();
+    let root = root.to_str().unwrap();
+    let stdout = run_success(&[
+        "pkg-query",
+        "somepath(code_graph_cli::daemon, code_graph_cli::graph)",
+        root,
+        "--format",
+        "json",
+    ]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let path: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(path.first(), Some(&"code_graph_cli::daemon"), "{stdout}");
+    assert_eq!(path.last(), Some(&"code_graph_cli::graph"), "{stdout}");
+
+    let stdout = run_success(&[
+        "pkg-query",
+        "rdeps(code_graph_cli::graph, 1) - code_graph_cli::graph",
+        root,
+    ]);
+    assert!(stdout.contains("pkg code_graph_cli::query"), "{stdout}");
+    assert!(!stdout.contains("pkg code_graph_cli::graph "), "{stdout}");
+}
+
+#[test]
+fn test_hover_resolves_use_to_definition() {
+    let root = project_root();
+    let source = std::fs::read_to_string(root.join("src/query/context.rs")).unwrap();
+    let (line, col) = source
+        .lines()
+        .enumerate()
+        .find_map(|(i, text)| text.find("refs::find_refs(").map(|c| (i + 1, c + 7)))
+        .expect("context.rs calls find_refs");
+    let position = format!("src/query/context.rs:{line}:{col}");
+    let stdout = run_success(&[
+        "hover",
+        &position,
+        root.to_str().unwrap(),
+        "--format",
+        "json",
+    ]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(json["name"], "find_refs", "{stdout}");
+    assert_eq!(json["file"], "src/query/refs.rs", "{stdout}");
+    assert!(
+        json["signature"]
+            .as_str()
+            .is_some_and(|s| s.starts_with("pub fn find_refs(")),
+        "{stdout}"
+    );
+    assert!(
+        json["doc"]
+            .as_str()
+            .is_some_and(|d| d.starts_with("Find all files")),
+        "{stdout}"
+    );
+    assert!(json["references"].as_u64().unwrap() > 0, "{stdout}");
+}
+
+#[test]
+fn test_imports_marks_declared_cargo_dependencies() {
+    let root = project_root();
+    let stdout = run_success(&["imports", "src/main.rs", root.to_str().unwrap()]);
+    assert!(
+        stdout.contains("clap::Parser (external, declared)"),
+        "{stdout}"
+    );
+    // Uniform paths into the binary's own modules are internal, not Cargo dependencies.
+    assert!(
+        stdout.contains("cli::{Cli, Commands, OutputFormat} (internal)\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_cache_lock_is_stolen_from_dead_owner_and_skippable() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::write(root.join("index.ts"), "export function main() {}\n").unwrap();
+    fs::create_dir_all(root.join(".code-graph")).unwrap();
+    let lock = root.join(".code-graph/lock");
+    let path = root.to_str().unwrap();
+
+    // A lock left behind by a crashed process does not block the next command.
+    fs::write(&lock, "999999999").unwrap();
+    let stdout = run_success(&["find", "main", path]);
+    assert!(stdout.contains("def main index.ts:1"), "{stdout}");
+    assert!(!lock.exists(), "lock is released after the command");
+
+    // A live owner (this test process) would make the command wait; --no-lock skips it.
+    fs::write(&lock, std::process::id().to_string()).unwrap();
+    let start = std::time::Instant::now();
+    let stdout = run_success(&["find", "main", path, "--no-lock"]);
+    assert!(stdout.contains("def main index.ts:1"), "{stdout}");
+    assert!(start.elapsed() < std::time::Duration::from_secs(30));
+    assert!(lock.exists(), "--no-lock leaves another owner's lock alone");
+}
+
+#[test]
+fn test_ts_visibility_in_find_and_dead_code() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::write(
+        root.join("index.ts"),
+        "export class Service {\n  protected retries = 3;\n  private helper() {}\n  \
+         private stale() {}\n  #tick() {}\n  run() {\n    this.helper();\n    this.#tick();\n  }\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["find", "*", path, "--visibility", "private"]);
+    assert!(stdout.contains("def helper index.ts:3 method"), "{stdout}");
+    assert!(stdout.contains("def #tick index.ts:5 method"), "{stdout}");
+    assert!(!stdout.contains("def run"), "{stdout}");
+    assert!(!stdout.contains("def retries"), "{stdout}");
+
+    let stdout = run_success(&["find", "*", path, "--visibility", "protected"]);
+    assert!(
+        stdout.contains("def retries index.ts:2 property protected"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("1 definitions found"), "{stdout}");
+
+    let stdout = run_success(&["dead-code", path]);
+    assert!(stdout.contains("method stale :4"), "{stdout}");
+    assert!(!stdout.contains("helper"), "{stdout}");
+    assert!(!stdout.contains("#tick"), "{stdout}");
+}
+
+#[test]
+fn test_rust_dead_code_fix_list_keeps_the_build() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src/util")).unwrap();
+    fs::write(
+        root.join("src/main.rs"),
+        "mod shapes;\nmod tokens;\nmod util;\n\nfn main() {\n    let c = shapes::Circle::new(2.0);\n    \
+         util::lock::acquire();\n}\n",
+    )
+    .unwrap();
+    // Uses the graph records no edge for: a function passed by value, a method called on
+    // an untyped closure parameter, and a serde default named in a string.
+    fs::write(
+        root.join("src/tokens.rs"),
+        "pub struct Tok(char);\n\nimpl Tok {\n    fn is_dot(&self) -> bool {\n        \
+         self.0 == '.'\n    }\n}\n\nfn double(x: u32) -> u32 {\n    x * 2\n}\n\n\
+         fn default_port() -> u16 {\n    80\n}\n\n#[derive(serde::Deserialize)]\n\
+         pub struct Config {\n    #[serde(default = \"default_port\")]\n    port: u16,\n}\n\n\
+         pub fn count(toks: &[Tok], xs: &[u32]) -> usize {\n    \
+         let doubled: Vec<u32> = xs.iter().copied().map(double).collect();\n    \
+         toks.iter().filter(|t| t.is_dot()).count() + doubled.len()\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/shapes.rs"),
+        "pub struct Circle {\n    r: f64,\n}\n\nimpl Circle {\n    pub fn new(r: f64) -> Self {\n        \
+         Circle { r }\n    }\n\n    pub fn area(&self) -> f64 {\n        self.squared() * 3.14\n    }\n\n    \
+         fn squared(&self) -> f64 {\n        self.r * self.r\n    }\n}\n\n\
+         impl Drop for Circle {\n    fn drop(&mut self) {}\n}\n\nfn unused_helper() {}\n\n\
+         #[cfg(test)]\nmod tests {\n    fn fixture() {}\n}\n",
+    )
+    .unwrap();
+    // `util/mod.rs` and `util/lock.rs` are only reached through `mod` declarations and the
+    // path-qualified `util::lock::acquire()` call.
+    fs::write(root.join("src/util/mod.rs"), "pub mod lock;\n").unwrap();
+    fs::write(
+        root.join("src/util/lock.rs"),
+        "pub fn acquire() {\n    release();\n}\n\nfn release() {}\n\nfn orphan() {}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["dead-code", path]);
+    assert!(stdout.contains("[file] unreachable files (0):"), "{stdout}");
+    for live in ["squared", "drop", "fixture", "release"] {
+        assert!(
+            !stdout.contains(live),
+            "{live} is used or excluded: {stdout}"
+        );
+    }
+
+    let stdout = run_success(&["dead-code", path, "--fix-list"]);
+    let mut lines: Vec<&str> = stdout.lines().collect();
+    lines.sort();
+    assert_eq!(lines, vec!["src/shapes.rs:23-23", "src/util/lock.rs:7-7"]);
+}
+
+#[test]
+fn test_callgraph_prints_multi_hop_trees() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "export function main() {\n  run();\n}\n\
+         export function run() {\n  step();\n}\n\
+         export function step() {\n  log();\n}\n\
+         export function log() {}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["callgraph", "main", path]);
+    assert!(stdout.contains("callgraph main src/app.ts:1"), "{stdout}");
+    assert!(stdout.contains("\n  -> run src/app.ts:4\n"), "{stdout}");
+    assert!(stdout.contains("\n    -> step src/app.ts:7\n"), "{stdout}");
+    assert!(stdout.contains("\n      -> log src/app.ts:10"), "{stdout}");
+
+    let stdout = run_success(&[
+        "callgraph",
+        "log",
+        path,
+        "--direction",
+        "in",
+        "--depth",
+        "2",
+    ]);
+    assert!(stdout.contains("\n  <- step src/app.ts:7\n"), "{stdout}");
+    assert!(stdout.contains("\n    <- run src/app.ts:4"), "{stdout}");
+    assert!(!stdout.contains("main"), "{stdout}");
+}
+
+#[test]
+fn test_deps_and_rdeps_list_transitive_files() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "import { handle } from './api';\nhandle();\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/api.ts"),
+        "import { query } from './db';\nexport function handle() {\n  return query();\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/db.ts"),
+        "export function query() {\n  return 1;\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["deps", "src/app.ts", path]);
+    assert!(
+        stdout.contains("deps src/api.ts [depth 1]\ndeps src/db.ts [depth 2]\n2 files"),
+        "{stdout}"
+    );
+
+    let stdout = run_success(&["rdeps", "src/db.ts", path, "--depth", "1"]);
+    assert!(stdout.contains("rdeps src/api.ts [depth 1]"), "{stdout}");
+    assert!(!stdout.contains("src/app.ts"), "{stdout}");
+
+    let stdout = run_success(&["rdeps", "src/db.ts", path, "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["file"], "src/db.ts");
+    assert_eq!(json["files"][1]["file"], "src/app.ts");
+    assert_eq!(json["files"][1]["depth"], 2);
+}
+
+#[test]
+fn test_context_lists_method_overrides() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/report.ts"),
+        "export abstract class Report {\n  abstract title(): string;\n  render(): string {\n    return this.title();\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/sales.ts"),
+        "import { Report } from './report';\nexport class Sales extends Report {\n  override title(): string {\n    return 'sales';\n  }\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["context", "title", path]);
+    assert!(
+        stdout.contains("overrides title src/report.ts:2"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("overridden-by title src/sales.ts:3"),
+        "{stdout}"
+    );
+
+    let stdout = run_success(&["context", "title", path, "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json[0]["overridden_by"][0]["file"], "src/sales.ts");
+}
+
+#[test]
+fn test_context_jumps_between_rust_trait_and_impl_methods() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/lib.rs"),
+        "pub mod square;\n\npub trait Shape {\n    fn area(&self) -> f64;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/square.rs"),
+        "use crate::Shape;\n\npub struct Square(f64);\n\nimpl Shape for Square {\n    fn area(&self) -> f64 {\n        self.0 * self.0\n    }\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["context", "Shape::area", path]);
+    assert!(
+        stdout.contains("overridden-by Square::area src/square.rs:6"),
+        "{stdout}"
+    );
+
+    let stdout = run_success(&["context", "Square::area", path]);
+    assert!(
+        stdout.contains("overrides Shape::area src/lib.rs:4"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_path_explains_dependency_chains() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "import { handle } from './api';\nexport function main() {\n  handle();\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/api.ts"),
+        "import { query } from './db';\nexport function handle() {\n  return query();\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/db.ts"),
+        "export function query() {\n  return 1;\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["path", "src/app.ts", "src/db.ts", path]);
+    assert!(
+        stdout.contains(
+            "path 1/1 (2 hops)\n  src/app.ts\n  -> imports src/api.ts\n  -> imports src/db.ts\n"
+        ),
+        "{stdout}"
+    );
+
+    let stdout = run_success(&["path", "main", "query", path]);
+    assert!(
+        stdout.contains(
+            "  main src/app.ts:2\n  -> calls handle src/api.ts:2\n  -> calls query src/db.ts:1"
+        ),
+        "{stdout}"
+    );
+
+    let (_, stderr) = run_failure(&["path", "src/db.ts", "src/app.ts", path]);
+    assert!(stderr.contains("no dependency path"), "{stderr}");
+}
+
+#[test]
+fn test_coupling_reports_co_changed_files_without_edges() {
+    use std::fs;
+    use std::process::Command;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(root)
+            .status()
+            .expect("failed to run git");
+        assert!(status.success(), "git {args:?} failed");
+    };
+    git(&["init", "-q"]);
+    for i in 0..3 {
+        fs::write(
+            root.join("src/server.ts"),
+            format!("export const PORT = {i};\n"),
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/client.ts"),
+            format!("export const PORT = {i};\n"),
+        )
+        .unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", &format!("change {i}")]);
+    }
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["coupling", path]);
+    assert!(
+        stdout.contains(
+            "coupling src/client.ts <-> src/server.ts  shared 3 (3/3 commits, degree 100%)"
+        ),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("1 hidden pairs (0 already linked) in 3 commits"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_metrics_report_package_instability() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    for dir in ["app", "api", "db"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    fs::write(
+        root.join("app/main.ts"),
+        "import { handle } from '../api/index';\nimport { query } from '../db/index';\nhandle();\nquery();\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("api/index.ts"),
+        "import { query } from '../db/index';\nexport function handle() {\n  query();\n}\n",
+    )
+    .unwrap();
+    fs::write(root.join("db/index.ts"), "export function query() {}\n").unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["metrics", path]);
+    assert!(
+        stdout.contains("package api files 1 ca 1 ce 1 instability 0.50"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("package app files 1 ca 0 ce 2 instability 1.00"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("package db files 1 ca 2 ce 0 instability 0.00"),
+        "{stdout}"
+    );
+
+    let stdout = run_success(&["metrics", path, "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json[0]["package"], "api");
+    assert_eq!(json[0]["instability"], 0.5);
+}
+
+#[test]
+fn test_complexity_lists_most_complex_functions() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "export function simple() {\n  return 1;\n}\n\nexport function branchy(a: number) {\n  if (a > 1) {\n    return 1;\n  }\n  while (a > 0 && a < 10) {\n    a--;\n  }\n  return a ? 2 : 3;\n}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["complexity", path]);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "complexity 5 branchy src/app.ts:5", "{stdout}");
+    assert_eq!(lines[1], "complexity 1 simple src/app.ts:1", "{stdout}");
+
+    let stdout = run_success(&["complexity", path, "--top", "1", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 1);
+    assert_eq!(json[0]["name"], "branchy");
+    assert_eq!(json[0]["complexity"], 5);
+}
+
+#[test]
+fn test_hotspots_rank_by_dependents_and_churn() {
+    use std::fs;
+    use std::process::Command;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/db.ts"), "export function query() {}\n").unwrap();
+    fs::write(root.join("src/log.ts"), "export function log() {}\n").unwrap();
+    fs::write(
+        root.join("src/a.ts"),
+        "import { query } from './db';\nimport { log } from './log';\nquery();\nlog();\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/b.ts"),
+        "import { query } from './db';\nquery();\n",
+    )
+    .unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(root)
+            .status()
+            .expect("failed to run git");
+        assert!(status.success(), "git {args:?} failed");
+    };
+    git(&["init", "-q"]);
+    git(&["add", "-A"]);
+    git(&["commit", "-q", "-m", "initial"]);
+    for i in 0..2 {
+        fs::write(
+            root.join("src/log.ts"),
+            format!("export function log() {{}}\n// {i}\n"),
+        )
+        .unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", &format!("change {i}")]);
+    }
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["hotspots", path]);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[0], "hotspot src/db.ts dependents 2 score 2",
+        "{stdout}"
+    );
+    assert_eq!(
+        lines[1], "hotspot src/log.ts dependents 1 score 1",
+        "{stdout}"
+    );
+    assert!(stdout.contains("2 hotspots"), "{stdout}");
+
+    let stdout = run_success(&["hotspots", path, "--level", "symbol", "--limit", "1"]);
+    assert!(
+        stdout.starts_with("hotspot query src/db.ts:1 dependents 2 score 2"),
+        "{stdout}"
+    );
+
+    // log.ts changed in all 3 commits, db.ts only in the first.
+    let stdout = run_success(&["hotspots", path, "--churn"]);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[0], "hotspot src/log.ts dependents 1 churn 3 score 3",
+        "{stdout}"
+    );
+    assert_eq!(
+        lines[1], "hotspot src/db.ts dependents 2 churn 1 score 2",
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("2 hotspots (churn from 3 commits)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_exports_lists_surface_with_outside_consumers() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src/lib")).unwrap();
+    fs::write(
+        root.join("src/lib/index.ts"),
+        "export * from './format';\nexport { parse } from './parse';\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/lib/format.ts"),
+        "export function format() {}\nexport function pad() {}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/lib/parse.ts"),
+        "export function parse() {}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "import { format } from './lib';\nformat();\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["exports", "src/lib", path]);
+    assert!(
+        stdout.contains("exports src/lib (entry: src/lib/index.ts)"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("1 refs   function format src/lib/format.ts:1 via src/lib/index.ts"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("unused   function parse src/lib/parse.ts:1 via src/lib/index.ts"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("3 exports, 2 unused outside the package"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_api_prints_signatures_reachable_from_entry() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src/lib")).unwrap();
+    fs::write(root.join("src/lib/index.ts"), "export * from './format';\n").unwrap();
+    fs::write(
+        root.join("src/lib/format.ts"),
+        "export function format(value: number, digits = 2): string {\n  return value.toFixed(digits);\n}\nfunction internal() {}\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["api", "src/lib", path]);
+    assert!(
+        stdout.contains("api src/lib (entry: src/lib/index.ts)"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(
+            "  export function format(value: number, digits = 2): string src/lib/format.ts:1"
+        ),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("internal"), "{stdout}");
+    assert!(stdout.contains("1 items"), "{stdout}");
+}
+
+#[test]
+fn test_touched_maps_commit_hunks_onto_symbols() {
+    use std::fs;
+    use std::process::Command;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(root)
+            .status()
+            .expect("failed to run git");
+        assert!(status.success(), "git {args:?} failed");
+    };
+    fs::write(
+        root.join("src/math.ts"),
+        "export function add(a: number, b: number) {\n  return a + b;\n}\n\nexport function sub(a: number, b: number) {\n  return a - b;\n}\n",
+    )
+    .unwrap();
+    git(&["init", "-q"]);
+    git(&["add", "-A"]);
+    git(&["commit", "-q", "-m", "initial"]);
+    fs::write(
+        root.join("src/math.ts"),
+        "export function add(a: number, b: number) {\n  return a + b;\n}\n\nexport function sub(a: number, b: number) {\n  return b - a;\n}\n\nexport function mul(a: number, b: number) {\n  return a * b;\n}\n",
+    )
+    .unwrap();
+    git(&["commit", "-q", "-am", "mul"]);
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["touched", "HEAD", path]);
+    assert!(stdout.starts_with("touched HEAD^..HEAD\n"), "{stdout}");
+    assert!(
+        stdout.contains("  modified function sub src/math.ts:5 (0 refs)"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("  added    function mul src/math.ts:9 (0 refs)"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains(" add "), "{stdout}");
+    assert!(
+        stdout.contains("2 symbols touched in 1 files (1 added, 0 removed, 1 modified)"),
+        "{stdout}"
+    );
+
+    let (_, stderr) = run_failure(&["touched", "nope", path]);
+    assert!(stderr.contains("nope"), "{stderr}");
+}
+
+#[test]
+fn test_lint_imports_reports_order_and_depth() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src/a/b/c")).unwrap();
+    fs::write(root.join("src/util.ts"), "export const x = 1;\n").unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "import { x } from './util';\nimport fs from 'fs';\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/a/b/c/deep.ts"),
+        "import fs from 'node:fs';\nimport { x } from '../../../util';\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let (stdout, _) = run_failure(&["lint-imports", path]);
+    assert!(
+        stdout.contains("src/app.ts:2 order `fs`: builtin import after relative import"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("src/a/b/c/deep.ts:2 deep-relative `../../../util`"),
+        "{stdout}"
+    );
+
+    fs::write(
+        root.join("code-graph.toml"),
+        "[lint_imports]\norder = [\"relative\", \"builtin\"]\nmax_parent_depth = 3\n",
+    )
+    .unwrap();
+    // Relative first now: only deep.ts's order is off, and three levels are allowed.
+    let (stdout, _) = run_failure(&["lint-imports", path, "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(json["violations"].as_array().unwrap().len(), 1, "{stdout}");
+    assert_eq!(
+        json["violations"][0]["file"], "src/a/b/c/deep.ts",
+        "{stdout}"
+    );
+    assert_eq!(json["violations"][0]["rule"], "order", "{stdout}");
+}
+
+#[test]
+fn test_check_reports_layering_violations() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src/domain")).unwrap();
+    fs::create_dir_all(root.join("src/infrastructure")).unwrap();
+    fs::write(
+        root.join("src/infrastructure/db.ts"),
+        "export function query() {}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/domain/user.ts"),
+        "import { query } from '../infrastructure/db';\nquery();\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["check", path]);
+    assert!(stdout.contains("0 violations of 0 rules"), "{stdout}");
+
+    fs::write(
+        root.join("code-graph.toml"),
+        "[[rules]]\nname = \"domain-is-pure\"\nfrom = [\"src/domain\"]\nforbid = [\"src/infrastructure\"]\n",
+    )
+    .unwrap();
+    let (stdout, _) = run_failure(&["check", path]);
+    assert!(
+        stdout.contains(
+            "src/domain/user.ts -> src/infrastructure/db.ts breaks domain-is-pure `../infrastructure/db`"
+        ),
+        "{stdout}"
+    );
+    assert!(stdout.contains("1 violations of 1 rules"), "{stdout}");
+}
+
+#[test]
+fn test_symbols_exclude_hides_names_from_find() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "export type ButtonProps = { a: number };\nexport function __helper() {}\nexport function render() { __helper(); }\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+    run_success(&["find", "__helper", path]);
+
+    fs::write(
+        root.join("code-graph.toml"),
+        "[symbols]\nexclude = [\"__*\", \"type:*Props\"]\n",
+    )
+    .unwrap();
+    // The cached graph is re-filtered on load.
+    run_failure(&["find", "__helper", path]);
+    let stdout = run_success(&["find", "render|Props", path]);
+    assert!(stdout.contains("def render src/app.ts:3"), "{stdout}");
+    assert!(!stdout.contains("ButtonProps"), "{stdout}");
+}
+
+#[test]
+fn test_can_delete_reports_blocking_references() {
+    let root = project_root();
+    let stdout = run_success(&[
+        "can-delete",
+        "src/query/refs.rs",
+        root.to_str().unwrap(),
+        "--format",
+        "json",
+    ]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(json["target_kind"], "file", "{stdout}");
+    assert_eq!(json["verdict"], "blocked", "{stdout}");
+    let refs = json["references"].as_array().unwrap();
+    assert!(
+        refs.iter()
+            .any(|r| r["kind"] == "import" && r["file"] == "src/query/rename.rs"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_duplicates_single_crate_is_clean() {
+    let root = project_root();
+    let stdout = run_success(&["duplicates", root.to_str().unwrap(), "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert!(json["utilities"].is_array(), "{stdout}");
+    // One crate declares each dependency once.
+    assert_eq!(json["dependencies"], serde_json::json!([]), "{stdout}");
+}
+
+#[test]
+fn test_unused_exports_lists_exports_without_outside_users() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/util.ts"),
+        "export function used() { return 1; }\nexport function unused() { return 2; }\nexport interface Shape { x: number }\nexport const LIMIT = 3;\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "import { used, Shape } from './util';\nexport function run(s: Shape) { return used(); }\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/index.ts"),
+        "import { run } from './app';\nrun({ x: 1 });\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["unused-exports", path]);
+    assert!(stdout.contains("src/util.ts:2 unused function"), "{stdout}");
+    assert!(stdout.contains("src/util.ts:4 LIMIT variable"), "{stdout}");
+    assert!(!stdout.contains(" used "), "{stdout}");
+    assert!(!stdout.contains("Shape"), "{stdout}");
+    assert!(stdout.contains("2 unused of 5 exports"), "{stdout}");
+
+    let stdout = run_success(&["unused-exports", path, "--scope", "lib"]);
+    assert!(stdout.contains("0 unused of 0 exports"), "{stdout}");
+}
+
+#[test]
+fn test_capabilities_json_describes_binary() {
+    let stdout = run_success(&["capabilities", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"), "{stdout}");
+    let commands: Vec<&str> = json["commands"]
+        .as_array()
+        .expect("commands array")
+        .iter()
+        .filter_map(|c| c["name"].as_str())
+        .collect();
+    assert!(commands.contains(&"dead-code"), "{commands:?}");
+    assert!(
+        json["export_formats"]
+            .as_array()
+            .is_some_and(|f| f.contains(&"mermaid".into()))
+    );
+    assert!(
+        json["config_keys"]
+            .as_array()
+            .is_some_and(|k| k.contains(&"snapshot.auto".into()))
+    );
+
+    let stdout = run_success(&["capabilities"]);
+    assert!(stdout.contains("export formats: dot, mermaid"), "{stdout}");
+}
+
+#[test]
+fn test_similar_groups_copy_pasted_symbols() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    let helper = "export function slugify(title: string): string {\n  const lower = title.toLowerCase();\n  const dashed = lower.replace(/[^a-z0-9]+/g, '-');\n  const trimmed = dashed.replace(/^-+|-+$/g, '');\n  return trimmed.slice(0, 64);\n}\n";
+    for dir in ["web", "api"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    fs::write(root.join("web/strings.ts"), helper).unwrap();
+    fs::write(
+        root.join("api/text.ts"),
+        helper.replace("slice(0, 64)", "slice(0, 80)"),
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["similar", path]);
+    assert!(stdout.contains("slugify "), "{stdout}");
+    assert!(
+        stdout.contains("  api/text.ts:1 function (6 lines)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("  web/strings.ts:1 function"), "{stdout}");
+    assert!(stdout.contains("1 groups of similar symbols"), "{stdout}");
+
+    let stdout = run_success(&["similar", path, "--threshold", "1"]);
+    assert!(stdout.contains("0 groups of similar symbols"), "{stdout}");
+
+    let (_, stderr) = run_failure(&["similar", path, "--threshold", "2"]);
+    assert!(
+        stderr.contains("--threshold must be between 0 and 1"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_version_verbose_prints_build_info() {
+    let stdout = run_success(&["version", "--verbose"]);
+    assert!(
+        stdout.starts_with(&format!("code-graph {}\n", env!("CARGO_PKG_VERSION"))),
+        "{stdout}"
+    );
+    assert!(stdout.contains("cache format: "), "{stdout}");
+    assert!(stdout.contains("languages: typescript"), "{stdout}");
+
+    let stdout = run_success(&["version", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert!(json["cache_version"].as_u64().is_some(), "{stdout}");
+}
+
+#[test]
+fn test_unused_deps_reports_dependencies_nothing_imports() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(
+        root.join("package.json"),
+        r#"{"name": "app", "dependencies": {"react": "^18", "lodash": "^4"}}"#,
+    )
+    .unwrap();
+    // An installed package resolves into node_modules; it still counts as external usage.
+    fs::create_dir_all(root.join("node_modules/react")).unwrap();
+    fs::write(
+        root.join("node_modules/react/package.json"),
+        r#"{"name": "react", "main": "index.js"}"#,
+    )
+    .unwrap();
+    fs::write(
+        root.join("node_modules/react/index.js"),
+        "module.exports = {};\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/app.ts"),
+        "import React from 'react';\nexport const app = React;\n",
+    )
+    .unwrap();
+    let path = root.to_str().unwrap();
+
+    let stdout = run_success(&["unused-deps", path]);
+    assert!(
+        stdout.contains("package.json lodash (dependencies)"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("react"), "{stdout}");
+    assert!(
+        stdout.contains("1 unused of 2 dependencies in 1 packages"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_index_relative_root_keeps_references() {
+    use std::fs;
+    let tmp = tempfile::TempDir::new().expect("failed to create temp dir");
+    let root = tmp.path();
+    fs::write(root.join("package.json"), r#"{"name": "app"}"#).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/users.ts"),
+        "export function getUser() { return 1; }\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/index.ts"),
+        "import { getUser } from './users';\nexport function main() { return getUser(); }\n",
+    )
+    .unwrap();
+
+    let run_in_root = |args: &[&str]| {
+        let out = Command::new(binary())
+            .args(args)
+            .current_dir(root)
+            .output()
+            .expect("failed to invoke code-graph binary");
+        assert!(out.status.success(), "command {args:?} failed: {out:?}");
+        String::from_utf8_lossy(&out.stdout).to_string()
+    };
+    run_in_root(&["index", "."]);
+    let refs = run_in_root(&["refs", "getUser", "."]);
+    assert!(refs.contains("src/index.ts"), "{refs}");
+    let refs = run_in_root(&["refs", "getUser", root.to_str().unwrap()]);
+    assert!(refs.contains("src/index.ts"), "{refs}");
+    let dead = run_in_root(&["dead-code", "."]);
+    assert!(!dead.contains("unreachable files (1)"), "{dead}");
+}